@@ -32,6 +32,10 @@ pub enum Error {
     #[error("Trade failed: {0}")]
     TradeError(String),
 
+    /// The server refused the trade as smaller than its `minimum` size in USD
+    #[error("Trade below server minimum: {detail}")]
+    BelowMinimumTrade { minimum: f64, detail: String },
+
     #[error("Profile not found: {0}")]
     ProfileNotFound(i64),
 
@@ -39,6 +43,28 @@ pub enum Error {
     Unknown(String),
 }
 
+/// Read the USD amount out of a minimum-trade rejection, e.g. "Minimum
+/// trade amount is $1.00". Takes the first `$` amount, or failing that the
+/// first number after "minimum".
+pub fn parse_minimum_trade_usd(text: &str) -> Option<f64> {
+    let lower = text.to_lowercase();
+    let rest = match lower.find('$') {
+        Some(i) => &lower[i + 1..],
+        None => &lower[lower.find("minimum")? + "minimum".len()..],
+    };
+    let number: String = rest
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .filter(|c| *c != ',')
+        .collect();
+    number
+        .trim_end_matches('.')
+        .parse::<f64>()
+        .ok()
+        .filter(|m| m.is_finite() && *m > 0.0)
+}
+
 /// Result type alias using our Error
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -53,3 +79,17 @@ impl From<serde_json::Error> for Error {
         Error::InvalidData(err.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_minimum_trade_usd() {
+        assert_eq!(parse_minimum_trade_usd("Minimum trade amount is $1.00"), Some(1.0));
+        assert_eq!(parse_minimum_trade_usd("Trade must be at least $0.50."), Some(0.5));
+        assert_eq!(parse_minimum_trade_usd("Below minimum of 2 USD"), Some(2.0));
+        assert_eq!(parse_minimum_trade_usd("Minimum trade is $1,000"), Some(1000.0));
+        assert_eq!(parse_minimum_trade_usd("Trade amount too small"), None);
+    }
+}
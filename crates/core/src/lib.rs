@@ -2,8 +2,10 @@
 
 pub mod errors;
 pub mod models;
+pub mod time;
 pub mod types;
 
-pub use errors::{Error, Result};
+pub use errors::{parse_minimum_trade_usd, Error, Result};
 pub use models::*;
+pub use time::{parse_api_timestamp, parse_db_timestamp};
 pub use types::*;
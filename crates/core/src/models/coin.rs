@@ -1,5 +1,7 @@
 //! Coin-related models
 
+use super::schema::skip_invalid;
+use crate::types::{checked_ratio, is_valid_price};
 use serde::{Deserialize, Serialize};
 
 /// API response wrapper for coin details
@@ -7,9 +9,9 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct CoinDetailsResponse {
     pub coin: CoinDetails,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub candlestick_data: Vec<CandlestickPoint>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub volume_data: Vec<VolumePoint>,
     #[serde(default)]
     pub timeframe: Option<String>,
@@ -43,7 +45,9 @@ pub struct CoinDetails {
     #[serde(default)]
     pub icon: Option<String>,
     pub current_price: f64,
-    pub market_cap: f64,
+    /// `None` when the API left it out, rather than a market cap of 0
+    #[serde(default)]
+    pub market_cap: Option<f64>,
     pub pool_coin_amount: f64,
     pub pool_base_currency_amount: f64,
     #[serde(default)]
@@ -56,6 +60,23 @@ pub struct CoinDetails {
     pub volume_24h: f64,
     #[serde(default)]
     pub change_24h: f64,
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// When this snapshot was fetched from the API (set client-side)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl CoinDetails {
+    /// How old this snapshot is, if the fetch time is known
+    pub fn age(&self, now: chrono::DateTime<chrono::Utc>) -> Option<std::time::Duration> {
+        self.fetched_at.map(|at| (now - at).to_std().unwrap_or_default())
+    }
+
+    /// The reported price is finite and positive
+    pub fn has_valid_price(&self) -> bool {
+        is_valid_price(self.current_price)
+    }
 }
 
 /// Deserialize ID that can be either string or number
@@ -119,9 +140,7 @@ impl Holding {
 
     /// Calculate profit/loss percentage
     pub fn pnl_percent(&self, current_price: f64) -> f64 {
-        if self.avg_entry_price == 0.0 {
-            return 0.0;
-        }
-        ((current_price - self.avg_entry_price) / self.avg_entry_price) * 100.0
+        checked_ratio(current_price - self.avg_entry_price, self.avg_entry_price)
+            .map_or(0.0, |r| r * 100.0)
     }
 }
@@ -0,0 +1,224 @@
+//! Weighted average cost of a position
+//!
+//! One calculator for every path that needs a cost basis or realized PnL,
+//! so they agree on the numbers. Buys add to the position at their cost;
+//! sells take coins out at the running average and realize the difference.
+//! A re-buy after a partial sell averages into what is left.
+
+use super::portfolio::CoinHolding;
+use super::trade::TradeType;
+use crate::types::checked_ratio;
+use serde::{Deserialize, Serialize};
+
+/// Holdings at or below this are treated as a closed position
+const DUST_QUANTITY: f64 = 1e-8;
+
+/// Running cost basis of one coin
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostBasis {
+    /// Coins held according to the recorded trades
+    pub quantity: f64,
+    /// USD cost of `quantity` at the weighted average
+    pub total_cost: f64,
+    /// Profit or loss locked in by sells so far
+    pub realized_pnl: f64,
+    /// Coins sold beyond what the recorded buys cover (imported or external
+    /// trades). They are assumed to have cost what they sold for, so they
+    /// realize nothing rather than a fake 100% gain.
+    pub unrecorded_sold: f64,
+}
+
+impl CostBasis {
+    /// Start from a position whose history isn't known, e.g. the server's
+    /// `avg_purchase_price` for a holding
+    pub fn seeded(quantity: f64, avg_price: f64) -> Self {
+        if !(quantity.is_finite() && avg_price.is_finite()) || quantity <= 0.0 || avg_price < 0.0 {
+            return Self::default();
+        }
+        Self { quantity, total_cost: quantity * avg_price, ..Self::default() }
+    }
+
+    /// Seed from a portfolio holding
+    pub fn from_holding(holding: &CoinHolding) -> Self {
+        Self::seeded(holding.quantity, holding.avg_purchase_price)
+    }
+
+    /// Replay trades given as (side, coins, USD) in the order they happened
+    pub fn from_trades<I>(trades: I) -> Self
+    where
+        I: IntoIterator<Item = (TradeType, f64, f64)>,
+    {
+        let mut basis = Self::default();
+        for (side, quantity, usd) in trades {
+            basis.apply(side, quantity, usd);
+        }
+        basis
+    }
+
+    /// Apply one trade; returns the PnL it realized (0 for buys)
+    pub fn apply(&mut self, side: TradeType, quantity: f64, usd: f64) -> f64 {
+        match side {
+            TradeType::Buy => {
+                self.buy(quantity, usd);
+                0.0
+            }
+            TradeType::Sell => self.sell(quantity, usd),
+        }
+    }
+
+    /// Add `quantity` coins bought for `usd` in total. Non-finite or
+    /// non-positive amounts are ignored.
+    pub fn buy(&mut self, quantity: f64, usd: f64) {
+        if !(quantity.is_finite() && usd.is_finite()) || quantity <= 0.0 || usd < 0.0 {
+            return;
+        }
+        self.quantity += quantity;
+        self.total_cost += usd;
+    }
+
+    /// Remove `quantity` coins sold for `usd` in total; returns the PnL this
+    /// sell realized. The part not covered by recorded buys realizes nothing
+    /// and is counted in `unrecorded_sold`.
+    pub fn sell(&mut self, quantity: f64, usd: f64) -> f64 {
+        if !(quantity.is_finite() && usd.is_finite()) || quantity <= 0.0 || usd < 0.0 {
+            return 0.0;
+        }
+        let covered = quantity.min(self.quantity);
+        let uncovered = quantity - covered;
+        if uncovered > DUST_QUANTITY {
+            self.unrecorded_sold += uncovered;
+        }
+
+        let proceeds = usd * covered / quantity;
+        let cost = self.avg_cost() * covered;
+        let pnl = proceeds - cost;
+
+        self.quantity -= covered;
+        self.total_cost -= cost;
+        // Snap to zero so float residue doesn't carry an average into a
+        // later re-buy
+        if self.quantity <= DUST_QUANTITY {
+            self.quantity = 0.0;
+            self.total_cost = 0.0;
+        }
+        self.realized_pnl += pnl;
+        pnl
+    }
+
+    /// Weighted average price paid per coin held (0 with no position)
+    pub fn avg_cost(&self) -> f64 {
+        checked_ratio(self.total_cost, self.quantity).unwrap_or(0.0)
+    }
+
+    /// Paper profit of the open position at `price`
+    pub fn unrealized_pnl(&self, price: f64) -> f64 {
+        self.quantity * price - self.total_cost
+    }
+
+    /// Paper profit of the open position at `price` as a percent of its cost
+    pub fn unrealized_pnl_pct(&self, price: f64) -> f64 {
+        checked_ratio(self.unrealized_pnl(price), self.total_cost).map_or(0.0, |r| r * 100.0)
+    }
+
+    /// Realized plus unrealized PnL at `price`
+    pub fn total_pnl(&self, price: f64) -> f64 {
+        self.realized_pnl + self.unrealized_pnl(price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn test_buys_average_and_sells_realize_at_the_average() {
+        let mut basis = CostBasis::default();
+        basis.buy(100.0, 100.0); // 100 @ $1
+        basis.buy(100.0, 300.0); // 100 @ $3
+        assert_eq!(basis.avg_cost(), 2.0);
+
+        // Partial sell at $4: the average doesn't move, $2/coin is realized
+        assert_eq!(basis.sell(50.0, 200.0), 100.0);
+        assert_eq!(basis.quantity, 150.0);
+        assert_eq!(basis.avg_cost(), 2.0);
+        assert_eq!(basis.realized_pnl, 100.0);
+
+        // A losing partial sell realizes a loss
+        assert_eq!(basis.sell(50.0, 50.0), -50.0);
+        assert_eq!(basis.realized_pnl, 50.0);
+
+        // Re-buy averages into what is left: 100 @ $2 + 100 @ $1
+        basis.buy(100.0, 100.0);
+        assert_eq!(basis.avg_cost(), 1.5);
+        assert_eq!(basis.unrealized_pnl(2.0), 100.0);
+        assert!(close(basis.unrealized_pnl_pct(2.0), 100.0 / 3.0));
+        assert_eq!(basis.total_pnl(2.0), 150.0);
+    }
+
+    #[test]
+    fn test_closing_then_rebuying_starts_a_fresh_average() {
+        let trades = [
+            (TradeType::Buy, 10.0, 10.0),
+            (TradeType::Sell, 3.0, 6.0),
+            (TradeType::Sell, 7.0, 3.5),
+            (TradeType::Buy, 4.0, 20.0),
+        ];
+        let basis = CostBasis::from_trades(trades);
+        assert_eq!(basis.quantity, 4.0);
+        assert_eq!(basis.avg_cost(), 5.0);
+        // +3 on the first sell, -3.5 on the second
+        assert!(close(basis.realized_pnl, -0.5));
+
+        // Sells in thirds leave float residue that must not linger
+        let mut basis = CostBasis::default();
+        basis.buy(1.0, 0.3);
+        for _ in 0..3 {
+            basis.sell(1.0 / 3.0, 0.1);
+        }
+        assert_eq!((basis.quantity, basis.total_cost), (0.0, 0.0));
+        basis.buy(2.0, 4.0);
+        assert_eq!(basis.avg_cost(), 2.0);
+    }
+
+    #[test]
+    fn test_sells_beyond_recorded_buys_realize_nothing_extra() {
+        // Only part of the position was bought through the app
+        let mut basis = CostBasis::default();
+        basis.buy(10.0, 10.0);
+        // 30 sold at $2: the 10 recorded gain $10, the other 20 are unknown
+        assert_eq!(basis.sell(30.0, 60.0), 10.0);
+        assert_eq!(basis.quantity, 0.0);
+        assert_eq!(basis.unrecorded_sold, 20.0);
+        assert_eq!(basis.realized_pnl, 10.0);
+
+        // Selling with no recorded buys at all doesn't invent a gain
+        let mut empty = CostBasis::default();
+        assert_eq!(empty.sell(5.0, 50.0), 0.0);
+        assert_eq!(empty.unrecorded_sold, 5.0);
+        assert_eq!(empty.avg_cost(), 0.0);
+    }
+
+    #[test]
+    fn test_seeding_and_bad_inputs() {
+        let mut basis = CostBasis::seeded(50.0, 2.0);
+        assert_eq!(basis.total_cost, 100.0);
+        basis.buy(50.0, 50.0);
+        assert_eq!(basis.avg_cost(), 1.5);
+
+        assert_eq!(CostBasis::seeded(f64::NAN, 1.0), CostBasis::default());
+        assert_eq!(CostBasis::seeded(10.0, -1.0), CostBasis::default());
+
+        let before = basis;
+        basis.buy(f64::INFINITY, 1.0);
+        basis.buy(-1.0, 1.0);
+        assert_eq!(basis.sell(f64::NAN, 1.0), 0.0);
+        assert_eq!(basis.sell(0.0, 1.0), 0.0);
+        assert_eq!(basis, before);
+        assert_eq!(CostBasis::default().unrealized_pnl_pct(1.0), 0.0);
+    }
+}
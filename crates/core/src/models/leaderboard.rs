@@ -1,18 +1,19 @@
 //! Leaderboard-related models
 
+use super::schema::skip_invalid;
 use serde::{Deserialize, Serialize};
 
 /// Full leaderboard response from /api/leaderboard
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LeaderboardResponse {
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub top_rugpullers: Vec<RugpullerEntry>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub biggest_losers: Vec<LoserEntry>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub cash_kings: Vec<WealthEntry>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "skip_invalid")]
     pub paper_millionaires: Vec<WealthEntry>,
 }
 
@@ -1,11 +1,14 @@
 //! Market-related models
 
+use super::schema::skip_invalid;
+use crate::types::is_valid_price;
 use serde::{Deserialize, Serialize};
 
 /// Market response from /api/market
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarketResponse {
+    #[serde(deserialize_with = "skip_invalid")]
     pub coins: Vec<MarketCoin>,
     #[serde(default)]
     pub total: Option<u32>,
@@ -26,7 +29,9 @@ pub struct MarketCoin {
     #[serde(default)]
     pub icon: Option<String>,
     pub current_price: f64,
-    pub market_cap: f64,
+    /// `None` when the API left it out, rather than a market cap of 0
+    #[serde(default)]
+    pub market_cap: Option<f64>,
     #[serde(default)]
     pub volume_24h: f64,
     #[serde(default)]
@@ -53,6 +58,7 @@ impl MarketCoin {
 /// Recent trades response from /api/trades/recent
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecentTradesResponse {
+    #[serde(deserialize_with = "skip_invalid")]
     pub trades: Vec<RecentTrade>,
 }
 
@@ -60,13 +66,15 @@ pub struct RecentTradesResponse {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RecentTrade {
-    #[serde(rename(deserialize = "type"))]
+    // The API sends `type`; `tradeType` is what we serialize, so stored trades read back
+    #[serde(rename(deserialize = "type"), alias = "tradeType")]
     pub trade_type: String,
     pub username: String,
     #[serde(default)]
     pub user_image: Option<String>,
     pub amount: f64,
     pub coin_symbol: String,
+    #[serde(default)]
     pub coin_name: String,
     #[serde(default)]
     pub coin_icon: Option<String>,
@@ -77,6 +85,11 @@ pub struct RecentTrade {
 }
 
 impl RecentTrade {
+    /// Price, amount and value are all usable numbers
+    pub fn has_valid_numbers(&self) -> bool {
+        is_valid_price(self.price) && self.amount.is_finite() && self.total_value.is_finite()
+    }
+
     /// Check if this is a buy trade
     pub fn is_buy(&self) -> bool {
         self.trade_type.to_uppercase() == "BUY"
@@ -99,9 +112,12 @@ impl RecentTrade {
 #[serde(rename_all = "camelCase")]
 pub struct CoinHoldersResponse {
     pub coin_symbol: String,
+    #[serde(default)]
     pub total_holders: u32,
+    #[serde(default)]
     pub circulating_supply: f64,
     pub pool_info: PoolInfo,
+    #[serde(deserialize_with = "skip_invalid")]
     pub holders: Vec<Holder>,
 }
 
@@ -118,14 +134,17 @@ pub struct PoolInfo {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Holder {
+    #[serde(default)]
     pub rank: u32,
     pub user_id: u32,
     pub username: String,
+    #[serde(default)]
     pub name: String,
     #[serde(default)]
     pub image: Option<String>,
     pub quantity: f64,
     pub percentage: f64,
+    #[serde(default)]
     pub liquidation_value: f64,
 }
 
@@ -147,6 +166,7 @@ impl Holder {
 /// Response from GET /api/coin/{SYMBOL}/comments
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoinCommentsResponse {
+    #[serde(deserialize_with = "skip_invalid")]
     pub comments: Vec<CoinComment>,
 }
 
@@ -162,6 +182,7 @@ pub struct CoinComment {
     pub user_name: Option<String>,
     #[serde(default)]
     pub user_image: Option<String>,
+    #[serde(default)]
     pub likes_count: i32,
     #[serde(default)]
     pub is_liked_by_user: bool,
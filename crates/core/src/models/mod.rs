@@ -1,14 +1,17 @@
 //! Data models for Rugplay entities
 
 mod coin;
+mod cost_basis;
 mod leaderboard;
 mod market;
 mod portfolio;
 mod reward;
+mod schema;
 mod trade;
 mod user;
 
 pub use coin::*;
+pub use cost_basis::*;
 pub use leaderboard::*;
 pub use market::*;
 pub use portfolio::*;
@@ -1,5 +1,7 @@
 //! Portfolio-related models
 
+use super::schema::missing_f64;
+use crate::types::{checked_ratio, finite_or, is_valid_price};
 use serde::{Deserialize, Serialize};
 
 /// Full portfolio response from /api/portfolio/total
@@ -7,11 +9,39 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct PortfolioResponse {
     pub base_currency_balance: f64,
+    /// Recomputed from the holdings by `sanitize` when missing
+    #[serde(default = "missing_f64")]
     pub total_coin_value: f64,
+    #[serde(default = "missing_f64")]
     pub total_value: f64,
+    /// Parsed strictly: a skipped holding would read as sold and tear down
+    /// its sentinel
     pub coin_holdings: Vec<CoinHolding>,
 }
 
+impl PortfolioResponse {
+    /// Drop holdings with an unusable price or quantity and zero out
+    /// non-finite totals. Returns how many holdings were dropped.
+    pub fn sanitize(&mut self) -> usize {
+        let before = self.coin_holdings.len();
+        self.coin_holdings.retain(CoinHolding::has_valid_numbers);
+        for h in &mut self.coin_holdings {
+            h.value = finite_or(h.value, h.quantity * h.current_price);
+            h.avg_purchase_price = finite_or(h.avg_purchase_price, 0.0);
+            h.cost_basis = finite_or(h.cost_basis, 0.0);
+            h.change_24h = finite_or(h.change_24h, 0.0);
+            h.percentage_change = finite_or(h.percentage_change, 0.0);
+        }
+        self.base_currency_balance = finite_or(self.base_currency_balance, 0.0);
+        self.total_coin_value = finite_or(
+            self.total_coin_value,
+            self.coin_holdings.iter().map(|h| h.value).sum(),
+        );
+        self.total_value = finite_or(self.total_value, self.base_currency_balance + self.total_coin_value);
+        before - self.coin_holdings.len()
+    }
+}
+
 /// Individual coin holding in portfolio
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +51,7 @@ pub struct CoinHolding {
     pub icon: Option<String>,
     pub quantity: f64,
     pub current_price: f64,
+    #[serde(default = "missing_f64")]
     pub value: f64,
     #[serde(default)]
     pub change_24h: f64,
@@ -33,6 +64,11 @@ pub struct CoinHolding {
 }
 
 impl CoinHolding {
+    /// Price is usable and quantity is a real number
+    pub fn has_valid_numbers(&self) -> bool {
+        is_valid_price(self.current_price) && self.quantity.is_finite()
+    }
+
     /// Calculate profit/loss in USD
     pub fn profit_loss(&self) -> f64 {
         self.value - self.cost_basis
@@ -41,7 +77,7 @@ impl CoinHolding {
     /// Calculate profit/loss percentage
     pub fn profit_loss_pct(&self) -> f64 {
         if self.cost_basis > 0.0 {
-            ((self.value - self.cost_basis) / self.cost_basis) * 100.0
+            checked_ratio(self.value - self.cost_basis, self.cost_basis).map_or(0.0, |r| r * 100.0)
         } else {
             0.0
         }
@@ -69,6 +105,21 @@ pub struct PortfolioSummary {
     pub total_profit_loss: f64,
     pub total_profit_loss_pct: f64,
     pub holdings_count: usize,
+    /// Cash set aside by the frozen reserve risk limit
+    #[serde(default)]
+    pub frozen_balance: f64,
+    /// Cash automation may actually spend (balance minus frozen)
+    #[serde(default)]
+    pub available_balance: f64,
+}
+
+impl PortfolioSummary {
+    /// Split the cash balance into frozen and available portions
+    pub fn with_frozen_reserve(mut self, reserve_usd: f64) -> Self {
+        self.frozen_balance = reserve_usd.max(0.0).min(self.balance);
+        self.available_balance = self.balance - self.frozen_balance;
+        self
+    }
 }
 
 impl From<&PortfolioResponse> for PortfolioSummary {
@@ -88,6 +139,53 @@ impl From<&PortfolioResponse> for PortfolioSummary {
             total_profit_loss,
             total_profit_loss_pct,
             holdings_count: p.coin_holdings.len(),
+            frozen_balance: 0.0,
+            available_balance: p.base_currency_balance,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(symbol: &str, quantity: f64, price: f64) -> CoinHolding {
+        CoinHolding {
+            symbol: symbol.to_string(),
+            icon: None,
+            quantity,
+            current_price: price,
+            value: quantity * price,
+            change_24h: 0.0,
+            avg_purchase_price: price,
+            percentage_change: 0.0,
+            cost_basis: quantity * price,
+        }
+    }
+
+    #[test]
+    fn test_sanitize_drops_nan_prices() {
+        let mut portfolio = PortfolioResponse {
+            base_currency_balance: 100.0,
+            total_coin_value: f64::NAN,
+            total_value: f64::NAN,
+            coin_holdings: vec![
+                holding("GOOD", 10.0, 2.0),
+                holding("NAN", 10.0, f64::NAN),
+                holding("ZERO", 10.0, 0.0),
+                holding("INF", f64::INFINITY, 1.0),
+            ],
+        };
+
+        assert_eq!(portfolio.sanitize(), 3);
+        assert_eq!(portfolio.coin_holdings.len(), 1);
+        assert_eq!(portfolio.coin_holdings[0].symbol, "GOOD");
+        assert_eq!(portfolio.total_coin_value, 20.0);
+        assert_eq!(portfolio.total_value, 120.0);
+
+        let mut bad_basis = holding("X", 1.0, 1.0);
+        bad_basis.cost_basis = 1e-320;
+        bad_basis.value = f64::MAX;
+        assert_eq!(bad_basis.profit_loss_pct(), 0.0);
+    }
+}
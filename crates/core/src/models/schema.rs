@@ -0,0 +1,121 @@
+//! Tolerant deserialization for API payloads
+//!
+//! Rugplay's schema can change under us without notice. Unknown fields are
+//! already ignored and optional ones default, but one malformed entry in a
+//! list (a renamed field on a single coin, a null where a number was) would
+//! still fail the whole response. List fields use [`skip_invalid`] so the
+//! bad entries are logged and dropped and the rest of the page still parses.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use tracing::warn;
+
+/// Deserialize a list, skipping (and logging) entries that don't match `T`.
+/// A `null` list reads as empty.
+pub(crate) fn skip_invalid<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let values = Option::<Vec<serde_json::Value>>::deserialize(deserializer)?.unwrap_or_default();
+    let total = values.len();
+    let items: Vec<T> = values
+        .into_iter()
+        .filter_map(|value| match serde_json::from_value(value) {
+            Ok(item) => Some(item),
+            Err(e) => {
+                warn!("Skipping {} with unexpected schema: {}", short_type_name::<T>(), e);
+                None
+            }
+        })
+        .collect();
+    if items.len() < total {
+        warn!("Parsed {} of {} {} entries", items.len(), total, short_type_name::<T>());
+    }
+    Ok(items)
+}
+
+/// `NaN` placeholder for a missing number that is recomputed after parsing
+pub(crate) fn missing_f64() -> f64 {
+    f64::NAN
+}
+
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{CoinDetailsResponse, MarketResponse, PortfolioResponse, TradeResponse};
+
+    #[test]
+    fn test_payloads_with_extra_and_missing_fields_still_parse() {
+        // New top-level and per-coin fields, a coin missing its market cap,
+        // and one coin whose price came back in an unexpected shape
+        let market: MarketResponse = serde_json::from_value(serde_json::json!({
+            "coins": [
+                { "symbol": "AAA", "name": "A", "currentPrice": 1.5, "marketCap": 900.0, "isFeatured": true },
+                { "symbol": "BBB", "name": "B", "currentPrice": 0.2 },
+                { "symbol": "CCC", "name": "C", "currentPrice": { "value": 3 } },
+            ],
+            "total": 3,
+            "sortOrder": "desc",
+        }))
+        .unwrap();
+        let symbols: Vec<_> = market.coins.iter().map(|c| c.symbol.as_str()).collect();
+        assert_eq!(symbols, ["AAA", "BBB"]);
+        assert_eq!(market.coins[0].market_cap, Some(900.0));
+        assert_eq!(market.coins[1].market_cap, None);
+
+        // Totals and per-holding value missing: sanitize recomputes them
+        let mut portfolio: PortfolioResponse = serde_json::from_value(serde_json::json!({
+            "baseCurrencyBalance": 100.0,
+            "coinHoldings": [{ "symbol": "AAA", "quantity": 10.0, "currentPrice": 2.0, "badge": "new" }],
+        }))
+        .unwrap();
+        assert_eq!(portfolio.sanitize(), 0);
+        assert_eq!(portfolio.coin_holdings[0].value, 20.0);
+        assert_eq!(portfolio.total_value, 120.0);
+
+        let trade: TradeResponse = serde_json::from_value(serde_json::json!({
+            "success": true,
+            "type": "BUY",
+            "coinsBought": 4.0,
+            "fee": 0.01,
+        }))
+        .unwrap();
+        assert_eq!(trade.coins_bought, Some(4.0));
+        // No price is not a price of 0
+        assert_eq!(trade.new_price, None);
+        assert_eq!(trade.fill_price(), None);
+
+        // A null candle list and a malformed candle don't lose the coin
+        let details: CoinDetailsResponse = serde_json::from_value(serde_json::json!({
+            "coin": {
+                "id": 7, "symbol": "AAA", "name": "A", "currentPrice": 1.0,
+                "poolCoinAmount": 1000.0, "poolBaseCurrencyAmount": 1000.0,
+            },
+            "candlestickData": [{ "time": 1, "open": 1.0, "high": 1.0, "low": 1.0, "close": 1.0 }, { "time": 2 }],
+            "volumeData": null,
+        }))
+        .unwrap();
+        assert_eq!(details.coin.id, "7");
+        assert_eq!(details.candlestick_data.len(), 1);
+        assert!(details.volume_data.is_empty());
+    }
+
+    #[test]
+    fn test_missing_essential_fields_still_fail() {
+        // Guessing a price or pool size would feed fake numbers into trades
+        assert!(serde_json::from_value::<CoinDetailsResponse>(serde_json::json!({
+            "coin": { "id": 7, "symbol": "AAA", "name": "A", "currentPrice": 1.0 },
+        }))
+        .is_err());
+        assert!(serde_json::from_value::<PortfolioResponse>(serde_json::json!({
+            "baseCurrencyBalance": 100.0,
+            "coinHoldings": [{ "symbol": "AAA", "currentPrice": 2.0 }],
+        }))
+        .is_err());
+    }
+}
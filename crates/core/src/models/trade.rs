@@ -1,5 +1,7 @@
 //! Trade-related models
 
+use super::schema::skip_invalid;
+use crate::types::is_valid_price;
 use serde::{Deserialize, Serialize};
 
 /// Trade type (buy or sell)
@@ -25,7 +27,7 @@ pub struct TradeRequest {
 #[serde(rename_all = "camelCase")]
 pub struct TradeResponse {
     pub success: bool,
-    #[serde(rename = "type")]
+    #[serde(rename = "type", default)]
     pub trade_type: String,
     #[serde(default)]
     pub coins_bought: Option<f64>,
@@ -35,10 +37,23 @@ pub struct TradeResponse {
     pub total_cost: Option<f64>,
     #[serde(default)]
     pub total_received: Option<f64>,
-    pub new_price: f64,
+    /// Price after the trade; `None` when the API left it out
+    #[serde(default)]
+    pub new_price: Option<f64>,
+    #[serde(default)]
     pub price_impact: f64,
     #[serde(default)]
     pub new_balance: f64,
+    /// Simulated by paper trading rather than returned by the API
+    #[serde(default)]
+    pub paper: bool,
+}
+
+impl TradeResponse {
+    /// The price after the trade, if the API reported a usable one
+    pub fn fill_price(&self) -> Option<f64> {
+        self.new_price.filter(|price| is_valid_price(*price))
+    }
 }
 
 /// Transaction record stored in local database
@@ -64,12 +79,13 @@ pub struct Transaction {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ApiTransactionsResponse {
+    #[serde(deserialize_with = "skip_invalid")]
     pub transactions: Vec<ApiTransaction>,
-    #[serde(deserialize_with = "deserialize_string_or_number")]
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub total: u32,
-    #[serde(deserialize_with = "deserialize_string_or_number")]
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub page: u32,
-    #[serde(deserialize_with = "deserialize_string_or_number")]
+    #[serde(default, deserialize_with = "deserialize_string_or_number")]
     pub limit: u32,
 }
 
@@ -198,7 +214,7 @@ where
 /// # Important
 /// Always use this before selling coins to avoid "insufficient coins" errors.
 pub fn truncate_to_8_decimals(value: f64) -> f64 {
-    (value * 1e8).floor() / 1e8
+    crate::types::truncate_to_decimals(value, crate::types::DEFAULT_COIN_DECIMALS)
 }
 
 #[cfg(test)]
@@ -43,9 +43,12 @@ pub struct SessionInfo {
 #[serde(rename_all = "camelCase")]
 pub struct UserData {
     pub id: String,
+    #[serde(default)]
     pub name: String,
     pub username: String,
+    #[serde(default)]
     pub email: String,
+    #[serde(default)]
     pub email_verified: bool,
     #[serde(default)]
     pub image: Option<String>,
@@ -79,6 +82,53 @@ pub struct UserProfile {
     pub session_expires_at: String,
 }
 
+/// Outcome of checking a session token before it is saved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TokenValidation {
+    /// The API accepted the token
+    #[serde(rename_all = "camelCase")]
+    Valid {
+        username: String,
+        user_id: String,
+        expires_at: String,
+    },
+    /// The token was recognised but its session has ended
+    Expired,
+    /// The API rejected the token or returned no session for it
+    Invalid { reason: String },
+    /// The API could not be reached; the token may still be fine
+    Unreachable { reason: String },
+}
+
+impl TokenValidation {
+    /// Classify the result of a `get-session` call made with the token
+    pub fn from_session(result: crate::Result<UserProfile>, now: chrono::DateTime<chrono::Utc>) -> Self {
+        match result {
+            Ok(profile) => {
+                let ended = crate::parse_api_timestamp(&profile.session_expires_at)
+                    .is_some_and(|at| at <= now);
+                if ended {
+                    TokenValidation::Expired
+                } else {
+                    TokenValidation::Valid {
+                        username: profile.username,
+                        user_id: profile.id,
+                        expires_at: profile.session_expires_at,
+                    }
+                }
+            }
+            Err(crate::Error::TokenExpired) => TokenValidation::Expired,
+            Err(crate::Error::NetworkError(e)) => TokenValidation::Unreachable { reason: e },
+            Err(e) => TokenValidation::Invalid { reason: e.to_string() },
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, TokenValidation::Valid { .. })
+    }
+}
+
 /// Locally stored profile (encrypted token stored separately)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Profile {
@@ -87,6 +137,12 @@ pub struct Profile {
     pub user_id: Option<String>,
     pub last_verified: Option<chrono::DateTime<chrono::Utc>>,
     pub is_active: bool,
+    /// Avatar from the last identity refresh
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    /// When the profile was last selected
+    #[serde(default)]
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Summary of a profile for display in UI (no sensitive data)
@@ -95,6 +151,8 @@ pub struct ProfileSummary {
     pub id: i64,
     pub username: String,
     pub last_verified: Option<String>,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
 }
 
 impl From<Profile> for ProfileSummary {
@@ -103,6 +161,7 @@ impl From<Profile> for ProfileSummary {
             id: p.id,
             username: p.username,
             last_verified: p.last_verified.map(|dt| dt.to_rfc3339()),
+            avatar_url: p.avatar_url,
         }
     }
 }
@@ -190,3 +249,55 @@ impl UserPublicStats {
         Self::parse_f64(&self.total_buy_volume) + Self::parse_f64(&self.total_sell_volume)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    fn profile(expires_at: &str) -> UserProfile {
+        UserProfile {
+            id: "u1".to_string(),
+            username: "alice".to_string(),
+            name: String::new(),
+            email: String::new(),
+            image: None,
+            balance: 0.0,
+            is_admin: false,
+            is_banned: false,
+            session_expires_at: expires_at.to_string(),
+        }
+    }
+
+    fn now() -> chrono::DateTime<chrono::Utc> {
+        "2026-01-15T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_token_validation_outcomes() {
+        let valid = TokenValidation::from_session(Ok(profile("2026-02-01T00:00:00Z")), now());
+        assert!(valid.is_valid());
+        assert_eq!(
+            valid,
+            TokenValidation::Valid {
+                username: "alice".to_string(),
+                user_id: "u1".to_string(),
+                expires_at: "2026-02-01T00:00:00Z".to_string(),
+            }
+        );
+
+        // Session object returned but already past its expiry
+        let ended = TokenValidation::from_session(Ok(profile("2026-01-01T00:00:00Z")), now());
+        assert_eq!(ended, TokenValidation::Expired);
+
+        assert_eq!(TokenValidation::from_session(Err(Error::TokenExpired), now()), TokenValidation::Expired);
+        assert!(matches!(
+            TokenValidation::from_session(Err(Error::InvalidData("null".into())), now()),
+            TokenValidation::Invalid { .. }
+        ));
+        assert!(matches!(
+            TokenValidation::from_session(Err(Error::NetworkError("timeout".into())), now()),
+            TokenValidation::Unreachable { .. }
+        ));
+    }
+}
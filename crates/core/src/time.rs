@@ -0,0 +1,119 @@
+//! Timestamp parsing for values read from SQLite and the Rugplay API
+//!
+//! SQLite `CURRENT_TIMESTAMP` columns come back as `YYYY-MM-DD HH:MM:SS`
+//! (UTC, no zone), rows written by the app may hold RFC 3339, and the API
+//! returns RFC 3339 with or without fractional seconds, occasionally without
+//! a zone, or as unix epoch numbers. Everything funnels through one parser so
+//! callers get a `DateTime<Utc>` or `None` — never a silent zero.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+/// Epoch values above this are taken as milliseconds (year 5138 in seconds)
+const EPOCH_MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Zone-less layouts, all interpreted as UTC
+const NAIVE_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M",
+];
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Some(at.with_timezone(&Utc));
+    }
+    // RFC 3339 with a space instead of `T` (e.g. "2024-05-01 12:00:00+00:00")
+    if let Ok(at) = DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%:z") {
+        return Some(at.with_timezone(&Utc));
+    }
+    for format in NAIVE_FORMATS {
+        if let Ok(at) = NaiveDateTime::parse_from_str(value, format) {
+            return Some(at.and_utc());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date.and_hms_opt(0, 0, 0).map(|at| at.and_utc());
+    }
+
+    let epoch: i64 = value.parse().ok()?;
+    if epoch <= 0 {
+        return None;
+    }
+    if epoch > EPOCH_MILLIS_THRESHOLD {
+        Utc.timestamp_millis_opt(epoch).single()
+    } else {
+        Utc.timestamp_opt(epoch, 0).single()
+    }
+}
+
+/// Parse a timestamp column read from the app database
+/// (`CURRENT_TIMESTAMP` text, RFC 3339, or epoch seconds)
+pub fn parse_db_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    parse_timestamp(value)
+}
+
+/// Parse a timestamp field returned by the Rugplay API
+/// (RFC 3339 with or without zone/fraction, or epoch seconds/millis)
+pub fn parse_api_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    parse_timestamp(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32, s: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, s).unwrap()
+    }
+
+    #[test]
+    fn test_db_formats() {
+        let expected = at(2024, 5, 1, 12, 30, 45);
+        // SQLite CURRENT_TIMESTAMP
+        assert_eq!(parse_db_timestamp("2024-05-01 12:30:45"), Some(expected));
+        // strftime with milliseconds
+        assert_eq!(
+            parse_db_timestamp("2024-05-01 12:30:45.250"),
+            Some(expected + chrono::Duration::milliseconds(250))
+        );
+        // Written by the app via to_rfc3339()
+        assert_eq!(parse_db_timestamp("2024-05-01T12:30:45+00:00"), Some(expected));
+        assert_eq!(parse_db_timestamp("2024-05-01 12:30:45+00:00"), Some(expected));
+        // Epoch seconds stored as text
+        assert_eq!(parse_db_timestamp("1714566645"), Some(expected));
+        assert_eq!(parse_db_timestamp("2024-05-01"), Some(at(2024, 5, 1, 0, 0, 0)));
+    }
+
+    #[test]
+    fn test_api_formats() {
+        let expected = at(2024, 5, 1, 12, 30, 45);
+        assert_eq!(parse_api_timestamp("2024-05-01T12:30:45Z"), Some(expected));
+        assert_eq!(
+            parse_api_timestamp("2024-05-01T12:30:45.123Z"),
+            Some(expected + chrono::Duration::milliseconds(123))
+        );
+        // Offsets are normalised to UTC
+        assert_eq!(parse_api_timestamp("2024-05-01T14:30:45+02:00"), Some(expected));
+        // Zone-less ISO is taken as UTC
+        assert_eq!(parse_api_timestamp("2024-05-01T12:30:45"), Some(expected));
+        assert_eq!(parse_api_timestamp("2024-05-01T12:30"), Some(at(2024, 5, 1, 12, 30, 0)));
+        // Epoch seconds and milliseconds
+        assert_eq!(parse_api_timestamp("1714566645"), Some(expected));
+        assert_eq!(parse_api_timestamp("1714566645000"), Some(expected));
+        assert_eq!(parse_api_timestamp(" 2024-05-01T12:30:45Z "), Some(expected));
+    }
+
+    #[test]
+    fn test_unparseable_values_are_none_not_epoch_zero() {
+        for bad in ["", "   ", "yesterday", "0", "-5", "2024-13-01 00:00:00", "12:30:45"] {
+            assert_eq!(parse_db_timestamp(bad), None, "{:?}", bad);
+            assert_eq!(parse_api_timestamp(bad), None, "{:?}", bad);
+        }
+    }
+}
@@ -1,6 +1,14 @@
 //! Shared type definitions and newtypes
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Decimal places the server accepts for coin amounts
+pub const DEFAULT_COIN_DECIMALS: u32 = 8;
+
+/// Most decimals a coin amount can be truncated to; an f64 holds ~15
+/// significant digits
+pub const MAX_COIN_DECIMALS: u32 = 15;
 
 /// USD amount (for clarity in function signatures)
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -29,9 +37,59 @@ impl CoinAmount {
         self.0
     }
 
-    /// Truncate to 8 decimal places (server precision)
+    /// Truncate to the default server precision
     pub fn truncated(&self) -> Self {
-        CoinAmount((self.0 * 1e8).floor() / 1e8)
+        self.truncated_to(DEFAULT_COIN_DECIMALS)
+    }
+
+    /// Truncate to `decimals` places (capped at `MAX_COIN_DECIMALS`)
+    pub fn truncated_to(&self, decimals: u32) -> Self {
+        CoinAmount(truncate_to_decimals(self.0, decimals))
+    }
+}
+
+/// Round `value` down to `decimals` places (capped at `MAX_COIN_DECIMALS`).
+/// Zero decimals keeps whole coins only.
+pub fn truncate_to_decimals(value: f64, decimals: u32) -> f64 {
+    let scale = 10f64.powi(decimals.min(MAX_COIN_DECIMALS) as i32);
+    (value * scale).floor() / scale
+}
+
+/// Decimals coin amounts are truncated to before a sell: a global default
+/// with per-coin overrides
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinPrecision {
+    #[serde(default = "default_coin_decimals")]
+    pub default_decimals: u32,
+    /// Symbol -> decimals, for coins the server handles differently
+    #[serde(default)]
+    pub coin_decimals: HashMap<String, u32>,
+}
+
+fn default_coin_decimals() -> u32 {
+    DEFAULT_COIN_DECIMALS
+}
+
+impl Default for CoinPrecision {
+    fn default() -> Self {
+        Self { default_decimals: DEFAULT_COIN_DECIMALS, coin_decimals: HashMap::new() }
+    }
+}
+
+impl CoinPrecision {
+    /// Decimals for `symbol`, matched case-insensitively
+    pub fn decimals_for(&self, symbol: &str) -> u32 {
+        self.coin_decimals
+            .iter()
+            .find(|(s, _)| s.eq_ignore_ascii_case(symbol))
+            .map_or(self.default_decimals, |(_, d)| *d)
+            .min(MAX_COIN_DECIMALS)
+    }
+
+    /// `amount` of `symbol` truncated to that coin's precision
+    pub fn truncate(&self, symbol: &str, amount: f64) -> f64 {
+        truncate_to_decimals(amount, self.decimals_for(symbol))
     }
 }
 
@@ -67,3 +125,97 @@ impl Percent {
         self.0 >= 5000.0
     }
 }
+
+// ─── Numeric guards ──────────────────────────────────────────────────
+//
+// NaN compares false against everything, so a bad price silently slips past
+// `<`/`>` guards. Values coming from the API or user config go through these
+// before they reach trade decisions.
+
+/// A usable price: finite and strictly positive
+pub fn is_valid_price(price: f64) -> bool {
+    price.is_finite() && price > 0.0
+}
+
+/// `numerator / denominator`, or `None` if the divisor is zero or the result
+/// isn't finite
+pub fn checked_ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    if denominator == 0.0 {
+        return None;
+    }
+    let ratio = numerator / denominator;
+    ratio.is_finite().then_some(ratio)
+}
+
+/// Replace NaN/Infinity with `fallback`
+pub fn finite_or(value: f64, fallback: f64) -> f64 {
+    if value.is_finite() {
+        value
+    } else {
+        fallback
+    }
+}
+
+/// Reject a non-finite config value, naming the offending field
+pub fn ensure_finite(field: &str, value: f64) -> crate::Result<f64> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(crate::Error::InvalidData(format!("{} must be a finite number, got {}", field, value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_guards_reject_nan_and_infinity() {
+        assert!(is_valid_price(0.0001));
+        assert!(!is_valid_price(0.0));
+        assert!(!is_valid_price(-1.0));
+        assert!(!is_valid_price(f64::NAN));
+        assert!(!is_valid_price(f64::INFINITY));
+
+        assert_eq!(checked_ratio(50.0, 200.0), Some(0.25));
+        assert_eq!(checked_ratio(1.0, 0.0), None);
+        assert_eq!(checked_ratio(f64::NAN, 2.0), None);
+        assert_eq!(checked_ratio(1.0, f64::NAN), None);
+        assert_eq!(checked_ratio(f64::INFINITY, 2.0), None);
+
+        assert_eq!(finite_or(f64::NAN, 0.0), 0.0);
+        assert_eq!(finite_or(f64::NEG_INFINITY, 1.0), 1.0);
+        assert_eq!(finite_or(3.5, 0.0), 3.5);
+
+        assert!(ensure_finite("scaleFactor", 0.5).is_ok());
+        let err = ensure_finite("scaleFactor", f64::NAN).unwrap_err();
+        assert!(err.to_string().contains("scaleFactor"));
+    }
+
+    #[test]
+    fn test_truncation_at_varying_precision() {
+        let amount = 12.345_678_987_6;
+        assert_eq!(CoinAmount(amount).truncated(), CoinAmount(12.345_678_98));
+        assert_eq!(truncate_to_decimals(amount, 4), 12.3456);
+        assert_eq!(truncate_to_decimals(amount, 1), 12.3);
+        // Zero precision keeps whole coins, never rounding up
+        assert_eq!(truncate_to_decimals(amount, 0), 12.0);
+        assert_eq!(truncate_to_decimals(0.999, 0), 0.0);
+        assert_eq!(CoinAmount(7.9).truncated_to(0), CoinAmount(7.0));
+        // Beyond f64 precision the cap applies instead of overflowing
+        assert_eq!(truncate_to_decimals(amount, 400), truncate_to_decimals(amount, MAX_COIN_DECIMALS));
+
+        let precision = CoinPrecision {
+            default_decimals: 6,
+            coin_decimals: HashMap::from([("WHOLE".to_string(), 0), ("FINE".to_string(), 99)]),
+        };
+        assert_eq!(precision.decimals_for("other"), 6);
+        assert_eq!(precision.decimals_for("whole"), 0);
+        assert_eq!(precision.decimals_for("FINE"), MAX_COIN_DECIMALS);
+        assert_eq!(precision.truncate("WHOLE", 41.99), 41.0);
+        assert_eq!(precision.truncate("ABC", 1.234_567_89), 1.234_567);
+        assert_eq!(CoinPrecision::default().truncate("ABC", 1.234_567_891), 1.234_567_89);
+        let stored: CoinPrecision = serde_json::from_str("{}").unwrap();
+        assert_eq!(stored, CoinPrecision::default());
+    }
+}
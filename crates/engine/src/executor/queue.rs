@@ -77,7 +77,7 @@ impl TradeExecutor {
 
         match &response {
             Ok(r) => info!(
-                "Trade executed: {} @ ${}, impact {}%",
+                "Trade executed: {} @ {:?}, impact {}%",
                 order.symbol, r.new_price, r.price_impact * 100.0
             ),
             Err(e) => error!("Trade failed for {}: {}", order.symbol, e),
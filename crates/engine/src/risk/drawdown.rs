@@ -0,0 +1,106 @@
+//! Portfolio drawdown circuit breaker
+//!
+//! Tracks the total portfolio value against its peak for the current
+//! trading day. Once the value has fallen more than a threshold below that
+//! peak the breaker is tripped and buys are refused; sells always go
+//! through so positions can still be exited. A new day starts a new peak.
+
+use rugplay_core::{Error, Result, TradeType};
+use serde::{Deserialize, Serialize};
+
+/// Session peak and latest value of the portfolio
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownGuard {
+    /// Trading day the peak belongs to (`YYYY-MM-DD`), empty before the first sample
+    pub day: String,
+    pub peak_value: f64,
+    pub current_value: f64,
+}
+
+impl DrawdownGuard {
+    /// Record a portfolio value sampled on `day`. Values that aren't finite
+    /// or are negative are ignored. Returns whether the peak or day changed.
+    pub fn record(&mut self, value: f64, day: &str) -> bool {
+        if !value.is_finite() || value < 0.0 {
+            return false;
+        }
+        self.current_value = value;
+        if self.day != day {
+            self.day = day.to_string();
+            self.peak_value = value;
+            return true;
+        }
+        if value > self.peak_value {
+            self.peak_value = value;
+            return true;
+        }
+        false
+    }
+
+    /// How far (%) the current value is below the session peak
+    pub fn drawdown_pct(&self) -> f64 {
+        if self.peak_value <= 0.0 {
+            return 0.0;
+        }
+        ((self.peak_value - self.current_value) / self.peak_value * 100.0).max(0.0)
+    }
+
+    /// Whether the drawdown has reached `threshold_pct`; 0 disables the breaker
+    pub fn is_tripped(&self, threshold_pct: f64) -> bool {
+        threshold_pct > 0.0 && self.drawdown_pct() >= threshold_pct
+    }
+
+    /// Refuse buys while the breaker is tripped
+    pub fn check_trade(&self, trade_type: TradeType, threshold_pct: f64) -> Result<()> {
+        if matches!(trade_type, TradeType::Buy) && self.is_tripped(threshold_pct) {
+            return Err(Error::TradeError(format!(
+                "Drawdown circuit breaker: portfolio is {:.1}% below today's peak of ${:.2} (limit {:.1}%), buys are paused",
+                self.drawdown_pct(),
+                self.peak_value,
+                threshold_pct
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_trips_on_drawdown_from_session_peak() {
+        let mut guard = DrawdownGuard::default();
+        assert!(guard.record(1_000.0, "2026-10-16"));
+        assert!(guard.record(1_200.0, "2026-10-16"));
+        assert!(!guard.record(1_000.0, "2026-10-16"));
+        assert!((guard.drawdown_pct() - 16.666_666).abs() < 1e-3);
+        assert!(!guard.is_tripped(20.0));
+        assert!(guard.check_trade(TradeType::Buy, 20.0).is_ok());
+
+        guard.record(900.0, "2026-10-16");
+        assert!(guard.is_tripped(20.0));
+        assert!(!guard.is_tripped(0.0));
+        let err = guard.check_trade(TradeType::Buy, 20.0).unwrap_err();
+        assert!(matches!(err, Error::TradeError(_)), "{}", err);
+        assert!(guard.check_trade(TradeType::Sell, 20.0).is_ok());
+
+        // Bad samples are ignored
+        assert!(!guard.record(f64::NAN, "2026-10-16"));
+        assert_eq!(guard.current_value, 900.0);
+    }
+
+    #[test]
+    fn test_new_day_starts_a_new_peak() {
+        let mut guard = DrawdownGuard::default();
+        guard.record(1_000.0, "2026-10-16");
+        guard.record(500.0, "2026-10-16");
+        assert!(guard.is_tripped(25.0));
+
+        assert!(guard.record(500.0, "2026-10-17"));
+        assert_eq!(guard.peak_value, 500.0);
+        assert_eq!(guard.drawdown_pct(), 0.0);
+        assert!(!guard.is_tripped(25.0));
+    }
+}
@@ -2,6 +2,10 @@
 //! 
 //! TODO: Implement comprehensive risk controls in Phase 2+
 
+mod drawdown;
 mod limits;
+mod score;
 
+pub use drawdown::*;
 pub use limits::*;
+pub use score::*;
@@ -0,0 +1,251 @@
+//! Coin risk score shared by the buy-side modules
+//!
+//! Every module that buys has its own view of what makes a coin dangerous:
+//! the dip buyer looks at holder concentration and volume, the sniper at
+//! age and liquidity. `assess_coin_risk` puts those heuristics in one place
+//! and turns them into a 0–100 score (higher is riskier) with the breakdown
+//! that produced it. Modules compare the score against their own limit.
+
+use rugplay_core::{parse_api_timestamp, CandlestickPoint, CoinDetails, CoinHoldersResponse};
+use serde::{Deserialize, Serialize};
+
+/// Component risk used when its input is missing
+const UNKNOWN_RISK: f64 = 50.0;
+
+/// Fewest candles the volatility component is measured on
+const MIN_VOLATILITY_CANDLES: usize = 3;
+
+/// Most recent candles the volatility component looks at
+const VOLATILITY_WINDOW: usize = 24;
+
+const CONCENTRATION_WEIGHT: f64 = 0.30;
+const LIQUIDITY_WEIGHT: f64 = 0.25;
+const AGE_WEIGHT: f64 = 0.15;
+const VOLUME_WEIGHT: f64 = 0.15;
+const VOLATILITY_WEIGHT: f64 = 0.15;
+
+/// One heuristic's contribution to the score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskComponent {
+    pub name: String,
+    /// 0 (safe) to 100 (risky)
+    pub risk: f64,
+    pub weight: f64,
+    pub detail: String,
+}
+
+/// Overall risk of buying a coin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskScore {
+    /// Weighted sum of the components, 0 (safe) to 100 (risky)
+    pub score: f64,
+    pub components: Vec<RiskComponent>,
+}
+
+impl RiskScore {
+    /// Whether the score is over `max_score`; a limit of 0 disables the check
+    pub fn exceeds(&self, max_score: f64) -> bool {
+        max_score > 0.0 && self.score > max_score
+    }
+
+    /// Component breakdown for logs, e.g. `Liquidity 35, Age 28`
+    pub fn breakdown(&self) -> String {
+        self.components
+            .iter()
+            .map(|c| format!("{} {:.0}", c.name, c.risk))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Score a coin. `holders` and `candles` may be missing; their components
+/// then count as medium risk. `now` is Unix seconds, used for the coin age.
+pub fn assess_coin_risk(
+    coin: &CoinDetails,
+    holders: Option<&CoinHoldersResponse>,
+    candles: &[CandlestickPoint],
+    now: i64,
+) -> RiskScore {
+    let components = vec![
+        concentration_risk(holders),
+        liquidity_risk(coin.pool_base_currency_amount),
+        age_risk(coin.created_at.as_deref(), now),
+        volume_risk(coin.volume_24h, coin.pool_base_currency_amount),
+        volatility_risk(candles),
+    ];
+    let score = components.iter().map(|c| c.risk * c.weight).sum::<f64>().clamp(0.0, 100.0);
+    RiskScore { score, components }
+}
+
+/// Map `value` onto 0–100: 0 at `safe`, 100 at `risky`, clamped outside.
+/// Works in either direction.
+fn scale(value: f64, safe: f64, risky: f64) -> f64 {
+    if !value.is_finite() {
+        return 100.0;
+    }
+    ((value - safe) / (risky - safe) * 100.0).clamp(0.0, 100.0)
+}
+
+fn component(name: &str, risk: f64, weight: f64, detail: String) -> RiskComponent {
+    RiskComponent { name: name.to_string(), risk, weight, detail }
+}
+
+/// Top holder at 10% is fine, at 60% it can rug alone; top 10 from 20% to 80%
+fn concentration_risk(holders: Option<&CoinHoldersResponse>) -> RiskComponent {
+    let Some(holders) = holders else {
+        return component("Concentration", UNKNOWN_RISK, CONCENTRATION_WEIGHT, "No holder data".into());
+    };
+    let top1 = holders.holders.iter().map(|h| h.percentage).fold(0.0, f64::max);
+    let top10: f64 = holders.holders.iter().filter(|h| h.rank <= 10).map(|h| h.percentage).sum();
+    let mut risk = scale(top1, 10.0, 60.0).max(scale(top10, 20.0, 80.0));
+    if holders.total_holders < 5 {
+        risk = risk.max(80.0);
+    }
+    component(
+        "Concentration",
+        risk,
+        CONCENTRATION_WEIGHT,
+        format!("Top holder {:.1}%, top 10 {:.1}%, {} holders", top1, top10, holders.total_holders),
+    )
+}
+
+/// Pool depth on a log scale: $100 is trivially drained, $10k is not
+fn liquidity_risk(pool_usd: f64) -> RiskComponent {
+    let risk = if pool_usd > 0.0 { scale(pool_usd.log10(), 4.0, 2.0) } else { 100.0 };
+    component("Liquidity", risk, LIQUIDITY_WEIGHT, format!("Pool ${:.0}", pool_usd))
+}
+
+/// Age on a log scale: 10 minutes is brand new, a week has a track record
+fn age_risk(created_at: Option<&str>, now: i64) -> RiskComponent {
+    let Some(created) = created_at.and_then(parse_api_timestamp) else {
+        return component("Age", UNKNOWN_RISK, AGE_WEIGHT, "Creation time unknown".into());
+    };
+    let age_secs = (now - created.timestamp()).max(1) as f64;
+    let risk = scale(age_secs.log10(), (7.0 * 86_400.0f64).log10(), 600.0f64.log10());
+    component("Age", risk, AGE_WEIGHT, format!("{:.1}h old", age_secs / 3600.0))
+}
+
+/// 24h volume against pool depth: 2x turnover is a live market, 5% a dead one
+fn volume_risk(volume_24h: f64, pool_usd: f64) -> RiskComponent {
+    if pool_usd <= 0.0 {
+        return component("Volume", 100.0, VOLUME_WEIGHT, "No pool".into());
+    }
+    let turnover = volume_24h.max(0.0) / pool_usd;
+    let risk = if turnover > 0.0 { scale(turnover.log10(), 2.0f64.log10(), 0.05f64.log10()) } else { 100.0 };
+    component("Volume", risk, VOLUME_WEIGHT, format!("{:.2}x pool turnover", turnover))
+}
+
+/// High-low range of recent candles against their average close
+fn volatility_risk(candles: &[CandlestickPoint]) -> RiskComponent {
+    if candles.len() < MIN_VOLATILITY_CANDLES {
+        return component(
+            "Volatility",
+            UNKNOWN_RISK,
+            VOLATILITY_WEIGHT,
+            format!("{} candles, too few to measure", candles.len()),
+        );
+    }
+    let recent = &candles[candles.len().saturating_sub(VOLATILITY_WINDOW)..];
+    let high = recent.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+    let low = recent.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+    let mean_close = recent.iter().map(|c| c.close).sum::<f64>() / recent.len() as f64;
+    let range_pct = if mean_close > 0.0 { (high - low) / mean_close * 100.0 } else { f64::INFINITY };
+    component(
+        "Volatility",
+        scale(range_pct, 10.0, 150.0),
+        VOLATILITY_WEIGHT,
+        format!("{:.0}% range over {} candles", range_pct, recent.len()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const NOW: i64 = 1_767_225_600; // 2026-01-01T00:00:00Z
+
+    fn coin(pool_usd: f64, volume_24h: f64, created_at: &str) -> CoinDetails {
+        serde_json::from_value(json!({
+            "id": 1, "symbol": "AAA", "name": "AAA", "currentPrice": 1.0,
+            "marketCap": pool_usd * 2.0, "volume24h": volume_24h,
+            "poolCoinAmount": pool_usd, "poolBaseCurrencyAmount": pool_usd,
+            "createdAt": created_at
+        }))
+        .unwrap()
+    }
+
+    fn holders(total: u32, percentages: &[f64]) -> CoinHoldersResponse {
+        let list: Vec<_> = percentages
+            .iter()
+            .enumerate()
+            .map(|(i, pct)| json!({ "rank": i + 1, "userId": i + 1, "username": format!("u{}", i), "quantity": 1.0, "percentage": pct }))
+            .collect();
+        serde_json::from_value(json!({
+            "coinSymbol": "AAA", "totalHolders": total,
+            "poolInfo": { "coinAmount": 1.0, "baseCurrencyAmount": 1.0, "currentPrice": 1.0 },
+            "holders": list
+        }))
+        .unwrap()
+    }
+
+    fn candles(range_pct: f64, count: usize) -> Vec<CandlestickPoint> {
+        (0..count)
+            .map(|i| CandlestickPoint {
+                time: i as i64,
+                open: 1.0,
+                high: 1.0 + range_pct / 200.0,
+                low: 1.0 - range_pct / 200.0,
+                close: 1.0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_known_coin_profiles_land_in_expected_ranges() {
+        // A month old, deep pool, busy market, spread-out holders
+        let established = assess_coin_risk(
+            &coin(50_000.0, 200_000.0, "2025-12-01T00:00:00Z"),
+            Some(&holders(200, &[8.0, 6.0, 5.0, 3.0, 3.0])),
+            &candles(6.0, 24),
+            NOW,
+        );
+        assert!(established.score < 20.0, "{} ({})", established.score, established.breakdown());
+
+        // A minute old, $50 pool, no trades, the creator holds almost everything
+        let fresh_rug = assess_coin_risk(
+            &coin(50.0, 0.0, "2025-12-31T23:59:00Z"),
+            Some(&holders(2, &[90.0, 10.0])),
+            &[],
+            NOW,
+        );
+        assert!(fresh_rug.score > 80.0, "{} ({})", fresh_rug.score, fresh_rug.breakdown());
+
+        // A day old with a modest pool and no holder data
+        let unproven = assess_coin_risk(
+            &coin(2_000.0, 1_000.0, "2025-12-31T00:00:00Z"),
+            None,
+            &candles(40.0, 12),
+            NOW,
+        );
+        assert!((30.0..70.0).contains(&unproven.score), "{} ({})", unproven.score, unproven.breakdown());
+        assert_eq!(unproven.components[0].risk, UNKNOWN_RISK);
+
+        assert!(fresh_rug.exceeds(60.0));
+        assert!(!established.exceeds(60.0));
+        assert!(!fresh_rug.exceeds(0.0));
+    }
+
+    #[test]
+    fn test_components_are_bounded_and_weights_sum_to_one() {
+        let mut broken = coin(0.0, 0.0, "not a date");
+        broken.volume_24h = f64::NAN;
+        let score = assess_coin_risk(&broken, None, &candles(1e9, 3), NOW);
+        assert!(score.components.iter().all(|c| (0.0..=100.0).contains(&c.risk)));
+        let weights: f64 = score.components.iter().map(|c| c.weight).sum();
+        assert!((weights - 1.0).abs() < 1e-9);
+        assert!((0.0..=100.0).contains(&score.score));
+    }
+}
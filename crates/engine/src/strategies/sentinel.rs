@@ -2,6 +2,8 @@
 //! 
 //! Client-side execution of risk management rules
 
+use rugplay_core::is_valid_price;
+
 /// Stop loss/take profit configuration for a position
 #[derive(Debug, Clone)]
 pub struct SentinelConfig {
@@ -47,6 +49,11 @@ impl TrackedPosition {
 
     /// Update with current price and check if any trigger is hit
     pub fn check_trigger(&mut self, current_price: f64) -> Option<SentinelTrigger> {
+        // A NaN/zero price would compare false everywhere; never act on one
+        if !is_valid_price(current_price) || !is_valid_price(self.entry_price) {
+            return None;
+        }
+
         // Update highest price for trailing stop
         if current_price > self.highest_price_seen {
             self.highest_price_seen = current_price;
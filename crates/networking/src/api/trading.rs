@@ -2,8 +2,7 @@
 
 use crate::RugplayClient;
 use rugplay_core::{
-    CoinDetails, Error, Result, TradeRequest, TradeResponse, TradeType,
-    truncate_to_8_decimals,
+    CoinDetails, CoinPrecision, Error, Result, TradeRequest, TradeResponse, TradeType,
 };
 use tracing::{info, warn};
 
@@ -39,22 +38,30 @@ pub async fn buy_coin(
 /// # Arguments
 /// * `client` - The authenticated Rugplay client
 /// * `symbol` - Coin symbol (e.g., "PEPE")
-/// * `coin_amount` - Amount of coins to sell (will be truncated to 8 decimals)
+/// * `coin_amount` - Amount of coins to sell (will be truncated to the coin's precision)
+/// * `precision` - Decimals accepted per coin (8 unless overridden)
 /// 
 /// # Important
-/// The coin amount is automatically truncated to 8 decimal places
+/// The coin amount is automatically truncated to the coin's decimal places
 /// to match server precision and avoid "insufficient coins" errors.
 pub async fn sell_coin(
     client: &RugplayClient,
     symbol: &str,
     coin_amount: f64,
+    precision: &CoinPrecision,
 ) -> Result<TradeResponse> {
     if coin_amount <= 0.0 {
         return Err(Error::InvalidData("Coin amount must be positive".to_string()));
     }
 
-    // CRITICAL: Truncate to 8 decimals to match server precision
-    let truncated_amount = truncate_to_8_decimals(coin_amount);
+    // CRITICAL: Truncate to the coin's decimals to match server precision
+    let truncated_amount = precision.truncate(symbol, coin_amount);
+    if truncated_amount <= 0.0 {
+        return Err(Error::InvalidData(format!(
+            "{} {} is below the {} decimals {} accepts",
+            coin_amount, symbol, precision.decimals_for(symbol), symbol
+        )));
+    }
     
     if truncated_amount != coin_amount {
         warn!(
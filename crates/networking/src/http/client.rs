@@ -3,22 +3,161 @@
 use reqwest::{
     cookie::Jar,
     header::{HeaderMap, HeaderValue, ACCEPT, ACCEPT_LANGUAGE, COOKIE, REFERER, USER_AGENT},
-    Client, Response,
+    Client, RequestBuilder, Response,
 };
 use rugplay_core::{
-    ApiTransactionsResponse, CoinDetails, CoinDetailsResponse, CoinHoldersResponse, Error,
+    parse_minimum_trade_usd, ApiTransactionsResponse, CoinDetails, CoinDetailsResponse, CoinHoldersResponse, Error,
     LeaderboardResponse, MarketResponse, PortfolioResponse, RecentTrade, RecentTradesResponse,
-    Result, SessionResponse, TradeRequest, TradeResponse, UserProfile,
+    Result, SessionResponse, TokenValidation, TradeRequest, TradeResponse, UserProfile,
     UserPublicProfileResponse,
 };
 use rugplay_persistence::cache::CoinCache;
-use std::sync::Arc;
-use tracing::{debug, error, instrument};
-
-const BASE_URL: &str = "https://rugplay.com";
-const API_BASE: &str = "https://rugplay.com/api";
+use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tracing::{debug, error, info, instrument, warn};
+
+/// Production site; API requests go to `{base}/api`
+pub const DEFAULT_BASE_URL: &str = "https://rugplay.com";
+/// Max characters of a server error body kept in trade error messages
+const MAX_ERROR_BODY_CHARS: usize = 300;
+/// How the API words a rejection for an order under the minimum size,
+/// followed by the minimum itself ("Minimum trade amount is $1.00")
+const BELOW_MINIMUM_PREFIX: &str = "Minimum trade amount is $";
 // Use a real browser User-Agent to avoid being blocked
-const USER_AGENT_VALUE: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
+pub(crate) const USER_AGENT_VALUE: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
+
+/// When the client throws away its connection pool and starts fresh.
+///
+/// After a laptop sleeps or switches networks the pooled connections are
+/// dead, and requests on them fail with connect errors until they time out.
+/// Rebuilding the underlying HTTP client drops them all at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Rebuild automatically; when off, only [`RugplayClient::reconnect`] does
+    pub enabled: bool,
+    /// Consecutive connection failures that trigger a rebuild
+    pub failure_threshold: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self { enabled: true, failure_threshold: 3 }
+    }
+}
+
+/// Connection failures and rebuilds counted across every client sharing it.
+///
+/// The app builds a client per task, so a per-client count would rarely
+/// reach the threshold. Sharing one lets failures anywhere add up; a rebuild
+/// bumps the generation and every sharing client drops its pool before its
+/// next request.
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    /// Connection failures since the last success or rebuild
+    failures: AtomicU32,
+    /// Times the HTTP clients have been rebuilt
+    generation: AtomicU32,
+}
+
+impl ConnectionHealth {
+    /// Connection failures since the last success or rebuild
+    pub fn failures(&self) -> u32 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// How many times the sharing clients have been rebuilt
+    pub fn reconnect_count(&self) -> u32 {
+        self.generation.load(Ordering::Relaxed)
+    }
+}
+
+/// How read requests are retried after a transient failure.
+///
+/// A 429, 502 or 503 response or a dropped connection is retried with
+/// exponential backoff and jitter, waiting for `Retry-After` instead when
+/// the server sends one. Trades and other POSTs are never retried: the
+/// first attempt may have gone through, and a retry could buy twice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Retries after the first attempt; 0 turns retrying off
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled for each one after
+    pub base_delay: Duration,
+    /// Longest wait between attempts; a `Retry-After` asking for more ends the retries
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Fail on the first error, as clients built with [`RugplayClient::new`] do
+    pub fn disabled() -> Self {
+        Self { max_retries: 0, ..Self::default() }
+    }
+
+    /// Wait before retry number `attempt + 1`: half the backoff plus up to as much again
+    fn backoff(&self, attempt: u32) -> Duration {
+        let full = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_delay);
+        full / 2 + full.mul_f64(jitter() / 2.0)
+    }
+}
+
+/// A random fraction in `[0, 1)`, from the std hasher's per-instance keys
+fn jitter() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+    let hash = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    (hash >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Statuses that mean "try again shortly" rather than "this request is wrong"
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 502 | 503)
+}
+
+/// Whether a send error is a refused or reset connection, worth another try
+fn is_transient(e: &reqwest::Error) -> bool {
+    if e.is_connect() {
+        return true;
+    }
+    let mut source = std::error::Error::source(e);
+    while let Some(inner) = source {
+        if let Some(io) = inner.downcast_ref::<std::io::Error>() {
+            if matches!(io.kind(), std::io::ErrorKind::ConnectionReset | std::io::ErrorKind::ConnectionAborted) {
+                return true;
+            }
+        }
+        source = inner.source();
+    }
+    false
+}
+
+/// A `Retry-After` header given in seconds (the HTTP-date form is ignored)
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    value.trim().parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// The USD minimum named by a trade rejection, if it is the API's
+/// minimum-size rejection
+fn below_minimum(detail: &str) -> Option<f64> {
+    if !detail.trim_start().starts_with(BELOW_MINIMUM_PREFIX) {
+        return None;
+    }
+    parse_minimum_trade_usd(detail)
+}
 
 /// HTTP client for interacting with Rugplay API
 /// 
@@ -26,10 +165,23 @@ const USER_AGENT_VALUE: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleW
 /// in all authenticated requests. Optionally uses an in-memory
 /// cache for coin data to reduce API calls.
 pub struct RugplayClient {
-    http: Client,
+    /// Swapped out wholesale by [`Self::reconnect`]
+    http: RwLock<Client>,
+    jar: Arc<Jar>,
     session_token: String,
+    /// Site origin used for the session cookie and Origin/Referer headers
+    base_url: String,
+    /// API root, e.g. `https://rugplay.com/api`
+    api_base: String,
     /// Optional shared coin cache (shared across all clients)
     cache: Option<Arc<CoinCache>>,
+    reconnect_policy: ReconnectPolicy,
+    /// Retries for read requests; off unless set
+    retry: RetryConfig,
+    /// Failure count and rebuilds, shared with other clients if attached
+    health: Arc<ConnectionHealth>,
+    /// Generation of `health` this client's HTTP client was built at
+    generation: AtomicU32,
 }
 
 impl RugplayClient {
@@ -38,33 +190,186 @@ impl RugplayClient {
     /// # Arguments
     /// * `session_token` - The `__Secure-better-auth.session_token` value
     pub fn new(session_token: &str) -> Self {
+        Self::with_base_url(session_token, DEFAULT_BASE_URL).expect("DEFAULT_BASE_URL is a valid URL")
+    }
+
+    /// Create a client for a different deployment (mock or staging server)
+    ///
+    /// # Arguments
+    /// * `session_token` - The `__Secure-better-auth.session_token` value
+    /// * `base_url` - Site origin, e.g. `http://127.0.0.1:8080`; the API is expected under `/api`
+    ///
+    /// Fails with [`Error::InvalidData`] if `base_url` is not a valid URL.
+    pub fn with_base_url(session_token: &str, base_url: &str) -> Result<Self> {
+        let base_url = base_url.trim_end_matches('/').to_string();
+        let url: reqwest::Url = base_url
+            .parse()
+            .map_err(|e| Error::InvalidData(format!("Invalid Rugplay base URL '{}': {}", base_url, e)))?;
+
         // Create cookie jar and add the session cookie
         let jar = Arc::new(Jar::default());
-        let url = BASE_URL.parse().unwrap();
         jar.add_cookie_str(
             &format!("__Secure-better-auth.session_token={}", session_token),
             &url,
         );
 
-        // Build client with cookie support
-        let http = Client::builder()
-            .cookie_provider(jar)
+        Ok(Self {
+            http: RwLock::new(Self::build_http(&jar)),
+            jar,
+            session_token: session_token.to_string(),
+            api_base: format!("{}/api", base_url),
+            base_url,
+            cache: None,
+            reconnect_policy: ReconnectPolicy::default(),
+            retry: RetryConfig::disabled(),
+            health: Arc::default(),
+            generation: AtomicU32::new(0),
+        })
+    }
+
+    /// Build an HTTP client with cookie support and an empty connection pool
+    fn build_http(jar: &Arc<Jar>) -> Client {
+        Client::builder()
+            .cookie_provider(jar.clone())
             .user_agent(USER_AGENT_VALUE)
             .build()
-            .expect("Failed to create HTTP client");
+            .expect("Failed to create HTTP client")
+    }
 
-        Self {
-            http,
-            session_token: session_token.to_string(),
-            cache: None,
+    /// Create a new client with a shared coin cache that retries
+    /// transient read failures per `retry`
+    pub fn new_with_cache(session_token: &str, cache: Arc<CoinCache>, retry: RetryConfig) -> Self {
+        Self::new(session_token).with_cache(cache).with_retry_config(retry)
+    }
+
+    /// Attach a shared coin cache to a client built with [`Self::with_base_url`]
+    pub fn with_cache(mut self, cache: Arc<CoinCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set when repeated connection failures rebuild the HTTP client
+    pub fn with_reconnect_policy(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = policy;
+        self
+    }
+
+    /// Count connection failures and rebuilds in `health`, shared with
+    /// every other client attached to it
+    pub fn with_connection_health(mut self, health: Arc<ConnectionHealth>) -> Self {
+        self.generation = AtomicU32::new(health.reconnect_count());
+        self.health = health;
+        self
+    }
+
+    /// Set how read requests are retried after a transient failure
+    pub fn with_retry_config(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Drop every pooled connection by rebuilding the HTTP client.
+    ///
+    /// Called automatically per the [`ReconnectPolicy`]; callers that learn
+    /// of a network change (e.g. the OS reports a resume) can call it directly.
+    /// Clients sharing its [`ConnectionHealth`] rebuild before their next request.
+    pub fn reconnect(&self) {
+        self.health.failures.store(0, Ordering::Relaxed);
+        let count = self.health.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        self.rebuild(count);
+        info!("Rebuilt HTTP client (reconnect #{})", count);
+    }
+
+    /// How many times the HTTP client, or one sharing its health, has been rebuilt
+    pub fn reconnect_count(&self) -> u32 {
+        self.health.reconnect_count()
+    }
+
+    fn rebuild(&self, generation: u32) {
+        let fresh = Self::build_http(&self.jar);
+        *self.http.write().unwrap_or_else(|e| e.into_inner()) = fresh;
+        self.generation.store(generation, Ordering::Relaxed);
+    }
+
+    /// Current HTTP client (cheap handle onto the shared pool), rebuilt
+    /// first if another client sharing the health has reconnected
+    fn http(&self) -> Client {
+        let current = self.health.reconnect_count();
+        if self.generation.load(Ordering::Relaxed) != current {
+            self.rebuild(current);
         }
+        self.http.read().unwrap_or_else(|e| e.into_inner()).clone()
     }
 
-    /// Create a new client with a shared coin cache
-    pub fn new_with_cache(session_token: &str, cache: Arc<CoinCache>) -> Self {
-        let mut client = Self::new(session_token);
-        client.cache = Some(cache);
-        client
+    /// Send a request, counting connection failures toward a rebuild
+    async fn send(&self, request: RequestBuilder) -> std::result::Result<Response, reqwest::Error> {
+        match request.send().await {
+            Ok(response) => {
+                self.health.failures.store(0, Ordering::Relaxed);
+                Ok(response)
+            }
+            Err(e) => {
+                if e.is_connect() || e.is_timeout() {
+                    self.record_connect_failure();
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Send an idempotent request, retrying transient failures per the [`RetryConfig`].
+    ///
+    /// Each attempt goes through [`Self::send`] on the current HTTP client,
+    /// so a rebuild triggered by one attempt's failure applies to the next.
+    async fn send_with_retry(&self, request: RequestBuilder) -> std::result::Result<Response, reqwest::Error> {
+        let request = request.build()?;
+        let mut attempt = 0;
+        loop {
+            // Streaming bodies can't be replayed; GETs never have one
+            let Some(this_try) = request.try_clone() else {
+                return self.send(RequestBuilder::from_parts(self.http(), request)).await;
+            };
+            let outcome = self.send(RequestBuilder::from_parts(self.http(), this_try)).await;
+            if attempt >= self.retry.max_retries {
+                return outcome;
+            }
+
+            let wait = match &outcome {
+                Ok(response) if is_retryable_status(response.status()) => match retry_after(response) {
+                    Some(wait) if wait > self.retry.max_delay => return outcome,
+                    Some(wait) => wait,
+                    None => self.retry.backoff(attempt),
+                },
+                Err(e) if is_transient(e) => self.retry.backoff(attempt),
+                _ => return outcome,
+            };
+            attempt += 1;
+            match &outcome {
+                Ok(response) => warn!("{} returned {}, retry {} in {:?}", request.url().path(), response.status(), attempt, wait),
+                Err(e) => warn!("{} failed ({}), retry {} in {:?}", request.url().path(), e, attempt, wait),
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn record_connect_failure(&self) {
+        let failures = self.health.failures.fetch_add(1, Ordering::Relaxed) + 1;
+        let policy = self.reconnect_policy;
+        if policy.enabled && failures >= policy.failure_threshold.max(1) {
+            warn!("{} consecutive connection failures, rebuilding HTTP client", failures);
+            self.reconnect();
+        }
+    }
+
+    /// Site origin this client talks to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Point the client at a different API root, keeping the site origin
+    pub fn with_api_base(mut self, api_base: &str) -> Self {
+        self.api_base = api_base.trim_end_matches('/').to_string();
+        self
     }
 
     /// Get default headers for requests (mimics browser)
@@ -75,11 +380,12 @@ impl RugplayClient {
         headers.insert(USER_AGENT, HeaderValue::from_static(USER_AGENT_VALUE));
         headers.insert(ACCEPT, HeaderValue::from_static("application/json, text/plain, */*"));
         headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.9"));
-        headers.insert(REFERER, HeaderValue::from_static("https://rugplay.com/"));
-        headers.insert(
-            "Origin",
-            HeaderValue::from_static("https://rugplay.com"),
-        );
+        if let Ok(referer) = HeaderValue::from_str(&format!("{}/", self.base_url)) {
+            headers.insert(REFERER, referer);
+        }
+        if let Ok(origin) = HeaderValue::from_str(&self.base_url) {
+            headers.insert("Origin", origin);
+        }
         headers.insert(
             "Sec-Fetch-Dest",
             HeaderValue::from_static("empty"),
@@ -106,6 +412,60 @@ impl RugplayClient {
         headers
     }
 
+    /// Turn a failed response body into a short, safe error detail.
+    ///
+    /// Prefers the `error`/`message` field of a JSON body, strips the
+    /// session token if the server echoed it, and caps the length.
+    fn error_detail(&self, body: &str) -> String {
+        let detail = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| {
+                ["error", "message"]
+                    .iter()
+                    .find_map(|k| v.get(k).and_then(|m| m.as_str()).map(str::to_string))
+            })
+            .unwrap_or_else(|| body.trim().to_string());
+
+        self.redact_and_cap(detail)
+    }
+
+    /// Strip the session token from text bound for logs or errors and cap its length
+    fn redact_and_cap(&self, text: String) -> String {
+        let mut text = if self.session_token.is_empty() {
+            text
+        } else {
+            text.replace(&self.session_token, "[REDACTED]")
+        };
+
+        if text.chars().count() > MAX_ERROR_BODY_CHARS {
+            text = text.chars().take(MAX_ERROR_BODY_CHARS).collect::<String>() + "…";
+        }
+        text
+    }
+
+    /// Read and parse a JSON response body.
+    ///
+    /// Models ignore unknown fields and skip malformed list entries, so this
+    /// only fails when the payload is unusable. When it does, the error and
+    /// a redacted preview of the body are logged so a server-side schema
+    /// change can be diagnosed from the logs.
+    async fn read_json<T: DeserializeOwned>(&self, response: Response, what: &str) -> Result<T> {
+        let body = response.text().await.map_err(|e| {
+            error!("Failed to read {} body: {}", what, e);
+            Error::InvalidData(e.to_string())
+        })?;
+
+        serde_json::from_str(&body).map_err(|e| {
+            error!(
+                "Unexpected {} schema: {}. Body preview: {}",
+                what,
+                e,
+                self.redact_and_cap(body.trim().to_string())
+            );
+            Error::InvalidData(e.to_string())
+        })
+    }
+
     /// Check if response indicates authentication failure
     fn check_auth_error(response: &Response) -> Option<Error> {
         match response.status().as_u16() {
@@ -122,19 +482,23 @@ impl RugplayClient {
         self.get_session().await
     }
 
+    /// Check whether this client's token is usable, without failing on bad tokens.
+    /// Network problems are reported separately so callers can still save offline.
+    #[instrument(skip(self))]
+    pub async fn validate_token(&self) -> TokenValidation {
+        TokenValidation::from_session(self.get_session().await, chrono::Utc::now())
+    }
+
     /// Get the current user's session and profile
     /// Uses the correct endpoint: /api/auth/get-session
     #[instrument(skip(self))]
     pub async fn get_session(&self) -> Result<UserProfile> {
-        let url = format!("{}/auth/get-session", API_BASE);
+        let url = format!("{}/auth/get-session", self.api_base);
         
         debug!("Fetching session from: {}", url);
         
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         debug!("Response status: {}", response.status());
@@ -149,10 +513,7 @@ impl RugplayClient {
         })?;
 
         // Parse the session response which contains { session: {...}, user: {...} }
-        let session_response: SessionResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse session response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let session_response: SessionResponse = self.read_json(response, "session response").await?;
 
         let profile = session_response.into_user_profile();
         debug!("Session verified for user: {}", profile.username);
@@ -183,13 +544,29 @@ impl RugplayClient {
             }
         }
 
-        let url = format!("{}/coin/{}", API_BASE, symbol);
+        self.fetch_coin(symbol).await
+    }
+
+    /// Get coin details no older than `max_age`.
+    /// A cached copy older than that is ignored and the coin is re-fetched.
+    #[instrument(skip(self))]
+    pub async fn get_coin_within(&self, symbol: &str, max_age: std::time::Duration) -> Result<CoinDetails> {
+        if let Some(ref cache) = self.cache {
+            if let Some(cached) = cache.get_within(symbol, max_age) {
+                debug!("Cache hit for {} within {:?}", symbol, max_age);
+                return Ok(cached);
+            }
+        }
+
+        self.fetch_coin(symbol).await
+    }
+
+    /// Fetch a coin from the API, bypassing the cache for the read
+    async fn fetch_coin(&self, symbol: &str) -> Result<CoinDetails> {
+        let url = format!("{}/coin/{}", self.api_base, symbol);
         
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -202,13 +579,19 @@ impl RugplayClient {
         })?;
 
         // API returns { "coin": {...}, "candlestickData": [...], ... }
-        let wrapper: CoinDetailsResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse coin response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let mut wrapper: CoinDetailsResponse = self.read_json(response, "coin response").await?;
+        wrapper.coin.fetched_at = Some(chrono::Utc::now());
 
         debug!("Coin fetched: {} @ ${}", wrapper.coin.symbol, wrapper.coin.current_price);
 
+        // Never cache or act on a bad price
+        if !wrapper.coin.has_valid_price() {
+            return Err(Error::InvalidData(format!(
+                "{} reported invalid price {}",
+                wrapper.coin.symbol, wrapper.coin.current_price
+            )));
+        }
+
         // Store in cache
         if let Some(ref cache) = self.cache {
             cache.insert(wrapper.coin.clone());
@@ -220,13 +603,10 @@ impl RugplayClient {
     /// Get full coin details including chart data
     #[instrument(skip(self))]
     pub async fn get_coin_with_chart(&self, symbol: &str, timeframe: &str) -> Result<CoinDetailsResponse> {
-        let url = format!("{}/coin/{}?timeframe={}", API_BASE, symbol, timeframe);
+        let url = format!("{}/coin/{}?timeframe={}", self.api_base, symbol, timeframe);
         
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -238,10 +618,8 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let details: CoinDetailsResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse coin response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let mut details: CoinDetailsResponse = self.read_json(response, "coin response").await?;
+        details.coin.fetched_at = Some(chrono::Utc::now());
 
         debug!("Coin with chart fetched: {} @ ${}, {} candlesticks", 
                details.coin.symbol, details.coin.current_price, details.candlestick_data.len());
@@ -253,18 +631,29 @@ impl RugplayClient {
     /// # Important
     /// - For BUY: `amount` is in USD
     /// - For SELL: `amount` is in coins (truncate to 8 decimals!)
+    /// - Never retried, even with a [`RetryConfig`]: a failed-looking trade may have filled
+    /// - Drops the coin from the attached cache whatever the outcome, for the same reason
     #[instrument(skip(self))]
     pub async fn trade(&self, symbol: &str, request: TradeRequest) -> Result<TradeResponse> {
-        let url = format!("{}/coin/{}/trade", API_BASE, symbol);
+        let result = self.send_trade(symbol, request).await;
+
+        // The trade moved the price (or may have). Only the pool reserves give
+        // a consistent quote, so the next read refetches rather than patching
+        // in `new_price`
+        if let Some(ref cache) = self.cache {
+            cache.invalidate(symbol);
+        }
+
+        result
+    }
+
+    async fn send_trade(&self, symbol: &str, request: TradeRequest) -> Result<TradeResponse> {
+        let url = format!("{}/coin/{}/trade", self.api_base, symbol);
         
         debug!("Executing {:?} trade for {}", request.trade_type, symbol);
 
         let response = self
-            .http
-            .post(&url)
-            .headers(self.default_headers())
-            .json(&request)
-            .send()
+            .send(self.http().post(&url).headers(self.default_headers()).json(&request))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -274,45 +663,41 @@ impl RugplayClient {
         let status = response.status();
         if status.is_client_error() || status.is_server_error() {
             let body = response.text().await.unwrap_or_default();
-            error!("Trade request failed: HTTP {} — {}", status, body);
-            return Err(Error::TradeError(format!("HTTP {}: {}", status, body)));
+            let detail = self.error_detail(&body);
+            error!("Trade request failed: HTTP {} — {}", status, detail);
+            if let Some(minimum) = below_minimum(&detail).filter(|_| status.is_client_error()) {
+                return Err(Error::BelowMinimumTrade { minimum, detail });
+            }
+            if detail.is_empty() {
+                return Err(Error::TradeError(format!("HTTP {}", status)));
+            }
+            return Err(Error::TradeError(format!("HTTP {}: {}", status, detail)));
         }
 
-        let trade_response: TradeResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse trade response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let trade_response: TradeResponse = self.read_json(response, "trade response").await?;
 
         if !trade_response.success {
             return Err(Error::TradeError("Trade was not successful".to_string()));
         }
 
         debug!(
-            "Trade executed: new price ${}, impact {}%",
+            "Trade executed: new price {:?}, impact {}%",
             trade_response.new_price,
             trade_response.price_impact * 100.0
         );
 
-        // Invalidate cache for this coin (price changed)
-        if let Some(ref cache) = self.cache {
-            cache.invalidate(symbol);
-        }
-
         Ok(trade_response)
     }
 
     /// Get the user's full portfolio with all holdings
     #[instrument(skip(self))]
     pub async fn get_portfolio(&self) -> Result<PortfolioResponse> {
-        let url = format!("{}/portfolio/total", API_BASE);
+        let url = format!("{}/portfolio/total", self.api_base);
         
         debug!("Fetching portfolio from: {}", url);
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         debug!("Portfolio response status: {}", response.status());
@@ -326,10 +711,12 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let portfolio: PortfolioResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse portfolio response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let mut portfolio: PortfolioResponse = self.read_json(response, "portfolio response").await?;
+
+        let dropped = portfolio.sanitize();
+        if dropped > 0 {
+            warn!("Portfolio: ignored {} holding(s) with invalid price or quantity", dropped);
+        }
 
         debug!(
             "Portfolio fetched: {} holdings, total value ${:.2}",
@@ -342,13 +729,10 @@ impl RugplayClient {
     /// Get recent trades from the platform (live feed)
     #[instrument(skip(self))]
     pub async fn get_recent_trades(&self, limit: u32) -> Result<Vec<RecentTrade>> {
-        let url = format!("{}/trades/recent?limit={}", API_BASE, limit);
+        let url = format!("{}/trades/recent?limit={}", self.api_base, limit);
         
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -360,12 +744,14 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let data: RecentTradesResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse recent trades: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let data: RecentTradesResponse = self.read_json(response, "recent trades").await?;
 
-        Ok(data.trades)
+        let total = data.trades.len();
+        let trades: Vec<RecentTrade> = data.trades.into_iter().filter(RecentTrade::has_valid_numbers).collect();
+        if trades.len() < total {
+            warn!("Recent trades: ignored {} trade(s) with invalid numbers", total - trades.len());
+        }
+        Ok(trades)
     }
 
     /// Get the user's transaction history from the Rugplay API
@@ -379,7 +765,7 @@ impl RugplayClient {
     ) -> Result<ApiTransactionsResponse> {
         let mut url = format!(
             "{}/transactions?page={}&limit={}&sortBy=timestamp&sortOrder=desc",
-            API_BASE, page, limit
+            self.api_base, page, limit
         );
 
         if let Some(tt) = trade_type {
@@ -397,10 +783,7 @@ impl RugplayClient {
         debug!("Fetching transactions from: {}", url);
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -412,15 +795,7 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let body_text = response.text().await.map_err(|e| {
-            error!("Failed to read transactions response body: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
-
-        let data: ApiTransactionsResponse = serde_json::from_str(&body_text).map_err(|e| {
-            error!("Failed to parse transactions: {}. Body preview: {}", e, &body_text[..body_text.len().min(500)]);
-            Error::InvalidData(e.to_string())
-        })?;
+        let data: ApiTransactionsResponse = self.read_json(response, "transactions response").await?;
 
         debug!("Fetched {} transactions (total: {})", data.transactions.len(), data.total);
         Ok(data)
@@ -429,13 +804,10 @@ impl RugplayClient {
     /// Get coin holders
     #[instrument(skip(self))]
     pub async fn get_coin_holders(&self, symbol: &str, limit: u32) -> Result<CoinHoldersResponse> {
-        let url = format!("{}/coin/{}/holders?limit={}", API_BASE, symbol, limit);
+        let url = format!("{}/coin/{}/holders?limit={}", self.api_base, symbol, limit);
         
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -447,10 +819,7 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let holders: CoinHoldersResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse holders response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let holders: CoinHoldersResponse = self.read_json(response, "holders response").await?;
 
         Ok(holders)
     }
@@ -467,7 +836,7 @@ impl RugplayClient {
     ) -> Result<MarketResponse> {
         let mut url = format!(
             "{}/market?page={}&limit={}&sortBy={}&sortOrder={}",
-            API_BASE, page, limit, sort_by, sort_order
+            self.api_base, page, limit, sort_by, sort_order
         );
         if let Some(q) = search {
             if !q.is_empty() {
@@ -486,10 +855,7 @@ impl RugplayClient {
         }
         
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -501,10 +867,7 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let market: MarketResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse market response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let market: MarketResponse = self.read_json(response, "market response").await?;
 
         Ok(market)
     }
@@ -512,33 +875,25 @@ impl RugplayClient {
     /// Check reward claim status
     #[instrument(skip(self))]
     pub async fn get_reward_status(&self) -> Result<rugplay_core::RewardStatusResponse> {
-        let url = format!("{}/rewards/claim", API_BASE);
+        let url = format!("{}/rewards/claim", self.api_base);
 
         debug!("Checking reward status");
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
             return Err(err);
         }
 
-        let status: rugplay_core::RewardStatusResponse = response
+        let status = response
             .error_for_status()
             .map_err(|e| {
                 error!("Reward status request failed: {}", e);
                 Error::ApiError(e.to_string())
-            })?
-            .json()
-            .await
-            .map_err(|e| {
-                error!("Failed to parse reward status response: {}", e);
-                Error::InvalidData(e.to_string())
             })?;
+        let status: rugplay_core::RewardStatusResponse = self.read_json(status, "reward status response").await?;
 
         debug!("Reward status: canClaim={}, timeRemaining={}ms ({}s)", status.can_claim, status.time_remaining, status.time_remaining / 1000);
         Ok(status)
@@ -547,33 +902,25 @@ impl RugplayClient {
     /// Claim daily reward
     #[instrument(skip(self))]
     pub async fn claim_daily_reward(&self) -> Result<rugplay_core::RewardClaimResponse> {
-        let url = format!("{}/rewards/claim", API_BASE);
+        let url = format!("{}/rewards/claim", self.api_base);
         
         debug!("Claiming daily reward");
 
         let response = self
-            .http
-            .post(&url)
-            .headers(self.default_headers())
-            .send()
+            .send(self.http().post(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
             return Err(err);
         }
 
-        let claim: rugplay_core::RewardClaimResponse = response
+        let claim = response
             .error_for_status()
             .map_err(|e| {
                 error!("Claim request failed: {}", e);
                 Error::ApiError(e.to_string())
-            })?
-            .json()
-            .await
-            .map_err(|e| {
-                error!("Failed to parse claim response: {}", e);
-                Error::InvalidData(e.to_string())
             })?;
+        let claim: rugplay_core::RewardClaimResponse = self.read_json(claim, "claim response").await?;
 
         debug!("Daily reward claimed: ${}", claim.reward_amount);
         Ok(claim)
@@ -585,14 +932,11 @@ impl RugplayClient {
     /// stats, recent transactions, and created coins.
     #[instrument(skip(self), fields(user_id))]
     pub async fn get_user_profile(&self, user_id: &str) -> Result<UserPublicProfileResponse> {
-        let url = format!("{}/user/{}", API_BASE, user_id);
+        let url = format!("{}/user/{}", self.api_base, user_id);
         debug!("Fetching public profile for user: {}", user_id);
 
         let resp = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await
             .map_err(|e| {
                 error!("User profile request failed: {}", e);
@@ -609,10 +953,7 @@ impl RugplayClient {
             )));
         }
 
-        let profile: UserPublicProfileResponse = resp.json().await.map_err(|e| {
-            error!("Failed to parse user profile response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let profile: UserPublicProfileResponse = self.read_json(resp, "user profile response").await?;
 
         debug!("Fetched profile for user: {} ({})", profile.profile.username, user_id);
         Ok(profile)
@@ -621,14 +962,11 @@ impl RugplayClient {
     /// Get the platform leaderboard
     #[instrument(skip(self))]
     pub async fn get_leaderboard(&self) -> Result<LeaderboardResponse> {
-        let url = format!("{}/leaderboard", API_BASE);
+        let url = format!("{}/leaderboard", self.api_base);
         debug!("Fetching leaderboard");
 
         let resp = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await
             .map_err(|e| {
                 error!("Leaderboard request failed: {}", e);
@@ -643,10 +981,7 @@ impl RugplayClient {
             )));
         }
 
-        let leaderboard: LeaderboardResponse = resp.json().await.map_err(|e| {
-            error!("Failed to parse leaderboard response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let leaderboard: LeaderboardResponse = self.read_json(resp, "leaderboard response").await?;
 
         debug!("Leaderboard fetched: {} rugpullers, {} losers, {} cash kings, {} paper millionaires",
             leaderboard.top_rugpullers.len(),
@@ -677,14 +1012,11 @@ impl RugplayClient {
     /// Get comments for a coin
     #[instrument(skip(self))]
     pub async fn get_coin_comments(&self, symbol: &str) -> Result<rugplay_core::CoinCommentsResponse> {
-        let url = format!("{}/coin/{}/comments", API_BASE, symbol);
+        let url = format!("{}/coin/{}/comments", self.api_base, symbol);
         debug!("Fetching comments for {}", symbol);
 
         let response = self
-            .http
-            .get(&url)
-            .headers(self.default_headers())
-            .send()
+            .send_with_retry(self.http().get(&url).headers(self.default_headers()))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -696,10 +1028,7 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let data: rugplay_core::CoinCommentsResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse comments response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let data: rugplay_core::CoinCommentsResponse = self.read_json(response, "comments response").await?;
 
         debug!("Fetched {} comments for {}", data.comments.len(), symbol);
         Ok(data)
@@ -708,17 +1037,13 @@ impl RugplayClient {
     /// Post a comment on a coin
     #[instrument(skip(self))]
     pub async fn post_coin_comment(&self, symbol: &str, content: &str) -> Result<rugplay_core::CoinComment> {
-        let url = format!("{}/coin/{}/comments", API_BASE, symbol);
+        let url = format!("{}/coin/{}/comments", self.api_base, symbol);
         debug!("Posting comment on {}", symbol);
 
         let body = serde_json::json!({ "content": content });
 
         let response = self
-            .http
-            .post(&url)
-            .headers(self.default_headers())
-            .json(&body)
-            .send()
+            .send(self.http().post(&url).headers(self.default_headers()).json(&body))
             .await?;
 
         if let Some(err) = Self::check_auth_error(&response) {
@@ -730,12 +1055,313 @@ impl RugplayClient {
             Error::ApiError(e.to_string())
         })?;
 
-        let data: rugplay_core::PostCommentResponse = response.json().await.map_err(|e| {
-            error!("Failed to parse post comment response: {}", e);
-            Error::InvalidData(e.to_string())
-        })?;
+        let data: rugplay_core::PostCommentResponse = self.read_json(response, "post comment response").await?;
 
         debug!("Comment posted on {} by user {}", symbol, data.comment.user_username);
         Ok(data.comment)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_core::TradeType;
+    use tokio::net::TcpListener;
+    use wiremock::matchers::{any, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// A server expecting exactly one request, answered with `status` and `body`
+    async fn mock_server(status: u16, body: impl Into<String>) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(any())
+            .respond_with(ResponseTemplate::new(status).set_body_raw(body.into(), "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    }
+
+    /// The API root of a mock server
+    fn api_root(server: &MockServer) -> String {
+        format!("{}/api", server.uri())
+    }
+
+    fn buy(amount: f64) -> TradeRequest {
+        TradeRequest { trade_type: TradeType::Buy, amount }
+    }
+
+    fn session_body(expires_at: &str) -> String {
+        format!(
+            r#"{{"session":{{"expiresAt":"{}","token":"t","userId":"u1","id":"s1"}},"user":{{"id":"u1","name":"Alice","username":"alice","email":"a@example.com","emailVerified":true,"baseCurrencyBalance":"100"}}}}"#,
+            expires_at
+        )
+    }
+
+    #[tokio::test]
+    async fn test_validate_token_valid_invalid_expired() {
+        let server = mock_server(200, session_body("2099-01-01T00:00:00Z")).await;
+        let api = api_root(&server);
+        let result = RugplayClient::new("good").with_api_base(&api).validate_token().await;
+        assert!(matches!(result, TokenValidation::Valid { ref username, .. } if username == "alice"), "{:?}", result);
+
+        // Unknown tokens get a null session back
+        let server = mock_server(200, "null").await;
+        let api = api_root(&server);
+        let result = RugplayClient::new("bad").with_api_base(&api).validate_token().await;
+        assert!(matches!(result, TokenValidation::Invalid { .. }), "{:?}", result);
+
+        let server = mock_server(401, "{}").await;
+        let api = api_root(&server);
+        let result = RugplayClient::new("old").with_api_base(&api).validate_token().await;
+        assert_eq!(result, TokenValidation::Expired);
+
+        let server = mock_server(200, session_body("2000-01-01T00:00:00Z")).await;
+        let api = api_root(&server);
+        let result = RugplayClient::new("old").with_api_base(&api).validate_token().await;
+        assert_eq!(result, TokenValidation::Expired);
+
+        // Nothing listening: offline rather than invalid
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let result = RugplayClient::new("good")
+            .with_api_base(&format!("http://{}/api", addr))
+            .validate_token()
+            .await;
+        assert!(matches!(result, TokenValidation::Unreachable { .. }), "{:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_bad_prices_are_rejected_at_the_boundary() {
+        let coin = r#"{"coin":{"id":1,"symbol":"ZERO","name":"Zero","currentPrice":0,"marketCap":0,"poolCoinAmount":0,"poolBaseCurrencyAmount":0}}"#;
+        let server = mock_server(200, coin).await;
+        let api = api_root(&server);
+        let err = RugplayClient::new("t").with_api_base(&api).get_coin("ZERO").await.unwrap_err();
+        assert!(matches!(err, Error::InvalidData(_)), "{:?}", err);
+
+        let portfolio = r#"{"baseCurrencyBalance":50,"totalCoinValue":10,"totalValue":60,"coinHoldings":[
+            {"symbol":"GOOD","quantity":5,"currentPrice":2,"value":10},
+            {"symbol":"BAD","quantity":5,"currentPrice":-1,"value":-5}]}"#;
+        let server = mock_server(200, portfolio).await;
+        let api = api_root(&server);
+        let portfolio = RugplayClient::new("t").with_api_base(&api).get_portfolio().await.unwrap();
+        assert_eq!(portfolio.coin_holdings.len(), 1);
+        assert_eq!(portfolio.coin_holdings[0].symbol, "GOOD");
+
+        let trades = r#"{"trades":[
+            {"type":"SELL","username":"w","amount":10,"coinSymbol":"A","coinName":"A","totalValue":100,"price":10,"timestamp":0,"userId":"1"},
+            {"type":"SELL","username":"w","amount":10,"coinSymbol":"B","coinName":"B","totalValue":100,"price":0,"timestamp":0,"userId":"1"}]}"#;
+        let server = mock_server(200, trades).await;
+        let api = api_root(&server);
+        let trades = RugplayClient::new("t").with_api_base(&api).get_recent_trades(10).await.unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].coin_symbol, "A");
+    }
+
+    #[tokio::test]
+    async fn test_stale_cached_coin_is_refetched_when_freshness_required() {
+        let coin = |price: f64| {
+            format!(
+                r#"{{"coin":{{"id":1,"symbol":"FRSH","name":"Fresh","currentPrice":{},"marketCap":1,"poolCoinAmount":1,"poolBaseCurrencyAmount":1}}}}"#,
+                price
+            )
+        };
+        let cache = Arc::new(CoinCache::with_capacity(std::time::Duration::from_secs(60), 10));
+
+        let server = mock_server(200, coin(1.0)).await;
+        let api = api_root(&server);
+        let client = RugplayClient::new_with_cache("t", cache.clone(), RetryConfig::default()).with_api_base(&api);
+        let first = client.get_coin("FRSH").await.unwrap();
+        assert_eq!(first.current_price, 1.0);
+        assert!(first.age(chrono::Utc::now()).unwrap() < std::time::Duration::from_secs(5));
+
+        // Within the TTL and a relaxed policy, the cached quote is reused (the server expects one request)
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        let cached = client.get_coin_within("FRSH", std::time::Duration::from_secs(10)).await.unwrap();
+        assert_eq!(cached.current_price, 1.0);
+        assert_eq!(cached.fetched_at, first.fetched_at);
+
+        // A strict policy treats the same entry as stale and refetches
+        let server = mock_server(200, coin(2.0)).await;
+        let api = api_root(&server);
+        let client = RugplayClient::new_with_cache("t", cache.clone(), RetryConfig::default()).with_api_base(&api);
+        let fresh = client.get_coin_within("FRSH", std::time::Duration::from_millis(10)).await.unwrap();
+        assert_eq!(fresh.current_price, 2.0);
+        assert!(fresh.fetched_at > first.fetched_at);
+        assert_eq!(cache.get("FRSH").unwrap().current_price, 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_trade_drops_the_cached_coin() {
+        let cached = |symbol: &str| {
+            serde_json::from_str::<CoinDetails>(&format!(
+                r#"{{"id":1,"symbol":"{}","name":"Moved","currentPrice":1.0,"marketCap":1,"poolCoinAmount":1,"poolBaseCurrencyAmount":1}}"#,
+                symbol
+            ))
+            .unwrap()
+        };
+        let cache = Arc::new(CoinCache::with_capacity(std::time::Duration::from_secs(60), 10));
+        cache.insert(cached("FILL"));
+        cache.insert(cached("FAIL"));
+        cache.insert(cached("KEEP"));
+
+        let server = mock_server(200, r#"{"success":true,"type":"BUY","newPrice":1.5,"priceImpact":0.5}"#).await;
+        let api = api_root(&server);
+        let client = RugplayClient::new_with_cache("t", cache.clone(), RetryConfig::default()).with_api_base(&api);
+        assert_eq!(client.trade("FILL", buy(50.0)).await.unwrap().new_price, Some(1.5));
+        assert!(cache.get("FILL").is_none());
+
+        // A server error may still have filled, so the quote is dropped too
+        let server = mock_server(502, String::new()).await;
+        let api = api_root(&server);
+        let client = RugplayClient::new_with_cache("t", cache.clone(), RetryConfig::default()).with_api_base(&api);
+        assert!(client.trade("FAIL", buy(50.0)).await.is_err());
+        assert!(cache.get("FAIL").is_none());
+
+        assert!(cache.get("KEEP").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_trade_error_surfaces_json_error_field() {
+        let server = mock_server(400, r#"{"error":"Insufficient balance","code":"BALANCE"}"#).await;
+        let api = api_root(&server);
+        let client = RugplayClient::new("secret-token").with_api_base(&api);
+
+        let err = client.trade("TEST", buy(50.0)).await.unwrap_err().to_string();
+        assert!(err.contains("400"), "{}", err);
+        assert!(err.contains("Insufficient balance"), "{}", err);
+        assert!(!err.contains("BALANCE"), "only the error field should be kept: {}", err);
+    }
+
+    #[tokio::test]
+    async fn test_trade_error_body_is_redacted_and_truncated() {
+        let body = format!("upstream echoed token secret-token {}", "x".repeat(1000));
+        let server = mock_server(500, body).await;
+        let api = api_root(&server);
+        let client = RugplayClient::new("secret-token").with_api_base(&api);
+
+        let err = client.trade("TEST", buy(50.0)).await.unwrap_err().to_string();
+        assert!(err.contains("[REDACTED]"), "{}", err);
+        assert!(!err.contains("secret-token"), "{}", err);
+        assert!(err.chars().count() < MAX_ERROR_BODY_CHARS + 50, "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_connection_error_burst_rebuilds_client() {
+        // Nothing listening: every request is a connect error
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let api = format!("http://{}/api", addr);
+
+        let policy = ReconnectPolicy { enabled: true, failure_threshold: 3 };
+        let client = RugplayClient::new("t").with_api_base(&api).with_reconnect_policy(policy);
+        for _ in 0..2 {
+            assert!(matches!(client.get_session().await, Err(Error::NetworkError(_))));
+        }
+        assert_eq!(client.reconnect_count(), 0);
+        assert!(client.get_session().await.is_err());
+        assert_eq!(client.reconnect_count(), 1);
+
+        // The count starts over after a rebuild, and a success resets it
+        for _ in 0..2 {
+            let _ = client.get_session().await;
+        }
+        assert_eq!(client.reconnect_count(), 1);
+        let server = mock_server(200, session_body("2099-01-01T00:00:00Z")).await;
+        let api = api_root(&server);
+        let client = client.with_api_base(&api);
+        client.get_session().await.unwrap();
+        assert_eq!(client.health.failures(), 0);
+
+        let disabled = ReconnectPolicy { enabled: false, ..policy };
+        let client = RugplayClient::new("t")
+            .with_api_base(&format!("http://{}/api", addr))
+            .with_reconnect_policy(disabled);
+        for _ in 0..5 {
+            let _ = client.get_session().await;
+        }
+        assert_eq!(client.reconnect_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_connection_failures_add_up_across_clients_sharing_health() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let api = format!("http://{}/api", addr);
+
+        // One short-lived client per request, as the app's tasks build them
+        let health = Arc::new(ConnectionHealth::default());
+        let client = || RugplayClient::new("t").with_api_base(&api).with_connection_health(health.clone());
+        for _ in 0..2 {
+            assert!(client().get_session().await.is_err());
+        }
+        assert_eq!((health.failures(), health.reconnect_count()), (2, 0));
+        let long_lived = client();
+        assert!(client().get_session().await.is_err());
+        assert_eq!((health.failures(), health.reconnect_count()), (0, 1));
+
+        // A client built before the rebuild drops its pool on its next request
+        assert_eq!(long_lived.generation.load(Ordering::Relaxed), 0);
+        let _ = long_lived.get_session().await;
+        assert_eq!(long_lived.generation.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_backoff_doubles_with_jitter_up_to_the_cap() {
+        let retry = RetryConfig {
+            max_retries: 5,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(1_000),
+        };
+        for (attempt, full) in [(0, 100), (1, 200), (2, 400), (3, 800), (4, 1_000), (30, 1_000)] {
+            let wait = retry.backoff(attempt);
+            assert!(wait >= Duration::from_millis(full / 2), "{}: {:?}", attempt, wait);
+            assert!(wait <= Duration::from_millis(full), "{}: {:?}", attempt, wait);
+        }
+        assert_eq!(RetryConfig::default().max_retries, 3);
+        assert_eq!(RugplayClient::new("t").retry, RetryConfig::disabled());
+    }
+
+    #[test]
+    fn test_production_is_the_default_base() {
+        let client = RugplayClient::new("t");
+        assert_eq!(client.base_url(), DEFAULT_BASE_URL);
+        assert_eq!(client.api_base, "https://rugplay.com/api");
+
+        let err = RugplayClient::with_base_url("t", "not a url").err().unwrap();
+        assert!(matches!(err, Error::InvalidData(_)), "{:?}", err);
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_to_configured_base() {
+        let server = MockServer::start().await;
+        let base = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/api/auth/get-session"))
+            .and(header("origin", base.as_str()))
+            .and(header("referer", format!("{}/", base).as_str()))
+            .and(header("cookie", "__Secure-better-auth.session_token=staging-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(session_body("2099-01-01T00:00:00Z"), "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let client = RugplayClient::with_base_url("staging-token", &format!("{}/", base)).unwrap();
+        assert_eq!(client.base_url(), base);
+        assert_eq!(client.get_session().await.unwrap().username, "alice");
+
+        let coin = r#"{"coin":{"id":1,"symbol":"STG","name":"Staging","currentPrice":2,"marketCap":1,"poolCoinAmount":1,"poolBaseCurrencyAmount":1}}"#;
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/coin/STG"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(coin, "application/json"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        let cache = Arc::new(CoinCache::with_capacity(std::time::Duration::from_secs(60), 10));
+        let client = RugplayClient::with_base_url("t", &server.uri()).unwrap().with_cache(cache.clone());
+        assert_eq!(client.get_coin("STG").await.unwrap().current_price, 2.0);
+        assert!(cache.get("STG").is_some());
+    }
+}
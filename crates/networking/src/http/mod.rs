@@ -2,4 +2,5 @@
 
 mod client;
 
-pub use client::RugplayClient;
+pub use client::{ConnectionHealth, ReconnectPolicy, RetryConfig, RugplayClient, DEFAULT_BASE_URL};
+pub(crate) use client::USER_AGENT_VALUE;
@@ -4,4 +4,4 @@ pub mod api;
 pub mod http;
 pub mod websocket;
 
-pub use http::RugplayClient;
+pub use http::{ConnectionHealth, ReconnectPolicy, RetryConfig, RugplayClient};
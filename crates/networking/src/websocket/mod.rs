@@ -1,8 +1,42 @@
-//! WebSocket connection for real-time updates
-//! 
-//! TODO: Implement in Phase 2
+//! WebSocket live trade feed
+//!
+//! Connects to Rugplay's live trade socket with the session cookie,
+//! subscribes to every trade and republishes each one as a [`RecentTrade`]
+//! on a broadcast channel. Dropped connections are retried with exponential
+//! backoff while the manager sits in [`ConnectionState::Reconnecting`].
+//! The server replays its latest trades to every new connection, so trades
+//! already published are filtered out by a bounded [`TradeDedup`].
 
-use rugplay_core::Result;
+use crate::http::{DEFAULT_BASE_URL, USER_AGENT_VALUE};
+use futures_util::{SinkExt, StreamExt};
+use rugplay_core::{Error, RecentTrade, Result};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::{HeaderValue, StatusCode};
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+use tracing::{debug, info, warn};
+
+/// Production live trade socket
+pub const DEFAULT_WS_URL: &str = "wss://ws.rugplay.com";
+
+/// Trades buffered per subscriber before the slowest one starts missing them
+pub const TRADE_CHANNEL_CAPACITY: usize = 512;
+
+/// Trade keys remembered to drop replays after a reconnect
+const DEDUP_CAPACITY: usize = 2_000;
+
+/// Max time for the handshake and subscription of one connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Channel carrying every trade on the site
+const ALL_TRADES_CHANNEL: &str = "trades:all";
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
 
 /// WebSocket connection state
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,34 +47,191 @@ pub enum ConnectionState {
     Reconnecting,
 }
 
-/// WebSocket manager placeholder
+/// Delay between reconnect attempts: `initial`, doubling up to `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectBackoff {
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self { initial: Duration::from_secs(1), max: Duration::from_secs(60) }
+    }
+}
+
+impl ReconnectBackoff {
+    /// Delay before the `attempt`-th consecutive reconnect (1-based)
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let doublings = attempt.saturating_sub(1).min(16);
+        self.initial.saturating_mul(1 << doublings).min(self.max)
+    }
+}
+
+/// Bounded memory of published trades
+#[derive(Debug)]
+pub struct TradeDedup {
+    capacity: usize,
+    order: VecDeque<String>,
+    seen: HashSet<String>,
+}
+
+impl TradeDedup {
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity: capacity.max(1), order: VecDeque::new(), seen: HashSet::new() }
+    }
+
+    /// Identifies a trade across connections; the socket sends no trade id
+    pub fn key(trade: &RecentTrade) -> String {
+        format!(
+            "{}:{}:{}:{}:{}",
+            trade.user_id,
+            trade.coin_symbol,
+            trade.timestamp,
+            trade.trade_type.to_uppercase(),
+            trade.amount.to_bits()
+        )
+    }
+
+    /// Remember `trade`; false if it was already seen
+    pub fn insert(&mut self, trade: &RecentTrade) -> bool {
+        let key = Self::key(trade);
+        if !self.seen.insert(key.clone()) {
+            return false;
+        }
+        self.order.push_back(key);
+        if self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.seen.remove(&oldest);
+            }
+        }
+        true
+    }
+}
+
+/// What a text frame from the socket asks of us
+#[derive(Debug)]
+enum SocketMessage {
+    Trade(Box<RecentTrade>),
+    Ping,
+    Other,
+}
+
+/// Decode one text frame. Trades arrive as `{"type": "all-trades" | "live-trade", "data": {...}}`
+/// with the same fields as `/api/trades/recent`.
+fn parse_message(text: &str) -> SocketMessage {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return SocketMessage::Other;
+    };
+    match value.get("type").and_then(|t| t.as_str()) {
+        Some("all-trades") | Some("live-trade") => value
+            .get("data")
+            .and_then(|data| serde_json::from_value::<RecentTrade>(data.clone()).ok())
+            .filter(RecentTrade::has_valid_numbers)
+            .map_or(SocketMessage::Other, |trade| SocketMessage::Trade(Box::new(trade))),
+        Some("ping") => SocketMessage::Ping,
+        _ => SocketMessage::Other,
+    }
+}
+
+/// Decode a trade frame from the live socket, if `text` is one
+pub fn decode_trade_message(text: &str) -> Option<RecentTrade> {
+    match parse_message(text) {
+        SocketMessage::Trade(trade) => Some(*trade),
+        _ => None,
+    }
+}
+
+/// Live trade socket for one session
 pub struct WebSocketManager {
-    state: ConnectionState,
+    url: String,
+    backoff: ReconnectBackoff,
+    state: watch::Sender<ConnectionState>,
+    trades: broadcast::Sender<RecentTrade>,
+    shutdown: Option<watch::Sender<bool>>,
+    task: Option<JoinHandle<()>>,
 }
 
 impl WebSocketManager {
     pub fn new() -> Self {
+        Self::with_url(DEFAULT_WS_URL)
+    }
+
+    /// Manager for a different socket (mock or staging server)
+    pub fn with_url(url: &str) -> Self {
+        let (state, _) = watch::channel(ConnectionState::Disconnected);
+        let (trades, _) = broadcast::channel(TRADE_CHANNEL_CAPACITY);
         Self {
-            state: ConnectionState::Disconnected,
+            url: url.to_string(),
+            backoff: ReconnectBackoff::default(),
+            state,
+            trades,
+            shutdown: None,
+            task: None,
         }
     }
 
+    /// Replace the reconnect schedule
+    pub fn with_backoff(mut self, backoff: ReconnectBackoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
     pub fn state(&self) -> ConnectionState {
-        self.state
+        *self.state.borrow()
+    }
+
+    /// Follow connection state changes
+    pub fn watch_state(&self) -> watch::Receiver<ConnectionState> {
+        self.state.subscribe()
     }
 
-    /// Connect to WebSocket server
-    /// 
-    /// TODO: Implement actual WebSocket connection
-    pub async fn connect(&mut self, _session_token: &str) -> Result<()> {
-        // Placeholder for Phase 2
-        self.state = ConnectionState::Connected;
+    /// Receive every new trade, each once, across reconnects
+    pub fn subscribe(&self) -> broadcast::Receiver<RecentTrade> {
+        self.trades.subscribe()
+    }
+
+    /// Connect to the WebSocket server, authenticated with the session cookie.
+    ///
+    /// Fails if the first connection can't be made; once connected, drops
+    /// are retried in the background until [`Self::disconnect`].
+    pub async fn connect(&mut self, session_token: &str) -> Result<()> {
+        self.disconnect().await?;
+        self.state.send_replace(ConnectionState::Connecting);
+
+        let socket = match open_socket(&self.url, session_token).await {
+            Ok(socket) => socket,
+            Err(e) => {
+                self.state.send_replace(ConnectionState::Disconnected);
+                return Err(e);
+            }
+        };
+        self.state.send_replace(ConnectionState::Connected);
+        info!("Live trade socket connected");
+
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        self.shutdown = Some(shutdown_tx);
+        self.task = Some(tokio::spawn(run_socket(
+            socket,
+            self.url.clone(),
+            session_token.to_string(),
+            self.backoff,
+            self.state.clone(),
+            self.trades.clone(),
+            shutdown_rx,
+        )));
         Ok(())
     }
 
     /// Disconnect from WebSocket server
     pub async fn disconnect(&mut self) -> Result<()> {
-        self.state = ConnectionState::Disconnected;
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(true);
+        }
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+        self.state.send_replace(ConnectionState::Disconnected);
         Ok(())
     }
 }
@@ -50,3 +241,207 @@ impl Default for WebSocketManager {
         Self::new()
     }
 }
+
+impl Drop for WebSocketManager {
+    fn drop(&mut self) {
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+    }
+}
+
+/// Open a socket with the session cookie and subscribe to all trades
+async fn open_socket(url: &str, session_token: &str) -> Result<Socket> {
+    tokio::time::timeout(CONNECT_TIMEOUT, handshake(url, session_token))
+        .await
+        .map_err(|_| Error::NetworkError("WebSocket connect timed out".to_string()))?
+}
+
+async fn handshake(url: &str, session_token: &str) -> Result<Socket> {
+    let mut request = url
+        .into_client_request()
+        .map_err(|e| Error::NetworkError(format!("Invalid WebSocket URL: {}", e)))?;
+    let headers = request.headers_mut();
+    let cookie = HeaderValue::from_str(&format!("__Secure-better-auth.session_token={}", session_token))
+        .map_err(|_| Error::AuthenticationError("Session token is not a valid header value".to_string()))?;
+    headers.insert("Cookie", cookie);
+    headers.insert("Origin", HeaderValue::from_static(DEFAULT_BASE_URL));
+    headers.insert("User-Agent", HeaderValue::from_static(USER_AGENT_VALUE));
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.map_err(|e| match e {
+        tokio_tungstenite::tungstenite::Error::Http(response)
+            if matches!(response.status(), StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) =>
+        {
+            Error::AuthenticationError(format!("Live trade socket refused the session ({})", response.status()))
+        }
+        e => Error::NetworkError(format!("WebSocket connect failed: {}", e)),
+    })?;
+
+    let subscribe = serde_json::json!({ "type": "subscribe", "channel": ALL_TRADES_CHANNEL });
+    socket
+        .send(Message::text(subscribe.to_string()))
+        .await
+        .map_err(|e| Error::NetworkError(format!("WebSocket subscribe failed: {}", e)))?;
+    Ok(socket)
+}
+
+/// Read trades until shutdown, reconnecting whenever the socket drops
+async fn run_socket(
+    mut socket: Socket,
+    url: String,
+    session_token: String,
+    backoff: ReconnectBackoff,
+    state: watch::Sender<ConnectionState>,
+    trades: broadcast::Sender<RecentTrade>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut dedup = TradeDedup::new(DEDUP_CAPACITY);
+    loop {
+        let closed = read_until_closed(&mut socket, &mut dedup, &trades, &mut shutdown).await;
+        if *shutdown.borrow() {
+            let _ = socket.close(None).await;
+            return;
+        }
+        warn!("Live trade socket dropped: {}", closed);
+
+        let mut attempt: u32 = 0;
+        socket = loop {
+            attempt += 1;
+            state.send_replace(ConnectionState::Reconnecting);
+            let delay = backoff.delay(attempt);
+            debug!("Reconnecting live trade socket in {:?} (attempt {})", delay, attempt);
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.changed() => return,
+            }
+            let opened = tokio::select! {
+                opened = open_socket(&url, &session_token) => opened,
+                _ = shutdown.changed() => return,
+            };
+            match opened {
+                Ok(socket) => break socket,
+                Err(e) => warn!("Live trade socket reconnect failed: {}", e),
+            }
+        };
+        state.send_replace(ConnectionState::Connected);
+        info!("Live trade socket reconnected after {} attempt(s)", attempt);
+    }
+}
+
+/// Publish trades from `socket` until it closes or shutdown is requested.
+/// Returns why the read loop ended.
+async fn read_until_closed(
+    socket: &mut Socket,
+    dedup: &mut TradeDedup,
+    trades: &broadcast::Sender<RecentTrade>,
+    shutdown: &mut watch::Receiver<bool>,
+) -> String {
+    loop {
+        let frame = tokio::select! {
+            frame = socket.next() => frame,
+            _ = shutdown.changed() => return "shutdown".to_string(),
+        };
+        let text = match frame {
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(Message::Close(frame))) => return format!("closed by server ({:?})", frame),
+            Some(Ok(_)) => continue,
+            Some(Err(e)) => return e.to_string(),
+            None => return "stream ended".to_string(),
+        };
+        match parse_message(&text) {
+            SocketMessage::Trade(trade) => {
+                if dedup.insert(&trade) {
+                    // No subscribers is fine; trades aren't queued for later ones
+                    let _ = trades.send(*trade);
+                }
+            }
+            SocketMessage::Ping => {
+                let pong = serde_json::json!({ "type": "pong" }).to_string();
+                if let Err(e) = socket.send(Message::text(pong)).await {
+                    return e.to_string();
+                }
+            }
+            SocketMessage::Other => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+    fn trade_frame(user: &str, symbol: &str, timestamp: i64) -> Message {
+        let frame = serde_json::json!({
+            "type": "all-trades",
+            "data": {
+                "type": "BUY",
+                "username": user,
+                "amount": 10.0,
+                "coinSymbol": symbol,
+                "coinName": symbol,
+                "totalValue": 25.0,
+                "price": 2.5,
+                "timestamp": timestamp,
+                "userId": user,
+            }
+        });
+        Message::text(frame.to_string())
+    }
+
+    #[allow(clippy::result_large_err)] // signature required by the handshake callback
+    fn check_cookie(request: &Request, response: Response) -> std::result::Result<Response, ErrorResponse> {
+        let cookie = request.headers().get("Cookie").and_then(|c| c.to_str().ok()).unwrap_or_default();
+        assert_eq!(cookie, "__Secure-better-auth.session_token=tok");
+        Ok(response)
+    }
+
+    /// Accept one connection, check the cookie, send `frames` and drop it
+    async fn serve_once(listener: &TcpListener, frames: Vec<Message>) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut socket = tokio_tungstenite::accept_hdr_async(stream, check_cookie).await.unwrap();
+        let subscribe = socket.next().await.unwrap().unwrap();
+        assert!(subscribe.to_text().unwrap().contains(ALL_TRADES_CHANNEL));
+        for frame in frames {
+            socket.send(frame).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_replay_is_deduplicated() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let backoff = ReconnectBackoff { initial: Duration::from_millis(10), max: Duration::from_millis(40) };
+        let mut manager = WebSocketManager::with_url(&url).with_backoff(backoff);
+        let mut trades = manager.subscribe();
+        let mut state = manager.watch_state();
+
+        let server = async {
+            serve_once(&listener, vec![trade_frame("a", "AAA", 1), trade_frame("b", "BBB", 2)]).await;
+            // Reconnect replays both trades before a new one
+            serve_once(
+                &listener,
+                vec![trade_frame("a", "AAA", 1), trade_frame("b", "BBB", 2), trade_frame("c", "CCC", 3)],
+            )
+            .await;
+        };
+        let (_, connected) = tokio::join!(server, manager.connect("tok"));
+        connected.unwrap();
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            received.push(trades.recv().await.unwrap().coin_symbol);
+        }
+        assert_eq!(received, vec!["AAA", "BBB", "CCC"]);
+        assert!(state.wait_for(|s| *s == ConnectionState::Reconnecting).await.is_ok());
+        assert!(trades.try_recv().is_err());
+
+        manager.disconnect().await.unwrap();
+        assert_eq!(manager.state(), ConnectionState::Disconnected);
+        assert_eq!(backoff.delay(1), Duration::from_millis(10));
+        assert_eq!(backoff.delay(2), Duration::from_millis(20));
+        assert_eq!(backoff.delay(10), Duration::from_millis(40));
+        assert!(decode_trade_message(r#"{"type":"ping"}"#).is_none());
+    }
+}
@@ -0,0 +1,348 @@
+//! RugplayClient against a mock Rugplay server
+//!
+//! Each test starts a `wiremock` server, points a client at it with
+//! `RugplayClient::with_base_url` and stubs only the endpoints it needs.
+
+use rugplay_core::{Error, TradeRequest, TradeType};
+use rugplay_networking::{RetryConfig, RugplayClient};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TOKEN: &str = "test-session-token";
+
+async fn setup() -> (MockServer, RugplayClient) {
+    let server = MockServer::start().await;
+    let client = RugplayClient::with_base_url(TOKEN, &server.uri()).unwrap();
+    (server, client)
+}
+
+async fn stub(server: &MockServer, verb: &str, endpoint: &str, response: ResponseTemplate) {
+    Mock::given(method(verb))
+        .and(path(endpoint))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
+
+fn session() -> serde_json::Value {
+    json!({
+        "session": {"expiresAt": "2099-01-01T00:00:00Z", "token": "t", "userId": "u1", "id": "s1"},
+        "user": {
+            "id": "u1", "name": "Alice", "username": "alice", "email": "a@example.com",
+            "emailVerified": true, "baseCurrencyBalance": "1234.5"
+        }
+    })
+}
+
+fn coin(symbol: &str, price: f64) -> serde_json::Value {
+    json!({
+        "coin": {
+            "id": 1, "symbol": symbol, "name": "Test Coin", "currentPrice": price,
+            "marketCap": 1000.0, "poolCoinAmount": 500.0, "poolBaseCurrencyAmount": 250.0
+        },
+        "candlestickData": []
+    })
+}
+
+fn buy(amount: f64) -> TradeRequest {
+    TradeRequest { trade_type: TradeType::Buy, amount }
+}
+
+#[tokio::test]
+async fn test_session_happy_path_sends_session_cookie() {
+    let (server, client) = setup().await;
+    Mock::given(method("GET"))
+        .and(path("/api/auth/get-session"))
+        .and(header("cookie", format!("__Secure-better-auth.session_token={}", TOKEN).as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(session()))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let profile = client.get_session().await.unwrap();
+    assert_eq!(profile.username, "alice");
+    assert_eq!(profile.balance, 1234.5);
+}
+
+#[tokio::test]
+async fn test_coin_market_and_portfolio_happy_paths() {
+    let (server, client) = setup().await;
+    stub(&server, "GET", "/api/coin/TEST", ResponseTemplate::new(200).set_body_json(coin("TEST", 0.5))).await;
+    Mock::given(method("GET"))
+        .and(path("/api/market"))
+        .and(query_param("page", "2"))
+        .and(query_param("sortBy", "marketCap"))
+        .and(query_param("search", "dog coin"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "coins": [{"symbol": "DOG", "name": "Dog Coin", "currentPrice": 0.01, "marketCap": 5000.0}],
+            "total": 1, "page": 2, "limit": 10, "totalPages": 1
+        })))
+        .mount(&server)
+        .await;
+    stub(
+        &server,
+        "GET",
+        "/api/portfolio/total",
+        ResponseTemplate::new(200).set_body_json(json!({
+            "baseCurrencyBalance": 100.0, "totalCoinValue": 20.0, "totalValue": 120.0,
+            "coinHoldings": [{"symbol": "TEST", "quantity": 40.0, "currentPrice": 0.5, "value": 20.0}]
+        })),
+    )
+    .await;
+
+    let details = client.get_coin("TEST").await.unwrap();
+    assert_eq!(details.symbol, "TEST");
+    assert_eq!(details.current_price, 0.5);
+    assert!(details.fetched_at.is_some());
+
+    let market = client
+        .get_market(2, 10, "marketCap", "desc", Some("dog coin"))
+        .await
+        .unwrap();
+    assert_eq!(market.coins.len(), 1);
+    assert_eq!(market.coins[0].symbol, "DOG");
+    assert_eq!(market.total_pages, Some(1));
+
+    let portfolio = client.get_portfolio().await.unwrap();
+    assert_eq!(portfolio.total_value, 120.0);
+    assert_eq!(portfolio.coin_holdings.len(), 1);
+    assert_eq!(portfolio.coin_holdings[0].quantity, 40.0);
+}
+
+#[tokio::test]
+async fn test_trade_posts_request_body() {
+    let (server, client) = setup().await;
+    Mock::given(method("POST"))
+        .and(path("/api/coin/TEST/trade"))
+        .and(body_json(json!({"type": "BUY", "amount": 25.0})))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true, "type": "BUY", "coinsBought": 48.5, "totalCost": 25.0,
+            "newPrice": 0.52, "priceImpact": 0.04, "newBalance": 75.0
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let trade = client.trade("TEST", buy(25.0)).await.unwrap();
+    assert_eq!(trade.coins_bought, Some(48.5));
+    assert_eq!(trade.new_price, Some(0.52));
+    assert_eq!(trade.new_balance, 75.0);
+
+    // A 200 that reports failure is still an error
+    server.reset().await;
+    stub(
+        &server,
+        "POST",
+        "/api/coin/TEST/trade",
+        ResponseTemplate::new(200).set_body_json(json!({
+            "success": false, "type": "BUY", "newPrice": 0.5, "priceImpact": 0.0
+        })),
+    )
+    .await;
+    let err = client.trade("TEST", buy(25.0)).await.unwrap_err();
+    assert!(matches!(err, Error::TradeError(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_trade_on_untradable_coin_keeps_the_server_reason() {
+    let (server, client) = setup().await;
+    stub(
+        &server,
+        "POST",
+        "/api/coin/NEW/trade",
+        ResponseTemplate::new(400).set_body_json(json!({"error": "Coin is not yet tradable. Only the creator can trade during the first minute."})),
+    )
+    .await;
+    // The sniper recognises the reason in the message and retries later
+    let err = client.trade("NEW", buy(10.0)).await.unwrap_err();
+    assert!(matches!(err, Error::TradeError(_)), "{:?}", err);
+    assert!(err.to_string().contains("Coin is not yet tradable."), "{}", err);
+}
+
+#[tokio::test]
+async fn test_sub_minimum_trade_is_below_minimum_trade() {
+    let (server, client) = setup().await;
+    stub(
+        &server,
+        "POST",
+        "/api/coin/TEST/trade",
+        ResponseTemplate::new(400).set_body_json(json!({"error": "Minimum trade amount is $1.00"})),
+    )
+    .await;
+    let err = client.trade("TEST", buy(0.25)).await.unwrap_err();
+    match err {
+        Error::BelowMinimumTrade { minimum, ref detail } => {
+            assert_eq!(minimum, 1.0);
+            assert_eq!(detail, "Minimum trade amount is $1.00");
+        }
+        other => panic!("expected BelowMinimumTrade, got {:?}", other),
+    }
+    assert!(err.to_string().starts_with("Trade below server minimum: "), "{}", err);
+
+    // Rejections that merely mention a minimum are other failures
+    for message in ["Insufficient balance for the minimum amount", "Order below minimum liquidity of $5"] {
+        server.reset().await;
+        stub(
+            &server,
+            "POST",
+            "/api/coin/TEST/trade",
+            ResponseTemplate::new(400).set_body_json(json!({"error": message})),
+        )
+        .await;
+        let err = client.trade("TEST", buy(0.25)).await.unwrap_err();
+        assert!(matches!(err, Error::TradeError(_)), "{}: {:?}", message, err);
+    }
+}
+
+#[tokio::test]
+async fn test_auth_errors_are_mapped_on_every_endpoint() {
+    for (status, expect_expired) in [(401, true), (403, false)] {
+        let (server, client) = setup().await;
+        for (verb, endpoint) in [
+            ("GET", "/api/auth/get-session"),
+            ("GET", "/api/coin/TEST"),
+            ("GET", "/api/market"),
+            ("GET", "/api/portfolio/total"),
+            ("POST", "/api/coin/TEST/trade"),
+        ] {
+            stub(&server, verb, endpoint, ResponseTemplate::new(status)).await;
+        }
+
+        let errors = [
+            client.get_session().await.map(|_| ()).unwrap_err(),
+            client.get_coin("TEST").await.map(|_| ()).unwrap_err(),
+            client.get_market(1, 10, "marketCap", "desc", None).await.map(|_| ()).unwrap_err(),
+            client.get_portfolio().await.map(|_| ()).unwrap_err(),
+            client.trade("TEST", buy(1.0)).await.map(|_| ()).unwrap_err(),
+        ];
+        for err in errors {
+            if expect_expired {
+                assert!(matches!(err, Error::TokenExpired), "{}: {:?}", status, err);
+            } else {
+                assert!(matches!(err, Error::AuthenticationError(_)), "{}: {:?}", status, err);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_server_errors_are_api_errors() {
+    let (server, client) = setup().await;
+    stub(&server, "GET", "/api/portfolio/total", ResponseTemplate::new(500)).await;
+    stub(&server, "GET", "/api/coin/TEST", ResponseTemplate::new(404)).await;
+
+    let err = client.get_portfolio().await.unwrap_err();
+    assert!(matches!(err, Error::ApiError(_)), "{:?}", err);
+    let err = client.get_coin("TEST").await.unwrap_err();
+    assert!(matches!(err, Error::ApiError(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_malformed_responses_are_invalid_data() {
+    let (server, client) = setup().await;
+    // Not JSON at all
+    stub(
+        &server,
+        "GET",
+        "/api/auth/get-session",
+        ResponseTemplate::new(200).set_body_string("<html>maintenance</html>"),
+    )
+    .await;
+    // JSON missing required fields
+    stub(
+        &server,
+        "GET",
+        "/api/coin/TEST",
+        ResponseTemplate::new(200).set_body_json(json!({"coin": {"symbol": "TEST"}})),
+    )
+    .await;
+    // Wrong shape: a list where an object is expected
+    stub(&server, "GET", "/api/portfolio/total", ResponseTemplate::new(200).set_body_json(json!([1, 2, 3]))).await;
+    // Truncated trade response
+    stub(
+        &server,
+        "POST",
+        "/api/coin/TEST/trade",
+        ResponseTemplate::new(200).set_body_string(r#"{"success": true, "type": "BUY", "newPr"#),
+    )
+    .await;
+
+    let errors = [
+        client.get_session().await.map(|_| ()).unwrap_err(),
+        client.get_coin("TEST").await.map(|_| ()).unwrap_err(),
+        client.get_portfolio().await.map(|_| ()).unwrap_err(),
+        client.trade("TEST", buy(1.0)).await.map(|_| ()).unwrap_err(),
+    ];
+    for err in errors {
+        assert!(matches!(err, Error::InvalidData(_)), "{:?}", err);
+    }
+}
+
+#[tokio::test]
+async fn test_unreachable_server_is_a_network_error() {
+    // Nothing listening on the port (wiremock pools its servers, so bind one ourselves)
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let err = RugplayClient::with_base_url(TOKEN, &base).unwrap().get_portfolio().await.unwrap_err();
+    assert!(matches!(err, Error::NetworkError(_)), "{:?}", err);
+}
+
+fn fast_retries(max_retries: u32) -> RetryConfig {
+    RetryConfig {
+        max_retries,
+        base_delay: Duration::from_millis(10),
+        max_delay: Duration::from_secs(2),
+    }
+}
+
+#[tokio::test]
+async fn test_rate_limited_read_is_retried_after_retry_after() {
+    let (server, client) = setup().await;
+    let client = client.with_retry_config(fast_retries(3));
+    Mock::given(method("GET"))
+        .and(path("/api/portfolio/total"))
+        .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+        .up_to_n_times(1)
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/portfolio/total"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "baseCurrencyBalance": 50.0, "totalCoinValue": 0.0, "totalValue": 50.0, "coinHoldings": []
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let started = Instant::now();
+    let portfolio = client.get_portfolio().await.unwrap();
+    assert_eq!(portfolio.total_value, 50.0);
+    assert!(started.elapsed() >= Duration::from_secs(1), "Retry-After was not honored");
+
+    // Retries give up after the configured count
+    stub(&server, "GET", "/api/coin/BUSY", ResponseTemplate::new(503)).await;
+    let err = client.with_retry_config(fast_retries(2)).get_coin("BUSY").await.unwrap_err();
+    assert!(matches!(err, Error::ApiError(_)), "{:?}", err);
+    let busy = server.received_requests().await.unwrap();
+    assert_eq!(busy.iter().filter(|r| r.url.path() == "/api/coin/BUSY").count(), 3);
+}
+
+#[tokio::test]
+async fn test_trades_are_never_retried() {
+    let (server, client) = setup().await;
+    let client = client.with_retry_config(fast_retries(3));
+    Mock::given(method("POST"))
+        .and(path("/api/coin/TEST/trade"))
+        .respond_with(ResponseTemplate::new(429))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let err = client.trade("TEST", buy(10.0)).await.unwrap_err();
+    assert!(err.to_string().contains("429"), "{}", err);
+}
@@ -52,6 +52,32 @@ impl CoinCache {
         }
     }
 
+    /// Get a coin only if it was cached no more than `max_age` ago.
+    /// Lets a caller demand fresher data than the cache TTL allows.
+    pub fn get_within(&self, symbol: &str, max_age: Duration) -> Option<CoinDetails> {
+        let cache = self.coins.read().ok()?;
+        let entry = cache.get(symbol)?;
+
+        if entry.is_expired() || entry.inserted_at.elapsed() > max_age {
+            None
+        } else {
+            Some(entry.value.clone())
+        }
+    }
+
+    /// How long until a coin's entry expires; `None` if it isn't cached
+    /// or already has
+    pub fn time_to_expiry(&self, symbol: &str) -> Option<Duration> {
+        self.time_to_expiry_at(symbol, Instant::now())
+    }
+
+    /// [`Self::time_to_expiry`] as of `now`
+    pub fn time_to_expiry_at(&self, symbol: &str, now: Instant) -> Option<Duration> {
+        let cache = self.coins.read().ok()?;
+        let entry = cache.get(symbol)?;
+        entry.ttl.checked_sub(now.saturating_duration_since(entry.inserted_at)).filter(|left| !left.is_zero())
+    }
+
     /// Insert or update a coin in cache.
     /// Evicts expired entries if at capacity.
     pub fn insert(&self, coin: CoinDetails) {
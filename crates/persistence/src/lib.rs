@@ -3,6 +3,8 @@
 pub mod cache;
 pub mod encryption;
 pub mod sqlite;
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support;
 
 pub use encryption::TokenEncryptor;
 pub use encryption::derive_machine_key;
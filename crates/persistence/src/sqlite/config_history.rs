@@ -0,0 +1,92 @@
+//! Config history — what changed each time a module's config was saved
+//!
+//! Settings only keep the current config. Each save also appends the
+//! field-level changes it made, so a change in behavior can be matched to
+//! the edit that caused it.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// One config save
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryRow {
+    pub id: i64,
+    pub module: String,
+    /// JSON array of the changed fields
+    pub changes: String,
+    pub changed_at: Option<String>,
+}
+
+/// Record the changes one save made to `module`'s config
+pub async fn append_config_change(pool: &SqlitePool, module: &str, changes_json: &str) -> Result<i64> {
+    let result = sqlx::query("INSERT INTO config_history (module, changes) VALUES (?1, ?2)")
+        .bind(module)
+        .bind(changes_json)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Number of recorded saves, for one module or all of them
+pub async fn count_config_history(pool: &SqlitePool, module: Option<&str>) -> Result<u64> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM config_history WHERE ?1 IS NULL OR module = ?1")
+        .bind(module)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(count as u64)
+}
+
+/// Recorded saves, newest first
+pub async fn get_config_history(
+    pool: &SqlitePool,
+    module: Option<&str>,
+    offset: u64,
+    limit: u64,
+) -> Result<Vec<ConfigHistoryRow>> {
+    sqlx::query_as::<_, ConfigHistoryRow>(
+        r#"
+        SELECT id, module, changes, changed_at
+        FROM config_history
+        WHERE ?1 IS NULL OR module = ?1
+        ORDER BY id DESC
+        LIMIT ?2 OFFSET ?3
+        "#,
+    )
+    .bind(module)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::Database;
+
+    #[tokio::test]
+    async fn test_config_history_is_newest_first_per_module() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+
+        append_config_change(pool, "sniper", r#"[{"field":"buyAmountUsd"}]"#).await.unwrap();
+        append_config_change(pool, "dipbuyer", r#"[{"field":"preset"}]"#).await.unwrap();
+        append_config_change(pool, "sniper", r#"[{"field":"maxCoinAgeSecs"}]"#).await.unwrap();
+
+        let sniper = get_config_history(pool, Some("sniper"), 0, 10).await.unwrap();
+        assert_eq!(sniper.len(), 2);
+        assert!(sniper[0].changes.contains("maxCoinAgeSecs"));
+        assert_eq!(count_config_history(pool, Some("sniper")).await.unwrap(), 2);
+
+        let all = get_config_history(pool, None, 1, 1).await.unwrap();
+        assert_eq!(all[0].module, "dipbuyer");
+        assert_eq!(count_config_history(pool, None).await.unwrap(), 3);
+    }
+}
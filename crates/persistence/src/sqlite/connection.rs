@@ -1,19 +1,98 @@
 //! Database connection and initialization
+//!
+//! # Concurrency model
+//!
+//! Several background loops (sentinel, sniper, mirror, dipbuyer, harvester,
+//! executor) share one connection pool and write settings and logs at the
+//! same time. The file database therefore runs in WAL mode: readers never
+//! block writers and vice versa, and writers are serialized by SQLite itself.
+//! A writer that finds the lock held waits up to [`BUSY_TIMEOUT`] instead of
+//! failing immediately with "database is locked".
+//!
+//! The pool is sized by [`PoolConfig`]: enough connections for every loop to
+//! hold one while commands still get through, and an acquire timeout so a
+//! stuck holder surfaces as an error instead of hanging callers forever.
 
 use rugplay_core::{Error, Result};
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous};
 use sqlx::SqlitePool;
 use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
+
+/// How long a connection waits for a lock before giving up
+pub const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connection pool sizing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Upper bound on open connections
+    pub max_connections: u32,
+    /// How long a caller waits for a free connection before erroring
+    pub acquire_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    /// Eight background loops plus headroom for UI commands and the mobile server
+    fn default() -> Self {
+        Self {
+            max_connections: 12,
+            acquire_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Defaults overridden by `RUGPLAY_DB_POOL_SIZE` and
+    /// `RUGPLAY_DB_ACQUIRE_TIMEOUT_SECS` when set to valid positive numbers.
+    /// Read from the environment because the pool exists before the settings table.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Some(n) = std::env::var("RUGPLAY_DB_POOL_SIZE").ok().and_then(|v| v.parse::<u32>().ok()) {
+            if n > 0 {
+                config.max_connections = n;
+            }
+        }
+        if let Some(secs) = std::env::var("RUGPLAY_DB_ACQUIRE_TIMEOUT_SECS").ok().and_then(|v| v.parse::<u64>().ok()) {
+            if secs > 0 {
+                config.acquire_timeout = Duration::from_secs(secs);
+            }
+        }
+        config
+    }
+}
+
+/// Snapshot of pool usage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatus {
+    /// Connections currently open
+    pub size: u32,
+    /// Open connections not checked out
+    pub idle: usize,
+    pub max_connections: u32,
+}
+
+impl PoolStatus {
+    /// Every connection is open and checked out; the next caller will wait
+    pub fn is_saturated(&self) -> bool {
+        self.size >= self.max_connections && self.idle == 0
+    }
+}
 
 /// Database wrapper for SQLite operations
 pub struct Database {
     pool: SqlitePool,
+    config: PoolConfig,
 }
 
 impl Database {
-    /// Connect to database at the given path, creating if necessary
+    /// Connect to database at the given path with the default pool config
     pub async fn connect(path: &Path) -> Result<Self> {
+        Self::connect_with_config(path, PoolConfig::default()).await
+    }
+
+    /// Connect to database at the given path, creating if necessary
+    pub async fn connect_with_config(path: &Path, config: PoolConfig) -> Result<Self> {
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent)
@@ -24,28 +103,35 @@ impl Database {
         let path_str = path.to_string_lossy();
         let options = SqliteConnectOptions::from_str(&format!("sqlite:{}", path_str))
             .map_err(|e| Error::DatabaseError(e.to_string()))?
-            .create_if_missing(true);
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            // NORMAL is durable in WAL mode and avoids an fsync per commit
+            .synchronous(SqliteSynchronous::Normal)
+            .busy_timeout(BUSY_TIMEOUT);
 
         let pool = SqlitePoolOptions::new()
-            .max_connections(5)
+            .max_connections(config.max_connections)
+            .acquire_timeout(config.acquire_timeout)
             .connect_with(options)
             .await
             .map_err(|e| Error::DatabaseError(e.to_string()))?;
 
-        let db = Self { pool };
+        let db = Self { pool, config };
         db.run_migrations().await?;
         Ok(db)
     }
 
     /// Connect to in-memory database (for testing)
     pub async fn connect_in_memory() -> Result<Self> {
+        let config = PoolConfig { max_connections: 1, ..PoolConfig::default() };
         let pool = SqlitePoolOptions::new()
-            .max_connections(1)
+            .max_connections(config.max_connections)
+            .acquire_timeout(config.acquire_timeout)
             .connect("sqlite::memory:")
             .await
             .map_err(|e| Error::DatabaseError(e.to_string()))?;
 
-        let db = Self { pool };
+        let db = Self { pool, config };
         db.run_migrations().await?;
         Ok(db)
     }
@@ -88,6 +174,7 @@ impl Database {
                 price REAL NOT NULL,
                 usd_value REAL NOT NULL,
                 timestamp TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                note TEXT,
                 FOREIGN KEY (profile_id) REFERENCES profiles(id)
             );
 
@@ -103,8 +190,11 @@ impl Database {
             CREATE TABLE IF NOT EXISTS whales (
                 user_id TEXT PRIMARY KEY,
                 username TEXT NOT NULL,
-                performance_score REAL DEFAULT 0.0,
-                tracked_since TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+                performance_score REAL,
+                tracked_since TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                max_latency_secs REAL,
+                score_detail TEXT,
+                scored_at TIMESTAMP
             );
 
             CREATE TABLE IF NOT EXISTS sentinels (
@@ -160,6 +250,108 @@ impl Database {
                 last_updated TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 notes TEXT DEFAULT ''
             );
+
+            CREATE TABLE IF NOT EXISTS history_rollup (
+                profile_id INTEGER NOT NULL,
+                module TEXT NOT NULL,
+                action TEXT NOT NULL,
+                entries INTEGER NOT NULL DEFAULT 0,
+                amount_usd REAL NOT NULL DEFAULT 0,
+                PRIMARY KEY (profile_id, module, action)
+            );
+
+            CREATE TABLE IF NOT EXISTS loop_snapshots (
+                module TEXT NOT NULL,
+                profile_id INTEGER NOT NULL,
+                state TEXT NOT NULL,
+                saved_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (module, profile_id),
+                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS decision_configs (
+                hash TEXT PRIMARY KEY,
+                module TEXT NOT NULL,
+                config TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE TABLE IF NOT EXISTS decision_events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                profile_id INTEGER NOT NULL,
+                module TEXT NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                config_hash TEXT NOT NULL,
+                input TEXT NOT NULL,
+                action TEXT NOT NULL,
+                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE,
+                FOREIGN KEY (config_hash) REFERENCES decision_configs(hash)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_decision_events_profile_time
+                ON decision_events (profile_id, recorded_at);
+
+            CREATE TABLE IF NOT EXISTS watchlist (
+                profile_id INTEGER NOT NULL,
+                symbol TEXT NOT NULL,
+                added_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                alert_pct REAL,
+                reference_price REAL,
+                PRIMARY KEY (profile_id, symbol),
+                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS price_alerts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                profile_id INTEGER NOT NULL,
+                symbol TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                target REAL NOT NULL,
+                window_secs INTEGER,
+                armed INTEGER NOT NULL DEFAULT 1,
+                last_triggered_at TIMESTAMP,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS equity_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                profile_id INTEGER NOT NULL,
+                total_value REAL NOT NULL,
+                recorded_at INTEGER NOT NULL,
+                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_equity_snapshots_profile_time
+                ON equity_snapshots (profile_id, recorded_at);
+
+            CREATE TABLE IF NOT EXISTS mirror_trades (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                profile_id INTEGER NOT NULL,
+                whale_username TEXT NOT NULL,
+                whale_user_id TEXT NOT NULL,
+                coin_symbol TEXT NOT NULL,
+                coin_name TEXT NOT NULL,
+                trade_type TEXT NOT NULL,
+                whale_amount_usd REAL NOT NULL,
+                our_amount_usd REAL NOT NULL,
+                timestamp TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                FOREIGN KEY (profile_id) REFERENCES profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_mirror_trades_profile
+                ON mirror_trades (profile_id, id);
+
+            CREATE TABLE IF NOT EXISTS config_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                module TEXT NOT NULL,
+                changes TEXT NOT NULL,
+                changed_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_config_history_module
+                ON config_history (module, id);
             "#,
         )
         .execute(&self.pool)
@@ -174,6 +366,93 @@ impl Database {
         .execute(&self.pool)
         .await;
 
+        // Per-sentinel price source override (idempotent)
+        let _ = sqlx::query("ALTER TABLE sentinels ADD COLUMN price_source TEXT")
+            .execute(&self.pool)
+            .await;
+
+        // Per-trigger sell percentages; NULL falls back to sell_percentage (idempotent)
+        for column in ["sl_sell_pct", "tp_sell_pct", "ts_sell_pct"] {
+            let _ = sqlx::query(&format!("ALTER TABLE sentinels ADD COLUMN {} REAL", column))
+                .execute(&self.pool)
+                .await;
+        }
+
+        // Take-profit ladder and how many of its rungs have sold (idempotent)
+        let _ = sqlx::query("ALTER TABLE sentinels ADD COLUMN tp_ladder TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE sentinels ADD COLUMN tp_rungs_filled INTEGER DEFAULT 0")
+            .execute(&self.pool)
+            .await;
+
+        // What a partly filled trigger still has to sell (idempotent)
+        let _ = sqlx::query("ALTER TABLE sentinels ADD COLUMN unsold_trigger TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE sentinels ADD COLUMN unsold_qty REAL")
+            .execute(&self.pool)
+            .await;
+
+        // Profile avatar and last-used time for the identity refresh (idempotent)
+        let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN avatar_url TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE profiles ADD COLUMN last_used_at TIMESTAMP")
+            .execute(&self.pool)
+            .await;
+
+        // Per-whale mirror latency override; NULL uses the global limit (idempotent)
+        let _ = sqlx::query("ALTER TABLE whales ADD COLUMN max_latency_secs REAL")
+            .execute(&self.pool)
+            .await;
+
+        // Whale performance analysis (JSON) and when it was computed (idempotent)
+        let _ = sqlx::query("ALTER TABLE whales ADD COLUMN score_detail TEXT")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query("ALTER TABLE whales ADD COLUMN scored_at TIMESTAMP")
+            .execute(&self.pool)
+            .await;
+        // Unscored whales used to default to a score of 0; NULL means not scored (idempotent)
+        let _ = sqlx::query(
+            "UPDATE whales SET performance_score = NULL WHERE scored_at IS NULL OR score_detail LIKE '%\"insufficientData\"%'",
+        )
+        .execute(&self.pool)
+        .await;
+
+        // Free-form note on journaled trades (idempotent)
+        let _ = sqlx::query("ALTER TABLE transactions ADD COLUMN note TEXT")
+            .execute(&self.pool)
+            .await;
+
+        // Server transaction id of trades recovered by journal reconciliation, so a
+        // trade is never recovered twice (idempotent). Rows recovered before the
+        // column existed carry the id in their note; later copies are duplicates.
+        let _ = sqlx::query("ALTER TABLE transactions ADD COLUMN server_tx_id INTEGER")
+            .execute(&self.pool)
+            .await;
+        let _ = sqlx::query(
+            r#"UPDATE transactions
+               SET server_tx_id = CAST(substr(note, 33, length(note) - 33) AS INTEGER)
+               WHERE server_tx_id IS NULL AND note LIKE 'Recovered from server history (#%)'"#,
+        )
+        .execute(&self.pool)
+        .await;
+        let _ = sqlx::query(
+            r#"DELETE FROM transactions
+               WHERE server_tx_id IS NOT NULL
+                 AND id NOT IN (SELECT MIN(id) FROM transactions WHERE server_tx_id IS NOT NULL
+                                GROUP BY profile_id, server_tx_id)"#,
+        )
+        .execute(&self.pool)
+        .await;
+        let _ = sqlx::query(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_transactions_server_tx ON transactions(profile_id, server_tx_id)",
+        )
+        .execute(&self.pool)
+        .await;
+
         // Deduplicate sentinels: keep only the newest per (profile_id, symbol)
         let deduped = crate::sqlite::deduplicate_sentinels(&self.pool).await.unwrap_or(0);
         if deduped > 0 {
@@ -196,4 +475,118 @@ impl Database {
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
+
+    /// The pool configuration this database was opened with
+    pub fn pool_config(&self) -> PoolConfig {
+        self.config
+    }
+
+    /// Current pool usage
+    pub fn pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle(),
+            max_connections: self.config.max_connections,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_concurrent_writers_do_not_hit_lock_errors() {
+        let dir = std::env::temp_dir().join(format!("rugplay-wal-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db = Database::connect(&dir.join("test.db")).await.unwrap();
+
+        let mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(mode.to_lowercase(), "wal");
+
+        // Mimic the automation loops: many tasks upserting settings at once
+        let mut tasks = Vec::new();
+        for task in 0..8 {
+            let pool = db.pool().clone();
+            tasks.push(tokio::spawn(async move {
+                for i in 0..50 {
+                    sqlx::query(
+                        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+                         ON CONFLICT(key) DO UPDATE SET value = ?2",
+                    )
+                    .bind(format!("task_{}_{}", task, i % 10))
+                    .bind(i.to_string())
+                    .execute(&pool)
+                    .await?;
+                }
+                Ok::<_, sqlx::Error>(())
+            }));
+        }
+
+        for task in tasks {
+            task.await.unwrap().expect("concurrent write failed");
+        }
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM settings")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(count, 80);
+
+        db.pool().close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_queries_stay_within_pool() {
+        let dir = std::env::temp_dir().join(format!("rugplay-pool-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let config = PoolConfig {
+            max_connections: 3,
+            acquire_timeout: Duration::from_secs(5),
+        };
+        let db = Arc::new(Database::connect_with_config(&dir.join("test.db"), config).await.unwrap());
+        assert_eq!(db.pool_config(), config);
+
+        // 12 tasks each hold a connection briefly; they must queue, not time out
+        let peak = Arc::new(AtomicU32::new(0));
+        let mut tasks = Vec::new();
+        for _ in 0..12 {
+            let db = db.clone();
+            let peak = peak.clone();
+            tasks.push(tokio::spawn(async move {
+                let mut conn = db.pool().acquire().await?;
+                let status = db.pool_status();
+                peak.fetch_max(status.size, Ordering::SeqCst);
+                sqlx::query("SELECT 1").execute(&mut *conn).await?;
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                Ok::<_, sqlx::Error>(status.is_saturated())
+            }));
+        }
+
+        let mut saw_saturation = false;
+        for task in tasks {
+            saw_saturation |= task.await.unwrap().expect("query timed out waiting for a connection");
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(saw_saturation);
+
+        // Connections go back to the pool asynchronously after the tasks finish
+        for _ in 0..50 {
+            if !db.pool_status().is_saturated() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(!db.pool_status().is_saturated());
+
+        db.pool().close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
 }
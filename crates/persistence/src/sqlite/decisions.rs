@@ -0,0 +1,135 @@
+//! Decision log — append-only record of automation decisions
+//!
+//! Every decision an automation module acts on is stored with the exact
+//! inputs it saw and the action it produced, so a time range can later be
+//! re-run through the strategy code. Configs are stored once per content
+//! hash and referenced from each event, which keeps the log compact when
+//! settings rarely change.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A recorded decision joined with the config it was made under
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct DecisionEventRow {
+    pub id: i64,
+    pub profile_id: i64,
+    pub module: String,
+    /// Unix epoch seconds
+    pub recorded_at: i64,
+    pub config_hash: String,
+    pub config: String,
+    pub input: String,
+    pub action: String,
+}
+
+/// Store a config under its hash (no-op if already stored)
+pub async fn save_decision_config(
+    pool: &SqlitePool,
+    config_hash: &str,
+    module: &str,
+    config_json: &str,
+) -> Result<()> {
+    sqlx::query("INSERT OR IGNORE INTO decision_configs (hash, module, config) VALUES (?1, ?2, ?3)")
+        .bind(config_hash)
+        .bind(module)
+        .bind(config_json)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Append a decision event. The config must already be stored.
+pub async fn append_decision_event(
+    pool: &SqlitePool,
+    profile_id: i64,
+    module: &str,
+    recorded_at: i64,
+    config_hash: &str,
+    input_json: &str,
+    action_json: &str,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO decision_events (profile_id, module, recorded_at, config_hash, input, action)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+    )
+    .bind(profile_id)
+    .bind(module)
+    .bind(recorded_at)
+    .bind(config_hash)
+    .bind(input_json)
+    .bind(action_json)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Decision events for a profile with `from <= recorded_at <= to`, oldest first
+pub async fn get_decision_events(
+    pool: &SqlitePool,
+    profile_id: i64,
+    from: i64,
+    to: i64,
+) -> Result<Vec<DecisionEventRow>> {
+    sqlx::query_as::<_, DecisionEventRow>(
+        r#"
+        SELECT e.id, e.profile_id, e.module, e.recorded_at, e.config_hash,
+               c.config, e.input, e.action
+        FROM decision_events e
+        JOIN decision_configs c ON c.hash = e.config_hash
+        WHERE e.profile_id = ?1 AND e.recorded_at BETWEEN ?2 AND ?3
+        ORDER BY e.recorded_at ASC, e.id ASC
+        "#,
+    )
+    .bind(profile_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profiles;
+
+    #[tokio::test]
+    async fn test_events_are_read_back_in_range_with_their_config() {
+        let db = db_with_profiles(2).await;
+        let pool = db.pool();
+
+        save_decision_config(pool, "h1", "dipbuyer", r#"{"min":0.5}"#).await.unwrap();
+        // Same hash again keeps the first copy
+        save_decision_config(pool, "h1", "dipbuyer", r#"{"min":0.9}"#).await.unwrap();
+        save_decision_config(pool, "h2", "sentinel", "{}").await.unwrap();
+
+        append_decision_event(pool, 1, "dipbuyer", 100, "h1", r#"{"n":1}"#, r#"{"action":"skip"}"#).await.unwrap();
+        append_decision_event(pool, 1, "sentinel", 200, "h2", r#"{"n":2}"#, r#"{"action":"sell"}"#).await.unwrap();
+        append_decision_event(pool, 1, "dipbuyer", 300, "h1", r#"{"n":3}"#, r#"{"action":"buy"}"#).await.unwrap();
+        append_decision_event(pool, 2, "dipbuyer", 200, "h1", r#"{"n":4}"#, r#"{"action":"buy"}"#).await.unwrap();
+
+        let events = get_decision_events(pool, 1, 100, 200).await.unwrap();
+        assert_eq!(events.iter().map(|e| e.input.as_str()).collect::<Vec<_>>(), [r#"{"n":1}"#, r#"{"n":2}"#]);
+        assert_eq!(events[0].config, r#"{"min":0.5}"#);
+        assert_eq!(events[1].module, "sentinel");
+
+        // Unknown config hashes are rejected
+        assert!(append_decision_event(pool, 1, "dipbuyer", 400, "missing", "{}", "{}").await.is_err());
+
+        crate::sqlite::delete_profile(pool, 2).await.unwrap();
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM decision_events")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 3);
+    }
+}
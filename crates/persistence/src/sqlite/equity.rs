@@ -0,0 +1,86 @@
+//! Equity snapshots — the account's total value over time
+//!
+//! Sampled by the app while total-equity alerts are on, so milestone and
+//! percentage-move alerts can be judged against a series rather than a
+//! single reading. Old samples are pruned by the same task.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// One total-value sample
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct EquitySnapshotRow {
+    pub profile_id: i64,
+    /// Cash plus coin value, in base currency
+    pub total_value: f64,
+    /// Unix epoch seconds
+    pub recorded_at: i64,
+}
+
+/// Append a sample
+pub async fn record_equity_snapshot(pool: &SqlitePool, profile_id: i64, total_value: f64, recorded_at: i64) -> Result<()> {
+    sqlx::query("INSERT INTO equity_snapshots (profile_id, total_value, recorded_at) VALUES (?1, ?2, ?3)")
+        .bind(profile_id)
+        .bind(total_value)
+        .bind(recorded_at)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Samples recorded at or after `since`, oldest first
+pub async fn get_equity_snapshots(pool: &SqlitePool, profile_id: i64, since: i64) -> Result<Vec<EquitySnapshotRow>> {
+    sqlx::query_as::<_, EquitySnapshotRow>(
+        r#"
+        SELECT profile_id, total_value, recorded_at
+        FROM equity_snapshots
+        WHERE profile_id = ?1 AND recorded_at >= ?2
+        ORDER BY recorded_at ASC, id ASC
+        "#,
+    )
+    .bind(profile_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+/// Delete samples recorded before `before`. Returns how many were removed.
+pub async fn prune_equity_snapshots(pool: &SqlitePool, before: i64) -> Result<u64> {
+    let result = sqlx::query("DELETE FROM equity_snapshots WHERE recorded_at < ?")
+        .bind(before)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profiles;
+
+    #[tokio::test]
+    async fn test_series_is_per_profile_and_prunable() {
+        let db = db_with_profiles(2).await;
+        let pool = db.pool();
+
+        for (at, value) in [(100, 1_000.0), (160, 1_050.0), (220, 990.0)] {
+            record_equity_snapshot(pool, 1, value, at).await.unwrap();
+        }
+        record_equity_snapshot(pool, 2, 5.0, 200).await.unwrap();
+
+        let series = get_equity_snapshots(pool, 1, 150).await.unwrap();
+        let values: Vec<f64> = series.iter().map(|s| s.total_value).collect();
+        assert_eq!(values, vec![1_050.0, 990.0]);
+
+        assert_eq!(prune_equity_snapshots(pool, 200).await.unwrap(), 2);
+        assert_eq!(get_equity_snapshots(pool, 1, 0).await.unwrap().len(), 1);
+        assert_eq!(get_equity_snapshots(pool, 2, 0).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,116 @@
+//! Database maintenance — VACUUM, ANALYZE and WAL checkpoint
+//!
+//! Safe to run while the app is live: SQLite takes the locks it needs and
+//! other connections wait on the busy timeout until the pass finishes.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use std::time::Instant;
+
+/// Outcome of a maintenance pass
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MaintenanceReport {
+    pub size_before_bytes: u64,
+    pub size_after_bytes: u64,
+    /// Whether a WAL checkpoint was requested
+    pub checkpointed: bool,
+    pub duration_ms: u64,
+}
+
+impl MaintenanceReport {
+    pub fn reclaimed_bytes(&self) -> u64 {
+        self.size_before_bytes.saturating_sub(self.size_after_bytes)
+    }
+}
+
+/// Current database size in bytes (page_count * page_size)
+pub async fn database_size(pool: &SqlitePool) -> Result<u64> {
+    let db_err = |e: sqlx::Error| Error::DatabaseError(e.to_string());
+    let page_count: i64 = sqlx::query_scalar("PRAGMA page_count")
+        .fetch_one(pool)
+        .await
+        .map_err(db_err)?;
+    let page_size: i64 = sqlx::query_scalar("PRAGMA page_size")
+        .fetch_one(pool)
+        .await
+        .map_err(db_err)?;
+    Ok((page_count * page_size).max(0) as u64)
+}
+
+/// Compact the database file and refresh query planner statistics.
+///
+/// Runs `VACUUM` then `ANALYZE`, and `PRAGMA wal_checkpoint(TRUNCATE)` when
+/// `checkpoint` is set (a no-op outside WAL mode).
+pub async fn run_maintenance(pool: &SqlitePool, checkpoint: bool) -> Result<MaintenanceReport> {
+    let db_err = |e: sqlx::Error| Error::DatabaseError(e.to_string());
+    let started = Instant::now();
+    let size_before_bytes = database_size(pool).await?;
+
+    sqlx::query("VACUUM").execute(pool).await.map_err(db_err)?;
+    sqlx::query("ANALYZE").execute(pool).await.map_err(db_err)?;
+    if checkpoint {
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(pool)
+            .await
+            .map_err(db_err)?;
+    }
+
+    Ok(MaintenanceReport {
+        size_before_bytes,
+        size_after_bytes: database_size(pool).await?,
+        checkpointed: checkpoint,
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::Database;
+    use crate::test_support::insert_profile;
+
+    #[tokio::test]
+    async fn test_maintenance_shrinks_file_after_deletions() {
+        let dir = std::env::temp_dir().join(format!("rugplay-maint-{}", std::process::id()));
+        let path = dir.join("test.db");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let db = Database::connect(&path).await.unwrap();
+        let pool = db.pool();
+
+        insert_profile(pool, 1).await;
+
+        let details = "x".repeat(2000);
+        for _ in 0..500 {
+            sqlx::query(
+                "INSERT INTO automation_log (profile_id, module, symbol, coin_name, action, amount_usd, details) \
+                 VALUES (1, 'sniper', 'TEST', 'Test', 'BUY', 1.0, ?)",
+            )
+            .bind(&details)
+            .execute(pool)
+            .await
+            .unwrap();
+        }
+        sqlx::query("DELETE FROM automation_log").execute(pool).await.unwrap();
+
+        let report = run_maintenance(pool, true).await.unwrap();
+
+        assert!(report.checkpointed);
+        assert!(report.size_after_bytes < report.size_before_bytes);
+        assert!(report.reclaimed_bytes() > 500 * 1000);
+        assert_eq!(report.size_after_bytes, database_size(pool).await.unwrap());
+        assert!(std::fs::metadata(&path).unwrap().len() <= report.size_after_bytes);
+
+        // The database is still usable afterwards
+        let profiles: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM profiles")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(profiles, 1);
+
+        pool.close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,142 @@
+//! Mirror trades — the copy-trading audit trail
+//!
+//! Every whale trade the mirror acted on, whether our copy filled or not.
+//! The mirror keeps a short in-memory cache for its own use; this table is
+//! the full history and survives restarts.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A mirrored trade as stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorTradeRow {
+    pub id: i64,
+    pub profile_id: i64,
+    pub whale_username: String,
+    pub whale_user_id: String,
+    pub coin_symbol: String,
+    pub coin_name: String,
+    pub trade_type: String,
+    pub whale_amount_usd: f64,
+    pub our_amount_usd: f64,
+    /// RFC 3339, when the mirror acted
+    pub timestamp: String,
+    pub success: bool,
+}
+
+/// A mirrored trade to record
+#[derive(Debug, Clone, Copy)]
+pub struct NewMirrorTrade<'a> {
+    pub whale_username: &'a str,
+    pub whale_user_id: &'a str,
+    pub coin_symbol: &'a str,
+    pub coin_name: &'a str,
+    pub trade_type: &'a str,
+    pub whale_amount_usd: f64,
+    pub our_amount_usd: f64,
+    pub timestamp: &'a str,
+    pub success: bool,
+}
+
+/// Record a mirrored trade for `profile_id`
+pub async fn insert_mirror_trade(pool: &SqlitePool, profile_id: i64, trade: &NewMirrorTrade<'_>) -> Result<i64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO mirror_trades
+            (profile_id, whale_username, whale_user_id, coin_symbol, coin_name, trade_type,
+             whale_amount_usd, our_amount_usd, timestamp, success)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(profile_id)
+    .bind(trade.whale_username)
+    .bind(trade.whale_user_id)
+    .bind(trade.coin_symbol)
+    .bind(trade.coin_name)
+    .bind(trade.trade_type)
+    .bind(trade.whale_amount_usd)
+    .bind(trade.our_amount_usd)
+    .bind(trade.timestamp)
+    .bind(trade.success)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// Mirrored trades of `profile_id`, newest first
+pub async fn get_mirror_trades(pool: &SqlitePool, profile_id: i64, limit: u64, offset: u64) -> Result<Vec<MirrorTradeRow>> {
+    sqlx::query_as::<_, MirrorTradeRow>(
+        r#"
+        SELECT id, profile_id, whale_username, whale_user_id, coin_symbol, coin_name, trade_type,
+               whale_amount_usd, our_amount_usd, timestamp, success
+        FROM mirror_trades
+        WHERE profile_id = ?1
+        ORDER BY id DESC
+        LIMIT ?2 OFFSET ?3
+        "#,
+    )
+    .bind(profile_id)
+    .bind(limit as i64)
+    .bind(offset as i64)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+/// Number of mirrored trades recorded for `profile_id`
+pub async fn count_mirror_trades(pool: &SqlitePool, profile_id: i64) -> Result<u64> {
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM mirror_trades WHERE profile_id = ?")
+        .bind(profile_id)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(count as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profiles;
+
+    fn trade(coin_symbol: &str, success: bool) -> NewMirrorTrade<'_> {
+        NewMirrorTrade {
+            whale_username: "whale",
+            whale_user_id: "42",
+            coin_symbol,
+            coin_name: coin_symbol,
+            trade_type: "BUY",
+            whale_amount_usd: 1_000.0,
+            our_amount_usd: 10.0,
+            timestamp: "2024-01-01T00:00:00Z",
+            success,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mirror_trades_are_paged_newest_first_per_profile() {
+        let db = db_with_profiles(2).await;
+        let pool = db.pool();
+
+        for (symbol, success) in [("AAA", true), ("BBB", false), ("CCC", true)] {
+            insert_mirror_trade(pool, 1, &trade(symbol, success)).await.unwrap();
+        }
+        insert_mirror_trade(pool, 2, &trade("ZZZ", true)).await.unwrap();
+
+        let page = get_mirror_trades(pool, 1, 2, 0).await.unwrap();
+        let symbols: Vec<&str> = page.iter().map(|t| t.coin_symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["CCC", "BBB"]);
+        assert!(!page[1].success);
+        assert_eq!(page[0].whale_amount_usd, 1_000.0);
+
+        let rest = get_mirror_trades(pool, 1, 2, 2).await.unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].coin_symbol, "AAA");
+        assert_eq!(count_mirror_trades(pool, 1).await.unwrap(), 3);
+        assert_eq!(count_mirror_trades(pool, 2).await.unwrap(), 1);
+    }
+}
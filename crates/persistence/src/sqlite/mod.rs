@@ -1,15 +1,35 @@
 //! SQLite database management
 
+mod config_history;
 mod connection;
+mod decisions;
+mod equity;
+mod maintenance;
+mod mirror_trades;
+mod price_alerts;
 mod profiles;
 mod reputation;
+mod retention;
 mod sentinels;
+mod settings;
+mod snapshots;
 mod transactions;
+mod watchlist;
 mod whales;
 
-pub use connection::Database;
+pub use config_history::*;
+pub use connection::{Database, PoolConfig, PoolStatus};
+pub use decisions::*;
+pub use equity::*;
+pub use maintenance::*;
+pub use mirror_trades::*;
+pub use price_alerts::*;
 pub use profiles::*;
 pub use reputation::*;
+pub use retention::*;
 pub use sentinels::*;
+pub use settings::*;
+pub use snapshots::*;
 pub use transactions::*;
+pub use watchlist::*;
 pub use whales::*;
@@ -0,0 +1,137 @@
+//! Price alerts — standalone "tell me when" triggers on any coin
+//!
+//! An alert fires once when its condition starts to hold and is then
+//! disarmed until the condition clears again, so a price hovering around a
+//! level doesn't notify on every check. Evaluation lives in the app; this
+//! module only stores alerts and their armed state.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A stored price alert
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAlertRow {
+    pub id: i64,
+    pub profile_id: i64,
+    pub symbol: String,
+    /// "above", "below" or "move"
+    pub kind: String,
+    /// Price level for above/below, percent for move
+    pub target: f64,
+    /// Look-back window for move alerts
+    pub window_secs: Option<i64>,
+    pub armed: bool,
+    pub last_triggered_at: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// Store a new alert and return its id
+pub async fn create_price_alert(
+    pool: &SqlitePool,
+    profile_id: i64,
+    symbol: &str,
+    kind: &str,
+    target: f64,
+    window_secs: Option<i64>,
+    armed: bool,
+) -> Result<i64> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO price_alerts (profile_id, symbol, kind, target, window_secs, armed)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        "#,
+    )
+    .bind(profile_id)
+    .bind(symbol)
+    .bind(kind)
+    .bind(target)
+    .bind(window_secs)
+    .bind(armed)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.last_insert_rowid())
+}
+
+/// All alerts for a profile, oldest first
+pub async fn get_price_alerts(pool: &SqlitePool, profile_id: i64) -> Result<Vec<PriceAlertRow>> {
+    sqlx::query_as::<_, PriceAlertRow>(
+        r#"
+        SELECT id, profile_id, symbol, kind, target, window_secs, armed, last_triggered_at, created_at
+        FROM price_alerts
+        WHERE profile_id = ?
+        ORDER BY id ASC
+        "#,
+    )
+    .bind(profile_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+/// Delete an alert. Returns whether it existed for this profile.
+pub async fn delete_price_alert(pool: &SqlitePool, profile_id: i64, id: i64) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM price_alerts WHERE id = ? AND profile_id = ?")
+        .bind(id)
+        .bind(profile_id)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Disarm an alert that just fired, recording when
+pub async fn mark_price_alert_fired(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("UPDATE price_alerts SET armed = 0, last_triggered_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Re-arm an alert whose condition has cleared
+pub async fn rearm_price_alert(pool: &SqlitePool, id: i64) -> Result<()> {
+    sqlx::query("UPDATE price_alerts SET armed = 1 WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profile;
+
+    #[tokio::test]
+    async fn test_alert_fire_and_rearm_round_trip() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+
+        let above = create_price_alert(pool, 1, "AAA", "above", 2.0, None, true).await.unwrap();
+        let moved = create_price_alert(pool, 1, "BBB", "move", 10.0, Some(600), false).await.unwrap();
+
+        mark_price_alert_fired(pool, above).await.unwrap();
+        rearm_price_alert(pool, moved).await.unwrap();
+
+        let alerts = get_price_alerts(pool, 1).await.unwrap();
+        assert_eq!(alerts.len(), 2);
+        assert!(!alerts[0].armed);
+        assert!(alerts[0].last_triggered_at.is_some());
+        assert!(alerts[1].armed);
+        assert_eq!(alerts[1].window_secs, Some(600));
+
+        // Only the owning profile can delete
+        assert!(!delete_price_alert(pool, 2, above).await.unwrap());
+        assert!(delete_price_alert(pool, 1, above).await.unwrap());
+        assert_eq!(get_price_alerts(pool, 1).await.unwrap().len(), 1);
+    }
+}
@@ -16,6 +16,8 @@ struct ProfileRow {
     iv: Vec<u8>,
     last_verified: Option<DateTime<Utc>>,
     is_active: i32,
+    avatar_url: Option<String>,
+    last_used_at: Option<DateTime<Utc>>,
 }
 
 impl From<ProfileRow> for Profile {
@@ -26,6 +28,8 @@ impl From<ProfileRow> for Profile {
             user_id: row.user_id,
             last_verified: row.last_verified,
             is_active: row.is_active != 0,
+            avatar_url: row.avatar_url,
+            last_used_at: row.last_used_at,
         }
     }
 }
@@ -58,7 +62,8 @@ pub async fn create_profile(
 pub async fn list_profiles(pool: &SqlitePool) -> Result<Vec<Profile>> {
     let rows: Vec<ProfileRow> = sqlx::query_as(
         r#"
-        SELECT id, username, user_id, token_encrypted, iv, last_verified, is_active
+        SELECT id, username, user_id, token_encrypted, iv, last_verified, is_active,
+               avatar_url, last_used_at
         FROM profiles
         ORDER BY last_verified DESC NULLS LAST
         "#,
@@ -74,7 +79,8 @@ pub async fn list_profiles(pool: &SqlitePool) -> Result<Vec<Profile>> {
 pub async fn get_profile(pool: &SqlitePool, id: i64) -> Result<Option<Profile>> {
     let row: Option<ProfileRow> = sqlx::query_as(
         r#"
-        SELECT id, username, user_id, token_encrypted, iv, last_verified, is_active
+        SELECT id, username, user_id, token_encrypted, iv, last_verified, is_active,
+               avatar_url, last_used_at
         FROM profiles
         WHERE id = ?
         "#,
@@ -91,7 +97,8 @@ pub async fn get_profile(pool: &SqlitePool, id: i64) -> Result<Option<Profile>>
 pub async fn get_active_profile(pool: &SqlitePool) -> Result<Option<Profile>> {
     let row: Option<ProfileRow> = sqlx::query_as(
         r#"
-        SELECT id, username, user_id, token_encrypted, iv, last_verified, is_active
+        SELECT id, username, user_id, token_encrypted, iv, last_verified, is_active,
+               avatar_url, last_used_at
         FROM profiles
         WHERE is_active = 1
         LIMIT 1
@@ -140,7 +147,7 @@ pub async fn set_active_profile(pool: &SqlitePool, id: i64) -> Result<()> {
         .map_err(|e| Error::DatabaseError(e.to_string()))?;
 
     // Activate the selected profile
-    sqlx::query("UPDATE profiles SET is_active = 1 WHERE id = ?")
+    sqlx::query("UPDATE profiles SET is_active = 1, last_used_at = CURRENT_TIMESTAMP WHERE id = ?")
         .bind(id)
         .execute(pool)
         .await
@@ -189,15 +196,97 @@ pub async fn update_last_verified(pool: &SqlitePool, id: i64) -> Result<()> {
     Ok(())
 }
 
-/// Delete a profile
+/// Store the identity the API reports for a profile.
+///
+/// Returns `true` when anything actually changed. Fails if the new username
+/// already belongs to another saved profile.
+pub async fn update_profile_identity(
+    pool: &SqlitePool,
+    id: i64,
+    username: &str,
+    user_id: &str,
+    avatar_url: Option<&str>,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE profiles
+        SET username = ?1, user_id = ?2, avatar_url = ?3
+        WHERE id = ?4
+          AND (username IS NOT ?1 OR user_id IS NOT ?2 OR avatar_url IS NOT ?3)
+        "#,
+    )
+    .bind(username)
+    .bind(user_id)
+    .bind(avatar_url)
+    .bind(id)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Delete a profile. Its trade journal is kept: `transactions` rows stay
+/// behind under the old id (profile ids are never reused), while any other
+/// row still referencing the profile blocks the delete as before.
 pub async fn delete_profile(pool: &SqlitePool, id: i64) -> Result<()> {
+    let db_err = |e: sqlx::Error| Error::DatabaseError(e.to_string());
+    let mut conn = pool.acquire().await.map_err(db_err)?;
+
+    // The journal's foreign key would refuse the delete, and SQLite only
+    // changes enforcement outside a transaction
+    sqlx::query("PRAGMA foreign_keys = OFF").execute(&mut *conn).await.map_err(db_err)?;
+    let deleted = delete_profile_keeping_journal(&mut conn, id).await;
+    let restored = sqlx::query("PRAGMA foreign_keys = ON").execute(&mut *conn).await;
+    if restored.is_err() {
+        // Never hand a connection without enforcement back to the pool
+        conn.detach();
+    }
+    deleted?;
+    restored.map_err(db_err)?;
+    Ok(())
+}
+
+async fn delete_profile_keeping_journal(conn: &mut sqlx::SqliteConnection, id: i64) -> Result<()> {
+    let db_err = |e: sqlx::Error| Error::DatabaseError(e.to_string());
+    let mut tx = sqlx::Connection::begin(conn).await.map_err(db_err)?;
+
     sqlx::query("DELETE FROM profiles WHERE id = ?")
         .bind(id)
-        .execute(pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+        .map_err(db_err)?;
 
-    Ok(())
+    // Enforcement is off, so cascades have to be done by hand and the
+    // remaining references checked
+    let cascading: Vec<(String,)> = sqlx::query_as(
+        "SELECT m.name FROM sqlite_master m, pragma_foreign_key_list(m.name) f
+         WHERE m.type = 'table' AND f.\"table\" = 'profiles' AND f.on_delete = 'CASCADE'",
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(db_err)?;
+    for (table,) in cascading {
+        sqlx::query(&format!("DELETE FROM \"{}\" WHERE profile_id = ?", table))
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_err)?;
+    }
+
+    let blocking: Vec<(String,)> =
+        sqlx::query_as("SELECT \"table\" FROM pragma_foreign_key_check WHERE \"table\" != 'transactions' LIMIT 1")
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(db_err)?;
+    if let Some((table,)) = blocking.first() {
+        return Err(Error::DatabaseError(format!(
+            "FOREIGN KEY constraint failed: {} still references profile {}",
+            table, id
+        )));
+    }
+
+    tx.commit().await.map_err(db_err)
 }
 
 /// Check if a profile with the given username exists
@@ -210,3 +299,72 @@ pub async fn profile_exists(pool: &SqlitePool, username: &str) -> Result<bool> {
 
     Ok(count.0 > 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::Database;
+
+    fn dummy_token() -> EncryptedToken {
+        EncryptedToken { ciphertext: vec![0], iv: [0u8; 12] }
+    }
+
+    #[tokio::test]
+    async fn test_server_username_change_updates_profile() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        let id = create_profile(pool, "old_name", Some("u1"), &dummy_token()).await.unwrap();
+        let other = create_profile(pool, "taken", Some("u2"), &dummy_token()).await.unwrap();
+
+        assert!(update_profile_identity(pool, id, "new_name", "u1", Some("https://img/a.png")).await.unwrap());
+        let profile = get_profile(pool, id).await.unwrap().unwrap();
+        assert_eq!(profile.username, "new_name");
+        assert_eq!(profile.avatar_url.as_deref(), Some("https://img/a.png"));
+        assert!(!profile_exists(pool, "old_name").await.unwrap());
+
+        // Same identity again is a no-op
+        assert!(!update_profile_identity(pool, id, "new_name", "u1", Some("https://img/a.png")).await.unwrap());
+
+        // Clashing with another saved profile is an error, not an overwrite
+        assert!(update_profile_identity(pool, other, "new_name", "u2", None).await.is_err());
+        assert_eq!(get_profile(pool, other).await.unwrap().unwrap().username, "taken");
+    }
+
+    #[tokio::test]
+    async fn test_selecting_profile_records_last_use() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        let id = create_profile(pool, "tester", None, &dummy_token()).await.unwrap();
+        assert!(get_profile(pool, id).await.unwrap().unwrap().last_used_at.is_none());
+
+        set_active_profile(pool, id).await.unwrap();
+        assert!(get_profile(pool, id).await.unwrap().unwrap().last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deleting_a_profile_keeps_its_trade_journal() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        let id = create_profile(pool, "tester", None, &dummy_token()).await.unwrap();
+        let trade = crate::sqlite::NewTransaction {
+            symbol: "KEEP",
+            trade_type: "BUY",
+            coin_amount: 10.0,
+            price: 1.0,
+            usd_value: 10.0,
+            note: None,
+        };
+        crate::sqlite::log_transaction(pool, id, &trade).await.unwrap();
+
+        // Other references still hold the profile in place
+        let sentinel = crate::sqlite::create_sentinel(pool, id, "KEEP", Some(-10.0), None, None, 100.0, 1.0).await.unwrap();
+        assert!(delete_profile(pool, id).await.is_err());
+        assert!(get_profile(pool, id).await.unwrap().is_some());
+        crate::sqlite::delete_sentinel(pool, sentinel).await.unwrap();
+
+        delete_profile(pool, id).await.unwrap();
+        assert!(get_profile(pool, id).await.unwrap().is_none());
+        let journal = crate::sqlite::get_transactions(pool, id, 10, 0, None, None).await.unwrap();
+        assert_eq!(journal.len(), 1);
+    }
+}
@@ -0,0 +1,208 @@
+//! History retention — prune old log rows
+//!
+//! `automation_log`, `snipe_log`, `decision_events` and triggered sentinels
+//! grow without bound.
+//! Pruning deletes rows older than a cutoff; automation log totals are first
+//! folded into `history_rollup` so lifetime per-module figures survive.
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Rows removed by a prune pass
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneReport {
+    pub automation_logs: u64,
+    pub snipe_logs: u64,
+    pub triggered_sentinels: u64,
+    pub decision_events: u64,
+}
+
+impl PruneReport {
+    pub fn total(&self) -> u64 {
+        self.automation_logs + self.snipe_logs + self.triggered_sentinels + self.decision_events
+    }
+}
+
+/// Aggregated totals of pruned automation log rows
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRollupRow {
+    pub profile_id: i64,
+    pub module: String,
+    pub action: String,
+    pub entries: i64,
+    pub amount_usd: f64,
+}
+
+/// Delete history rows older than `older_than_days` days.
+///
+/// Automation log rows are rolled up per (profile, module, action) before
+/// being deleted. Runs in a single transaction.
+pub async fn prune_history(pool: &SqlitePool, older_than_days: u32) -> Result<PruneReport> {
+    let cutoff = format!("-{} days", older_than_days);
+    let db_err = |e: sqlx::Error| Error::DatabaseError(e.to_string());
+
+    let mut tx = pool.begin().await.map_err(db_err)?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO history_rollup (profile_id, module, action, entries, amount_usd)
+        SELECT profile_id, module, action, COUNT(*), SUM(amount_usd)
+        FROM automation_log
+        WHERE created_at < datetime('now', ?1)
+        GROUP BY profile_id, module, action
+        ON CONFLICT(profile_id, module, action) DO UPDATE SET
+            entries = entries + excluded.entries,
+            amount_usd = amount_usd + excluded.amount_usd
+        "#,
+    )
+    .bind(&cutoff)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_err)?;
+
+    let automation_logs = sqlx::query("DELETE FROM automation_log WHERE created_at < datetime('now', ?1)")
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?
+        .rows_affected();
+
+    let snipe_logs = sqlx::query("DELETE FROM snipe_log WHERE created_at < datetime('now', ?1)")
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_err)?
+        .rows_affected();
+
+    let triggered_sentinels = sqlx::query(
+        "DELETE FROM sentinels WHERE triggered_at IS NOT NULL AND triggered_at < datetime('now', ?1)",
+    )
+    .bind(&cutoff)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_err)?
+    .rows_affected();
+
+    let decision_events = sqlx::query(
+        "DELETE FROM decision_events WHERE recorded_at < CAST(strftime('%s', 'now', ?1) AS INTEGER)",
+    )
+    .bind(&cutoff)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_err)?
+    .rows_affected();
+
+    tx.commit().await.map_err(db_err)?;
+
+    Ok(PruneReport {
+        automation_logs,
+        snipe_logs,
+        triggered_sentinels,
+        decision_events,
+    })
+}
+
+/// Get the rolled-up totals of pruned automation logs for a profile
+pub async fn get_history_rollup(pool: &SqlitePool, profile_id: i64) -> Result<Vec<HistoryRollupRow>> {
+    sqlx::query_as::<_, HistoryRollupRow>(
+        "SELECT profile_id, module, action, entries, amount_usd FROM history_rollup \
+         WHERE profile_id = ? ORDER BY module, action",
+    )
+    .bind(profile_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profile;
+
+    async fn insert_log(pool: &SqlitePool, module: &str, amount: f64, age_days: i64) {
+        sqlx::query(
+            "INSERT INTO automation_log (profile_id, module, symbol, coin_name, action, amount_usd, created_at) \
+             VALUES (1, ?, 'TEST', 'Test', 'BUY', ?, datetime('now', ?))",
+        )
+        .bind(module)
+        .bind(amount)
+        .bind(format!("-{} days", age_days))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn insert_snipe(pool: &SqlitePool, age_days: i64) {
+        sqlx::query(
+            "INSERT INTO snipe_log (profile_id, symbol, coin_name, buy_amount_usd, market_cap, price, coin_age_secs, created_at) \
+             VALUES (1, 'NEW', 'New', 10.0, 1000.0, 0.01, 30, datetime('now', ?))",
+        )
+        .bind(format!("-{} days", age_days))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    async fn count(pool: &SqlitePool, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_prune_removes_only_rows_outside_window() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+
+        insert_log(pool, "sniper", 100.0, 45).await;
+        insert_log(pool, "sniper", 50.0, 31).await;
+        insert_log(pool, "sniper", 25.0, 2).await;
+        insert_snipe(pool, 60).await;
+        insert_snipe(pool, 1).await;
+        let now = chrono::Utc::now().timestamp();
+        crate::sqlite::save_decision_config(pool, "h", "dipbuyer", "{}").await.unwrap();
+        crate::sqlite::append_decision_event(pool, 1, "dipbuyer", now - 40 * 86400, "h", "{}", "{}").await.unwrap();
+        crate::sqlite::append_decision_event(pool, 1, "dipbuyer", now - 3600, "h", "{}", "{}").await.unwrap();
+
+        let report = prune_history(pool, 30).await.unwrap();
+
+        assert_eq!(report.automation_logs, 2);
+        assert_eq!(report.snipe_logs, 1);
+        assert_eq!(report.decision_events, 1);
+        assert_eq!(report.total(), 4);
+        assert_eq!(count(pool, "automation_log").await, 1);
+        assert_eq!(count(pool, "snipe_log").await, 1);
+        assert_eq!(count(pool, "decision_events").await, 1);
+
+        let remaining: f64 = sqlx::query_scalar("SELECT amount_usd FROM automation_log")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, 25.0);
+    }
+
+    #[tokio::test]
+    async fn test_pruned_totals_are_rolled_up() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+
+        insert_log(pool, "dipbuyer", 100.0, 40).await;
+        prune_history(pool, 30).await.unwrap();
+        insert_log(pool, "dipbuyer", 60.0, 35).await;
+        insert_log(pool, "dipbuyer", 5.0, 1).await;
+        prune_history(pool, 30).await.unwrap();
+
+        let rollup = get_history_rollup(pool, 1).await.unwrap();
+        assert_eq!(rollup.len(), 1);
+        assert_eq!(rollup[0].module, "dipbuyer");
+        assert_eq!(rollup[0].entries, 2);
+        assert_eq!(rollup[0].amount_usd, 160.0);
+
+        // Nothing left to prune is a no-op
+        assert_eq!(prune_history(pool, 30).await.unwrap(), PruneReport::default());
+    }
+}
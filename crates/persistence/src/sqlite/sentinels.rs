@@ -19,6 +19,76 @@ pub struct SentinelRow {
     pub is_active: bool,
     pub created_at: Option<String>,
     pub triggered_at: Option<String>,
+    /// Price source override ("last_trade" or "mark"); `None` uses the global setting
+    #[sqlx(default)]
+    #[serde(default)]
+    pub price_source: Option<String>,
+    /// Sell percentage when the stop-loss fires; `None` uses `sell_percentage`
+    #[sqlx(default)]
+    #[serde(default)]
+    pub sl_sell_pct: Option<f64>,
+    /// Sell percentage when the take-profit fires; `None` uses `sell_percentage`
+    #[sqlx(default)]
+    #[serde(default)]
+    pub tp_sell_pct: Option<f64>,
+    /// Sell percentage when the trailing stop fires; `None` uses `sell_percentage`
+    #[sqlx(default)]
+    #[serde(default)]
+    pub ts_sell_pct: Option<f64>,
+    /// Take-profit ladder as a JSON array of [`TpRung`]s; `None` uses `take_profit_pct`
+    #[sqlx(default)]
+    #[serde(default)]
+    pub tp_ladder: Option<String>,
+    /// Rungs of the ladder already sold into
+    #[sqlx(default)]
+    #[serde(default)]
+    pub tp_rungs_filled: i64,
+    /// Trigger whose last sell only partly went out (a split sell stopped
+    /// early, or the take-profit gap cap held it back)
+    #[sqlx(default)]
+    #[serde(default)]
+    pub unsold_trigger: Option<String>,
+    /// Coins that trigger still owes; its next sell is for the rest
+    #[sqlx(default)]
+    #[serde(default)]
+    pub unsold_qty: Option<f64>,
+}
+
+/// One take-profit rung: sell `sell_pct`% of the position once the price
+/// is `gain_pct`% above entry
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TpRung {
+    pub gain_pct: f64,
+    pub sell_pct: f64,
+}
+
+impl SentinelRow {
+    /// The stored take-profit ladder, if it has one. An unreadable or
+    /// empty ladder counts as none.
+    pub fn tp_rungs(&self) -> Option<Vec<TpRung>> {
+        self.tp_ladder
+            .as_deref()
+            .and_then(|json| serde_json::from_str::<Vec<TpRung>>(json).ok())
+            .filter(|rungs| !rungs.is_empty())
+    }
+
+    /// Coins `trigger_type` still owes from a sell that only partly went out
+    pub fn unsold_for(&self, trigger_type: &str) -> Option<f64> {
+        self.unsold_qty
+            .filter(|qty| qty.is_finite() && *qty > 0.0)
+            .filter(|_| self.unsold_trigger.as_deref() == Some(trigger_type))
+    }
+}
+
+/// Stop-loss and take-profit are percentages of the entry, so a zero or
+/// missing entry would arm a sentinel that fires on any price
+fn check_entry_price(entry_price: f64) -> Result<()> {
+    if entry_price.is_finite() && entry_price > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::InvalidData(format!("Invalid sentinel entry price {}", entry_price)))
+    }
 }
 
 /// Create a new sentinel (raw insert, no duplicate check).
@@ -33,6 +103,7 @@ pub async fn create_sentinel(
     sell_percentage: f64,
     entry_price: f64,
 ) -> Result<i64> {
+    check_entry_price(entry_price)?;
     let result = sqlx::query(
         r#"
         INSERT INTO sentinels (profile_id, symbol, stop_loss_pct, take_profit_pct, 
@@ -58,7 +129,8 @@ pub async fn create_sentinel(
 
 /// Create or update a sentinel for a coin. If an active, non-triggered sentinel
 /// already exists for this profile+symbol, update its entry price instead of
-/// creating a duplicate. Returns the sentinel ID.
+/// creating a duplicate. A new entry price starts its take-profit ladder over,
+/// since the rungs are gains above the entry. Returns the sentinel ID.
 pub async fn upsert_sentinel(
     pool: &SqlitePool,
     profile_id: i64,
@@ -69,11 +141,14 @@ pub async fn upsert_sentinel(
     sell_percentage: f64,
     entry_price: f64,
 ) -> Result<i64> {
+    check_entry_price(entry_price)?;
     let existing = sqlx::query_as::<_, SentinelRow>(
         r#"
         SELECT id, profile_id, symbol, stop_loss_pct, take_profit_pct,
                trailing_stop_pct, sell_percentage, entry_price,
-               highest_price_seen, is_active, created_at, triggered_at
+               highest_price_seen, is_active, created_at, triggered_at, price_source,
+               sl_sell_pct, tp_sell_pct, ts_sell_pct, tp_ladder, tp_rungs_filled,
+               unsold_trigger, unsold_qty
         FROM sentinels
         WHERE profile_id = ? AND symbol = ? AND triggered_at IS NULL
         ORDER BY created_at DESC
@@ -92,10 +167,13 @@ pub async fn upsert_sentinel(
             sqlx::query(
                 r#"
                 UPDATE sentinels
-                SET entry_price = ?, highest_price_seen = ?, is_active = 1,
-                    stop_loss_pct = ?, take_profit_pct = ?, trailing_stop_pct = ?,
-                    sell_percentage = ?
-                WHERE id = ?
+                SET entry_price = ?1, highest_price_seen = ?2, is_active = 1,
+                    stop_loss_pct = ?3, take_profit_pct = ?4, trailing_stop_pct = ?5,
+                    sell_percentage = ?6,
+                    tp_rungs_filled = CASE WHEN entry_price = ?1 THEN tp_rungs_filled ELSE 0 END,
+                    unsold_trigger = CASE WHEN entry_price = ?1 THEN unsold_trigger END,
+                    unsold_qty = CASE WHEN entry_price = ?1 THEN unsold_qty END
+                WHERE id = ?7
                 "#,
             )
             .bind(entry_price)
@@ -147,7 +225,9 @@ pub async fn get_sentinels(pool: &SqlitePool, profile_id: i64) -> Result<Vec<Sen
         r#"
         SELECT id, profile_id, symbol, stop_loss_pct, take_profit_pct, 
                trailing_stop_pct, sell_percentage, entry_price, 
-               highest_price_seen, is_active, created_at, triggered_at
+               highest_price_seen, is_active, created_at, triggered_at, price_source,
+               sl_sell_pct, tp_sell_pct, ts_sell_pct, tp_ladder, tp_rungs_filled,
+               unsold_trigger, unsold_qty
         FROM sentinels
         WHERE profile_id = ?
         ORDER BY created_at DESC
@@ -167,7 +247,9 @@ pub async fn get_active_sentinels(pool: &SqlitePool) -> Result<Vec<SentinelRow>>
         r#"
         SELECT id, profile_id, symbol, stop_loss_pct, take_profit_pct, 
                trailing_stop_pct, sell_percentage, entry_price, 
-               highest_price_seen, is_active, created_at, triggered_at
+               highest_price_seen, is_active, created_at, triggered_at, price_source,
+               sl_sell_pct, tp_sell_pct, ts_sell_pct, tp_ladder, tp_rungs_filled,
+               unsold_trigger, unsold_qty
         FROM sentinels
         WHERE is_active = 1
         "#,
@@ -200,7 +282,8 @@ pub async fn mark_sentinel_triggered(pool: &SqlitePool, sentinel_id: i64) -> Res
     sqlx::query(
         r#"
         UPDATE sentinels 
-        SET is_active = 0, triggered_at = CURRENT_TIMESTAMP 
+        SET is_active = 0, triggered_at = CURRENT_TIMESTAMP,
+            unsold_trigger = NULL, unsold_qty = NULL
         WHERE id = ?
         "#,
     )
@@ -228,6 +311,112 @@ pub async fn set_sentinel_active(
     Ok(())
 }
 
+/// Set or clear a sentinel's price source override
+pub async fn set_sentinel_price_source(
+    pool: &SqlitePool,
+    sentinel_id: i64,
+    price_source: Option<&str>,
+) -> Result<()> {
+    sqlx::query("UPDATE sentinels SET price_source = ? WHERE id = ?")
+        .bind(price_source)
+        .bind(sentinel_id)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Set or clear a sentinel's per-trigger sell percentages
+pub async fn set_sentinel_trigger_sells(
+    pool: &SqlitePool,
+    sentinel_id: i64,
+    sl_sell_pct: Option<f64>,
+    tp_sell_pct: Option<f64>,
+    ts_sell_pct: Option<f64>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE sentinels
+        SET sl_sell_pct = ?, tp_sell_pct = ?, ts_sell_pct = ?, has_custom_settings = 1
+        WHERE id = ?
+        "#,
+    )
+    .bind(sl_sell_pct)
+    .bind(tp_sell_pct)
+    .bind(ts_sell_pct)
+    .bind(sentinel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Set or clear a sentinel's take-profit ladder. Any rungs already filled
+/// are forgotten, so the new ladder starts from its first rung.
+pub async fn set_sentinel_tp_ladder(
+    pool: &SqlitePool,
+    sentinel_id: i64,
+    ladder: Option<&[TpRung]>,
+) -> Result<()> {
+    let json = ladder
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+    sqlx::query(
+        r#"
+        UPDATE sentinels
+        SET tp_ladder = ?, tp_rungs_filled = 0, has_custom_settings = 1
+        WHERE id = ?
+        "#,
+    )
+    .bind(json)
+    .bind(sentinel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Mark the next take-profit rung as filled, keeping the sentinel armed
+/// for the one after it
+pub async fn fill_tp_rung(pool: &SqlitePool, sentinel_id: i64) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE sentinels
+        SET tp_rungs_filled = tp_rungs_filled + 1, unsold_trigger = NULL, unsold_qty = NULL
+        WHERE id = ?
+        "#,
+    )
+    .bind(sentinel_id)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Record that a sell for `trigger_type` left `quantity` coins unsold. The
+/// sentinel stays armed, and that trigger's next sell is for the rest.
+pub async fn set_sentinel_unsold(
+    pool: &SqlitePool,
+    sentinel_id: i64,
+    trigger_type: &str,
+    quantity: f64,
+) -> Result<()> {
+    sqlx::query("UPDATE sentinels SET unsold_trigger = ?, unsold_qty = ? WHERE id = ?")
+        .bind(trigger_type)
+        .bind(quantity)
+        .bind(sentinel_id)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
 /// Delete a sentinel
 pub async fn delete_sentinel(pool: &SqlitePool, sentinel_id: i64) -> Result<()> {
     sqlx::query("DELETE FROM sentinels WHERE id = ?")
@@ -245,7 +434,9 @@ pub async fn get_sentinel_by_id(pool: &SqlitePool, sentinel_id: i64) -> Result<O
         r#"
         SELECT id, profile_id, symbol, stop_loss_pct, take_profit_pct, 
                trailing_stop_pct, sell_percentage, entry_price, 
-               highest_price_seen, is_active, created_at, triggered_at
+               highest_price_seen, is_active, created_at, triggered_at, price_source,
+               sl_sell_pct, tp_sell_pct, ts_sell_pct, tp_ladder, tp_rungs_filled,
+               unsold_trigger, unsold_qty
         FROM sentinels
         WHERE id = ?
         "#,
@@ -269,7 +460,8 @@ pub async fn rearm_sentinel(
     sqlx::query(
         r#"
         UPDATE sentinels
-        SET entry_price = ?, highest_price_seen = ?, triggered_at = NULL, is_active = 1
+        SET entry_price = ?, highest_price_seen = ?, triggered_at = NULL, is_active = 1,
+            unsold_trigger = NULL, unsold_qty = NULL
         WHERE id = ?
         "#,
     )
@@ -438,6 +630,7 @@ pub async fn sync_entry_price(
     sentinel_id: i64,
     avg_entry_price: f64,
 ) -> Result<()> {
+    check_entry_price(avg_entry_price)?;
     sqlx::query(
         "UPDATE sentinels SET entry_price = ? WHERE id = ?",
     )
@@ -540,3 +733,132 @@ pub async fn remove_blacklisted_sentinels(
 
     Ok(result.rows_affected())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::Database;
+    use crate::test_support::{db_with_profile, insert_profile};
+
+    #[tokio::test]
+    async fn test_price_source_override_round_trips() {
+        let dir = std::env::temp_dir().join(format!("rugplay-sentinel-src-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db = Database::connect(&dir.join("test.db")).await.unwrap();
+        let pool = db.pool();
+
+        insert_profile(pool, 1).await;
+        let id = upsert_sentinel(pool, 1, "TEST", Some(-20.0), Some(100.0), None, 100.0, 1.0)
+            .await
+            .unwrap();
+
+        let row = get_sentinel_by_id(pool, id).await.unwrap().unwrap();
+        assert_eq!(row.price_source, None);
+
+        set_sentinel_price_source(pool, id, Some("mark")).await.unwrap();
+        let rows = get_sentinels(pool, 1).await.unwrap();
+        assert_eq!(rows[0].price_source.as_deref(), Some("mark"));
+
+        set_sentinel_price_source(pool, id, None).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().price_source, None);
+
+        pool.close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[tokio::test]
+    async fn test_no_sentinel_is_armed_without_an_entry_price() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+
+        for entry in [0.0, -1.0, f64::NAN] {
+            assert!(create_sentinel(pool, 1, "TEST", Some(-20.0), None, None, 100.0, entry).await.is_err());
+            assert!(upsert_sentinel(pool, 1, "TEST", Some(-20.0), None, None, 100.0, entry).await.is_err());
+        }
+        assert!(get_sentinels(pool, 1).await.unwrap().is_empty());
+
+        let id = upsert_sentinel(pool, 1, "TEST", Some(-20.0), None, None, 100.0, 2.0).await.unwrap();
+        assert!(sync_entry_price(pool, id, 0.0).await.is_err());
+        assert!(upsert_sentinel(pool, 1, "TEST", Some(-20.0), None, None, 100.0, 0.0).await.is_err());
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().entry_price, 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_trigger_sell_percentages_round_trip() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+        let id = upsert_sentinel(pool, 1, "TEST", Some(-20.0), Some(100.0), Some(10.0), 100.0, 1.0)
+            .await
+            .unwrap();
+        let row = get_sentinel_by_id(pool, id).await.unwrap().unwrap();
+        assert_eq!((row.sl_sell_pct, row.tp_sell_pct, row.ts_sell_pct), (None, None, None));
+
+        set_sentinel_trigger_sells(pool, id, None, Some(50.0), Some(25.0)).await.unwrap();
+        let row = &get_active_sentinels(pool).await.unwrap()[0];
+        assert_eq!((row.sl_sell_pct, row.tp_sell_pct, row.ts_sell_pct), (None, Some(50.0), Some(25.0)));
+    }
+
+    #[tokio::test]
+    async fn test_tp_ladder_round_trips_and_counts_filled_rungs() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+        let id = upsert_sentinel(pool, 1, "TEST", Some(-20.0), Some(100.0), None, 100.0, 1.0)
+            .await
+            .unwrap();
+        let row = get_sentinel_by_id(pool, id).await.unwrap().unwrap();
+        assert_eq!((row.tp_rungs(), row.tp_rungs_filled), (None, 0));
+
+        let ladder = [
+            TpRung { gain_pct: 100.0, sell_pct: 25.0 },
+            TpRung { gain_pct: 300.0, sell_pct: 25.0 },
+            TpRung { gain_pct: 1000.0, sell_pct: 50.0 },
+        ];
+        set_sentinel_tp_ladder(pool, id, Some(&ladder)).await.unwrap();
+        fill_tp_rung(pool, id).await.unwrap();
+        fill_tp_rung(pool, id).await.unwrap();
+        let row = &get_active_sentinels(pool).await.unwrap()[0];
+        assert_eq!(row.tp_rungs().unwrap(), ladder);
+        assert_eq!(row.tp_rungs_filled, 2);
+
+        // Re-syncing the same entry keeps the filled rungs; a new entry starts over
+        upsert_sentinel(pool, 1, "TEST", Some(-20.0), Some(100.0), None, 100.0, 1.0).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().tp_rungs_filled, 2);
+        set_sentinel_unsold(pool, id, "take_profit", 3.0).await.unwrap();
+        assert_eq!(upsert_sentinel(pool, 1, "TEST", Some(-20.0), Some(100.0), None, 100.0, 1.5).await.unwrap(), id);
+        let row = get_sentinel_by_id(pool, id).await.unwrap().unwrap();
+        assert_eq!((row.entry_price, row.tp_rungs_filled, row.unsold_qty), (1.5, 0, None));
+        fill_tp_rung(pool, id).await.unwrap();
+        fill_tp_rung(pool, id).await.unwrap();
+
+        // A new ladder starts over; clearing it goes back to the single take-profit
+        set_sentinel_tp_ladder(pool, id, Some(&ladder[..1])).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().tp_rungs_filled, 0);
+        set_sentinel_tp_ladder(pool, id, None).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().tp_rungs(), None);
+    }
+
+    #[tokio::test]
+    async fn test_unsold_remainder_is_kept_until_the_trigger_completes() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+        let id = upsert_sentinel(pool, 1, "TEST", Some(-20.0), Some(100.0), None, 100.0, 1.0)
+            .await
+            .unwrap();
+
+        set_sentinel_unsold(pool, id, "stop_loss", 40.0).await.unwrap();
+        let row = get_sentinel_by_id(pool, id).await.unwrap().unwrap();
+        assert_eq!(row.unsold_for("stop_loss"), Some(40.0));
+        assert_eq!(row.unsold_for("take_profit"), None);
+        assert!(row.is_active && row.triggered_at.is_none());
+
+        // Filling a rung, re-arming or triggering settles what was owed
+        fill_tp_rung(pool, id).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().unsold_qty, None);
+        set_sentinel_unsold(pool, id, "take_profit", 5.0).await.unwrap();
+        rearm_sentinel(pool, id, 2.0).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().unsold_qty, None);
+        set_sentinel_unsold(pool, id, "stop_loss", 5.0).await.unwrap();
+        mark_sentinel_triggered(pool, id).await.unwrap();
+        assert_eq!(get_sentinel_by_id(pool, id).await.unwrap().unwrap().unsold_for("stop_loss"), None);
+    }
+}
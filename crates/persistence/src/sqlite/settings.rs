@@ -0,0 +1,134 @@
+//! Key/value settings
+//!
+//! Most settings have a single owner that writes the whole value, so a plain
+//! upsert is enough. Values several tasks add to (the sniper's sniped symbols
+//! and their timestamps, harvester claim counts) go through [`update_setting_json`]
+//! instead: it reads, modifies and writes the value inside one
+//! `BEGIN IMMEDIATE` transaction, which takes SQLite's write lock up front.
+//! Concurrent updates of a key therefore run one after another (waiting up to
+//! the connection's busy timeout) rather than overwriting each other.
+
+use rugplay_core::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+/// Read a raw setting value
+pub async fn get_setting(pool: &SqlitePool, key: &str) -> Result<Option<String>> {
+    sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+/// Replace a setting value
+pub async fn set_setting(pool: &SqlitePool, key: &str, value: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = ?2",
+    )
+    .bind(key)
+    .bind(value)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+/// Atomically update a JSON setting: `update` receives the stored value
+/// (default when missing) and whatever it leaves behind is written back.
+/// Returns what `update` returned. A stored value that doesn't parse as `T`
+/// is an [`Error::InvalidData`] and is left untouched rather than replaced.
+pub async fn update_setting_json<T, R>(pool: &SqlitePool, key: &str, update: impl FnOnce(&mut T) -> R) -> Result<R>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    let db_err = |e: sqlx::Error| Error::DatabaseError(e.to_string());
+    let mut tx = pool.begin_with("BEGIN IMMEDIATE").await.map_err(db_err)?;
+
+    let stored: Option<String> = sqlx::query_scalar("SELECT value FROM settings WHERE key = ?")
+        .bind(key)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_err)?;
+    let mut value: T = match stored {
+        Some(json) => serde_json::from_str(&json)
+            .map_err(|e| Error::InvalidData(format!("setting '{}' is unreadable: {}", key, e)))?,
+        None => T::default(),
+    };
+
+    let result = update(&mut value);
+
+    let json = serde_json::to_string(&value).map_err(|e| Error::DatabaseError(e.to_string()))?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = ?2",
+    )
+    .bind(key)
+    .bind(&json)
+    .execute(&mut *tx)
+    .await
+    .map_err(db_err)?;
+
+    tx.commit().await.map_err(db_err)?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sqlite::Database;
+    use std::collections::{BTreeSet, HashMap};
+
+    #[tokio::test]
+    async fn test_concurrent_updates_of_one_key_are_not_lost() {
+        let dir = std::env::temp_dir().join(format!("rugplay-settings-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let db = Database::connect(&dir.join("test.db")).await.unwrap();
+
+        // Like several loops each recording symbols into the same blobs
+        let mut tasks = Vec::new();
+        for task in 0..8 {
+            let pool = db.pool().clone();
+            tasks.push(tokio::spawn(async move {
+                for i in 0..25 {
+                    let symbol = format!("S{}_{}", task, i);
+                    update_setting_json(&pool, "symbols", |set: &mut BTreeSet<String>| set.insert(symbol.clone()))
+                        .await?;
+                    update_setting_json(&pool, "timestamps", |map: &mut HashMap<String, i64>| {
+                        map.insert(symbol, i);
+                    })
+                    .await?;
+                }
+                Ok::<_, Error>(())
+            }));
+        }
+        for task in tasks {
+            task.await.unwrap().expect("concurrent update failed");
+        }
+
+        let symbols: BTreeSet<String> =
+            serde_json::from_str(&get_setting(db.pool(), "symbols").await.unwrap().unwrap()).unwrap();
+        assert_eq!(symbols.len(), 200);
+        let timestamps: HashMap<String, i64> =
+            serde_json::from_str(&get_setting(db.pool(), "timestamps").await.unwrap().unwrap()).unwrap();
+        assert_eq!(timestamps.len(), 200);
+        assert_eq!(timestamps["S7_24"], 24);
+
+        // The closure's result is handed back: a repeat insert reports false
+        let inserted = update_setting_json(db.pool(), "symbols", |set: &mut BTreeSet<String>| set.insert("S0_0".to_string()))
+            .await
+            .unwrap();
+        assert!(!inserted);
+
+        // A value of another shape is reported and kept, not reset to the default
+        set_setting(db.pool(), "counter", "\"not a number\"").await.unwrap();
+        let err = update_setting_json(db.pool(), "counter", |n: &mut u32| *n += 1).await.unwrap_err();
+        assert!(matches!(err, Error::InvalidData(_)), "{:?}", err);
+        assert_eq!(get_setting(db.pool(), "counter").await.unwrap().as_deref(), Some("\"not a number\""));
+
+        db.pool().close().await;
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,87 @@
+//! Loop snapshots — in-memory automation state saved across restarts
+//!
+//! Background loops keep dedup sets, cooldowns and daily spend in memory.
+//! On shutdown each loop writes its state here as JSON, keyed by module and
+//! profile, and takes it back verbatim on the next start. A snapshot is
+//! consumed when read, so a crash afterwards can't restore stale state.
+
+use rugplay_core::{Error, Result};
+use sqlx::SqlitePool;
+
+/// Store (or replace) the snapshot for a module and profile
+pub async fn save_loop_snapshot(
+    pool: &SqlitePool,
+    module: &str,
+    profile_id: i64,
+    state_json: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO loop_snapshots (module, profile_id, state, saved_at)
+        VALUES (?1, ?2, ?3, CURRENT_TIMESTAMP)
+        ON CONFLICT(module, profile_id) DO UPDATE SET
+            state = excluded.state,
+            saved_at = excluded.saved_at
+        "#,
+    )
+    .bind(module)
+    .bind(profile_id)
+    .bind(state_json)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Read and delete the snapshot for a module and profile
+pub async fn take_loop_snapshot(
+    pool: &SqlitePool,
+    module: &str,
+    profile_id: i64,
+) -> Result<Option<String>> {
+    sqlx::query_scalar::<_, String>(
+        "DELETE FROM loop_snapshots WHERE module = ? AND profile_id = ? RETURNING state",
+    )
+    .bind(module)
+    .bind(profile_id)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profiles;
+
+    #[tokio::test]
+    async fn test_snapshot_round_trips_once_per_module_and_profile() {
+        let db = db_with_profiles(2).await;
+        let pool = db.pool();
+
+        save_loop_snapshot(pool, "dipbuyer", 1, r#"{"old":true}"#).await.unwrap();
+        save_loop_snapshot(pool, "dipbuyer", 1, r#"{"seen":["k1"]}"#).await.unwrap();
+        save_loop_snapshot(pool, "mirror", 1, "{}").await.unwrap();
+
+        // Other profiles and modules don't see it
+        assert_eq!(take_loop_snapshot(pool, "dipbuyer", 2).await.unwrap(), None);
+        assert_eq!(take_loop_snapshot(pool, "mirror", 1).await.unwrap().as_deref(), Some("{}"));
+
+        // Latest save wins and is consumed by the read
+        assert_eq!(
+            take_loop_snapshot(pool, "dipbuyer", 1).await.unwrap().as_deref(),
+            Some(r#"{"seen":["k1"]}"#)
+        );
+        assert_eq!(take_loop_snapshot(pool, "dipbuyer", 1).await.unwrap(), None);
+
+        // Deleting a profile drops its snapshots
+        save_loop_snapshot(pool, "sniper", 2, "{}").await.unwrap();
+        crate::sqlite::delete_profile(pool, 2).await.unwrap();
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM loop_snapshots")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+}
@@ -15,30 +15,41 @@ pub struct TransactionRow {
     pub price: f64,
     pub usd_value: f64,
     pub timestamp: Option<String>,
+    /// Why the trade was made: the user's note for manual trades, the
+    /// module's reason string for automated ones
+    pub note: Option<String>,
+}
+
+/// A trade to record in the local journal
+#[derive(Debug, Clone, Copy)]
+pub struct NewTransaction<'a> {
+    pub symbol: &'a str,
+    pub trade_type: &'a str,
+    pub coin_amount: f64,
+    pub price: f64,
+    pub usd_value: f64,
+    pub note: Option<&'a str>,
 }
 
 /// Log a new transaction
 pub async fn log_transaction(
     pool: &SqlitePool,
     profile_id: i64,
-    symbol: &str,
-    trade_type: &str,
-    coin_amount: f64,
-    price: f64,
-    usd_value: f64,
+    tx: &NewTransaction<'_>,
 ) -> Result<i64> {
     let result = sqlx::query(
         r#"
-        INSERT INTO transactions (profile_id, symbol, trade_type, coin_amount, price, usd_value)
-        VALUES (?, ?, ?, ?, ?, ?)
+        INSERT INTO transactions (profile_id, symbol, trade_type, coin_amount, price, usd_value, note)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
         "#,
     )
     .bind(profile_id)
-    .bind(symbol)
-    .bind(trade_type)
-    .bind(coin_amount)
-    .bind(price)
-    .bind(usd_value)
+    .bind(tx.symbol)
+    .bind(tx.trade_type)
+    .bind(tx.coin_amount)
+    .bind(tx.price)
+    .bind(tx.usd_value)
+    .bind(tx.note.map(str::trim).filter(|n| !n.is_empty()))
     .execute(pool)
     .await
     .map_err(|e| Error::DatabaseError(e.to_string()))?;
@@ -46,6 +57,40 @@ pub async fn log_transaction(
     Ok(result.last_insert_rowid())
 }
 
+/// Journal a trade recovered from the server's history, which happened at
+/// `timestamp` (`YYYY-MM-DD HH:MM:SS`, UTC) instead of now. Keyed on the
+/// server's transaction id, so recovering the same trade twice is a no-op;
+/// returns whether a row was written.
+pub async fn log_server_transaction(
+    pool: &SqlitePool,
+    profile_id: i64,
+    server_tx_id: i64,
+    tx: &NewTransaction<'_>,
+    timestamp: &str,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO transactions (profile_id, symbol, trade_type, coin_amount, price, usd_value, note, timestamp, server_tx_id)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(profile_id, server_tx_id) DO NOTHING
+        "#,
+    )
+    .bind(profile_id)
+    .bind(tx.symbol)
+    .bind(tx.trade_type)
+    .bind(tx.coin_amount)
+    .bind(tx.price)
+    .bind(tx.usd_value)
+    .bind(tx.note.map(str::trim).filter(|n| !n.is_empty()))
+    .bind(timestamp)
+    .bind(server_tx_id)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
 /// Get transactions for a profile with optional filters
 pub async fn get_transactions(
     pool: &SqlitePool,
@@ -57,7 +102,7 @@ pub async fn get_transactions(
 ) -> Result<Vec<TransactionRow>> {
     let mut query = String::from(
         r#"
-        SELECT id, profile_id, symbol, trade_type, coin_amount, price, usd_value, timestamp
+        SELECT id, profile_id, symbol, trade_type, coin_amount, price, usd_value, timestamp, note
         FROM transactions
         WHERE profile_id = ?
         "#
@@ -70,7 +115,7 @@ pub async fn get_transactions(
         query.push_str(" AND symbol = ?");
     }
 
-    query.push_str(" ORDER BY timestamp DESC LIMIT ? OFFSET ?");
+    query.push_str(" ORDER BY timestamp DESC, id DESC LIMIT ? OFFSET ?");
 
     let mut builder = sqlx::query_as::<_, TransactionRow>(&query)
         .bind(profile_id);
@@ -123,3 +168,54 @@ pub async fn get_traded_symbols(
 
     Ok(rows.into_iter().map(|r| r.0).collect())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profile;
+
+    fn trade<'a>(symbol: &'a str, trade_type: &'a str, note: Option<&'a str>) -> NewTransaction<'a> {
+        NewTransaction { symbol, trade_type, coin_amount: 10.0, price: 2.0, usd_value: 20.0, note }
+    }
+
+    #[tokio::test]
+    async fn test_notes_persist_and_come_back_through_filters() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+
+        log_transaction(pool, 1, &trade("AAA", "BUY", Some("testing thesis X"))).await.unwrap();
+        log_transaction(pool, 1, &trade("AAA", "SELL", Some("Sentinel: stop loss hit"))).await.unwrap();
+        log_transaction(pool, 1, &trade("BBB", "BUY", None)).await.unwrap();
+        // Blank notes are stored as no note
+        log_transaction(pool, 1, &trade("BBB", "SELL", Some("   "))).await.unwrap();
+
+        let buys_of_a = get_transactions(pool, 1, 10, 0, Some("BUY"), Some("AAA")).await.unwrap();
+        assert_eq!(buys_of_a.len(), 1);
+        assert_eq!(buys_of_a[0].note.as_deref(), Some("testing thesis X"));
+
+        let sells = get_transactions(pool, 1, 10, 0, Some("SELL"), None).await.unwrap();
+        let notes: Vec<_> = sells.iter().map(|t| t.note.as_deref()).collect();
+        assert_eq!(notes, [None, Some("Sentinel: stop loss hit")]);
+
+        let of_b = get_transactions(pool, 1, 10, 0, None, Some("BBB")).await.unwrap();
+        assert!(of_b.iter().all(|t| t.note.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_a_server_trade_is_only_recovered_once() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+
+        let at = "2026-01-02 03:04:05";
+        assert!(log_server_transaction(pool, 1, 42, &trade("AAA", "BUY", None), at).await.unwrap());
+        assert!(!log_server_transaction(pool, 1, 42, &trade("AAA", "BUY", None), at).await.unwrap());
+        assert!(log_server_transaction(pool, 1, 43, &trade("AAA", "SELL", None), at).await.unwrap());
+        // Trades the app journaled itself carry no server id and never collide
+        log_transaction(pool, 1, &trade("AAA", "BUY", None)).await.unwrap();
+        log_transaction(pool, 1, &trade("AAA", "BUY", None)).await.unwrap();
+
+        let rows = get_transactions(pool, 1, 10, 0, None, Some("AAA")).await.unwrap();
+        assert_eq!(rows.len(), 4);
+        assert_eq!(rows.iter().filter(|t| t.timestamp.as_deref() == Some(at)).count(), 2);
+    }
+}
@@ -0,0 +1,125 @@
+//! Watchlist — coins a profile follows without holding them
+
+use rugplay_core::{Error, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// A watched coin
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchlistRow {
+    pub profile_id: i64,
+    pub symbol: String,
+    pub added_at: Option<String>,
+    /// Alert when the price moves this many percent (either way) from the
+    /// reference price. None disables alerts for the coin.
+    pub alert_pct: Option<f64>,
+    /// Price the next move is measured from: the price when the coin was
+    /// first priced, reset after each alert
+    pub reference_price: Option<f64>,
+}
+
+/// Add a coin to the watchlist, or update its alert threshold if already watched
+pub async fn add_to_watchlist(
+    pool: &SqlitePool,
+    profile_id: i64,
+    symbol: &str,
+    alert_pct: Option<f64>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO watchlist (profile_id, symbol, alert_pct) VALUES (?1, ?2, ?3)
+        ON CONFLICT(profile_id, symbol) DO UPDATE SET alert_pct = ?3
+        "#,
+    )
+    .bind(profile_id)
+    .bind(symbol)
+    .bind(alert_pct)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Remove a coin from the watchlist. Returns whether it was watched.
+pub async fn remove_from_watchlist(pool: &SqlitePool, profile_id: i64, symbol: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM watchlist WHERE profile_id = ? AND symbol = ?")
+        .bind(profile_id)
+        .bind(symbol)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// All watched coins for a profile, oldest first
+pub async fn get_watchlist(pool: &SqlitePool, profile_id: i64) -> Result<Vec<WatchlistRow>> {
+    sqlx::query_as::<_, WatchlistRow>(
+        r#"
+        SELECT profile_id, symbol, added_at, alert_pct, reference_price
+        FROM watchlist
+        WHERE profile_id = ?
+        ORDER BY added_at ASC, symbol ASC
+        "#,
+    )
+    .bind(profile_id)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))
+}
+
+/// Set the price future alert moves are measured from
+pub async fn set_watchlist_reference_price(
+    pool: &SqlitePool,
+    profile_id: i64,
+    symbol: &str,
+    price: f64,
+) -> Result<()> {
+    sqlx::query("UPDATE watchlist SET reference_price = ? WHERE profile_id = ? AND symbol = ?")
+        .bind(price)
+        .bind(profile_id)
+        .bind(symbol)
+        .execute(pool)
+        .await
+        .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::db_with_profiles;
+
+    #[tokio::test]
+    async fn test_watchlist_crud_is_per_profile() {
+        let db = db_with_profiles(2).await;
+        let pool = db.pool();
+
+        add_to_watchlist(pool, 1, "AAA", None).await.unwrap();
+        add_to_watchlist(pool, 1, "BBB", Some(10.0)).await.unwrap();
+        add_to_watchlist(pool, 2, "AAA", None).await.unwrap();
+
+        let symbols: Vec<_> = get_watchlist(pool, 1).await.unwrap().into_iter().map(|w| w.symbol).collect();
+        assert_eq!(symbols, ["AAA", "BBB"]);
+
+        // Re-adding updates the threshold and keeps the reference price
+        set_watchlist_reference_price(pool, 1, "AAA", 0.5).await.unwrap();
+        add_to_watchlist(pool, 1, "AAA", Some(25.0)).await.unwrap();
+        let list = get_watchlist(pool, 1).await.unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list[0].alert_pct, Some(25.0));
+        assert_eq!(list[0].reference_price, Some(0.5));
+
+        assert!(remove_from_watchlist(pool, 1, "AAA").await.unwrap());
+        assert!(!remove_from_watchlist(pool, 1, "AAA").await.unwrap());
+        assert_eq!(get_watchlist(pool, 1).await.unwrap().len(), 1);
+        // Other profiles are untouched
+        assert_eq!(get_watchlist(pool, 2).await.unwrap()[0].symbol, "AAA");
+
+        crate::sqlite::delete_profile(pool, 2).await.unwrap();
+        assert!(get_watchlist(pool, 2).await.unwrap().is_empty());
+    }
+}
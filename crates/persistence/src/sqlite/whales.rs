@@ -9,8 +9,18 @@ use sqlx::SqlitePool;
 pub struct TrackedWhale {
     pub user_id: String,
     pub username: String,
-    pub performance_score: f64,
+    /// `None` until scored, and while the whale has too little history to score
+    pub performance_score: Option<f64>,
     pub tracked_since: String,
+    /// Oldest trade of this whale the mirror still copies, in seconds;
+    /// `None` uses the mirror's global `max_latency_secs`
+    #[serde(default)]
+    pub max_latency_secs: Option<f64>,
+    /// Latest performance analysis as JSON; `None` until first scored
+    #[serde(default)]
+    pub score_detail: Option<String>,
+    #[serde(default)]
+    pub scored_at: Option<String>,
 }
 
 /// Add a whale to the tracking list
@@ -20,7 +30,7 @@ pub async fn add_whale(
     username: &str,
 ) -> Result<()> {
     sqlx::query(
-        "INSERT OR REPLACE INTO whales (user_id, username) VALUES (?, ?)",
+        "INSERT OR REPLACE INTO whales (user_id, username, performance_score) VALUES (?, ?, NULL)",
     )
     .bind(user_id)
     .bind(username)
@@ -45,7 +55,7 @@ pub async fn remove_whale(pool: &SqlitePool, user_id: &str) -> Result<()> {
 /// List all tracked whales
 pub async fn list_whales(pool: &SqlitePool) -> Result<Vec<TrackedWhale>> {
     let whales = sqlx::query_as::<_, TrackedWhale>(
-        "SELECT user_id, username, performance_score, tracked_since FROM whales ORDER BY tracked_since DESC",
+        "SELECT user_id, username, performance_score, tracked_since, max_latency_secs, score_detail, scored_at FROM whales ORDER BY tracked_since DESC",
     )
     .fetch_all(pool)
     .await
@@ -57,7 +67,7 @@ pub async fn list_whales(pool: &SqlitePool) -> Result<Vec<TrackedWhale>> {
 /// Get a single tracked whale by user_id
 pub async fn get_whale(pool: &SqlitePool, user_id: &str) -> Result<Option<TrackedWhale>> {
     let whale = sqlx::query_as::<_, TrackedWhale>(
-        "SELECT user_id, username, performance_score, tracked_since FROM whales WHERE user_id = ?",
+        "SELECT user_id, username, performance_score, tracked_since, max_latency_secs, score_detail, scored_at FROM whales WHERE user_id = ?",
     )
     .bind(user_id)
     .fetch_optional(pool)
@@ -67,20 +77,39 @@ pub async fn get_whale(pool: &SqlitePool, user_id: &str) -> Result<Option<Tracke
     Ok(whale)
 }
 
-/// Update a whale's performance score
+/// Update a whale's performance score and the analysis behind it (JSON),
+/// stamping when it was computed. A `None` score (too little history) is
+/// stored as NULL, not 0. Returns false if the whale isn't tracked.
 pub async fn update_whale_score(
     pool: &SqlitePool,
     user_id: &str,
-    score: f64,
-) -> Result<()> {
-    sqlx::query("UPDATE whales SET performance_score = ? WHERE user_id = ?")
-        .bind(score)
+    score: Option<f64>,
+    score_detail: &str,
+) -> Result<bool> {
+    let result = sqlx::query(
+        "UPDATE whales SET performance_score = ?, score_detail = ?, scored_at = CURRENT_TIMESTAMP WHERE user_id = ?",
+    )
+    .bind(score)
+    .bind(score_detail)
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .map_err(|e| Error::DatabaseError(e.to_string()))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Set or clear (`None`) a whale's copy latency override.
+/// Returns false if the whale isn't tracked.
+pub async fn set_whale_max_latency(pool: &SqlitePool, user_id: &str, max_latency_secs: Option<f64>) -> Result<bool> {
+    let result = sqlx::query("UPDATE whales SET max_latency_secs = ? WHERE user_id = ?")
+        .bind(max_latency_secs)
         .bind(user_id)
         .execute(pool)
         .await
         .map_err(|e| Error::DatabaseError(e.to_string()))?;
 
-    Ok(())
+    Ok(result.rows_affected() > 0)
 }
 
 /// Count tracked whales
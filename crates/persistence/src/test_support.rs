@@ -0,0 +1,31 @@
+//! Test fixtures: databases with the profiles most tests need
+//!
+//! Built for this crate's tests and, with the `test-support` feature, for
+//! the tests of crates that depend on it.
+
+use crate::sqlite::Database;
+use sqlx::SqlitePool;
+
+/// Add profile `id` (username `tester<id>`, dummy token) to `pool`
+pub async fn insert_profile(pool: &SqlitePool, id: i64) {
+    sqlx::query("INSERT INTO profiles (id, username, token_encrypted, iv) VALUES (?, ?, x'00', x'00')")
+        .bind(id)
+        .bind(format!("tester{}", id))
+        .execute(pool)
+        .await
+        .expect("insert test profile");
+}
+
+/// An in-memory database holding profiles `1..=count`
+pub async fn db_with_profiles(count: i64) -> Database {
+    let db = Database::connect_in_memory().await.expect("open in-memory database");
+    for id in 1..=count {
+        insert_profile(db.pool(), id).await;
+    }
+    db
+}
+
+/// An in-memory database holding profile 1
+pub async fn db_with_profile() -> Database {
+    db_with_profiles(1).await
+}
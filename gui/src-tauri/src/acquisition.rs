@@ -0,0 +1,126 @@
+//! Acquisition Pause — stop opening new positions in one switch
+//!
+//! Disables every buy-side module (sniper, mirror, dipbuyer) at once while
+//! leaving the sentinel monitor untouched, so existing positions stay
+//! protected. Remembers which modules were running so resuming restores
+//! exactly the previous setup.
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+/// A background module that opens new positions
+pub trait BuySideModule {
+    fn module_name(&self) -> &'static str;
+    fn is_enabled(&self) -> bool;
+    fn enable(&self);
+    fn disable(&self);
+}
+
+/// Persisted acquisition pause state (settings key `acquisition_pause`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcquisitionPauseState {
+    pub paused: bool,
+    pub paused_at: Option<String>,
+    /// Modules that were running when the pause began (re-enabled on resume)
+    #[serde(default)]
+    pub resume_modules: Vec<String>,
+}
+
+impl AcquisitionPauseState {
+    /// Disable all buy-side modules, remembering which ones were running.
+    /// Pausing twice keeps the original resume list.
+    pub fn pause(&mut self, modules: &[&dyn BuySideModule]) {
+        if !self.paused {
+            self.resume_modules = modules
+                .iter()
+                .filter(|m| m.is_enabled())
+                .map(|m| m.module_name().to_string())
+                .collect();
+            self.paused = true;
+            self.paused_at = Some(chrono::Utc::now().to_rfc3339());
+        }
+        for module in modules {
+            module.disable();
+        }
+        info!("Acquisition paused (will resume: {:?})", self.resume_modules);
+    }
+
+    /// Re-enable the modules that were running before the pause.
+    /// Returns the names of the modules that were re-enabled.
+    pub fn resume(&mut self, modules: &[&dyn BuySideModule]) -> Vec<String> {
+        if !self.paused {
+            return Vec::new();
+        }
+        let mut resumed = Vec::new();
+        for module in modules {
+            if self.resume_modules.iter().any(|n| n == module.module_name()) {
+                module.enable();
+                resumed.push(module.module_name().to_string());
+            }
+        }
+        self.paused = false;
+        self.paused_at = None;
+        self.resume_modules.clear();
+        info!("Acquisition resumed ({:?})", resumed);
+        resumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    struct FakeModule {
+        name: &'static str,
+        enabled: Cell<bool>,
+    }
+
+    impl FakeModule {
+        fn new(name: &'static str, enabled: bool) -> Self {
+            Self { name, enabled: Cell::new(enabled) }
+        }
+    }
+
+    impl BuySideModule for FakeModule {
+        fn module_name(&self) -> &'static str { self.name }
+        fn is_enabled(&self) -> bool { self.enabled.get() }
+        fn enable(&self) { self.enabled.set(true) }
+        fn disable(&self) { self.enabled.set(false) }
+    }
+
+    #[test]
+    fn test_pause_disables_buyers_but_not_sentinel() {
+        let sniper = FakeModule::new("sniper", true);
+        let mirror = FakeModule::new("mirror", false);
+        let dipbuyer = FakeModule::new("dipbuyer", true);
+        // The sentinel monitor is not a buy-side module and is never handed to pause()
+        let sentinel = FakeModule::new("sentinel", true);
+
+        let mut state = AcquisitionPauseState::default();
+        state.pause(&[&sniper, &mirror, &dipbuyer]);
+
+        assert!(state.paused);
+        assert!(!sniper.is_enabled());
+        assert!(!mirror.is_enabled());
+        assert!(!dipbuyer.is_enabled());
+        assert!(sentinel.is_enabled());
+
+        let resumed = state.resume(&[&sniper, &mirror, &dipbuyer]);
+        assert_eq!(resumed, vec!["sniper".to_string(), "dipbuyer".to_string()]);
+        assert!(sniper.is_enabled());
+        assert!(!mirror.is_enabled());
+        assert!(dipbuyer.is_enabled());
+        assert!(!state.paused);
+    }
+
+    #[test]
+    fn test_double_pause_keeps_resume_list() {
+        let sniper = FakeModule::new("sniper", true);
+        let mut state = AcquisitionPauseState::default();
+        state.pause(&[&sniper]);
+        state.pause(&[&sniper]);
+        assert_eq!(state.resume_modules, vec!["sniper".to_string()]);
+    }
+}
@@ -0,0 +1,193 @@
+//! App Events — every event the backend sends to the frontend
+//!
+//! Events used to be emitted as `app_handle.emit("some-name", payload)`
+//! wherever they happened, so a typo in a name silently broke its listener
+//! and there was no list of what the frontend can listen for. Each event is
+//! now an [`AppEvent`] variant carrying its payload type, and [`emit_event`]
+//! is the single place that turns one into a Tauri event.
+
+use crate::config_diff::ConfigChangedEvent;
+use crate::dipbuyer::{DipBuyerSkippedEvent, DipBuyerTickEvent, DipBuyerTriggeredEvent};
+use crate::equity_alert_loop::EquityAlertFiredEvent;
+use crate::kill_switch::EmergencyStopEvent;
+use crate::harvester::{HarvesterClaimedEvent, HarvesterTickEvent};
+use crate::mirror::{MirrorTickEvent, MirrorTriggeredEvent};
+use crate::mobile_server::MobileConnectionEvent;
+use crate::price_alert_loop::PriceAlertFiredEvent;
+use crate::profile_failover::ProfileFailoverEvent;
+use crate::sentinel_loop::{SentinelTickEvent, SentinelTriggeredEvent};
+use crate::sniper::{SniperTickEvent, SniperTriggeredEvent};
+use crate::trade_approval::TradeApprovalEvent;
+use crate::trade_executor::TradeExecutedEvent;
+use crate::warmup::WarmupStatus;
+use serde::Serialize;
+use tauri::Emitter;
+
+/// An event for the frontend. Serializes as its payload alone; the event
+/// name comes from [`AppEvent::name`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AppEvent {
+    TradeExecuted(TradeExecutedEvent),
+    SentinelTick(SentinelTickEvent),
+    SentinelTriggered(SentinelTriggeredEvent),
+    SniperTick(SniperTickEvent),
+    SniperTriggered(SniperTriggeredEvent),
+    DipBuyerTick(DipBuyerTickEvent),
+    DipBuyerTriggered(DipBuyerTriggeredEvent),
+    DipBuyerSkipped(DipBuyerSkippedEvent),
+    MirrorTick(MirrorTickEvent),
+    MirrorTriggered(MirrorTriggeredEvent),
+    HarvesterTick(HarvesterTickEvent),
+    HarvesterClaimed(HarvesterClaimedEvent),
+    MobileConnection(MobileConnectionEvent),
+    PriceAlertFired(PriceAlertFiredEvent),
+    EquityAlertFired(EquityAlertFiredEvent),
+    ProfileFailover(ProfileFailoverEvent),
+    ConfigChanged(ConfigChangedEvent),
+    WarmupStatus(WarmupStatus),
+    EmergencyStop(EmergencyStopEvent),
+    TradeApproval(TradeApprovalEvent),
+}
+
+impl AppEvent {
+    /// Name the frontend `listen`s on
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TradeExecuted(_) => "trade-executed",
+            Self::SentinelTick(_) => "sentinel-tick",
+            Self::SentinelTriggered(_) => "sentinel-triggered",
+            Self::SniperTick(_) => "sniper-tick",
+            Self::SniperTriggered(_) => "sniper-triggered",
+            Self::DipBuyerTick(_) => "dipbuyer-tick",
+            Self::DipBuyerTriggered(_) => "dipbuyer-triggered",
+            Self::DipBuyerSkipped(_) => "dipbuyer-skipped",
+            Self::MirrorTick(_) => "mirror-tick",
+            Self::MirrorTriggered(_) => "mirror-triggered",
+            Self::HarvesterTick(_) => "harvester-tick",
+            Self::HarvesterClaimed(_) => "harvester-claimed",
+            Self::MobileConnection(_) => "mobile-connection",
+            Self::PriceAlertFired(_) => "price-alert-fired",
+            Self::EquityAlertFired(_) => "equity-alert-fired",
+            Self::ProfileFailover(_) => "profile-failover",
+            Self::ConfigChanged(_) => "config-changed",
+            Self::WarmupStatus(_) => "warmup-status",
+            Self::EmergencyStop(_) => "emergency-stop",
+            Self::TradeApproval(_) => "trade-approval",
+        }
+    }
+}
+
+/// Send `event` to every window
+pub fn emit_event(app_handle: &tauri::AppHandle, event: AppEvent) -> tauri::Result<()> {
+    app_handle.emit(event.name(), &event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mobile_server::SessionRole;
+    use crate::equity_alerts::EquityAlert;
+    use crate::price_alerts::AlertCondition;
+    use crate::sentinel_loop::MonitorStatus;
+    use crate::skip_reason::SkipReason;
+    use crate::trade_amount::AmountUnit;
+    use crate::trade_approval::{ApprovalStatus, PendingApproval};
+    use crate::trade_reason::TradeReason;
+
+    fn s(v: &str) -> String {
+        v.to_string()
+    }
+
+    #[test]
+    fn test_each_event_has_the_name_its_listener_uses() {
+        let reason = TradeReason::Sniper { symbol: s("AAA"), age_secs: 90, market_cap: 1_000.0 };
+        let events = vec![
+            (AppEvent::TradeExecuted(TradeExecutedEvent {
+                symbol: s("AAA"), trade_type: s("BUY"), amount: 10.0, new_price: Some(1.0), price_impact: 0.0,
+                new_balance: 90.0, reason: reason.to_string(), reason_detail: reason, source: s("sniper"),
+                success: true, error: None, paper: false,
+            }), "trade-executed"),
+            (AppEvent::SentinelTick(SentinelTickEvent {
+                status: MonitorStatus::Running, checked: 3, active_count: 2, last_check_at: s("now"),
+            }), "sentinel-tick"),
+            (AppEvent::SentinelTriggered(SentinelTriggeredEvent {
+                sentinel_id: 1, symbol: s("AAA"), reason: s("SL"), trigger_type: s("stop_loss"),
+                current_price: 0.8, entry_price: 1.0, sell_amount: 5.0, sell_percentage: 100.0,
+            }), "sentinel-triggered"),
+            (AppEvent::SniperTick(SniperTickEvent {
+                enabled: true, total_sniped: 0, last_sniped_at: None, coins_checked: 20, skipped: Vec::new(),
+            }), "sniper-tick"),
+            (AppEvent::SniperTriggered(SniperTriggeredEvent {
+                symbol: s("AAA"), coin_name: s("A"), buy_amount_usd: 10.0, market_cap: 1_000.0, price: 1.0,
+                coin_age_secs: 90,
+            }), "sniper-triggered"),
+            (AppEvent::DipBuyerTick(DipBuyerTickEvent {
+                enabled: true, total_bought: 0, last_bought_at: None, trades_scanned: 50, dips_detected: 1,
+            }), "dipbuyer-tick"),
+            (AppEvent::DipBuyerTriggered(DipBuyerTriggeredEvent {
+                symbol: s("AAA"), coin_name: s("A"), buy_amount_usd: 10.0, seller_username: s("w"),
+                sell_value_usd: 500.0, seller_rank: Some(1), market_cap: 1_000.0, price: 1.0, change_24h: -20.0,
+                confidence_score: 0.7, slippage_pct: 1.0, sell_impact_pct: 5.0,
+            }), "dipbuyer-triggered"),
+            (AppEvent::DipBuyerSkipped(DipBuyerSkippedEvent {
+                symbol: s("AAA"), seller_username: s("w"), sell_value_usd: 500.0, reason: s("x"),
+                skip: SkipReason::SignalReject { detail: s("x") },
+            }), "dipbuyer-skipped"),
+            (AppEvent::MirrorTick(MirrorTickEvent {
+                enabled: true, tracked_whale_count: 3, total_mirrored: 0, last_mirrored_at: None, trades_checked: 50,
+            }), "mirror-tick"),
+            (AppEvent::MirrorTriggered(MirrorTriggeredEvent {
+                whale_username: s("w"), whale_user_id: s("1"), coin_symbol: s("AAA"), coin_name: s("A"),
+                whale_amount_usd: 500.0, our_amount_usd: 50.0, trade_type: s("BUY"), latency_secs: 1.0,
+            }), "mirror-triggered"),
+            (AppEvent::HarvesterTick(HarvesterTickEvent {
+                enabled: true, seconds_until_next: 60, last_claim_at: None, total_claims: 0, profiles_count: 1,
+            }), "harvester-tick"),
+            (AppEvent::HarvesterClaimed(HarvesterClaimedEvent {
+                profile_id: 1, username: s("u"), reward_amount: 10.0, new_balance: 100.0, login_streak: 2,
+                next_claim_at: None, total_claims: 1,
+            }), "harvester-claimed"),
+            (AppEvent::MobileConnection(MobileConnectionEvent {
+                event_type: s("connected"), token_prefix: s("abcd"), role: SessionRole::Viewer, label: s("phone"),
+                total_sessions: 1,
+            }), "mobile-connection"),
+            (AppEvent::PriceAlertFired(PriceAlertFiredEvent {
+                id: 1, symbol: s("AAA"), condition: AlertCondition::Above { price: 2.0 }, price: 2.1, move_pct: None,
+            }), "price-alert-fired"),
+            (AppEvent::EquityAlertFired(EquityAlertFiredEvent {
+                alert: EquityAlert::Milestone { level: 1_000.0, upward: true, value: 1_010.0 },
+                title: s("Equity above $1000.00"), body: s("x"),
+            }), "equity-alert-fired"),
+            (AppEvent::ProfileFailover(ProfileFailoverEvent {
+                from_profile_id: Some(1), to_profile_id: 2, username: s("backup"), reason: s("token expired"),
+            }), "profile-failover"),
+            (AppEvent::ConfigChanged(ConfigChangedEvent { module: s("sniper"), changes: Vec::new() }), "config-changed"),
+            (AppEvent::WarmupStatus(crate::warmup::WarmupGate::default().status()), "warmup-status"),
+            (AppEvent::EmergencyStop(EmergencyStopEvent { active: true, cancelled_trades: 2, at: s("now") }), "emergency-stop"),
+            (AppEvent::TradeApproval(TradeApprovalEvent {
+                status: ApprovalStatus::Proposed,
+                approval: PendingApproval {
+                    id: 1, symbol: s("AAA"), trade_type: rugplay_core::TradeType::Buy, amount: 10.0,
+                    amount_unit: AmountUnit::Usd, reason: s("x"),
+                    reason_detail: TradeReason::Sniper { symbol: s("AAA"), age_secs: 90, market_cap: 1_000.0 },
+                    source: s("sniper"), proposed_at: 0, expires_at: 120,
+                },
+                pending_count: 1,
+            }), "trade-approval"),
+        ];
+
+        for (event, name) in &events {
+            assert_eq!(event.name(), *name);
+        }
+        // The payload goes out bare, without a variant wrapper
+        assert_eq!(
+            serde_json::to_value(&events[16].0).unwrap(),
+            serde_json::json!({ "module": "sniper", "changes": [] })
+        );
+        let mut names: Vec<&str> = events.iter().map(|(e, _)| e.name()).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), events.len(), "event names must be unique");
+    }
+}
@@ -0,0 +1,236 @@
+//! Bot Status — one consolidated snapshot of every subsystem
+//!
+//! The desktop header and the mobile dashboard used to piece status together
+//! from separate handle lookups. `get_bot_status` gathers everything once
+//! into a [`BotStatus`]; the automation loops report liveness through
+//! [`Heartbeats`] so the snapshot can show when each one last ran.
+
+use crate::warmup::WarmupStatus;
+use chrono::{DateTime, Utc};
+use rugplay_core::PortfolioSummary;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Automation loops that report a heartbeat, in display order
+pub const MODULES: &[&str] = &["sentinel", "sniper", "mirror", "dipbuyer", "harvester"];
+
+/// Last tick of each automation loop, written by the loops themselves
+#[derive(Debug, Default)]
+pub struct Heartbeats(Mutex<HashMap<&'static str, DateTime<Utc>>>);
+
+impl Heartbeats {
+    /// Record that `module` just ticked
+    pub fn beat(&self, module: &'static str) {
+        self.beat_at(module, Utc::now());
+    }
+
+    pub fn beat_at(&self, module: &'static str, at: DateTime<Utc>) {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).insert(module, at);
+    }
+
+    /// When `module` last ticked, if it has since startup
+    pub fn last(&self, module: &str) -> Option<DateTime<Utc>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).get(module).copied()
+    }
+}
+
+/// On/off state of one module as read from its handle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModuleState {
+    pub enabled: bool,
+    /// Running but not acting (only the sentinel monitor pauses)
+    pub paused: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleStatus {
+    pub name: String,
+    pub enabled: bool,
+    pub paused: bool,
+    pub last_tick_at: Option<String>,
+    pub last_tick_age_secs: Option<i64>,
+}
+
+/// Trade executor queue and the buy limits it is currently enforcing
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorStatus {
+    pub queue_depth: usize,
+    pub daily_trades: u32,
+    pub daily_volume_usd: f64,
+    pub cooldown_remaining_secs: u64,
+    /// Limits refusing every new buy right now (empty = buys allowed)
+    pub buy_blocks: Vec<String>,
+    /// Smallest buy the server accepts, once known
+    pub server_min_trade_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileStatus {
+    pub id: i64,
+    pub username: String,
+}
+
+/// Whether the Rugplay API answered the status probe
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum Connectivity {
+    Online,
+    Offline { error: String },
+    /// Not probed (no active profile, or the caller skipped the probe)
+    Unknown,
+}
+
+/// Everything the status snapshot is built from
+#[derive(Debug, Clone, Default)]
+pub struct StatusInputs {
+    /// Modules whose handle is registered
+    pub modules: HashMap<&'static str, ModuleState>,
+    pub executor: Option<ExecutorStatus>,
+    pub acquisition_paused: bool,
+    pub active_profile: Option<ProfileStatus>,
+    /// Portfolio probe result; `None` when it wasn't attempted
+    pub portfolio: Option<Result<PortfolioSummary, String>>,
+    pub warmup: Option<WarmupStatus>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BotStatus {
+    pub modules: Vec<ModuleStatus>,
+    pub executor: Option<ExecutorStatus>,
+    /// Acquisition kill switch: every buy-side module is held off
+    pub acquisition_paused: bool,
+    pub active_profile: Option<ProfileStatus>,
+    pub connectivity: Connectivity,
+    pub portfolio: Option<PortfolioSummary>,
+    /// Startup warm-up; automation doesn't trade until it is ready
+    pub warmup: Option<WarmupStatus>,
+    pub timestamp: String,
+}
+
+impl BotStatus {
+    pub fn build(inputs: StatusInputs, heartbeats: &Heartbeats, now: DateTime<Utc>) -> Self {
+        let modules = MODULES
+            .iter()
+            .filter_map(|&name| {
+                let state = inputs.modules.get(name)?;
+                let last = heartbeats.last(name);
+                Some(ModuleStatus {
+                    name: name.to_string(),
+                    enabled: state.enabled,
+                    paused: state.paused,
+                    last_tick_at: last.map(|at| at.to_rfc3339()),
+                    last_tick_age_secs: last.map(|at| (now - at).num_seconds().max(0)),
+                })
+            })
+            .collect();
+
+        let (connectivity, portfolio) = match inputs.portfolio {
+            Some(Ok(summary)) => (Connectivity::Online, Some(summary)),
+            Some(Err(error)) => (Connectivity::Offline { error }, None),
+            None => (Connectivity::Unknown, None),
+        };
+
+        Self {
+            modules,
+            executor: inputs.executor,
+            acquisition_paused: inputs.acquisition_paused,
+            active_profile: inputs.active_profile,
+            connectivity,
+            portfolio,
+            warmup: inputs.warmup,
+            timestamp: now.to_rfc3339(),
+        }
+    }
+
+    pub fn module(&self, name: &str) -> Option<&ModuleStatus> {
+        self.modules.iter().find(|m| m.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(balance: f64) -> PortfolioSummary {
+        PortfolioSummary {
+            balance,
+            portfolio_value: 0.0,
+            total_value: balance,
+            total_profit_loss: 0.0,
+            total_profit_loss_pct: 0.0,
+            holdings_count: 0,
+            frozen_balance: 0.0,
+            available_balance: balance,
+        }
+    }
+
+    #[test]
+    fn test_status_reflects_each_subsystem() {
+        let now = Utc::now();
+        let heartbeats = Heartbeats::default();
+        heartbeats.beat_at("sentinel", now - chrono::Duration::seconds(12));
+        heartbeats.beat_at("sniper", now - chrono::Duration::seconds(3));
+
+        let inputs = StatusInputs {
+            modules: HashMap::from([
+                ("sentinel", ModuleState { enabled: true, paused: true }),
+                ("sniper", ModuleState { enabled: true, paused: false }),
+                ("mirror", ModuleState::default()),
+                ("harvester", ModuleState { enabled: true, paused: false }),
+            ]),
+            executor: Some(ExecutorStatus {
+                queue_depth: 2,
+                daily_trades: 40,
+                buy_blocks: vec!["Risk limit: 40 trades today, max 40".into()],
+                ..Default::default()
+            }),
+            acquisition_paused: true,
+            active_profile: Some(ProfileStatus { id: 1, username: "trader".into() }),
+            portfolio: Some(Ok(summary(250.0))),
+            warmup: None,
+        };
+        let status = BotStatus::build(inputs, &heartbeats, now);
+
+        // Display order, and modules without a handle are left out
+        let names: Vec<_> = status.modules.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, ["sentinel", "sniper", "mirror", "harvester"]);
+
+        let sentinel = status.module("sentinel").unwrap();
+        assert!(sentinel.enabled && sentinel.paused);
+        assert_eq!(sentinel.last_tick_age_secs, Some(12));
+        assert_eq!(status.module("sniper").unwrap().last_tick_age_secs, Some(3));
+        let mirror = status.module("mirror").unwrap();
+        assert!(!mirror.enabled);
+        assert_eq!(mirror.last_tick_at, None);
+
+        let executor = status.executor.as_ref().unwrap();
+        assert_eq!(executor.queue_depth, 2);
+        assert_eq!(executor.buy_blocks.len(), 1);
+        assert!(status.acquisition_paused);
+        assert_eq!(status.active_profile.as_ref().unwrap().username, "trader");
+        assert_eq!(status.connectivity, Connectivity::Online);
+        assert_eq!(status.portfolio.as_ref().unwrap().balance, 250.0);
+    }
+
+    #[test]
+    fn test_failed_or_skipped_probe_is_reported() {
+        let heartbeats = Heartbeats::default();
+        let offline = StatusInputs { portfolio: Some(Err("connection refused".into())), ..Default::default() };
+        let status = BotStatus::build(offline, &heartbeats, Utc::now());
+        assert_eq!(status.connectivity, Connectivity::Offline { error: "connection refused".into() });
+        assert!(status.portfolio.is_none());
+        assert!(status.modules.is_empty());
+
+        let status = BotStatus::build(StatusInputs::default(), &heartbeats, Utc::now());
+        assert_eq!(status.connectivity, Connectivity::Unknown);
+        assert_eq!(
+            serde_json::to_value(&status.connectivity).unwrap(),
+            serde_json::json!({ "state": "unknown" })
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! Budget Window — when daily buy/spend caps reset
+//!
+//! The dip buyer and sniper keep a (timestamp, usd) entry per buy and cap
+//! how many buys / how much spend fall inside the current budget window.
+//! By default that window is the trailing 24 hours. Users who think in
+//! calendar days can instead have the budget reset once a day at a fixed
+//! UTC hour, after which every earlier buy stops counting.
+
+use serde::{Deserialize, Serialize};
+
+const SECS_PER_DAY: i64 = 86_400;
+
+/// How the daily buy/spend budget is refreshed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum BudgetResetMode {
+    /// Buys count for 24 hours after they happen
+    #[default]
+    Rolling24h,
+    /// The budget resets every day at `hour`:00 UTC (0-23)
+    DailyAt { hour: u8 },
+}
+
+impl BudgetResetMode {
+    /// Earliest timestamp (inclusive) that still counts toward the budget at `now`
+    pub fn window_start(&self, now: i64) -> i64 {
+        match *self {
+            BudgetResetMode::Rolling24h => now - SECS_PER_DAY + 1,
+            BudgetResetMode::DailyAt { hour } => {
+                let reset_today = now - now.rem_euclid(SECS_PER_DAY) + i64::from(hour.min(23)) * 3600;
+                if reset_today > now {
+                    reset_today - SECS_PER_DAY
+                } else {
+                    reset_today
+                }
+            }
+        }
+    }
+
+    /// Whether a buy at `ts` still counts toward the budget at `now`
+    pub fn counts(&self, ts: i64, now: i64) -> bool {
+        ts >= self.window_start(now)
+    }
+
+    /// Drop (timestamp, usd) entries that no longer count toward the budget
+    pub fn prune(&self, entries: &mut Vec<(i64, f64)>, now: i64) {
+        let start = self.window_start(now);
+        entries.retain(|(ts, _)| *ts >= start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-05-01 00:00:00 UTC
+    const MAY_1: i64 = 1_714_521_600;
+    const HOUR: i64 = 3600;
+
+    #[test]
+    fn test_rolling_window_keeps_last_24h() {
+        let mode = BudgetResetMode::Rolling24h;
+        let now = MAY_1 + 10 * HOUR;
+        let mut buys = vec![(now - SECS_PER_DAY, 10.0), (now - SECS_PER_DAY + 1, 20.0), (now - 60, 30.0)];
+        mode.prune(&mut buys, now);
+        assert_eq!(buys, vec![(now - SECS_PER_DAY + 1, 20.0), (now - 60, 30.0)]);
+    }
+
+    #[test]
+    fn test_daily_at_resets_at_configured_hour() {
+        let mode = BudgetResetMode::DailyAt { hour: 6 };
+        // Bought at 23:00 and 05:59, one second before the reset
+        let buys = vec![(MAY_1 - HOUR, 100.0), (MAY_1 + 6 * HOUR - 1, 50.0)];
+
+        let mut before = buys.clone();
+        mode.prune(&mut before, MAY_1 + 6 * HOUR - 1);
+        assert_eq!(before.len(), 2);
+
+        // At 06:00 both are gone even though the later one is a second old
+        let mut after = buys.clone();
+        mode.prune(&mut after, MAY_1 + 6 * HOUR);
+        assert!(after.is_empty());
+
+        // A buy just after the reset counts until the next day's reset
+        let fresh = MAY_1 + 6 * HOUR + 1;
+        assert!(mode.counts(fresh, MAY_1 + 30 * HOUR - 1));
+        assert!(!mode.counts(fresh, MAY_1 + 30 * HOUR));
+    }
+
+    #[test]
+    fn test_daily_at_midnight_and_serde_shape() {
+        let mode = BudgetResetMode::DailyAt { hour: 0 };
+        assert_eq!(mode.window_start(MAY_1 + 23 * HOUR), MAY_1);
+        assert_eq!(mode.window_start(MAY_1), MAY_1);
+        // Out-of-range hours clamp to 23:00
+        assert_eq!(BudgetResetMode::DailyAt { hour: 40 }.window_start(MAY_1 + 12 * HOUR), MAY_1 - HOUR);
+
+        assert_eq!(serde_json::to_string(&mode).unwrap(), r#"{"mode":"dailyAt","hour":0}"#);
+        let parsed: BudgetResetMode = serde_json::from_str(r#"{"mode":"rolling24h"}"#).unwrap();
+        assert_eq!(parsed, BudgetResetMode::Rolling24h);
+    }
+}
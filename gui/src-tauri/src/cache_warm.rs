@@ -0,0 +1,116 @@
+//! Cache Warm — keeps the coin cache fresh for coins that matter
+//!
+//! The coin cache only fills when something reads it, so the sentinel
+//! check after a quiet spell often pays for a fetch. The warmer refetches
+//! held and watched coins shortly before their entries expire, a few per
+//! tick so a large portfolio doesn't burst the API. Everything else is left
+//! to expire. Settings key `cache_warming`.
+
+use rugplay_persistence::cache::CoinCache;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+/// Cache warming settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheWarmConfig {
+    /// Off by default: warming spends API calls the user hasn't asked for
+    pub enabled: bool,
+    /// Refetch an entry once it has less than this long left
+    pub lead_secs: u64,
+    /// Most coins refetched per tick
+    pub max_per_tick: usize,
+    /// Warm watchlist coins as well as held ones
+    pub include_watchlist: bool,
+}
+
+impl Default for CacheWarmConfig {
+    fn default() -> Self {
+        Self { enabled: false, lead_secs: 10, max_per_tick: 3, include_watchlist: true }
+    }
+}
+
+impl CacheWarmConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        // The coin cache keeps entries for 30 seconds
+        if !(1..=25).contains(&self.lead_secs) {
+            return Err("Refresh lead must be between 1 and 25 seconds".to_string());
+        }
+        if !(1..=20).contains(&self.max_per_tick) {
+            return Err("Coins refreshed per tick must be between 1 and 20".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn lead(&self) -> Duration {
+        Duration::from_secs(self.lead_secs)
+    }
+}
+
+/// The coins to keep warm: everything held, plus the watchlist if asked
+pub fn warm_set<'a>(
+    held: impl IntoIterator<Item = &'a str>,
+    watched: impl IntoIterator<Item = &'a str>,
+    include_watchlist: bool,
+) -> Vec<String> {
+    let mut set: BTreeSet<String> = held.into_iter().map(str::to_string).collect();
+    if include_watchlist {
+        set.extend(watched.into_iter().map(str::to_string));
+    }
+    set.into_iter().collect()
+}
+
+/// Coins of `warm` to refetch at `now`: those with less than `lead` left or
+/// not cached at all, soonest to expire first, at most `max` of them. The
+/// rest come due on later ticks, which spreads the fetches out.
+pub fn due_for_refresh(cache: &CoinCache, warm: &[String], lead: Duration, max: usize, now: Instant) -> Vec<String> {
+    let mut due: Vec<(Duration, &String)> = warm
+        .iter()
+        .filter_map(|symbol| {
+            let left = cache.time_to_expiry_at(symbol, now).unwrap_or(Duration::ZERO);
+            (left < lead).then_some((left, symbol))
+        })
+        .collect();
+    due.sort();
+    due.into_iter().take(max).map(|(_, symbol)| symbol.clone()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_core::CoinDetails;
+
+    fn coin(symbol: &str) -> CoinDetails {
+        serde_json::from_str(&format!(
+            r#"{{"id":1,"symbol":"{}","name":"{}","currentPrice":1.0,"marketCap":1,"poolCoinAmount":1,"poolBaseCurrencyAmount":1}}"#,
+            symbol, symbol
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_held_coins_refresh_before_expiry_and_unheld_ones_lapse() {
+        let cache = CoinCache::with_capacity(Duration::from_secs(30), 10);
+        let lead = Duration::from_secs(10);
+        let start = Instant::now();
+        cache.insert(coin("HELD"));
+        cache.insert(coin("SOLD"));
+        let warm = warm_set(["HELD"], ["WATCH"], true);
+        assert_eq!(warm, vec!["HELD", "WATCH"]);
+
+        // A watched coin nobody has read yet is warmed straight away
+        assert_eq!(due_for_refresh(&cache, &warm, lead, 5, start), vec!["WATCH"]);
+        cache.insert(coin("WATCH"));
+        assert!(due_for_refresh(&cache, &warm, lead, 5, start).is_empty());
+
+        // Inside the lead window the warm coins come due, soonest to expire
+        // first and a few per tick; the sold coin is left to lapse
+        let later = start + Duration::from_secs(25);
+        assert_eq!(due_for_refresh(&cache, &warm, lead, 1, later), vec!["HELD"]);
+        assert_eq!(due_for_refresh(&cache, &warm, lead, 5, later), vec!["HELD", "WATCH"]);
+        assert!(cache.time_to_expiry_at("SOLD", later).is_some());
+        assert_eq!(cache.time_to_expiry_at("SOLD", start + Duration::from_secs(31)), None);
+        assert_eq!(warm_set(["HELD"], ["WATCH"], false), vec!["HELD"]);
+    }
+}
@@ -0,0 +1,109 @@
+//! Cache Warm Loop — refetches held and watched coins before they expire
+//!
+//! Every tick asks `cache_warm::due_for_refresh` which of the active
+//! profile's coins are about to drop out of the shared coin cache and
+//! refetches them, spaced apart. The warm set follows the portfolio: it is
+//! rebuilt from holdings (and the watchlist) every minute, so coins sold
+//! since then simply stop being warmed.
+
+use crate::cache_warm::{due_for_refresh, warm_set, CacheWarmConfig};
+use crate::trade_executor::active_client;
+use crate::AppState;
+use rugplay_persistence::sqlite;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tracing::debug;
+
+/// How often entries are checked; well under the refresh lead
+const TICK_SECS: u64 = 2;
+
+/// How often the warm set is rebuilt from the portfolio
+const WARM_SET_REFRESH_SECS: u64 = 60;
+
+/// Pause between refetches within a tick
+const FETCH_SPACING_MS: u64 = 250;
+
+/// Load the cache warming settings
+pub async fn load_cache_warm_config(app_handle: &tauri::AppHandle) -> Option<CacheWarmConfig> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>("SELECT value FROM settings WHERE key = 'cache_warming'")
+        .fetch_optional(db.pool())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+}
+
+/// The active profile's held coins, plus its watchlist if configured
+async fn build_warm_set(app_handle: &tauri::AppHandle, config: &CacheWarmConfig) -> Result<Vec<String>, String> {
+    let client = active_client(app_handle).await?;
+    let portfolio = client.get_portfolio().await.map_err(|e| e.to_string())?;
+
+    let watched = if config.include_watchlist {
+        let state = app_handle.state::<AppState>();
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let profile = sqlite::get_active_profile(db.pool())
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("No active profile")?;
+        sqlite::get_watchlist(db.pool(), profile.id).await.map_err(|e| e.to_string())?
+    } else {
+        Vec::new()
+    };
+
+    Ok(warm_set(
+        portfolio.coin_holdings.iter().filter(|h| h.quantity > 0.0).map(|h| h.symbol.as_str()),
+        watched.iter().map(|w| w.symbol.as_str()),
+        config.include_watchlist,
+    ))
+}
+
+/// Spawn the background cache warmer
+pub fn spawn_cache_warm_task(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        let mut warm: Vec<String> = Vec::new();
+        let mut built_at: Option<Instant> = None;
+        loop {
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+
+            let config = load_cache_warm_config(&app_handle).await.unwrap_or_default();
+            if !config.enabled {
+                built_at = None;
+                continue;
+            }
+
+            if built_at.is_none_or(|at| at.elapsed() >= Duration::from_secs(WARM_SET_REFRESH_SECS)) {
+                match build_warm_set(&app_handle, &config).await {
+                    Ok(set) => warm = set,
+                    Err(e) => {
+                        debug!("Cache warm: warm set not refreshed: {}", e);
+                        warm.clear();
+                    }
+                }
+                built_at = Some(Instant::now());
+            }
+
+            let cache = app_handle.state::<AppState>().coin_cache.clone();
+            let due = due_for_refresh(&cache, &warm, config.lead(), config.max_per_tick, Instant::now());
+            if due.is_empty() {
+                continue;
+            }
+            let Ok(client) = active_client(&app_handle).await else { continue };
+            for (i, symbol) in due.iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(Duration::from_millis(FETCH_SPACING_MS)).await;
+                }
+                // A zero max age skips the cached copy; the fetch re-caches it
+                if let Err(e) = client.get_coin_within(symbol, Duration::ZERO).await {
+                    // Left out until the next rebuild rather than retried every tick
+                    debug!("Cache warm: {} not refreshed: {}", symbol, e);
+                    warm.retain(|s| s != symbol);
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,162 @@
+//! Claim Outcome — what one daily reward attempt came to
+//!
+//! The harvester and the Claim Now button both check eligibility with
+//! `GET /api/rewards/claim` and only then post the claim. Each attempt ends
+//! in one of three ways: the reward was claimed, there was nothing to claim
+//! yet (a cooldown, not an error), or something failed and the attempt is
+//! retried after a back-off. The latest outcome per profile is kept on the
+//! harvester handle and shown with its status.
+
+use chrono::{DateTime, Duration, Utc};
+use rugplay_core::{RewardClaimResponse, RewardStatusResponse};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::poll_interval::is_rate_limited;
+
+/// Back-off after a failed claim attempt (seconds)
+pub const RETRY_BACKOFF_SECS: i64 = 300; // 5 minutes
+
+/// The reward cooldown, used when the server doesn't say when the next claim opens
+pub const CLAIM_INTERVAL_SECS: i64 = 12 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum ClaimOutcome {
+    Claimed {
+        amount: f64,
+        new_balance: f64,
+        login_streak: u32,
+        next_eligible_at: DateTime<Utc>,
+    },
+    /// Still on cooldown; nothing was posted
+    NothingToClaim { next_eligible_at: DateTime<Utc> },
+    Failed {
+        reason: String,
+        rate_limited: bool,
+        retry_at: DateTime<Utc>,
+    },
+}
+
+impl ClaimOutcome {
+    /// The outcome of an eligibility check, or `None` if the reward is claimable
+    pub fn from_status(status: &RewardStatusResponse, now: DateTime<Utc>) -> Option<Self> {
+        if status.can_claim {
+            return None;
+        }
+        // time_remaining is in milliseconds
+        let next_eligible_at = status
+            .next_claim_time
+            .as_deref()
+            .and_then(rugplay_core::parse_api_timestamp)
+            .unwrap_or(now + Duration::milliseconds(status.time_remaining.max(0)));
+        Some(Self::NothingToClaim { next_eligible_at })
+    }
+
+    /// The outcome of a claim the server answered
+    pub fn from_claim(response: &RewardClaimResponse, now: DateTime<Utc>) -> Self {
+        if !response.success {
+            return Self::failed("Server declined the claim", now);
+        }
+        let next_eligible_at = match response.next_claim_time.as_deref() {
+            Some(nct) => rugplay_core::parse_api_timestamp(nct).unwrap_or_else(|| {
+                warn!("Harvester: couldn't parse next_claim_time '{}', falling back to now+12h", nct);
+                now + Duration::seconds(CLAIM_INTERVAL_SECS)
+            }),
+            None => now + Duration::seconds(CLAIM_INTERVAL_SECS),
+        };
+        Self::Claimed {
+            amount: response.reward_amount,
+            new_balance: response.new_balance,
+            login_streak: response.login_streak,
+            next_eligible_at,
+        }
+    }
+
+    /// A failed attempt, retried after the back-off
+    pub fn failed(reason: impl Into<String>, now: DateTime<Utc>) -> Self {
+        let reason = reason.into();
+        Self::Failed {
+            rate_limited: is_rate_limited(&reason),
+            reason,
+            retry_at: now + Duration::seconds(RETRY_BACKOFF_SECS),
+        }
+    }
+
+    pub fn is_claimed(&self) -> bool {
+        matches!(self, Self::Claimed { .. })
+    }
+}
+
+/// A profile's latest claim outcome
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileClaimOutcome {
+    pub profile_id: i64,
+    pub username: String,
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub outcome: ClaimOutcome,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_responses_map_to_outcomes() {
+        let now = DateTime::parse_from_rfc3339("2025-06-01T12:00:00Z").unwrap().to_utc();
+
+        // Cooldown: the server's next claim time wins, timeRemaining is the fallback
+        let status: RewardStatusResponse = serde_json::from_str(
+            r#"{"canClaim":false,"timeRemaining":3600000,"nextClaimTime":"2025-06-01T13:30:00.000Z"}"#,
+        )
+        .unwrap();
+        let expected = DateTime::parse_from_rfc3339("2025-06-01T13:30:00Z").unwrap().to_utc();
+        assert_eq!(
+            ClaimOutcome::from_status(&status, now),
+            Some(ClaimOutcome::NothingToClaim { next_eligible_at: expected })
+        );
+        let status: RewardStatusResponse =
+            serde_json::from_str(r#"{"canClaim":false,"timeRemaining":3600000,"nextClaimTime":null}"#).unwrap();
+        assert_eq!(
+            ClaimOutcome::from_status(&status, now),
+            Some(ClaimOutcome::NothingToClaim { next_eligible_at: now + Duration::hours(1) })
+        );
+        let status: RewardStatusResponse =
+            serde_json::from_str(r#"{"canClaim":true,"rewardAmount":1500,"nextClaimTime":null}"#).unwrap();
+        assert_eq!(ClaimOutcome::from_status(&status, now), None);
+
+        // Claimed, with and without a next claim time
+        let claim: RewardClaimResponse = serde_json::from_str(
+            r#"{"success":true,"rewardAmount":1500,"newBalance":11500,"loginStreak":4,"nextClaimTime":"2025-06-02T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let outcome = ClaimOutcome::from_claim(&claim, now);
+        assert!(outcome.is_claimed());
+        assert!(matches!(outcome, ClaimOutcome::Claimed { amount, login_streak: 4, next_eligible_at, .. }
+            if amount == 1500.0 && next_eligible_at == now + Duration::hours(12)));
+        let claim: RewardClaimResponse =
+            serde_json::from_str(r#"{"success":true,"rewardAmount":800,"nextClaimTime":null}"#).unwrap();
+        assert!(matches!(ClaimOutcome::from_claim(&claim, now), ClaimOutcome::Claimed { next_eligible_at, .. }
+            if next_eligible_at == now + Duration::seconds(CLAIM_INTERVAL_SECS)));
+
+        // A declined claim and request errors are failures with a retry time
+        let declined: RewardClaimResponse = serde_json::from_str(r#"{"success":false,"nextClaimTime":null}"#).unwrap();
+        assert!(matches!(ClaimOutcome::from_claim(&declined, now), ClaimOutcome::Failed { rate_limited: false, .. }));
+        let throttled = ClaimOutcome::failed("API error: HTTP status client error (429 Too Many Requests)", now);
+        assert!(matches!(&throttled, ClaimOutcome::Failed { rate_limited: true, retry_at, .. }
+            if *retry_at == now + Duration::seconds(RETRY_BACKOFF_SECS)));
+
+        let json = serde_json::to_value(ProfileClaimOutcome {
+            profile_id: 3,
+            username: "trader".to_string(),
+            at: now,
+            outcome: throttled,
+        })
+        .unwrap();
+        assert_eq!(json["kind"], "failed");
+        assert_eq!(json["profileId"], 3);
+        assert_eq!(json["rateLimited"], true);
+    }
+}
@@ -0,0 +1,34 @@
+//! Coin Risk — fetching what the shared risk score needs
+//!
+//! The score itself lives in the engine crate (`assess_coin_risk`). The
+//! sniper and mirror only see a market listing or a trade, so when their
+//! risk limit is on they fetch the coin's chart and holders here first. The
+//! dip buyer already has both and scores them directly.
+
+use rugplay_core::CoinHoldersResponse;
+use rugplay_engine::risk::{assess_coin_risk, RiskScore};
+use rugplay_networking::RugplayClient;
+
+/// Holders fetched for the concentration component
+pub const RISK_HOLDER_LIMIT: u32 = 20;
+
+/// Score `symbol` from fresh coin, chart and holder data, returning the
+/// holders too so later checks needn't fetch them again. Missing holders
+/// only make that component neutral; a missing coin is an error.
+pub async fn fetch_coin_risk(
+    client: &RugplayClient,
+    symbol: &str,
+) -> Result<(RiskScore, Option<CoinHoldersResponse>), String> {
+    let coin_data = client
+        .get_coin_with_chart(symbol, "1h")
+        .await
+        .map_err(|e| e.to_string())?;
+    let holders = client.get_coin_holders(symbol, RISK_HOLDER_LIMIT).await.ok();
+    let risk = assess_coin_risk(
+        &coin_data.coin,
+        holders.as_ref(),
+        &coin_data.candlestick_data,
+        chrono::Utc::now().timestamp(),
+    );
+    Ok((risk, holders))
+}
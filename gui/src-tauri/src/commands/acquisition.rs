@@ -0,0 +1,107 @@
+//! Tauri commands for pausing all buy-side modules at once, and for the
+//! emergency stop that halts all automation
+
+use crate::acquisition::{AcquisitionPauseState, BuySideModule};
+use crate::kill_switch::{self, EmergencyStopEvent};
+use crate::{dipbuyer, mirror, sniper, AppState, DipBuyerHandle, MirrorHandle, SniperHandle};
+use tauri::{Manager, State};
+
+/// Get the current acquisition pause state
+#[tauri::command]
+pub async fn get_acquisition_paused(
+    app_handle: tauri::AppHandle,
+) -> Result<AcquisitionPauseState, String> {
+    Ok(load_acquisition_pause_state(&app_handle).await)
+}
+
+/// Pause (or resume) sniper, mirror, and dipbuyer in one call.
+/// The sentinel monitor is intentionally left running.
+#[tauri::command]
+pub async fn set_acquisition_paused(
+    app_handle: tauri::AppHandle,
+    sniper_handle: State<'_, SniperHandle>,
+    mirror_handle: State<'_, MirrorHandle>,
+    dipbuyer_handle: State<'_, DipBuyerHandle>,
+    paused: bool,
+) -> Result<AcquisitionPauseState, String> {
+    if !paused {
+        kill_switch::ensure_not_killed(&app_handle).await?;
+    }
+    let mut state = load_acquisition_pause_state(&app_handle).await;
+    {
+        // Not held across the awaits below: the command future must be Send
+        let modules: [&dyn BuySideModule; 3] = [&*sniper_handle, &*mirror_handle, &*dipbuyer_handle];
+        if paused {
+            state.pause(&modules);
+        } else {
+            state.resume(&modules);
+        }
+    }
+
+    // Persist each module's enabled flag so a restart honours the pause
+    sniper::save_sniper_enabled(&app_handle, sniper_handle.is_enabled()).await;
+    mirror::save_mirror_enabled(&app_handle, mirror_handle.is_enabled()).await;
+    dipbuyer::save_dipbuyer_enabled(&app_handle, dipbuyer_handle.is_enabled()).await;
+
+    save_acquisition_pause_state(&app_handle, &state).await?;
+    Ok(state)
+}
+
+/// Whether the emergency stop is in force
+#[tauri::command]
+pub async fn get_emergency_stop(app_handle: tauri::AppHandle) -> Result<bool, String> {
+    Ok(kill_switch::load_automation_killed(&app_handle).await)
+}
+
+/// Halt everything: sniper, mirror and dipbuyer are disabled, the sentinel
+/// monitor is paused and every queued trade is cancelled
+#[tauri::command]
+pub async fn emergency_stop(app_handle: tauri::AppHandle) -> Result<EmergencyStopEvent, String> {
+    kill_switch::engage(&app_handle).await
+}
+
+/// Lift the emergency stop; buy-side modules must be re-enabled one by one
+#[tauri::command]
+pub async fn release_emergency_stop(app_handle: tauri::AppHandle) -> Result<EmergencyStopEvent, String> {
+    kill_switch::release(&app_handle).await
+}
+
+/// Load the acquisition pause state from the settings table
+pub async fn load_acquisition_pause_state(app_handle: &tauri::AppHandle) -> AcquisitionPauseState {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else {
+        return AcquisitionPauseState::default();
+    };
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'acquisition_pause'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|j| serde_json::from_str(&j).ok())
+    .unwrap_or_default()
+}
+
+async fn save_acquisition_pause_state(
+    app_handle: &tauri::AppHandle,
+    pause: &AcquisitionPauseState,
+) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let json = serde_json::to_string(pause).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('acquisition_pause', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -1,12 +1,16 @@
 //! Authentication commands for Tauri
 
+use crate::profile_duplicates::{self, DuplicateTokenPolicy, TokenCollision};
+use crate::profile_failover::ProfileFailoverConfig;
+use crate::profile_refresh;
+use crate::token_migration::{self, MigrationReport, ProfileKeyHealth};
 use crate::AppState;
-use rugplay_core::{ProfileSummary, UserProfile};
+use rugplay_core::{ProfileSummary, TokenValidation, UserProfile};
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::{Deserialize, Serialize};
 use tauri::State;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 /// Result of attempting to log in to a profile
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,39 +37,134 @@ pub async fn list_profiles(state: State<'_, AppState>) -> Result<Vec<ProfileSumm
     Ok(profiles.into_iter().map(ProfileSummary::from).collect())
 }
 
+/// Re-read username/avatar from the API for one profile, or all of them
+#[tauri::command]
+pub async fn refresh_profiles(
+    profile_id: Option<i64>,
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProfileSummary>, String> {
+    let ids = match profile_id {
+        Some(id) => vec![id],
+        None => {
+            let db_guard = state.db.read().await;
+            let db = db_guard.as_ref().ok_or("Database not initialized")?;
+            sqlite::list_profiles(db.pool())
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(|p| p.id)
+                .collect()
+        }
+    };
+
+    for (id, result) in profile_refresh::refresh_profiles(&app_handle, &ids).await {
+        if let Err(e) = result {
+            warn!("Failed to refresh profile {}: {}", id, e);
+        }
+    }
+
+    list_profiles(state).await
+}
+
+/// Check a session token against the Rugplay API without saving it.
+/// Reports whether it is valid, expired, rejected, or the API is unreachable.
+#[tauri::command]
+pub async fn validate_token(token: String) -> Result<TokenValidation, String> {
+    if token.trim().is_empty() {
+        return Ok(TokenValidation::Invalid { reason: "Token is empty".to_string() });
+    }
+    Ok(RugplayClient::new(token.trim()).validate_token().await)
+}
+
+/// Validate a token before saving it. Returns the API user on success.
+/// With `force`, a token that fails validation is accepted anyway (e.g. offline)
+/// and `None` is returned.
+async fn validate_before_save(token: &str, force: bool) -> Result<Option<(String, String)>, String> {
+    match RugplayClient::new(token).validate_token().await {
+        TokenValidation::Valid { username, user_id, .. } => Ok(Some((username, user_id))),
+        other if force => {
+            warn!("Saving unverified token at user's request: {:?}", other);
+            Ok(None)
+        }
+        TokenValidation::Expired => Err("Invalid token: session expired".to_string()),
+        TokenValidation::Invalid { reason } => {
+            error!("Token validation failed: {}", reason);
+            Err(format!("Invalid token: {}", reason))
+        }
+        TokenValidation::Unreachable { reason } => Err(format!(
+            "Could not reach Rugplay to verify the token ({}). Save anyway to store it unverified.",
+            reason
+        )),
+    }
+}
+
 /// Add a new profile with session token
 /// 
-/// Validates the token with Rugplay API and fetches the username
+/// Validates the token with Rugplay API and fetches the username.
+/// `force` saves a token that could not be verified; `username` is then required.
 #[tauri::command]
 pub async fn add_profile(
     token: String,
+    force: Option<bool>,
+    username: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<ProfileSummary, String> {
     info!("Adding new profile");
 
-    // Validate token with API
-    let client = RugplayClient::new(&token);
-    let user_profile = client.verify_auth().await.map_err(|e| {
-        error!("Token validation failed: {}", e);
-        format!("Invalid token: {}", e)
-    })?;
+    let token = token.trim().to_string();
+    let verified = validate_before_save(&token, force.unwrap_or(false)).await?;
+    let (username, user_id) = match &verified {
+        Some((username, user_id)) => (username.clone(), Some(user_id.clone())),
+        None => {
+            let username = username
+                .map(|u| u.trim().to_string())
+                .filter(|u| !u.is_empty())
+                .ok_or("Enter the username this token belongs to so it can be saved unverified")?;
+            (username, None)
+        }
+    };
 
-    info!("Token valid for user: {}", user_profile.username);
+    info!("Saving profile for user: {} (verified: {})", username, verified.is_some());
 
     // Check if profile already exists
     let db_guard = state.db.read().await;
     let db = db_guard.as_ref().ok_or("Database not initialized")?;
     
-    if sqlite::profile_exists(db.pool(), &user_profile.username)
+    if sqlite::profile_exists(db.pool(), &username)
         .await
         .map_err(|e| e.to_string())?
     {
         return Err(format!(
             "Profile for '{}' already exists",
-            user_profile.username
+            username
         ));
     }
 
+    // The same token under another name (e.g. saved unverified) is the same account
+    let owner = profile_duplicates::find_token_owner(db.pool(), &state.encryptor, &token, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let policy = profile_duplicates::load_duplicate_token_policy(db.pool()).await;
+    match policy.resolve(owner.as_ref(), true) {
+        TokenCollision::Unique => {}
+        TokenCollision::Warn(message) => warn!("Adding profile '{}': {}", username, message),
+        TokenCollision::Refuse(message) => return Err(message),
+        TokenCollision::Merge(existing_id) => {
+            info!("Token already saved as profile {}, not adding '{}'", existing_id, username);
+            if verified.is_some() {
+                sqlite::update_last_verified(db.pool(), existing_id)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            let existing = sqlite::get_profile(db.pool(), existing_id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or("Profile not found")?;
+            return Ok(ProfileSummary::from(existing));
+        }
+    }
+
     // Encrypt token
     let encrypted = state
         .encryptor
@@ -75,24 +174,30 @@ pub async fn add_profile(
     // Save to database
     let profile_id = sqlite::create_profile(
         db.pool(),
-        &user_profile.username,
-        Some(&user_profile.id),
+        &username,
+        user_id.as_deref(),
         &encrypted,
     )
     .await
     .map_err(|e| e.to_string())?;
 
-    // Update last verified
-    sqlite::update_last_verified(db.pool(), profile_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    // Only mark verified when the API actually accepted the token
+    let last_verified = if verified.is_some() {
+        sqlite::update_last_verified(db.pool(), profile_id)
+            .await
+            .map_err(|e| e.to_string())?;
+        Some(chrono::Utc::now().to_rfc3339())
+    } else {
+        None
+    };
 
     info!("Profile created with ID: {}", profile_id);
 
     Ok(ProfileSummary {
         id: profile_id,
-        username: user_profile.username,
-        last_verified: Some(chrono::Utc::now().to_rfc3339()),
+        username,
+        last_verified,
+        avatar_url: None,
     })
 }
 
@@ -101,6 +206,7 @@ pub async fn add_profile(
 /// Validates the saved token. Returns TokenExpired if invalid.
 #[tauri::command]
 pub async fn select_profile(
+    app_handle: tauri::AppHandle,
     profile_id: i64,
     state: State<'_, AppState>,
 ) -> Result<LoginResult, String> {
@@ -134,8 +240,25 @@ pub async fn select_profile(
                 .await
                 .map_err(|e| e.to_string())?;
 
+            // Pick up renames since the profile was added
+            if let Err(e) = sqlite::update_profile_identity(
+                db.pool(),
+                profile_id,
+                &user_profile.username,
+                &user_profile.id,
+                user_profile.image.as_deref(),
+            )
+            .await
+            {
+                warn!("Could not update stored identity for profile {}: {}", profile_id, e);
+            }
+
             info!("Logged in as: {}", user_profile.username);
 
+            // Watchlist-only mode now trades this profile's watchlist
+            drop(db_guard);
+            crate::commands::watchlist::sync_tradable_universe(&app_handle).await;
+
             Ok(LoginResult::Success {
                 profile: user_profile,
             })
@@ -154,20 +277,20 @@ pub async fn select_profile(
 }
 
 /// Update token for an existing profile
+///
+/// `force` saves a token that could not be verified.
 #[tauri::command]
 pub async fn update_profile_token(
     profile_id: i64,
     new_token: String,
+    force: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<ProfileSummary, String> {
     info!("Updating token for profile: {}", profile_id);
 
     // Validate new token
-    let client = RugplayClient::new(&new_token);
-    let user_profile = client.verify_auth().await.map_err(|e| {
-        error!("New token validation failed: {}", e);
-        format!("Invalid token: {}", e)
-    })?;
+    let new_token = new_token.trim().to_string();
+    let verified = validate_before_save(&new_token, force.unwrap_or(false)).await?;
 
     // Encrypt new token
     let encrypted = state
@@ -178,21 +301,45 @@ pub async fn update_profile_token(
     // Update in database
     let db_guard = state.db.read().await;
     let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let profile = sqlite::get_profile(db.pool(), profile_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("Profile not found")?;
+
+    let owner = profile_duplicates::find_token_owner(db.pool(), &state.encryptor, &new_token, Some(profile_id))
+        .await
+        .map_err(|e| e.to_string())?;
+    let policy = profile_duplicates::load_duplicate_token_policy(db.pool()).await;
+    match policy.resolve(owner.as_ref(), false) {
+        TokenCollision::Warn(message) => warn!("Updating profile {}: {}", profile_id, message),
+        TokenCollision::Refuse(message) => return Err(message),
+        TokenCollision::Unique | TokenCollision::Merge(_) => {}
+    }
     
     sqlite::update_profile_token(db.pool(), profile_id, &encrypted)
         .await
         .map_err(|e| e.to_string())?;
 
-    sqlite::update_last_verified(db.pool(), profile_id)
-        .await
-        .map_err(|e| e.to_string())?;
+    let avatar_url = profile.avatar_url.clone();
+
+    let (username, last_verified) = match verified {
+        Some((username, _)) => {
+            sqlite::update_last_verified(db.pool(), profile_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            (username, Some(chrono::Utc::now().to_rfc3339()))
+        }
+        None => (profile.username, profile.last_verified.map(|dt| dt.to_rfc3339())),
+    };
 
     info!("Token updated for profile: {}", profile_id);
 
     Ok(ProfileSummary {
         id: profile_id,
-        username: user_profile.username,
-        last_verified: Some(chrono::Utc::now().to_rfc3339()),
+        username,
+        last_verified,
+        avatar_url,
     })
 }
 
@@ -246,3 +393,120 @@ pub async fn get_active_profile(
 
     Ok(profile.map(ProfileSummary::from))
 }
+
+#[tauri::command]
+pub async fn get_profile_failover(
+    state: State<'_, AppState>,
+) -> Result<ProfileFailoverConfig, String> {
+    Ok(state.profile_failover.read().await.clone())
+}
+
+/// Set the ordered backup profiles automation fails over to
+#[tauri::command]
+pub async fn set_profile_failover(
+    state: State<'_, AppState>,
+    mut config: ProfileFailoverConfig,
+) -> Result<ProfileFailoverConfig, String> {
+    let mut seen = std::collections::HashSet::new();
+    config.profile_ids.retain(|id| seen.insert(*id));
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    sqlx::query::<sqlx::Sqlite>(
+        "INSERT INTO settings (key, value) VALUES ('profile_failover', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1"
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    *state.profile_failover.write().await = config.clone();
+    info!("Profile failover {} ({} profiles)", if config.enabled { "enabled" } else { "disabled" }, config.profile_ids.len());
+    Ok(config)
+}
+
+#[tauri::command]
+pub async fn get_duplicate_token_policy(state: State<'_, AppState>) -> Result<DuplicateTokenPolicy, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    Ok(profile_duplicates::load_duplicate_token_policy(db.pool()).await)
+}
+
+/// Set what happens when a token being saved already belongs to another profile
+#[tauri::command]
+pub async fn set_duplicate_token_policy(
+    state: State<'_, AppState>,
+    policy: DuplicateTokenPolicy,
+) -> Result<DuplicateTokenPolicy, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    profile_duplicates::save_duplicate_token_policy(db.pool(), policy)
+        .await
+        .map_err(|e| e.to_string())?;
+    info!("Duplicate token policy set to {:?}", policy);
+    Ok(policy)
+}
+
+/// Saved profiles that share a token, as groups of profile ids
+#[tauri::command]
+pub async fn get_duplicate_profiles(state: State<'_, AppState>) -> Result<Vec<Vec<i64>>, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    profile_duplicates::duplicate_groups(db.pool(), &state.encryptor)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Load persisted failover settings from DB (called during startup)
+pub async fn load_profile_failover_from_db(app_handle: &tauri::AppHandle) -> Option<ProfileFailoverConfig> {
+    use tauri::Manager;
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    let json: String = sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'profile_failover'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+/// Report which saved profiles still have tokens under the legacy key
+#[tauri::command]
+pub async fn check_encryption_health(
+    state: State<'_, AppState>,
+) -> Result<Vec<ProfileKeyHealth>, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    token_migration::check_profiles(db.pool(), &state.encryptor)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Re-run the legacy→machine key migration, for every profile or only
+/// the given ones
+#[tauri::command]
+pub async fn run_encryption_migration(
+    profile_ids: Option<Vec<i64>>,
+    state: State<'_, AppState>,
+) -> Result<MigrationReport, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let report = token_migration::migrate_profiles(db.pool(), &state.encryptor, profile_ids.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    info!(
+        "Encryption migration re-run: {} of {} profiles migrated",
+        report.migrated, report.checked
+    );
+    Ok(report)
+}
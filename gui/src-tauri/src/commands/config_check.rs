@@ -0,0 +1,52 @@
+//! Tauri command for checking module filters against the live market
+
+use crate::config_check::{self, FilterCheck};
+use crate::dipbuyer::DipBuyerHandle;
+use crate::sniper::SniperHandle;
+use tauri::Manager;
+use tracing::debug;
+
+/// Coins sampled per module when no sample size is given
+const DEFAULT_SAMPLE_SIZE: u32 = 50;
+const MAX_SAMPLE_SIZE: u32 = 100;
+
+/// Dry-run the sniper and dip buyer entry filters over the current market.
+/// The sniper is checked against the newest coins, the dip buyer against
+/// the most traded ones. Nothing is bought.
+#[tauri::command]
+pub async fn validate_config_against_market(
+    app_handle: tauri::AppHandle,
+    sample_size: Option<u32>,
+) -> Result<Vec<FilterCheck>, String> {
+    let limit = sample_size.unwrap_or(DEFAULT_SAMPLE_SIZE).clamp(1, MAX_SAMPLE_SIZE);
+    let client = crate::trade_executor::active_client(&app_handle).await?;
+    let mut checks = Vec::new();
+
+    if let Some(sniper) = app_handle.try_state::<SniperHandle>() {
+        let cfg = sniper.get_config().await;
+        let newest = client
+            .get_market(1, limit, "createdAt", "desc", None)
+            .await
+            .map_err(|e| e.to_string())?;
+        checks.push(config_check::check_sniper(&cfg.gates(), &newest.coins, chrono::Utc::now()));
+    }
+
+    if let Some(dipbuyer) = app_handle.try_state::<DipBuyerHandle>() {
+        let cfg = dipbuyer.get_config().await;
+        let traded = client
+            .get_market(1, limit, "volume24h", "desc", None)
+            .await
+            .map_err(|e| e.to_string())?;
+        checks.push(config_check::check_dipbuyer(&traded.coins, |coin| {
+            cfg.gates(&cfg.resolve_tier(&coin.symbol, coin.market_cap))
+        }));
+    }
+
+    for check in &checks {
+        debug!(
+            "Config check {}: {}/{} qualify, most restrictive {:?}",
+            check.module, check.qualifying, check.sampled, check.most_restrictive
+        );
+    }
+    Ok(checks)
+}
@@ -1,6 +1,8 @@
 //! Tauri commands for the Dip Buyer module
 
+use crate::decision_log::{self, ReplayOverrides, ReplayReport};
 use crate::dipbuyer::{self, Aggressiveness, DipBuyerConfig, DipBuyerHandle};
+use crate::pagination::{page_bounds, Page};
 use serde::Serialize;
 use tauri::{Manager, State};
 
@@ -63,6 +65,7 @@ pub async fn set_dipbuyer_enabled(
     enabled: bool,
 ) -> Result<bool, String> {
     if enabled {
+        crate::kill_switch::ensure_not_killed(&app_handle).await?;
         handle.enable();
     } else {
         handle.disable();
@@ -78,8 +81,10 @@ pub async fn update_dipbuyer_config(
     handle: State<'_, DipBuyerHandle>,
     config: DipBuyerConfig,
 ) -> Result<DipBuyerConfig, String> {
+    let previous = handle.get_config().await;
     handle.set_config(config.clone()).await;
     dipbuyer::save_dipbuyer_config(&app_handle, &config).await;
+    crate::record_config_change(&app_handle, "dipbuyer", &previous, &config).await;
     Ok(config)
 }
 
@@ -107,6 +112,7 @@ pub async fn reset_dipbuyer_config(
 
     handle.set_config(fresh.clone()).await;
     dipbuyer::save_dipbuyer_config(&app_handle, &fresh).await;
+    crate::record_config_change(&app_handle, "dipbuyer", &current, &fresh).await;
 
     Ok(fresh)
 }
@@ -155,13 +161,14 @@ pub async fn get_dipbuyer_history(
     }).collect())
 }
 
-/// Get all automation log entries across all modules
+/// Get automation log entries across all modules, newest first
 #[tauri::command]
 pub async fn get_automation_log(
     app_handle: tauri::AppHandle,
     module: Option<String>,
+    offset: Option<u32>,
     limit: Option<u32>,
-) -> Result<Vec<AutomationLogEntry>, String> {
+) -> Result<Page<AutomationLogEntry>, String> {
     let state = app_handle.state::<crate::AppState>();
     let db_guard = state.db.read().await;
     let db = db_guard.as_ref().ok_or("Database not initialized")?;
@@ -171,36 +178,54 @@ pub async fn get_automation_log(
         .map_err(|e| e.to_string())?
         .ok_or("No active profile")?;
 
-    let cap = limit.unwrap_or(100).min(500) as i64;
+    let (offset, cap) = page_bounds(offset, limit, 100, 500);
 
-    let entries = if let Some(ref mod_filter) = module {
-        sqlx::query_as::<_, (i64, String, String, String, String, f64, String, Option<String>)>(
+    let (total, entries) = if let Some(ref mod_filter) = module {
+        let total: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM automation_log WHERE profile_id = ? AND module = ?",
+        )
+        .bind(active.id)
+        .bind(mod_filter)
+        .fetch_one(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+        let entries = sqlx::query_as::<_, (i64, String, String, String, String, f64, String, Option<String>)>(
             "SELECT id, module, symbol, coin_name, action, amount_usd, details, created_at \
              FROM automation_log WHERE profile_id = ? AND module = ? \
-             ORDER BY created_at DESC LIMIT ?",
+             ORDER BY created_at DESC LIMIT ? OFFSET ?",
         )
         .bind(active.id)
         .bind(mod_filter)
-        .bind(cap)
+        .bind(cap as i64)
+        .bind(offset as i64)
         .fetch_all(db.pool())
         .await
-        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+        (total, entries)
     } else {
-        sqlx::query_as::<_, (i64, String, String, String, String, f64, String, Option<String>)>(
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM automation_log WHERE profile_id = ?")
+            .bind(active.id)
+            .fetch_one(db.pool())
+            .await
+            .map_err(|e| e.to_string())?;
+        let entries = sqlx::query_as::<_, (i64, String, String, String, String, f64, String, Option<String>)>(
             "SELECT id, module, symbol, coin_name, action, amount_usd, details, created_at \
              FROM automation_log WHERE profile_id = ? \
-             ORDER BY created_at DESC LIMIT ?",
+             ORDER BY created_at DESC LIMIT ? OFFSET ?",
         )
         .bind(active.id)
-        .bind(cap)
+        .bind(cap as i64)
+        .bind(offset as i64)
         .fetch_all(db.pool())
         .await
-        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+        (total, entries)
     };
 
-    Ok(entries.into_iter().map(|(id, module, symbol, coin_name, action, amount_usd, details, created_at)| {
+    let items = entries.into_iter().map(|(id, module, symbol, coin_name, action, amount_usd, details, created_at)| {
         AutomationLogEntry { id, module, symbol, coin_name, action, amount_usd, details, created_at }
-    }).collect())
+    }).collect();
+    Ok(Page::new(items, total.max(0) as u64, offset, cap))
 }
 
 #[derive(Debug, Serialize)]
@@ -215,3 +240,19 @@ pub struct AutomationLogEntry {
     pub details: String,
     pub created_at: Option<String>,
 }
+
+/// Re-run recorded automation decisions between two epoch timestamps.
+/// With `overrides`, the given module configs replace the recorded ones.
+#[tauri::command]
+pub async fn replay_decisions(
+    app_handle: tauri::AppHandle,
+    from: i64,
+    to: i64,
+    overrides: Option<ReplayOverrides>,
+) -> Result<ReplayReport, String> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    decision_log::replay_events_with(db, from, to, &overrides.unwrap_or_default()).await
+}
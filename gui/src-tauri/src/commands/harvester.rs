@@ -1,8 +1,11 @@
 //! Tauri commands for the Harvester module
 
-use crate::harvester::HarvesterHandle;
+use crate::claim_outcome::ProfileClaimOutcome;
+use crate::harvester::{attempt_claim, HarvesterHandle};
+use rugplay_persistence::sqlite;
 use serde::Serialize;
 use tauri::{Manager, State};
+use tracing::warn;
 
 /// Harvester status response sent to the frontend
 #[derive(Debug, Serialize)]
@@ -13,12 +16,16 @@ pub struct HarvesterStatusResponse {
     pub next_claim_at: Option<String>,
     pub seconds_until_next: i64,
     pub total_claims: u32,
+    /// The most recent claim attempt across all profiles
+    pub last_outcome: Option<ProfileClaimOutcome>,
+    /// Each profile's latest claim attempt
+    pub outcomes: Vec<ProfileClaimOutcome>,
 }
 
 #[tauri::command]
 pub async fn get_harvester_status(
     app_handle: tauri::AppHandle,
-    _handle: State<'_, HarvesterHandle>,
+    handle: State<'_, HarvesterHandle>,
 ) -> Result<HarvesterStatusResponse, String> {
     // Harvester is always enabled
     let enabled = true;
@@ -112,12 +119,17 @@ pub async fn get_harvester_status(
         Some("Now".to_string())
     };
 
+    let outcomes = handle.last_outcomes();
+    let last_outcome = outcomes.iter().max_by_key(|o| o.at).cloned();
+
     Ok(HarvesterStatusResponse {
         enabled,
         last_claim_at,
         next_claim_at,
         seconds_until_next: min_seconds_until_next,
         total_claims,
+        last_outcome,
+        outcomes,
     })
 }
 
@@ -134,7 +146,8 @@ pub async fn set_harvester_enabled(
 #[tauri::command]
 pub async fn force_claim_reward(
     app_handle: tauri::AppHandle,
-) -> Result<String, String> {
+    handle: State<'_, HarvesterHandle>,
+) -> Result<Vec<ProfileClaimOutcome>, String> {
     // Force-claim for ALL profiles, not just the active one
     let state = app_handle.state::<crate::AppState>();
 
@@ -151,89 +164,29 @@ pub async fn force_claim_reward(
 
     drop(db_guard);
 
-    let mut results: Vec<String> = Vec::new();
-    let mut any_success = false;
-
+    let mut results = Vec::with_capacity(profiles.len());
     for profile in &profiles {
-        // Decrypt token
-        let token = match decrypt_token(&app_handle, profile.id).await {
-            Ok(t) => t,
-            Err(e) => {
-                results.push(format!("{}: token error — {}", profile.username, e));
-                continue;
-            }
-        };
-
-        let client = rugplay_networking::RugplayClient::new_with_cache(
-            &token,
-            state.coin_cache.clone(),
-        );
-
-        // Check if eligible first
-        match client.get_reward_status().await {
-            Ok(status) => {
-                if !status.can_claim {
-                    // time_remaining from API is in MILLISECONDS
-                    let remaining_secs = status.time_remaining / 1000;
-                    let h = remaining_secs / 3600;
-                    let m = (remaining_secs % 3600) / 60;
-                    results.push(format!("{}: not ready ({}h {}m left)", profile.username, h, m));
-                    continue;
-                }
-            }
-            Err(e) => {
-                results.push(format!("{}: status check failed — {}", profile.username, e));
-                continue;
-            }
+        let outcome = attempt_claim(&app_handle, profile).await;
+        if outcome.is_claimed() {
+            // Update per-profile DB state
+            save_claim_to_db(&app_handle, profile.id, chrono::Utc::now().timestamp()).await;
         }
 
-        // Claim
-        match client.claim_daily_reward().await {
-            Ok(claim_response) => {
-                any_success = true;
-
-                // Update per-profile DB state
-                let now = chrono::Utc::now().timestamp();
-                save_claim_to_db(&app_handle, profile.id, now).await;
-
-                results.push(format!(
-                    "{}: ${:.2} claimed (streak: {})",
-                    profile.username,
-                    claim_response.reward_amount,
-                    claim_response.login_streak
-                ));
-            }
-            Err(e) => {
-                results.push(format!("{}: claim failed — {}", profile.username, e));
-            }
-        }
+        let outcome = ProfileClaimOutcome {
+            profile_id: profile.id,
+            username: profile.username.clone(),
+            at: chrono::Utc::now(),
+            outcome,
+        };
+        handle.record_outcome(outcome.clone());
+        results.push(outcome);
     }
 
-    if any_success {
-        Ok(results.join("\n"))
-    } else {
-        Err(results.join("\n"))
-    }
+    Ok(results)
 }
 
 // ─── Helpers ─────────────────────────────────────────────────────────
 
-async fn decrypt_token(app_handle: &tauri::AppHandle, profile_id: i64) -> Result<String, String> {
-    let state = app_handle.state::<crate::AppState>();
-    let db_guard = state.db.read().await;
-    let db = db_guard.as_ref().ok_or("Database not initialized")?;
-
-    let encrypted = rugplay_persistence::sqlite::get_profile_token(db.pool(), profile_id)
-        .await
-        .map_err(|e| e.to_string())?
-        .ok_or("Token not found")?;
-
-    state
-        .encryptor
-        .decrypt(&encrypted)
-        .map_err(|e| e.to_string())
-}
-
 async fn save_claim_to_db(app_handle: &tauri::AppHandle, profile_id: i64, now: i64) {
     let state = app_handle.state::<crate::AppState>();
     let db_guard = state.db.read().await;
@@ -252,23 +205,8 @@ async fn save_claim_to_db(app_handle: &tauri::AppHandle, profile_id: i64, now: i
     .await;
 
     // Increment total claims
-    let total: u32 = sqlx::query_scalar::<sqlx::Sqlite, String>(
-        "SELECT value FROM settings WHERE key = ?1"
-    )
-    .bind(format!("{}_total_claims", key_prefix))
-    .fetch_optional(pool)
-    .await
-    .ok()
-    .flatten()
-    .and_then(|v: String| v.parse().ok())
-    .unwrap_or(0) + 1;
-
-    let _ = sqlx::query(
-        "INSERT INTO settings (key, value) VALUES (?1, ?2)
-         ON CONFLICT(key) DO UPDATE SET value = ?2"
-    )
-    .bind(format!("{}_total_claims", key_prefix))
-    .bind(total.to_string())
-    .execute(pool)
-    .await;
+    let key = format!("{}_total_claims", key_prefix);
+    if let Err(e) = sqlite::update_setting_json(pool, &key, |total: &mut u32| *total += 1).await {
+        warn!("Failed to count harvester claim: {}", e);
+    }
 }
@@ -1,5 +1,9 @@
 //! Transaction history commands - fetches from Rugplay API
 
+use crate::journal_reconcile::ReconciliationReport;
+use crate::journal_reconcile_loop;
+use crate::pagination::Page;
+use crate::trade_journal;
 use crate::AppState;
 use rugplay_core::ApiTransaction;
 use rugplay_networking::RugplayClient;
@@ -25,6 +29,8 @@ pub struct TransactionRecord {
     pub is_incoming: bool,
     pub sender: Option<String>,
     pub recipient: Option<String>,
+    /// Note from the local trade journal, when a journaled trade matches
+    pub note: Option<String>,
 }
 
 impl From<ApiTransaction> for TransactionRecord {
@@ -52,20 +58,11 @@ impl From<ApiTransaction> for TransactionRecord {
             is_incoming: tx.is_incoming,
             sender: tx.sender,
             recipient: tx.recipient,
+            note: None,
         }
     }
 }
 
-/// Response with transactions and pagination info
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TransactionListResponse {
-    pub transactions: Vec<TransactionRecord>,
-    pub total: u32,
-    pub page: u32,
-    pub limit: u32,
-}
-
 /// Get transaction history from the Rugplay API
 #[tauri::command]
 pub async fn get_transactions(
@@ -74,7 +71,7 @@ pub async fn get_transactions(
     trade_type: Option<String>,
     search: Option<String>,
     state: State<'_, AppState>,
-) -> Result<TransactionListResponse, String> {
+) -> Result<Page<TransactionRecord>, String> {
     let per_page = limit.unwrap_or(25);
     debug!("Fetching transactions page {} with {} per page", page, per_page);
 
@@ -114,16 +111,36 @@ pub async fn get_transactions(
         api_response.total
     );
 
-    Ok(TransactionListResponse {
-        transactions: api_response
-            .transactions
-            .into_iter()
-            .map(TransactionRecord::from)
-            .collect(),
-        total: api_response.total,
-        page: api_response.page,
-        limit: api_response.limit,
-    })
+    let mut transactions: Vec<TransactionRecord> = api_response
+        .transactions
+        .into_iter()
+        .map(TransactionRecord::from)
+        .collect();
+    let journal = sqlite::get_transactions(
+        db.pool(),
+        active_profile.id,
+        trade_journal::JOURNAL_MATCH_WINDOW,
+        0,
+        None,
+        None,
+    )
+    .await
+    .unwrap_or_default();
+    let keys: Vec<_> = transactions
+        .iter()
+        .map(|t| (t.symbol.as_str(), t.trade_type.as_str(), t.timestamp.as_str()))
+        .collect();
+    let notes = trade_journal::match_journal_notes(&keys, &journal);
+    for (record, note) in transactions.iter_mut().zip(notes) {
+        record.note = note;
+    }
+
+    // The API pages by number from 1; fall back to what was asked for when
+    // the response leaves the fields out
+    let limit = if api_response.limit > 0 { api_response.limit } else { per_page } as u64;
+    let page = if api_response.page > 0 { api_response.page } else { page.max(1) } as u64;
+    let offset = (page - 1) * limit;
+    Ok(Page::new(transactions, api_response.total as u64, offset, limit))
 }
 
 /// Get list of all symbols the user has traded (from API transactions)
@@ -171,6 +188,7 @@ pub async fn log_transaction(
     coin_amount: f64,
     price: f64,
     usd_value: f64,
+    note: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<i64, String> {
     debug!("Logging transaction: {} {} @ {}", trade_type, symbol, price);
@@ -183,21 +201,62 @@ pub async fn log_transaction(
         .map_err(|e| e.to_string())?
         .ok_or("No active profile")?;
 
-    let id = sqlite::log_transaction(
-        db.pool(),
-        active_profile.id,
-        &symbol,
-        &trade_type,
+    let tx = sqlite::NewTransaction {
+        symbol: &symbol,
+        trade_type: &trade_type,
         coin_amount,
         price,
         usd_value,
-    )
-    .await
-    .map_err(|e| {
-        error!("Failed to log transaction: {}", e);
-        e.to_string()
-    })?;
+        note: note.as_deref(),
+    };
+    let id = sqlite::log_transaction(db.pool(), active_profile.id, &tx)
+        .await
+        .map_err(|e| {
+            error!("Failed to log transaction: {}", e);
+            e.to_string()
+        })?;
 
     debug!("Transaction logged with id {}", id);
     Ok(id)
 }
+
+/// Export the local trade journal (with notes) as CSV, newest first
+#[tauri::command]
+pub async fn export_trade_journal(
+    trade_type: Option<String>,
+    symbol: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let rows = sqlite::get_transactions(
+        db.pool(),
+        active_profile.id,
+        u32::MAX,
+        0,
+        trade_type.as_deref(),
+        symbol.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(trade_journal::journal_csv(&rows))
+}
+
+/// Reconcile the local trade journal with the server's history now
+#[tauri::command]
+pub async fn reconcile_trade_journal(app_handle: tauri::AppHandle) -> Result<ReconciliationReport, String> {
+    journal_reconcile_loop::run_reconciliation(&app_handle).await
+}
+
+/// Last journal reconciliation report, if one has run
+#[tauri::command]
+pub async fn get_journal_reconciliation(app_handle: tauri::AppHandle) -> Result<Option<ReconciliationReport>, String> {
+    Ok(journal_reconcile_loop::load_reconciliation_report(&app_handle).await)
+}
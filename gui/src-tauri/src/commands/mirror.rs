@@ -1,6 +1,9 @@
 //! Tauri commands for the Mirror module
 
-use crate::mirror::{self, MirrorConfig, MirrorHandle, MirrorTradeRecord};
+use crate::mirror::{self, MirrorConfig, MirrorHandle, MirrorTradeRecord, WhaleAnalysis};
+use crate::pagination::{page_bounds, Page, DEFAULT_PAGE_LIMIT};
+use crate::whale_score::WhaleScore;
+use rugplay_core::ensure_finite;
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::Serialize;
@@ -37,8 +40,16 @@ pub struct WhaleProfileResponse {
 pub struct TrackedWhaleResponse {
     pub user_id: String,
     pub username: String,
-    pub performance_score: f64,
+    /// `None` until scored, and while history is too thin to score
+    pub performance_score: Option<f64>,
     pub tracked_since: String,
+    /// False when the whale is ranked out by `maxTrackedWhales`
+    pub watched: bool,
+    /// Copy latency override; `None` uses the global `maxLatencySecs`
+    pub max_latency_secs: Option<f64>,
+    /// Latest analysis; `None` until the whale is first scored
+    pub score: Option<WhaleScore>,
+    pub scored_at: Option<String>,
 }
 
 #[tauri::command]
@@ -94,6 +105,7 @@ pub async fn set_mirror_enabled(
     enabled: bool,
 ) -> Result<bool, String> {
     if enabled {
+        crate::kill_switch::ensure_not_killed(&app_handle).await?;
         handle.enable();
     } else {
         handle.disable();
@@ -109,8 +121,21 @@ pub async fn update_mirror_config(
     handle: State<'_, MirrorHandle>,
     config: MirrorConfig,
 ) -> Result<MirrorConfig, String> {
+    for (field, value) in [
+        ("scaleFactor", config.scale_factor),
+        ("maxTradeUsd", config.max_trade_usd),
+        ("maxLatencySecs", config.max_latency_secs),
+        ("minWhaleScore", config.min_whale_score),
+    ] {
+        ensure_finite(field, value).map_err(|e| e.to_string())?;
+    }
+    if !(0.0..=100.0).contains(&config.min_whale_score) {
+        return Err("Min whale score must be between 0 and 100".to_string());
+    }
+    let previous = handle.get_config().await;
     handle.set_config(config.clone()).await;
     mirror::save_mirror_config(&app_handle, &config).await;
+    crate::record_config_change(&app_handle, "mirror", &previous, &config).await;
     Ok(config)
 }
 
@@ -130,8 +155,11 @@ pub async fn add_tracked_whale(
         .await
         .map_err(|e| e.to_string())?;
 
-    // Add to in-memory tracking
-    handle.add_whale(user_id).await;
+    // Re-rank the in-memory tracking against the cap
+    let whales = sqlite::list_whales(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+    handle.set_whales(whales).await;
 
     Ok(())
 }
@@ -151,8 +179,45 @@ pub async fn remove_tracked_whale(
         .await
         .map_err(|e| e.to_string())?;
 
-    // Remove from in-memory tracking
-    handle.remove_whale(&user_id).await;
+    // Re-rank the in-memory tracking; a capped-out whale may take its place
+    let whales = sqlite::list_whales(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+    handle.set_whales(whales).await;
+
+    Ok(())
+}
+
+/// Set (or clear with `None`) how old a whale's trade may be and still be copied
+#[tauri::command]
+pub async fn set_whale_max_latency(
+    app_handle: tauri::AppHandle,
+    handle: State<'_, MirrorHandle>,
+    user_id: String,
+    max_latency_secs: Option<f64>,
+) -> Result<(), String> {
+    if let Some(secs) = max_latency_secs {
+        ensure_finite("maxLatencySecs", secs).map_err(|e| e.to_string())?;
+        if secs <= 0.0 {
+            return Err("Latency override must be greater than 0 seconds".to_string());
+        }
+    }
+
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    if !sqlite::set_whale_max_latency(db.pool(), &user_id, max_latency_secs)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Err(format!("Whale {} is not tracked", user_id));
+    }
+
+    let whales = sqlite::list_whales(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+    handle.set_whales(whales).await;
 
     Ok(())
 }
@@ -160,6 +225,7 @@ pub async fn remove_tracked_whale(
 #[tauri::command]
 pub async fn list_tracked_whales(
     app_handle: tauri::AppHandle,
+    handle: State<'_, MirrorHandle>,
 ) -> Result<Vec<TrackedWhaleResponse>, String> {
     let state = app_handle.state::<crate::AppState>();
     let db_guard = state.db.read().await;
@@ -168,14 +234,19 @@ pub async fn list_tracked_whales(
     let whales = sqlite::list_whales(db.pool())
         .await
         .map_err(|e| e.to_string())?;
+    let watched = handle.get_tracked_whale_ids().await;
 
     Ok(whales
         .into_iter()
         .map(|w| TrackedWhaleResponse {
+            watched: watched.contains(&w.user_id),
             user_id: w.user_id,
             username: w.username,
             performance_score: w.performance_score,
             tracked_since: w.tracked_since,
+            max_latency_secs: w.max_latency_secs,
+            score: w.score_detail.as_deref().and_then(|d| serde_json::from_str(d).ok()),
+            scored_at: w.scored_at,
         })
         .collect())
 }
@@ -236,9 +307,37 @@ pub async fn get_whale_profile(
     })
 }
 
+/// Score a whale's recent trading (win rate and average round-trip PnL).
+/// The score of a tracked whale is saved and used by `minWhaleScore`.
+#[tauri::command]
+pub async fn analyze_whale(
+    app_handle: tauri::AppHandle,
+    user_id: String,
+) -> Result<WhaleAnalysis, String> {
+    let client = crate::trade_executor::active_client(&app_handle).await?;
+    mirror::analyze_whale(&app_handle, &client, &user_id).await
+}
+
+/// Mirrored trades of the active profile, newest first
 #[tauri::command]
 pub async fn get_mirror_trades(
-    handle: State<'_, MirrorHandle>,
-) -> Result<Vec<MirrorTradeRecord>, String> {
-    Ok(handle.get_trade_history().await)
+    offset: Option<u32>,
+    limit: Option<u32>,
+    app_handle: tauri::AppHandle,
+) -> Result<Page<MirrorTradeRecord>, String> {
+    let (offset, limit) = page_bounds(offset, limit, DEFAULT_PAGE_LIMIT, 200);
+
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let total = sqlite::count_mirror_trades(db.pool(), profile.id).await.map_err(|e| e.to_string())?;
+    let rows = sqlite::get_mirror_trades(db.pool(), profile.id, limit, offset)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(Page::new(rows.into_iter().map(MirrorTradeRecord::from).collect(), total, offset, limit))
 }
@@ -1,6 +1,7 @@
 //! Tauri commands for the Mobile Access server (Phase 6)
 
-use crate::mobile_server::{ConnectionMode, MobileServerHandle, MobileServerStatus, SessionRole};
+use crate::mobile_permissions::PermissionMatrix;
+use crate::mobile_server::{self, ConnectionMode, MobileServerHandle, MobileServerStatus, SessionRole};
 use crate::AppState;
 use tauri::{Manager, State};
 use tracing::info;
@@ -89,3 +90,24 @@ pub async fn set_mobile_session_role(
     info!("Setting session {} role to {}", token_prefix, role);
     handle.set_session_role(&token_prefix, role).await
 }
+
+#[tauri::command]
+pub async fn get_mobile_permissions(
+    app_handle: tauri::AppHandle,
+) -> Result<PermissionMatrix, String> {
+    let state = app_handle.state::<AppState>();
+    Ok(mobile_server::load_permission_matrix(&state).await)
+}
+
+#[tauri::command]
+pub async fn set_mobile_permissions(
+    app_handle: tauri::AppHandle,
+    handle: State<'_, MobileServerHandle>,
+    permissions: PermissionMatrix,
+) -> Result<PermissionMatrix, String> {
+    let state = app_handle.state::<AppState>();
+    mobile_server::save_permission_matrix(&state, &permissions).await?;
+    handle.set_permissions(permissions.clone()).await;
+    info!("Mobile permission matrix saved");
+    Ok(permissions)
+}
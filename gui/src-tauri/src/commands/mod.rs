@@ -1,7 +1,9 @@
 //! Tauri command handlers
 
+pub mod acquisition;
 pub mod auth;
 pub mod comments;
+pub mod config_check;
 pub mod dipbuyer;
 pub mod harvester;
 pub mod history;
@@ -10,16 +12,23 @@ pub mod mobile;
 pub mod monitor;
 pub mod notifications;
 pub mod portfolio;
+pub mod price_alerts;
 pub mod profiles;
 pub mod research;
 pub mod risk;
 pub mod sentinel;
 pub mod settings;
+pub mod setup;
 pub mod sniper;
+pub mod status;
+pub mod trade_queue;
 pub mod trading;
+pub mod watchlist;
 
+pub use acquisition::*;
 pub use auth::*;
 pub use comments::*;
+pub use config_check::*;
 pub use dipbuyer::*;
 pub use harvester::*;
 pub use history::*;
@@ -28,10 +37,15 @@ pub use mobile::*;
 pub use monitor::*;
 pub use notifications::*;
 pub use portfolio::*;
+pub use price_alerts::*;
 pub use profiles::*;
 pub use research::*;
 pub use risk::*;
 pub use sentinel::*;
 pub use settings::*;
+pub use setup::*;
 pub use sniper::*;
+pub use status::*;
+pub use trade_queue::*;
 pub use trading::*;
+pub use watchlist::*;
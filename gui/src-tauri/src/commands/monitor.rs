@@ -1,6 +1,7 @@
 //! Sentinel Monitor control commands (start/stop/pause/resume/status)
 
-use crate::sentinel_loop::{MonitorStatus, SentinelMonitorHandle};
+use crate::sentinel_eval::{PriceSource, SellRetryPolicy, TpGapPolicy};
+use crate::sentinel_loop::{self, MonitorStatus, SentinelMonitorHandle};
 use serde::Serialize;
 use tauri::State;
 use tracing::{debug, info};
@@ -12,6 +13,15 @@ pub struct MonitorStatusResponse {
     pub status: MonitorStatus,
     pub interval_secs: u64,
     pub is_paused: bool,
+    pub max_sells_per_tick: u32,
+    /// Backoff and failure cap for failed sentinel sells
+    pub sell_retry: SellRetryPolicy,
+    /// Default price source for sentinels without their own
+    pub price_source: PriceSource,
+    /// Pool liquidity below which held coins are sold (0 = off)
+    pub min_liquidity_usd: f64,
+    /// How take-profits react when spot has dumped below the target
+    pub tp_gap: TpGapPolicy,
 }
 
 /// Get sentinel monitor status
@@ -24,11 +34,21 @@ pub async fn get_sentinel_monitor_status(
     let status = handle.status().await;
     let interval_secs = handle.get_interval().await;
     let is_paused = handle.is_paused().await;
+    let max_sells_per_tick = handle.get_max_sells_per_tick().await;
+    let sell_retry = handle.get_sell_retry_policy().await;
+    let price_source = handle.get_price_source().await;
+    let min_liquidity_usd = handle.get_min_liquidity_usd().await;
+    let tp_gap = handle.get_tp_gap_policy().await;
 
     Ok(MonitorStatusResponse {
         status,
         interval_secs,
         is_paused,
+        max_sells_per_tick,
+        sell_retry,
+        price_source,
+        min_liquidity_usd,
+        tp_gap,
     })
 }
 
@@ -45,8 +65,10 @@ pub async fn pause_sentinel_monitor(
 /// Resume the sentinel monitor
 #[tauri::command]
 pub async fn resume_sentinel_monitor(
+    app_handle: tauri::AppHandle,
     handle: State<'_, SentinelMonitorHandle>,
 ) -> Result<(), String> {
+    crate::kill_switch::ensure_not_killed(&app_handle).await?;
     info!("Resuming sentinel monitor");
     handle.resume().await;
     Ok(())
@@ -69,3 +91,83 @@ pub async fn set_sentinel_monitor_interval(
     handle.set_interval(interval_secs).await;
     Ok(())
 }
+
+/// Cap how many sentinel sells fire per tick (0 = unlimited).
+/// Extra triggers are deferred to the next tick so crash exits stay orderly.
+#[tauri::command]
+pub async fn set_sentinel_max_sells_per_tick(
+    app_handle: tauri::AppHandle,
+    max_sells: u32,
+    handle: State<'_, SentinelMonitorHandle>,
+) -> Result<(), String> {
+    if max_sells > 50 {
+        return Err("Max sells per tick must be at most 50".to_string());
+    }
+
+    handle.set_max_sells_per_tick(max_sells).await;
+    sentinel_loop::save_max_sells_per_tick(&app_handle, max_sells).await;
+    Ok(())
+}
+
+/// Set how failed sentinel sells are retried: the first retry after
+/// `baseSecs`, doubling up to `maxSecs`, deactivating the sentinel after
+/// `maxFailures` consecutive failures.
+#[tauri::command]
+pub async fn set_sentinel_sell_retry(
+    app_handle: tauri::AppHandle,
+    policy: SellRetryPolicy,
+    handle: State<'_, SentinelMonitorHandle>,
+) -> Result<(), String> {
+    policy.validate()?;
+
+    handle.set_sell_retry_policy(policy).await;
+    sentinel_loop::save_sell_retry_policy(&app_handle, &policy).await;
+    Ok(())
+}
+
+/// Set the default price source sentinels evaluate against: last trade, or
+/// the AMM mark price from pool reserves. Sentinels can override it.
+#[tauri::command]
+pub async fn set_sentinel_monitor_price_source(
+    app_handle: tauri::AppHandle,
+    price_source: PriceSource,
+    handle: State<'_, SentinelMonitorHandle>,
+) -> Result<(), String> {
+    handle.set_price_source(price_source).await;
+    sentinel_loop::save_price_source(&app_handle, price_source).await;
+    Ok(())
+}
+
+/// Set the minimum pool liquidity (in base currency) to keep holding a coin.
+/// Below it, sentinels sell the position before the pool drains any further.
+/// 0 turns the rule off.
+#[tauri::command]
+pub async fn set_sentinel_min_liquidity(
+    app_handle: tauri::AppHandle,
+    min_liquidity_usd: f64,
+    handle: State<'_, SentinelMonitorHandle>,
+) -> Result<(), String> {
+    if !min_liquidity_usd.is_finite() || min_liquidity_usd < 0.0 {
+        return Err("Minimum liquidity must be a non-negative number".to_string());
+    }
+
+    handle.set_min_liquidity_usd(min_liquidity_usd).await;
+    sentinel_loop::save_min_liquidity_usd(&app_handle, min_liquidity_usd).await;
+    Ok(())
+}
+
+/// Set what a take-profit does when the pool's spot price is more than
+/// `gapPct` below its target: sell anyway, sell only what fits within
+/// `maxSlippagePct`, or skip the sell and stay armed.
+#[tauri::command]
+pub async fn set_sentinel_tp_gap(
+    app_handle: tauri::AppHandle,
+    policy: TpGapPolicy,
+    handle: State<'_, SentinelMonitorHandle>,
+) -> Result<(), String> {
+    policy.validate()?;
+
+    handle.set_tp_gap_policy(policy).await;
+    sentinel_loop::save_tp_gap_policy(&app_handle, &policy).await;
+    Ok(())
+}
@@ -1,11 +1,19 @@
 //! Portfolio commands for Tauri
 
-use crate::AppState;
-use rugplay_core::{CoinHoldersResponse, MarketResponse, PortfolioResponse, PortfolioSummary, RecentTrade};
+use crate::price_freshness::{CoinQuote, PriceDecision};
+use crate::rebalance::{
+    plan_rebalance, validate_targets, RebalanceCoin, RebalanceFill, RebalanceOptions, RebalanceResult,
+};
+use crate::trade_amount::TradeAmount;
+use crate::trade_executor::{TradeExecutorHandle, REBALANCE_SOURCE};
+use crate::trade_reason::TradeReason;
+use crate::{save_automation_log, AppState};
+use rugplay_core::{CoinHoldersResponse, MarketResponse, PortfolioResponse, PortfolioSummary, RecentTrade, TradeType};
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
-use tauri::State;
-use tracing::{debug, error, info};
+use std::collections::HashMap;
+use tauri::{Manager, State};
+use tracing::{debug, error, info, warn};
 
 /// Get the current user's portfolio with all holdings
 #[tauri::command]
@@ -46,9 +54,16 @@ pub async fn get_portfolio(state: State<'_, AppState>) -> Result<PortfolioRespon
 
 /// Get a summary of the portfolio for header display
 #[tauri::command]
-pub async fn get_portfolio_summary(state: State<'_, AppState>) -> Result<PortfolioSummary, String> {
+pub async fn get_portfolio_summary(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PortfolioSummary, String> {
     let portfolio = get_portfolio(state).await?;
-    Ok(PortfolioSummary::from(&portfolio))
+    let frozen_reserve = match app_handle.try_state::<TradeExecutorHandle>() {
+        Some(executor) => executor.get_risk_limits().await.frozen_reserve_usd,
+        None => 0.0,
+    };
+    Ok(PortfolioSummary::from(&portfolio).with_frozen_reserve(frozen_reserve))
 }
 
 /// Get market coins with sorting
@@ -161,6 +176,40 @@ pub async fn get_coin_details(
     Ok(coin)
 }
 
+/// Get a price quote for the trade dialog, no older than the preview freshness policy
+#[tauri::command]
+pub async fn get_coin_quote(
+    symbol: String,
+    state: State<'_, AppState>,
+) -> Result<CoinQuote, String> {
+    let max_age = state.price_freshness.read().await.max_age(PriceDecision::TradePreview);
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let token = state
+        .encryptor
+        .decrypt(&sqlite::get_profile_token(db.pool(), active_profile.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Profile token not found")?)
+        .map_err(|e| e.to_string())?;
+    drop(db_guard);
+
+    let client = state.api_client(&token);
+    let coin = client.get_coin_within(&symbol, max_age).await.map_err(|e| {
+        error!("Failed to fetch quote for {}: {}", symbol, e);
+        e.to_string()
+    })?;
+
+    Ok(CoinQuote::from_coin(&coin, chrono::Utc::now()))
+}
+
 /// Get detailed coin information with chart data
 #[tauri::command]
 pub async fn get_coin_with_chart(
@@ -240,3 +289,142 @@ pub async fn get_recent_trades(
     debug!("Fetched {} recent trades (transfers filtered)", trades.len());
     Ok(trades)
 }
+
+/// Trade the portfolio toward target allocations, given as a percentage of
+/// total value per coin. Coins without a target are left alone. With
+/// `dry_run` the planned orders are returned without submitting anything.
+#[tauri::command]
+pub async fn rebalance_to_targets(
+    app_handle: tauri::AppHandle,
+    targets: HashMap<String, f64>,
+    dry_run: Option<bool>,
+    options: Option<RebalanceOptions>,
+    state: State<'_, AppState>,
+) -> Result<RebalanceResult, String> {
+    let targets: HashMap<String, f64> = targets.into_iter().map(|(s, p)| (s.trim().to_uppercase(), p)).collect();
+    validate_targets(&targets)?;
+    let options = options.unwrap_or_default();
+    let dry_run = dry_run.unwrap_or(false);
+
+    let token = {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let active_profile = sqlite::get_active_profile(db.pool())
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("No active profile")?;
+        state
+            .encryptor
+            .decrypt(&sqlite::get_profile_token(db.pool(), active_profile.id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or("Profile token not found")?)
+            .map_err(|e| e.to_string())?
+    };
+
+    let client = state.api_client(&token);
+    let portfolio = client.get_portfolio().await.map_err(|e| e.to_string())?;
+    let max_age = state.price_freshness.read().await.max_age(PriceDecision::TradePreview);
+
+    // Held coins count toward the total at their portfolio price; targeted
+    // coins are quoted for a fresh price and their pool depth
+    let mut coins: Vec<RebalanceCoin> = portfolio
+        .coin_holdings
+        .iter()
+        .map(|h| RebalanceCoin {
+            symbol: h.symbol.clone(),
+            quantity: h.quantity,
+            price: h.current_price,
+            pool_base: 0.0,
+        })
+        .collect();
+    for symbol in targets.keys() {
+        let coin = match client.get_coin_within(symbol, max_age).await {
+            Ok(coin) => coin,
+            Err(e) => {
+                warn!("Rebalance: could not quote {}: {}", symbol, e);
+                continue;
+            }
+        };
+        match coins.iter_mut().find(|c| &c.symbol == symbol) {
+            Some(held) => {
+                held.price = coin.current_price;
+                held.pool_base = coin.pool_base_currency_amount;
+            }
+            None => coins.push(RebalanceCoin {
+                symbol: symbol.clone(),
+                quantity: 0.0,
+                price: coin.current_price,
+                pool_base: coin.pool_base_currency_amount,
+            }),
+        }
+    }
+
+    let plan = plan_rebalance(portfolio.base_currency_balance, &coins, &targets, &options);
+    info!(
+        "Rebalance{}: {} orders, {} skipped, portfolio ${:.2}",
+        if dry_run { " preview" } else { "" },
+        plan.orders.len(),
+        plan.skipped.len(),
+        plan.total_value
+    );
+    if dry_run {
+        return Ok(RebalanceResult { plan, dry_run, fills: Vec::new() });
+    }
+
+    let executor = app_handle
+        .try_state::<TradeExecutorHandle>()
+        .ok_or("Trade executor not running")?;
+
+    // Orders run one at a time, sells first, so buys see the sell proceeds
+    let mut fills = Vec::with_capacity(plan.orders.len());
+    for order in &plan.orders {
+        let reason = TradeReason::Rebalance {
+            symbol: order.symbol.clone(),
+            current_pct: order.current_pct,
+            target_pct: order.target_pct,
+        };
+        let result = executor
+            .submit_manual_trade(
+                order.symbol.clone(),
+                order.trade_type,
+                TradeAmount::native(order.trade_type, order.amount),
+                reason.clone(),
+                REBALANCE_SOURCE,
+                false,
+            )
+            .await;
+
+        let error = match result {
+            Ok(response) => {
+                let (action, usd) = match order.trade_type {
+                    TradeType::Buy => ("BUY", response.total_cost.unwrap_or(order.value_usd)),
+                    TradeType::Sell => ("SELL", response.total_received.unwrap_or(order.value_usd)),
+                };
+                save_automation_log(
+                    &app_handle,
+                    REBALANCE_SOURCE,
+                    &order.symbol,
+                    &order.symbol,
+                    action,
+                    usd,
+                    &serde_json::json!({ "reason": reason.to_value() }).to_string(),
+                )
+                .await;
+                None
+            }
+            Err(e) => {
+                error!("Rebalance: {} of {} failed: {}", reason, order.symbol, e);
+                Some(e)
+            }
+        };
+        fills.push(RebalanceFill {
+            symbol: order.symbol.clone(),
+            trade_type: order.trade_type,
+            success: error.is_none(),
+            error,
+        });
+    }
+
+    Ok(RebalanceResult { plan, dry_run, fills })
+}
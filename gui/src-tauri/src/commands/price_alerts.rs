@@ -0,0 +1,157 @@
+//! Price alert commands for Tauri
+
+use crate::equity_alert_loop::load_equity_alert_config;
+use crate::equity_alerts::EquityAlertConfig;
+use crate::price_alerts::{starts_armed, AlertCondition};
+use crate::AppState;
+use rugplay_persistence::sqlite;
+use serde::Serialize;
+use tauri::State;
+use tracing::{debug, info};
+
+/// A stored price alert
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAlert {
+    pub id: i64,
+    pub symbol: String,
+    pub condition: AlertCondition,
+    pub armed: bool,
+    pub last_triggered_at: Option<String>,
+    pub created_at: Option<String>,
+}
+
+/// Create a price alert for any coin. An above/below alert whose level is
+/// already crossed starts disarmed and waits for the price to come back.
+#[tauri::command]
+pub async fn create_price_alert(
+    symbol: String,
+    condition: AlertCondition,
+    state: State<'_, AppState>,
+) -> Result<PriceAlert, String> {
+    let symbol = symbol.trim().trim_start_matches('*').to_uppercase();
+    if symbol.is_empty() {
+        return Err("Symbol is required".to_string());
+    }
+    condition.validate()?;
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let token = state
+        .encryptor
+        .decrypt(&sqlite::get_profile_token(db.pool(), active_profile.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Profile token not found")?)
+        .map_err(|e| e.to_string())?;
+    let client = state.api_client(&token);
+    let price = match client.get_coin(&symbol).await {
+        Ok(coin) if coin.has_valid_price() => Some(coin.current_price),
+        Ok(_) => None,
+        Err(e) => {
+            debug!("Price alert for {} created without a current price: {}", symbol, e);
+            None
+        }
+    };
+
+    let armed = starts_armed(&condition, price);
+    let (kind, target, window_secs) = condition.columns();
+    let id = sqlite::create_price_alert(db.pool(), active_profile.id, &symbol, kind, target, window_secs, armed)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!("Price alert #{} created: {} (armed: {})", id, condition.describe(&symbol), armed);
+    Ok(PriceAlert {
+        id,
+        symbol,
+        condition,
+        armed,
+        last_triggered_at: None,
+        created_at: None,
+    })
+}
+
+/// Price alerts for the active profile
+#[tauri::command]
+pub async fn get_price_alerts(state: State<'_, AppState>) -> Result<Vec<PriceAlert>, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let rows = sqlite::get_price_alerts(db.pool(), active_profile.id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let condition = AlertCondition::from_row(&row)?;
+            Some(PriceAlert {
+                id: row.id,
+                symbol: row.symbol,
+                condition,
+                armed: row.armed,
+                last_triggered_at: row.last_triggered_at,
+                created_at: row.created_at,
+            })
+        })
+        .collect())
+}
+
+/// Delete a price alert
+#[tauri::command]
+pub async fn delete_price_alert(id: i64, state: State<'_, AppState>) -> Result<bool, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    sqlite::delete_price_alert(db.pool(), active_profile.id, id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Total-equity alert settings
+#[tauri::command]
+pub async fn get_equity_alerts(app_handle: tauri::AppHandle) -> Result<EquityAlertConfig, String> {
+    Ok(load_equity_alert_config(&app_handle).await.unwrap_or_default())
+}
+
+/// Save total-equity alert settings; the sampler picks them up on its next tick
+#[tauri::command]
+pub async fn set_equity_alerts(
+    config: EquityAlertConfig,
+    state: State<'_, AppState>,
+) -> Result<EquityAlertConfig, String> {
+    config.validate()?;
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('equity_alerts', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    info!(
+        "Equity alerts {}: {} milestone(s), {} move rule(s)",
+        if config.enabled { "enabled" } else { "disabled" },
+        config.milestones.len(),
+        config.moves.len()
+    );
+    Ok(config)
+}
@@ -1,6 +1,12 @@
-//! Tauri commands for Risk Limits configuration
+//! Tauri commands for Risk Limits, split sell, price freshness, coin
+//! precision, poll interval and entry script configuration
 
-use crate::trade_executor::{RiskLimits, TradeExecutorHandle};
+use crate::entry_script::{EntryScript, EntryScriptConfig};
+use crate::order_split::{SplitSellConfig, MAX_TRANCHES};
+use crate::poll_interval::AdaptivePollPolicy;
+use crate::price_freshness::PriceFreshnessPolicy;
+use crate::trade_executor::{DrawdownStatus, RiskLimits, TradeExecutorHandle};
+use rugplay_core::{ensure_finite, CoinPrecision, MAX_COIN_DECIMALS};
 use tauri::{Manager, State};
 
 #[tauri::command]
@@ -16,6 +22,24 @@ pub async fn set_risk_limits(
     handle: State<'_, TradeExecutorHandle>,
     limits: RiskLimits,
 ) -> Result<RiskLimits, String> {
+    for (field, value) in [
+        ("maxPositionUsd", limits.max_position_usd),
+        ("maxDailyVolumeUsd", limits.max_daily_volume_usd),
+        ("frozenReserveUsd", limits.frozen_reserve_usd),
+    ] {
+        ensure_finite(field, value).map_err(|e| e.to_string())?;
+    }
+    ensure_finite("maxTradeUsd", limits.max_trade_usd).map_err(|e| e.to_string())?;
+    ensure_finite("maxDrawdownPct", limits.max_drawdown_pct).map_err(|e| e.to_string())?;
+    if !(0.0..100.0).contains(&limits.max_drawdown_pct) {
+        return Err("Max drawdown must be between 0 and 100%".to_string());
+    }
+    if limits.adaptive_rate_limit && limits.max_rate_limit_ms < limits.rate_limit_ms {
+        return Err("Max delay between trades can't be below the minimum".to_string());
+    }
+    for (module, cap) in &limits.module_max_trade_usd {
+        ensure_finite(&format!("moduleMaxTradeUsd.{}", module), *cap).map_err(|e| e.to_string())?;
+    }
     handle.set_risk_limits(limits.clone()).await;
 
     // Persist to DB
@@ -35,6 +59,15 @@ pub async fn set_risk_limits(
     Ok(limits)
 }
 
+/// Portfolio value against today's peak and whether the drawdown circuit
+/// breaker is refusing buys
+#[tauri::command]
+pub async fn get_drawdown_status(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<DrawdownStatus, String> {
+    Ok(handle.drawdown_status().await)
+}
+
 /// Load persisted risk limits from DB (called during startup)
 pub async fn load_risk_limits_from_db(app_handle: &tauri::AppHandle) -> Option<RiskLimits> {
     let state = app_handle.state::<crate::AppState>();
@@ -51,3 +84,305 @@ pub async fn load_risk_limits_from_db(app_handle: &tauri::AppHandle) -> Option<R
 
     serde_json::from_str(&json).ok()
 }
+
+/// Whether automated orders are simulated instead of sent
+#[tauri::command]
+pub async fn get_paper_trading(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<bool, String> {
+    Ok(handle.is_paper_trading())
+}
+
+/// Switch automated orders between paper and real trading
+#[tauri::command]
+pub async fn set_paper_trading(
+    app_handle: tauri::AppHandle,
+    handle: State<'_, TradeExecutorHandle>,
+    enabled: bool,
+) -> Result<bool, String> {
+    // Persist first: a dry run must not silently go live after a restart
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    sqlx::query::<sqlx::Sqlite>(
+        "INSERT INTO settings (key, value) VALUES ('paper_trading', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1"
+    )
+    .bind(if enabled { "true" } else { "false" })
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    handle.set_paper_trading(enabled);
+    Ok(enabled)
+}
+
+/// Load the persisted paper trading flag from DB (called during startup)
+pub async fn load_paper_trading_from_db(app_handle: &tauri::AppHandle) -> bool {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return false };
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'paper_trading'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .is_some_and(|v| v == "true")
+}
+
+#[tauri::command]
+pub async fn get_split_sell_config(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<SplitSellConfig, String> {
+    Ok(handle.get_split_config().await)
+}
+
+#[tauri::command]
+pub async fn set_split_sell_config(
+    app_handle: tauri::AppHandle,
+    handle: State<'_, TradeExecutorHandle>,
+    config: SplitSellConfig,
+) -> Result<SplitSellConfig, String> {
+    if config.tranches == 0 || config.tranches > MAX_TRANCHES {
+        return Err(format!("Tranches must be between 1 and {}", MAX_TRANCHES));
+    }
+    if config.min_value_usd < 0.0 {
+        return Err("Minimum value cannot be negative".to_string());
+    }
+    handle.set_split_config(config.clone()).await;
+
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    if let Some(db) = db_guard.as_ref() {
+        let json = serde_json::to_string(&config).unwrap_or_default();
+        let _ = sqlx::query::<sqlx::Sqlite>(
+            "INSERT INTO settings (key, value) VALUES ('split_sell_config', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1"
+        )
+        .bind(&json)
+        .execute(db.pool())
+        .await;
+    }
+
+    Ok(config)
+}
+
+/// Load persisted split sell config from DB (called during startup)
+pub async fn load_split_sell_config_from_db(app_handle: &tauri::AppHandle) -> Option<SplitSellConfig> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    let json: String = sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'split_sell_config'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+#[tauri::command]
+pub async fn get_price_freshness(
+    state: State<'_, crate::AppState>,
+) -> Result<PriceFreshnessPolicy, String> {
+    Ok(state.price_freshness.read().await.clone())
+}
+
+#[tauri::command]
+pub async fn set_price_freshness(
+    state: State<'_, crate::AppState>,
+    policy: PriceFreshnessPolicy,
+) -> Result<PriceFreshnessPolicy, String> {
+    *state.price_freshness.write().await = policy.clone();
+
+    let db_guard = state.db.read().await;
+    if let Some(db) = db_guard.as_ref() {
+        let json = serde_json::to_string(&policy).unwrap_or_default();
+        let _ = sqlx::query::<sqlx::Sqlite>(
+            "INSERT INTO settings (key, value) VALUES ('price_freshness', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1"
+        )
+        .bind(&json)
+        .execute(db.pool())
+        .await;
+    }
+
+    Ok(policy)
+}
+
+/// Load persisted price freshness policy from DB (called during startup)
+pub async fn load_price_freshness_from_db(app_handle: &tauri::AppHandle) -> Option<PriceFreshnessPolicy> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    let json: String = sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'price_freshness'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+#[tauri::command]
+pub async fn get_coin_precision(
+    state: State<'_, crate::AppState>,
+) -> Result<CoinPrecision, String> {
+    Ok(state.coin_precision.read().await.clone())
+}
+
+#[tauri::command]
+pub async fn set_coin_precision(
+    state: State<'_, crate::AppState>,
+    precision: CoinPrecision,
+) -> Result<CoinPrecision, String> {
+    let too_fine = std::iter::once(("default", &precision.default_decimals))
+        .chain(precision.coin_decimals.iter().map(|(s, d)| (s.as_str(), d)))
+        .find(|(_, d)| **d > MAX_COIN_DECIMALS);
+    if let Some((coin, decimals)) = too_fine {
+        return Err(format!("{} decimals for {} exceeds the maximum of {}", decimals, coin, MAX_COIN_DECIMALS));
+    }
+    *state.coin_precision.write().await = precision.clone();
+
+    let db_guard = state.db.read().await;
+    if let Some(db) = db_guard.as_ref() {
+        let json = serde_json::to_string(&precision).unwrap_or_default();
+        let _ = sqlx::query::<sqlx::Sqlite>(
+            "INSERT INTO settings (key, value) VALUES ('coin_precision', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1"
+        )
+        .bind(&json)
+        .execute(db.pool())
+        .await;
+    }
+
+    Ok(precision)
+}
+
+/// Load persisted coin precision from DB (called during startup)
+pub async fn load_coin_precision_from_db(app_handle: &tauri::AppHandle) -> Option<CoinPrecision> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    let json: String = sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'coin_precision'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+#[tauri::command]
+pub async fn get_adaptive_poll(
+    state: State<'_, crate::AppState>,
+) -> Result<AdaptivePollPolicy, String> {
+    Ok(state.poll_policy.read().await.clone())
+}
+
+#[tauri::command]
+pub async fn set_adaptive_poll(
+    state: State<'_, crate::AppState>,
+    policy: AdaptivePollPolicy,
+) -> Result<AdaptivePollPolicy, String> {
+    if policy.min_secs == 0 {
+        return Err("Minimum poll interval must be at least 1 second".to_string());
+    }
+    if policy.max_secs < policy.min_secs {
+        return Err("Maximum poll interval can't be below the minimum".to_string());
+    }
+    *state.poll_policy.write().await = policy.clone();
+
+    let db_guard = state.db.read().await;
+    if let Some(db) = db_guard.as_ref() {
+        let json = serde_json::to_string(&policy).unwrap_or_default();
+        let _ = sqlx::query::<sqlx::Sqlite>(
+            "INSERT INTO settings (key, value) VALUES ('adaptive_poll', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1"
+        )
+        .bind(&json)
+        .execute(db.pool())
+        .await;
+    }
+
+    Ok(policy)
+}
+
+/// Load persisted adaptive poll bounds from DB (called during startup)
+pub async fn load_adaptive_poll_from_db(app_handle: &tauri::AppHandle) -> Option<AdaptivePollPolicy> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    let json: String = sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'adaptive_poll'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()?;
+
+    serde_json::from_str(&json).ok()
+}
+
+#[tauri::command]
+pub async fn get_entry_script(app_handle: tauri::AppHandle) -> Result<EntryScriptConfig, String> {
+    Ok(load_entry_script_from_db(&app_handle).await.unwrap_or_default())
+}
+
+/// Compile and install the entry script; a script that doesn't compile is
+/// refused and the previous one stays in force
+#[tauri::command]
+pub async fn set_entry_script(
+    app_handle: tauri::AppHandle,
+    config: EntryScriptConfig,
+) -> Result<EntryScriptConfig, String> {
+    let compiled = EntryScript::compile(&config)?;
+
+    let state = app_handle.state::<crate::AppState>();
+    {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+        sqlx::query::<sqlx::Sqlite>(
+            "INSERT INTO settings (key, value) VALUES ('entry_script', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1"
+        )
+        .bind(&json)
+        .execute(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+    *state.entry_script.write().await = compiled.map(std::sync::Arc::new);
+
+    Ok(config)
+}
+
+/// Load the persisted entry script from DB (called during startup)
+pub async fn load_entry_script_from_db(app_handle: &tauri::AppHandle) -> Option<EntryScriptConfig> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    let json: String = sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'entry_script'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()?;
+
+    serde_json::from_str(&json).ok()
+}
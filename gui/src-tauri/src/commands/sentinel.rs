@@ -1,8 +1,12 @@
 //! Sentinel commands for managing stop-loss/take-profit
 
 use crate::AppState;
-use crate::sentinel_eval::evaluate_sentinel;
-use rugplay_core::{TradeRequest, TradeType, truncate_to_8_decimals};
+use crate::pagination::{page_bounds, Page};
+use crate::protective_sentinel::guarded_entry_price;
+use crate::sentinel_eval::{after_sell, simulate_path, trigger_sell_percentage, trigger_sell_quantity, validate_tp_ladder, AfterSell, PriceSource, SimulatedTrigger, TpGapDecision};
+use crate::sentinel_loop::{fetch_sentinel_prices, SentinelMonitorHandle};
+use crate::sentinel_reconcile::{self, ReconcileSummary, SentinelSyncDefaults};
+use rugplay_core::{parse_db_timestamp, TradeRequest, TradeType, truncate_to_8_decimals};
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::{Deserialize, Serialize};
@@ -25,10 +29,20 @@ pub struct SentinelConfig {
     pub is_active: bool,
     pub created_at: Option<String>,
     pub triggered_at: Option<String>,
+    /// Own price source, or `None` to follow the monitor default
+    pub price_source: Option<PriceSource>,
+    /// Per-trigger sell %, or `None` to use `sell_percentage`
+    pub sl_sell_pct: Option<f64>,
+    pub tp_sell_pct: Option<f64>,
+    pub ts_sell_pct: Option<f64>,
+    /// Stored take-profit ladder, or `None` for the single take-profit
+    pub tp_ladder: Option<Vec<sqlite::TpRung>>,
+    pub tp_rungs_filled: i64,
 }
 
 impl From<sqlite::SentinelRow> for SentinelConfig {
     fn from(row: sqlite::SentinelRow) -> Self {
+        let tp_ladder = row.tp_rungs();
         Self {
             id: row.id,
             symbol: row.symbol,
@@ -41,6 +55,12 @@ impl From<sqlite::SentinelRow> for SentinelConfig {
             is_active: row.is_active,
             created_at: row.created_at,
             triggered_at: row.triggered_at,
+            price_source: row.price_source.as_deref().and_then(PriceSource::parse),
+            sl_sell_pct: row.sl_sell_pct,
+            tp_sell_pct: row.tp_sell_pct,
+            ts_sell_pct: row.ts_sell_pct,
+            tp_ladder,
+            tp_rungs_filled: row.tp_rungs_filled,
         }
     }
 }
@@ -107,9 +127,14 @@ pub async fn create_sentinel(
     Ok(SentinelConfig::from(sentinel))
 }
 
-/// List all sentinels for the active profile
+/// List the sentinels of the active profile, newest first. Without a limit
+/// the page holds every sentinel.
 #[tauri::command]
-pub async fn list_sentinels(state: State<'_, AppState>) -> Result<Vec<SentinelConfig>, String> {
+pub async fn list_sentinels(
+    offset: Option<u32>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<Page<SentinelConfig>, String> {
     debug!("Listing sentinels");
 
     let db_guard = state.db.read().await;
@@ -152,9 +177,11 @@ pub async fn list_sentinels(state: State<'_, AppState>) -> Result<Vec<SentinelCo
         }
     }
 
-    let result: Vec<SentinelConfig> = best_per_symbol.into_values().collect();
+    let mut result: Vec<SentinelConfig> = best_per_symbol.into_values().collect();
+    result.sort_by_key(|s| std::cmp::Reverse(s.id));
     debug!("Found {} sentinels (filtered from raw)", result.len());
-    Ok(result)
+    let (offset, limit) = page_bounds(offset, limit, u32::MAX, u32::MAX);
+    Ok(Page::slice(result, offset, limit))
 }
 
 /// Toggle a sentinel's active status
@@ -243,6 +270,104 @@ pub async fn update_sentinel(
     Ok(())
 }
 
+/// Pin a sentinel to a price source, or pass `None` to follow the monitor default
+#[tauri::command]
+pub async fn set_sentinel_price_source(
+    sentinel_id: i64,
+    price_source: Option<PriceSource>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlite::set_sentinel_price_source(db.pool(), sentinel_id, price_source.map(|p| p.as_str()))
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!("Sentinel {} price source: {}", sentinel_id, price_source.map_or("default", |p| p.as_str()));
+    Ok(())
+}
+
+/// Set how much a stop-loss, take-profit or trailing stop sells; `None`
+/// leaves that trigger on the sentinel's sell percentage
+#[tauri::command]
+pub async fn set_sentinel_trigger_sells(
+    sentinel_id: i64,
+    sl_sell_pct: Option<f64>,
+    tp_sell_pct: Option<f64>,
+    ts_sell_pct: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    for (field, value) in [("slSellPct", sl_sell_pct), ("tpSellPct", tp_sell_pct), ("tsSellPct", ts_sell_pct)] {
+        if let Some(pct) = value {
+            if !pct.is_finite() || pct <= 0.0 || pct > 100.0 {
+                return Err(format!("{} must be between 0 and 100, got {}", field, pct));
+            }
+        }
+    }
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlite::set_sentinel_trigger_sells(db.pool(), sentinel_id, sl_sell_pct, tp_sell_pct, ts_sell_pct)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!("Sentinel {} trigger sells: SL={:?} TP={:?} TS={:?}",
+          sentinel_id, sl_sell_pct, tp_sell_pct, ts_sell_pct);
+    Ok(())
+}
+
+/// Set a take-profit ladder that scales out of the position in steps, or
+/// clear it (`None`) to go back to the single take-profit. Rung sell
+/// percentages are shares of the position when the ladder is set.
+#[tauri::command]
+pub async fn set_sentinel_tp_ladder(
+    sentinel_id: i64,
+    ladder: Option<Vec<sqlite::TpRung>>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(ladder) = &ladder {
+        validate_tp_ladder(ladder)?;
+    }
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlite::set_sentinel_tp_ladder(db.pool(), sentinel_id, ladder.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    info!("Sentinel {} take-profit ladder: {:?}", sentinel_id, ladder);
+    Ok(())
+}
+
+/// Longest price path `simulate_sentinel_path` replays
+const MAX_SIMULATION_STEPS: usize = 10_000;
+
+/// Replay a hypothetical price path through a sentinel's current settings
+/// and return the triggers it would fire. Nothing is sold or saved.
+#[tauri::command]
+pub async fn simulate_sentinel_path(
+    sentinel_id: i64,
+    price_path: Vec<f64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<SimulatedTrigger>, String> {
+    if price_path.len() > MAX_SIMULATION_STEPS {
+        return Err(format!("Price path is limited to {} steps, got {}", MAX_SIMULATION_STEPS, price_path.len()));
+    }
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let sentinel = sqlite::get_sentinel_by_id(db.pool(), sentinel_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Sentinel {} not found", sentinel_id))?;
+
+    Ok(simulate_path(&sentinel, &price_path))
+}
+
 /// Result from a sentinel check
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -259,6 +384,7 @@ pub struct SentinelCheckResult {
 /// Also syncs sentinels with portfolio (removes sold coins, adds new ones).
 #[tauri::command]
 pub async fn run_sentinel_check(
+    monitor: State<'_, SentinelMonitorHandle>,
     state: State<'_, AppState>,
 ) -> Result<SentinelCheckResult, String> {
     info!("Running sentinel check");
@@ -288,6 +414,7 @@ pub async fn run_sentinel_check(
         (profile, tok, sents)
     }; // db_guard dropped here
 
+    // Uncached: a manual check must judge live prices
     let client = RugplayClient::new(&token);
 
     // Phase 2: API calls (no DB lock held)
@@ -369,8 +496,8 @@ pub async fn run_sentinel_check(
             }
             // Skip sentinels in grace period (created within last 120s)
             if let Some(ref created_str) = s.created_at {
-                if let Ok(created) = chrono::NaiveDateTime::parse_from_str(created_str, "%Y-%m-%d %H:%M:%S") {
-                    let age = now_epoch - created.and_utc().timestamp();
+                if let Some(created) = parse_db_timestamp(created_str) {
+                    let age = now_epoch - created.timestamp();
                     if age < 120 {
                         debug!("Sentinel #{}: skipping {} (grace period, {}s old)", s.id, s.symbol, age);
                         return false;
@@ -381,6 +508,15 @@ pub async fn run_sentinel_check(
         })
         .collect();
 
+    let prices = fetch_sentinel_prices(
+        &client,
+        &active_sentinels,
+        monitor.get_price_source().await,
+        monitor.get_min_liquidity_usd().await,
+        monitor.get_tp_gap_policy().await,
+    )
+    .await;
+
     for sentinel in &active_sentinels {
         result.checked += 1;
 
@@ -389,7 +525,7 @@ pub async fn run_sentinel_check(
             None => continue,
         };
 
-        let current_price = holding.current_price;
+        let current_price = prices.price_for(sentinel, holding.current_price);
 
         // Update highest price seen (short DB lock)
         if current_price > sentinel.highest_price_seen {
@@ -399,14 +535,26 @@ pub async fn run_sentinel_check(
             }
         }
 
-        let trigger = evaluate_sentinel(sentinel, current_price);
+        let trigger = prices.evaluate(sentinel, current_price);
 
         if let Some(trigger) = trigger {
             let reason = trigger.reason.clone();
+            let gap = prices.tp_gap(sentinel, &trigger);
+            if let TpGapDecision::Abort { spot } = gap {
+                info!("Sentinel take-profit for {} held back: spot {} is below the target {}", sentinel.symbol, spot, trigger.trigger_price);
+                continue;
+            }
             info!("Sentinel triggered for {}: {}", sentinel.symbol, reason);
 
-            let sell_qty = holding.quantity * (sentinel.sell_percentage / 100.0);
-            let sell_qty = truncate_to_8_decimals(sell_qty);
+            let sell_pct = trigger_sell_percentage(sentinel, &trigger);
+            let sell_qty = trigger_sell_quantity(sentinel, &trigger, sell_pct, holding.quantity);
+            let target_qty = truncate_to_8_decimals(sell_qty);
+            let sell_qty = match gap {
+                TpGapDecision::Capped { max_quantity, .. } if max_quantity < target_qty => {
+                    truncate_to_8_decimals(max_quantity)
+                }
+                _ => target_qty,
+            };
 
             if sell_qty > 0.0 {
                 let trade_request = TradeRequest {
@@ -416,8 +564,10 @@ pub async fn run_sentinel_check(
 
                 tokio::time::sleep(std::time::Duration::from_millis(500)).await;
 
-                // Trade with no DB lock held
-                match client.trade(&sentinel.symbol, trade_request).await {
+                // Trade with no DB lock held, then drop the coin's now stale cached quote
+                let trade_result = client.trade(&sentinel.symbol, trade_request).await;
+                state.coin_cache.invalidate(&sentinel.symbol);
+                match trade_result {
                     Ok(trade_response) => {
                         info!("Sentinel sell executed for {}: sold {} coins", sentinel.symbol, sell_qty);
                         result.triggered += 1;
@@ -427,11 +577,24 @@ pub async fn run_sentinel_check(
                         if trade_response.success {
                             let db_guard = state.db.read().await;
                             if let Some(db) = db_guard.as_ref() {
-                                if sentinel.sell_percentage >= 100.0 {
-                                    let _ = sqlite::mark_sentinel_triggered(db.pool(), sentinel.id).await;
+                                let unsold = truncate_to_8_decimals(target_qty - sell_qty);
+                                if unsold > 0.0 {
+                                    info!("Sentinel #{} stays armed for the {} {} left after a capped sell", sentinel.id, unsold, sentinel.symbol);
+                                    let _ = sqlite::set_sentinel_unsold(db.pool(), sentinel.id, trigger.trigger_type.as_str(), unsold).await;
                                 } else {
-                                    let _ = sqlite::rearm_sentinel(db.pool(), sentinel.id, current_price).await;
-                                    info!("Sentinel #{} re-armed after partial sell ({:.0}%)", sentinel.id, sentinel.sell_percentage);
+                                    match after_sell(sentinel, &trigger, sell_pct) {
+                                        AfterSell::Triggered => {
+                                            let _ = sqlite::mark_sentinel_triggered(db.pool(), sentinel.id).await;
+                                        }
+                                        AfterSell::NextRung => {
+                                            let _ = sqlite::fill_tp_rung(db.pool(), sentinel.id).await;
+                                            info!("Sentinel #{} take-profit rung {} filled, armed for the next", sentinel.id, trigger.tp_rung.map_or(0, |r| r + 1));
+                                        }
+                                        AfterSell::Rearm => {
+                                            let _ = sqlite::rearm_sentinel(db.pool(), sentinel.id, current_price).await;
+                                            info!("Sentinel #{} re-armed after partial sell ({:.0}%)", sentinel.id, sell_pct);
+                                        }
+                                    }
                                 }
                             }
                         } else {
@@ -534,24 +697,13 @@ pub async fn sync_sentinels(
 
         // Guard: if the weighted avg entry would cause an IMMEDIATE SL trigger
         // at the current price, use the current market price instead.
-        let entry_price = if let Some(sl) = default_stop_loss_pct {
-            if sl < 0.0 {
-                let sl_floor = avg_entry * (1.0 + sl / 100.0);
-                if holding.current_price <= sl_floor && holding.current_price > 0.0 {
-                    info!(
-                        "Sync: using current price {:.8} instead of avg {:.8} for {} (would instantly trigger SL={:.0}%)",
-                        holding.current_price, avg_entry, holding.symbol, sl
-                    );
-                    holding.current_price
-                } else {
-                    avg_entry
-                }
-            } else {
-                avg_entry
-            }
-        } else {
-            avg_entry
-        };
+        let entry_price = guarded_entry_price(avg_entry, holding.current_price, default_stop_loss_pct);
+        if entry_price != avg_entry {
+            info!(
+                "Sync: using current price {:.8} instead of avg {:.8} for {} (would instantly trigger SL={:.0}%)",
+                holding.current_price, avg_entry, holding.symbol, default_stop_loss_pct.unwrap_or_default()
+            );
+        }
 
         if sentinel_symbols.contains(&holding.symbol) {
             // Existing sentinel: sync entry price with portfolio avg if it drifted
@@ -608,6 +760,53 @@ pub async fn sync_sentinels(
     Ok(result)
 }
 
+/// Full one-shot reconcile of sentinels against the portfolio: removes
+/// orphaned, blacklisted and duplicate sentinels and covers every unprotected
+/// holding with the default sentinel. The thorough, manual version of the
+/// monitor's auto-sync.
+#[tauri::command]
+pub async fn reconcile_sentinels(state: State<'_, AppState>) -> Result<ReconcileSummary, String> {
+    info!("Reconciling sentinels with portfolio");
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let token = state
+        .encryptor
+        .decrypt(&sqlite::get_profile_token(db.pool(), active_profile.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Profile token not found")?)
+        .map_err(|e| e.to_string())?;
+
+    // A failed fetch must not look like an empty portfolio: that would delete every sentinel
+    let portfolio = RugplayClient::new(&token).get_portfolio().await.map_err(|e| e.to_string())?;
+    let defaults = SentinelSyncDefaults::load(db.pool()).await.map_err(|e| e.to_string())?;
+
+    let summary = sentinel_reconcile::reconcile(db.pool(), active_profile.id, &portfolio.coin_holdings, &defaults)
+        .await
+        .map_err(|e| {
+            error!("Sentinel reconcile failed: {}", e);
+            e.to_string()
+        })?;
+
+    info!(
+        "Sentinel reconcile: {} orphaned, {} triggered, {} blacklisted, {} duplicate removed; {} created ({} guarded)",
+        summary.orphans_removed,
+        summary.triggered_purged,
+        summary.blacklisted_removed,
+        summary.duplicates_removed,
+        summary.created.len(),
+        summary.guarded.len()
+    );
+    Ok(summary)
+}
+
 /// Update ALL sentinels with new settings (batch apply)
 #[tauri::command]
 pub async fn update_all_sentinels(
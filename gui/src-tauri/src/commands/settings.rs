@@ -3,10 +3,18 @@
 //! Migrates sentinel defaults + blacklisted coins from localStorage
 //! to the SQLite settings table in the backend.
 
+use crate::cache_warm::CacheWarmConfig;
+use crate::cache_warm_loop::load_cache_warm_config;
+use crate::config_diff::FieldChange;
+use crate::pagination::{page_bounds, Page, DEFAULT_PAGE_LIMIT};
+use crate::protective_sentinel::EntryPriceSource;
+use crate::retention::{self, RetentionConfig, MIN_RETENTION_DAYS};
 use crate::AppState;
+use rugplay_persistence::sqlite::{self, MaintenanceReport, PruneReport};
 use serde::{Deserialize, Serialize};
 use sqlx;
 use tauri::State;
+use tracing::info;
 
 /// Application settings (sentinel defaults + blacklist)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +33,9 @@ pub struct SentinelDefaults {
     pub take_profit_pct: f64,
     pub trailing_stop_pct: Option<f64>,
     pub sell_percentage: f64,
+    /// Entry price for sentinels the sniper, mirror and dip buyer create
+    #[serde(default)]
+    pub entry_price_source: EntryPriceSource,
 }
 
 /// Reset app settings to research-backed defaults.
@@ -41,6 +52,7 @@ pub async fn reset_app_settings(
             take_profit_pct: research.take_profit_pct,
             trailing_stop_pct: research.trailing_stop_pct,
             sell_percentage: research.sell_percentage,
+            entry_price_source: EntryPriceSource::default(),
         },
         auto_manage_sentinels: true,
         blacklisted_coins: Vec::new(),
@@ -133,10 +145,14 @@ pub async fn set_app_settings(
 pub struct StorageInfo {
     pub data_dir: String,
     pub db_size_bytes: u64,
+    /// Size of the write-ahead log, if the database runs in WAL mode
+    pub wal_size_bytes: u64,
     pub profile_count: i64,
     pub transaction_count: i64,
     pub sentinel_count: i64,
     pub automation_log_count: i64,
+    /// Automation log entries already pruned into the history rollup
+    pub archived_log_count: i64,
 }
 
 /// Get the local data directory path plus DB size
@@ -150,9 +166,12 @@ pub async fn get_storage_info(
     let db_size_bytes = std::fs::metadata(&db_path)
         .map(|m| m.len())
         .unwrap_or(0);
+    let wal_size_bytes = std::fs::metadata(state.data_dir.join("rugplay.db-wal"))
+        .map(|m| m.len())
+        .unwrap_or(0);
 
     let db_guard = state.db.read().await;
-    let (profile_count, transaction_count, sentinel_count, automation_log_count) =
+    let (profile_count, transaction_count, sentinel_count, automation_log_count, archived_log_count) =
         if let Some(db) = db_guard.as_ref() {
             let profiles: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM profiles")
                 .fetch_one(db.pool()).await.unwrap_or(0);
@@ -162,18 +181,22 @@ pub async fn get_storage_info(
                 .fetch_one(db.pool()).await.unwrap_or(0);
             let logs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM automation_log")
                 .fetch_one(db.pool()).await.unwrap_or(0);
-            (profiles, txns, sentinels, logs)
+            let archived: i64 = sqlx::query_scalar("SELECT COALESCE(SUM(entries), 0) FROM history_rollup")
+                .fetch_one(db.pool()).await.unwrap_or(0);
+            (profiles, txns, sentinels, logs, archived)
         } else {
-            (0, 0, 0, 0)
+            (0, 0, 0, 0, 0)
         };
 
     Ok(StorageInfo {
         data_dir,
         db_size_bytes,
+        wal_size_bytes,
         profile_count,
         transaction_count,
         sentinel_count,
         automation_log_count,
+        archived_log_count,
     })
 }
 
@@ -234,18 +257,140 @@ pub async fn clear_transaction_history(
         .map_err(|e| e.to_string())
 }
 
-/// Run VACUUM to compact the database file
+/// Compact the database (VACUUM), refresh planner stats (ANALYZE) and
+/// optionally checkpoint the WAL. Reports size before and after.
 #[tauri::command]
-pub async fn vacuum_database(
+pub async fn run_db_maintenance(
     state: State<'_, AppState>,
-) -> Result<(), String> {
+    checkpoint: Option<bool>,
+) -> Result<MaintenanceReport, String> {
     let db_guard = state.db.read().await;
     let db = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    sqlx::query("VACUUM")
-        .execute(db.pool())
+    let report = sqlite::run_maintenance(db.pool(), checkpoint.unwrap_or(true))
         .await
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    info!(
+        "DB maintenance: {} -> {} bytes in {}ms",
+        report.size_before_bytes,
+        report.size_after_bytes,
+        report.duration_ms
+    );
+    Ok(report)
+}
+
+/// Get the history retention policy
+#[tauri::command]
+pub async fn get_retention_config(
+    app_handle: tauri::AppHandle,
+) -> Result<RetentionConfig, String> {
+    Ok(retention::load_retention_config(&app_handle).await)
+}
+
+/// Update the history retention policy
+#[tauri::command]
+pub async fn set_retention_config(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+    retention_days: u32,
+) -> Result<RetentionConfig, String> {
+    if retention_days < MIN_RETENTION_DAYS {
+        return Err(format!("Retention must be at least {} day", MIN_RETENTION_DAYS));
+    }
+    let mut config = retention::load_retention_config(&app_handle).await;
+    config.enabled = enabled;
+    config.retention_days = retention_days;
+    retention::save_retention_config(&app_handle, &config).await?;
+    Ok(config)
+}
+
+/// Get the coin cache warming settings
+#[tauri::command]
+pub async fn get_cache_warming(app_handle: tauri::AppHandle) -> Result<CacheWarmConfig, String> {
+    Ok(load_cache_warm_config(&app_handle).await.unwrap_or_default())
+}
+
+/// Save the coin cache warming settings; the warmer picks them up on its next tick
+#[tauri::command]
+pub async fn set_cache_warming(
+    config: CacheWarmConfig,
+    state: State<'_, AppState>,
+) -> Result<CacheWarmConfig, String> {
+    config.validate()?;
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('cache_warming', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    info!(
+        "Cache warming {} (lead {}s, {} per tick)",
+        if config.enabled { "enabled" } else { "disabled" },
+        config.lead_secs,
+        config.max_per_tick
+    );
+    Ok(config)
+}
+
+/// Delete automation logs, snipe logs and triggered sentinels older than N days.
+/// Automation log totals are kept in the history rollup.
+#[tauri::command]
+pub async fn prune_history(
+    app_handle: tauri::AppHandle,
+    older_than_days: u32,
+) -> Result<PruneReport, String> {
+    if older_than_days < MIN_RETENTION_DAYS {
+        return Err(format!("Retention must be at least {} day", MIN_RETENTION_DAYS));
+    }
+    retention::run_prune(&app_handle, older_than_days).await
+}
+
+/// One recorded config save
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigHistoryEntry {
+    pub id: i64,
+    pub module: String,
+    pub changes: Vec<FieldChange>,
+    pub changed_at: Option<String>,
+}
+
+/// What each config save changed, newest first; all modules when `module`
+/// is not given
+#[tauri::command]
+pub async fn get_config_history(
+    state: State<'_, AppState>,
+    module: Option<String>,
+    offset: Option<u32>,
+    limit: Option<u32>,
+) -> Result<Page<ConfigHistoryEntry>, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let (offset, limit) = page_bounds(offset, limit, DEFAULT_PAGE_LIMIT, 200);
+
+    let total = sqlite::count_config_history(db.pool(), module.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+    let rows = sqlite::get_config_history(db.pool(), module.as_deref(), offset, limit)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let items = rows
+        .into_iter()
+        .map(|row| ConfigHistoryEntry {
+            id: row.id,
+            module: row.module,
+            changes: serde_json::from_str(&row.changes).unwrap_or_default(),
+            changed_at: row.changed_at,
+        })
+        .collect();
+    Ok(Page::new(items, total, offset, limit))
 }
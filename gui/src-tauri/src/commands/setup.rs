@@ -0,0 +1,97 @@
+//! Tauri commands for first-run setup
+//!
+//! `get_setup_state` reports which onboarding steps are open; profile steps
+//! are completed with the existing auth commands (`add_profile`,
+//! `select_profile`, `update_profile_token`) and `apply_setup_defaults`
+//! saves starting configs for the rest.
+
+use crate::setup_state::{SetupFacts, SetupState, SetupStep};
+use crate::trade_executor::TradeExecutorHandle;
+use crate::AppState;
+use rugplay_persistence::sqlite;
+use tauri::State;
+use tracing::info;
+
+/// Report what is still missing before the app is fully set up
+#[tauri::command]
+pub async fn get_setup_state(state: State<'_, AppState>) -> Result<SetupState, String> {
+    Ok(SetupState::from_facts(&gather_setup_facts(&state).await))
+}
+
+/// Save default sentinel settings and risk limits for any that were never
+/// configured. Existing configs are left untouched.
+#[tauri::command]
+pub async fn apply_setup_defaults(
+    state: State<'_, AppState>,
+    executor: State<'_, TradeExecutorHandle>,
+) -> Result<SetupState, String> {
+    let before = SetupState::from_facts(&gather_setup_facts(&state).await);
+    if !before.database_ready {
+        return Err("Database not initialized".to_string());
+    }
+
+    if !before.is_done(SetupStep::ConfigureSettings) {
+        super::settings::reset_app_settings(state.clone()).await?;
+        info!("Setup: saved default app settings");
+    }
+
+    if !before.is_done(SetupStep::ReviewRiskLimits) {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let limits = executor.get_risk_limits().await;
+        let json = serde_json::to_string(&limits).map_err(|e| e.to_string())?;
+        sqlx::query(
+            "INSERT INTO settings (key, value) VALUES ('risk_limits', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1",
+        )
+        .bind(&json)
+        .execute(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+        info!("Setup: saved default risk limits");
+    }
+
+    Ok(SetupState::from_facts(&gather_setup_facts(&state).await))
+}
+
+/// Inspect the database, profiles, token encryption and saved configs
+async fn gather_setup_facts(state: &AppState) -> SetupFacts {
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else {
+        return SetupFacts::default();
+    };
+
+    let profile_count = sqlite::list_profiles(db.pool())
+        .await
+        .map(|p| p.len())
+        .unwrap_or(0);
+
+    let active = sqlite::get_active_profile(db.pool()).await.ok().flatten();
+    let active_token_readable = match &active {
+        Some(profile) => Some(
+            matches!(
+                sqlite::get_profile_token(db.pool(), profile.id).await,
+                Ok(Some(enc)) if state.encryptor.decrypt(&enc).is_ok()
+            ),
+        ),
+        None => None,
+    };
+
+    let has_setting = |key: &'static str| async move {
+        sqlx::query_scalar::<sqlx::Sqlite, i64>("SELECT COUNT(*) FROM settings WHERE key = ?")
+            .bind(key)
+            .fetch_one(db.pool())
+            .await
+            .unwrap_or(0)
+            > 0
+    };
+
+    SetupFacts {
+        database_ready: true,
+        profile_count,
+        active_profile_id: active.map(|p| p.id),
+        active_token_readable,
+        has_app_settings: has_setting("app_settings").await,
+        has_risk_limits: has_setting("risk_limits").await,
+    }
+}
@@ -65,6 +65,7 @@ pub async fn set_sniper_enabled(
     enabled: bool,
 ) -> Result<bool, String> {
     if enabled {
+        crate::kill_switch::ensure_not_killed(&app_handle).await?;
         handle.enable();
     } else {
         handle.disable();
@@ -80,8 +81,10 @@ pub async fn update_sniper_config(
     handle: State<'_, SniperHandle>,
     config: SniperConfig,
 ) -> Result<SniperConfig, String> {
+    let previous = handle.get_config().await;
     handle.set_config(config.clone()).await;
     sniper::save_sniper_config(&app_handle, &config).await;
+    crate::record_config_change(&app_handle, "sniper", &previous, &config).await;
     Ok(config)
 }
 
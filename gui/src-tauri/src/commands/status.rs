@@ -0,0 +1,142 @@
+//! Consolidated bot status for the dashboard
+
+use crate::bot_status::{BotStatus, ModuleState, ProfileStatus, StatusInputs};
+use crate::diagnostics::{self, CacheStats, DiagnosticReport, RecentLogs};
+use crate::mobile_server::MobileServerHandle;
+use crate::notifications::NotificationHandle;
+use crate::sentinel_loop::MonitorStatus;
+use crate::warmup::WarmupStatus;
+use crate::{AppState, DipBuyerHandle, HarvesterHandle, MirrorHandle, SentinelMonitorHandle, SniperHandle, TradeExecutorHandle};
+use rugplay_persistence::sqlite;
+use serde_json::json;
+use std::sync::Arc;
+use tauri::Manager;
+
+/// Gather every subsystem's state into one snapshot.
+///
+/// With `probe_portfolio` the active profile's portfolio is fetched, which
+/// doubles as the connectivity check; without it connectivity is unknown.
+pub async fn collect_bot_status(app_handle: &tauri::AppHandle, probe_portfolio: bool) -> BotStatus {
+    let state = app_handle.state::<AppState>();
+    let mut inputs = StatusInputs::default();
+
+    if let Some(handle) = app_handle.try_state::<SentinelMonitorHandle>() {
+        let status = handle.status().await;
+        inputs.modules.insert(
+            "sentinel",
+            ModuleState {
+                enabled: status != MonitorStatus::Stopped,
+                paused: handle.is_paused().await,
+            },
+        );
+    }
+    if let Some(handle) = app_handle.try_state::<SniperHandle>() {
+        inputs.modules.insert("sniper", ModuleState { enabled: handle.is_enabled(), paused: false });
+    }
+    if let Some(handle) = app_handle.try_state::<MirrorHandle>() {
+        inputs.modules.insert("mirror", ModuleState { enabled: handle.is_enabled(), paused: false });
+    }
+    if let Some(handle) = app_handle.try_state::<DipBuyerHandle>() {
+        inputs.modules.insert("dipbuyer", ModuleState { enabled: handle.is_enabled(), paused: false });
+    }
+    if let Some(handle) = app_handle.try_state::<HarvesterHandle>() {
+        inputs.modules.insert("harvester", ModuleState { enabled: handle.is_enabled(), paused: false });
+    }
+
+    if let Some(executor) = app_handle.try_state::<TradeExecutorHandle>() {
+        inputs.executor = Some(executor.status().await);
+    }
+    inputs.warmup = Some(state.warmup.status());
+    inputs.acquisition_paused = crate::commands::load_acquisition_pause_state(app_handle).await.paused;
+
+    {
+        let db_guard = state.db.read().await;
+        if let Some(db) = db_guard.as_ref() {
+            if let Ok(Some(profile)) = sqlite::get_active_profile(db.pool()).await {
+                inputs.active_profile = Some(ProfileStatus { id: profile.id, username: profile.username });
+            }
+        }
+    }
+
+    if probe_portfolio && inputs.active_profile.is_some() {
+        inputs.portfolio = Some(crate::commands::get_portfolio_summary(app_handle.clone(), state.clone()).await);
+    }
+
+    BotStatus::build(inputs, &state.heartbeats, chrono::Utc::now())
+}
+
+/// Status of every module, the trade executor, the kill switch, the active
+/// profile, API connectivity and the portfolio in a single call
+#[tauri::command]
+pub async fn get_bot_status(app_handle: tauri::AppHandle) -> Result<BotStatus, String> {
+    Ok(collect_bot_status(&app_handle, true).await)
+}
+
+/// Progress of the startup warm-up that holds automated trades back
+#[tauri::command]
+pub async fn get_warmup_status(app_handle: tauri::AppHandle) -> Result<WarmupStatus, String> {
+    Ok(app_handle.state::<AppState>().warmup.status())
+}
+
+/// Redacted snapshot of configuration, module and executor state, schema,
+/// cache and recent logs for attaching to a bug report
+#[tauri::command]
+pub async fn generate_diagnostic_report(app_handle: tauri::AppHandle) -> Result<serde_json::Value, String> {
+    let state = app_handle.state::<AppState>();
+    let status = collect_bot_status(&app_handle, false).await;
+
+    let mut report = DiagnosticReport {
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+        status: serde_json::to_value(&status).map_err(|e| e.to_string())?,
+        cache: CacheStats { coin_entries: state.coin_cache.len() },
+        ..Default::default()
+    };
+
+    let modules = &mut report.modules;
+    if let Some(handle) = app_handle.try_state::<SentinelMonitorHandle>() {
+        let monitor = crate::commands::get_sentinel_monitor_status(handle).await?;
+        modules.insert("sentinel".to_string(), json!(monitor));
+    }
+    if let Some(handle) = app_handle.try_state::<SniperHandle>() {
+        modules.insert("sniper".to_string(), json!(handle.get_config().await));
+    }
+    if let Some(handle) = app_handle.try_state::<MirrorHandle>() {
+        modules.insert("mirror".to_string(), json!(handle.get_config().await));
+    }
+    if let Some(handle) = app_handle.try_state::<DipBuyerHandle>() {
+        modules.insert("dipbuyer".to_string(), json!(handle.get_config().await));
+    }
+    if let Some(executor) = app_handle.try_state::<TradeExecutorHandle>() {
+        modules.insert(
+            "executor".to_string(),
+            json!({
+                "riskLimits": executor.get_risk_limits().await,
+                "paperTrading": executor.is_paper_trading(),
+                "drawdown": executor.drawdown_status().await,
+                "stats": executor.stats().await,
+            }),
+        );
+    }
+    if let Some(handle) = app_handle.try_state::<NotificationHandle>() {
+        modules.insert("notifications".to_string(), json!(handle.get_config().await));
+    }
+    if let Some(handle) = app_handle.try_state::<MobileServerHandle>() {
+        modules.insert("mobile".to_string(), json!(handle.get_status().await));
+    }
+
+    {
+        let db_guard = state.db.read().await;
+        if let Some(db) = db_guard.as_ref() {
+            report.schema = diagnostics::collect_schema(db.pool()).await.map_err(|e| e.to_string())?;
+            report.settings = diagnostics::collect_settings(db.pool()).await.map_err(|e| e.to_string())?;
+        }
+    }
+
+    if let Some(logs) = app_handle.try_state::<Arc<RecentLogs>>() {
+        report.recent_logs = logs.lines();
+    }
+
+    Ok(report.into_redacted_json())
+}
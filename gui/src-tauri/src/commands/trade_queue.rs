@@ -0,0 +1,105 @@
+//! Tauri commands for inspecting and editing the trade executor queue,
+//! and for approving the trades semi-auto mode holds back
+
+use crate::trade_approval::{PendingApproval, SemiAutoConfig};
+use crate::trade_executor::{PendingTrade, TradeExecutorHandle};
+use crate::trade_pacing::ExecutorStats;
+use tauri::{Manager, State};
+
+/// List orders that are queued but have not started executing
+#[tauri::command]
+pub async fn list_pending_trades(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<Vec<PendingTrade>, String> {
+    Ok(handle.list_pending_trades().await)
+}
+
+/// Remove a queued order before the executor runs it
+#[tauri::command]
+pub async fn cancel_pending_trade(
+    handle: State<'_, TradeExecutorHandle>,
+    id: u64,
+) -> Result<PendingTrade, String> {
+    handle.cancel_pending_trade(id).await
+}
+
+/// Trade API request counts, rate limit hits and the current delay between trades
+#[tauri::command]
+pub async fn get_executor_stats(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<ExecutorStats, String> {
+    Ok(handle.stats().await)
+}
+
+/// Automated trades waiting for the user's approval, oldest first
+#[tauri::command]
+pub async fn list_pending_approvals(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<Vec<PendingApproval>, String> {
+    Ok(handle.list_pending_approvals().await)
+}
+
+/// Let a held trade go ahead
+#[tauri::command]
+pub async fn approve_pending(
+    handle: State<'_, TradeExecutorHandle>,
+    id: u64,
+) -> Result<PendingApproval, String> {
+    handle.approve_pending(id).await
+}
+
+/// Drop a held trade; the module that proposed it is told it was rejected
+#[tauri::command]
+pub async fn reject_pending(
+    handle: State<'_, TradeExecutorHandle>,
+    id: u64,
+) -> Result<PendingApproval, String> {
+    handle.reject_pending(id).await
+}
+
+#[tauri::command]
+pub async fn get_semi_auto_config(
+    handle: State<'_, TradeExecutorHandle>,
+) -> Result<SemiAutoConfig, String> {
+    Ok(handle.get_semi_auto_config().await)
+}
+
+#[tauri::command]
+pub async fn set_semi_auto_config(
+    app_handle: tauri::AppHandle,
+    handle: State<'_, TradeExecutorHandle>,
+    config: SemiAutoConfig,
+) -> Result<SemiAutoConfig, String> {
+    config.validate()?;
+
+    // Persist first, like paper trading: a restart must not quietly go fully automatic
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let json = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    sqlx::query::<sqlx::Sqlite>(
+        "INSERT INTO settings (key, value) VALUES ('semi_auto', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1"
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    handle.set_semi_auto_config(config.clone()).await;
+    Ok(config)
+}
+
+/// Load the persisted semi-auto settings from DB (called during startup)
+pub async fn load_semi_auto_from_db(app_handle: &tauri::AppHandle) -> Option<SemiAutoConfig> {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>("SELECT value FROM settings WHERE key = 'semi_auto'")
+        .fetch_optional(db.pool())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+}
@@ -1,11 +1,16 @@
 //! Trade commands for Tauri
 
-use crate::AppState;
-use rugplay_core::{TradeRequest, TradeType, truncate_to_8_decimals};
+use crate::order_split::SplitFill;
+use crate::price_freshness::{CoinQuote, PriceDecision};
+use crate::trade_amount::TradeAmount;
+use crate::trade_executor::{TradeCheck, TradeExecutorHandle, TradePriority, MANUAL_SOURCE};
+use crate::trade_reason::TradeReason;
+use crate::{save_automation_log, AppState};
+use rugplay_core::{TradeRequest, TradeResponse, TradeType, truncate_to_8_decimals};
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{Manager, State};
 use tracing::{debug, error, info};
 
 /// Trade direction from frontend
@@ -24,10 +29,13 @@ pub struct TradeResult {
     pub trade_type: String,
     pub coins_amount: f64,
     pub usd_amount: f64,
-    pub new_price: f64,
+    /// `None` when the API reported no price for the fill
+    pub new_price: Option<f64>,
     pub price_impact: f64,
     pub new_balance: f64,
     pub message: String,
+    /// Coins a split sell left unsold after a tranche failed
+    pub unsold_amount: f64,
 }
 
 /// Execute a trade (buy or sell)
@@ -36,11 +44,16 @@ pub struct TradeResult {
 /// * `symbol` - Coin symbol (e.g., "BTC")
 /// * `direction` - "BUY" or "SELL"
 /// * `amount` - For BUY: USD amount to spend. For SELL: coin amount to sell.
+/// * `override_reserve` - Allow a BUY to spend into the frozen reserve
+/// * `split` - Sell in tranches using the split sell config
 #[tauri::command]
 pub async fn execute_trade(
+    app_handle: tauri::AppHandle,
     symbol: String,
     direction: TradeDirection,
     amount: f64,
+    override_reserve: Option<bool>,
+    split: Option<bool>,
     state: State<'_, AppState>,
 ) -> Result<TradeResult, String> {
     info!("Executing {:?} trade for {} - amount: {}", direction, symbol, amount);
@@ -66,7 +79,23 @@ pub async fn execute_trade(
             .ok_or("Profile token not found")?)
         .map_err(|e| e.to_string())?;
 
-    let client = RugplayClient::new(&token);
+    // The shared cache lets the trade drop the coin's now-stale quote
+    let client = RugplayClient::new(&token).with_cache(state.coin_cache.clone());
+
+    // Manual buys honour the frozen reserve unless the user overrides it
+    if matches!(direction, TradeDirection::Buy) {
+        if let Some(executor) = app_handle.try_state::<TradeExecutorHandle>() {
+            let limits = executor.get_risk_limits().await;
+            if limits.frozen_reserve_usd > 0.0 && !override_reserve.unwrap_or(false) {
+                let balance = client
+                    .get_portfolio()
+                    .await
+                    .map_err(|e| format!("Could not verify frozen reserve: {}", e))?
+                    .base_currency_balance;
+                limits.check_frozen_reserve(balance, amount, false)?;
+            }
+        }
+    }
 
     // Convert direction to trade type
     let trade_type = match direction {
@@ -91,33 +120,34 @@ pub async fn execute_trade(
         amount: adjusted_amount,
     };
 
-    match client.trade(&symbol, request).await {
-        Ok(response) => {
-            let (coins_amount, usd_amount, message) = match direction {
-                TradeDirection::Buy => {
-                    let coins = response.coins_bought.unwrap_or(0.0);
-                    let cost = response.total_cost.unwrap_or(adjusted_amount);
-                    (coins, cost, format!("Bought {:.8} {} for ${:.2}", coins, symbol, cost))
-                }
-                TradeDirection::Sell => {
-                    let coins = response.coins_sold.unwrap_or(adjusted_amount);
-                    let received = response.total_received.unwrap_or(0.0);
-                    (coins, received, format!("Sold {:.8} {} for ${:.2}", coins, symbol, received))
-                }
-            };
-
-            info!("Trade successful: {}", message);
-
-            Ok(TradeResult {
-                success: true,
-                trade_type: response.trade_type,
-                coins_amount,
-                usd_amount,
-                new_price: response.new_price,
-                price_impact: response.price_impact,
-                new_balance: response.new_balance,
-                message,
-            })
+    // Split sells go through the executor so tranches are serialized with other trades
+    let result = if split.unwrap_or(false) && matches!(direction, TradeDirection::Sell) {
+        drop(db_guard);
+        let executor = app_handle
+            .try_state::<TradeExecutorHandle>()
+            .ok_or("Trade executor not running")?;
+        executor
+            .submit_split_sell(
+                symbol.clone(),
+                adjusted_amount,
+                TradePriority::Normal,
+                TradeReason::Manual { side: TradeType::Sell, split: true, mobile: false, note: None },
+                "manual",
+            )
+            .await
+            .map(|fill| split_result(&symbol, adjusted_amount, fill))
+    } else {
+        client
+            .trade(&symbol, request)
+            .await
+            .map(|response| trade_result(direction, &symbol, adjusted_amount, response))
+            .map_err(|e| e.to_string())
+    };
+
+    match result {
+        Ok(result) => {
+            info!("Trade successful: {}", result.message);
+            Ok(result)
         }
         Err(e) => {
             error!("Trade failed: {}", e);
@@ -126,6 +156,242 @@ pub async fn execute_trade(
     }
 }
 
+/// Build the frontend result from an API trade response
+fn trade_result(
+    direction: TradeDirection,
+    symbol: &str,
+    adjusted_amount: f64,
+    response: TradeResponse,
+) -> TradeResult {
+    let (coins_amount, usd_amount, message) = match direction {
+        TradeDirection::Buy => {
+            let coins = response.coins_bought.unwrap_or(0.0);
+            let cost = response.total_cost.unwrap_or(adjusted_amount);
+            (coins, cost, format!("Bought {:.8} {} for ${:.2}", coins, symbol, cost))
+        }
+        TradeDirection::Sell => {
+            let coins = response.coins_sold.unwrap_or(adjusted_amount);
+            let received = response.total_received.unwrap_or(0.0);
+            (coins, received, format!("Sold {:.8} {} for ${:.2}", coins, symbol, received))
+        }
+    };
+
+    TradeResult {
+        success: true,
+        new_price: response.fill_price(),
+        trade_type: response.trade_type,
+        coins_amount,
+        usd_amount,
+        price_impact: response.price_impact,
+        new_balance: response.new_balance,
+        message,
+        unsold_amount: 0.0,
+    }
+}
+
+/// Build the frontend result from a split sell of `quantity`, saying what a
+/// failed tranche left unsold
+fn split_result(symbol: &str, quantity: f64, fill: SplitFill) -> TradeResult {
+    let partial = fill.is_partial();
+    let mut result = trade_result(TradeDirection::Sell, symbol, fill.sold, fill.response);
+    if partial {
+        result.unsold_amount = fill.unsold;
+        result.message = format!(
+            "{}; {:.8} of {:.8} left unsold: {}",
+            result.message,
+            fill.unsold,
+            quantity,
+            fill.error.as_deref().unwrap_or("split sell stopped")
+        );
+    }
+    result
+}
+
+/// Result of a manual trade, with the quote it was confirmed against
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManualTradeReceipt {
+    #[serde(flatten)]
+    pub trade: TradeResult,
+    /// Always "manual", so history and PnL can separate it from automation
+    pub source: String,
+    pub quoted_price: f64,
+    pub quote_fetched_at: Option<String>,
+    pub quote_age_ms: u64,
+    pub note: Option<String>,
+}
+
+/// Request to place a manual trade
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManualTradeRequest {
+    pub symbol: String,
+    pub direction: TradeDirection,
+    pub amount: f64,
+    pub override_reserve: Option<bool>,
+    pub split: Option<bool>,
+    pub note: Option<String>,
+}
+
+/// Place a user-initiated trade through the executor.
+///
+/// Unlike `execute_trade`, the order is tagged with the `manual` source and
+/// queued with everything else, so the executor's hard risk limits (max
+/// position, daily count/volume, loss cooldown) still apply. The acquisition
+/// pause only disables automation modules and does not block it. A fresh
+/// quote is fetched first under the trade-preview freshness policy and
+/// returned with the fill, and the trade is written to the automation log
+/// under the `manual` module. An optional `note` ("testing thesis X") is
+/// used as the order reason and kept with the trade in the journal.
+#[tauri::command]
+pub async fn manual_trade(
+    app_handle: tauri::AppHandle,
+    request: ManualTradeRequest,
+    state: State<'_, AppState>,
+) -> Result<ManualTradeReceipt, String> {
+    let ManualTradeRequest { symbol, direction, amount, override_reserve, split, note } = request;
+    info!("Manual {:?} trade for {} - amount: {}", direction, symbol, amount);
+
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err("Amount must be greater than 0".to_string());
+    }
+
+    let max_age = state.price_freshness.read().await.max_age(PriceDecision::TradePreview);
+
+    let token = {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let active_profile = sqlite::get_active_profile(db.pool())
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("No active profile")?;
+        state
+            .encryptor
+            .decrypt(&sqlite::get_profile_token(db.pool(), active_profile.id)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or("Profile token not found")?)
+            .map_err(|e| e.to_string())?
+    };
+
+    let client = state.api_client(&token);
+    let coin = client
+        .get_coin_within(&symbol, max_age)
+        .await
+        .map_err(|e| format!("Could not quote {}: {}", symbol, e))?;
+    let quote = CoinQuote::from_coin(&coin, chrono::Utc::now());
+
+    let executor = app_handle
+        .try_state::<TradeExecutorHandle>()
+        .ok_or("Trade executor not running")?;
+
+    let (trade_type, adjusted_amount) = match direction {
+        TradeDirection::Buy => (TradeType::Buy, amount),
+        TradeDirection::Sell => (TradeType::Sell, truncate_to_8_decimals(amount)),
+    };
+
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let split = split.unwrap_or(false) && matches!(direction, TradeDirection::Sell);
+    let reason = TradeReason::Manual { side: trade_type, split, mobile: false, note: note.clone() };
+
+    let result = if split {
+        executor
+            .submit_split_sell(
+                symbol.clone(),
+                adjusted_amount,
+                TradePriority::Normal,
+                reason,
+                MANUAL_SOURCE,
+            )
+            .await
+            .map(|fill| split_result(&symbol, adjusted_amount, fill))
+    } else {
+        executor
+            .submit_manual_trade(
+                symbol.clone(),
+                trade_type,
+                TradeAmount::native(trade_type, adjusted_amount),
+                reason,
+                MANUAL_SOURCE,
+                override_reserve.unwrap_or(false),
+            )
+            .await
+            .map(|response| trade_result(direction, &symbol, adjusted_amount, response))
+    };
+
+    let trade = result.map_err(|e| {
+        error!("Manual trade failed: {}", e);
+        format!("Trade failed: {}", e)
+    })?;
+
+    info!("Manual trade successful: {}", trade.message);
+
+    let action = match direction {
+        TradeDirection::Buy => "BUY",
+        TradeDirection::Sell => "SELL",
+    };
+    save_automation_log(
+        &app_handle,
+        MANUAL_SOURCE,
+        &symbol,
+        &coin.name,
+        action,
+        trade.usd_amount,
+        &serde_json::json!({
+            "coinsAmount": trade.coins_amount,
+            "quotedPrice": quote.price,
+            "quoteAgeMs": quote.age_ms,
+            "newPrice": trade.new_price,
+            "priceImpact": trade.price_impact,
+            "note": note,
+        })
+        .to_string(),
+    )
+    .await;
+
+    Ok(ManualTradeReceipt {
+        trade,
+        source: MANUAL_SOURCE.to_string(),
+        quoted_price: quote.price,
+        quote_fetched_at: quote.fetched_at,
+        quote_age_ms: quote.age_ms,
+        note,
+    })
+}
+
+/// Run every risk check for a prospective trade without executing it.
+///
+/// Returns whether the executor would accept the order and, if not, each
+/// failing rule with its current value and limit.
+#[tauri::command]
+pub async fn check_trade_allowed(
+    app_handle: tauri::AppHandle,
+    symbol: String,
+    direction: TradeDirection,
+    amount: f64,
+    override_reserve: Option<bool>,
+) -> Result<TradeCheck, String> {
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err("Amount must be greater than 0".to_string());
+    }
+
+    let executor = app_handle
+        .try_state::<TradeExecutorHandle>()
+        .ok_or("Trade executor not running")?;
+    let trade_type = match direction {
+        TradeDirection::Buy => TradeType::Buy,
+        TradeDirection::Sell => TradeType::Sell,
+    };
+
+    let check = executor
+        .check_trade_allowed(&app_handle, &symbol, MANUAL_SOURCE, trade_type, amount, override_reserve.unwrap_or(false))
+        .await;
+    if !check.allowed {
+        debug!("{:?} {} of {} would be rejected: {} rule(s)", direction, amount, symbol, check.violations.len());
+    }
+    Ok(check)
+}
+
 /// Get the user's current balance
 #[tauri::command]
 pub async fn get_balance(state: State<'_, AppState>) -> Result<f64, String> {
@@ -0,0 +1,222 @@
+//! Watchlist commands for Tauri
+
+use crate::notifications::{NotificationCategory, NotificationHandle};
+use crate::trade_executor::TradeExecutorHandle;
+use crate::watchlist::{check_alert, enrich_watchlist, WatchedCoin};
+use crate::AppState;
+use rugplay_persistence::sqlite;
+use tauri::{Manager, State};
+use tracing::{debug, error, info, warn};
+
+fn normalize_symbol(symbol: &str) -> Result<String, String> {
+    let symbol = symbol.trim().trim_start_matches('*').to_uppercase();
+    if symbol.is_empty() {
+        return Err("Symbol is required".to_string());
+    }
+    Ok(symbol)
+}
+
+/// Watch a coin. Re-adding a watched coin only updates its alert threshold.
+#[tauri::command]
+pub async fn add_to_watchlist(
+    app_handle: tauri::AppHandle,
+    symbol: String,
+    alert_pct: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let symbol = normalize_symbol(&symbol)?;
+    if let Some(pct) = alert_pct {
+        if !pct.is_finite() || pct <= 0.0 {
+            return Err("Alert threshold must be greater than 0".to_string());
+        }
+    }
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    sqlite::add_to_watchlist(db.pool(), active_profile.id, &symbol, alert_pct)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    drop(db_guard);
+    sync_tradable_universe(&app_handle).await;
+
+    info!("Watching {} (alert: {:?}%)", symbol, alert_pct);
+    Ok(())
+}
+
+/// Stop watching a coin
+#[tauri::command]
+pub async fn remove_from_watchlist(
+    app_handle: tauri::AppHandle,
+    symbol: String,
+    state: State<'_, AppState>,
+) -> Result<bool, String> {
+    let symbol = normalize_symbol(&symbol)?;
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let removed = sqlite::remove_from_watchlist(db.pool(), active_profile.id, &symbol)
+        .await
+        .map_err(|e| e.to_string())?;
+    drop(db_guard);
+    sync_tradable_universe(&app_handle).await;
+    Ok(removed)
+}
+
+/// Watched coins with current prices (through the coin cache).
+///
+/// Coins seen for the first time get their current price as the alert
+/// reference. A coin that moved past its alert threshold sends a
+/// notification and its reference moves to the current price.
+#[tauri::command]
+pub async fn get_watchlist_with_prices(
+    app_handle: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<WatchedCoin>, String> {
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let active_profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+
+    let rows = sqlite::get_watchlist(db.pool(), active_profile.id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let token = state
+        .encryptor
+        .decrypt(&sqlite::get_profile_token(db.pool(), active_profile.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Profile token not found")?)
+        .map_err(|e| e.to_string())?;
+    let pool = db.pool().clone();
+    drop(db_guard);
+
+    let client = state.api_client(&token);
+    let watched = enrich_watchlist(&rows, |symbol| {
+        let client = &client;
+        async move { client.get_coin(&symbol).await.map_err(|e| e.to_string()) }
+    })
+    .await;
+    debug!("Watchlist priced: {} coins", watched.len());
+
+    for coin in &watched {
+        let Some(price) = coin.current_price else { continue };
+
+        let new_reference = if coin.reference_price.is_none() {
+            Some(price)
+        } else if let Some(alert) = check_alert(coin) {
+            info!("Watchlist alert: {} moved {:+.1}% to ${:.8}", alert.symbol, alert.move_pct, alert.price);
+            if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+                notif
+                    .notify_dedup(
+                        NotificationCategory::Alert,
+                        &format!("Watchlist: {} {:+.1}%", alert.symbol, alert.move_pct),
+                        &format!("${} is now ${:.8}", alert.symbol, alert.price),
+                    )
+                    .await;
+            }
+            Some(price)
+        } else {
+            None
+        };
+
+        if let Some(reference) = new_reference {
+            if let Err(e) = sqlite::set_watchlist_reference_price(&pool, active_profile.id, &coin.symbol, reference).await {
+                error!("Failed to update watchlist reference for {}: {}", coin.symbol, e);
+            }
+        }
+    }
+
+    Ok(watched)
+}
+
+#[tauri::command]
+pub async fn get_watchlist_only(handle: State<'_, TradeExecutorHandle>) -> Result<bool, String> {
+    Ok(handle.is_watchlist_only().await)
+}
+
+/// Limit automated buys to the watchlist (or stop limiting them)
+#[tauri::command]
+pub async fn set_watchlist_only(
+    app_handle: tauri::AppHandle,
+    handle: State<'_, TradeExecutorHandle>,
+    enabled: bool,
+) -> Result<bool, String> {
+    {
+        let state = app_handle.state::<AppState>();
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        sqlx::query::<sqlx::Sqlite>(
+            "INSERT INTO settings (key, value) VALUES ('watchlist_only', ?1)
+             ON CONFLICT(key) DO UPDATE SET value = ?1"
+        )
+        .bind(if enabled { "true" } else { "false" })
+        .execute(db.pool())
+        .await
+        .map_err(|e| e.to_string())?;
+    }
+
+    // The universe must be current before the gate closes
+    sync_tradable_universe(&app_handle).await;
+    handle.set_watchlist_only(enabled).await;
+    Ok(enabled)
+}
+
+/// Load the persisted watchlist-only flag from DB (called during startup)
+pub async fn load_watchlist_only_from_db(app_handle: &tauri::AppHandle) -> bool {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return false };
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'watchlist_only'"
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .is_some_and(|v| v == "true")
+}
+
+/// Hand the active profile's watchlist to the executor as the tradable
+/// universe. Called whenever the list or the active profile changes.
+pub async fn sync_tradable_universe(app_handle: &tauri::AppHandle) {
+    let Some(executor) = app_handle.try_state::<TradeExecutorHandle>() else { return };
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+
+    let symbols = match sqlite::get_active_profile(db.pool()).await {
+        Ok(Some(profile)) => match sqlite::get_watchlist(db.pool(), profile.id).await {
+            Ok(rows) => rows.into_iter().map(|row| row.symbol).collect(),
+            Err(e) => {
+                warn!("Failed to load the watchlist for the tradable universe: {}", e);
+                return;
+            }
+        },
+        // Nobody logged in: nothing is tradable
+        Ok(None) => Vec::new(),
+        Err(e) => {
+            warn!("Failed to load the active profile for the tradable universe: {}", e);
+            return;
+        }
+    };
+    executor.set_tradable_symbols(symbols).await;
+}
@@ -0,0 +1,159 @@
+//! Config Check — how much of the live market a module's filters let through
+//!
+//! A sniper or dip buyer config can be valid and still buy nothing, e.g. an
+//! age limit shorter than the creator cooldown or a volume floor no coin
+//! reaches. The check runs the module's own entry gates over a market
+//! sample and counts, per filter, how many coins it excludes on its own, so
+//! the filter doing the damage stands out.
+//!
+//! Only coin properties are checked. Per-trade gates (the dip buyer's sell
+//! value) and spend already used today are left out; the sniper's daily cap
+//! still shows up when a single buy would exceed it.
+
+use crate::skip_reason::{dip_market_skips, sniper_skips, DipGates, SkipReason, SniperGates};
+use rugplay_core::{parse_api_timestamp, MarketCoin};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Coins one filter excludes out of the sample
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterExclusion {
+    /// Skip reason kind, e.g. `lowVolume`
+    pub filter: String,
+    pub excluded: usize,
+    /// One excluded coin's message, to show the threshold in play
+    pub example: String,
+}
+
+/// Result of checking one module's filters against a market sample
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilterCheck {
+    pub module: String,
+    pub sampled: usize,
+    pub qualifying: usize,
+    pub qualifying_symbols: Vec<String>,
+    /// Most excluding first. A coin failing several filters counts for each.
+    pub exclusions: Vec<FilterExclusion>,
+    /// Filter excluding the most coins, if any excludes one
+    pub most_restrictive: Option<String>,
+}
+
+impl FilterCheck {
+    fn from_skips<'a>(module: &str, coins: &[MarketCoin], skips: impl Iterator<Item = (&'a MarketCoin, Vec<SkipReason>)>) -> Self {
+        let mut qualifying_symbols = Vec::new();
+        let mut counts: HashMap<&'static str, (usize, String)> = HashMap::new();
+        for (coin, reasons) in skips {
+            if reasons.is_empty() {
+                qualifying_symbols.push(coin.symbol.clone());
+            }
+            for reason in reasons {
+                let entry = counts
+                    .entry(reason.kind())
+                    .or_insert_with(|| (0, format!("{}: {}", coin.symbol, reason.message())));
+                entry.0 += 1;
+            }
+        }
+
+        let mut exclusions: Vec<FilterExclusion> = counts
+            .into_iter()
+            .map(|(filter, (excluded, example))| FilterExclusion { filter: filter.to_string(), excluded, example })
+            .collect();
+        exclusions.sort_by(|a, b| b.excluded.cmp(&a.excluded).then_with(|| a.filter.cmp(&b.filter)));
+
+        Self {
+            module: module.to_string(),
+            sampled: coins.len(),
+            qualifying: qualifying_symbols.len(),
+            qualifying_symbols,
+            most_restrictive: exclusions.first().map(|e| e.filter.clone()),
+            exclusions,
+        }
+    }
+}
+
+/// Run the sniper's filters over `coins` as if they were seen at `now`
+pub fn check_sniper(gates: &SniperGates, coins: &[MarketCoin], now: chrono::DateTime<chrono::Utc>) -> FilterCheck {
+    let skips = coins.iter().map(|coin| {
+        let age_secs = coin.created_at.as_deref()
+            .and_then(parse_api_timestamp)
+            .map(|dt| (now - dt).num_seconds());
+        (coin, sniper_skips(gates, coin, age_secs, 0.0))
+    });
+    FilterCheck::from_skips("sniper", coins, skips)
+}
+
+/// Run the dip buyer's market gates over `coins`. `gates_for` resolves the
+/// gates for a coin, since tiers make them depend on its market cap.
+pub fn check_dipbuyer(coins: &[MarketCoin], gates_for: impl Fn(&MarketCoin) -> DipGates) -> FilterCheck {
+    let skips = coins.iter().map(|coin| {
+        let gates = gates_for(coin);
+        (coin, dip_market_skips(&gates, coin.market_cap, coin.volume_24h, coin.change_24h))
+    });
+    FilterCheck::from_skips("dipbuyer", coins, skips)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(symbol: &str, market_cap: f64, volume_24h: f64, change_24h: f64, created_at: &str) -> MarketCoin {
+        MarketCoin {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            icon: None,
+            current_price: 0.01,
+            market_cap: Some(market_cap),
+            volume_24h,
+            change_24h,
+            created_at: Some(created_at.to_string()),
+            creator_name: Some(format!("{}_dev", symbol.to_lowercase())),
+        }
+    }
+
+    #[test]
+    fn test_canned_snapshot_names_the_most_restrictive_filter() {
+        let now = parse_api_timestamp("2026-03-01T12:00:00Z").unwrap();
+        let snapshot = vec![
+            coin("FRESH", 2_000.0, 50.0, 5.0, "2026-03-01T11:58:00Z"),   // 120s old
+            coin("BABY", 1_500.0, 0.0, 0.0, "2026-03-01T11:59:30Z"),     // 30s old
+            coin("STALE", 3_000.0, 800.0, -10.0, "2026-03-01T11:50:00Z"), // 600s old
+            coin("OLD", 9_000.0, 5_000.0, -70.0, "2026-03-01T10:00:00Z"),
+            coin("BIG", 250_000.0, 90_000.0, -20.0, "2026-02-20T00:00:00Z"),
+        ];
+
+        let blacklist = vec!["big_dev".to_string()];
+        let sniper = SniperGates {
+            max_market_cap_usd: 100_000.0,
+            max_coin_age_secs: 300,
+            min_coin_age_secs: 65,
+            blacklisted_creators: &blacklist,
+            max_daily_spend_usd: 0.0,
+            buy_amount_usd: 10.0,
+        };
+        let check = check_sniper(&sniper, &snapshot, now);
+        assert_eq!((check.sampled, check.qualifying), (5, 1));
+        assert_eq!(check.qualifying_symbols, vec!["FRESH"]);
+        assert_eq!(check.most_restrictive.as_deref(), Some("coinTooOld"));
+        let counts: Vec<(&str, usize)> = check.exclusions.iter().map(|e| (e.filter.as_str(), e.excluded)).collect();
+        // BIG fails three filters and counts for each
+        assert_eq!(
+            counts,
+            vec![("coinTooOld", 3), ("blacklistedCreator", 1), ("creatorCooldown", 1), ("highMarketCap", 1)]
+        );
+        assert_eq!(check.exclusions[2].example, "BABY: In creator cooldown (age 30s < 65s)");
+
+        // Tiered gates: coins over $100k need more volume
+        let dip = check_dipbuyer(&snapshot, |c| DipGates {
+            min_sell_value_usd: 0.0,
+            min_volume_24h: if c.market_cap > Some(100_000.0) { 100_000.0 } else { 500.0 },
+            min_market_cap: 2_000.0,
+            max_market_cap: 0.0,
+            max_price_drop_pct: -50.0,
+        });
+        assert_eq!(dip.qualifying_symbols, vec!["STALE"]);
+        assert_eq!(dip.most_restrictive.as_deref(), Some("lowVolume"));
+        assert_eq!(dip.exclusions[0].excluded, 3);
+    }
+}
@@ -0,0 +1,146 @@
+//! Config Diff — which fields a config save changed
+//!
+//! Saving a module config replaces the whole struct, so neither the logs nor
+//! the user can tell afterwards what an edit touched. The old and new
+//! configs are compared field by field through their serialized form;
+//! nested objects are walked with dotted paths, lists are compared whole.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One field that differs between two configs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    /// Serialized field name, dotted for nested fields (`signalWeights.momentum`)
+    pub field: String,
+    pub kind: ChangeKind,
+    pub old: Option<Value>,
+    pub new: Option<Value>,
+}
+
+/// Emitted after a config save that changed something
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigChangedEvent {
+    pub module: String,
+    pub changes: Vec<FieldChange>,
+}
+
+impl FieldChange {
+    /// Short form for logs, e.g. `buyAmountUsd: 10 -> 25`
+    pub fn summary(&self) -> String {
+        let show = |v: &Option<Value>| v.as_ref().map_or("-".to_string(), Value::to_string);
+        format!("{}: {} -> {}", self.field, show(&self.old), show(&self.new))
+    }
+}
+
+/// Fields that differ between `old` and `new`, sorted by field name.
+/// Unchanged fields are left out.
+pub fn diff_configs<T: Serialize>(old: &T, new: &T) -> Vec<FieldChange> {
+    let old = serde_json::to_value(old).unwrap_or(Value::Null);
+    let new = serde_json::to_value(new).unwrap_or(Value::Null);
+    diff_values(&old, &new)
+}
+
+/// [`diff_configs`] for already serialized configs, e.g. one stored by an
+/// older version that lacks newer fields
+pub fn diff_values(old: &Value, new: &Value) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    walk("", old, new, &mut changes);
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
+fn walk(prefix: &str, old: &Value, new: &Value, changes: &mut Vec<FieldChange>) {
+    let (Value::Object(old_map), Value::Object(new_map)) = (old, new) else {
+        if old != new {
+            changes.push(FieldChange {
+                field: prefix.to_string(),
+                kind: ChangeKind::Changed,
+                old: Some(old.clone()),
+                new: Some(new.clone()),
+            });
+        }
+        return;
+    };
+
+    let path = |key: &str| if prefix.is_empty() { key.to_string() } else { format!("{}.{}", prefix, key) };
+    for (key, old_value) in old_map {
+        match new_map.get(key) {
+            Some(new_value) => walk(&path(key), old_value, new_value, changes),
+            None => changes.push(FieldChange {
+                field: path(key),
+                kind: ChangeKind::Removed,
+                old: Some(old_value.clone()),
+                new: None,
+            }),
+        }
+    }
+    for (key, new_value) in new_map {
+        if !old_map.contains_key(key) {
+            changes.push(FieldChange {
+                field: path(key),
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some(new_value.clone()),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_finds_added_changed_and_skips_unchanged() {
+        let old = json!({
+            "buyAmountUsd": 10.0,
+            "blacklistedCreators": ["a"],
+            "signalWeights": { "momentum": 1.0, "holders": 0.5 },
+            "legacyFlag": true,
+            "maxCoinAgeSecs": 300
+        });
+        let new = json!({
+            "buyAmountUsd": 25.0,
+            "blacklistedCreators": ["a", "b"],
+            "signalWeights": { "momentum": 1.0, "holders": 0.8 },
+            "maxCoinAgeSecs": 300,
+            "minCoinAgeSecs": 65
+        });
+
+        let changes = diff_values(&old, &new);
+        let fields: Vec<(&str, ChangeKind)> = changes.iter().map(|c| (c.field.as_str(), c.kind)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("blacklistedCreators", ChangeKind::Changed),
+                ("buyAmountUsd", ChangeKind::Changed),
+                ("legacyFlag", ChangeKind::Removed),
+                ("minCoinAgeSecs", ChangeKind::Added),
+                ("signalWeights.holders", ChangeKind::Changed),
+            ]
+        );
+        assert_eq!(changes[1].summary(), "buyAmountUsd: 10.0 -> 25.0");
+        assert_eq!(changes[3].summary(), "minCoinAgeSecs: - -> 65");
+
+        // Saving the same config records nothing
+        assert!(diff_values(&new, &new).is_empty());
+        #[derive(Serialize)]
+        struct Cfg {
+            amount: f64,
+        }
+        assert!(diff_configs(&Cfg { amount: 1.0 }, &Cfg { amount: 1.0 }).is_empty());
+        assert_eq!(diff_configs(&Cfg { amount: 1.0 }, &Cfg { amount: 2.0 }).len(), 1);
+    }
+}
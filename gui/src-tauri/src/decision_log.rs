@@ -0,0 +1,585 @@
+//! Decision Log — replayable record of automation decisions
+//!
+//! The dip buyer and sentinel monitor record each decision they act on: the
+//! inputs the strategy saw, the config it ran under (stored once per content
+//! hash) and the action that came out. `replay_events` feeds the recorded
+//! inputs back through the same pure functions. Replaying unchanged configs
+//! must reproduce every action; replaying with overridden configs answers
+//! "what would these settings have done".
+//!
+//! Only the strategy step is recorded. Stateful gates the loops apply before
+//! or after it (dedup, cooldowns, daily caps, executor risk checks) are not.
+
+use crate::dipbuyer_signals::{
+    analyze_dip, apply_repeat_dump_decay, apply_thin_history_penalty, has_momentum_history, AtrSizing,
+    ConfidenceCurve, DipAnalysis, SignalResult, SignalWeights, DEFAULT_WITHOUT_HOLDERS_BUY_SCALE,
+};
+use crate::sentinel_eval::{evaluate_liquidity, evaluate_sentinel};
+use crate::skip_reason::dip_decision_skip;
+use rugplay_core::{CandlestickPoint, CoinDetails, CoinHoldersResponse, RecentTrade};
+use rugplay_persistence::sqlite::{self, DecisionEventRow, SentinelRow};
+use rugplay_persistence::Database;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// What a module decided to do
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum DecisionAction {
+    Buy { symbol: String, amount_usd: f64 },
+    Sell { symbol: String, trigger: String },
+    Skip { symbol: String, reason: String },
+    Hold { symbol: String },
+}
+
+/// A pure strategy step whose inputs and output can be recorded and replayed
+pub trait DecisionModel {
+    /// Module name events are stored under
+    const MODULE: &'static str;
+    type Config: Serialize + DeserializeOwned;
+    type Input: Serialize + DeserializeOwned;
+
+    fn decide(config: &Self::Config, input: &Self::Input) -> DecisionAction;
+}
+
+// ─── Dip Buyer ───────────────────────────────────────────────────────
+
+/// Dip buyer settings that feed the scoring step (tier already resolved)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DipDecisionConfig {
+    pub signal_weights: SignalWeights,
+    pub skip_top_n_holders: u32,
+    pub max_buy_slippage_pct: f64,
+    pub confidence_decay_per_dump: f64,
+    pub min_confidence_score: f64,
+    pub scale_by_confidence: bool,
+    /// Stricter sizing curve used instead of the linear scale when enabled
+    #[serde(default)]
+    pub confidence_curve: ConfidenceCurve,
+    /// Buy size multiplier when holder data was unavailable
+    #[serde(default = "default_without_holders_buy_scale")]
+    pub without_holders_buy_scale: f64,
+    /// Fraction of confidence taken off when momentum had too few candles
+    /// to read (0 = disabled, and always 0 with momentum analysis off)
+    #[serde(default)]
+    pub thin_history_confidence_penalty: f64,
+    /// Scale the buy inversely to the coin's candle volatility (None = off)
+    #[serde(default)]
+    pub atr_sizing: Option<AtrSizing>,
+}
+
+fn default_without_holders_buy_scale() -> f64 { DEFAULT_WITHOUT_HOLDERS_BUY_SCALE }
+
+/// Everything the dip buyer looked at for one candidate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DipDecisionInput {
+    pub sell_trade: RecentTrade,
+    pub coin: CoinDetails,
+    pub candles: Vec<CandlestickPoint>,
+    /// `None` when the holders endpoint failed and the dip was scored without it
+    #[serde(default)]
+    pub holders: Option<CoinHoldersResponse>,
+    pub base_buy_amount: f64,
+    /// Earlier dumps of the same coin inside the decay window
+    pub prior_dumps: u32,
+}
+
+/// Score a dip candidate and decide whether to buy it
+pub fn evaluate_dip(config: &DipDecisionConfig, input: &DipDecisionInput) -> (DipAnalysis, DecisionAction) {
+    let symbol = input.sell_trade.coin_symbol.clone();
+    let mut analysis = analyze_dip(
+        &symbol,
+        &input.sell_trade,
+        &input.coin,
+        &input.candles,
+        input.holders.as_ref(),
+        input.base_buy_amount,
+        &config.signal_weights,
+        config.skip_top_n_holders,
+        config.max_buy_slippage_pct,
+    );
+
+    // Falling-knife guard: each prior dump of this coin lowers confidence
+    apply_repeat_dump_decay(&mut analysis, input.prior_dumps, config.confidence_decay_per_dump);
+    // New coins have little history, so their neutral momentum says less
+    if !has_momentum_history(&input.candles) {
+        apply_thin_history_penalty(&mut analysis, input.candles.len(), config.thin_history_confidence_penalty);
+    }
+
+    let action = if let Some(skip) = dip_decision_skip(&analysis, config.min_confidence_score) {
+        DecisionAction::Skip { symbol, reason: skip.message() }
+    } else {
+        let mut amount_usd = if config.scale_by_confidence {
+            let buy_pct = if config.confidence_curve.enabled {
+                config.confidence_curve.buy_pct(analysis.confidence_score, config.min_confidence_score)
+            } else {
+                analysis.recommended_buy_pct
+            };
+            (input.base_buy_amount * buy_pct).max(1.0)
+        } else {
+            input.base_buy_amount
+        };
+        // Less is known about a coin scored without holder data
+        if input.holders.is_none() {
+            amount_usd = (amount_usd * config.without_holders_buy_scale).max(1.0);
+        }
+        // Calm coins get more, choppy ones less; too few candles keeps the size
+        if let Some((scale, atr)) = config.atr_sizing.as_ref().and_then(|s| s.scale(&input.candles)) {
+            amount_usd = (amount_usd * scale).max(1.0);
+            analysis.signals.push(SignalResult {
+                name: "Volatility".into(),
+                raw_value: atr,
+                score: scale,
+                weight: 0.0, // applied to the size, not the confidence
+                weighted: 0.0,
+                reason: format!("ATR {:.1}% of price, size ×{:.2}", atr, scale),
+            });
+        }
+        DecisionAction::Buy { symbol, amount_usd }
+    };
+
+    (analysis, action)
+}
+
+pub struct DipBuyerDecision;
+
+impl DecisionModel for DipBuyerDecision {
+    const MODULE: &'static str = "dipbuyer";
+    type Config = DipDecisionConfig;
+    type Input = DipDecisionInput;
+
+    fn decide(config: &Self::Config, input: &Self::Input) -> DecisionAction {
+        evaluate_dip(config, input).1
+    }
+}
+
+// ─── Sentinel ────────────────────────────────────────────────────────
+
+/// Sentinel thresholds, in percent as stored on the sentinel
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentinelDecisionConfig {
+    pub stop_loss_pct: Option<f64>,
+    pub take_profit_pct: Option<f64>,
+    pub trailing_stop_pct: Option<f64>,
+    /// Liquidity-drain exit threshold (0 = off)
+    #[serde(default)]
+    pub min_liquidity_usd: f64,
+}
+
+impl SentinelDecisionConfig {
+    pub fn from_row(sentinel: &SentinelRow) -> Self {
+        Self {
+            stop_loss_pct: sentinel.stop_loss_pct,
+            take_profit_pct: sentinel.take_profit_pct,
+            trailing_stop_pct: sentinel.trailing_stop_pct,
+            min_liquidity_usd: 0.0,
+        }
+    }
+}
+
+/// The sentinel as evaluated and the price it was checked against
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentinelDecisionInput {
+    pub sentinel: SentinelRow,
+    pub price: f64,
+    /// Pool base-currency reserves, when they were fetched
+    #[serde(default)]
+    pub liquidity_usd: Option<f64>,
+}
+
+pub struct SentinelDecision;
+
+impl DecisionModel for SentinelDecision {
+    const MODULE: &'static str = "sentinel";
+    type Config = SentinelDecisionConfig;
+    type Input = SentinelDecisionInput;
+
+    fn decide(config: &Self::Config, input: &Self::Input) -> DecisionAction {
+        let mut sentinel = input.sentinel.clone();
+        sentinel.stop_loss_pct = config.stop_loss_pct;
+        sentinel.take_profit_pct = config.take_profit_pct;
+        sentinel.trailing_stop_pct = config.trailing_stop_pct;
+
+        let symbol = sentinel.symbol.clone();
+        let trigger = evaluate_sentinel(&sentinel, input.price)
+            .or_else(|| evaluate_liquidity(input.liquidity_usd, config.min_liquidity_usd, input.price));
+        match trigger {
+            Some(trigger) => DecisionAction::Sell { symbol, trigger: trigger.trigger_type.as_str().to_string() },
+            None => DecisionAction::Hold { symbol },
+        }
+    }
+}
+
+// ─── Recording ───────────────────────────────────────────────────────
+
+/// Stable content hash of a config's JSON (FNV-1a, 64-bit)
+pub fn config_hash(config_json: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in config_json.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Append one decision to the active profile's log
+pub async fn record_decision<M: DecisionModel>(
+    db: &Database,
+    config: &M::Config,
+    input: &M::Input,
+    action: &DecisionAction,
+) {
+    let profile_id = match sqlite::get_active_profile(db.pool()).await {
+        Ok(Some(p)) => p.id,
+        _ => return,
+    };
+    let encoded = serde_json::to_string(config).and_then(|config| {
+        Ok((config, serde_json::to_string(input)?, serde_json::to_string(action)?))
+    });
+    let (config_json, input_json, action_json) = match encoded {
+        Ok(encoded) => encoded,
+        Err(e) => {
+            warn!("{}: failed to encode decision: {}", M::MODULE, e);
+            return;
+        }
+    };
+
+    let hash = config_hash(&config_json);
+    let recorded_at = chrono::Utc::now().timestamp();
+    let result = match sqlite::save_decision_config(db.pool(), &hash, M::MODULE, &config_json).await {
+        Ok(()) => {
+            sqlite::append_decision_event(db.pool(), profile_id, M::MODULE, recorded_at, &hash, &input_json, &action_json)
+                .await
+                .map(|_| ())
+        }
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        warn!("{}: failed to record decision: {}", M::MODULE, e);
+    }
+}
+
+// ─── Replay ──────────────────────────────────────────────────────────
+
+/// Configs to use instead of the recorded ones ("what if")
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayOverrides {
+    #[serde(default)]
+    pub dipbuyer: Option<DipDecisionConfig>,
+    #[serde(default)]
+    pub sentinel: Option<SentinelDecisionConfig>,
+}
+
+/// A recorded decision that came out differently on replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayChange {
+    pub event_id: i64,
+    pub module: String,
+    pub recorded_at: i64,
+    pub recorded: DecisionAction,
+    pub replayed: DecisionAction,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayReport {
+    pub events: usize,
+    pub reproduced: usize,
+    pub changed: Vec<ReplayChange>,
+    /// Events from unknown modules or with undecodable JSON
+    pub unreadable: usize,
+}
+
+fn replay_row<M: DecisionModel>(
+    row: &DecisionEventRow,
+    override_config: Option<&M::Config>,
+) -> Option<(DecisionAction, DecisionAction)> {
+    let recorded: DecisionAction = serde_json::from_str(&row.action).ok()?;
+    let input: M::Input = serde_json::from_str(&row.input).ok()?;
+    let replayed = match override_config {
+        Some(config) => M::decide(config, &input),
+        None => M::decide(&serde_json::from_str(&row.config).ok()?, &input),
+    };
+    Some((recorded, replayed))
+}
+
+/// Re-run recorded decisions through the strategy functions
+pub fn replay_rows(rows: &[DecisionEventRow], overrides: &ReplayOverrides) -> ReplayReport {
+    let mut report = ReplayReport { events: rows.len(), ..Default::default() };
+
+    for row in rows {
+        let outcome = match row.module.as_str() {
+            DipBuyerDecision::MODULE => replay_row::<DipBuyerDecision>(row, overrides.dipbuyer.as_ref()),
+            SentinelDecision::MODULE => replay_row::<SentinelDecision>(row, overrides.sentinel.as_ref()),
+            _ => None,
+        };
+        match outcome {
+            Some((recorded, replayed)) if recorded == replayed => report.reproduced += 1,
+            Some((recorded, replayed)) => report.changed.push(ReplayChange {
+                event_id: row.id,
+                module: row.module.clone(),
+                recorded_at: row.recorded_at,
+                recorded,
+                replayed,
+            }),
+            None => report.unreadable += 1,
+        }
+    }
+
+    report
+}
+
+/// Replay the active profile's decisions recorded between `from` and `to` (epoch seconds)
+pub async fn replay_events(db: &Database, from: i64, to: i64) -> Result<ReplayReport, String> {
+    replay_events_with(db, from, to, &ReplayOverrides::default()).await
+}
+
+/// Replay a time range with some module configs replaced
+pub async fn replay_events_with(
+    db: &Database,
+    from: i64,
+    to: i64,
+    overrides: &ReplayOverrides,
+) -> Result<ReplayReport, String> {
+    let profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+    let rows = sqlite::get_decision_events(db.pool(), profile.id, from, to)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(replay_rows(&rows, overrides))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dipbuyer_signals::NEUTRAL_MOMENTUM_SCORE;
+
+    async fn setup() -> Database {
+        let db = Database::connect_in_memory().await.unwrap();
+        sqlx::query(
+            "INSERT INTO profiles (id, username, token_encrypted, iv, is_active) VALUES (1, 'tester', x'00', x'00', 1)",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+        db
+    }
+
+    fn dip_config() -> DipDecisionConfig {
+        DipDecisionConfig {
+            signal_weights: SignalWeights::default(),
+            skip_top_n_holders: 2,
+            max_buy_slippage_pct: 10.0,
+            confidence_decay_per_dump: 0.2,
+            min_confidence_score: 0.3,
+            scale_by_confidence: false,
+            confidence_curve: ConfidenceCurve::default(),
+            without_holders_buy_scale: 0.5,
+            thin_history_confidence_penalty: 0.0,
+            atr_sizing: None,
+        }
+    }
+
+    /// A sell of `sell_usd` by user `seller` on a coin with `top_pct` held by rank 1
+    fn dip_input(symbol: &str, sell_usd: f64, seller: u32, top_pct: f64, prior_dumps: u32) -> DipDecisionInput {
+        let trade = serde_json::json!({
+            "type": "SELL", "username": "seller", "amount": 1000.0, "coinSymbol": symbol,
+            "coinName": symbol, "totalValue": sell_usd, "price": 0.5,
+            "timestamp": 1_700_000_000, "userId": seller.to_string()
+        });
+        let coin = serde_json::json!({
+            "id": 7, "symbol": symbol, "name": symbol, "currentPrice": 0.5, "marketCap": 50_000.0,
+            "poolCoinAmount": 20_000.0, "poolBaseCurrencyAmount": 10_000.0,
+            "volume24h": 8_000.0, "change24h": -5.0
+        });
+        let holders = serde_json::json!({
+            "coinSymbol": symbol, "totalHolders": 80, "circulatingSupply": 1_000_000.0,
+            "poolInfo": {"coinAmount": 20_000.0, "baseCurrencyAmount": 10_000.0, "currentPrice": 0.5},
+            "holders": [
+                {"rank": 1, "userId": 1, "username": "a", "name": "a", "quantity": 1.0, "percentage": top_pct, "liquidationValue": 1.0},
+                {"rank": 2, "userId": 2, "username": "b", "name": "b", "quantity": 1.0, "percentage": 5.0, "liquidationValue": 1.0},
+                {"rank": 3, "userId": 3, "username": "c", "name": "c", "quantity": 1.0, "percentage": 4.0, "liquidationValue": 1.0}
+            ]
+        });
+        DipDecisionInput {
+            sell_trade: serde_json::from_value(trade).unwrap(),
+            coin: serde_json::from_value(coin).unwrap(),
+            candles: (0..6)
+                .map(|i| CandlestickPoint { time: i, open: 0.6, high: 0.62, low: 0.5, close: 0.55 - i as f64 * 0.01 })
+                .collect(),
+            holders: Some(serde_json::from_value(holders).unwrap()),
+            base_buy_amount: 50.0,
+            prior_dumps,
+        }
+    }
+
+    fn sentinel_input(symbol: &str, price: f64) -> SentinelDecisionInput {
+        SentinelDecisionInput {
+            sentinel: SentinelRow {
+                id: 1,
+                profile_id: 1,
+                symbol: symbol.to_string(),
+                stop_loss_pct: Some(-20.0),
+                take_profit_pct: Some(100.0),
+                trailing_stop_pct: None,
+                sell_percentage: 100.0,
+                entry_price: 1.0,
+                highest_price_seen: 1.0,
+                is_active: true,
+                created_at: None,
+                triggered_at: None,
+                price_source: None,
+                sl_sell_pct: None,
+                tp_sell_pct: None,
+                ts_sell_pct: None,
+                tp_ladder: None,
+                tp_rungs_filled: 0,
+                unsold_trigger: None,
+                unsold_qty: None,
+            },
+            price,
+            liquidity_usd: None,
+        }
+    }
+
+    /// Record a session the way the loops do: decide, then log the decision
+    async fn record_session(db: &Database) -> Vec<DecisionAction> {
+        let config = dip_config();
+        let mut actions = Vec::new();
+        for input in [
+            dip_input("AAA", 2_000.0, 50, 10.0, 0),
+            // Seller is the top holder: whale dump
+            dip_input("BBB", 2_000.0, 1, 10.0, 0),
+            // Ninth dump in a row: confidence decayed below the threshold
+            dip_input("CCC", 2_000.0, 50, 10.0, 8),
+        ] {
+            let action = DipBuyerDecision::decide(&config, &input);
+            record_decision::<DipBuyerDecision>(db, &config, &input, &action).await;
+            actions.push(action);
+        }
+        for price in [0.7, 2.5] {
+            let input = sentinel_input("DDD", price);
+            let config = SentinelDecisionConfig::from_row(&input.sentinel);
+            let action = SentinelDecision::decide(&config, &input);
+            record_decision::<SentinelDecision>(db, &config, &input, &action).await;
+            actions.push(action);
+        }
+        actions
+    }
+
+    #[tokio::test]
+    async fn test_replaying_recorded_session_reproduces_actions() {
+        let db = setup().await;
+        let actions = record_session(&db).await;
+
+        assert!(matches!(actions[0], DecisionAction::Buy { amount_usd, .. } if amount_usd == 50.0), "{:?}", actions[0]);
+        assert!(matches!(&actions[1], DecisionAction::Skip { reason, .. } if reason.contains("whale dump")));
+        assert!(matches!(&actions[2], DecisionAction::Skip { reason, .. } if reason.starts_with("Low confidence")));
+        assert_eq!(actions[3], DecisionAction::Sell { symbol: "DDD".into(), trigger: "stop_loss".into() });
+        assert_eq!(actions[4], DecisionAction::Sell { symbol: "DDD".into(), trigger: "take_profit".into() });
+
+        let report = replay_events(&db, 0, i64::MAX).await.unwrap();
+        assert_eq!(report.events, 5);
+        assert_eq!(report.reproduced, 5);
+        assert!(report.changed.is_empty(), "{:?}", report.changed);
+        assert_eq!(report.unreadable, 0);
+
+        // One config row per distinct config
+        let configs: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM decision_configs")
+            .fetch_one(db.pool())
+            .await
+            .unwrap();
+        assert_eq!(configs, 2);
+
+        // Outside the range nothing is replayed
+        assert_eq!(replay_events(&db, 0, 1).await.unwrap().events, 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_other_settings_reports_changed_actions() {
+        let db = setup().await;
+        record_session(&db).await;
+
+        let overrides = ReplayOverrides {
+            // Stricter buyer, and no stop loss on sentinels
+            dipbuyer: Some(DipDecisionConfig { min_confidence_score: 0.99, ..dip_config() }),
+            sentinel: Some(SentinelDecisionConfig { take_profit_pct: Some(100.0), ..Default::default() }),
+        };
+        let report = replay_events_with(&db, 0, i64::MAX, &overrides).await.unwrap();
+
+        // The whale-dump skip and the take-profit sell are unaffected
+        assert_eq!(report.reproduced, 2);
+        assert_eq!(report.changed.len(), 3);
+        assert!(matches!(report.changed[0].recorded, DecisionAction::Buy { .. }));
+        assert!(matches!(&report.changed[0].replayed, DecisionAction::Skip { symbol, .. } if symbol == "AAA"));
+        // Still skipped, but against the new threshold
+        assert!(matches!(&report.changed[1].replayed, DecisionAction::Skip { reason, .. } if reason.contains("< 0.99")));
+        assert_eq!(report.changed[2].module, "sentinel");
+        assert_eq!(report.changed[2].replayed, DecisionAction::Hold { symbol: "DDD".into() });
+    }
+
+    #[test]
+    fn test_dip_without_holders_buys_smaller_instead_of_dropping() {
+        let config = dip_config();
+        // The seller is the top holder, but without holder data that can't be seen
+        let mut input = dip_input("EEE", 2_000.0, 1, 10.0, 0);
+        assert!(matches!(DipBuyerDecision::decide(&config, &input), DecisionAction::Skip { .. }));
+
+        input.holders = None;
+        let (analysis, action) = evaluate_dip(&config, &input);
+        assert!(!analysis.hard_reject);
+        let holder_signal = analysis.signals.iter().find(|s| s.name == "Holder Safety").unwrap();
+        assert_eq!(holder_signal.score, 0.5);
+        assert_eq!(action, DecisionAction::Buy { symbol: "EEE".into(), amount_usd: 25.0 });
+
+        // Recorded degraded inputs replay the same way
+        let json = serde_json::to_string(&input).unwrap();
+        let decoded: DipDecisionInput = serde_json::from_str(&json).unwrap();
+        assert!(decoded.holders.is_none());
+        assert_eq!(DipBuyerDecision::decide(&config, &decoded), action);
+    }
+
+    #[test]
+    fn test_empty_candles_score_neutral_momentum() {
+        let mut config = dip_config();
+        let mut input = dip_input("NEW", 500.0, 50, 10.0, 0);
+        let (with_history, _) = evaluate_dip(&config, &input);
+
+        // Brand new coin: no candles at all, then too few to read
+        for count in [0, 2] {
+            input.candles.truncate(count);
+            let (analysis, action) = evaluate_dip(&config, &input);
+            let momentum = analysis.signals.iter().find(|s| s.name == "Momentum").unwrap();
+            assert_eq!(momentum.score, NEUTRAL_MOMENTUM_SCORE);
+            assert!(momentum.reason.contains("neutral"));
+            assert!(analysis.confidence_score.is_finite());
+            assert_eq!(evaluate_dip(&config, &input).1, action);
+            assert!(analysis.signals.iter().all(|s| s.name != "Thin History"));
+        }
+        let (neutral, _) = evaluate_dip(&config, &input);
+
+        // The penalty only touches the thin-history coin
+        config.thin_history_confidence_penalty = 0.2;
+        let (penalized, _) = evaluate_dip(&config, &input);
+        assert!((penalized.confidence_score - neutral.confidence_score * 0.8).abs() < 1e-9);
+        assert!(penalized.signals.iter().any(|s| s.name == "Thin History"));
+        let full = dip_input("NEW", 500.0, 50, 10.0, 0);
+        assert_eq!(evaluate_dip(&config, &full).0.confidence_score, with_history.confidence_score);
+    }
+
+    #[test]
+    fn test_config_hash_is_stable() {
+        assert_eq!(config_hash(""), "cbf29ce484222325");
+        assert_eq!(config_hash("{}"), config_hash("{}"));
+        assert_ne!(config_hash(r#"{"a":1}"#), config_hash(r#"{"a":2}"#));
+    }
+}
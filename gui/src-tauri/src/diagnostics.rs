@@ -0,0 +1,306 @@
+//! Diagnostics — a redacted snapshot of the app for bug reports
+//!
+//! `generate_diagnostic_report` gathers everything needed to reproduce a
+//! problem into one JSON document the user can attach: module and executor
+//! state, every persisted setting, the database schema, cache stats and the
+//! most recent log lines. Nothing secret may leave the machine, so the whole
+//! document goes through [`redact_json`] last: any field whose name marks it
+//! as a credential (token, PIN, password, ...) is blanked, and long
+//! token-like strings or `pin=`/`token=` parameters inside any text are
+//! masked. Profile tokens are never read in the first place.
+
+use rugplay_core::Result;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::SqlitePool;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+/// Log lines kept for the report
+pub const LOG_CAPACITY: usize = 200;
+
+/// Replacement for anything secret
+const REDACTED: &str = "[redacted]";
+
+/// Field name words that mark a value as secret
+const SECRET_WORDS: &[&str] = &[
+    "token", "tokens", "pin", "password", "passphrase", "secret", "cookie", "authorization", "qr", "iv",
+    "encrypted",
+];
+
+/// Query parameters whose values are masked inside text
+const SECRET_PARAMS: &[&str] = &["pin=", "token="];
+
+/// Runs of token characters at least this long (with letters and digits)
+/// are treated as credentials
+const MIN_TOKEN_LEN: usize = 32;
+
+/// The most recent log lines, redacted as they are recorded
+#[derive(Debug)]
+pub struct RecentLogs {
+    lines: Mutex<VecDeque<String>>,
+    capacity: usize,
+}
+
+impl Default for RecentLogs {
+    fn default() -> Self {
+        Self::new(LOG_CAPACITY)
+    }
+}
+
+impl RecentLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self { lines: Mutex::new(VecDeque::with_capacity(capacity)), capacity }
+    }
+
+    pub fn push(&self, line: &str) {
+        let mut lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(redact_text(line));
+    }
+
+    /// Oldest first
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap_or_else(|e| e.into_inner()).iter().cloned().collect()
+    }
+}
+
+/// Whether a field name marks its value as secret. Names are split into
+/// words on `_`, `-` and camelCase humps, so `tokenPrefix` and `mobile_pin`
+/// match but `pinned` doesn't.
+pub fn is_secret_key(key: &str) -> bool {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in key.chars() {
+        let hump = c.is_uppercase() && prev_lower;
+        if (!c.is_alphanumeric() || hump) && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        if c.is_alphanumeric() {
+            word.push(c.to_ascii_lowercase());
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    words.push(word);
+    words.iter().any(|w| SECRET_WORDS.contains(&w.as_str()))
+}
+
+fn is_token_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '=' | '+')
+}
+
+/// Mask credentials inside free text: `pin=`/`token=` parameter values and
+/// long runs of token characters mixing letters and digits
+pub fn redact_text(text: &str) -> String {
+    // ASCII lowercasing keeps byte offsets, so one copy serves every lookup
+    let lower = text.to_ascii_lowercase();
+    let mut out = String::with_capacity(text.len());
+    let mut at = 0;
+    while at < text.len() {
+        if let Some(param) = SECRET_PARAMS.iter().find(|p| lower[at..].starts_with(**p)) {
+            out.push_str(&text[at..at + param.len()]);
+            out.push_str(REDACTED);
+            at += param.len();
+            at += text[at..].find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(text.len() - at);
+            continue;
+        }
+        let run = text[at..].find(|c: char| !is_token_char(c)).unwrap_or(text.len() - at);
+        if run == 0 {
+            let c = text[at..].chars().next().unwrap_or_default();
+            out.push(c);
+            at += c.len_utf8();
+            continue;
+        }
+        // Stop a run at a secret parameter so it is still caught
+        let run = SECRET_PARAMS
+            .iter()
+            .filter_map(|p| lower[at..at + run].find(*p).filter(|&i| i > 0))
+            .min()
+            .unwrap_or(run);
+        let word = &text[at..at + run];
+        let looks_like_token = word.len() >= MIN_TOKEN_LEN
+            && word.chars().any(|c| c.is_ascii_digit())
+            && word.chars().any(|c| c.is_ascii_alphabetic());
+        out.push_str(if looks_like_token { REDACTED } else { word });
+        at += run;
+    }
+    out
+}
+
+/// Blank secret fields and mask secrets in every string, recursively
+pub fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, field) in map.iter_mut() {
+                if is_secret_key(key) {
+                    if !field.is_null() {
+                        *field = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        Value::String(text) => *text = redact_text(text),
+        _ => {}
+    }
+}
+
+/// Database layout: SQLite's `user_version` and the row count of each table
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaInfo {
+    pub user_version: i64,
+    pub tables: BTreeMap<String, i64>,
+}
+
+pub async fn collect_schema(pool: &SqlitePool) -> Result<SchemaInfo> {
+    let db_err = |e: sqlx::Error| rugplay_core::Error::DatabaseError(e.to_string());
+    let user_version: i64 = sqlx::query_scalar("PRAGMA user_version").fetch_one(pool).await.map_err(db_err)?;
+    let names: Vec<String> = sqlx::query_scalar(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await
+    .map_err(db_err)?;
+
+    let mut tables = BTreeMap::new();
+    for name in names {
+        let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")))
+            .fetch_one(pool)
+            .await
+            .map_err(db_err)?;
+        tables.insert(name, count);
+    }
+    Ok(SchemaInfo { user_version, tables })
+}
+
+/// Every persisted setting, JSON values parsed so redaction sees their fields
+pub async fn collect_settings(pool: &SqlitePool) -> Result<BTreeMap<String, Value>> {
+    let rows: Vec<(String, String)> = sqlx::query_as("SELECT key, value FROM settings ORDER BY key")
+        .fetch_all(pool)
+        .await
+        .map_err(|e| rugplay_core::Error::DatabaseError(e.to_string()))?;
+
+    Ok(rows
+        .into_iter()
+        .map(|(key, value)| {
+            let parsed = serde_json::from_str(&value).unwrap_or(Value::String(value));
+            (key, parsed)
+        })
+        .collect())
+}
+
+/// Cache sizes
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub coin_entries: usize,
+}
+
+/// Everything the report contains, before redaction
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticReport {
+    pub generated_at: String,
+    pub app_version: String,
+    pub os: String,
+    /// Consolidated status: modules, executor, warm-up, profile
+    pub status: Value,
+    /// Live state read from each module's handle, by module
+    pub modules: BTreeMap<String, Value>,
+    pub schema: SchemaInfo,
+    pub settings: BTreeMap<String, Value>,
+    pub cache: CacheStats,
+    pub recent_logs: Vec<String>,
+}
+
+impl DiagnosticReport {
+    /// The report as it may be shared: serialized and redacted
+    pub fn into_redacted_json(self) -> Value {
+        let mut json = serde_json::to_value(self).unwrap_or(Value::Null);
+        redact_json(&mut json);
+        json
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_persistence::Database;
+    use serde_json::json;
+
+    const SESSION_TOKEN: &str = "eyJhbGciOiJIUzI1NiJ9x4f8a9b2c7d1e6f0a3b5c8d9e2f1a4b7c";
+
+    #[tokio::test]
+    async fn test_report_has_every_section_and_no_secrets() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        sqlx::query("INSERT INTO profiles (id, username, token_encrypted, iv) VALUES (1, 'tester', ?, x'00')")
+            .bind(SESSION_TOKEN.as_bytes())
+            .execute(pool)
+            .await
+            .unwrap();
+        for (key, value) in [
+            ("risk_limits", json!({ "maxTradeUsd": 500.0 }).to_string()),
+            ("mobile_session", json!({ "pin": "482913", "sessionToken": SESSION_TOKEN }).to_string()),
+            ("paper_trading", "true".to_string()),
+        ] {
+            sqlx::query("INSERT INTO settings (key, value) VALUES (?, ?)").bind(key).bind(value).execute(pool).await.unwrap();
+        }
+
+        let logs = RecentLogs::new(2);
+        logs.push("dropped");
+        logs.push(&format!("INFO auth: verified session {}", SESSION_TOKEN));
+        logs.push("WARN mobile: QR at http://10.0.0.2:3000?pin=482913&x=1");
+        assert_eq!(logs.lines().len(), 2);
+
+        let mut modules = BTreeMap::new();
+        modules.insert(
+            "mobile".to_string(),
+            json!({ "running": true, "pin": "482913", "qrSvg": "<svg/>", "sessions": [{ "tokenPrefix": "eyJh", "role": "viewer" }] }),
+        );
+        let report = DiagnosticReport {
+            generated_at: "2026-10-16T00:00:00Z".to_string(),
+            status: json!({ "activeProfile": { "id": 1, "username": "tester" } }),
+            modules,
+            schema: collect_schema(pool).await.unwrap(),
+            settings: collect_settings(pool).await.unwrap(),
+            cache: CacheStats { coin_entries: 3 },
+            recent_logs: logs.lines(),
+            ..Default::default()
+        };
+        let json = report.into_redacted_json();
+
+        for section in ["generatedAt", "status", "modules", "schema", "settings", "cache", "recentLogs"] {
+            assert!(json.get(section).is_some(), "missing {}", section);
+        }
+        assert_eq!(json["schema"]["tables"]["profiles"], 1);
+        assert_eq!(json["settings"]["risk_limits"]["maxTradeUsd"], 500.0);
+        assert_eq!(json["settings"]["paper_trading"], true);
+        assert_eq!(json["modules"]["mobile"]["running"], true);
+        assert_eq!(json["status"]["activeProfile"]["username"], "tester");
+
+        let text = json.to_string();
+        for secret in [SESSION_TOKEN, "482913", "<svg/>", "eyJh"] {
+            assert!(!text.contains(secret), "{} leaked: {}", secret, text);
+        }
+    }
+
+    #[test]
+    fn test_secret_detection_leaves_ordinary_fields_alone() {
+        for key in ["pin", "PIN", "tokenPrefix", "mobile_pin", "sessionToken", "qrSvg", "token_encrypted", "apiSecret"] {
+            assert!(is_secret_key(key), "{}", key);
+        }
+        for key in ["pinned", "maxTradeUsd", "sentinel_price_source", "tokenomics", "spinner"] {
+            assert!(!is_secret_key(key), "{}", key);
+        }
+        let path = "/home/trader/.local/share/RugplayBot/rugplay.db";
+        assert_eq!(redact_text(path), path);
+        assert_eq!(redact_text("sold 12.5 MOON at 0.00012345"), "sold 12.5 MOON at 0.00012345");
+    }
+}
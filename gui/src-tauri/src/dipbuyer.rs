@@ -5,23 +5,48 @@
 //! dumps while top holders remain, and the coin meets liquidity/volume
 //! filters, the bot buys the dip via the trade executor.
 
-use crate::dipbuyer_signals::{DipAnalysis, SignalWeights, analyze_dip};
-use crate::notifications::NotificationHandle;
-use crate::trade_executor::{TradeExecutorHandle, TradePriority};
-use crate::AppState;
-use rugplay_core::TradeType;
-use rugplay_networking::RugplayClient;
+use crate::acquisition::BuySideModule;
+use crate::app_event::{emit_event, AppEvent};
+use crate::budget_window::BudgetResetMode;
+use crate::decision_log::{evaluate_dip, DecisionAction, DipBuyerDecision, DipDecisionConfig, DipDecisionInput};
+use crate::dipbuyer_signals::{
+    AtrSizing, ConfidenceCurve, DipAnalysis, SignalWeights, in_coin_cooldown, is_cooldown_exempt, resolve_max_slippage,
+    within_daily_limits, DEFAULT_WITHOUT_HOLDERS_BUY_SCALE,
+};
+use crate::feed_depth::FeedDepth;
+use crate::loop_snapshot::{DipBuyerSnapshot, LoopTask, SHUTDOWN_TIMEOUT};
+use crate::notifications::{NotificationCategory, NotificationHandle};
+use crate::seen_trades::SeenTrades;
+use crate::skip_reason::{dip_decision_skip, dip_gate_skip, dip_position_skip, DipGates, SkipReason};
+use crate::tick_budget::{rank_candidates, TickBudget};
+use crate::price_freshness::PriceDecision;
+use crate::trade_amount::TradeAmount;
+use crate::trade_approval::HeldOrders;
+use crate::trade_executor::{AutomatedTrade, Submission, TradeExecutorHandle, TradePriority};
+use crate::trade_reason::TradeReason;
+use crate::wash_trades::{wash_trade_indices, WashSensitivity};
+use crate::poll_interval::{AdaptiveInterval, PollOutcome};
+use crate::protective_sentinel::ProtectiveSentinelConfig;
+use crate::{ensure_protective_sentinel, paper_tagged_details, record_decision, restore_loop_snapshot, retune_poll_interval, save_loop_snapshot, AppState};
+use rugplay_core::{parse_db_timestamp, RecentTrade, TradeResponse, TradeType};
+use rugplay_engine::risk::assess_coin_risk;
 use rugplay_persistence::sqlite;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::sync::Arc;
-use tauri::{Emitter, Manager};
+use tauri::Manager;
 use tokio::sync::{watch, RwLock};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
 
+/// Seen trade keys kept for dedup before eviction kicks in
+const SEEN_TRADES_CAPACITY: usize = 1000;
+
+/// Seen trade keys older than this are evicted first
+const SEEN_TRADES_WINDOW_SECS: i64 = 3600;
+
 // ─── Aggressiveness Presets ──────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -60,9 +85,15 @@ impl Aggressiveness {
                 take_profit_pct: 200.0,
                 trailing_stop_pct: None,
                 blacklisted_coins: Vec::new(),
+                cooldown_exempt_coins: Vec::new(),
+                recent_trades_depth: 0,
+                wash_trade_sensitivity: WashSensitivity::Off,
+                max_evaluations_per_tick: 10,
                 min_confidence_score: 0.65,
                 max_buy_slippage_pct: 3.0,
+                coin_slippage_overrides: HashMap::new(),
                 use_momentum_analysis: true,
+                thin_history_confidence_penalty: 0.0,
                 signal_weights: SignalWeights {
                     sell_impact: 0.30,
                     holder_safety: 0.40,
@@ -70,8 +101,18 @@ impl Aggressiveness {
                     volume_quality: 0.10,
                 },
                 scale_by_confidence: true,
+                confidence_curve: ConfidenceCurve::default(),
                 max_position_pct: 5.0,
                 portfolio_aware: true,
+                proceed_without_portfolio: false,
+                confidence_decay_per_dump: 0.20,
+                dump_decay_window_secs: 3600,
+                proceed_without_holders: false,
+                without_holders_buy_scale: 0.5,
+                budget_reset_mode: BudgetResetMode::Rolling24h,
+                max_risk_score: 60.0,
+                atr_position_sizing: false,
+                atr_sizing: AtrSizing::default(),
             },
             Aggressiveness::Moderate => DipBuyerConfig {
                 preset: Aggressiveness::Moderate,
@@ -98,13 +139,29 @@ impl Aggressiveness {
                 take_profit_pct: 500.0,
                 trailing_stop_pct: None,
                 blacklisted_coins: Vec::new(),
+                cooldown_exempt_coins: Vec::new(),
+                recent_trades_depth: 0,
+                wash_trade_sensitivity: WashSensitivity::Off,
+                max_evaluations_per_tick: 15,
                 min_confidence_score: 0.55,
                 max_buy_slippage_pct: 5.0,
+                coin_slippage_overrides: HashMap::new(),
                 use_momentum_analysis: true,
+                thin_history_confidence_penalty: 0.0,
                 signal_weights: SignalWeights::default(),
                 scale_by_confidence: true,
+                confidence_curve: ConfidenceCurve::default(),
                 max_position_pct: 10.0,
                 portfolio_aware: true,
+                proceed_without_portfolio: false,
+                confidence_decay_per_dump: 0.15,
+                dump_decay_window_secs: 3600,
+                proceed_without_holders: false,
+                without_holders_buy_scale: 0.5,
+                budget_reset_mode: BudgetResetMode::Rolling24h,
+                max_risk_score: 70.0,
+                atr_position_sizing: false,
+                atr_sizing: AtrSizing::default(),
             },
             Aggressiveness::Aggressive => DipBuyerConfig {
                 preset: Aggressiveness::Aggressive,
@@ -131,9 +188,15 @@ impl Aggressiveness {
                 take_profit_pct: 1000.0,
                 trailing_stop_pct: None,
                 blacklisted_coins: Vec::new(),
+                cooldown_exempt_coins: Vec::new(),
+                recent_trades_depth: 0,
+                wash_trade_sensitivity: WashSensitivity::Off,
+                max_evaluations_per_tick: 25,
                 min_confidence_score: 0.45,
                 max_buy_slippage_pct: 10.0,
+                coin_slippage_overrides: HashMap::new(),
                 use_momentum_analysis: true,
+                thin_history_confidence_penalty: 0.0,
                 signal_weights: SignalWeights {
                     sell_impact: 0.40,
                     holder_safety: 0.25,
@@ -141,8 +204,18 @@ impl Aggressiveness {
                     volume_quality: 0.15,
                 },
                 scale_by_confidence: false,
+                confidence_curve: ConfidenceCurve::default(),
                 max_position_pct: 0.0,
                 portfolio_aware: false,
+                proceed_without_portfolio: false,
+                confidence_decay_per_dump: 0.10,
+                dump_decay_window_secs: 3600,
+                proceed_without_holders: false,
+                without_holders_buy_scale: 0.75,
+                budget_reset_mode: BudgetResetMode::Rolling24h,
+                max_risk_score: 80.0,
+                atr_position_sizing: false,
+                atr_sizing: AtrSizing::default(),
             },
         }
     }
@@ -215,11 +288,22 @@ pub struct DipBuyerConfig {
     pub max_price_drop_pct: f64,
     /// Polling interval in seconds
     pub poll_interval_secs: u64,
+    /// Recent trades fetched per poll (0 = default 50). Grows automatically
+    /// while the feed turns over faster than the poll interval.
+    #[serde(default)]
+    pub recent_trades_depth: u32,
+    /// How eagerly sells that are half of a self-trade round trip are ignored
+    #[serde(default)]
+    pub wash_trade_sensitivity: WashSensitivity,
+    /// Most dip candidates run through the API-heavy checks per tick, biggest
+    /// sells first; the rest are deferred to the next tick (0 = no limit)
+    #[serde(default)]
+    pub max_evaluations_per_tick: u32,
     /// Cooldown per coin in seconds (don't buy same coin twice in this window)
     pub cooldown_per_coin_secs: u64,
-    /// Maximum buys per 24h rolling window
+    /// Maximum buys per budget window (see `budget_reset_mode`)
     pub max_daily_buys: u32,
-    /// Maximum USD spent per 24h rolling window
+    /// Maximum USD spent per budget window
     pub max_daily_spend_usd: f64,
     /// Auto-create sentinel after buying
     pub auto_create_sentinel: bool,
@@ -228,6 +312,10 @@ pub struct DipBuyerConfig {
     pub trailing_stop_pct: Option<f64>,
     /// Coins to never buy
     pub blacklisted_coins: Vec<String>,
+    /// Coins bought on every qualifying dip, skipping `cooldown_per_coin_secs`.
+    /// Daily buy and spend limits still apply.
+    #[serde(default)]
+    pub cooldown_exempt_coins: Vec<String>,
 
     // ── Signal Analysis (v1.2) ──────────────────────────────
     /// Minimum confidence score (0.0–1.0) to execute a buy
@@ -236,26 +324,74 @@ pub struct DipBuyerConfig {
     /// Maximum slippage % our buy can cause (0 = disabled)
     #[serde(default = "default_max_slippage")]
     pub max_buy_slippage_pct: f64,
+    /// Per-coin max slippage %, checked before the tier and global values
+    /// (0 = no override)
+    #[serde(default)]
+    pub coin_slippage_overrides: HashMap<String, f64>,
     /// Use candlestick momentum analysis
     #[serde(default = "default_true")]
     pub use_momentum_analysis: bool,
+    /// Fraction of confidence taken off coins with too few candles for
+    /// momentum analysis, which are scored neutral (0 = disabled)
+    #[serde(default)]
+    pub thin_history_confidence_penalty: f64,
     /// Signal weights
     #[serde(default)]
     pub signal_weights: SignalWeights,
     /// Scale buy amount by confidence (high confidence = full amount, lower = reduced)
     #[serde(default = "default_true")]
     pub scale_by_confidence: bool,
+    /// With `scale_by_confidence`, size along this curve instead of the
+    /// linear scale so only the strongest dips get the full tier amount
+    #[serde(default)]
+    pub confidence_curve: ConfidenceCurve,
     /// Max % of portfolio value to hold in any single coin (0 = disabled)
     #[serde(default)]
     pub max_position_pct: f64,
     /// Check existing holdings before buying
     #[serde(default = "default_true")]
     pub portfolio_aware: bool,
+    /// Buy anyway when holdings can't be fetched for the position check.
+    /// Off by default: the buy is skipped rather than risk exceeding
+    /// `max_position_pct`.
+    #[serde(default)]
+    pub proceed_without_portfolio: bool,
+    /// Confidence penalty per prior dump of the same coin inside the decay window (0 = disabled)
+    #[serde(default = "default_confidence_decay")]
+    pub confidence_decay_per_dump: f64,
+    /// How far back (seconds) prior dumps count toward the confidence decay
+    #[serde(default = "default_dump_decay_window")]
+    pub dump_decay_window_secs: u64,
+    /// Keep scoring a dip when the holders endpoint fails, using a neutral
+    /// holder-safety signal instead of skipping the candidate. Off in every
+    /// preset: buying blind on holder concentration is opt-in.
+    #[serde(default)]
+    pub proceed_without_holders: bool,
+    /// Buy size multiplier for dips scored without holder data
+    #[serde(default = "default_without_holders_buy_scale")]
+    pub without_holders_buy_scale: f64,
+    /// When the daily buy/spend caps reset (rolling 24h or a fixed UTC hour)
+    #[serde(default)]
+    pub budget_reset_mode: BudgetResetMode,
+    /// Skip dips on coins whose risk score (0–100) is above this (0 = no limit)
+    #[serde(default)]
+    pub max_risk_score: f64,
+    /// Scale the buy amount inversely to the coin's recent volatility (ATR
+    /// over the momentum candles). Coins with too few candles, or any coin
+    /// with momentum analysis off, keep the normal amount.
+    #[serde(default)]
+    pub atr_position_sizing: bool,
+    /// Volatility limits for `atr_position_sizing`
+    #[serde(default)]
+    pub atr_sizing: AtrSizing,
 }
 
 fn default_min_confidence() -> f64 { 0.55 }
 fn default_max_slippage() -> f64 { 5.0 }
 fn default_true() -> bool { true }
+fn default_confidence_decay() -> f64 { 0.15 }
+fn default_dump_decay_window() -> u64 { 3600 }
+fn default_without_holders_buy_scale() -> f64 { DEFAULT_WITHOUT_HOLDERS_BUY_SCALE }
 
 impl Default for DipBuyerConfig {
     fn default() -> Self {
@@ -267,8 +403,10 @@ impl DipBuyerConfig {
     /// Resolve per-tier settings for a coin based on its market cap.
     /// If tiers are enabled and one matches, the tier's non-zero values
     /// override the global defaults. Unset tier values (0) fall back to globals.
-    pub fn resolve_tier(&self, market_cap: f64) -> ResolvedTierSettings {
-        if self.use_coin_tiers {
+    /// A per-coin slippage override beats both. An unknown market cap
+    /// matches no tier.
+    pub fn resolve_tier(&self, symbol: &str, market_cap: Option<f64>) -> ResolvedTierSettings {
+        if let (true, Some(market_cap)) = (self.use_coin_tiers, market_cap) {
             for tier in &self.coin_tiers {
                 if tier.matches(market_cap) {
                     return ResolvedTierSettings {
@@ -283,11 +421,12 @@ impl DipBuyerConfig {
                         } else {
                             self.min_volume_24h
                         },
-                        max_buy_slippage_pct: if tier.max_buy_slippage_pct > 0.0 {
-                            tier.max_buy_slippage_pct
-                        } else {
-                            self.max_buy_slippage_pct
-                        },
+                        max_buy_slippage_pct: resolve_max_slippage(
+                            &self.coin_slippage_overrides,
+                            symbol,
+                            Some(tier.max_buy_slippage_pct),
+                            self.max_buy_slippage_pct,
+                        ),
                         tier_label: Some(tier.label.clone()),
                     };
                 }
@@ -297,10 +436,57 @@ impl DipBuyerConfig {
             buy_amount_usd: self.buy_amount_usd,
             min_sell_value_usd: self.min_sell_value_usd,
             min_volume_24h: self.min_volume_24h,
-            max_buy_slippage_pct: self.max_buy_slippage_pct,
+            max_buy_slippage_pct: resolve_max_slippage(
+                &self.coin_slippage_overrides,
+                symbol,
+                None,
+                self.max_buy_slippage_pct,
+            ),
             tier_label: None,
         }
     }
+
+    /// Settings the scoring step runs under for a resolved tier
+    pub fn decision_config(&self, tier: &ResolvedTierSettings) -> DipDecisionConfig {
+        DipDecisionConfig {
+            signal_weights: self.signal_weights.clone(),
+            skip_top_n_holders: self.skip_top_n_holders,
+            max_buy_slippage_pct: tier.max_buy_slippage_pct,
+            confidence_decay_per_dump: self.confidence_decay_per_dump,
+            min_confidence_score: self.min_confidence_score,
+            scale_by_confidence: self.scale_by_confidence,
+            confidence_curve: self.confidence_curve.clone(),
+            without_holders_buy_scale: self.without_holders_buy_scale,
+            // Without momentum analysis no candles are fetched at all
+            thin_history_confidence_penalty: if self.use_momentum_analysis {
+                self.thin_history_confidence_penalty
+            } else {
+                0.0
+            },
+            atr_sizing: self.atr_position_sizing.then(|| self.atr_sizing.clone()),
+        }
+    }
+
+    /// Hard gates a candidate must pass before scoring, for a resolved tier
+    pub fn gates(&self, tier: &ResolvedTierSettings) -> DipGates {
+        DipGates {
+            min_sell_value_usd: tier.min_sell_value_usd,
+            min_volume_24h: tier.min_volume_24h,
+            min_market_cap: self.min_market_cap,
+            max_market_cap: self.max_market_cap,
+            max_price_drop_pct: self.max_price_drop_pct,
+        }
+    }
+
+    /// Sentinel put on every dip buy; the sell % follows the sentinel defaults
+    pub fn protective_sentinel(&self) -> ProtectiveSentinelConfig {
+        ProtectiveSentinelConfig {
+            stop_loss_pct: self.stop_loss_pct,
+            take_profit_pct: self.take_profit_pct,
+            trailing_stop_pct: self.trailing_stop_pct,
+            sell_percentage: None,
+        }
+    }
 }
 
 // ─── Events ──────────────────────────────────────────────────────────
@@ -338,7 +524,9 @@ pub struct DipBuyerSkippedEvent {
     pub symbol: String,
     pub seller_username: String,
     pub sell_value_usd: f64,
+    /// Human-readable form of `skip`
     pub reason: String,
+    pub skip: SkipReason,
 }
 
 // ─── Handle ──────────────────────────────────────────────────────────
@@ -348,6 +536,7 @@ pub struct DipBuyerHandle {
     enabled_tx: Arc<watch::Sender<bool>>,
     config: Arc<RwLock<DipBuyerConfig>>,
     cancel: CancellationToken,
+    task: LoopTask,
 }
 
 impl DipBuyerHandle {
@@ -377,6 +566,21 @@ impl DipBuyerHandle {
     pub fn stop(&self) {
         self.cancel.cancel();
     }
+
+    /// Stop the loop and wait for it to save its in-memory state
+    pub async fn shutdown(&self) {
+        self.stop();
+        if !self.task.join(SHUTDOWN_TIMEOUT).await {
+            warn!("DipBuyer: loop did not stop in time, state snapshot skipped");
+        }
+    }
+}
+
+impl BuySideModule for DipBuyerHandle {
+    fn module_name(&self) -> &'static str { "dipbuyer" }
+    fn is_enabled(&self) -> bool { DipBuyerHandle::is_enabled(self) }
+    fn enable(&self) { DipBuyerHandle::enable(self) }
+    fn disable(&self) { DipBuyerHandle::disable(self) }
 }
 
 // ─── Spawn ───────────────────────────────────────────────────────────
@@ -389,10 +593,19 @@ pub fn spawn_dipbuyer(
     let config = Arc::new(RwLock::new(DipBuyerConfig::default()));
     let cancel = CancellationToken::new();
 
+    let task = LoopTask::spawn(dipbuyer_loop(
+        app_handle.clone(),
+        enabled_rx,
+        config.clone(),
+        executor,
+        cancel.clone(),
+    ));
+
     let handle = DipBuyerHandle {
         enabled_tx: Arc::new(enabled_tx),
-        config: config.clone(),
-        cancel: cancel.clone(),
+        config,
+        cancel,
+        task,
     };
 
     let restore_handle = handle.clone();
@@ -406,8 +619,6 @@ pub fn spawn_dipbuyer(
         }
     });
 
-    tokio::spawn(dipbuyer_loop(app_handle, enabled_rx, config, executor, cancel));
-
     handle
 }
 
@@ -423,48 +634,92 @@ async fn dipbuyer_loop(
     info!("DipBuyer loop started");
 
     // State tracking
-    let mut seen_trade_keys: HashSet<String> = HashSet::new();
-    let mut total_bought: u32 = load_dipbuyer_total(&app_handle).await;
-    let mut last_bought_at: Option<String> = load_dipbuyer_last_at(&app_handle).await;
-    let mut coin_cooldowns: HashMap<String, i64> = HashMap::new();
-    let mut daily_buys: Vec<(i64, f64)> = Vec::new(); // (timestamp, usd_amount)
+    let mut seen_trade_keys = SeenTrades::default();
+    let mut progress = DipBuyerProgress {
+        total_bought: load_dipbuyer_total(&app_handle).await,
+        last_bought_at: load_dipbuyer_last_at(&app_handle).await,
+        coin_cooldowns: HashMap::new(),
+        daily_buys: Vec::new(),
+    };
+    // Dip buys waiting for the user's approval (semi-auto mode)
+    let mut held: HeldOrders<DipBuy> = HeldOrders::default();
+    let mut recent_dumps: HashMap<String, Vec<(String, i64)>> = HashMap::new(); // symbol -> (trade_key, seen_at)
 
-    // Restore state from automation_log so restarts don't cause duplicate buys
+    // Restore the state saved at the last shutdown verbatim. Without one (first
+    // run or a crash), rebuild it from automation_log so restarts don't cause
+    // duplicate buys.
     let mut last_tick_ts = load_dipbuyer_last_tick_ts(&app_handle).await;
-    restore_state_from_log(
-        &app_handle,
-        &mut coin_cooldowns,
-        &mut daily_buys,
-        &mut seen_trade_keys,
-        last_tick_ts,
-    ).await;
+    if let Some(snapshot) = restore_loop_snapshot::<DipBuyerSnapshot>(&app_handle).await {
+        seen_trade_keys = snapshot.seen_trade_keys;
+        progress.coin_cooldowns = snapshot.coin_cooldowns;
+        progress.daily_buys = snapshot.daily_buys;
+        recent_dumps = snapshot.recent_dumps;
+    } else {
+        restore_state_from_log(
+            &app_handle,
+            &mut progress.coin_cooldowns,
+            &mut progress.daily_buys,
+            &mut seen_trade_keys,
+            last_tick_ts,
+        ).await;
+    }
 
     if let Some(saved_config) = load_dipbuyer_config(&app_handle).await {
         *config.write().await = saved_config;
     }
 
+    let mut feed_depth = FeedDepth::new(config.read().await.recent_trades_depth);
     let mut interval = tokio::time::interval(
         std::time::Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
     );
+    let mut adaptive = AdaptiveInterval::new(interval.period());
+    let mut latest_trade_at: i64 = 0;
+    let mut live_wake = app_handle.state::<AppState>().live_feed.waker();
 
     loop {
         tokio::select! {
             _ = cancel.cancelled() => {
+                let snapshot = DipBuyerSnapshot {
+                    seen_trade_keys: std::mem::take(&mut seen_trade_keys),
+                    coin_cooldowns: std::mem::take(&mut progress.coin_cooldowns),
+                    daily_buys: std::mem::take(&mut progress.daily_buys),
+                    recent_dumps: std::mem::take(&mut recent_dumps),
+                };
+                save_loop_snapshot(&app_handle, &snapshot).await;
                 info!("DipBuyer cancelled, exiting");
                 return;
             }
-            _ = interval.tick() => {
+            _ = live_wake.next_tick(&mut interval) => {
+                app_handle.state::<AppState>().heartbeats.beat("dipbuyer");
+
+                // Nothing acts until the startup warm-up has loaded its state
+                if !app_handle.state::<AppState>().warmup.is_ready() {
+                    debug!("DipBuyer: warming up, skipping tick");
+                    continue;
+                }
+
+                // Approved dip buys that filled since the last tick
+                if !held.is_empty() {
+                    let cfg = config.read().await.clone();
+                    for (buy, result) in held.take_settled() {
+                        match result {
+                            Ok(response) => record_dip_buy(&app_handle, &cfg, &buy, &response, &mut progress).await,
+                            Err(e) => info!("DipBuyer: proposal for {} not filled: {}", buy.symbol, e),
+                        }
+                    }
+                }
+
                 let enabled = *enabled_rx.borrow_and_update();
 
                 if !enabled {
                     let tick = DipBuyerTickEvent {
                         enabled: false,
-                        total_bought,
-                        last_bought_at: last_bought_at.clone(),
+                        total_bought: progress.total_bought,
+                        last_bought_at: progress.last_bought_at.clone(),
                         trades_scanned: 0,
                         dips_detected: 0,
                     };
-                    let _ = app_handle.emit("dipbuyer-tick", &tick);
+                    let _ = emit_event(&app_handle, AppEvent::DipBuyerTick(tick));
                     continue;
                 }
 
@@ -476,11 +731,13 @@ async fn dipbuyer_loop(
                     }
                 };
 
-                let client = RugplayClient::new_with_cache(&token, {
-                    let state = app_handle.state::<AppState>();
-                    state.coin_cache.clone()
-                });
+                let client = app_handle.state::<AppState>().api_client(&token);
                 let cfg = config.read().await.clone();
+                let quote_max_age = {
+                    let state = app_handle.state::<AppState>();
+                    let policy = state.price_freshness.read().await;
+                    policy.max_age(PriceDecision::AutomatedBuy)
+                };
 
                 // Update interval if config changed
                 let desired_interval = if cfg.poll_interval_secs > 0 {
@@ -488,8 +745,7 @@ async fn dipbuyer_loop(
                 } else {
                     DEFAULT_POLL_INTERVAL_SECS
                 };
-                let current_period = interval.period();
-                if current_period != std::time::Duration::from_secs(desired_interval) {
+                if adaptive.set_configured(std::time::Duration::from_secs(desired_interval)) {
                     interval = tokio::time::interval(
                         std::time::Duration::from_secs(desired_interval),
                     );
@@ -497,24 +753,35 @@ async fn dipbuyer_loop(
 
                 let now_epoch = chrono::Utc::now().timestamp();
 
-                // Prune expired cooldowns
-                coin_cooldowns.retain(|_, ts| now_epoch - *ts < cfg.cooldown_per_coin_secs as i64);
+                // Prune expired cooldowns (exempt coins never hold one)
+                progress.coin_cooldowns.retain(|symbol, ts| {
+                    !is_cooldown_exempt(&cfg.cooldown_exempt_coins, symbol)
+                        && now_epoch - *ts < cfg.cooldown_per_coin_secs as i64
+                });
 
-                // Prune daily buys > 24h
-                daily_buys.retain(|(ts, _)| now_epoch - *ts < 86400);
-                let buys_today: u32 = daily_buys.len() as u32;
-                let spent_today: f64 = daily_buys.iter().map(|(_, a)| a).sum();
+                // Prune dumps that fell out of the decay window
+                let decay_window = cfg.dump_decay_window_secs as i64;
+                recent_dumps.retain(|_, dumps| {
+                    dumps.retain(|(_, ts)| now_epoch - *ts < decay_window);
+                    !dumps.is_empty()
+                });
+
+                // Prune daily buys that fell out of the budget window
+                cfg.budget_reset_mode.prune(&mut progress.daily_buys, now_epoch);
+                let committed = committed_buys(&progress.daily_buys, &held);
+                let buys_today: u32 = committed.len() as u32;
+                let spent_today: f64 = committed.iter().map(|(_, a)| a).sum();
 
                 if buys_today >= cfg.max_daily_buys {
                     debug!("DipBuyer: daily buy limit reached ({}/{})", buys_today, cfg.max_daily_buys);
                     let tick = DipBuyerTickEvent {
                         enabled: true,
-                        total_bought,
-                        last_bought_at: last_bought_at.clone(),
+                        total_bought: progress.total_bought,
+                        last_bought_at: progress.last_bought_at.clone(),
                         trades_scanned: 0,
                         dips_detected: 0,
                     };
-                    let _ = app_handle.emit("dipbuyer-tick", &tick);
+                    let _ = emit_event(&app_handle, AppEvent::DipBuyerTick(tick));
                     continue;
                 }
 
@@ -523,20 +790,38 @@ async fn dipbuyer_loop(
                     continue;
                 }
 
-                // Poll recent trades
-                let trades = match client.get_recent_trades(50).await {
-                    Ok(t) => t,
-                    Err(e) => {
-                        error!("DipBuyer: failed to fetch recent trades: {}", e);
-                        continue;
-                    }
+                // Recent trades from the live socket, or polled while it is down
+                feed_depth.set_configured(cfg.recent_trades_depth);
+                let live_trades = app_handle.state::<AppState>().live_feed.recent(feed_depth.limit() as usize);
+                let trades = match live_trades {
+                    Some(t) => t,
+                    None => match client.get_recent_trades(feed_depth.limit()).await {
+                        Ok(t) => t,
+                        Err(e) => {
+                            error!("DipBuyer: failed to fetch recent trades: {}", e);
+                            let outcome = PollOutcome::from_error(&e.to_string());
+                            retune_poll_interval(&app_handle, &mut adaptive, &mut interval, outcome, "DipBuyer").await;
+                            continue;
+                        }
+                    },
                 };
+                let timestamps: Vec<i64> = trades.iter().map(|t| t.timestamp).collect();
+                let newest = timestamps.iter().copied().max().unwrap_or(latest_trade_at);
+                let outcome = if newest > latest_trade_at { PollOutcome::Active } else { PollOutcome::Quiet };
+                latest_trade_at = latest_trade_at.max(newest);
+                retune_poll_interval(&app_handle, &mut adaptive, &mut interval, outcome, "DipBuyer").await;
+                if feed_depth.observe(&timestamps) {
+                    info!("DipBuyer: trade feed outran the poll window, fetching {} trades", feed_depth.limit());
+                }
 
                 let mut trades_scanned = 0u32;
                 let mut dips_detected = 0u32;
                 let mut max_trade_ts: i64 = last_tick_ts;
+                let wash_trades = wash_trade_indices(&trades, cfg.wash_trade_sensitivity);
 
-                for trade in &trades {
+                // Cheap filters first; survivors are ranked for the API-heavy checks below
+                let mut candidates: Vec<(&RecentTrade, String, u32)> = Vec::new();
+                for (index, trade) in trades.iter().enumerate() {
                     trades_scanned += 1;
 
                     // Skip trades we already evaluated before restart
@@ -554,6 +839,12 @@ async fn dipbuyer_loop(
                         continue;
                     }
 
+                    // A seller trading with themselves isn't dumping
+                    if wash_trades.contains(&index) {
+                        debug!("DipBuyer: ignoring likely wash trade by {} on {}", trade.username, trade.coin_symbol);
+                        continue;
+                    }
+
                     // Deduplicate: use a key of (userId, symbol, timestamp, amount)
                     let trade_key = format!(
                         "{}:{}:{}:{:.4}",
@@ -573,20 +864,45 @@ async fn dipbuyer_loop(
                         continue;
                     }
 
+                    // Remember this dump (once per trade) so later dumps of the same coin decay confidence
+                    let dumps = recent_dumps.entry(trade.coin_symbol.clone()).or_default();
+                    if !dumps.iter().any(|(k, _)| k == &trade_key) {
+                        dumps.push((trade_key.clone(), now_epoch));
+                    }
+                    let prior_dumps = dumps.iter().filter(|(k, _)| k != &trade_key).count() as u32;
+                    candidates.push((trade, trade_key, prior_dumps));
+                }
+
+                // Biggest sells first; past the per-tick cap the rest wait for the next tick
+                rank_candidates(&mut candidates, |(trade, _, _)| trade.total_value);
+                let mut budget = TickBudget::new(cfg.max_evaluations_per_tick);
+                let mut oldest_deferred: Option<i64> = None;
+
+                for (trade, trade_key, prior_dumps) in candidates {
                     // Check cooldown for this coin
-                    if coin_cooldowns.contains_key(&trade.coin_symbol) {
+                    if in_coin_cooldown(&progress.coin_cooldowns, &cfg.cooldown_exempt_coins, &trade.coin_symbol)
+                        || held.is_held(&trade.coin_symbol)
+                    {
                         debug!("DipBuyer: {} still in cooldown", trade.coin_symbol);
                         continue;
                     }
 
-                    // Check daily budget (use max possible buy amount for conservative check)
-                    if cfg.max_daily_spend_usd > 0.0 && spent_today + cfg.buy_amount_usd > cfg.max_daily_spend_usd {
-                        debug!("DipBuyer: would exceed daily spend limit for {}", trade.coin_symbol);
+                    // Check daily count and budget against buys made so far, including
+                    // earlier ones this tick (use max possible buy amount for a conservative check)
+                    if !within_daily_limits(&committed_buys(&progress.daily_buys, &held), now_epoch, cfg.budget_reset_mode, cfg.max_daily_buys, cfg.max_daily_spend_usd, cfg.buy_amount_usd) {
+                        debug!("DipBuyer: would exceed daily buy/spend limit for {}", trade.coin_symbol);
+                        continue;
+                    }
+
+                    if !budget.take() {
+                        if trade.timestamp > 0 {
+                            oldest_deferred = Some(oldest_deferred.map_or(trade.timestamp, |ts| ts.min(trade.timestamp)));
+                        }
                         continue;
                     }
 
                     // Mark as seen regardless of outcome
-                    seen_trade_keys.insert(trade_key);
+                    seen_trade_keys.insert(trade_key, now_epoch);
 
                     // ─── Analyze the coin ─────────────────────────────────
 
@@ -600,7 +916,7 @@ async fn dipbuyer_loop(
                             }
                         }
                     } else {
-                        match client.get_coin(&trade.coin_symbol).await {
+                        match client.get_coin_within(&trade.coin_symbol, quote_max_age).await {
                             Ok(c) => rugplay_core::CoinDetailsResponse {
                                 coin: c,
                                 candlestick_data: Vec::new(),
@@ -616,126 +932,122 @@ async fn dipbuyer_loop(
                     let coin = &coin_data.coin;
 
                     // Resolve tier settings (per-tier overrides fall back to globals)
-                    let tier = cfg.resolve_tier(coin.market_cap);
-
-                    // Tier-aware sell value re-check (initial check used global as quick pre-filter)
-                    if trade.total_value < tier.min_sell_value_usd {
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                            &format!("Sell ${:.0} below tier min ${:.0}", trade.total_value, tier.min_sell_value_usd));
-                        continue;
-                    }
+                    let tier = cfg.resolve_tier(&coin.symbol, coin.market_cap);
 
-                    // Hard gate: Volume filter (tier-aware)
-                    if coin.volume_24h < tier.min_volume_24h {
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                            &format!("Low volume (${:.0} < ${:.0})", coin.volume_24h, tier.min_volume_24h));
-                        continue;
-                    }
-
-                    // Hard gate: Market cap filters
-                    if coin.market_cap < cfg.min_market_cap {
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                            &format!("Low market cap (${:.0} < ${:.0})", coin.market_cap, cfg.min_market_cap));
-                        continue;
-                    }
-                    if cfg.max_market_cap > 0.0 && coin.market_cap > cfg.max_market_cap {
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                            &format!("High market cap (${:.0} > ${:.0})", coin.market_cap, cfg.max_market_cap));
+                    // Hard gates: tier-aware sell value (the initial check used the
+                    // global as a quick pre-filter) and volume, market cap, 24h change
+                    if let Some(skip) = dip_gate_skip(&cfg.gates(&tier), trade.total_value, coin) {
+                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value, skip);
                         continue;
                     }
+                    // The gates skip coins without a market cap
+                    let Some(market_cap) = coin.market_cap else { continue };
 
-                    // Hard gate: 24h change filter
-                    if cfg.max_price_drop_pct < 0.0 && coin.change_24h < cfg.max_price_drop_pct {
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                            &format!("Already dropped too much ({:.1}% < {:.1}%)", coin.change_24h, cfg.max_price_drop_pct));
-                        continue;
-                    }
-
-                    // Fetch holders for analysis
+                    // Fetch holders for analysis. If that fails, either drop the
+                    // candidate or score it with a neutral holder signal
                     let holders = match client.get_coin_holders(&trade.coin_symbol, 20).await {
-                        Ok(h) => h,
+                        Ok(h) => Some(h),
+                        Err(e) if cfg.proceed_without_holders => {
+                            warn!(
+                                "DipBuyer: holders unavailable for {} ({}), scoring without holder data",
+                                trade.coin_symbol, e
+                            );
+                            None
+                        }
                         Err(e) => {
                             debug!("DipBuyer: failed to get holders for {}: {}", trade.coin_symbol, e);
                             continue;
                         }
                     };
 
+                    // Shared coin risk score, against this module's own limit
+                    if cfg.max_risk_score > 0.0 {
+                        let risk = assess_coin_risk(coin, holders.as_ref(), &coin_data.candlestick_data, now_epoch);
+                        if risk.exceeds(cfg.max_risk_score) {
+                            debug!("DipBuyer: {} risk {:.0} ({})", trade.coin_symbol, risk.score, risk.breakdown());
+                            emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
+                                SkipReason::HighRisk { actual: risk.score, threshold: cfg.max_risk_score });
+                            continue;
+                        }
+                    }
+
                     let base_buy_amount = tier.buy_amount_usd;
 
                     // ─── Portfolio-aware position check ───────────────────
                     if cfg.portfolio_aware && cfg.max_position_pct > 0.0 {
-                        match client.get_portfolio().await {
-                            Ok(portfolio) => {
-                                let total_value = portfolio.total_value;
-                                if total_value > 0.0 {
-                                    let existing_value = portfolio.coin_holdings.iter()
-                                        .find(|h| h.symbol == trade.coin_symbol)
-                                        .map(|h| h.value)
-                                        .unwrap_or(0.0);
-                                    let after_buy = existing_value + base_buy_amount;
-                                    let position_pct = (after_buy / total_value) * 100.0;
-                                    if position_pct > cfg.max_position_pct {
-                                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                                            &format!("Position {:.1}% would exceed max {:.1}%", position_pct, cfg.max_position_pct));
-                                        continue;
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                debug!("DipBuyer: portfolio check failed for {}: {}", trade.coin_symbol, e);
-                            }
+                        let portfolio = client.get_portfolio().await
+                            .map(|portfolio| {
+                                let existing_value = portfolio.coin_holdings.iter()
+                                    .find(|h| h.symbol == trade.coin_symbol)
+                                    .map_or(0.0, |h| h.value);
+                                (portfolio.total_value, existing_value)
+                            })
+                            .map_err(|e| e.to_string());
+                        if let Err(e) = &portfolio {
+                            warn!(
+                                "DipBuyer: portfolio check failed for {} ({}), {}",
+                                trade.coin_symbol, e,
+                                if cfg.proceed_without_portfolio { "buying without it" } else { "skipping" }
+                            );
+                        }
+                        if let Some(skip) = dip_position_skip(portfolio, base_buy_amount, cfg.max_position_pct, cfg.proceed_without_portfolio) {
+                            emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value, skip);
+                            continue;
                         }
                     }
 
                     // ─── Run confidence scoring engine ────────────────────
-                    let analysis = analyze_dip(
-                        &trade.coin_symbol,
-                        trade,
-                        coin,
-                        &coin_data.candlestick_data,
-                        &holders,
+                    // Hard rejects (whale dump, extreme concentration, slippage),
+                    // repeat-dump decay, the confidence threshold and sizing all
+                    // happen in the recorded, replayable decision step
+                    let decision_cfg = cfg.decision_config(&tier);
+                    let decision_input = DipDecisionInput {
+                        sell_trade: trade.clone(),
+                        coin: coin.clone(),
+                        candles: coin_data.candlestick_data.clone(),
+                        holders: holders.clone(),
                         base_buy_amount,
-                        &cfg.signal_weights,
-                        cfg.skip_top_n_holders,
-                        tier.max_buy_slippage_pct,
-                    );
-
-                    // Hard rejection from signals (whale dump, extreme concentration, slippage)
-                    if analysis.hard_reject {
-                        let reason = analysis.reject_reason.as_deref().unwrap_or("Signal hard reject");
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value, reason);
-                        continue;
-                    }
+                        prior_dumps,
+                    };
+                    let (analysis, action) = evaluate_dip(&decision_cfg, &decision_input);
+                    record_decision::<DipBuyerDecision>(&app_handle, &decision_cfg, &decision_input, &action).await;
+
+                    let buy_amount = match action {
+                        DecisionAction::Buy { amount_usd, .. } => amount_usd,
+                        DecisionAction::Skip { reason, .. } => {
+                            let skip = dip_decision_skip(&analysis, decision_cfg.min_confidence_score)
+                                .unwrap_or(SkipReason::SignalReject { detail: reason });
+                            emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value, skip);
+                            continue;
+                        }
+                        _ => continue,
+                    };
 
-                    // Confidence threshold check
-                    if analysis.confidence_score < cfg.min_confidence_score {
-                        emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
-                            &format!("Low confidence {:.2} < {:.2} ({})",
-                                analysis.confidence_score, cfg.min_confidence_score,
-                                analysis.signals.iter().map(|s| format!("{}:{:.2}", s.name, s.score)).collect::<Vec<_>>().join(", ")
-                            ));
-                        continue;
+                    // The user's entry script, if any, has the last word
+                    let entry_script = app_handle.state::<AppState>().entry_script.read().await.clone();
+                    if let Some(script) = entry_script {
+                        let signals: Vec<(String, f64)> =
+                            analysis.signals.iter().map(|s| (s.name.clone(), s.score)).collect();
+                        if let Err(detail) = script.check_blocking("dipbuyer", coin, holders.as_ref(), &signals).await {
+                            debug!("DipBuyer: {} vetoed: {}", trade.coin_symbol, detail);
+                            emit_skip(&app_handle, &trade.coin_symbol, &trade.username, trade.total_value,
+                                SkipReason::ScriptReject { detail });
+                            continue;
+                        }
                     }
 
                     // ─── DIP CONFIRMED — BUY ─────────────────────────────
 
                     dips_detected += 1;
 
-                    // Scale buy amount by confidence if enabled
-                    let buy_amount = if cfg.scale_by_confidence {
-                        (base_buy_amount * analysis.recommended_buy_pct).max(1.0)
-                    } else {
-                        base_buy_amount
-                    };
-
                     // Final daily spend check with resolved amount
-                    if cfg.max_daily_spend_usd > 0.0 && spent_today + buy_amount > cfg.max_daily_spend_usd {
-                        debug!("DipBuyer: resolved buy ${:.0} for {} would exceed daily spend", buy_amount, trade.coin_symbol);
+                    if !within_daily_limits(&committed_buys(&progress.daily_buys, &held), now_epoch, cfg.budget_reset_mode, cfg.max_daily_buys, cfg.max_daily_spend_usd, buy_amount) {
+                        debug!("DipBuyer: resolved buy ${:.0} for {} would exceed daily limits", buy_amount, trade.coin_symbol);
                         continue;
                     }
 
                     let seller_rank = trade.user_id.parse::<u32>().ok().and_then(|sid| {
-                        holders.holders.iter().find(|h| h.user_id == sid).map(|h| h.rank)
+                        holders.as_ref()?.holders.iter().find(|h| h.user_id == sid).map(|h| h.rank)
                     });
 
                     info!(
@@ -755,73 +1067,38 @@ async fn dipbuyer_loop(
                         seller_username: trade.username.clone(),
                         sell_value_usd: trade.total_value,
                         seller_rank,
-                        market_cap: coin.market_cap,
+                        market_cap,
                         price: coin.current_price,
                         change_24h: coin.change_24h,
                         confidence_score: analysis.confidence_score,
                         slippage_pct: analysis.slippage_pct,
                         sell_impact_pct: analysis.sell_impact_pct,
                     };
-                    let _ = app_handle.emit("dipbuyer-triggered", &event);
+                    let _ = emit_event(&app_handle, AppEvent::DipBuyerTriggered(event));
 
-                    let reason = format!(
-                        "DipBuyer: {} sold ${:.0} of {} (conf={:.2}, slip={:.1}%), buy ${:.0}",
-                        trade.username, trade.total_value, trade.coin_symbol,
-                        analysis.confidence_score, analysis.slippage_pct, buy_amount
-                    );
+                    let reason = dip_buy_reason(trade, &analysis, buy_amount);
 
-                    match executor.submit_trade(
-                        trade.coin_symbol.clone(),
-                        TradeType::Buy,
+                    let buy = DipBuy {
+                        symbol: trade.coin_symbol.clone(),
+                        coin_name: trade.coin_name.clone(),
                         buy_amount,
-                        TradePriority::Normal,
+                        seller: trade.username.clone(),
+                        sell_value: trade.total_value,
+                        seller_rank,
+                        market_cap,
+                        price: coin.current_price,
+                        change_24h: coin.change_24h,
+                        analysis,
                         reason,
-                    ).await {
-                        Ok(response) => {
-                            info!("DipBuyer: bought {} @ ${:.8} for ${:.0}", trade.coin_symbol, response.new_price, buy_amount);
-                            total_bought += 1;
-                            last_bought_at = Some(chrono::Utc::now().to_rfc3339());
-
-                            coin_cooldowns.insert(trade.coin_symbol.clone(), now_epoch);
-                            daily_buys.push((now_epoch, buy_amount));
-
-                            if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
-                                notif.send_raw(
-                                    &format!("Dip Buy: {}", trade.coin_symbol),
-                                    &format!(
-                                        "${:.2} @ ${:.8} (conf {:.0}%) — {} dumped ${:.0}",
-                                        buy_amount, response.new_price,
-                                        analysis.confidence_score * 100.0,
-                                        trade.username, trade.total_value
-                                    ),
-                                ).await;
-                            }
-
-                            save_dipbuyer_state(&app_handle, total_bought, last_bought_at.as_deref()).await;
-
-                            save_dipbuyer_log_entry(
-                                &app_handle,
-                                &trade.coin_symbol,
-                                &trade.coin_name,
-                                buy_amount,
-                                &trade.username,
-                                trade.total_value,
-                                seller_rank,
-                                coin.market_cap,
-                                response.new_price,
-                                coin.change_24h,
-                                &analysis,
-                            ).await;
-
-                            // Auto-create sentinel
-                            if cfg.auto_create_sentinel {
-                                create_sentinel_for_dip(
-                                    &app_handle,
-                                    &trade.coin_symbol,
-                                    response.new_price,
-                                    &cfg,
-                                ).await;
-                            }
+                        decided_at: now_epoch,
+                    };
+                    match submit_dip_buy(&executor, &buy).await {
+                        Ok(Submission::Filled(response)) => {
+                            record_dip_buy(&app_handle, &cfg, &buy, &response, &mut progress).await;
+                        }
+                        Ok(Submission::Held(proposal)) => {
+                            info!("DipBuyer: {} proposed for approval (#{})", trade.coin_symbol, proposal.approval.id);
+                            held.hold(proposal, buy);
                         }
                         Err(e) => {
                             error!("DipBuyer: failed to buy {}: {}", trade.coin_symbol, e);
@@ -829,12 +1106,14 @@ async fn dipbuyer_loop(
                     }
                 }
 
-                // Prune seen_trade_keys if set grows too large.
-                // We keep the set from getting unbounded but can't do LRU with
-                // HashSet alone, so we shrink to ~200 by clearing and relying on
-                // last_tick_ts for primary dedup on restart.
-                if seen_trade_keys.len() > 1000 {
-                    seen_trade_keys.clear();
+                // Keep the seen set bounded by evicting only the oldest keys,
+                // so recently processed trades are never reconsidered
+                seen_trade_keys.evict(now_epoch, SEEN_TRADES_WINDOW_SECS, SEEN_TRADES_CAPACITY);
+
+                // Deferred trades must stay newer than the saved timestamp to be picked up again
+                if let Some(oldest) = oldest_deferred {
+                    debug!("DipBuyer: evaluation cap of {} reached, deferring the rest", cfg.max_evaluations_per_tick);
+                    max_trade_ts = max_trade_ts.min(oldest - 1);
                 }
 
                 // Persist the latest trade timestamp so restarts skip already-evaluated trades
@@ -845,12 +1124,12 @@ async fn dipbuyer_loop(
 
                 let tick = DipBuyerTickEvent {
                     enabled: true,
-                    total_bought,
-                    last_bought_at: last_bought_at.clone(),
+                    total_bought: progress.total_bought,
+                    last_bought_at: progress.last_bought_at.clone(),
                     trades_scanned,
                     dips_detected,
                 };
-                let _ = app_handle.emit("dipbuyer-tick", &tick);
+                let _ = emit_event(&app_handle, AppEvent::DipBuyerTick(tick));
             }
         }
     }
@@ -858,15 +1137,134 @@ async fn dipbuyer_loop(
 
 // ─── Helpers ─────────────────────────────────────────────────────────
 
-fn emit_skip(app_handle: &tauri::AppHandle, symbol: &str, seller: &str, sell_value: f64, reason: &str) {
+/// Why the dip buyer buys after `trade`
+fn dip_buy_reason(trade: &RecentTrade, analysis: &DipAnalysis, buy_amount: f64) -> TradeReason {
+    TradeReason::Dip {
+        seller: trade.username.clone(),
+        sell_value_usd: trade.total_value,
+        symbol: trade.coin_symbol.clone(),
+        confidence: analysis.confidence_score,
+        slippage_pct: analysis.slippage_pct,
+        buy_usd: buy_amount,
+    }
+}
+
+/// What the dip buyer has bought so far
+struct DipBuyerProgress {
+    total_bought: u32,
+    last_bought_at: Option<String>,
+    /// Coin -> time of its last buy
+    coin_cooldowns: HashMap<String, i64>,
+    /// (timestamp, usd_amount) of each buy, for the daily limits
+    daily_buys: Vec<(i64, f64)>,
+}
+
+/// A confirmed dip buy, with what the dip buyer saw when it decided
+#[derive(Debug, Clone)]
+struct DipBuy {
+    symbol: String,
+    coin_name: String,
+    buy_amount: f64,
+    seller: String,
+    sell_value: f64,
+    seller_rank: Option<u32>,
+    market_cap: f64,
+    /// Quoted price, standing in when the fill reports none
+    price: f64,
+    change_24h: f64,
+    analysis: DipAnalysis,
+    reason: TradeReason,
+    decided_at: i64,
+}
+
+/// Buys made plus buys still waiting for approval, which count against the
+/// daily limits as if approved
+fn committed_buys(daily_buys: &[(i64, f64)], held: &HeldOrders<DipBuy>) -> Vec<(i64, f64)> {
+    daily_buys.iter().copied().chain(held.contexts().map(|buy| (buy.decided_at, buy.buy_amount))).collect()
+}
+
+/// Book a filled dip buy: cooldown, daily limits, stats, log and the protective sentinel
+async fn record_dip_buy(
+    app_handle: &tauri::AppHandle,
+    cfg: &DipBuyerConfig,
+    buy: &DipBuy,
+    response: &TradeResponse,
+    progress: &mut DipBuyerProgress,
+) {
+    let price = response.fill_price().unwrap_or(buy.price);
+    info!("DipBuyer: bought {} @ ${:.8} for ${:.0}", buy.symbol, price, buy.buy_amount);
+    let now_epoch = chrono::Utc::now().timestamp();
+    progress.total_bought += 1;
+    progress.last_bought_at = Some(chrono::Utc::now().to_rfc3339());
+
+    progress.coin_cooldowns.insert(buy.symbol.clone(), now_epoch);
+    progress.daily_buys.push((now_epoch, buy.buy_amount));
+
+    if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+        notif.notify_dedup(
+            NotificationCategory::Trade,
+            &format!("Dip Buy: {}", buy.symbol),
+            &format!(
+                "${:.2} @ ${:.8} (conf {:.0}%) — {} dumped ${:.0}",
+                buy.buy_amount, price,
+                buy.analysis.confidence_score * 100.0,
+                buy.seller, buy.sell_value
+            ),
+        ).await;
+    }
+
+    if !response.paper {
+        save_dipbuyer_state(app_handle, progress.total_bought, progress.last_bought_at.as_deref()).await;
+    }
+
+    save_dipbuyer_log_entry(
+        app_handle,
+        &buy.symbol,
+        &buy.coin_name,
+        buy.buy_amount,
+        &buy.seller,
+        buy.sell_value,
+        buy.seller_rank,
+        buy.market_cap,
+        price,
+        buy.change_24h,
+        &buy.analysis,
+        &buy.reason,
+    ).await;
+
+    // Auto-create sentinel
+    if cfg.auto_create_sentinel {
+        ensure_protective_sentinel(app_handle, "DipBuyer", &buy.symbol, response, &cfg.protective_sentinel()).await;
+    }
+}
+
+/// Buy a confirmed dip through the executor. In semi-auto mode it comes
+/// back held for approval at once.
+async fn submit_dip_buy(executor: &TradeExecutorHandle, buy: &DipBuy) -> Result<Submission, String> {
+    executor
+        .submit_automated(AutomatedTrade {
+            symbol: buy.symbol.clone(),
+            trade_type: TradeType::Buy,
+            amount: TradeAmount::Usd(buy.buy_amount),
+            priority: TradePriority::Normal,
+            reason: buy.reason.clone(),
+            source: "dipbuyer".to_string(),
+            quoted_price: Some(buy.price),
+        })
+        .await
+}
+
+fn emit_skip(app_handle: &tauri::AppHandle, symbol: &str, seller: &str, sell_value: f64, skip: SkipReason) {
+    let reason = skip.message();
     debug!("DipBuyer: skipping {} — {}", symbol, reason);
     let event = DipBuyerSkippedEvent {
         symbol: symbol.to_string(),
         seller_username: seller.to_string(),
         sell_value_usd: sell_value,
-        reason: reason.to_string(),
+        reason,
+        skip,
     };
-    let _ = app_handle.emit("dipbuyer-skipped", &event);
+    let _ = emit_event(app_handle, AppEvent::DipBuyerSkipped(event));
 }
 
 async fn get_active_token(app_handle: &tauri::AppHandle) -> Result<String, String> {
@@ -892,73 +1290,6 @@ async fn get_active_token(app_handle: &tauri::AppHandle) -> Result<String, Strin
     Ok(token)
 }
 
-async fn create_sentinel_for_dip(
-    app_handle: &tauri::AppHandle,
-    symbol: &str,
-    fallback_price: f64,
-    config: &DipBuyerConfig,
-) {
-    let state = app_handle.state::<AppState>();
-
-    // Fetch portfolio avg_purchase_price so the sentinel tracks the true
-    // weighted average across all buys, not just the latest dip buy price.
-    let avg_entry = match get_active_token(app_handle).await {
-        Ok(token) => {
-            let client = RugplayClient::new(&token);
-            match client.get_portfolio().await {
-                Ok(portfolio) => {
-                    portfolio.coin_holdings.iter()
-                        .find(|h| h.symbol == symbol)
-                        .map(|h| if h.avg_purchase_price > 0.0 { h.avg_purchase_price } else { fallback_price })
-                        .unwrap_or(fallback_price)
-                }
-                Err(_) => fallback_price,
-            }
-        }
-        Err(_) => fallback_price,
-    };
-
-    let db_guard = state.db.read().await;
-    let Some(db) = db_guard.as_ref() else { return };
-
-    let profile = match sqlite::get_active_profile(db.pool()).await {
-        Ok(Some(p)) => p,
-        _ => return,
-    };
-
-    // Load sentinel defaults for sell_percentage so we don't override
-    // user preferences with a hardcoded 100%.
-    let sell_pct = {
-        let settings_json: Option<String> = sqlx::query_scalar(
-            "SELECT value FROM settings WHERE key = 'app_settings'",
-        )
-        .fetch_optional(db.pool())
-        .await
-        .ok()
-        .flatten();
-
-        settings_json
-            .and_then(|j| serde_json::from_str::<serde_json::Value>(&j).ok())
-            .and_then(|s| s["sentinelDefaults"]["sellPercentage"].as_f64())
-            .unwrap_or(100.0)
-    };
-
-    if let Err(e) = sqlite::upsert_sentinel(
-        db.pool(),
-        profile.id,
-        symbol,
-        Some(config.stop_loss_pct),
-        Some(config.take_profit_pct),
-        config.trailing_stop_pct,
-        sell_pct,
-        avg_entry,
-    ).await {
-        error!("DipBuyer: failed to upsert sentinel for {}: {}", symbol, e);
-    } else {
-        debug!("DipBuyer: sentinel upserted for {} (avg entry: {:.8})", symbol, avg_entry);
-    }
-}
-
 // ─── DB Persistence ──────────────────────────────────────────────────
 
 async fn load_dipbuyer_config(app_handle: &tauri::AppHandle) -> Option<DipBuyerConfig> {
@@ -1090,6 +1421,7 @@ async fn save_dipbuyer_log_entry(
     price: f64,
     change_24h: f64,
     analysis: &DipAnalysis,
+    reason: &TradeReason,
 ) {
     let state = app_handle.state::<AppState>();
     let db_guard = state.db.read().await;
@@ -1118,7 +1450,7 @@ async fn save_dipbuyer_log_entry(
     .bind(symbol)
     .bind(coin_name)
     .bind(buy_amount_usd)
-    .bind(serde_json::json!({
+    .bind(paper_tagged_details(app_handle, "dipbuyer", "BUY", &serde_json::json!({
         "sellerUsername": seller_username,
         "sellValueUsd": sell_value_usd,
         "sellerRank": seller_rank,
@@ -1129,7 +1461,8 @@ async fn save_dipbuyer_log_entry(
         "slippagePct": (analysis.slippage_pct * 100.0).round() / 100.0,
         "sellImpactPct": (analysis.sell_impact_pct * 100.0).round() / 100.0,
         "signals": signals_json,
-    }).to_string())
+        "reason": reason.to_value(),
+    }).to_string()))
     .execute(db.pool())
     .await;
 
@@ -1170,11 +1503,12 @@ async fn save_dipbuyer_last_tick_ts(app_handle: &tauri::AppHandle, ts: i64) {
 
 /// Restore coin_cooldowns, daily_buys, and seen_trade_keys from the
 /// automation_log table so that app restarts don't cause duplicate purchases.
+/// Only used when the last shutdown left no `DipBuyerSnapshot` (e.g. a crash).
 async fn restore_state_from_log(
     app_handle: &tauri::AppHandle,
     coin_cooldowns: &mut HashMap<String, i64>,
     daily_buys: &mut Vec<(i64, f64)>,
-    seen_trade_keys: &mut HashSet<String>,
+    seen_trade_keys: &mut SeenTrades,
     last_tick_ts: i64,
 ) {
     let state = app_handle.state::<AppState>();
@@ -1204,35 +1538,43 @@ async fn restore_state_from_log(
         return;
     }
 
-    let _now_epoch = chrono::Utc::now().timestamp();
+    let now_epoch = chrono::Utc::now().timestamp();
 
     for (symbol, amount_usd, details_json, created_at) in &rows {
-        // Parse the created_at timestamp to epoch
-        let entry_epoch = chrono::NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S")
-            .or_else(|_| chrono::DateTime::parse_from_rfc3339(created_at).map(|dt| dt.naive_utc()))
-            .map(|dt| dt.and_utc().timestamp())
-            .unwrap_or(0);
+        // The query already limits rows to the last 24h, so an unreadable
+        // created_at is treated as just now (the conservative choice for limits)
+        let entry_epoch = match parse_db_timestamp(created_at) {
+            Some(at) => at.timestamp(),
+            None => {
+                warn!("DipBuyer: unreadable log timestamp '{}' for {}, assuming now", created_at, symbol);
+                now_epoch
+            }
+        };
 
         // Restore daily_buys (all entries are already within 24h from query)
-        if entry_epoch > 0 {
-            daily_buys.push((entry_epoch, *amount_usd));
-        }
+        daily_buys.push((entry_epoch, *amount_usd));
 
         // Restore coin_cooldowns — mark the coin with its buy timestamp
         // The main loop will prune expired ones using cooldown_per_coin_secs
         coin_cooldowns.entry(symbol.clone()).or_insert(entry_epoch);
 
-        // Reconstruct a seen_trade_key from the log details to prevent re-buying
-        // on the same triggering sell trade
+        // Reconstruct a seen_trade_key from the logged trade reason to prevent
+        // re-buying on the same triggering sell trade
         if let Ok(details) = serde_json::from_str::<serde_json::Value>(details_json) {
-            let seller = details.get("sellerUsername").and_then(|v| v.as_str()).unwrap_or("");
-            let sell_val = details.get("sellValueUsd").and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let (seller, sell_val) = match details.get("reason").and_then(TradeReason::from_value) {
+                Some(TradeReason::Dip { seller, sell_value_usd, .. }) => (seller, sell_value_usd),
+                // Entries written before reasons were logged
+                _ => (
+                    details.get("sellerUsername").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+                    details.get("sellValueUsd").and_then(|v| v.as_f64()).unwrap_or(0.0),
+                ),
+            };
             if !seller.is_empty() {
                 // We don't have the exact userId:symbol:timestamp:value key, so we
                 // mark the symbol itself as seen for trades near this timestamp.
                 // The last_tick_ts filter handles the primary dedup; this is a safety net.
                 let approx_key = format!("restored:{}:{}:{:.4}", symbol, seller, sell_val);
-                seen_trade_keys.insert(approx_key);
+                seen_trade_keys.insert(approx_key, entry_epoch);
             }
         }
     }
@@ -1244,3 +1586,149 @@ async fn restore_state_from_log(
         last_tick_ts,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_approval::SemiAutoConfig;
+    use crate::trade_executor::spawn_recording_executor;
+    use rugplay_core::CandlestickPoint;
+
+    /// A $2000 sell by user `seller` on a coin whose top holder owns 10%
+    fn dip_input(symbol: &str, seller: u32) -> DipDecisionInput {
+        let trade = serde_json::json!({
+            "type": "SELL", "username": "seller", "amount": 1000.0, "coinSymbol": symbol,
+            "coinName": symbol, "totalValue": 2_000.0, "price": 0.5,
+            "timestamp": 1_700_000_000, "userId": seller.to_string()
+        });
+        let coin = serde_json::json!({
+            "id": 7, "symbol": symbol, "name": symbol, "currentPrice": 0.5, "marketCap": 50_000.0,
+            "poolCoinAmount": 20_000.0, "poolBaseCurrencyAmount": 10_000.0,
+            "volume24h": 8_000.0, "change24h": -5.0
+        });
+        let holders = serde_json::json!({
+            "coinSymbol": symbol, "totalHolders": 80, "circulatingSupply": 1_000_000.0,
+            "poolInfo": {"coinAmount": 20_000.0, "baseCurrencyAmount": 10_000.0, "currentPrice": 0.5},
+            "holders": [
+                {"rank": 1, "userId": 1, "username": "a", "name": "a", "quantity": 1.0, "percentage": 10.0, "liquidationValue": 1.0},
+                {"rank": 2, "userId": 2, "username": "b", "name": "b", "quantity": 1.0, "percentage": 5.0, "liquidationValue": 1.0},
+                {"rank": 3, "userId": 3, "username": "c", "name": "c", "quantity": 1.0, "percentage": 4.0, "liquidationValue": 1.0}
+            ]
+        });
+        DipDecisionInput {
+            sell_trade: serde_json::from_value(trade).unwrap(),
+            coin: serde_json::from_value(coin).unwrap(),
+            candles: (0..6)
+                .map(|i| CandlestickPoint { time: i, open: 0.6, high: 0.62, low: 0.5, close: 0.55 - i as f64 * 0.01 })
+                .collect(),
+            holders: Some(serde_json::from_value(holders).unwrap()),
+            base_buy_amount: 50.0,
+            prior_dumps: 0,
+        }
+    }
+
+    fn dip_buy(input: &DipDecisionInput, analysis: DipAnalysis, buy_amount: f64) -> DipBuy {
+        let trade = &input.sell_trade;
+        DipBuy {
+            symbol: trade.coin_symbol.clone(),
+            coin_name: trade.coin_name.clone(),
+            buy_amount,
+            seller: trade.username.clone(),
+            sell_value: trade.total_value,
+            seller_rank: None,
+            market_cap: input.coin.market_cap.unwrap_or(0.0),
+            price: input.coin.current_price,
+            change_24h: input.coin.change_24h,
+            reason: dip_buy_reason(trade, &analysis, buy_amount),
+            analysis,
+            decided_at: trade.timestamp,
+        }
+    }
+
+    fn decision_config() -> DipDecisionConfig {
+        DipDecisionConfig {
+            signal_weights: SignalWeights::default(),
+            skip_top_n_holders: 2,
+            max_buy_slippage_pct: 10.0,
+            confidence_decay_per_dump: 0.2,
+            min_confidence_score: 0.3,
+            scale_by_confidence: false,
+            confidence_curve: ConfidenceCurve::default(),
+            without_holders_buy_scale: 0.5,
+            thin_history_confidence_penalty: 0.0,
+            atr_sizing: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dipbuyer_submits_buys_for_confirmed_dips_only() {
+        let config = decision_config();
+        // A retail sell, then the top holder dumping
+        let feed = [dip_input("AAA", 50), dip_input("BBB", 1)];
+
+        let (executor, recorder) = spawn_recording_executor();
+        let mut expected = Vec::new();
+        for input in &feed {
+            let (analysis, action) = evaluate_dip(&config, input);
+            if let DecisionAction::Buy { amount_usd, .. } = action {
+                let buy = dip_buy(input, analysis, amount_usd);
+                submit_dip_buy(&executor, &buy).await.unwrap();
+                expected.push(buy.reason);
+            }
+        }
+
+        let trades = recorder.take();
+        assert_eq!(trades.len(), 1, "{:?}", trades);
+        assert_eq!(trades[0].symbol, "AAA");
+        assert_eq!(trades[0].trade_type, TradeType::Buy);
+        assert_eq!(trades[0].amount, 50.0);
+        assert_eq!(trades[0].priority, TradePriority::Normal);
+        assert_eq!(trades[0].source, "dipbuyer");
+        assert_eq!(trades[0].reason, expected[0]);
+        assert!(matches!(&trades[0].reason, TradeReason::Dip { seller, buy_usd, .. } if seller == "seller" && *buy_usd == 50.0));
+        assert!(recorder.trades().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_held_dip_buy_counts_against_limits_without_blocking() {
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_semi_auto_config(SemiAutoConfig { enabled: true, ..Default::default() }).await;
+        let input = dip_input("AAA", 50);
+        let (analysis, _) = evaluate_dip(&decision_config(), &input);
+        let buy = dip_buy(&input, analysis, 50.0);
+
+        let submitted = tokio::time::timeout(std::time::Duration::from_secs(1), submit_dip_buy(&executor, &buy));
+        let Submission::Held(proposal) = submitted.await.unwrap().unwrap() else {
+            panic!("dip buy was not held");
+        };
+        let mut held = HeldOrders::default();
+        held.hold(proposal, buy);
+
+        // Still waiting: nothing sent, the coin isn't proposed twice and the
+        // daily cap already counts it
+        assert!(recorder.trades().is_empty());
+        assert!(held.is_held("AAA"));
+        let now = input.sell_trade.timestamp;
+        let committed = committed_buys(&[(now - 60, 25.0)], &held);
+        assert_eq!(committed.len(), 2);
+        assert!(!within_daily_limits(&committed, now, BudgetResetMode::default(), 2, 0.0, 50.0));
+        assert!(within_daily_limits(&committed, now, BudgetResetMode::default(), 3, 200.0, 50.0));
+
+        executor.reject_all_pending("test").await;
+        let settled = held.take_settled();
+        assert!(settled[0].1.is_err());
+        assert!(committed_buys(&[], &held).is_empty());
+    }
+
+    #[test]
+    fn test_buying_without_holder_data_is_opt_in() {
+        for preset in [Aggressiveness::Conservative, Aggressiveness::Moderate, Aggressiveness::Aggressive] {
+            assert!(!preset.to_preset().proceed_without_holders, "{:?}", preset);
+        }
+        // Configs saved before the setting existed don't turn it on
+        let mut saved = serde_json::to_value(DipBuyerConfig::default()).unwrap();
+        saved.as_object_mut().unwrap().remove("proceedWithoutHolders");
+        let config: DipBuyerConfig = serde_json::from_value(saved).unwrap();
+        assert!(!config.proceed_without_holders);
+    }
+}
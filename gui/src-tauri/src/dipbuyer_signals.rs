@@ -5,9 +5,11 @@
 //! multiplied by its weight. The composite score determines whether to buy
 //! and at what sizing.
 
+use crate::budget_window::BudgetResetMode;
 use rugplay_core::{CoinDetails, CandlestickPoint};
 use rugplay_core::{CoinHoldersResponse, RecentTrade};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 // ─── Signal Breakdown ────────────────────────────────────────────────
 
@@ -227,18 +229,57 @@ pub fn calc_holder_safety(
     })
 }
 
+/// Holder-safety score used when holder data couldn't be fetched
+pub const NEUTRAL_HOLDER_SCORE: f64 = 0.5;
+
+/// Default buy size multiplier for dips scored without holder data
+pub const DEFAULT_WITHOUT_HOLDERS_BUY_SCALE: f64 = 0.5;
+
+/// Stand-in holder-safety signal for a dip scored without holder data.
+/// Neither rewards nor punishes the coin, and never hard-rejects.
+pub fn neutral_holder_safety() -> SignalResult {
+    SignalResult {
+        name: "Holder Safety".into(),
+        raw_value: 0.0,
+        score: NEUTRAL_HOLDER_SCORE,
+        weight: 0.0,
+        weighted: 0.0,
+        reason: "Holder data unavailable (neutral)".into(),
+    }
+}
+
+/// Fewest candles momentum is read from; brand new coins often have less
+pub const MIN_MOMENTUM_CANDLES: usize = 3;
+
+/// Momentum score given when there isn't enough history to read a trend
+pub const NEUTRAL_MOMENTUM_SCORE: f64 = 0.5;
+
+/// Whether `candles` are enough for [`calc_momentum`] to read a trend
+pub fn has_momentum_history(candles: &[CandlestickPoint]) -> bool {
+    candles.len() >= MIN_MOMENTUM_CANDLES
+}
+
+/// Stand-in momentum signal for a coin with too little candle history.
+/// Neither rewards nor punishes the coin.
+pub fn neutral_momentum(candle_count: usize) -> SignalResult {
+    SignalResult {
+        name: "Momentum".into(),
+        raw_value: candle_count as f64,
+        score: NEUTRAL_MOMENTUM_SCORE,
+        weight: 0.0,
+        weighted: 0.0,
+        reason: format!(
+            "Insufficient candle data ({} of {} needed, neutral)",
+            candle_count, MIN_MOMENTUM_CANDLES
+        ),
+    }
+}
+
 /// Analyze candlestick data for short-term momentum.
 /// Looks for exhaustion signals (oversold bounce potential) vs ongoing crash.
 pub fn calc_momentum(candles: &[CandlestickPoint], current_price: f64) -> SignalResult {
-    if candles.len() < 3 {
-        return SignalResult {
-            name: "Momentum".into(),
-            raw_value: 0.0,
-            score: 0.5, // Neutral when no data
-            weight: 0.0,
-            weighted: 0.0,
-            reason: "Insufficient candle data".into(),
-        };
+    if !has_momentum_history(candles) {
+        return neutral_momentum(candles.len());
     }
 
     // Use the last N candles (up to 20)
@@ -366,29 +407,52 @@ pub fn calc_volume_quality(volume_24h: f64, pool_base: f64, market_cap: f64) ->
 // ─── Composite Score ─────────────────────────────────────────────────
 
 /// Run the full analysis pipeline on a dip candidate.
+/// Without `holders` the holder-safety signal is neutral and the pool
+/// depth comes from the coin details instead.
 pub fn analyze_dip(
     symbol: &str,
     sell_trade: &RecentTrade,
     coin: &CoinDetails,
     chart_data: &[CandlestickPoint],
-    holders: &CoinHoldersResponse,
+    holders: Option<&CoinHoldersResponse>,
     buy_amount_usd: f64,
     weights: &SignalWeights,
     skip_top_n: u32,
     max_slippage_pct: f64,
 ) -> DipAnalysis {
-    let pool_base = holders.pool_info.base_currency_amount;
+    let pool_base = holders.map_or(coin.pool_base_currency_amount, |h| h.pool_info.base_currency_amount);
+
+    // NaN slips through every `>` guard below (slippage included), so bad
+    // feed data is rejected up front instead of being scored. A missing
+    // market cap is just as unusable.
+    let market_cap = coin.market_cap.unwrap_or(f64::NAN);
+    let inputs = [sell_trade.total_value, pool_base, market_cap, coin.volume_24h, buy_amount_usd];
+    if !coin.has_valid_price() || inputs.iter().any(|v| !v.is_finite()) {
+        return DipAnalysis {
+            symbol: symbol.to_string(),
+            confidence_score: 0.0,
+            signals: Vec::new(),
+            hard_reject: true,
+            reject_reason: Some("Invalid price or pool data".to_string()),
+            recommended_buy_pct: 0.0,
+            slippage_pct: 0.0,
+            sell_impact_pct: 0.0,
+        };
+    }
 
     // Signal 1: Sell impact analysis
     let (sell_impact_pct, mut s_impact) = calc_sell_impact(sell_trade.total_value, pool_base);
     s_impact.weight = weights.sell_impact;
 
     // Signal 2: Holder safety
-    let seller_id_u32: Option<u32> = sell_trade.user_id.parse().ok();
-    let creator_id = coin.creator_id.as_deref();
-    let (hard_reject, reject_reason, mut s_holders) = calc_holder_safety(
-        holders, seller_id_u32, creator_id, skip_top_n,
-    );
+    let (hard_reject, reject_reason, mut s_holders) = match holders {
+        Some(holders) => {
+            let seller_id_u32: Option<u32> = sell_trade.user_id.parse().ok();
+            let creator_id = coin.creator_id.as_deref();
+            calc_holder_safety(holders, seller_id_u32, creator_id, skip_top_n)
+        }
+        None => (false, None, neutral_holder_safety()),
+    };
 
     if hard_reject {
         s_holders.weight = weights.holder_safety;
@@ -410,7 +474,7 @@ pub fn analyze_dip(
     s_momentum.weight = weights.momentum;
 
     // Signal 4: Volume quality
-    let mut s_volume = calc_volume_quality(coin.volume_24h, pool_base, coin.market_cap);
+    let mut s_volume = calc_volume_quality(coin.volume_24h, pool_base, market_cap);
     s_volume.weight = weights.volume_quality;
 
     // Calculate slippage for our buy
@@ -445,15 +509,7 @@ pub fn analyze_dip(
         0.0
     };
 
-    // Buy sizing: scale amount based on confidence
-    // >= 0.75 → 100%, 0.55–0.75 → 50–100% linear, < 0.55 → 0% (no buy)
-    let recommended_buy_pct = if composite >= 0.75 {
-        1.0
-    } else if composite >= 0.55 {
-        0.5 + (composite - 0.55) / 0.20 * 0.5
-    } else {
-        0.0
-    };
+    let recommended_buy_pct = buy_pct_for_confidence(composite);
 
     DipAnalysis {
         symbol: symbol.to_string(),
@@ -466,3 +522,402 @@ pub fn analyze_dip(
         sell_impact_pct,
     }
 }
+
+/// Buy sizing: scale amount based on confidence.
+/// >= 0.75 → 100%, 0.55–0.75 → 50–100% linear, < 0.55 → 0% (no buy)
+pub fn buy_pct_for_confidence(confidence: f64) -> f64 {
+    if confidence >= 0.75 {
+        1.0
+    } else if confidence >= 0.55 {
+        0.5 + (confidence - 0.55) / 0.20 * 0.5
+    } else {
+        0.0
+    }
+}
+
+/// Sizing curve where bigger buys need stronger signals. A dip at exactly
+/// the minimum confidence buys `min_size_scale` of the tier amount; the
+/// full amount takes `full_size_confidence`. An `exponent` above 1 keeps
+/// sizes small until confidence is well clear of the minimum.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfidenceCurve {
+    pub enabled: bool,
+    pub min_size_scale: f64,
+    pub full_size_confidence: f64,
+    pub exponent: f64,
+}
+
+impl Default for ConfidenceCurve {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_size_scale: 0.2,
+            full_size_confidence: 0.85,
+            exponent: 2.0,
+        }
+    }
+}
+
+impl ConfidenceCurve {
+    /// Fraction of the tier amount to buy at `confidence`, given the
+    /// minimum confidence that allows a buy at all
+    pub fn buy_pct(&self, confidence: f64, min_confidence: f64) -> f64 {
+        if !confidence.is_finite() || confidence < min_confidence {
+            return 0.0;
+        }
+        if confidence >= self.full_size_confidence || self.full_size_confidence <= min_confidence {
+            return 1.0;
+        }
+        let progress = (confidence - min_confidence) / (self.full_size_confidence - min_confidence);
+        let floor = self.min_size_scale.clamp(0.0, 1.0);
+        floor + (1.0 - floor) * progress.powf(self.exponent.max(0.1))
+    }
+}
+
+// ─── Volatility Sizing ───────────────────────────────────────────────
+
+/// Fewest candles an ATR is read from
+pub const MIN_ATR_CANDLES: usize = 5;
+
+/// Candles (true ranges) averaged into the ATR
+pub const ATR_PERIOD: usize = 14;
+
+/// Average true range over the last [`ATR_PERIOD`] candles, as a % of the
+/// latest close. `None` with fewer than [`MIN_ATR_CANDLES`] candles or
+/// unusable prices.
+pub fn atr_pct(candles: &[CandlestickPoint]) -> Option<f64> {
+    if candles.len() < MIN_ATR_CANDLES {
+        return None;
+    }
+    let recent = &candles[candles.len().saturating_sub(ATR_PERIOD + 1)..];
+    let ranges: Vec<f64> = recent
+        .windows(2)
+        .map(|pair| {
+            let (prev, c) = (&pair[0], &pair[1]);
+            (c.high - c.low).max((c.high - prev.close).abs()).max((c.low - prev.close).abs())
+        })
+        .collect();
+    let atr = ranges.iter().sum::<f64>() / ranges.len() as f64;
+    let last_close = recent.last()?.close;
+    let pct = atr / last_close * 100.0;
+    (last_close > 0.0 && pct.is_finite()).then_some(pct)
+}
+
+/// Volatility-scaled buy sizing: a coin whose ATR is `target_atr_pct` buys
+/// the normal amount, calmer coins more and choppier coins less, in
+/// proportion, within `min_scale`..`max_scale`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtrSizing {
+    pub target_atr_pct: f64,
+    pub min_scale: f64,
+    pub max_scale: f64,
+}
+
+impl Default for AtrSizing {
+    fn default() -> Self {
+        Self {
+            target_atr_pct: 10.0,
+            min_scale: 0.25,
+            max_scale: 1.5,
+        }
+    }
+}
+
+impl AtrSizing {
+    /// Buy amount multiplier and the ATR % it came from, or `None` when
+    /// the candles can't give an ATR and the amount should stay as it is
+    pub fn scale(&self, candles: &[CandlestickPoint]) -> Option<(f64, f64)> {
+        let atr = atr_pct(candles)?;
+        let (low, high) = (self.min_scale.max(0.0), self.max_scale.max(self.min_scale.max(0.0)));
+        let scale = if atr > 0.0 { self.target_atr_pct / atr } else { high };
+        Some((scale.clamp(low, high), atr))
+    }
+}
+
+// ─── Repeated Dump Decay ─────────────────────────────────────────────
+
+/// Confidence multiplier for a coin that has already dumped `recent_dumps`
+/// times inside the decay window. Each prior dump compounds the penalty,
+/// so a coin in a steady downtrend stops looking like a fresh dip.
+pub fn repeat_dump_multiplier(recent_dumps: u32, decay_per_dump: f64) -> f64 {
+    if recent_dumps == 0 || decay_per_dump <= 0.0 {
+        return 1.0;
+    }
+    (1.0 - decay_per_dump.min(1.0)).powi(recent_dumps as i32)
+}
+
+/// Apply the repeated-dump decay to a finished analysis, re-deriving the
+/// buy sizing from the decayed confidence. Hard rejects are left untouched.
+pub fn apply_repeat_dump_decay(analysis: &mut DipAnalysis, recent_dumps: u32, decay_per_dump: f64) {
+    if analysis.hard_reject {
+        return;
+    }
+    let multiplier = repeat_dump_multiplier(recent_dumps, decay_per_dump);
+    if multiplier >= 1.0 {
+        return;
+    }
+    analysis.confidence_score *= multiplier;
+    analysis.recommended_buy_pct = buy_pct_for_confidence(analysis.confidence_score);
+    analysis.signals.push(SignalResult {
+        name: "Repeat Dumps".into(),
+        raw_value: recent_dumps as f64,
+        score: multiplier,
+        weight: 0.0, // applied as a multiplier, not a weighted term
+        weighted: 0.0,
+        reason: format!("{} recent dumps, confidence ×{:.2}", recent_dumps, multiplier),
+    });
+}
+
+/// Lower confidence for a coin whose momentum was scored neutral for lack
+/// of candles, re-deriving the buy sizing. `penalty` is the fraction of
+/// confidence taken off (0 = disabled). Hard rejects are left untouched.
+pub fn apply_thin_history_penalty(analysis: &mut DipAnalysis, candle_count: usize, penalty: f64) {
+    if analysis.hard_reject || penalty <= 0.0 {
+        return;
+    }
+    let multiplier = 1.0 - penalty.min(1.0);
+    analysis.confidence_score *= multiplier;
+    analysis.recommended_buy_pct = buy_pct_for_confidence(analysis.confidence_score);
+    analysis.signals.push(SignalResult {
+        name: "Thin History".into(),
+        raw_value: candle_count as f64,
+        score: multiplier,
+        weight: 0.0, // applied as a multiplier, not a weighted term
+        weighted: 0.0,
+        reason: format!("{} candles, confidence ×{:.2}", candle_count, multiplier),
+    });
+}
+
+// ─── Cooldowns & Daily Limits ────────────────────────────────────────
+
+/// Whether `symbol` is on the cooldown-exempt list (case-insensitive)
+pub fn is_cooldown_exempt(exempt_coins: &[String], symbol: &str) -> bool {
+    exempt_coins.iter().any(|c| c.eq_ignore_ascii_case(symbol))
+}
+
+/// Whether a coin is still cooling down after a buy. Exempt coins never are.
+pub fn in_coin_cooldown(cooldowns: &HashMap<String, i64>, exempt_coins: &[String], symbol: &str) -> bool {
+    !is_cooldown_exempt(exempt_coins, symbol) && cooldowns.contains_key(symbol)
+}
+
+/// Max buy slippage % for a coin: its per-coin override when set (> 0),
+/// else the tier value when set (> 0), else the global cap
+pub fn resolve_max_slippage(
+    overrides: &HashMap<String, f64>,
+    symbol: &str,
+    tier_pct: Option<f64>,
+    global_pct: f64,
+) -> f64 {
+    overrides
+        .iter()
+        .find(|(coin, pct)| coin.eq_ignore_ascii_case(symbol) && **pct > 0.0)
+        .map(|(_, pct)| *pct)
+        .or(tier_pct.filter(|pct| *pct > 0.0))
+        .unwrap_or(global_pct)
+}
+
+/// Whether one more buy of `amount` fits the daily caps of the budget window.
+/// `daily_buys` holds (timestamp, usd) per buy; a spend cap of 0 is unlimited.
+pub fn within_daily_limits(
+    daily_buys: &[(i64, f64)],
+    now: i64,
+    reset: BudgetResetMode,
+    max_daily_buys: u32,
+    max_daily_spend_usd: f64,
+    amount: f64,
+) -> bool {
+    let recent = daily_buys.iter().filter(|(ts, _)| reset.counts(*ts, now));
+    let (count, spent) = recent.fold((0u32, 0.0), |(n, sum), (_, usd)| (n + 1, sum + usd));
+    count < max_daily_buys && (max_daily_spend_usd <= 0.0 || spent + amount <= max_daily_spend_usd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analysis_with_confidence(confidence: f64) -> DipAnalysis {
+        DipAnalysis {
+            symbol: "TEST".into(),
+            confidence_score: confidence,
+            signals: Vec::new(),
+            hard_reject: false,
+            reject_reason: None,
+            recommended_buy_pct: buy_pct_for_confidence(confidence),
+            slippage_pct: 0.0,
+            sell_impact_pct: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_repeat_dumps_reduce_confidence() {
+        let mut first = analysis_with_confidence(0.80);
+        apply_repeat_dump_decay(&mut first, 0, 0.15);
+        assert_eq!(first.confidence_score, 0.80);
+        assert_eq!(first.recommended_buy_pct, 1.0);
+
+        let mut second = analysis_with_confidence(0.80);
+        apply_repeat_dump_decay(&mut second, 1, 0.15);
+        let mut fourth = analysis_with_confidence(0.80);
+        apply_repeat_dump_decay(&mut fourth, 3, 0.15);
+
+        assert!(second.confidence_score < first.confidence_score);
+        assert!(fourth.confidence_score < second.confidence_score);
+        assert!((second.confidence_score - 0.68).abs() < 1e-9);
+        assert!(second.recommended_buy_pct < 1.0);
+        assert_eq!(fourth.recommended_buy_pct, 0.0);
+    }
+
+    #[test]
+    fn test_repeat_dump_decay_disabled() {
+        assert_eq!(repeat_dump_multiplier(5, 0.0), 1.0);
+        let mut rejected = analysis_with_confidence(0.0);
+        rejected.hard_reject = true;
+        apply_repeat_dump_decay(&mut rejected, 5, 0.5);
+        assert!(rejected.signals.is_empty());
+    }
+
+    #[test]
+    fn test_nan_pool_or_price_is_hard_rejected() {
+        let trade: RecentTrade = serde_json::from_value(serde_json::json!({
+            "type": "SELL", "username": "w", "amount": 100.0, "coinSymbol": "DIP",
+            "coinName": "Dip", "totalValue": 500.0, "price": 5.0, "timestamp": 0, "userId": "7"
+        }))
+        .unwrap();
+        let mut coin: CoinDetails = serde_json::from_value(serde_json::json!({
+            "id": 1, "symbol": "DIP", "name": "Dip", "currentPrice": 5.0,
+            "marketCap": 50000.0, "poolCoinAmount": 1000.0, "poolBaseCurrencyAmount": 5000.0
+        }))
+        .unwrap();
+        let mut holders: CoinHoldersResponse = serde_json::from_value(serde_json::json!({
+            "coinSymbol": "DIP", "totalHolders": 0, "circulatingSupply": 10000.0,
+            "poolInfo": { "coinAmount": 1000.0, "baseCurrencyAmount": 0.0, "currentPrice": 5.0 },
+            "holders": []
+        }))
+        .unwrap();
+        holders.pool_info.base_currency_amount = f64::NAN;
+
+        let weights = SignalWeights::default();
+        let analysis = analyze_dip("DIP", &trade, &coin, &[], Some(&holders), 50.0, &weights, 0, 5.0);
+        assert!(analysis.hard_reject);
+        assert_eq!(analysis.recommended_buy_pct, 0.0);
+
+        holders.pool_info.base_currency_amount = 5000.0;
+        coin.current_price = f64::NAN;
+        let analysis = analyze_dip("DIP", &trade, &coin, &[], Some(&holders), 50.0, &weights, 0, 5.0);
+        assert!(analysis.hard_reject);
+        assert_eq!(analysis.reject_reason.as_deref(), Some("Invalid price or pool data"));
+    }
+
+    #[test]
+    fn test_missing_holders_score_neutral_from_coin_pool() {
+        let trade: RecentTrade = serde_json::from_value(serde_json::json!({
+            "type": "SELL", "username": "w", "amount": 100.0, "coinSymbol": "DIP",
+            "coinName": "Dip", "totalValue": 500.0, "price": 5.0, "timestamp": 0, "userId": "7"
+        }))
+        .unwrap();
+        let coin: CoinDetails = serde_json::from_value(serde_json::json!({
+            "id": 1, "symbol": "DIP", "name": "Dip", "currentPrice": 5.0, "volume24h": 4000.0,
+            "marketCap": 50000.0, "poolCoinAmount": 1000.0, "poolBaseCurrencyAmount": 5000.0
+        }))
+        .unwrap();
+        let weights = SignalWeights::default();
+
+        let analysis = analyze_dip("DIP", &trade, &coin, &[], None, 50.0, &weights, 2, 5.0);
+        assert!(!analysis.hard_reject);
+        assert_eq!(analysis.signals.len(), 4);
+        let holders = &analysis.signals[1];
+        assert_eq!(holders.score, NEUTRAL_HOLDER_SCORE);
+        assert_eq!(holders.weighted, NEUTRAL_HOLDER_SCORE * weights.holder_safety);
+        // Pool depth falls back to the coin's own pool figures
+        assert_eq!(analysis.slippage_pct, calc_buy_slippage(50.0, 5000.0));
+        assert_eq!(analysis.sell_impact_pct, calc_sell_impact(500.0, 5000.0).0);
+    }
+
+    #[test]
+    fn test_exempt_coins_ignore_cooldown_but_others_honor_it() {
+        let exempt = vec!["CONV".to_string()];
+        let mut cooldowns = HashMap::new();
+        cooldowns.insert("CONV".to_string(), 1_000);
+        cooldowns.insert("OTHER".to_string(), 1_000);
+
+        assert!(!in_coin_cooldown(&cooldowns, &exempt, "CONV"));
+        assert!(in_coin_cooldown(&cooldowns, &exempt, "OTHER"));
+        assert!(!in_coin_cooldown(&cooldowns, &exempt, "FRESH"));
+        assert!(is_cooldown_exempt(&exempt, "conv"));
+    }
+
+    #[test]
+    fn test_coin_slippage_override_beats_tier_and_global() {
+        let overrides = HashMap::from([("THIN".to_string(), 12.0), ("OFF".to_string(), 0.0)]);
+
+        assert_eq!(resolve_max_slippage(&overrides, "THIN", None, 5.0), 12.0);
+        assert_eq!(resolve_max_slippage(&overrides, "thin", Some(3.0), 5.0), 12.0);
+        // Other coins keep the tier value, then the global cap
+        assert_eq!(resolve_max_slippage(&overrides, "OTHER", Some(3.0), 5.0), 3.0);
+        assert_eq!(resolve_max_slippage(&overrides, "OTHER", Some(0.0), 5.0), 5.0);
+        // A zero override is unset
+        assert_eq!(resolve_max_slippage(&overrides, "OFF", None, 5.0), 5.0);
+    }
+
+    #[test]
+    fn test_daily_limits_still_apply_to_exempt_coins() {
+        let now = 100_000;
+        // Three buys of an exempt coin in quick succession
+        let buys = vec![(now - 60, 100.0), (now - 30, 100.0), (now - 10, 100.0)];
+
+        assert!(within_daily_limits(&buys, now, BudgetResetMode::Rolling24h, 4, 500.0, 100.0));
+        // Count cap
+        assert!(!within_daily_limits(&buys, now, BudgetResetMode::Rolling24h, 3, 0.0, 100.0));
+        // Spend cap
+        assert!(!within_daily_limits(&buys, now, BudgetResetMode::Rolling24h, 10, 350.0, 100.0));
+        // Buys older than 24h drop out of the window
+        let old = vec![(now - 90_000, 1_000.0)];
+        assert!(within_daily_limits(&old, now, BudgetResetMode::Rolling24h, 1, 500.0, 100.0));
+    }
+
+    #[test]
+    fn test_atr_sizing_favors_calm_coins_and_needs_enough_candles() {
+        // Candles swinging `range` around a close of 1.0
+        let candles = |n: usize, range: f64| -> Vec<CandlestickPoint> {
+            (0..n)
+                .map(|i| CandlestickPoint { time: i as i64, open: 1.0, high: 1.0 + range / 2.0, low: 1.0 - range / 2.0, close: 1.0 })
+                .collect()
+        };
+        let sizing = AtrSizing::default();
+
+        let (calm, calm_atr) = sizing.scale(&candles(20, 0.08)).unwrap();
+        assert!((calm_atr - 8.0).abs() < 1e-9);
+        assert!((calm - 1.25).abs() < 1e-9);
+        let (choppy, _) = sizing.scale(&candles(20, 0.20)).unwrap();
+        assert!((choppy - 0.5).abs() < 1e-9);
+        // Clamped at both ends
+        assert_eq!(sizing.scale(&candles(20, 0.01)).unwrap().0, 1.5);
+        assert_eq!(sizing.scale(&candles(20, 0.90)).unwrap().0, 0.25);
+
+        // Too little history: the caller keeps its normal size
+        assert!(sizing.scale(&candles(MIN_ATR_CANDLES - 1, 0.08)).is_none());
+        assert!(sizing.scale(&[]).is_none());
+        assert!(atr_pct(&candles(10, f64::NAN)).is_none());
+    }
+
+    #[test]
+    fn test_curve_sizes_borderline_small_and_strong_at_full_tier() {
+        let curve = ConfidenceCurve { enabled: true, ..Default::default() };
+        let tier_amount = 500.0;
+        let min_confidence = 0.55;
+
+        let borderline = tier_amount * curve.buy_pct(0.56, min_confidence);
+        assert!(borderline > 0.0 && borderline < 110.0, "borderline bought ${:.2}", borderline);
+        assert_eq!(tier_amount * curve.buy_pct(0.90, min_confidence), tier_amount);
+
+        // Sizes climb with confidence, and stay below the linear scale in between
+        let mid = curve.buy_pct(0.70, min_confidence);
+        assert!(mid > curve.buy_pct(0.56, min_confidence) && mid < curve.buy_pct(0.80, min_confidence));
+        assert!(mid < buy_pct_for_confidence(0.70));
+
+        assert_eq!(curve.buy_pct(0.50, min_confidence), 0.0);
+        assert_eq!(curve.buy_pct(f64::NAN, min_confidence), 0.0);
+    }
+}
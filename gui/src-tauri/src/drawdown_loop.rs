@@ -0,0 +1,143 @@
+//! Drawdown Loop — samples portfolio value for the drawdown circuit breaker
+//!
+//! While the `maxDrawdownPct` risk limit is set, every tick records the
+//! active profile's total portfolio value in the trade executor's
+//! `DrawdownGuard`, which refuses buys once the value is that far below
+//! today's peak. The peak is persisted (settings key `drawdown_guard`)
+//! whenever it moves, so a restart later the same day doesn't reset it; a
+//! new day or a different profile starts from the next sample.
+
+use crate::notifications::{NotificationCategory, NotificationHandle};
+use crate::trade_executor::{active_client, TradeExecutorHandle};
+use crate::AppState;
+use rugplay_engine::risk::DrawdownGuard;
+use rugplay_persistence::sqlite;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// How often portfolio value is sampled
+const TICK_SECS: u64 = 30;
+
+/// The persisted session peak and the profile it was measured on
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DrawdownSnapshot {
+    profile_id: i64,
+    guard: DrawdownGuard,
+}
+
+/// The trading day a sample belongs to
+fn trading_day() -> String {
+    chrono::Utc::now().format("%Y-%m-%d").to_string()
+}
+
+async fn load_snapshot(app_handle: &tauri::AppHandle) -> Option<DrawdownSnapshot> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>("SELECT value FROM settings WHERE key = 'drawdown_guard'")
+        .fetch_optional(db.pool())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+}
+
+async fn save_snapshot(app_handle: &tauri::AppHandle, snapshot: &DrawdownSnapshot) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let Ok(json) = serde_json::to_string(snapshot) else { return };
+
+    let _ = sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('drawdown_guard', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(json)
+    .execute(db.pool())
+    .await;
+}
+
+/// One sample for the active profile. `profile_seen` is the profile the
+/// executor's guard currently belongs to.
+async fn sample_drawdown(
+    app_handle: &tauri::AppHandle,
+    executor: &TradeExecutorHandle,
+    profile_seen: &mut Option<i64>,
+) -> Result<(), String> {
+    if executor.get_risk_limits().await.max_drawdown_pct <= 0.0 {
+        return Ok(());
+    }
+
+    let profile_id = {
+        let state = app_handle.state::<AppState>();
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        sqlite::get_active_profile(db.pool())
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("No active profile")?
+            .id
+    };
+    let day = trading_day();
+
+    // Pick up today's persisted peak for this profile, or start over
+    if *profile_seen != Some(profile_id) {
+        let guard = match load_snapshot(app_handle).await {
+            Some(snapshot) if snapshot.profile_id == profile_id && snapshot.guard.day == day => snapshot.guard,
+            _ => DrawdownGuard::default(),
+        };
+        executor.restore_drawdown(guard).await;
+        *profile_seen = Some(profile_id);
+    }
+
+    let client = active_client(app_handle).await?;
+    let portfolio = client.get_portfolio().await.map_err(|e| e.to_string())?;
+
+    let was_tripped = executor.drawdown_status().await.tripped;
+    let peak_moved = executor.record_portfolio_value(portfolio.total_value, &day).await;
+    let status = executor.drawdown_status().await;
+    if peak_moved {
+        save_snapshot(app_handle, &DrawdownSnapshot { profile_id, guard: status.guard.clone() }).await;
+    }
+
+    if status.tripped && !was_tripped {
+        warn!(
+            "Drawdown circuit breaker tripped: ${:.2} is {:.1}% below today's peak of ${:.2}, buys paused",
+            status.guard.current_value, status.drawdown_pct, status.guard.peak_value
+        );
+        if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+            notif
+                .notify_dedup(
+                    NotificationCategory::Failure,
+                    "Drawdown Limit Hit",
+                    &format!(
+                        "Portfolio is {:.1}% below today's peak (limit {:.1}%). Buys are paused, sells continue.",
+                        status.drawdown_pct, status.threshold_pct
+                    ),
+                )
+                .await;
+        }
+    } else if was_tripped && !status.tripped {
+        info!("Drawdown circuit breaker reset ({:.1}% below peak), buys resumed", status.drawdown_pct);
+    }
+
+    Ok(())
+}
+
+/// Spawn the background portfolio sampler for the drawdown breaker
+pub fn spawn_drawdown_task(app_handle: tauri::AppHandle, executor: TradeExecutorHandle) {
+    tokio::spawn(async move {
+        let mut profile_seen = None;
+        // Sample straight away so a breaker tripped before a restart holds
+        loop {
+            if let Err(e) = sample_drawdown(&app_handle, &executor, &mut profile_seen).await {
+                debug!("Drawdown: sample skipped: {}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
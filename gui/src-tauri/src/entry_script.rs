@@ -0,0 +1,224 @@
+//! Entry Scripts — a user-written entry gate for the sniper and dip buyer
+//!
+//! Power users can add their own accept/reject logic as a small Rhai script
+//! (settings key `entry_script`) instead of forking. It runs once a coin has
+//! passed a module's own filters, right before the buy, with these inputs:
+//!
+//! - `source`: the module asking, `"sniper"` or `"dipbuyer"`
+//! - `coin`: the coin as the API returns it (`coin.marketCap`, `coin.volume24h`, ...)
+//! - `holders`: the coin's holders response, or `()` when it couldn't be fetched
+//! - `signals`: dip buyer signal scores (0–1) by name, empty for the sniper
+//!
+//! The script's value must be a bool, `false` vetoes the buy. Rhai has no
+//! file, network or process access and printing is discarded; each run is
+//! also cut off after `timeout_ms`. A script that fails, times out or
+//! returns something else vetoes the buy as well: a broken filter shouldn't
+//! let through what it was written to keep out.
+
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+use rugplay_core::CoinHoldersResponse;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Longest allowed `timeout_ms`; scripts run inside the module's tick
+pub const MAX_TIMEOUT_MS: u64 = 1_000;
+
+/// Operations after which a run is stopped, whatever the clock says
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+fn default_timeout_ms() -> u64 { 50 }
+
+/// The entry script (settings key `entry_script`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryScriptConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub script: String,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl Default for EntryScriptConfig {
+    fn default() -> Self {
+        Self { enabled: false, script: String::new(), timeout_ms: default_timeout_ms() }
+    }
+}
+
+/// A compiled entry script, ready to check candidates
+#[derive(Debug, Clone)]
+pub struct EntryScript {
+    ast: AST,
+    timeout: Duration,
+}
+
+/// A locked-down engine: no `eval`, no output, bounded size and depth
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.disable_symbol("eval");
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10_000);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine
+}
+
+impl EntryScript {
+    /// Compile the configured script; `None` when it is off or empty
+    pub fn compile(config: &EntryScriptConfig) -> Result<Option<Self>, String> {
+        if config.timeout_ms == 0 || config.timeout_ms > MAX_TIMEOUT_MS {
+            return Err(format!("Script timeout must be between 1 and {} ms", MAX_TIMEOUT_MS));
+        }
+        if !config.enabled || config.script.trim().is_empty() {
+            return Ok(None);
+        }
+        let ast = sandboxed_engine()
+            .compile(&config.script)
+            .map_err(|e| format!("Entry script does not compile: {}", e))?;
+        Ok(Some(Self { ast, timeout: Duration::from_millis(config.timeout_ms) }))
+    }
+
+    /// Run the script for one candidate. `Err` says why the buy is vetoed.
+    pub fn check(
+        &self,
+        source: &str,
+        coin: &impl Serialize,
+        holders: Option<&CoinHoldersResponse>,
+        signals: &[(String, f64)],
+    ) -> Result<(), String> {
+        self.run(ScriptInputs::new(source, coin, holders, signals)?)
+    }
+
+    /// [`Self::check`] on the blocking pool: a script may spin until its
+    /// timeout, which mustn't stall the runtime the modules tick on
+    pub async fn check_blocking(
+        self: Arc<Self>,
+        source: &str,
+        coin: &impl Serialize,
+        holders: Option<&CoinHoldersResponse>,
+        signals: &[(String, f64)],
+    ) -> Result<(), String> {
+        let inputs = ScriptInputs::new(source, coin, holders, signals)?;
+        tokio::task::spawn_blocking(move || self.run(inputs))
+            .await
+            .map_err(|e| format!("entry script failed: {}", e))?
+    }
+
+    fn run(&self, inputs: ScriptInputs) -> Result<(), String> {
+        let mut scope = Scope::new();
+        scope.push_constant("source", inputs.source);
+        scope.push_constant("coin", inputs.coin);
+        scope.push_constant("holders", inputs.holders);
+        scope.push_constant("signals", inputs.signals);
+
+        let mut engine = sandboxed_engine();
+        let (started, timeout) = (Instant::now(), self.timeout);
+        engine.on_progress(move |_| (started.elapsed() > timeout).then_some(Dynamic::UNIT));
+
+        match engine.eval_ast_with_scope::<Dynamic>(&mut scope, &self.ast) {
+            Ok(value) => match value.as_bool() {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("rejected by entry script".to_string()),
+                Err(kind) => Err(format!("entry script returned {} instead of a bool", kind)),
+            },
+            Err(e) if matches!(*e, rhai::EvalAltResult::ErrorTerminated(..)) => {
+                Err(format!("entry script timed out after {} ms", timeout.as_millis()))
+            }
+            Err(e) => Err(format!("entry script failed: {}", e)),
+        }
+    }
+}
+
+/// A candidate converted to script values, owned so a run can move threads
+struct ScriptInputs {
+    source: String,
+    coin: Dynamic,
+    holders: Dynamic,
+    signals: Map,
+}
+
+impl ScriptInputs {
+    fn new(
+        source: &str,
+        coin: &impl Serialize,
+        holders: Option<&CoinHoldersResponse>,
+        signals: &[(String, f64)],
+    ) -> Result<Self, String> {
+        Ok(Self {
+            source: source.to_string(),
+            coin: to_dynamic(coin)?,
+            holders: holders.map_or(Ok(Dynamic::UNIT), to_dynamic)?,
+            signals: signals.iter().map(|(name, score)| (name.as_str().into(), Dynamic::from(*score))).collect(),
+        })
+    }
+}
+
+fn to_dynamic(value: &impl Serialize) -> Result<Dynamic, String> {
+    rhai::serde::to_dynamic(value).map_err(|e| format!("entry script input: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_core::MarketCoin;
+
+    fn coin(market_cap: f64) -> MarketCoin {
+        serde_json::from_value(serde_json::json!({
+            "symbol": "MOON",
+            "name": "Moon",
+            "currentPrice": 0.5,
+            "marketCap": market_cap,
+            "volume24h": 20_000.0,
+        }))
+        .unwrap()
+    }
+
+    fn script(source: &str) -> EntryScript {
+        let config = EntryScriptConfig { enabled: true, script: source.to_string(), timeout_ms: 50 };
+        EntryScript::compile(&config).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_script_returning_false_vetoes_a_qualifying_trade() {
+        let filter = script(r#"source != "sniper" || coin.marketCap < 50000.0"#);
+        assert!(filter.check("sniper", &coin(10_000.0), None, &[]).is_ok());
+        let veto = filter.check("sniper", &coin(80_000.0), None, &[]).unwrap_err();
+        assert_eq!(veto, "rejected by entry script");
+
+        // Dip buyer signals and missing holders are visible to the script
+        let filter = script(r#"holders == () && signals.momentum >= 0.5"#);
+        assert!(filter.check("dipbuyer", &coin(10_000.0), None, &[("momentum".to_string(), 0.7)]).is_ok());
+        assert!(filter.check("dipbuyer", &coin(10_000.0), None, &[("momentum".to_string(), 0.2)]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scripts_run_off_the_async_runtime() {
+        let filter = Arc::new(script(r#"coin.marketCap < 50000.0"#));
+        assert!(filter.clone().check_blocking("sniper", &coin(10_000.0), None, &[]).await.is_ok());
+        let runaway = Arc::new(script("loop {}"));
+        let veto = runaway.check_blocking("sniper", &coin(10_000.0), None, &[]).await.unwrap_err();
+        assert!(veto.contains("timed out"), "{}", veto);
+    }
+
+    #[test]
+    fn test_broken_or_runaway_scripts_veto_and_bad_ones_are_refused() {
+        let runaway = script("loop {}");
+        let veto = runaway.check("sniper", &coin(10_000.0), None, &[]).unwrap_err();
+        assert!(veto.contains("timed out"), "{}", veto);
+
+        let not_bool = script("42");
+        assert!(not_bool.check("sniper", &coin(10_000.0), None, &[]).unwrap_err().contains("instead of a bool"));
+        assert!(script("coin.nope.deeper").check("sniper", &coin(10_000.0), None, &[]).is_err());
+
+        let bad = EntryScriptConfig { enabled: true, script: "if (".to_string(), timeout_ms: 50 };
+        assert!(EntryScript::compile(&bad).is_err());
+        assert!(EntryScript::compile(&EntryScriptConfig { script: "if (".to_string(), ..EntryScriptConfig::default() })
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,109 @@
+//! Equity Alert Loop — samples total account value and checks equity alerts
+//!
+//! While equity alerts are enabled, every tick fetches the active profile's
+//! portfolio, appends its total value to `equity_snapshots` and evaluates
+//! the configured alerts over the stored series with
+//! `equity_alerts::EquityAlertState`. Nothing is sampled while they are off.
+
+use crate::app_event::{emit_event, AppEvent};
+use crate::equity_alerts::{EquityAlert, EquityAlertConfig, EquityAlertState};
+use crate::notifications::{NotificationCategory, NotificationHandle};
+use crate::trade_executor::active_client;
+use crate::AppState;
+use rugplay_persistence::sqlite;
+use serde::Serialize;
+use std::time::Duration;
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// How often equity is sampled
+const TICK_SECS: u64 = 60;
+
+/// Samples are kept at least this long, longer if a move window needs it
+const MIN_RETENTION_SECS: i64 = 7 * 24 * 3600;
+
+/// Emitted to the frontend when an equity alert fires
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityAlertFiredEvent {
+    pub alert: EquityAlert,
+    pub title: String,
+    pub body: String,
+}
+
+/// Load the equity alert settings
+pub async fn load_equity_alert_config(app_handle: &tauri::AppHandle) -> Option<EquityAlertConfig> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>("SELECT value FROM settings WHERE key = 'equity_alerts'")
+        .fetch_optional(db.pool())
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+}
+
+/// One sample and check for the active profile
+async fn check_equity(app_handle: &tauri::AppHandle, alerts: &mut EquityAlertState) -> Result<(), String> {
+    let config = load_equity_alert_config(app_handle).await.unwrap_or_default();
+    if !config.enabled {
+        return Ok(());
+    }
+
+    let client = active_client(app_handle).await?;
+    let portfolio = client.get_portfolio().await.map_err(|e| e.to_string())?;
+    if !portfolio.total_value.is_finite() {
+        return Err("portfolio total value is not a number".to_string());
+    }
+    let now = chrono::Utc::now().timestamp();
+
+    let state = app_handle.state::<AppState>();
+    let series = {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let profile = sqlite::get_active_profile(db.pool())
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("No active profile")?;
+        sqlite::record_equity_snapshot(db.pool(), profile.id, portfolio.total_value, now)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let retention = MIN_RETENTION_SECS.max(config.max_window_secs() as i64);
+        if let Err(e) = sqlite::prune_equity_snapshots(db.pool(), now - retention).await {
+            debug!("Equity alerts: failed to prune old samples: {}", e);
+        }
+
+        // Milestones only need the previous sample, moves their whole window
+        let since = now - config.max_window_secs().max(TICK_SECS * 2) as i64;
+        sqlite::get_equity_snapshots(db.pool(), profile.id, since).await.map_err(|e| e.to_string())?
+    };
+
+    for alert in alerts.evaluate(&config, &series) {
+        let (title, body) = (alert.title(), alert.body());
+        info!("Equity alert fired: {} — {}", title, body);
+        if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+            notif.notify_dedup(NotificationCategory::Alert, &title, &body).await;
+        }
+        if let Err(e) = emit_event(app_handle, AppEvent::EquityAlertFired(EquityAlertFiredEvent { alert, title, body })) {
+            warn!("Failed to emit equity-alert-fired event: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Spawn the background equity sampler
+pub fn spawn_equity_alert_task(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        let mut alerts = EquityAlertState::default();
+        loop {
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+            if let Err(e) = check_equity(&app_handle, &mut alerts).await {
+                debug!("Equity alerts: check skipped: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,236 @@
+//! Equity Alerts — heads-ups on the account's total value
+//!
+//! Unlike price alerts these ignore individual coins and watch the series of
+//! equity snapshots (cash plus coin value) sampled by `equity_alert_loop`:
+//!
+//! - a milestone fires when consecutive samples cross its level, either way
+//! - a move fires when equity has moved at least `pct` percent from the
+//!   oldest sample within its window
+//!
+//! Both wait to clearly clear before they can fire again, with the same
+//! hysteresis as price alerts, so equity hovering around a level or
+//! oscillating at the edge of a move doesn't notify on every sample.
+
+use rugplay_persistence::sqlite::EquitySnapshotRow;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Milestones re-arm once equity is this far away from the level
+const CROSS_REARM_MARGIN_PCT: f64 = 1.0;
+
+/// Moves re-arm once they shrink below this fraction of the target
+const MOVE_REARM_FRACTION: f64 = 0.5;
+
+/// A percentage move of total equity within a window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityMoveAlert {
+    pub pct: f64,
+    pub window_secs: u64,
+}
+
+/// Total-equity alerts (settings key `equity_alerts`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EquityAlertConfig {
+    pub enabled: bool,
+    /// Equity levels to report crossing, in base currency
+    #[serde(default)]
+    pub milestones: Vec<f64>,
+    #[serde(default)]
+    pub moves: Vec<EquityMoveAlert>,
+}
+
+impl Default for EquityAlertConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            milestones: Vec::new(),
+            moves: vec![EquityMoveAlert { pct: 10.0, window_secs: 3_600 }],
+        }
+    }
+}
+
+impl EquityAlertConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.milestones.iter().any(|level| !(level.is_finite() && *level > 0.0)) {
+            return Err("Equity milestones must be greater than 0".to_string());
+        }
+        for rule in &self.moves {
+            if !(rule.pct.is_finite() && rule.pct > 0.0) {
+                return Err("Move percentage must be greater than 0".to_string());
+            }
+            if rule.window_secs < 60 {
+                return Err("Move window must be at least 1 minute".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Longest move window; samples older than this aren't needed
+    pub fn max_window_secs(&self) -> u64 {
+        self.moves.iter().map(|rule| rule.window_secs).max().unwrap_or(0)
+    }
+}
+
+/// An alert that fired on the latest sample
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum EquityAlert {
+    /// Equity crossed `level`, upward or downward
+    Milestone { level: f64, upward: bool, value: f64 },
+    /// Equity moved `moved_pct` from `from` within `window_secs`
+    Move { moved_pct: f64, window_secs: u64, from: f64, value: f64 },
+}
+
+impl EquityAlert {
+    pub fn title(&self) -> String {
+        match *self {
+            Self::Milestone { level, upward: true, .. } => format!("Equity above ${:.2}", level),
+            Self::Milestone { level, upward: false, .. } => format!("Equity below ${:.2}", level),
+            Self::Move { moved_pct, .. } if moved_pct >= 0.0 => format!("Equity up {:.1}%", moved_pct),
+            Self::Move { moved_pct, .. } => format!("Equity down {:.1}%", moved_pct.abs()),
+        }
+    }
+
+    pub fn body(&self) -> String {
+        match *self {
+            Self::Milestone { value, .. } => format!("Total account value is now ${:.2}", value),
+            Self::Move { window_secs, from, value, .. } => format!(
+                "Total account value went from ${:.2} to ${:.2} within {} min",
+                from,
+                value,
+                window_secs.div_ceil(60)
+            ),
+        }
+    }
+}
+
+/// Which alerts have fired and not yet cleared
+#[derive(Debug, Default)]
+pub struct EquityAlertState {
+    /// Milestone levels (as bits) waiting to move clear of the level
+    crossed: HashSet<u64>,
+    /// (pct bits, window) of moves waiting to shrink back
+    moved: HashSet<(u64, u64)>,
+}
+
+impl EquityAlertState {
+    /// Judge the newest sample of `series` (oldest first) against `config`
+    pub fn evaluate(&mut self, config: &EquityAlertConfig, series: &[EquitySnapshotRow]) -> Vec<EquityAlert> {
+        let mut fired = Vec::new();
+        let Some(latest) = series.last() else { return fired };
+        let value = latest.total_value;
+        let previous = series.len().checked_sub(2).map(|i| series[i].total_value);
+
+        let margin = CROSS_REARM_MARGIN_PCT / 100.0;
+        for &level in &config.milestones {
+            let key = level.to_bits();
+            if self.crossed.contains(&key) {
+                if (value - level).abs() > level * margin {
+                    self.crossed.remove(&key);
+                }
+                continue;
+            }
+            let Some(previous) = previous else { continue };
+            let upward = previous < level && value >= level;
+            let downward = previous > level && value <= level;
+            if upward || downward {
+                self.crossed.insert(key);
+                fired.push(EquityAlert::Milestone { level, upward, value });
+            }
+        }
+
+        for rule in &config.moves {
+            let key = (rule.pct.to_bits(), rule.window_secs);
+            let window_start = latest.recorded_at - rule.window_secs as i64;
+            let Some(from) = series
+                .iter()
+                .find(|s| s.recorded_at >= window_start)
+                .map(|s| s.total_value)
+                .filter(|from| *from > 0.0)
+            else {
+                continue;
+            };
+            let moved_pct = (value - from) / from * 100.0;
+            if self.moved.contains(&key) {
+                if moved_pct.abs() < rule.pct * MOVE_REARM_FRACTION {
+                    self.moved.remove(&key);
+                }
+            } else if moved_pct.abs() >= rule.pct {
+                self.moved.insert(key);
+                fired.push(EquityAlert::Move { moved_pct, window_secs: rule.window_secs, from, value });
+            }
+        }
+
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(samples: &[(i64, f64)]) -> Vec<EquitySnapshotRow> {
+        samples
+            .iter()
+            .map(|&(recorded_at, total_value)| EquitySnapshotRow { profile_id: 1, total_value, recorded_at })
+            .collect()
+    }
+
+    /// Feed samples one at a time, as the loop does, collecting what fires
+    fn replay(config: &EquityAlertConfig, samples: &[(i64, f64)]) -> Vec<EquityAlert> {
+        let mut state = EquityAlertState::default();
+        let all = series(samples);
+        (1..=all.len()).flat_map(|n| state.evaluate(config, &all[..n])).collect()
+    }
+
+    #[test]
+    fn test_milestones_fire_on_crossing_in_either_direction() {
+        let config = EquityAlertConfig { enabled: true, milestones: vec![1_000.0, 2_000.0], moves: Vec::new() };
+        // Crosses 1000 up, wobbles around it, falls clearly below and back down through it
+        let fired = replay(&config, &[(0, 900.0), (60, 1_005.0), (120, 999.0), (180, 1_004.0), (240, 1_030.0), (300, 980.0)]);
+        assert_eq!(
+            fired,
+            vec![
+                EquityAlert::Milestone { level: 1_000.0, upward: true, value: 1_005.0 },
+                EquityAlert::Milestone { level: 1_000.0, upward: false, value: 980.0 },
+            ]
+        );
+        assert_eq!(fired[1].title(), "Equity below $1000.00");
+
+        // A jump over several levels reports each; the first sample alone never fires
+        let fired = replay(&config, &[(0, 2_500.0)]);
+        assert!(fired.is_empty());
+        let fired = replay(&config, &[(0, 500.0), (60, 2_500.0)]);
+        assert_eq!(fired.len(), 2);
+        assert!(EquityAlertConfig { milestones: vec![0.0], ..config }.validate().is_err());
+    }
+
+    #[test]
+    fn test_moves_compare_against_the_window_start() {
+        let config = EquityAlertConfig {
+            enabled: true,
+            milestones: Vec::new(),
+            moves: vec![EquityMoveAlert { pct: 10.0, window_secs: 600 }],
+        };
+        // Slow drift: never 10% within any 10 minutes
+        assert!(replay(&config, &[(0, 1_000.0), (600, 1_060.0), (1_200, 1_120.0), (1_800, 1_180.0)]).is_empty());
+
+        // Sharp drop: fires once, re-arms only after recovering most of it
+        let fired = replay(
+            &config,
+            &[(0, 1_000.0), (300, 880.0), (360, 870.0), (900, 880.0), (1_000, 880.0), (1_500, 760.0)],
+        );
+        assert_eq!(fired.len(), 2);
+        match fired[0] {
+            EquityAlert::Move { moved_pct, from, value, .. } => {
+                assert!((moved_pct + 12.0).abs() < 1e-9);
+                assert_eq!((from, value), (1_000.0, 880.0));
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+        assert_eq!(fired[0].title(), "Equity down 12.0%");
+        assert!(EquityAlertConfig { moves: vec![EquityMoveAlert { pct: 5.0, window_secs: 10 }], ..config }.validate().is_err());
+    }
+}
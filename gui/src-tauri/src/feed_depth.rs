@@ -0,0 +1,142 @@
+//! Feed Depth — how many recent trades the polling loops fetch
+//!
+//! The mirror and dip buyer poll `/trades/recent` once per tick. On a busy
+//! market a fixed window can turn over completely between ticks, and any
+//! trade that scrolled past is never seen. Each loop starts at its configured
+//! depth; when the oldest fetched trade is newer than the newest trade of the
+//! previous poll, the window was saturated and the depth doubles (up to
+//! `MAX_FEED_DEPTH`). It eases back once the feed calms down.
+
+/// Depth used when a module config leaves it at 0
+pub const DEFAULT_FEED_DEPTH: u32 = 50;
+
+/// Upper bound for both configured and auto-increased depth
+pub const MAX_FEED_DEPTH: u32 = 500;
+
+/// Whether a poll may have missed trades: the window came back full and
+/// doesn't reach back to the previous poll's newest trade.
+pub fn is_window_saturated(timestamps: &[i64], limit: u32, previous_newest: Option<i64>) -> bool {
+    let Some(previous_newest) = previous_newest else { return false };
+    let Some(&oldest) = timestamps.iter().min() else { return false };
+    timestamps.len() >= limit as usize && oldest > previous_newest
+}
+
+/// Adaptive fetch depth for one polling loop
+#[derive(Debug, Clone)]
+pub struct FeedDepth {
+    configured: u32,
+    current: u32,
+    previous_newest: Option<i64>,
+}
+
+impl FeedDepth {
+    pub fn new(configured: u32) -> Self {
+        let configured = Self::clamp(configured);
+        Self { configured, current: configured, previous_newest: None }
+    }
+
+    fn clamp(depth: u32) -> u32 {
+        if depth == 0 {
+            DEFAULT_FEED_DEPTH
+        } else {
+            depth.min(MAX_FEED_DEPTH)
+        }
+    }
+
+    /// Number of trades to request on the next poll
+    pub fn limit(&self) -> u32 {
+        self.current
+    }
+
+    /// Apply a config change; the adaptive depth restarts from it
+    pub fn set_configured(&mut self, configured: u32) {
+        let configured = Self::clamp(configured);
+        if configured != self.configured {
+            self.configured = configured;
+            self.current = configured;
+        }
+    }
+
+    /// Record the trade timestamps of a poll and adjust the depth.
+    /// Returns true when the window was saturated.
+    pub fn observe(&mut self, timestamps: &[i64]) -> bool {
+        let saturated = is_window_saturated(timestamps, self.current, self.previous_newest);
+        if saturated {
+            self.current = (self.current * 2).min(MAX_FEED_DEPTH);
+        } else if let Some(previous_newest) = self.previous_newest {
+            // Shrink back when under a quarter of the window was new trades
+            let fresh = timestamps.iter().filter(|&&ts| ts > previous_newest).count();
+            if self.current > self.configured && fresh * 4 < self.current as usize {
+                self.current = (self.current / 2).max(self.configured);
+            }
+        }
+
+        if let Some(&newest) = timestamps.iter().max() {
+            self.previous_newest = Some(self.previous_newest.map_or(newest, |prev| prev.max(newest)));
+        }
+        saturated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `count` trades one per second, newest at `newest`
+    fn window(newest: i64, count: i64) -> Vec<i64> {
+        (0..count).map(|i| newest - i).collect()
+    }
+
+    #[test]
+    fn test_saturated_window_detection() {
+        // Full window that starts after the last poll's newest trade
+        assert!(is_window_saturated(&window(200, 50), 50, Some(100)));
+        // Overlaps the previous poll: nothing was skipped
+        assert!(!is_window_saturated(&window(120, 50), 50, Some(100)));
+        // Not full: the feed simply had fewer trades
+        assert!(!is_window_saturated(&window(200, 10), 50, Some(100)));
+        // First poll has nothing to compare against
+        assert!(!is_window_saturated(&window(200, 50), 50, None));
+        assert!(!is_window_saturated(&[], 50, Some(100)));
+    }
+
+    #[test]
+    fn test_depth_grows_while_saturated_and_eases_back() {
+        let mut depth = FeedDepth::new(0);
+        assert_eq!(depth.limit(), DEFAULT_FEED_DEPTH);
+
+        assert!(!depth.observe(&window(1000, 50)));
+        assert_eq!(depth.limit(), 50);
+
+        // Busy market: each poll is entirely new trades
+        assert!(depth.observe(&window(1100, 50)));
+        assert_eq!(depth.limit(), 100);
+        assert!(depth.observe(&window(1300, 100)));
+        assert_eq!(depth.limit(), 200);
+
+        // Window now reaches back past the previous poll
+        assert!(!depth.observe(&window(1400, 200)));
+        assert_eq!(depth.limit(), 200);
+
+        // Quiet again: only a few new trades per poll
+        assert!(!depth.observe(&window(1410, 200)));
+        assert_eq!(depth.limit(), 100);
+        assert!(!depth.observe(&window(1415, 100)));
+        assert_eq!(depth.limit(), 50);
+        assert!(!depth.observe(&window(1416, 50)));
+        assert_eq!(depth.limit(), 50);
+    }
+
+    #[test]
+    fn test_depth_is_capped_and_config_resets_it() {
+        let mut depth = FeedDepth::new(400);
+        depth.observe(&window(1000, 400));
+        assert!(depth.observe(&window(2000, 400)));
+        assert_eq!(depth.limit(), MAX_FEED_DEPTH);
+
+        assert_eq!(FeedDepth::new(5000).limit(), MAX_FEED_DEPTH);
+
+        depth.set_configured(80);
+        assert_eq!(depth.limit(), 80);
+    }
+}
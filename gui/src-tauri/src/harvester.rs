@@ -7,24 +7,22 @@
 //!
 //! The harvester runs for all profiles and can be disabled by the user.
 
+use crate::app_event::{emit_event, AppEvent};
+use crate::claim_outcome::{ClaimOutcome, ProfileClaimOutcome, CLAIM_INTERVAL_SECS};
 use crate::AppState;
 use crate::notifications::NotificationHandle;
 use crate::save_automation_log;
-use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::Serialize;
 use std::collections::HashMap;
-use std::sync::Arc;
-use tauri::{Emitter, Manager};
+use std::sync::{Arc, Mutex};
+use tauri::Manager;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
 /// How often to check / emit countdown (60 seconds)
 const CHECK_INTERVAL_SECS: u64 = 60;
 
-/// Back-off after a failed claim attempt (seconds)
-const RETRY_BACKOFF_SECS: i64 = 300; // 5 minutes
-
 // ─── Events ──────────────────────────────────────────────────────────
 
 /// Emitted when a claim is successfully made
@@ -64,6 +62,29 @@ struct ProfileClaimState {
     backoff_until: i64,
 }
 
+impl ProfileClaimState {
+    /// Move the timers on after an attempt
+    fn apply(&mut self, outcome: &ClaimOutcome, now: i64) {
+        match outcome {
+            ClaimOutcome::Claimed { next_eligible_at, .. } => {
+                self.last_claim_epoch = now;
+                self.total_claims += 1;
+                self.next_eligible_epoch = next_eligible_at.timestamp();
+                self.backoff_until = 0;
+            }
+            ClaimOutcome::NothingToClaim { next_eligible_at } => {
+                self.next_eligible_epoch = next_eligible_at.timestamp();
+            }
+            ClaimOutcome::Failed { retry_at, .. } => {
+                self.backoff_until = retry_at.timestamp();
+            }
+        }
+    }
+}
+
+/// Latest claim outcome per profile, shared by the loop and the commands
+type OutcomeMap = Arc<Mutex<HashMap<i64, ProfileClaimOutcome>>>;
+
 // ─── Handle ──────────────────────────────────────────────────────────
 
 /// Handle to control the harvester from Tauri commands
@@ -71,6 +92,7 @@ struct ProfileClaimState {
 pub struct HarvesterHandle {
     cancel: CancellationToken,
     enabled_tx: Arc<tokio::sync::watch::Sender<bool>>,
+    outcomes: OutcomeMap,
 }
 
 impl HarvesterHandle {
@@ -91,6 +113,18 @@ impl HarvesterHandle {
         info!("Harvester disabled by user");
     }
 
+    /// Keep `outcome` as its profile's latest
+    pub fn record_outcome(&self, outcome: ProfileClaimOutcome) {
+        record_outcome(&self.outcomes, outcome);
+    }
+
+    /// Each profile's latest claim outcome, by profile id
+    pub fn last_outcomes(&self) -> Vec<ProfileClaimOutcome> {
+        let mut outcomes: Vec<_> = self.outcomes.lock().unwrap_or_else(|e| e.into_inner()).values().cloned().collect();
+        outcomes.sort_by_key(|o| o.profile_id);
+        outcomes
+    }
+
     /// Stop the harvester task entirely
     pub fn stop(&self) {
         self.cancel.cancel();
@@ -109,6 +143,7 @@ pub fn spawn_harvester(app_handle: tauri::AppHandle) -> HarvesterHandle {
     let handle = HarvesterHandle {
         cancel: cancel.clone(),
         enabled_tx: Arc::new(enabled_tx),
+        outcomes: Arc::default(),
     };
 
     // Restore enabled state from DB
@@ -123,7 +158,7 @@ pub fn spawn_harvester(app_handle: tauri::AppHandle) -> HarvesterHandle {
         }
     });
 
-    tokio::spawn(harvester_loop(app_handle, cancel, enabled_rx));
+    tokio::spawn(harvester_loop(app_handle, cancel, enabled_rx, handle.outcomes.clone()));
 
     handle
 }
@@ -134,6 +169,7 @@ async fn harvester_loop(
     app_handle: tauri::AppHandle,
     cancel: CancellationToken,
     enabled_rx: tokio::sync::watch::Receiver<bool>,
+    outcomes: OutcomeMap,
 ) {
     info!("Harvester loop started (multi-profile)");
 
@@ -155,6 +191,8 @@ async fn harvester_loop(
                 return;
             }
             _ = interval.tick() => {
+                app_handle.state::<AppState>().heartbeats.beat("harvester");
+
                 let now = chrono::Utc::now().timestamp();
 
                 // Check if harvester is enabled
@@ -205,76 +243,35 @@ async fn harvester_loop(
                         continue;
                     }
 
-                    // This profile might be eligible — decrypt token and check server
-                    let token = match decrypt_profile_token(&app_handle, profile.id).await {
-                        Ok(t) => t,
-                        Err(e) => {
-                            debug!("Harvester: can't decrypt token for profile {} ({}): {}", profile.id, profile.username, e);
-                            state.backoff_until = now + RETRY_BACKOFF_SECS;
-                            continue;
-                        }
-                    };
-
-                    let client = RugplayClient::new_with_cache(&token, {
-                        let app_state = app_handle.state::<AppState>();
-                        app_state.coin_cache.clone()
+                    let outcome = attempt_claim(&app_handle, profile).await;
+                    state.apply(&outcome, now);
+                    record_outcome(&outcomes, ProfileClaimOutcome {
+                        profile_id: profile.id,
+                        username: profile.username.clone(),
+                        at: chrono::Utc::now(),
+                        outcome: outcome.clone(),
                     });
 
-                    // Step 1: Check eligibility with GET /api/rewards/claim
-                    let reward_status = match client.get_reward_status().await {
-                        Ok(s) => s,
-                        Err(e) => {
-                            warn!("Harvester: reward status check failed for profile {} ({}): {}", profile.id, profile.username, e);
-                            state.backoff_until = now + RETRY_BACKOFF_SECS;
-                            continue;
+                    match outcome {
+                        ClaimOutcome::NothingToClaim { .. } => {
+                            debug!(
+                                "Harvester: profile {} ({}) not ready, {}s remaining",
+                                profile.id, profile.username, state.next_eligible_epoch - now
+                            );
                         }
-                    };
-
-                    // Update our tracking from server data
-                    // NOTE: time_remaining from API is in MILLISECONDS, convert to seconds
-                    let remaining_secs = reward_status.time_remaining / 1000;
-                    if !reward_status.can_claim {
-                        state.next_eligible_epoch = now + remaining_secs;
-                        debug!(
-                            "Harvester: profile {} ({}) not ready, {}s remaining",
-                            profile.id, profile.username, remaining_secs
-                        );
-                        continue;
-                    }
-
-                    // Step 2: Server says we can claim!
-                    info!(
-                        "Harvester: claiming reward for profile {} ({}) — ${:.2}",
-                        profile.id, profile.username, reward_status.reward_amount
-                    );
-
-                    match client.claim_daily_reward().await {
-                        Ok(claim_response) => {
-                            state.last_claim_epoch = now;
-                            state.total_claims += 1;
-
-                            // Parse next_claim_time from server response
-                            if let Some(ref nct) = claim_response.next_claim_time {
-                                if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(nct) {
-                                    state.next_eligible_epoch = dt.timestamp();
-                                } else {
-                                    warn!("Harvester: couldn't parse next_claim_time '{}', falling back to now+12h", nct);
-                                    state.next_eligible_epoch = now + 12 * 3600;
-                                }
-                            } else {
-                                state.next_eligible_epoch = now + 12 * 3600;
-                            }
-
-                            state.backoff_until = 0;
-
+                        ClaimOutcome::Failed { reason, .. } => {
+                            // Backed off — could be 429 rate limit or other server error
+                            error!("Harvester: profile {} ({}): {}", profile.id, profile.username, reason);
+                        }
+                        ClaimOutcome::Claimed { amount, new_balance, login_streak, next_eligible_at } => {
                             // Persist
                             save_profile_claim_state(&app_handle, profile.id, state).await;
 
                             info!(
                                 "Harvester: profile {} ({}) claimed ${:.2} (streak: {}, total: {})",
                                 profile.id, profile.username,
-                                claim_response.reward_amount,
-                                claim_response.login_streak,
+                                amount,
+                                login_streak,
                                 state.total_claims
                             );
 
@@ -282,22 +279,22 @@ async fn harvester_loop(
                             let event = HarvesterClaimedEvent {
                                 profile_id: profile.id,
                                 username: profile.username.clone(),
-                                reward_amount: claim_response.reward_amount,
-                                new_balance: claim_response.new_balance,
-                                login_streak: claim_response.login_streak,
-                                next_claim_at: claim_response.next_claim_time.clone(),
+                                reward_amount: amount,
+                                new_balance,
+                                login_streak,
+                                next_claim_at: Some(next_eligible_at.to_rfc3339()),
                                 total_claims: state.total_claims,
                             };
-                            if let Err(e) = app_handle.emit("harvester-claimed", &event) {
+                            if let Err(e) = emit_event(&app_handle, AppEvent::HarvesterClaimed(event)) {
                                 warn!("Failed to emit harvester-claimed: {}", e);
                             }
 
                             // Native notification
                             if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
                                 notif.notify_harvester_claimed(
-                                    claim_response.reward_amount,
+                                    amount,
                                     &profile.username,
-                                    claim_response.login_streak,
+                                    login_streak,
                                 ).await;
                             }
 
@@ -307,20 +304,14 @@ async fn harvester_loop(
                                 "REWARD",
                                 &format!("Reward ({})", profile.username),
                                 "CLAIM",
-                                claim_response.reward_amount,
+                                amount,
                                 &serde_json::json!({
                                     "username": profile.username,
-                                    "loginStreak": claim_response.login_streak,
-                                    "newBalance": claim_response.new_balance,
+                                    "loginStreak": login_streak,
+                                    "newBalance": new_balance,
                                 }).to_string(),
                             ).await;
                         }
-                        Err(e) => {
-                            let err_str = e.to_string();
-                            error!("Harvester: claim failed for profile {} ({}): {}", profile.id, profile.username, err_str);
-                            // Back off — could be 429 rate limit or other server error
-                            state.backoff_until = now + RETRY_BACKOFF_SECS;
-                        }
                     }
                 }
 
@@ -357,7 +348,7 @@ async fn harvester_loop(
                     profiles_count: profiles.len() as u32,
                 };
 
-                if let Err(e) = app_handle.emit("harvester-tick", &tick) {
+                if let Err(e) = emit_event(&app_handle, AppEvent::HarvesterTick(tick)) {
                     warn!("Failed to emit harvester-tick: {}", e);
                 }
             }
@@ -375,7 +366,44 @@ fn emit_idle_tick(app_handle: &tauri::AppHandle) {
         total_claims: 0,
         profiles_count: 0,
     };
-    let _ = app_handle.emit("harvester-tick", &tick);
+    let _ = emit_event(app_handle, AppEvent::HarvesterTick(tick));
+}
+
+/// Check a profile's reward and claim it if it is ready. Nothing is saved;
+/// callers apply the outcome.
+pub async fn attempt_claim(app_handle: &tauri::AppHandle, profile: &rugplay_core::Profile) -> ClaimOutcome {
+    let token = match decrypt_profile_token(app_handle, profile.id).await {
+        Ok(t) => t,
+        Err(e) => return ClaimOutcome::failed(format!("Token error: {}", e), chrono::Utc::now()),
+    };
+
+    let client = app_handle.state::<AppState>().api_client(&token);
+
+    // Step 1: Check eligibility with GET /api/rewards/claim
+    let reward_status = match client.get_reward_status().await {
+        Ok(s) => s,
+        Err(e) => return ClaimOutcome::failed(format!("Reward status check failed: {}", e), chrono::Utc::now()),
+    };
+    if let Some(outcome) = ClaimOutcome::from_status(&reward_status, chrono::Utc::now()) {
+        return outcome;
+    }
+
+    // Step 2: Server says we can claim!
+    info!(
+        "Harvester: claiming reward for profile {} ({}) — ${:.2}",
+        profile.id, profile.username, reward_status.reward_amount
+    );
+    match client.claim_daily_reward().await {
+        Ok(claim_response) => ClaimOutcome::from_claim(&claim_response, chrono::Utc::now()),
+        Err(e) => ClaimOutcome::failed(format!("Claim failed: {}", e), chrono::Utc::now()),
+    }
+}
+
+fn record_outcome(outcomes: &OutcomeMap, outcome: ProfileClaimOutcome) {
+    outcomes
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(outcome.profile_id, outcome);
 }
 
 /// Get all profiles from the database
@@ -457,7 +485,7 @@ async fn load_all_profile_states(
             "last_claim" => {
                 state.last_claim_epoch = value.parse().unwrap_or(0);
                 if state.last_claim_epoch > 0 {
-                    state.next_eligible_epoch = state.last_claim_epoch + 12 * 3600;
+                    state.next_eligible_epoch = state.last_claim_epoch + CLAIM_INTERVAL_SECS;
                 }
             }
             "total_claims" => {
@@ -496,7 +524,7 @@ async fn load_all_profile_states(
         for profile in &profiles {
             if !states.contains_key(&profile.id) {
                 states.insert(profile.id, ProfileClaimState {
-                    next_eligible_epoch: last_claim + 12 * 3600,
+                    next_eligible_epoch: last_claim + CLAIM_INTERVAL_SECS,
                     total_claims,
                     last_claim_epoch: last_claim,
                     backoff_until: 0,
@@ -578,5 +606,5 @@ fn emit_disabled_tick(app_handle: &tauri::AppHandle) {
         total_claims: 0,
         profiles_count: 0,
     };
-    let _ = app_handle.emit("harvester-tick", &tick);
+    let _ = emit_event(app_handle, AppEvent::HarvesterTick(tick));
 }
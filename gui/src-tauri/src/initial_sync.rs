@@ -0,0 +1,77 @@
+//! Initial Sync — the warm-up pass run before automation may trade
+//!
+//! Loads what the loops would otherwise discover on their first tick: the
+//! active profile, the portfolio (each held coin is fetched into the shared
+//! coin cache) and the top of the market. Steps that fail are retried every
+//! `RETRY_INTERVAL_SECS` until all have completed; `warmup` describes what
+//! is held back in the meantime.
+
+use crate::app_event::{emit_event, AppEvent};
+use crate::trade_executor::active_client;
+use crate::warmup::WarmupStep;
+use crate::AppState;
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// Delay between passes while a step keeps failing
+const RETRY_INTERVAL_SECS: u64 = 15;
+
+/// Market coins fetched during warm-up
+const MARKET_PREFETCH: u32 = 20;
+
+/// Spawn the initial sync. Call once persisted settings are loaded.
+pub fn spawn_initial_sync(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        loop {
+            let ready = run_sync_pass(&app_handle).await;
+            let status = app_handle.state::<AppState>().warmup.status();
+            let _ = emit_event(&app_handle, AppEvent::WarmupStatus(status.clone()));
+            if ready {
+                info!("Warm-up finished after {} pass(es), automation may trade", status.attempts);
+                return;
+            }
+            warn!("Warm-up pending {:?}: {}", status.pending, status.last_error.unwrap_or_default());
+            tokio::time::sleep(std::time::Duration::from_secs(RETRY_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Run every step not yet done. Returns whether the warm-up is complete.
+async fn run_sync_pass(app_handle: &tauri::AppHandle) -> bool {
+    let warmup = app_handle.state::<AppState>().warmup.clone();
+    warmup.begin_attempt();
+
+    let client = match active_client(app_handle).await {
+        Ok(client) => client,
+        Err(e) => {
+            warmup.fail(WarmupStep::Profile, &e);
+            return warmup.is_ready();
+        }
+    };
+    if let Err(e) = client.get_profile().await {
+        warmup.fail(WarmupStep::Profile, &e.to_string());
+        return warmup.is_ready();
+    }
+    warmup.complete(WarmupStep::Profile);
+
+    match client.get_portfolio().await {
+        Ok(portfolio) => {
+            for holding in &portfolio.coin_holdings {
+                if let Err(e) = client.get_coin(&holding.symbol).await {
+                    debug!("Warm-up: failed to cache {}: {}", holding.symbol, e);
+                }
+            }
+            warmup.complete(WarmupStep::Portfolio);
+        }
+        Err(e) => warmup.fail(WarmupStep::Portfolio, &e.to_string()),
+    }
+
+    match client.get_market(1, MARKET_PREFETCH, "marketCap", "desc", None).await {
+        Ok(_) => {
+            warmup.complete(WarmupStep::Market);
+        }
+        Err(e) => warmup.fail(WarmupStep::Market, &e.to_string()),
+    }
+
+    warmup.is_ready()
+}
@@ -0,0 +1,264 @@
+//! Journal Reconciliation — does the local trade journal match the server?
+//!
+//! PnL reporting reads the local `transactions` journal, which misses trades
+//! that errored locally but executed anyway, and trades placed outside the
+//! app. The server's transaction history is authoritative: each pass pairs
+//! server trades with journal rows by symbol, side, time and USD value.
+//! Server trades the journal lacks are added to it; journal rows the server
+//! has no record of are only flagged, since they may be manual entries.
+
+use chrono::{DateTime, Utc};
+use rugplay_core::{parse_api_timestamp, parse_db_timestamp, ApiTransaction};
+use rugplay_persistence::sqlite::TransactionRow;
+use serde::{Deserialize, Serialize};
+
+/// Max clock difference between a journal row and the server's record of it
+pub const RECONCILE_TOLERANCE_SECS: i64 = 120;
+
+/// Relative difference tolerated between the journaled and recorded USD value
+const VALUE_TOLERANCE: f64 = 0.02;
+
+/// A trade only one side knows about
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JournalDiscrepancy {
+    /// Journal row id, for trades missing on the server
+    pub local_id: Option<i64>,
+    /// Server transaction id, for trades missing locally
+    pub server_id: Option<i64>,
+    pub symbol: String,
+    pub trade_type: String,
+    pub coin_amount: f64,
+    pub price: f64,
+    pub usd_value: f64,
+    pub timestamp: String,
+}
+
+/// Outcome of one reconciliation pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReport {
+    pub checked_at: String,
+    /// Oldest trade compared; `None` when the whole server history was read
+    pub window_start: Option<String>,
+    pub matched: usize,
+    pub missing_locally: Vec<JournalDiscrepancy>,
+    pub missing_on_server: Vec<JournalDiscrepancy>,
+    /// Server trades written to the journal this pass
+    #[serde(default)]
+    pub repaired: usize,
+}
+
+impl ReconciliationReport {
+    pub fn is_clean(&self) -> bool {
+        self.missing_locally.is_empty() && self.missing_on_server.is_empty()
+    }
+
+    /// One-line summary for logs
+    pub fn summary(&self) -> String {
+        format!(
+            "{} matched, {} missing locally ({} repaired), {} missing on server",
+            self.matched,
+            self.missing_locally.len(),
+            self.repaired,
+            self.missing_on_server.len()
+        )
+    }
+}
+
+/// Compare `journal` against `server` trades (newest first, as the API
+/// returns them). When `server_complete` is false the server list is only
+/// the latest page, so journal rows older than its oldest trade are left
+/// out. Trades within the tolerance of `now` aren't flagged on either side
+/// yet: the server may not list a fresh journal row, and a fresh server trade
+/// may still be waiting on its own journal write. They are still paired, so a
+/// settled row isn't flagged for lack of a counterpart that is merely recent.
+pub fn reconcile_journal(
+    journal: &[TransactionRow],
+    server: &[ApiTransaction],
+    server_complete: bool,
+    now: DateTime<Utc>,
+) -> ReconciliationReport {
+    let trades: Vec<(&ApiTransaction, &str, DateTime<Utc>)> = server
+        .iter()
+        .filter(|tx| !tx.is_transfer && is_trade(&tx.trade_type))
+        .filter_map(|tx| Some((tx, tx.coin.as_ref()?.symbol.as_str(), parse_api_timestamp(&tx.timestamp)?)))
+        .collect();
+    let window_start = if server_complete { None } else { trades.iter().map(|(_, _, at)| *at).min() };
+    let settled = now - chrono::Duration::seconds(RECONCILE_TOLERANCE_SECS);
+
+    let rows: Vec<(&TransactionRow, DateTime<Utc>)> = journal
+        .iter()
+        .filter(|row| is_trade(&row.trade_type))
+        .filter_map(|row| Some((row, parse_db_timestamp(row.timestamp.as_deref()?)?)))
+        .filter(|(_, at)| window_start.is_none_or(|start| *at >= start - chrono::Duration::seconds(RECONCILE_TOLERANCE_SECS)))
+        .collect();
+
+    let mut used = vec![false; rows.len()];
+    let mut matched = 0;
+    let mut missing_locally = Vec::new();
+    for (tx, symbol, at) in &trades {
+        let closest = rows
+            .iter()
+            .enumerate()
+            .filter(|(i, (row, _))| {
+                !used[*i]
+                    && row.symbol.eq_ignore_ascii_case(symbol)
+                    && row.trade_type.eq_ignore_ascii_case(&tx.trade_type)
+                    && values_match(row.usd_value, tx.total_base_currency_amount)
+            })
+            .map(|(i, (_, logged))| (i, (*logged - *at).num_seconds().abs()))
+            .filter(|(_, gap)| *gap <= RECONCILE_TOLERANCE_SECS)
+            .min_by_key(|(_, gap)| *gap);
+        match closest {
+            Some((i, _)) => {
+                used[i] = true;
+                matched += 1;
+            }
+            None if *at > settled => {}
+            None => missing_locally.push(JournalDiscrepancy {
+                local_id: None,
+                server_id: Some(tx.id),
+                symbol: symbol.to_string(),
+                trade_type: tx.trade_type.to_uppercase(),
+                coin_amount: tx.quantity,
+                price: tx.price_per_coin,
+                usd_value: tx.total_base_currency_amount,
+                timestamp: at.to_rfc3339(),
+            }),
+        }
+    }
+
+    let missing_on_server = rows
+        .iter()
+        .zip(&used)
+        .filter(|((_, at), used)| !**used && *at <= settled)
+        .map(|((row, at), _)| JournalDiscrepancy {
+            local_id: Some(row.id),
+            server_id: None,
+            symbol: row.symbol.clone(),
+            trade_type: row.trade_type.to_uppercase(),
+            coin_amount: row.coin_amount,
+            price: row.price,
+            usd_value: row.usd_value,
+            timestamp: at.to_rfc3339(),
+        })
+        .collect();
+
+    ReconciliationReport {
+        checked_at: now.to_rfc3339(),
+        window_start: window_start.map(|at| at.to_rfc3339()),
+        matched,
+        missing_locally,
+        missing_on_server,
+        repaired: 0,
+    }
+}
+
+fn is_trade(trade_type: &str) -> bool {
+    trade_type.eq_ignore_ascii_case("BUY") || trade_type.eq_ignore_ascii_case("SELL")
+}
+
+fn values_match(local: f64, server: f64) -> bool {
+    (local - server).abs() <= server.abs().max(0.01) * VALUE_TOLERANCE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server_tx(id: i64, kind: &str, symbol: &str, total: f64, timestamp: &str) -> ApiTransaction {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": kind,
+            "quantity": total * 10.0,
+            "pricePerCoin": 0.1,
+            "totalBaseCurrencyAmount": total,
+            "timestamp": timestamp,
+            "coin": { "id": 1, "name": symbol, "symbol": symbol },
+        }))
+        .unwrap()
+    }
+
+    fn row(id: i64, kind: &str, symbol: &str, usd_value: f64, timestamp: &str) -> TransactionRow {
+        TransactionRow {
+            id,
+            profile_id: 1,
+            symbol: symbol.to_string(),
+            trade_type: kind.to_string(),
+            coin_amount: usd_value * 10.0,
+            price: 0.1,
+            usd_value,
+            timestamp: Some(timestamp.to_string()),
+            note: None,
+        }
+    }
+
+    #[test]
+    fn test_missing_trades_are_found_on_both_sides() {
+        let now = parse_api_timestamp("2026-03-01T13:00:00Z").unwrap();
+        let server = vec![
+            // Executed despite a local timeout, so never journaled
+            server_tx(3, "SELL", "MOON", 80.0, "2026-03-01T12:30:00Z"),
+            server_tx(2, "BUY", "MOON", 50.0, "2026-03-01T12:00:05Z"),
+            server_tx(1, "BUY", "DOGE", 20.0, "2026-03-01T11:00:00Z"),
+        ];
+        let journal = vec![
+            // Journaled a bit after the server recorded it
+            row(12, "BUY", "MOON", 50.0, "2026-03-01 12:00:09"),
+            row(11, "BUY", "DOGE", 20.0, "2026-03-01 11:00:30"),
+            // Journaled but the server never saw it
+            row(13, "BUY", "PUMP", 10.0, "2026-03-01 12:45:00"),
+            // Logged seconds ago; the server may not list it yet
+            row(14, "BUY", "PUMP", 10.0, "2026-03-01 12:59:30"),
+            // Older than the server page, can't be compared
+            row(10, "SELL", "OLD", 5.0, "2026-02-20 10:00:00"),
+        ];
+
+        let report = reconcile_journal(&journal, &server, false, now);
+        assert_eq!(report.matched, 2);
+        assert_eq!(report.missing_locally.len(), 1);
+        assert_eq!(report.missing_locally[0].server_id, Some(3));
+        assert_eq!((report.missing_locally[0].symbol.as_str(), report.missing_locally[0].trade_type.as_str()), ("MOON", "SELL"));
+        let flagged: Vec<Option<i64>> = report.missing_on_server.iter().map(|d| d.local_id).collect();
+        assert_eq!(flagged, vec![Some(13)]);
+        assert_eq!(report.window_start.as_deref(), Some("2026-03-01T11:00:00+00:00"));
+        assert_eq!(report.summary(), "2 matched, 1 missing locally (0 repaired), 1 missing on server");
+
+        // With the full server history the old row is compared too
+        let full = reconcile_journal(&journal, &server, true, now);
+        assert_eq!(full.missing_on_server.len(), 2);
+
+        // A value far off isn't the same trade
+        let wrong_size = vec![row(12, "BUY", "MOON", 75.0, "2026-03-01 12:00:09")];
+        let report = reconcile_journal(&wrong_size, &server[1..2], false, now);
+        assert_eq!((report.matched, report.missing_locally.len(), report.missing_on_server.len()), (0, 1, 1));
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_recent_server_trades_are_not_reported_yet() {
+        let now = parse_api_timestamp("2026-03-01T13:00:00Z").unwrap();
+        let server = vec![
+            // Just executed; the local outcome listener hasn't journaled it yet
+            server_tx(5, "BUY", "PUMP", 10.0, "2026-03-01T12:59:30Z"),
+            // Recorded just after the cutoff, journaled just before it
+            server_tx(4, "SELL", "MOON", 30.0, "2026-03-01T12:58:05Z"),
+            server_tx(3, "BUY", "DOGE", 20.0, "2026-03-01T12:30:00Z"),
+        ];
+        let journal = vec![
+            row(14, "SELL", "MOON", 30.0, "2026-03-01 12:57:55"),
+            row(13, "BUY", "DOGE", 20.0, "2026-03-01 12:30:02"),
+        ];
+
+        let report = reconcile_journal(&journal, &server, false, now);
+        assert_eq!(report.matched, 2);
+        assert!(report.is_clean());
+
+        // Once settled, the unjournaled trade is reported
+        let later = now + chrono::Duration::seconds(RECONCILE_TOLERANCE_SECS);
+        let report = reconcile_journal(&journal, &server, false, later);
+        let missing: Vec<Option<i64>> = report.missing_locally.iter().map(|d| d.server_id).collect();
+        assert_eq!(missing, vec![Some(5)]);
+    }
+}
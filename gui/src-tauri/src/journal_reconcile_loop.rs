@@ -0,0 +1,126 @@
+//! Journal Reconciliation Loop — periodic check of the trade journal
+//!
+//! Every pass reads the latest pages of the server's transaction history,
+//! compares them with the active profile's journal via
+//! `journal_reconcile::reconcile_journal`, writes the server trades the
+//! journal is missing and stores the report (settings key
+//! `journal_reconciliation`).
+
+use crate::journal_reconcile::{reconcile_journal, ReconciliationReport};
+use crate::trade_executor::active_client;
+use crate::AppState;
+use rugplay_core::{parse_api_timestamp, ApiTransaction};
+use rugplay_persistence::sqlite;
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// How often the journal is reconciled (1 hour)
+const RECONCILE_INTERVAL_SECS: u64 = 3600;
+
+/// Delay before the first pass so startup isn't slowed down
+const STARTUP_DELAY_SECS: u64 = 300;
+
+/// Server transactions fetched per page, and the most pages read per pass
+const SERVER_PAGE_SIZE: u32 = 100;
+const MAX_SERVER_PAGES: u32 = 5;
+
+/// Journal rows compared per pass (newest first)
+const JOURNAL_LIMIT: u32 = 2000;
+
+/// Load the last reconciliation report
+pub async fn load_reconciliation_report(app_handle: &tauri::AppHandle) -> Option<ReconciliationReport> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'journal_reconciliation'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|j| serde_json::from_str(&j).ok())
+}
+
+/// Reconcile the active profile's journal with the server now, add the
+/// trades it is missing and store the report
+pub async fn run_reconciliation(app_handle: &tauri::AppHandle) -> Result<ReconciliationReport, String> {
+    let client = active_client(app_handle).await?;
+    let mut server: Vec<ApiTransaction> = Vec::new();
+    let mut complete = false;
+    for page in 1..=MAX_SERVER_PAGES {
+        let response = client
+            .get_transactions(page, SERVER_PAGE_SIZE, None, None)
+            .await
+            .map_err(|e| e.to_string())?;
+        let fetched = response.transactions.len();
+        server.extend(response.transactions);
+        if fetched < SERVER_PAGE_SIZE as usize {
+            complete = true;
+            break;
+        }
+    }
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let profile = sqlite::get_active_profile(db.pool())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or("No active profile")?;
+    let journal = sqlite::get_transactions(db.pool(), profile.id, JOURNAL_LIMIT, 0, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut report = reconcile_journal(&journal, &server, complete, chrono::Utc::now());
+    for missing in &report.missing_locally {
+        let (Some(server_id), Some(at)) = (missing.server_id, parse_api_timestamp(&missing.timestamp)) else { continue };
+        let note = format!("Recovered from server history (#{})", server_id);
+        let tx = sqlite::NewTransaction {
+            symbol: &missing.symbol,
+            trade_type: &missing.trade_type,
+            coin_amount: missing.coin_amount,
+            price: missing.price,
+            usd_value: missing.usd_value,
+            note: Some(&note),
+        };
+        let timestamp = at.format("%Y-%m-%d %H:%M:%S").to_string();
+        match sqlite::log_server_transaction(db.pool(), profile.id, server_id, &tx, &timestamp).await {
+            Ok(true) => report.repaired += 1,
+            Ok(false) => {}
+            Err(e) => warn!("Reconciliation: failed to journal {} {}: {}", missing.trade_type, missing.symbol, e),
+        }
+    }
+
+    let json = serde_json::to_string(&report).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('journal_reconciliation', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if report.is_clean() {
+        debug!("Reconciliation: journal matches the server ({} trades)", report.matched);
+    } else {
+        info!("Reconciliation: {}", report.summary());
+    }
+    Ok(report)
+}
+
+/// Spawn the background reconciliation task
+pub fn spawn_journal_reconcile_task(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(STARTUP_DELAY_SECS)).await;
+
+        loop {
+            if let Err(e) = run_reconciliation(&app_handle).await {
+                debug!("Reconciliation: pass skipped: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(RECONCILE_INTERVAL_SECS)).await;
+        }
+    });
+}
@@ -0,0 +1,143 @@
+//! Emergency Stop — halt every automation module in one call
+//!
+//! Unlike the acquisition pause, which only holds off the buy-side modules,
+//! the emergency stop also pauses the sentinel monitor, empties the trade
+//! executor's queue and rejects every trade awaiting approval. The executor
+//! is halted first, so an order placed by a tick that was already running
+//! when the stop came in is refused instead of slipping in behind the
+//! cleared queue.
+//!
+//! The stop is persisted (settings key `automation_killed`) and applied again
+//! at startup. Modules can't be re-enabled while it is in force; releasing it
+//! resumes automated trading, and the sentinel monitor only if it was the
+//! stop that paused it (`automation_killed_paused_monitor`): a monitor the
+//! user had paused stays paused. The buy-side modules stay off until the
+//! user turns them back on.
+
+use crate::app_event::{emit_event, AppEvent};
+use crate::trade_executor::TradeExecutorHandle;
+use crate::{dipbuyer, mirror, sniper, AppState, DipBuyerHandle, MirrorHandle, SentinelMonitorHandle, SniperHandle};
+use rugplay_persistence::sqlite;
+use serde::Serialize;
+use tauri::Manager;
+use tracing::{info, warn};
+
+/// Emitted when the emergency stop is engaged or released
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmergencyStopEvent {
+    pub active: bool,
+    /// Queued and awaiting-approval trades cancelled by the stop (0 on release)
+    pub cancelled_trades: usize,
+    pub at: String,
+}
+
+/// Halt all automation: disable the sniper, mirror and dip buyer, pause the
+/// sentinel monitor and cancel every queued trade
+pub async fn engage(app_handle: &tauri::AppHandle) -> Result<EmergencyStopEvent, String> {
+    let executor = app_handle.try_state::<TradeExecutorHandle>();
+    if let Some(executor) = &executor {
+        executor.set_automation_halted(true);
+    }
+
+    if let Some(handle) = app_handle.try_state::<SniperHandle>() {
+        handle.disable();
+    }
+    if let Some(handle) = app_handle.try_state::<MirrorHandle>() {
+        handle.disable();
+    }
+    if let Some(handle) = app_handle.try_state::<DipBuyerHandle>() {
+        handle.disable();
+    }
+    // Engaging twice mustn't forget that the first one paused the monitor
+    let mut paused_monitor = load_automation_killed(app_handle).await && load_paused_monitor(app_handle).await;
+    if let Some(handle) = app_handle.try_state::<SentinelMonitorHandle>() {
+        paused_monitor |= !handle.is_paused().await;
+        handle.pause().await;
+    }
+
+    let cancelled_trades = match &executor {
+        Some(executor) => {
+            executor.clear_queue().await.len()
+                + executor.reject_all_pending("Emergency stop: automated trading is halted").await.len()
+        }
+        None => 0,
+    };
+
+    // Persist every module as off, so a restart can't bring one back
+    sniper::save_sniper_enabled(app_handle, false).await;
+    mirror::save_mirror_enabled(app_handle, false).await;
+    dipbuyer::save_dipbuyer_enabled(app_handle, false).await;
+    save_setting(app_handle, PAUSED_MONITOR_KEY, paused_monitor).await?;
+    save_setting(app_handle, KILLED_KEY, true).await?;
+
+    warn!("EMERGENCY STOP: all automation halted, {} queued trade(s) cancelled", cancelled_trades);
+    Ok(announce(app_handle, true, cancelled_trades))
+}
+
+/// Lift the emergency stop: automated trading resumes, and the sentinel
+/// monitor if the stop paused it; the buy-side modules stay disabled
+pub async fn release(app_handle: &tauri::AppHandle) -> Result<EmergencyStopEvent, String> {
+    let paused_monitor = load_paused_monitor(app_handle).await;
+    save_setting(app_handle, KILLED_KEY, false).await?;
+    save_setting(app_handle, PAUSED_MONITOR_KEY, false).await?;
+
+    if let Some(executor) = app_handle.try_state::<TradeExecutorHandle>() {
+        executor.set_automation_halted(false);
+    }
+    if paused_monitor {
+        if let Some(handle) = app_handle.try_state::<SentinelMonitorHandle>() {
+            handle.resume().await;
+        }
+    } else {
+        info!("Sentinel monitor was paused before the emergency stop: leaving it paused");
+    }
+
+    info!("Emergency stop released");
+    Ok(announce(app_handle, false, 0))
+}
+
+fn announce(app_handle: &tauri::AppHandle, active: bool, cancelled_trades: usize) -> EmergencyStopEvent {
+    let event = EmergencyStopEvent { active, cancelled_trades, at: chrono::Utc::now().to_rfc3339() };
+    if let Err(e) = emit_event(app_handle, AppEvent::EmergencyStop(event.clone())) {
+        warn!("Failed to emit emergency-stop event: {}", e);
+    }
+    event
+}
+
+/// Refuse to re-enable automation while the emergency stop is in force
+pub async fn ensure_not_killed(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    if load_automation_killed(app_handle).await {
+        return Err("Emergency stop is active — release it before re-enabling automation".to_string());
+    }
+    Ok(())
+}
+
+const KILLED_KEY: &str = "automation_killed";
+const PAUSED_MONITOR_KEY: &str = "automation_killed_paused_monitor";
+
+/// Whether the emergency stop is in force
+pub async fn load_automation_killed(app_handle: &tauri::AppHandle) -> bool {
+    load_setting(app_handle, KILLED_KEY).await
+}
+
+/// Whether the stop in force paused a running sentinel monitor
+async fn load_paused_monitor(app_handle: &tauri::AppHandle) -> bool {
+    load_setting(app_handle, PAUSED_MONITOR_KEY).await
+}
+
+async fn load_setting(app_handle: &tauri::AppHandle, key: &str) -> bool {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return false };
+
+    sqlite::get_setting(db.pool(), key).await.ok().flatten().is_some_and(|v| v == "true")
+}
+
+async fn save_setting(app_handle: &tauri::AppHandle, key: &str, value: bool) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    sqlite::set_setting(db.pool(), key, if value { "true" } else { "false" }).await.map_err(|e| e.to_string())
+}
@@ -1,16 +1,76 @@
 //! Rugplay GUI - Tauri application library
 
+pub mod acquisition;
+pub mod app_event;
+pub mod bot_status;
+pub mod budget_window;
+pub mod cache_warm;
+pub mod cache_warm_loop;
+pub mod claim_outcome;
+pub mod coin_risk;
 pub mod commands;
+pub mod config_check;
+pub mod config_diff;
+pub mod decision_log;
+pub mod diagnostics;
+pub mod drawdown_loop;
 pub mod dipbuyer;
 pub mod dipbuyer_signals;
+pub mod entry_script;
+pub mod equity_alert_loop;
+pub mod equity_alerts;
+pub mod feed_depth;
 pub mod harvester;
+pub mod initial_sync;
+pub mod journal_reconcile;
+pub mod journal_reconcile_loop;
+pub mod kill_switch;
+pub mod live_feed;
+pub mod log_buffer;
+pub mod loop_snapshot;
+pub mod min_trade;
 pub mod mirror;
+pub mod mobile_permissions;
 pub mod mobile_server;
+pub mod notification_outbox;
 pub mod notifications;
+pub mod order_split;
+pub mod pagination;
+pub mod paper_trading;
+pub mod poll_interval;
+pub mod price_alert_loop;
+pub mod price_alerts;
+pub mod price_freshness;
+pub mod profile_duplicates;
+pub mod profile_failover;
+pub mod profile_refresh;
+pub mod protective_sentinel;
+pub mod rebalance;
+pub mod retention;
+pub mod round_trip;
+pub mod seen_trades;
 pub mod sentinel_eval;
 pub mod sentinel_loop;
+pub mod sentinel_reconcile;
+pub mod setup_state;
+pub mod skip_reason;
+pub mod snipe_retry;
 pub mod sniper;
+pub mod tick_budget;
+pub mod token_migration;
+pub mod trade_amount;
+pub mod trade_approval;
 pub mod trade_executor;
+pub mod trade_journal;
+pub mod trade_outcome;
+pub mod trade_pacing;
+pub mod trade_reason;
+pub mod trade_reconcile;
+pub mod warmup;
+pub mod wash_trades;
+pub mod watchlist;
+pub mod whale_priority;
+pub mod whale_score;
 mod state;
 
 pub use dipbuyer::DipBuyerHandle;
@@ -22,4 +82,5 @@ pub use sentinel_loop::SentinelMonitorHandle;
 pub use sniper::SniperHandle;
 pub use state::AppState;
 pub use state::save_automation_log;
+pub use state::{ensure_protective_sentinel, paper_tagged_details, record_config_change, record_decision, restore_loop_snapshot, retune_poll_interval, save_loop_snapshot};
 pub use trade_executor::TradeExecutorHandle;
@@ -0,0 +1,234 @@
+//! Live Feed — recent trades pushed over the socket instead of polled
+//!
+//! `spawn_live_feed` keeps a `WebSocketManager` connected with the active
+//! profile's session and copies every trade it broadcasts into the shared
+//! [`LiveTradeFeed`]. While the socket is connected the mirror and dip buyer
+//! read their recent trades from that buffer and wake as soon as trades
+//! arrive; while it is down they poll `get_recent_trades` as before. The
+//! socket drops replayed trades itself, and both loops still skip trade keys
+//! they have already processed, so switching between the two never acts on
+//! a trade twice.
+
+use crate::trade_executor::active_client;
+use crate::AppState;
+use rugplay_core::RecentTrade;
+use rugplay_networking::websocket::{ConnectionState, WebSocketManager};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tokio::sync::{broadcast, watch};
+use tracing::{debug, info, warn};
+
+/// Trades kept for loops reading the buffer
+pub const LIVE_BUFFER_CAPACITY: usize = 500;
+
+/// How often the connection is checked against the active profile
+const SUPERVISE_INTERVAL_SECS: u64 = 30;
+
+/// Minimum spacing of loop ticks woken by arriving trades
+const MIN_WAKE_GAP: Duration = Duration::from_secs(1);
+
+/// Latest trades from the socket, newest first
+pub struct LiveTradeFeed {
+    live: AtomicBool,
+    /// Current connection; updates from replaced connections are ignored
+    generation: AtomicU64,
+    trades: Mutex<VecDeque<RecentTrade>>,
+    /// Bumped on every new trade so waiting loops wake up
+    arrivals: watch::Sender<u64>,
+}
+
+impl Default for LiveTradeFeed {
+    fn default() -> Self {
+        Self {
+            live: AtomicBool::new(false),
+            generation: AtomicU64::new(0),
+            trades: Mutex::new(VecDeque::new()),
+            arrivals: watch::channel(0).0,
+        }
+    }
+}
+
+impl LiveTradeFeed {
+    pub fn is_live(&self) -> bool {
+        self.live.load(Ordering::Acquire)
+    }
+
+    pub fn set_live(&self, live: bool) {
+        self.live.store(live, Ordering::Release);
+    }
+
+    /// Start tracking a new connection, retiring the previous one
+    fn next_generation(&self) -> u64 {
+        self.set_live(false);
+        self.generation.fetch_add(1, Ordering::AcqRel) + 1
+    }
+
+    /// Set liveness on behalf of connection `generation`, if still current
+    fn set_live_for(&self, generation: u64, live: bool) {
+        if self.generation.load(Ordering::Acquire) == generation {
+            self.set_live(live);
+        }
+    }
+
+    pub fn push(&self, trade: RecentTrade) {
+        let mut trades = self.trades.lock().unwrap_or_else(|e| e.into_inner());
+        trades.push_front(trade);
+        trades.truncate(LIVE_BUFFER_CAPACITY);
+        drop(trades);
+        self.arrivals.send_modify(|n| *n = n.wrapping_add(1));
+    }
+
+    /// The newest `limit` trades, like `get_recent_trades` returns them.
+    /// `None` while the socket is down, so the caller polls instead.
+    pub fn recent(&self, limit: usize) -> Option<Vec<RecentTrade>> {
+        if !self.is_live() {
+            return None;
+        }
+        let trades = self.trades.lock().unwrap_or_else(|e| e.into_inner());
+        Some(trades.iter().take(limit).cloned().collect())
+    }
+
+    /// Wakes a loop when trades arrive
+    pub fn waker(&self) -> LiveWake {
+        LiveWake { arrivals: self.arrivals.subscribe(), last: Instant::now() }
+    }
+}
+
+/// A loop's view of trade arrivals
+pub struct LiveWake {
+    arrivals: watch::Receiver<u64>,
+    last: Instant,
+}
+
+impl LiveWake {
+    /// Wait for the next poll tick, or until trades arrive on the socket.
+    /// Bursts of trades are coalesced into one tick per `MIN_WAKE_GAP`.
+    pub async fn next_tick(&mut self, interval: &mut tokio::time::Interval) {
+        tokio::select! {
+            _ = interval.tick() => {}
+            Ok(()) = self.arrivals.changed() => {
+                let since = self.last.elapsed();
+                if since < MIN_WAKE_GAP {
+                    tokio::time::sleep(MIN_WAKE_GAP - since).await;
+                }
+                self.arrivals.borrow_and_update();
+            }
+        }
+        self.last = Instant::now();
+    }
+}
+
+/// Keep the live socket connected with the active profile's session
+pub fn spawn_live_feed(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        let feed = app_handle.state::<AppState>().live_feed.clone();
+        let mut manager: Option<(String, WebSocketManager)> = None;
+        loop {
+            let token = active_client(&app_handle).await.ok().map(|c| c.session_token().to_string());
+            let current = manager.as_ref().map(|(t, m)| (t.clone(), m.state()));
+            let reconnect = match (&token, &current) {
+                (None, _) => false,
+                (Some(token), Some((connected_with, state))) => {
+                    token != connected_with || *state == ConnectionState::Disconnected
+                }
+                (Some(_), None) => true,
+            };
+
+            if token.is_none() || reconnect {
+                if let Some((_, mut old)) = manager.take() {
+                    feed.next_generation();
+                    let _ = old.disconnect().await;
+                }
+            }
+            if let (Some(token), true) = (token, reconnect) {
+                let mut socket = WebSocketManager::new();
+                match socket.connect(&token).await {
+                    Ok(()) => {
+                        info!("Live trade feed connected, recent trades now pushed");
+                        forward_trades(feed.clone(), socket.subscribe(), socket.watch_state());
+                        manager = Some((token, socket));
+                    }
+                    Err(e) => debug!("Live trade feed unavailable, polling instead: {}", e),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(SUPERVISE_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Copy one connection's trades into the feed until its manager is dropped
+fn forward_trades(
+    feed: std::sync::Arc<LiveTradeFeed>,
+    mut trades: broadcast::Receiver<RecentTrade>,
+    mut state: watch::Receiver<ConnectionState>,
+) {
+    let generation = feed.next_generation();
+    feed.set_live_for(generation, *state.borrow() == ConnectionState::Connected);
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                trade = trades.recv() => match trade {
+                    Ok(trade) => feed.push(trade),
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("Live trade feed fell behind, {} trades missed", missed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                changed = state.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let connected = *state.borrow_and_update() == ConnectionState::Connected;
+                    debug!("Live trade feed {}", if connected { "live" } else { "down, polling" });
+                    feed.set_live_for(generation, connected);
+                }
+            }
+        }
+        feed.set_live_for(generation, false);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(timestamp: i64) -> RecentTrade {
+        serde_json::from_value(serde_json::json!({
+            "type": "BUY",
+            "username": "whale",
+            "amount": 1.0,
+            "coinSymbol": "AAA",
+            "totalValue": 10.0,
+            "price": 10.0,
+            "timestamp": timestamp,
+            "userId": "1",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_buffer_serves_newest_trades_only_while_live() {
+        let feed = LiveTradeFeed::default();
+        let mut wake = feed.waker();
+        for ts in 0..(LIVE_BUFFER_CAPACITY as i64 + 10) {
+            feed.push(trade(ts));
+        }
+        // Down: callers fall back to polling
+        assert!(feed.recent(5).is_none());
+
+        feed.set_live(true);
+        let newest: Vec<i64> = feed.recent(3).unwrap().iter().map(|t| t.timestamp).collect();
+        assert_eq!(newest, vec![509, 508, 507]);
+        assert_eq!(feed.recent(usize::MAX).unwrap().len(), LIVE_BUFFER_CAPACITY);
+
+        // Arrivals wake the loop before its (long) poll interval
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        interval.tick().await;
+        let woke = tokio::time::timeout(Duration::from_secs(5), wake.next_tick(&mut interval)).await;
+        assert!(woke.is_ok());
+    }
+}
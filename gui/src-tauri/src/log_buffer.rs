@@ -0,0 +1,60 @@
+//! Log Buffer — keeps the latest log lines for diagnostic reports
+//!
+//! A tracing layer alongside the console output that records every INFO,
+//! WARN and ERROR event into the managed [`RecentLogs`] ring, redacted as
+//! it is written.
+
+use crate::diagnostics::RecentLogs;
+use std::fmt::Write;
+use std::sync::Arc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Tracing layer feeding [`RecentLogs`]
+pub struct RecentLogsLayer(pub Arc<RecentLogs>);
+
+/// Formats an event's message followed by its other fields
+#[derive(Default)]
+struct LineVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for LineVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), value);
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for RecentLogsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        if *meta.level() > Level::INFO {
+            return;
+        }
+        let mut visitor = LineVisitor::default();
+        event.record(&mut visitor);
+        self.0.push(&format!(
+            "{} {} {}: {}{}",
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            meta.level(),
+            meta.target(),
+            visitor.message,
+            visitor.fields
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+//! Loop Snapshots — shutdown-save of automation loop memory
+//!
+//! The sniper, mirror, dip buyer and sentinel loops keep dedup sets,
+//! cooldowns and daily spend in memory. When a loop is shut down it writes
+//! that state to `loop_snapshots` for the active profile, and the next start
+//! restores it verbatim. Rebuilding state from `automation_log` is only the
+//! fallback when no snapshot exists (first run, crash, profile switch).
+
+use rugplay_persistence::sqlite;
+use rugplay_persistence::Database;
+use crate::seen_trades::SeenTrades;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+
+/// How long shutdown waits for a loop to finish its current tick
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// In-memory loop state that is saved on shutdown
+pub trait LoopSnapshot: Serialize + DeserializeOwned {
+    /// Module name the snapshot is stored under
+    const MODULE: &'static str;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DipBuyerSnapshot {
+    pub seen_trade_keys: SeenTrades,
+    pub coin_cooldowns: HashMap<String, i64>,
+    /// (timestamp, usd_amount)
+    pub daily_buys: Vec<(i64, f64)>,
+    /// symbol -> (trade_key, seen_at)
+    pub recent_dumps: HashMap<String, Vec<(String, i64)>>,
+}
+
+impl LoopSnapshot for DipBuyerSnapshot {
+    const MODULE: &'static str = "dipbuyer";
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SniperSnapshot {
+    /// (timestamp, usd_amount)
+    pub daily_spend: Vec<(i64, f64)>,
+}
+
+impl LoopSnapshot for SniperSnapshot {
+    const MODULE: &'static str = "sniper";
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MirrorSnapshot {
+    pub seen_trades: SeenTrades,
+}
+
+impl LoopSnapshot for MirrorSnapshot {
+    const MODULE: &'static str = "mirror";
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SentinelSnapshot {
+    /// symbol -> epoch when the cooldown expires
+    pub trigger_cooldowns: HashMap<String, i64>,
+    /// sentinel id -> consecutive sell failures
+    pub sell_failures: HashMap<i64, u32>,
+    /// sentinel id -> epoch before which a failed sell isn't retried
+    #[serde(default)]
+    pub sell_retry_at: HashMap<i64, i64>,
+}
+
+impl LoopSnapshot for SentinelSnapshot {
+    const MODULE: &'static str = "sentinel";
+}
+
+/// Save a loop's state for the active profile
+pub async fn save_snapshot<T: LoopSnapshot>(db: &Database, snapshot: &T) {
+    let profile_id = match sqlite::get_active_profile(db.pool()).await {
+        Ok(Some(p)) => p.id,
+        _ => return,
+    };
+    let json = match serde_json::to_string(snapshot) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("{}: failed to encode snapshot: {}", T::MODULE, e);
+            return;
+        }
+    };
+    match sqlite::save_loop_snapshot(db.pool(), T::MODULE, profile_id, &json).await {
+        Ok(()) => info!("{}: saved in-memory state snapshot", T::MODULE),
+        Err(e) => warn!("{}: failed to save snapshot: {}", T::MODULE, e),
+    }
+}
+
+/// Take the active profile's saved state, if the last shutdown left one
+pub async fn restore_snapshot<T: LoopSnapshot>(db: &Database) -> Option<T> {
+    let profile_id = sqlite::get_active_profile(db.pool()).await.ok()??.id;
+    let json = sqlite::take_loop_snapshot(db.pool(), T::MODULE, profile_id)
+        .await
+        .ok()??;
+    match serde_json::from_str(&json) {
+        Ok(snapshot) => {
+            info!("{}: restored in-memory state from shutdown snapshot", T::MODULE);
+            Some(snapshot)
+        }
+        Err(e) => {
+            warn!("{}: discarding unreadable snapshot: {}", T::MODULE, e);
+            None
+        }
+    }
+}
+
+/// Join handle of a loop task, so shutdown can wait for the snapshot save
+#[derive(Clone, Default)]
+pub struct LoopTask(Arc<Mutex<Option<JoinHandle<()>>>>);
+
+impl LoopTask {
+    pub fn spawn<F>(future: F) -> Self
+    where
+        F: std::future::Future<Output = ()> + Send + 'static,
+    {
+        Self(Arc::new(Mutex::new(Some(tokio::spawn(future)))))
+    }
+
+    /// Wait for the task to exit. Returns false if it didn't finish in time.
+    pub async fn join(&self, timeout: Duration) -> bool {
+        let task = self.0.lock().ok().and_then(|mut task| task.take());
+        match task {
+            Some(task) => tokio::time::timeout(timeout, task).await.is_ok(),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget_window::BudgetResetMode;
+    use crate::dipbuyer_signals::{in_coin_cooldown, within_daily_limits};
+
+    async fn setup() -> Database {
+        let db = Database::connect_in_memory().await.unwrap();
+        sqlx::query(
+            "INSERT INTO profiles (id, username, token_encrypted, iv, is_active) VALUES (1, 'tester', x'00', x'00', 1)",
+        )
+        .execute(db.pool())
+        .await
+        .unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_round_trips_verbatim() {
+        let db = setup().await;
+        let sentinel = SentinelSnapshot {
+            trigger_cooldowns: HashMap::from([("AAA".to_string(), 1_700_000_300)]),
+            sell_failures: HashMap::from([(42, 3)]),
+            sell_retry_at: HashMap::from([(42, 1_700_000_060)]),
+        };
+        save_snapshot(&db, &sentinel).await;
+
+        assert_eq!(restore_snapshot::<SentinelSnapshot>(&db).await, Some(sentinel));
+        // Consumed: a second start without a clean shutdown falls back to logs
+        assert_eq!(restore_snapshot::<SentinelSnapshot>(&db).await, None);
+        assert_eq!(restore_snapshot::<MirrorSnapshot>(&db).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_restored_dipbuyer_state_suppresses_duplicate_buy() {
+        let db = setup().await;
+        let now = 1_700_000_000;
+        let trade_key = "user1:AAA:1699999990:512.0000".to_string();
+
+        // State right after buying the AAA dip
+        let mut seen_trade_keys = SeenTrades::default();
+        seen_trade_keys.insert(trade_key.clone(), now);
+        let before = DipBuyerSnapshot {
+            seen_trade_keys,
+            coin_cooldowns: HashMap::from([("AAA".to_string(), now)]),
+            daily_buys: vec![(now, 250.0)],
+            recent_dumps: HashMap::from([("AAA".to_string(), vec![(trade_key.clone(), now)])]),
+        };
+        save_snapshot(&db, &before).await;
+
+        // After restart the same sell trade is fetched again
+        let after = restore_snapshot::<DipBuyerSnapshot>(&db).await.unwrap();
+        assert_eq!(after, before);
+        assert!(after.seen_trade_keys.contains(&trade_key));
+        assert!(in_coin_cooldown(&after.coin_cooldowns, &[], "AAA"));
+        // With a one-buy daily cap, the restored buy still counts
+        assert!(!within_daily_limits(&after.daily_buys, now + 60, BudgetResetMode::Rolling24h, 1, 0.0, 250.0));
+    }
+
+    #[tokio::test]
+    async fn test_join_waits_for_loop_exit() {
+        let stuck = LoopTask::spawn(std::future::pending());
+        assert!(!stuck.join(Duration::from_millis(20)).await);
+
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let task = LoopTask::spawn(async move {
+            let _ = rx.await;
+        });
+        tx.send(()).unwrap();
+        assert!(task.join(Duration::from_secs(1)).await);
+    }
+}
@@ -2,28 +2,48 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use rugplay_gui_lib::{commands, AppState};
+use rugplay_gui_lib::{commands, AppState, DipBuyerHandle, MirrorHandle, SentinelMonitorHandle, SniperHandle};
+use rugplay_gui_lib::diagnostics::RecentLogs;
+use rugplay_gui_lib::cache_warm_loop::spawn_cache_warm_task;
 use rugplay_gui_lib::dipbuyer::spawn_dipbuyer;
+use rugplay_gui_lib::entry_script::EntryScript;
 use rugplay_gui_lib::harvester::spawn_harvester;
+use rugplay_gui_lib::initial_sync::spawn_initial_sync;
+use rugplay_gui_lib::live_feed::spawn_live_feed;
+use rugplay_gui_lib::log_buffer::RecentLogsLayer;
+use rugplay_gui_lib::journal_reconcile_loop::spawn_journal_reconcile_task;
+use rugplay_gui_lib::kill_switch;
 use rugplay_gui_lib::mirror::spawn_mirror;
 use rugplay_gui_lib::mobile_server::MobileServerHandle;
+use rugplay_gui_lib::notification_outbox::SHUTDOWN_FLUSH_TIMEOUT;
 use rugplay_gui_lib::notifications::{NotificationHandle, load_notification_config};
+use rugplay_gui_lib::price_alert_loop::spawn_price_alert_task;
+use rugplay_gui_lib::equity_alert_loop::spawn_equity_alert_task;
+use rugplay_gui_lib::drawdown_loop::spawn_drawdown_task;
+use rugplay_gui_lib::profile_refresh::spawn_profile_refresh_task;
+use rugplay_gui_lib::retention::spawn_retention_task;
 use rugplay_gui_lib::trade_executor::spawn_trade_executor;
+use rugplay_gui_lib::trade_approval::spawn_approval_listener;
+use rugplay_gui_lib::trade_outcome::spawn_outcome_listeners;
 use rugplay_gui_lib::sentinel_loop::spawn_sentinel_monitor;
 use rugplay_gui_lib::sniper::spawn_sniper;
-use rugplay_persistence::TokenEncryptor;
+use rugplay_gui_lib::token_migration;
+use rugplay_gui_lib::warmup::WarmupStep;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tauri::Manager;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 fn main() {
-    // Initialize logging
+    // Initialize logging, keeping the latest lines for diagnostic reports
+    let recent_logs = Arc::new(RecentLogs::default());
     tracing_subscriber::registry()
         .with(
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "rugplay_gui=debug,rugplay_core=debug".into()),
         )
         .with(tracing_subscriber::fmt::layer())
+        .with(RecentLogsLayer(recent_logs.clone()))
         .init();
 
     tracing::info!("Starting RugPlay Manager");
@@ -58,6 +78,7 @@ fn main() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
         .manage(app_state)
+        .manage(recent_logs)
         .setup(|app| {
             let state = app.state::<AppState>();
             let state_clone = state.inner().clone();
@@ -70,6 +91,7 @@ fn main() {
                     return;
                 }
 
+                state_clone.spawn_pool_monitor();
                 tracing::info!("Database initialized, running encryption migration");
 
                 // Migrate tokens encrypted with legacy key [0u8; 32] to new machine-bound key
@@ -91,17 +113,91 @@ fn main() {
                     executor_handle.set_risk_limits(limits).await;
                     tracing::info!("Risk limits loaded from DB");
                 }
+                if commands::risk::load_paper_trading_from_db(&app_handle).await {
+                    executor_handle.set_paper_trading(true);
+                    tracing::warn!("Paper trading is on: automated orders are simulated");
+                }
+                if let Some(semi_auto) = commands::trade_queue::load_semi_auto_from_db(&app_handle).await {
+                    if semi_auto.enabled {
+                        tracing::info!("Semi-auto mode is on: automated trades wait for approval");
+                    }
+                    executor_handle.set_semi_auto_config(semi_auto).await;
+                }
+                if commands::watchlist::load_watchlist_only_from_db(&app_handle).await {
+                    executor_handle.set_watchlist_only(true).await;
+                }
+                if let Some(split) = commands::risk::load_split_sell_config_from_db(&app_handle).await {
+                    executor_handle.set_split_config(split).await;
+                }
+                if let Some(policy) = commands::risk::load_price_freshness_from_db(&app_handle).await {
+                    *app_handle.state::<AppState>().price_freshness.write().await = policy;
+                }
+                if let Some(precision) = commands::risk::load_coin_precision_from_db(&app_handle).await {
+                    *app_handle.state::<AppState>().coin_precision.write().await = precision;
+                }
+                if let Some(config) = commands::risk::load_entry_script_from_db(&app_handle).await {
+                    match EntryScript::compile(&config) {
+                        Ok(script) => *app_handle.state::<AppState>().entry_script.write().await = script.map(Arc::new),
+                        Err(e) => tracing::warn!("Entry script not loaded: {}", e),
+                    }
+                }
+                if let Some(policy) = commands::risk::load_adaptive_poll_from_db(&app_handle).await {
+                    *app_handle.state::<AppState>().poll_policy.write().await = policy;
+                }
+                if let Some(failover) = commands::auth::load_profile_failover_from_db(&app_handle).await {
+                    *app_handle.state::<AppState>().profile_failover.write().await = failover;
+                }
 
                 app_handle.manage(executor_handle.clone());
+                commands::watchlist::sync_tradable_universe(&app_handle).await;
+
+                // An emergency stop stays in force across restarts until released
+                let automation_killed = kill_switch::load_automation_killed(&app_handle).await;
+                if automation_killed {
+                    executor_handle.set_automation_halted(true);
+                    tracing::warn!("Emergency stop is active: automation stays halted until released");
+                }
+
+                // React to resolved trades (frontend event, rejection alerts)
+                spawn_outcome_listeners(app_handle.clone(), &executor_handle);
+                spawn_approval_listener(app_handle.clone(), &executor_handle);
+
+                // Warm up before automation trades: settings are loaded, the
+                // initial sync fetches the profile, portfolio and market
+                app_handle.state::<AppState>().warmup.complete(WarmupStep::Settings);
+                spawn_initial_sync(app_handle.clone());
+                spawn_live_feed(app_handle.clone());
 
                 // Spawn sentinel monitor (background SL/TP/TS checking loop)
                 let monitor_handle = spawn_sentinel_monitor(app_handle.clone(), executor_handle.clone());
+                if automation_killed {
+                    monitor_handle.pause().await;
+                }
                 app_handle.manage(monitor_handle);
 
                 // Spawn harvester (12h auto-claim loop)
                 let harvester_handle = spawn_harvester(app_handle.clone());
                 app_handle.manage(harvester_handle);
 
+                // Spawn history retention (periodic pruning of old log rows)
+                spawn_retention_task(app_handle.clone());
+
+                // Spawn profile refresh (keeps stored usernames/avatars current)
+                spawn_profile_refresh_task(app_handle.clone());
+
+                // Spawn price alerts (cross/move triggers for any coin)
+                spawn_price_alert_task(app_handle.clone());
+                spawn_equity_alert_task(app_handle.clone());
+
+                // Spawn drawdown sampler (portfolio-wide buy circuit breaker)
+                spawn_drawdown_task(app_handle.clone(), executor_handle.clone());
+
+                // Spawn cache warmer (keeps held coins' quotes fresh)
+                spawn_cache_warm_task(app_handle.clone());
+
+                // Spawn journal reconciliation (local trade journal vs server history)
+                spawn_journal_reconcile_task(app_handle.clone());
+
                 // Spawn sniper (auto-buy new coins loop)
                 let sniper_handle = spawn_sniper(app_handle.clone(), executor_handle.clone());
                 app_handle.manage(sniper_handle);
@@ -126,15 +222,28 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             // Auth commands
             commands::list_profiles,
+            commands::validate_token,
+            commands::refresh_profiles,
             commands::add_profile,
             commands::select_profile,
             commands::update_profile_token,
             commands::delete_profile,
             commands::logout,
             commands::get_active_profile,
+            commands::get_profile_failover,
+            commands::set_profile_failover,
+            commands::get_duplicate_token_policy,
+            commands::set_duplicate_token_policy,
+            commands::get_duplicate_profiles,
+            commands::check_encryption_health,
+            commands::run_encryption_migration,
+            // First-run setup commands
+            commands::get_setup_state,
+            commands::apply_setup_defaults,
             // Portfolio commands
             commands::get_portfolio,
             commands::get_portfolio_summary,
+            commands::rebalance_to_targets,
             commands::get_market,
             commands::get_coin_details,
             commands::get_coin_with_chart,
@@ -145,6 +254,8 @@ fn main() {
             commands::post_coin_comment,
             // Trading commands
             commands::execute_trade,
+            commands::manual_trade,
+            commands::check_trade_allowed,
             commands::get_balance,
             // Sentinel commands
             commands::create_sentinel,
@@ -153,8 +264,13 @@ fn main() {
             commands::delete_sentinel,
             commands::update_sentinel_price,
             commands::update_sentinel,
+            commands::set_sentinel_price_source,
+            commands::set_sentinel_trigger_sells,
+            commands::set_sentinel_tp_ladder,
+            commands::simulate_sentinel_path,
             commands::run_sentinel_check,
             commands::sync_sentinels,
+            commands::reconcile_sentinels,
             commands::update_all_sentinels,
             commands::toggle_all_sentinels,
             commands::purge_blacklisted_sentinels,
@@ -163,6 +279,11 @@ fn main() {
             commands::pause_sentinel_monitor,
             commands::resume_sentinel_monitor,
             commands::set_sentinel_monitor_interval,
+            commands::set_sentinel_max_sells_per_tick,
+            commands::set_sentinel_monitor_price_source,
+            commands::set_sentinel_min_liquidity,
+            commands::set_sentinel_sell_retry,
+            commands::set_sentinel_tp_gap,
             // Harvester commands
             commands::get_harvester_status,
             commands::set_harvester_enabled,
@@ -180,8 +301,10 @@ fn main() {
             commands::update_mirror_config,
             commands::add_tracked_whale,
             commands::remove_tracked_whale,
+            commands::set_whale_max_latency,
             commands::list_tracked_whales,
             commands::get_whale_profile,
+            commands::analyze_whale,
             commands::get_mirror_trades,
             // Dip Buyer commands
             commands::get_dipbuyer_status,
@@ -191,9 +314,44 @@ fn main() {
             commands::reset_dipbuyer_config,
             commands::get_dipbuyer_history,
             commands::get_automation_log,
+            commands::replay_decisions,
+            commands::validate_config_against_market,
+            // Acquisition pause commands
+            commands::get_acquisition_paused,
+            commands::set_acquisition_paused,
+            commands::get_emergency_stop,
+            commands::emergency_stop,
+            commands::release_emergency_stop,
+            // Bot status
+            commands::get_bot_status,
+            commands::generate_diagnostic_report,
+            commands::get_warmup_status,
             // Risk limit commands
             commands::get_risk_limits,
             commands::set_risk_limits,
+            commands::get_drawdown_status,
+            commands::get_paper_trading,
+            commands::set_paper_trading,
+            commands::get_split_sell_config,
+            commands::set_split_sell_config,
+            commands::get_price_freshness,
+            commands::set_price_freshness,
+            commands::get_coin_precision,
+            commands::set_coin_precision,
+            commands::get_adaptive_poll,
+            commands::set_adaptive_poll,
+            commands::get_entry_script,
+            commands::set_entry_script,
+            commands::get_coin_quote,
+            // Trade queue commands
+            commands::list_pending_trades,
+            commands::cancel_pending_trade,
+            commands::get_executor_stats,
+            commands::list_pending_approvals,
+            commands::approve_pending,
+            commands::reject_pending,
+            commands::get_semi_auto_config,
+            commands::set_semi_auto_config,
             // Notification commands
             commands::get_notification_config,
             commands::set_notification_config,
@@ -205,11 +363,30 @@ fn main() {
             commands::clear_automation_logs,
             commands::clear_triggered_sentinels,
             commands::clear_transaction_history,
-            commands::vacuum_database,
+            commands::run_db_maintenance,
+            commands::get_retention_config,
+            commands::set_retention_config,
+            commands::get_cache_warming,
+            commands::set_cache_warming,
+            commands::prune_history,
+            commands::get_config_history,
             // Transaction history commands
             commands::get_transactions,
             commands::get_traded_symbols,
             commands::log_transaction,
+            commands::export_trade_journal,
+            commands::reconcile_trade_journal,
+            commands::get_journal_reconciliation,
+            commands::add_to_watchlist,
+            commands::remove_from_watchlist,
+            commands::get_watchlist_with_prices,
+            commands::get_watchlist_only,
+            commands::set_watchlist_only,
+            commands::create_price_alert,
+            commands::get_price_alerts,
+            commands::delete_price_alert,
+            commands::get_equity_alerts,
+            commands::set_equity_alerts,
             // Mobile access commands
             commands::start_mobile_server,
             commands::stop_mobile_server,
@@ -218,6 +395,8 @@ fn main() {
             commands::set_mobile_default_role,
             commands::kick_mobile_session,
             commands::set_mobile_session_role,
+            commands::get_mobile_permissions,
+            commands::set_mobile_permissions,
             // User profile & leaderboard commands
             commands::get_user_profile_full,
             commands::get_leaderboard,
@@ -235,6 +414,26 @@ fn main() {
         .expect("error while building tauri application")
         .run(|app_handle, event| {
             if let tauri::RunEvent::Exit = event {
+                // Stop the automation loops so each saves its in-memory
+                // dedup/cooldown state for the next start
+                let sniper = app_handle.try_state::<SniperHandle>().map(|h| h.inner().clone());
+                let mirror = app_handle.try_state::<MirrorHandle>().map(|h| h.inner().clone());
+                let dipbuyer = app_handle.try_state::<DipBuyerHandle>().map(|h| h.inner().clone());
+                let sentinel = app_handle.try_state::<SentinelMonitorHandle>().map(|h| h.inner().clone());
+                tauri::async_runtime::block_on(async {
+                    tokio::join!(
+                        async { if let Some(h) = &sniper { h.shutdown().await } },
+                        async { if let Some(h) = &mirror { h.shutdown().await } },
+                        async { if let Some(h) = &dipbuyer { h.shutdown().await } },
+                        async { if let Some(h) = &sentinel { h.shutdown().await } },
+                    );
+                });
+
+                // Deliver notifications still queued (e.g. a final stop-loss)
+                if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+                    notif.flush(SHUTDOWN_FLUSH_TIMEOUT);
+                }
+
                 // Shutdown mobile server when app exits
                 let handle = app_handle.try_state::<MobileServerHandle>();
                 if let Some(handle) = handle {
@@ -251,103 +450,25 @@ fn main() {
 /// Migrate profile tokens from the legacy `[0u8; 32]` encryption key
 /// to the current machine-bound key derived via Argon2id.
 ///
-/// Runs once at startup. For each profile:
-/// 1. Try decrypting with the current (new) key — if it works, no migration needed.
-/// 2. If that fails, try decrypting with the legacy key.
-/// 3. If the legacy key works, re-encrypt with the new key and save back.
+/// Runs once at startup; `run_encryption_migration` re-runs it on demand.
 async fn migrate_encryption_keys(state: &AppState) {
-    use rugplay_persistence::sqlite;
-
     let db_guard = state.db.read().await;
     let Some(db) = db_guard.as_ref() else {
         tracing::warn!("DB not ready during migration — skipping");
         return;
     };
 
-    let profiles = match sqlite::list_profiles(db.pool()).await {
-        Ok(p) => p,
-        Err(e) => {
-            tracing::error!("Failed to list profiles for migration: {}", e);
-            return;
-        }
-    };
-
-    if profiles.is_empty() {
-        tracing::debug!("No profiles to migrate");
-        return;
-    }
-
-    // Build a legacy encryptor with the old [0u8; 32] key
-    let legacy_encryptor = match TokenEncryptor::new(&rugplay_persistence::LEGACY_KEY) {
-        Ok(e) => e,
-        Err(e) => {
-            tracing::error!("Failed to create legacy encryptor: {}", e);
-            return;
-        }
-    };
-
-    for profile in &profiles {
-        let encrypted = match sqlite::get_profile_token(db.pool(), profile.id).await {
-            Ok(Some(enc)) => enc,
-            Ok(None) => continue,
-            Err(e) => {
-                tracing::warn!("Could not read token for profile {}: {}", profile.id, e);
-                continue;
-            }
-        };
-
-        // Try current key first
-        if state.encryptor.decrypt(&encrypted).is_ok() {
-            tracing::debug!("Profile {} already uses current key", profile.id);
-            continue;
-        }
-
-        // Try legacy key
-        match legacy_encryptor.decrypt(&encrypted) {
-            Ok(plaintext) => {
-                tracing::info!(
-                    "Profile {} ({}): migrating from legacy key to machine key",
-                    profile.id,
-                    profile.username
-                );
-
-                // Re-encrypt with the new key
-                match state.encryptor.encrypt(&plaintext) {
-                    Ok(new_encrypted) => {
-                        if let Err(e) = sqlite::update_profile_token(
-                            db.pool(),
-                            profile.id,
-                            &new_encrypted,
-                        )
-                        .await
-                        {
-                            tracing::error!(
-                                "Failed to save migrated token for profile {}: {}",
-                                profile.id,
-                                e
-                            );
-                        } else {
-                            tracing::info!(
-                                "Profile {} migrated successfully",
-                                profile.id
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!(
-                            "Failed to re-encrypt token for profile {}: {}",
-                            profile.id,
-                            e
-                        );
-                    }
-                }
-            }
-            Err(_) => {
-                tracing::warn!(
-                    "Profile {} token cannot be decrypted with either key — token may be corrupt or from another machine",
-                    profile.id
-                );
-            }
+    match token_migration::migrate_profiles(db.pool(), &state.encryptor, None).await {
+        Ok(report) if report.migrated > 0 || report.unreadable > 0 || report.failed > 0 => {
+            tracing::info!(
+                "Encryption migration: {} migrated, {} current, {} unreadable, {} failed",
+                report.migrated,
+                report.already_current,
+                report.unreadable,
+                report.failed
+            );
         }
+        Ok(_) => tracing::debug!("No profile tokens to migrate"),
+        Err(e) => tracing::error!("Encryption migration failed: {}", e),
     }
 }
\ No newline at end of file
@@ -0,0 +1,80 @@
+//! Minimum Trade — keeping sub-minimum buys away from the server
+//!
+//! Rugplay refuses orders below a minimum size, which confidence-scaled dip
+//! buys and small mirror copies can fall under. The executor learns the
+//! minimum from the first rejection that names it; after that a smaller buy
+//! is rounded up when the gap fits the user's allowance, and otherwise
+//! skipped with a clear reason instead of being sent to fail.
+//!
+//! Sells are never held back: an exit is always worth attempting.
+
+use rugplay_core::parse_minimum_trade_usd;
+
+/// Whether a trade error is the server's minimum-size rejection
+pub fn is_below_minimum(error: &str) -> bool {
+    error.contains("Trade below server minimum")
+}
+
+/// The USD minimum a rejection names, if it is a minimum-size rejection
+pub fn learned_minimum(error: &str) -> Option<f64> {
+    if is_below_minimum(error) {
+        parse_minimum_trade_usd(error)
+    } else {
+        None
+    }
+}
+
+/// What to do with a buy given the known server minimum
+#[derive(Debug, Clone, PartialEq)]
+pub enum MinTradeCheck {
+    Proceed,
+    /// Submit this amount (the minimum) instead
+    RoundUp(f64),
+    /// Don't submit; the reason says why
+    Skip(String),
+}
+
+/// Check a buy of `amount` USD against `server_min`. Buys short of the
+/// minimum by at most `max_round_up_usd` are rounded up to it.
+pub fn check_buy(amount: f64, server_min: Option<f64>, max_round_up_usd: f64) -> MinTradeCheck {
+    let Some(minimum) = server_min.filter(|m| *m > 0.0) else {
+        return MinTradeCheck::Proceed;
+    };
+    if amount >= minimum {
+        return MinTradeCheck::Proceed;
+    }
+    if minimum - amount <= max_round_up_usd.max(0.0) {
+        return MinTradeCheck::RoundUp(minimum);
+    }
+    MinTradeCheck::Skip(format!(
+        "Skipped: ${:.2} is below the server minimum trade of ${:.2}",
+        amount, minimum
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sub_minimum_buy_is_skipped_with_reason() {
+        // Nothing is known until the server has rejected something
+        assert_eq!(check_buy(0.25, None, 0.0), MinTradeCheck::Proceed);
+
+        let error = "Trade API error: Trade below server minimum: Minimum trade amount is $1.00";
+        assert!(is_below_minimum(error));
+        let minimum = learned_minimum(error);
+        assert_eq!(minimum, Some(1.0));
+        assert_eq!(learned_minimum("Trade API error: Trade failed: HTTP 400 Bad Request: $5 fee"), None);
+
+        assert_eq!(
+            check_buy(0.25, minimum, 0.0),
+            MinTradeCheck::Skip("Skipped: $0.25 is below the server minimum trade of $1.00".to_string())
+        );
+        assert_eq!(check_buy(1.0, minimum, 0.0), MinTradeCheck::Proceed);
+
+        // Within the allowance the buy is rounded up instead
+        assert_eq!(check_buy(0.8, minimum, 0.25), MinTradeCheck::RoundUp(1.0));
+        assert!(matches!(check_buy(0.7, minimum, 0.25), MinTradeCheck::Skip(_)));
+    }
+}
@@ -4,16 +4,30 @@
 //! Copies trades with a configurable scale factor and max trade size.
 //! Optionally creates sentinels for bought coins.
 
-use crate::trade_executor::{TradeExecutorHandle, TradePriority};
+use crate::acquisition::BuySideModule;
+use crate::app_event::{emit_event, AppEvent};
+use crate::coin_risk::fetch_coin_risk;
+use crate::feed_depth::FeedDepth;
+use crate::loop_snapshot::{LoopTask, MirrorSnapshot, SHUTDOWN_TIMEOUT};
+use crate::seen_trades::SeenTrades;
+use crate::trade_amount::TradeAmount;
+use crate::trade_approval::HeldOrders;
+use crate::trade_executor::{AutomatedTrade, Submission, TradeExecutorHandle, TradePriority};
+use crate::trade_reason::TradeReason;
+use crate::wash_trades::{wash_trade_indices, WashSensitivity};
+use crate::whale_priority::{prioritize_whales, WatchedWhale, DEFAULT_MAX_TRACKED_WHALES};
+use crate::whale_score::{self, passes_min_score, score_trades, WhaleScore, WhaleTrade};
 use crate::AppState;
-use crate::save_automation_log;
-use rugplay_core::TradeType;
+use crate::poll_interval::{AdaptiveInterval, PollOutcome};
+use crate::protective_sentinel::ProtectiveSentinelConfig;
+use crate::{ensure_protective_sentinel, restore_loop_snapshot, retune_poll_interval, save_automation_log, save_loop_snapshot};
+use rugplay_core::{RecentTrade, TradeResponse, TradeType};
 use rugplay_networking::RugplayClient;
-use rugplay_persistence::sqlite;
+use rugplay_persistence::sqlite::{self, TrackedWhale};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tauri::{Emitter, Manager};
+use tauri::Manager;
 use tokio::sync::{watch, RwLock};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
@@ -21,6 +35,15 @@ use tracing::{debug, error, info, warn};
 /// Default polling interval for mirror (seconds)
 const DEFAULT_POLL_INTERVAL_SECS: u64 = 10;
 
+/// Seen trade keys kept for dedup before eviction kicks in
+const SEEN_TRADES_CAPACITY: usize = 500;
+
+/// Seen trade keys older than this are evicted first
+const SEEN_TRADES_WINDOW_SECS: i64 = 3600;
+
+/// How often the loop looks for a whale whose score is due for a refresh
+const SCORE_CHECK_INTERVAL_SECS: u64 = 60;
+
 // ─── Config ──────────────────────────────────────────────────────────
 
 /// Mirror configuration — persisted to DB settings table
@@ -48,10 +71,41 @@ pub struct MirrorConfig {
     /// Polling interval in seconds (0 = use default 10s)
     #[serde(default)]
     pub poll_interval_secs: u64,
+    /// Recent trades fetched per poll (0 = default 50). Grows automatically
+    /// while the feed turns over faster than the poll interval.
+    #[serde(default)]
+    pub recent_trades_depth: u32,
+    /// Most whales watched at once (0 = no limit). The best performers are
+    /// kept when more are tracked.
+    #[serde(default = "default_max_tracked_whales")]
+    pub max_tracked_whales: u32,
+    /// How eagerly a whale's self-trade round trips are left uncopied
+    #[serde(default)]
+    pub wash_trade_sensitivity: WashSensitivity,
+    /// Skip buys of coins whose risk score (0–100) is above this (0 = no limit)
+    #[serde(default)]
+    pub max_risk_score: f64,
+    /// Skip buys of whales scoring below this (0–100, 0 = no gate). Whales
+    /// with too little history to score are still copied.
+    #[serde(default)]
+    pub min_whale_score: f64,
 }
 
 fn default_true() -> bool { true }
 fn default_sell_pct() -> f64 { 100.0 }
+fn default_max_tracked_whales() -> u32 { DEFAULT_MAX_TRACKED_WHALES }
+
+impl MirrorConfig {
+    /// Sentinel put on every mirrored buy
+    pub fn protective_sentinel(&self) -> ProtectiveSentinelConfig {
+        ProtectiveSentinelConfig {
+            stop_loss_pct: self.stop_loss_pct,
+            take_profit_pct: self.take_profit_pct,
+            trailing_stop_pct: self.trailing_stop_pct,
+            sell_percentage: Some(self.sell_percentage),
+        }
+    }
+}
 
 impl Default for MirrorConfig {
     fn default() -> Self {
@@ -66,6 +120,11 @@ impl Default for MirrorConfig {
             sell_percentage: 100.0,
             skip_if_already_held: true,
             poll_interval_secs: 0,    // use default 10s
+            recent_trades_depth: 0,   // use default 50
+            max_tracked_whales: DEFAULT_MAX_TRACKED_WHALES,
+            wash_trade_sensitivity: WashSensitivity::Off,
+            max_risk_score: 0.0,      // disabled by default
+            min_whale_score: 0.0,     // disabled by default
         }
     }
 }
@@ -97,7 +156,8 @@ pub struct MirrorTickEvent {
     pub trades_checked: u32,
 }
 
-/// A record of a mirrored trade (kept in-memory for the session)
+/// A record of a mirrored trade. Stored in `mirror_trades`; the newest
+/// [`TRADE_HISTORY_CACHE`] are also kept in memory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MirrorTradeRecord {
@@ -112,6 +172,33 @@ pub struct MirrorTradeRecord {
     pub success: bool,
 }
 
+impl From<sqlite::MirrorTradeRow> for MirrorTradeRecord {
+    fn from(row: sqlite::MirrorTradeRow) -> Self {
+        Self {
+            whale_username: row.whale_username,
+            whale_user_id: row.whale_user_id,
+            coin_symbol: row.coin_symbol,
+            coin_name: row.coin_name,
+            trade_type: row.trade_type,
+            whale_amount_usd: row.whale_amount_usd,
+            our_amount_usd: row.our_amount_usd,
+            timestamp: row.timestamp,
+            success: row.success,
+        }
+    }
+}
+
+/// Mirrored trades kept in memory, oldest dropped first
+pub const TRADE_HISTORY_CACHE: usize = 200;
+
+fn push_trade_record(history: &mut Vec<MirrorTradeRecord>, record: MirrorTradeRecord) {
+    history.push(record);
+    if history.len() > TRADE_HISTORY_CACHE {
+        let drain = history.len() - TRADE_HISTORY_CACHE;
+        history.drain(..drain);
+    }
+}
+
 // ─── Handle ──────────────────────────────────────────────────────────
 
 /// Handle to control the mirror from Tauri commands
@@ -119,11 +206,16 @@ pub struct MirrorTradeRecord {
 pub struct MirrorHandle {
     enabled_tx: Arc<watch::Sender<bool>>,
     config: Arc<RwLock<MirrorConfig>>,
-    /// Set of tracked whale user_ids (synced from DB)
-    tracked_whales: Arc<RwLock<HashSet<String>>>,
-    /// History of mirrored trades (session-only, for UI display)
+    /// Every tracked whale (synced from DB)
+    whale_pool: Arc<RwLock<Vec<TrackedWhale>>>,
+    /// User ids actually watched, capped at `max_tracked_whales`, with each
+    /// whale's `max_latency_secs` override and score
+    tracked_whales: Arc<RwLock<HashMap<String, WatchedWhale>>>,
+    /// Cache of the newest `TRADE_HISTORY_CACHE` mirrored trades, loaded from
+    /// the `mirror_trades` table at startup and written through to it
     trade_history: Arc<RwLock<Vec<MirrorTradeRecord>>>,
     cancel: CancellationToken,
+    task: LoopTask,
 }
 
 impl MirrorHandle {
@@ -148,18 +240,37 @@ impl MirrorHandle {
     pub async fn set_config(&self, config: MirrorConfig) {
         *self.config.write().await = config;
         info!("Mirror config updated");
+        self.apply_whale_cap().await;
     }
 
-    pub async fn add_whale(&self, user_id: String) {
-        self.tracked_whales.write().await.insert(user_id);
+    /// Replace the tracked whales and re-apply the cap
+    pub async fn set_whales(&self, whales: Vec<TrackedWhale>) {
+        *self.whale_pool.write().await = whales;
+        self.apply_whale_cap().await;
     }
 
-    pub async fn remove_whale(&self, user_id: &str) {
-        self.tracked_whales.write().await.remove(user_id);
+    async fn apply_whale_cap(&self) {
+        let cap = self.config.read().await.max_tracked_whales;
+        let pool = self.whale_pool.read().await;
+        let selection = prioritize_whales(&pool, cap);
+        if !selection.dropped.is_empty() {
+            let names: Vec<&str> = selection.dropped.iter().map(|w| w.username.as_str()).collect();
+            warn!(
+                "Mirror: {} whales tracked but only {} watched, ignoring lowest-ranked: {}",
+                pool.len(),
+                cap,
+                names.join(", ")
+            );
+        }
+        *self.tracked_whales.write().await = pool
+            .iter()
+            .filter(|w| selection.active.contains(&w.user_id))
+            .map(|w| (w.user_id.clone(), WatchedWhale::from(w)))
+            .collect();
     }
 
     pub async fn get_tracked_whale_ids(&self) -> HashSet<String> {
-        self.tracked_whales.read().await.clone()
+        self.tracked_whales.read().await.keys().cloned().collect()
     }
 
     pub async fn get_trade_history(&self) -> Vec<MirrorTradeRecord> {
@@ -167,18 +278,27 @@ impl MirrorHandle {
     }
 
     pub async fn add_trade_record(&self, record: MirrorTradeRecord) {
-        let mut history = self.trade_history.write().await;
-        history.push(record);
-        // Keep last 200 records
-        if history.len() > 200 {
-            let drain = history.len() - 200;
-            history.drain(..drain);
-        }
+        push_trade_record(&mut *self.trade_history.write().await, record);
     }
 
     pub fn stop(&self) {
         self.cancel.cancel();
     }
+
+    /// Stop the loop and wait for it to save its in-memory state
+    pub async fn shutdown(&self) {
+        self.stop();
+        if !self.task.join(SHUTDOWN_TIMEOUT).await {
+            warn!("Mirror: loop did not stop in time, state snapshot skipped");
+        }
+    }
+}
+
+impl BuySideModule for MirrorHandle {
+    fn module_name(&self) -> &'static str { "mirror" }
+    fn is_enabled(&self) -> bool { MirrorHandle::is_enabled(self) }
+    fn enable(&self) { MirrorHandle::enable(self) }
+    fn disable(&self) { MirrorHandle::disable(self) }
 }
 
 // ─── Spawn ───────────────────────────────────────────────────────────
@@ -190,16 +310,28 @@ pub fn spawn_mirror(
 ) -> MirrorHandle {
     let (enabled_tx, enabled_rx) = watch::channel(false);
     let config = Arc::new(RwLock::new(MirrorConfig::default()));
-    let tracked_whales = Arc::new(RwLock::new(HashSet::new()));
+    let tracked_whales = Arc::new(RwLock::new(HashMap::new()));
     let trade_history = Arc::new(RwLock::new(Vec::new()));
     let cancel = CancellationToken::new();
 
+    let task = LoopTask::spawn(mirror_loop(
+        app_handle.clone(),
+        enabled_rx,
+        config.clone(),
+        tracked_whales.clone(),
+        trade_history.clone(),
+        executor,
+        cancel.clone(),
+    ));
+
     let handle = MirrorHandle {
         enabled_tx: Arc::new(enabled_tx),
-        config: config.clone(),
-        tracked_whales: tracked_whales.clone(),
-        trade_history: trade_history.clone(),
-        cancel: cancel.clone(),
+        config,
+        whale_pool: Arc::new(RwLock::new(Vec::new())),
+        tracked_whales,
+        trade_history,
+        cancel,
+        task,
     };
 
     // Load tracked whales from DB after a short delay
@@ -208,6 +340,9 @@ pub fn spawn_mirror(
     tokio::spawn(async move {
         tokio::time::sleep(std::time::Duration::from_secs(3)).await;
 
+        // Warm the trade history cache before the loop can add to it
+        load_trade_history_from_db(&restore_app, &restore_handle).await;
+
         // Load saved enabled state
         let saved_enabled = load_mirror_enabled(&restore_app).await;
         if saved_enabled {
@@ -224,16 +359,6 @@ pub fn spawn_mirror(
         load_whales_from_db(&restore_app, &restore_handle).await;
     });
 
-    tokio::spawn(mirror_loop(
-        app_handle,
-        enabled_rx,
-        config,
-        tracked_whales,
-        trade_history,
-        executor,
-        cancel,
-    ));
-
     handle
 }
 
@@ -243,7 +368,7 @@ async fn mirror_loop(
     app_handle: tauri::AppHandle,
     mut enabled_rx: watch::Receiver<bool>,
     config: Arc<RwLock<MirrorConfig>>,
-    tracked_whales: Arc<RwLock<HashSet<String>>>,
+    tracked_whales: Arc<RwLock<HashMap<String, WatchedWhale>>>,
     trade_history: Arc<RwLock<Vec<MirrorTradeRecord>>>,
     executor: TradeExecutorHandle,
     cancel: CancellationToken,
@@ -252,21 +377,58 @@ async fn mirror_loop(
 
     // Track already-seen trade keys with timestamps for LRU eviction
     // Key: "{user_id}:{coin_symbol}:{timestamp}:{trade_type}" -> epoch_seen
-    let mut seen_trades: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
-    let mut total_mirrored: u32 = load_mirror_total(&app_handle).await;
-    let mut last_mirrored_at: Option<String> = load_mirror_last_at(&app_handle).await;
+    // (restored from the snapshot saved at the last shutdown)
+    let mut seen_trades: SeenTrades =
+        restore_loop_snapshot::<MirrorSnapshot>(&app_handle)
+            .await
+            .map(|snapshot| snapshot.seen_trades)
+            .unwrap_or_default();
+    let mut feed_depth = FeedDepth::new(config.read().await.recent_trades_depth);
+    let mut progress = MirrorProgress {
+        total_mirrored: load_mirror_total(&app_handle).await,
+        last_mirrored_at: load_mirror_last_at(&app_handle).await,
+    };
+    // Copies waiting for the user's approval (semi-auto mode)
+    let mut held: HeldOrders<MirrorCopy> = HeldOrders::default();
 
     let mut interval = tokio::time::interval(
         std::time::Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS),
     );
+    let mut adaptive = AdaptiveInterval::new(interval.period());
+    let mut latest_trade_at: i64 = 0;
+    let mut score_checked_at: Option<std::time::Instant> = None;
+    let mut live_wake = app_handle.state::<AppState>().live_feed.waker();
 
     loop {
         tokio::select! {
             _ = cancel.cancelled() => {
+                let snapshot = MirrorSnapshot { seen_trades: std::mem::take(&mut seen_trades) };
+                save_loop_snapshot(&app_handle, &snapshot).await;
                 info!("Mirror cancelled, exiting");
                 return;
             }
-            _ = interval.tick() => {
+            _ = live_wake.next_tick(&mut interval) => {
+                app_handle.state::<AppState>().heartbeats.beat("mirror");
+
+                // Nothing acts until the startup warm-up has loaded its state
+                if !app_handle.state::<AppState>().warmup.is_ready() {
+                    debug!("Mirror: warming up, skipping tick");
+                    continue;
+                }
+
+                // Approved copies that filled since the last tick
+                if !held.is_empty() {
+                    let cfg = config.read().await.clone();
+                    for (copy, result) in held.take_settled() {
+                        match result {
+                            Ok(resp) => {
+                                record_mirror(&app_handle, &cfg, &copy, Some(resp), &trade_history, &mut progress).await;
+                            }
+                            Err(e) => info!("Mirror: proposal for {} not filled: {}", copy.trade.coin_symbol, e),
+                        }
+                    }
+                }
+
                 let enabled = *enabled_rx.borrow_and_update();
 
                 let whale_ids = tracked_whales.read().await.clone();
@@ -276,11 +438,11 @@ async fn mirror_loop(
                     let tick = MirrorTickEvent {
                         enabled,
                         tracked_whale_count: whale_ids.len() as u32,
-                        total_mirrored,
-                        last_mirrored_at: last_mirrored_at.clone(),
+                        total_mirrored: progress.total_mirrored,
+                        last_mirrored_at: progress.last_mirrored_at.clone(),
                         trades_checked: 0,
                     };
-                    let _ = app_handle.emit("mirror-tick", &tick);
+                    let _ = emit_event(&app_handle, AppEvent::MirrorTick(tick));
                     continue;
                 }
 
@@ -293,14 +455,36 @@ async fn mirror_loop(
                     }
                 };
 
-                // Fetch recent trades from live feed
-                let trades = match client.get_recent_trades(50).await {
-                    Ok(t) => t,
-                    Err(e) => {
-                        warn!("Mirror: failed to fetch recent trades: {}", e);
-                        continue;
-                    }
+                // Rescore at most one whale per check, stalest first
+                let score_due = score_checked_at
+                    .is_none_or(|at| at.elapsed().as_secs() >= SCORE_CHECK_INTERVAL_SECS);
+                if score_due {
+                    score_checked_at = Some(std::time::Instant::now());
+                    refresh_stale_whale_score(&app_handle, &client).await;
+                }
+
+                // Recent trades from the live socket, or polled while it is down
+                feed_depth.set_configured(config.read().await.recent_trades_depth);
+                let live_trades = app_handle.state::<AppState>().live_feed.recent(feed_depth.limit() as usize);
+                let trades = match live_trades {
+                    Some(t) => t,
+                    None => match client.get_recent_trades(feed_depth.limit()).await {
+                        Ok(t) => t,
+                        Err(e) => {
+                            warn!("Mirror: failed to fetch recent trades: {}", e);
+                            let outcome = PollOutcome::from_error(&e.to_string());
+                            retune_poll_interval(&app_handle, &mut adaptive, &mut interval, outcome, "Mirror").await;
+                            continue;
+                        }
+                    },
                 };
+                let timestamps: Vec<i64> = trades.iter().map(|t| t.timestamp).collect();
+                let newest = timestamps.iter().copied().max().unwrap_or(latest_trade_at);
+                let outcome = if newest > latest_trade_at { PollOutcome::Active } else { PollOutcome::Quiet };
+                latest_trade_at = latest_trade_at.max(newest);
+                if feed_depth.observe(&timestamps) {
+                    info!("Mirror: trade feed outran the poll window, fetching {} trades", feed_depth.limit());
+                }
 
                 // Filter out transfers — only mirror actual BUY/SELL trades
                 let trades: Vec<_> = trades
@@ -321,16 +505,24 @@ async fn mirror_loop(
                 } else {
                     DEFAULT_POLL_INTERVAL_SECS
                 };
-                let current_period = interval.period();
-                if current_period != std::time::Duration::from_secs(desired_interval) {
+                if adaptive.set_configured(std::time::Duration::from_secs(desired_interval)) {
                     interval = tokio::time::interval(std::time::Duration::from_secs(desired_interval));
                     info!("Mirror: poll interval updated to {}s", desired_interval);
+                } else {
+                    retune_poll_interval(&app_handle, &mut adaptive, &mut interval, outcome, "Mirror").await;
                 }
 
-                // Fetch current holdings to check skip_if_already_held
-                let held_symbols: HashSet<String> = if cfg.skip_if_already_held {
+                // Fetch current holdings for skip_if_already_held and to keep
+                // whale exits from coins we hold out of the wash filter
+                let mut holdings_known = false;
+                let held_symbols: HashSet<String> = if cfg.skip_if_already_held
+                    || cfg.wash_trade_sensitivity != WashSensitivity::Off
+                {
                     match client.get_portfolio().await {
-                        Ok(portfolio) => portfolio.coin_holdings.iter().map(|h| h.symbol.clone()).collect(),
+                        Ok(portfolio) => {
+                            holdings_known = true;
+                            portfolio.coin_holdings.iter().map(|h| h.symbol.clone()).collect()
+                        }
                         Err(e) => {
                             debug!("Mirror: couldn't fetch portfolio for holdings check: {}", e);
                             HashSet::new()
@@ -340,11 +532,13 @@ async fn mirror_loop(
                     HashSet::new()
                 };
 
-                for trade in &trades {
+                let wash_trades = wash_trade_indices(&trades, cfg.wash_trade_sensitivity);
+
+                for (index, trade) in trades.iter().enumerate() {
                     trades_checked += 1;
 
                     // Check if this trade is from a tracked whale
-                    if !whale_ids.contains(&trade.user_id) {
+                    if !whale_ids.contains_key(&trade.user_id) {
                         continue;
                     }
 
@@ -353,43 +547,57 @@ async fn mirror_loop(
                         "{}:{}:{}:{}",
                         trade.user_id, trade.coin_symbol, trade.timestamp, trade.trade_type
                     );
-                    if seen_trades.contains_key(&trade_key) {
-                        continue;
-                    }
-
-                    // Check latency — skip if trade is too old
-                    let trade_age_secs = (now - trade.timestamp) as f64;
-                    if trade_age_secs > cfg.max_latency_secs {
-                        debug!(
-                            "Mirror: skipping old whale trade from {} — {:.1}s old (max {:.1}s)",
-                            trade.username, trade_age_secs, cfg.max_latency_secs
-                        );
-                        // Still mark as seen so we don't re-process next tick
-                        seen_trades.insert(trade_key, now);
+                    if seen_trades.contains(&trade_key) {
                         continue;
                     }
 
-                    // Skip if user already holds this coin (for BUY trades)
-                    if trade.is_buy() && cfg.skip_if_already_held && held_symbols.contains(&trade.coin_symbol) {
-                        debug!("Mirror: skipping BUY of {} (already held)", trade.coin_symbol);
+                    // Copying a whale's self-trades just churns fees. A sell of a
+                    // coin we (may) hold is always copied: dropping it would
+                    // leave our copied position open.
+                    let exits_our_position =
+                        !trade.is_buy() && (!holdings_known || held_symbols.contains(&trade.coin_symbol));
+                    if wash_trades.contains(&index) && !exits_our_position {
+                        debug!("Mirror: skipping likely wash trade by {} on {}", trade.username, trade.coin_symbol);
                         seen_trades.insert(trade_key, now);
                         continue;
                     }
 
-                    // Calculate scaled amount
-                    let scaled_usd = trade.total_value * cfg.scale_factor;
-                    let capped_usd = if cfg.max_trade_usd > 0.0 {
-                        scaled_usd.min(cfg.max_trade_usd)
-                    } else {
-                        scaled_usd
+                    // Latency, holdings, scaling and minimum size
+                    let whale = whale_ids.get(&trade.user_id).copied().unwrap_or_default();
+                    let order = match mirror_order(&cfg, trade, now, &held_symbols, whale) {
+                        Ok(order) => order,
+                        Err(skip) => {
+                            debug!("Mirror: skipping {} trade of {} ({})", trade.username, trade.coin_symbol, skip);
+                            // Still mark as seen so we don't re-process next tick
+                            seen_trades.insert(trade_key, now);
+                            continue;
+                        }
                     };
-
-                    // Skip very small trades
-                    if capped_usd < 1.0 {
-                        seen_trades.insert(trade_key.clone(), now);
-                        continue;
+                    // Shared coin risk score on buys, against the mirror's own limit.
+                    // A coin that can't be scored is not copied.
+                    if trade.is_buy() && cfg.max_risk_score > 0.0 {
+                        let too_risky = match fetch_coin_risk(&client, &trade.coin_symbol).await {
+                            Ok((risk, _)) if risk.exceeds(cfg.max_risk_score) => {
+                                debug!(
+                                    "Mirror: skipping {} buy of {} (risk {:.0} > {:.0}: {})",
+                                    trade.username, trade.coin_symbol, risk.score, cfg.max_risk_score, risk.breakdown()
+                                );
+                                true
+                            }
+                            Ok(_) => false,
+                            Err(e) => {
+                                debug!("Mirror: risk check failed for {}: {}", trade.coin_symbol, e);
+                                true
+                            }
+                        };
+                        if too_risky {
+                            seen_trades.insert(trade_key, now);
+                            continue;
+                        }
                     }
 
+                    let capped_usd = order.value_usd;
+
                     info!(
                         "Mirror: Whale {} {} ${:.2} of {} — copying ${:.2} (scale {:.0}%)",
                         trade.username,
@@ -400,165 +608,45 @@ async fn mirror_loop(
                         cfg.scale_factor * 100.0,
                     );
 
-                    // Determine trade type
-                    let trade_type = if trade.is_buy() {
-                        TradeType::Buy
-                    } else {
-                        TradeType::Sell
-                    };
-
-                    // For SELL trades, we need coin amount not USD
-                    // For BUY trades, API expects USD amount
-                    let amount = match trade_type {
-                        TradeType::Buy => capped_usd,
-                        TradeType::Sell => {
-                            // Calculate coin amount from USD value and price
-                            if trade.price > 0.0 {
-                                let coins = capped_usd / trade.price;
-                                // Truncate to 8 decimals (server precision)
-                                (coins * 1e8).floor() / 1e8
-                            } else {
-                                warn!("Mirror: price is 0 for sell, skipping");
-                                seen_trades.insert(trade_key, now);
-                                continue;
-                            }
-                        }
-                    };
-
                     // Submit trade through executor
-                    let reason = format!(
-                        "Mirror: copying {} {} ${:.2} of {}",
-                        trade.username, trade.trade_type, trade.total_value, trade.coin_symbol
-                    );
-
-                    let success = match executor
-                        .submit_trade(
-                            trade.coin_symbol.clone(),
-                            trade_type.clone(),
-                            amount,
-                            TradePriority::Normal,
-                            reason,
-                        )
-                        .await
-                    {
-                        Ok(_resp) => {
-                            info!(
-                                "Mirror: successfully mirrored {} {} ${:.2} of {}",
-                                trade.username, trade.trade_type, capped_usd, trade.coin_symbol
-                            );
-                            save_automation_log(
-                                &app_handle,
-                                "mirror",
-                                &trade.coin_symbol,
-                                &trade.coin_name,
-                                &trade.trade_type.to_uppercase(),
-                                capped_usd,
-                                &serde_json::json!({
-                                    "whaleUsername": trade.username,
-                                    "whaleAmountUsd": trade.total_value,
-                                }).to_string(),
-                            ).await;
-                            true
+                    let copy = MirrorCopy { trade: trade.clone(), order };
+                    match submit_mirror(&executor, &copy).await {
+                        Ok(Submission::Filled(resp)) => {
+                            record_mirror(&app_handle, &cfg, &copy, Some(resp), &trade_history, &mut progress).await;
+                        }
+                        Ok(Submission::Held(proposal)) => {
+                            info!("Mirror: copy of {} on {} proposed for approval (#{})",
+                                trade.username, trade.coin_symbol, proposal.approval.id);
+                            held.hold(proposal, copy);
                         }
                         Err(e) => {
                             error!(
                                 "Mirror: failed to execute mirrored trade for {}: {}",
                                 trade.coin_symbol, e
                             );
-                            false
-                        }
-                    };
-
-                    // Record the mirrored trade
-                    let record = MirrorTradeRecord {
-                        whale_username: trade.username.clone(),
-                        whale_user_id: trade.user_id.clone(),
-                        coin_symbol: trade.coin_symbol.clone(),
-                        coin_name: trade.coin_name.clone(),
-                        trade_type: trade.trade_type.clone(),
-                        whale_amount_usd: trade.total_value,
-                        our_amount_usd: capped_usd,
-                        timestamp: chrono::Utc::now().to_rfc3339(),
-                        success,
-                    };
-
-                    // Store in history
-                    {
-                        let mut history = trade_history.write().await;
-                        history.push(record.clone());
-                        if history.len() > 200 {
-                            let drain = history.len() - 200;
-                            history.drain(..drain);
+                            record_mirror(&app_handle, &cfg, &copy, None, &trade_history, &mut progress).await;
                         }
                     }
 
-                    // Emit event to frontend
-                    let event = MirrorTriggeredEvent {
-                        whale_username: trade.username.clone(),
-                        whale_user_id: trade.user_id.clone(),
-                        coin_symbol: trade.coin_symbol.clone(),
-                        coin_name: trade.coin_name.clone(),
-                        whale_amount_usd: trade.total_value,
-                        our_amount_usd: capped_usd,
-                        trade_type: trade.trade_type.clone(),
-                        latency_secs: trade_age_secs,
-                    };
-                    let _ = app_handle.emit("mirror-triggered", &event);
-
-                    // Send notification
-                    if let Some(notif) = try_notify(&app_handle) {
-                        let trade_type_str = if trade.is_buy() { "BUY" } else { "SELL" };
-                        notif
-                            .notify_trade_executed(
-                                &trade.coin_symbol,
-                                &format!("Mirror {}", trade_type_str),
-                                capped_usd,
-                            )
-                            .await;
-                    }
-
-                    // Auto-create sentinel for buys
-                    if success && trade.is_buy() && cfg.auto_create_sentinel {
-                        create_auto_sentinel(
-                            &app_handle,
-                            &trade.coin_symbol,
-                            trade.price,
-                            cfg.stop_loss_pct,
-                            cfg.take_profit_pct,
-                            cfg.trailing_stop_pct,
-                            cfg.sell_percentage,
-                        )
-                        .await;
-                    }
-
                     // Mark as seen
                     seen_trades.insert(trade_key, now);
-
-                    total_mirrored += 1;
-                    last_mirrored_at = Some(chrono::Utc::now().to_rfc3339());
-
-                    // Persist stats
-                    save_mirror_total(&app_handle, total_mirrored).await;
-                    save_mirror_last_at(&app_handle, last_mirrored_at.as_deref().unwrap_or(""))
-                        .await;
                 }
 
-                // LRU eviction: remove entries older than 1 hour (instead of clearing all)
-                if seen_trades.len() > 500 {
-                    let one_hour_ago = now - 3600;
-                    seen_trades.retain(|_, ts| *ts > one_hour_ago);
-                    debug!("Mirror: evicted old seen_trades, {} remaining", seen_trades.len());
+                // Evict the oldest keys once over capacity (never clear recent ones)
+                let evicted = seen_trades.evict(now, SEEN_TRADES_WINDOW_SECS, SEEN_TRADES_CAPACITY);
+                if evicted > 0 {
+                    debug!("Mirror: evicted {} old seen_trades, {} remaining", evicted, seen_trades.len());
                 }
 
                 // Emit tick event
                 let tick = MirrorTickEvent {
                     enabled: true,
                     tracked_whale_count: whale_ids.len() as u32,
-                    total_mirrored,
-                    last_mirrored_at: last_mirrored_at.clone(),
+                    total_mirrored: progress.total_mirrored,
+                    last_mirrored_at: progress.last_mirrored_at.clone(),
                     trades_checked,
                 };
-                let _ = app_handle.emit("mirror-tick", &tick);
+                let _ = emit_event(&app_handle, AppEvent::MirrorTick(tick));
             }
         }
     }
@@ -566,6 +654,205 @@ async fn mirror_loop(
 
 // ─── Helpers ─────────────────────────────────────────────────────────
 
+/// How the mirror copies one whale trade
+#[derive(Debug, Clone, PartialEq)]
+struct MirrorOrder {
+    trade_type: TradeType,
+    amount: TradeAmount,
+    /// Size of the copy in USD
+    value_usd: f64,
+    /// Seconds between the whale's trade and `now`
+    latency_secs: f64,
+}
+
+/// Size a copy of a tracked whale's trade, or say why it isn't copied.
+/// `whale` carries the whale's own `max_latency_secs`, if set, and score.
+fn mirror_order(
+    cfg: &MirrorConfig,
+    trade: &RecentTrade,
+    now: i64,
+    held_symbols: &HashSet<String>,
+    whale: WatchedWhale,
+) -> Result<MirrorOrder, String> {
+    let latency_secs = (now - trade.timestamp) as f64;
+    let max_latency_secs = whale.max_latency_secs.unwrap_or(cfg.max_latency_secs);
+    if latency_secs > max_latency_secs {
+        return Err(format!("{:.1}s old (max {:.1}s)", latency_secs, max_latency_secs));
+    }
+
+    // Sells are still copied, so positions opened on a whale's lead get closed with it
+    if trade.is_buy() && !passes_min_score(whale.score, cfg.min_whale_score) {
+        return Err(format!(
+            "whale score {:.0} below {:.0}",
+            whale.score.unwrap_or_default(),
+            cfg.min_whale_score
+        ));
+    }
+
+    if trade.is_buy() && cfg.skip_if_already_held && held_symbols.contains(&trade.coin_symbol) {
+        return Err("already held".to_string());
+    }
+
+    let scaled_usd = trade.total_value * cfg.scale_factor;
+    // f64::min(NaN, cap) returns the cap, so check before capping
+    if !scaled_usd.is_finite() {
+        return Err("invalid scaled amount".to_string());
+    }
+    let value_usd = if cfg.max_trade_usd > 0.0 {
+        scaled_usd.min(cfg.max_trade_usd)
+    } else {
+        scaled_usd
+    };
+    if value_usd < 1.0 {
+        return Err(format!("copy of ${:.2} is too small", value_usd));
+    }
+
+    // Both sides are sized in USD; the executor converts a sell to coins
+    // at the price when it runs, not the whale's possibly stale one
+    let trade_type = if trade.is_buy() { TradeType::Buy } else { TradeType::Sell };
+    let amount = TradeAmount::Usd(value_usd);
+
+    Ok(MirrorOrder { trade_type, amount, value_usd, latency_secs })
+}
+
+/// Running totals of the mirror's copies
+struct MirrorProgress {
+    total_mirrored: u32,
+    last_mirrored_at: Option<String>,
+}
+
+/// A whale trade and how the mirror copies it
+#[derive(Debug, Clone)]
+struct MirrorCopy {
+    trade: RecentTrade,
+    order: MirrorOrder,
+}
+
+/// Book a copy once it is placed: `fill` is `None` if it failed. Records it
+/// in the history, emits the event and notification, creates the protective
+/// sentinel for a bought coin and updates the stats.
+async fn record_mirror(
+    app_handle: &tauri::AppHandle,
+    cfg: &MirrorConfig,
+    copy: &MirrorCopy,
+    fill: Option<TradeResponse>,
+    trade_history: &RwLock<Vec<MirrorTradeRecord>>,
+    progress: &mut MirrorProgress,
+) {
+    let MirrorCopy { trade, order } = copy;
+    let capped_usd = order.value_usd;
+    if fill.is_some() {
+        info!(
+            "Mirror: successfully mirrored {} {} ${:.2} of {}",
+            trade.username, trade.trade_type, capped_usd, trade.coin_symbol
+        );
+        save_automation_log(
+            app_handle,
+            "mirror",
+            &trade.coin_symbol,
+            &trade.coin_name,
+            &trade.trade_type.to_uppercase(),
+            capped_usd,
+            &serde_json::json!({
+                "whaleUsername": trade.username,
+                "whaleAmountUsd": trade.total_value,
+            }).to_string(),
+        ).await;
+    }
+    let success = fill.is_some();
+    // Paper fills stay out of the stored history and stats
+    let paper = fill.as_ref().is_some_and(|resp| resp.paper);
+
+    // Record the mirrored trade
+    let record = MirrorTradeRecord {
+        whale_username: trade.username.clone(),
+        whale_user_id: trade.user_id.clone(),
+        coin_symbol: trade.coin_symbol.clone(),
+        coin_name: trade.coin_name.clone(),
+        trade_type: trade.trade_type.clone(),
+        whale_amount_usd: trade.total_value,
+        our_amount_usd: capped_usd,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        success,
+    };
+
+    // Store in history
+    if !paper {
+        save_mirror_trade(app_handle, &record).await;
+    }
+    push_trade_record(&mut *trade_history.write().await, record);
+
+    // Emit event to frontend
+    let event = MirrorTriggeredEvent {
+        whale_username: trade.username.clone(),
+        whale_user_id: trade.user_id.clone(),
+        coin_symbol: trade.coin_symbol.clone(),
+        coin_name: trade.coin_name.clone(),
+        whale_amount_usd: trade.total_value,
+        our_amount_usd: capped_usd,
+        trade_type: trade.trade_type.clone(),
+        latency_secs: order.latency_secs,
+    };
+    let _ = emit_event(app_handle, AppEvent::MirrorTriggered(event));
+
+    // Send notification
+    if let Some(notif) = try_notify(app_handle) {
+        let trade_type_str = if trade.is_buy() { "BUY" } else { "SELL" };
+        notif
+            .notify_trade_executed(
+                &trade.coin_symbol,
+                &format!("Mirror {}", trade_type_str),
+                capped_usd,
+            )
+            .await;
+    }
+
+    // Auto-create sentinel for buys
+    if let Some(resp) = fill.filter(|_| trade.is_buy() && cfg.auto_create_sentinel) {
+        ensure_protective_sentinel(
+            app_handle,
+            "Mirror",
+            &trade.coin_symbol,
+            &resp,
+            &cfg.protective_sentinel(),
+        )
+        .await;
+    }
+
+    progress.total_mirrored += 1;
+    progress.last_mirrored_at = Some(chrono::Utc::now().to_rfc3339());
+
+    // Persist stats
+    if !paper {
+        save_mirror_total(app_handle, progress.total_mirrored).await;
+        save_mirror_last_at(app_handle, progress.last_mirrored_at.as_deref().unwrap_or(""))
+            .await;
+    }
+}
+
+/// Place a copy of a whale's trade through the executor. In semi-auto mode
+/// it comes back held for approval at once.
+async fn submit_mirror(executor: &TradeExecutorHandle, copy: &MirrorCopy) -> Result<Submission, String> {
+    let MirrorCopy { trade, order } = copy;
+    let reason = TradeReason::Mirror {
+        whale: trade.username.clone(),
+        whale_side: trade.trade_type.clone(),
+        value_usd: trade.total_value,
+        symbol: trade.coin_symbol.clone(),
+    };
+    executor
+        .submit_automated(AutomatedTrade {
+            symbol: trade.coin_symbol.clone(),
+            trade_type: order.trade_type,
+            amount: order.amount,
+            priority: TradePriority::Normal,
+            reason,
+            source: "mirror".to_string(),
+            quoted_price: Some(trade.price),
+        })
+        .await
+}
+
 /// Try to get the NotificationHandle without panicking
 fn try_notify(app_handle: &tauri::AppHandle) -> Option<crate::notifications::NotificationHandle> {
     app_handle
@@ -588,47 +875,86 @@ async fn get_active_client(app_handle: &tauri::AppHandle) -> Option<RugplayClien
     let encrypted = sqlite::get_profile_token(pool, active.id).await.ok()??;
     let token = state.encryptor.decrypt(&encrypted).ok()?;
 
-    Some(RugplayClient::new_with_cache(&token, state.coin_cache.clone()))
+    Some(state.api_client(&token))
 }
 
-/// Create a sentinel for a mirrored buy
-async fn create_auto_sentinel(
+/// A whale's score with the profile it was computed from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WhaleAnalysis {
+    pub user_id: String,
+    pub username: String,
+    pub portfolio_value: f64,
+    pub total_volume: f64,
+    /// Whether the whale is tracked (and the score was saved with it)
+    pub tracked: bool,
+    pub analyzed_at: String,
+    #[serde(flatten)]
+    pub score: WhaleScore,
+}
+
+/// Fetch a whale's public profile and score their recent transactions.
+/// For a tracked whale the score is saved and the mirror picks it up.
+pub async fn analyze_whale(
     app_handle: &tauri::AppHandle,
-    symbol: &str,
-    entry_price: f64,
-    stop_loss_pct: f64,
-    take_profit_pct: f64,
-    trailing_stop_pct: Option<f64>,
-    sell_percentage: f64,
-) {
-    let state = app_handle.state::<AppState>();
-    let db_guard = state.db.read().await;
-    let db = match db_guard.as_ref() {
-        Some(d) => d,
-        None => return,
+    client: &RugplayClient,
+    user_id: &str,
+) -> Result<WhaleAnalysis, String> {
+    let profile = client
+        .get_user_profile(user_id)
+        .await
+        .map_err(|e| format!("Failed to fetch profile: {}", e))?;
+    let trades: Vec<WhaleTrade> = profile.recent_transactions.iter().filter_map(WhaleTrade::from_json).collect();
+    let score = score_trades(&trades);
+
+    let tracked = {
+        let state = app_handle.state::<AppState>();
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let detail = serde_json::to_string(&score).map_err(|e| e.to_string())?;
+        sqlite::update_whale_score(db.pool(), user_id, score.score(), &detail)
+            .await
+            .map_err(|e| e.to_string())?
     };
-    let pool = db.pool();
+    if tracked {
+        if let Some(handle) = app_handle.try_state::<MirrorHandle>() {
+            load_whales_from_db(app_handle, &handle).await;
+        }
+    }
 
-    let active = match sqlite::get_active_profile(pool).await {
-        Ok(Some(p)) => p,
-        _ => return,
-    };
+    Ok(WhaleAnalysis {
+        user_id: user_id.to_string(),
+        username: profile.profile.username,
+        portfolio_value: profile.stats.total_portfolio_value_f64(),
+        total_volume: profile.stats.total_volume(),
+        tracked,
+        analyzed_at: chrono::Utc::now().to_rfc3339(),
+        score,
+    })
+}
 
-    if let Err(e) = sqlite::upsert_sentinel(
-        pool,
-        active.id,
-        symbol,
-        Some(stop_loss_pct),
-        Some(take_profit_pct),
-        trailing_stop_pct,
-        sell_percentage,
-        entry_price,
-    )
-    .await
-    {
-        error!("Mirror: failed to auto-create sentinel for {}: {}", symbol, e);
-    } else {
-        info!("Mirror: auto-created sentinel for {} at entry ${:.8}", symbol, entry_price);
+/// Rescore the tracked whale whose score is oldest, if it is due
+async fn refresh_stale_whale_score(app_handle: &tauri::AppHandle, client: &RugplayClient) {
+    let stalest = {
+        let state = app_handle.state::<AppState>();
+        let db_guard = state.db.read().await;
+        let Some(db) = db_guard.as_ref() else { return };
+        let now = chrono::Utc::now();
+        sqlite::list_whales(db.pool())
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|w| whale_score::is_stale(w.scored_at.as_deref(), now))
+            .min_by(|a, b| a.scored_at.cmp(&b.scored_at))
+    };
+    let Some(whale) = stalest else { return };
+
+    match analyze_whale(app_handle, client, &whale.user_id).await {
+        Ok(analysis) => match analysis.score.score() {
+            Some(score) => info!("Mirror: whale {} scored {:.1}", whale.username, score),
+            None => info!("Mirror: whale {} has too little history to score", whale.username),
+        },
+        Err(e) => debug!("Mirror: couldn't score whale {}: {}", whale.username, e),
     }
 }
 
@@ -643,11 +969,8 @@ async fn load_whales_from_db(app_handle: &tauri::AppHandle, handle: &MirrorHandl
 
     match sqlite::list_whales(db.pool()).await {
         Ok(whales) => {
-            let mut ids = handle.tracked_whales.write().await;
-            for w in &whales {
-                ids.insert(w.user_id.clone());
-            }
             info!("Mirror: loaded {} tracked whales from DB", whales.len());
+            handle.set_whales(whales).await;
         }
         Err(e) => {
             error!("Mirror: failed to load whales from DB: {}", e);
@@ -655,6 +978,51 @@ async fn load_whales_from_db(app_handle: &tauri::AppHandle, handle: &MirrorHandl
     }
 }
 
+/// Fill the in-memory trade history from `mirror_trades`
+async fn load_trade_history_from_db(app_handle: &tauri::AppHandle, handle: &MirrorHandle) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let Ok(Some(profile)) = sqlite::get_active_profile(db.pool()).await else { return };
+
+    match sqlite::get_mirror_trades(db.pool(), profile.id, TRADE_HISTORY_CACHE as u64, 0).await {
+        Ok(rows) => {
+            let mut history = handle.trade_history.write().await;
+            // Stored newest first; the cache is oldest first. Keep anything
+            // recorded before this ran (only possible if enabled by hand).
+            let recorded = std::mem::take(&mut *history);
+            for record in rows.into_iter().rev().map(MirrorTradeRecord::from).chain(recorded) {
+                push_trade_record(&mut history, record);
+            }
+            debug!("Mirror: loaded {} stored trades into the history cache", history.len());
+        }
+        Err(e) => error!("Mirror: failed to load trade history from DB: {}", e),
+    }
+}
+
+/// Append a mirrored trade to the active profile's audit trail
+async fn save_mirror_trade(app_handle: &tauri::AppHandle, record: &MirrorTradeRecord) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let Ok(Some(profile)) = sqlite::get_active_profile(db.pool()).await else { return };
+
+    let trade = sqlite::NewMirrorTrade {
+        whale_username: &record.whale_username,
+        whale_user_id: &record.whale_user_id,
+        coin_symbol: &record.coin_symbol,
+        coin_name: &record.coin_name,
+        trade_type: &record.trade_type,
+        whale_amount_usd: record.whale_amount_usd,
+        our_amount_usd: record.our_amount_usd,
+        timestamp: &record.timestamp,
+        success: record.success,
+    };
+    if let Err(e) = sqlite::insert_mirror_trade(db.pool(), profile.id, &trade).await {
+        warn!("Mirror: failed to store trade record for {}: {}", record.coin_symbol, e);
+    }
+}
+
 // ─── Settings persistence ────────────────────────────────────────────
 
 pub async fn save_mirror_enabled(app_handle: &tauri::AppHandle, enabled: bool) {
@@ -774,3 +1142,124 @@ async fn save_mirror_last_at(app_handle: &tauri::AppHandle, at: &str) {
         .await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_amount::AmountUnit;
+    use crate::trade_executor::{spawn_recording_executor, RecordedTrade};
+
+    const NOW: i64 = 1_700_000_100;
+
+    fn whale_trade(side: &str, symbol: &str, total_value: f64, price: f64, age_secs: i64) -> RecentTrade {
+        serde_json::from_value(serde_json::json!({
+            "type": side, "username": "whale", "amount": total_value / price, "coinSymbol": symbol,
+            "totalValue": total_value, "price": price, "timestamp": NOW - age_secs, "userId": "42"
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_mirror_submits_scaled_copies_of_fresh_whale_trades() {
+        let cfg = MirrorConfig::default();
+        let held: HashSet<String> = ["HELD".to_string()].into();
+        let feed = vec![
+            whale_trade("BUY", "MOON", 2_000.0, 0.25, 2),
+            whale_trade("SELL", "SUN", 1_000.0, 0.5, 1),
+            whale_trade("BUY", "OLD", 2_000.0, 0.25, 30),
+            whale_trade("BUY", "HELD", 2_000.0, 0.25, 1),
+            whale_trade("BUY", "DUST", 5.0, 0.25, 1),
+        ];
+
+        let (executor, recorder) = spawn_recording_executor();
+        let mut skipped = Vec::new();
+        for trade in &feed {
+            match mirror_order(&cfg, trade, NOW, &held, WatchedWhale::default()) {
+                Ok(order) => {
+                    submit_mirror(&executor, &MirrorCopy { trade: trade.clone(), order }).await.unwrap();
+                }
+                Err(_) => skipped.push(trade.coin_symbol.as_str()),
+            }
+        }
+        assert_eq!(skipped, vec!["OLD", "HELD", "DUST"]);
+
+        let reason = |side: &str, value_usd: f64, symbol: &str| TradeReason::Mirror {
+            whale: "whale".into(),
+            whale_side: side.into(),
+            value_usd,
+            symbol: symbol.into(),
+        };
+        assert_eq!(
+            recorder.trades(),
+            vec![
+                RecordedTrade {
+                    symbol: "MOON".into(),
+                    trade_type: TradeType::Buy,
+                    amount: 200.0,
+                    amount_unit: AmountUnit::Usd,
+                    priority: TradePriority::Normal,
+                    reason: reason("BUY", 2_000.0, "MOON"),
+                    source: "mirror".into(),
+                },
+                // Sells are placed in USD too, converted to coins at execution
+                RecordedTrade {
+                    symbol: "SUN".into(),
+                    trade_type: TradeType::Sell,
+                    amount: 100.0,
+                    amount_unit: AmountUnit::Usd,
+                    priority: TradePriority::Normal,
+                    reason: reason("SELL", 1_000.0, "SUN"),
+                    source: "mirror".into(),
+                },
+            ]
+        );
+    }
+
+    fn latency(max_latency_secs: f64) -> WatchedWhale {
+        WatchedWhale { max_latency_secs: Some(max_latency_secs), score: None }
+    }
+
+    #[test]
+    fn test_whale_latency_override_takes_precedence_over_global() {
+        let cfg = MirrorConfig { max_latency_secs: 5.0, ..MirrorConfig::default() };
+        let held = HashSet::new();
+        let slow = whale_trade("BUY", "MOON", 2_000.0, 0.25, 20);
+        let quick = whale_trade("BUY", "MOON", 2_000.0, 0.25, 3);
+
+        // A swing trader given more time is copied where the global limit skips
+        assert!(mirror_order(&cfg, &slow, NOW, &held, WatchedWhale::default()).is_err());
+        assert_eq!(mirror_order(&cfg, &slow, NOW, &held, latency(30.0)).unwrap().latency_secs, 20.0);
+
+        // A scalper held to a tighter limit is skipped where the global would copy
+        assert!(mirror_order(&cfg, &quick, NOW, &held, WatchedWhale::default()).is_ok());
+        let skip = mirror_order(&cfg, &quick, NOW, &held, latency(2.0)).unwrap_err();
+        assert_eq!(skip, "3.0s old (max 2.0s)");
+    }
+
+    #[test]
+    fn test_min_whale_score_gates_buys_but_not_sells() {
+        let cfg = MirrorConfig { min_whale_score: 50.0, ..MirrorConfig::default() };
+        let held = HashSet::new();
+        let buy = whale_trade("BUY", "MOON", 2_000.0, 0.25, 1);
+        let sell = whale_trade("SELL", "MOON", 2_000.0, 0.25, 1);
+        let losing = WatchedWhale { max_latency_secs: None, score: Some(32.0) };
+
+        assert_eq!(mirror_order(&cfg, &buy, NOW, &held, losing).unwrap_err(), "whale score 32 below 50");
+        assert!(mirror_order(&cfg, &sell, NOW, &held, losing).is_ok());
+        assert!(mirror_order(&cfg, &buy, NOW, &held, WatchedWhale { score: Some(71.5), ..losing }).is_ok());
+
+        // Too little history to score is not a zero: the whale is still copied
+        assert!(mirror_order(&cfg, &buy, NOW, &held, WatchedWhale::default()).is_ok());
+        let thin = TrackedWhale {
+            user_id: "42".into(),
+            username: "whale".into(),
+            performance_score: None,
+            tracked_since: "2025-06-01 12:00:00".into(),
+            max_latency_secs: None,
+            score_detail: Some(r#"{"kind":"insufficientData","roundTrips":1,"tradesAnalyzed":3,"minRoundTrips":5}"#.into()),
+            scored_at: Some("2025-06-01 12:00:00".into()),
+        };
+        assert_eq!(WatchedWhale::from(&thin).score, None);
+        assert!(mirror_order(&cfg, &buy, NOW, &held, WatchedWhale::from(&thin)).is_ok());
+    }
+}
@@ -3,6 +3,7 @@
 
   let sessionToken = null;
   let sessionRole = 'viewer';
+  let sessionPerms = null;
   let currentPage = 'portfolio';
   let autoRefresh = true;
   let refreshTimer = null;
@@ -81,33 +82,48 @@
 
   // ── Role Management ──
 
-  function applyRole(role) {
+  // Fallback used when the server doesn't send a permission set
+  function defaultPerms(role) {
+    return {
+      viewPortfolio: true,
+      viewActivity: role === 'trusted' || role === 'admin',
+      toggleModules: role === 'admin',
+      executeTrades: role === 'admin',
+    };
+  }
+
+  function can(capability) {
+    return !!(sessionPerms && sessionPerms[capability]);
+  }
+
+  function applyRole(role, perms) {
     sessionRole = (role || 'viewer').toLowerCase();
+    sessionPerms = perms || defaultPerms(sessionRole);
 
     // Update badge
     var badge = document.getElementById('role-badge');
     badge.textContent = sessionRole.charAt(0).toUpperCase() + sessionRole.slice(1);
     badge.className = 'role-badge ' + sessionRole;
 
-    // Show/hide nav buttons by role
-    var isTrusted = sessionRole === 'trusted' || sessionRole === 'admin';
-    var isAdmin = sessionRole === 'admin';
+    // Show/hide nav buttons by granted capabilities
+    var canActivity = can('viewActivity');
+    var canTrade = can('executeTrades');
 
     var navSentinels = document.getElementById('nav-sentinels');
     var navActivity = document.getElementById('nav-activity');
     var navTrade = document.getElementById('nav-trade');
 
-    if (navSentinels) navSentinels.classList.toggle('role-hidden', !isTrusted);
-    if (navActivity) navActivity.classList.toggle('role-hidden', !isTrusted);
-    if (navTrade) navTrade.classList.toggle('role-hidden', !isAdmin);
+    if (navSentinels) navSentinels.classList.toggle('role-hidden', !canActivity);
+    if (navActivity) navActivity.classList.toggle('role-hidden', !canActivity);
+    if (navTrade) navTrade.classList.toggle('role-hidden', !canTrade);
 
     // Update settings display
     var roleInfo = document.getElementById('session-role-info');
     if (roleInfo) roleInfo.textContent = sessionRole.charAt(0).toUpperCase() + sessionRole.slice(1);
 
     // If current page is now inaccessible, switch to portfolio
-    if ((currentPage === 'sentinels' || currentPage === 'activity') && !isTrusted) switchPage('portfolio');
-    if (currentPage === 'trade' && !isAdmin) switchPage('portfolio');
+    if ((currentPage === 'sentinels' || currentPage === 'activity') && !canActivity) switchPage('portfolio');
+    if (currentPage === 'trade' && !canTrade) switchPage('portfolio');
   }
 
   // ── PIN Auth ──
@@ -173,7 +189,7 @@
       if (data.success && data.sessionToken) {
         sessionToken = data.sessionToken;
         localStorage.setItem('rpm_session', sessionToken);
-        applyRole(data.role || 'viewer');
+        applyRole(data.role || 'viewer', data.permissions);
         showApp();
       } else {
         showPinError(data.message || 'Invalid PIN');
@@ -189,7 +205,7 @@
   async function fetchAndApplyRole() {
     var data = await api('/api/session/role');
     if (data && data.role) {
-      applyRole(data.role);
+      applyRole(data.role, data.permissions);
     } else {
       applyRole('viewer');
     }
@@ -230,6 +246,24 @@
         switchPage(page);
       });
     });
+
+    document.getElementById('pending-list').addEventListener('click', async function(e) {
+      var btn = e.target.closest('.pending-cancel');
+      if (!btn) return;
+      btn.disabled = true;
+      await api('/api/trades/pending/' + btn.getAttribute('data-id') + '/cancel', { method: 'POST' });
+      loadPendingTrades();
+    });
+
+    document.getElementById('approval-list').addEventListener('click', async function(e) {
+      var btn = e.target.closest('.approval-approve, .approval-reject');
+      if (!btn) return;
+      btn.disabled = true;
+      var action = btn.classList.contains('approval-approve') ? 'approve' : 'reject';
+      await api('/api/trades/approvals/' + btn.getAttribute('data-id') + '/' + action, { method: 'POST' });
+      loadPendingApprovals();
+      loadPendingTrades();
+    });
   }
 
   function switchPage(page) {
@@ -245,11 +279,16 @@
 
   async function loadAllData() {
     $refreshIndicator.classList.add('visible');
-    var tasks = [loadPortfolio(), loadModules(), loadTrades()];
-    var isTrusted = sessionRole === 'trusted' || sessionRole === 'admin';
-    if (isTrusted) {
+    var tasks = [loadModules()];
+    if (can('viewPortfolio')) {
+      tasks.push(loadPortfolio());
+      tasks.push(loadTrades());
+    }
+    if (can('viewActivity')) {
       tasks.push(loadSentinels());
       tasks.push(loadActivity());
+      tasks.push(loadPendingTrades());
+      tasks.push(loadPendingApprovals());
     }
     await Promise.all(tasks);
     $refreshIndicator.classList.remove('visible');
@@ -257,7 +296,7 @@
 
   async function loadPortfolio() {
     var data = await api('/api/portfolio');
-    if (!data) return;
+    if (!data || data._forbidden) return;
 
     var balance = parseFloat(data.baseCurrencyBalance || 0);
     var totalCoinValue = parseFloat(data.totalCoinValue || 0);
@@ -338,6 +377,8 @@
         if (mc.key === 'sentinel') {
           if (mod.isPaused) { statusClass = 'paused'; statusText = 'Paused'; }
           else { statusClass = 'on'; statusText = mod.status || 'Active'; }
+        } else if (data.acquisitionPaused && (mc.key === 'sniper' || mc.key === 'mirror' || mc.key === 'dipbuyer')) {
+          statusClass = 'paused'; statusText = 'Buying Paused';
         } else if (mod.enabled) { statusClass = 'on'; statusText = 'Active'; }
         else { statusClass = 'off'; statusText = 'Disabled'; }
       }
@@ -352,7 +393,7 @@
 
   async function loadTrades() {
     var data = await api('/api/trades/recent?limit=30');
-    if (!data) return;
+    if (!data || data._forbidden) return;
 
     var $list = document.getElementById('trades-list');
     if (!Array.isArray(data) || !data.length) {
@@ -485,6 +526,61 @@
     document.getElementById('activity-updated').textContent = 'Updated ' + nowStr();
   }
 
+  async function loadPendingTrades() {
+    var data = await api('/api/trades/pending');
+    if (!data || data._forbidden) return;
+
+    var $list = document.getElementById('pending-list');
+    if (!Array.isArray(data) || !data.length) {
+      $list.innerHTML = '<div class="empty-state"><p>Nothing queued</p></div>';
+      return;
+    }
+
+    var canCancel = can('executeTrades');
+    $list.innerHTML = data.map(function(t) {
+      var isBuy = (t.tradeType || '').toUpperCase() === 'BUY';
+      var amount = t.amountUnit === 'usd' ? formatUSD(t.amount) : formatCompact(t.amount) + ' coins';
+      return '<div class="activity-item">' +
+        '<div class="activity-icon ' + (isBuy ? 'buy' : 'sell') + '">' + (isBuy ? '&#8593;' : '&#8595;') + '</div>' +
+        '<div class="activity-text">' +
+          '<div class="activity-title">' + (isBuy ? 'BUY ' : 'SELL ') + t.symbol + ' \u00B7 ' + amount + '</div>' +
+          '<div class="activity-detail">' + t.source + ' \u00B7 ' + t.priority + ' \u00B7 ' + t.reason + '</div>' +
+        '</div>' +
+        (canCancel ? '<button class="pending-cancel" data-id="' + t.id + '">Cancel</button>' : '') +
+      '</div>';
+    }).join('');
+  }
+
+  async function loadPendingApprovals() {
+    var data = await api('/api/trades/approvals');
+    if (!data || data._forbidden) return;
+
+    var $list = document.getElementById('approval-list');
+    if (!Array.isArray(data) || !data.length) {
+      $list.innerHTML = '<div class="empty-state"><p>No trades awaiting approval</p></div>';
+      return;
+    }
+
+    var canDecide = can('executeTrades');
+    var now = Date.now() / 1000;
+    $list.innerHTML = data.map(function(a) {
+      var isBuy = (a.tradeType || '').toUpperCase() === 'BUY';
+      var amount = a.amountUnit === 'usd' ? formatUSD(a.amount) : formatCompact(a.amount) + ' coins';
+      var left = Math.max(0, Math.round(a.expiresAt - now));
+      return '<div class="activity-item">' +
+        '<div class="activity-icon ' + (isBuy ? 'buy' : 'sell') + '">' + (isBuy ? '&#8593;' : '&#8595;') + '</div>' +
+        '<div class="activity-text">' +
+          '<div class="activity-title">' + (isBuy ? 'BUY ' : 'SELL ') + a.symbol + ' \u00B7 ' + amount + '</div>' +
+          '<div class="activity-detail">' + a.source + ' \u00B7 ' + a.reason + ' \u00B7 expires in ' + left + 's</div>' +
+        '</div>' +
+        (canDecide
+          ? '<button class="approval-approve" data-id="' + a.id + '">Approve</button>' +
+            '<button class="pending-cancel approval-reject" data-id="' + a.id + '">Reject</button>'
+          : '') +
+      '</div>';
+    }).join('');
+  }
+
   // ── Trade Form (Admin) ── exposed globally for inline onclick handlers
 
   window.setTradeType = function(type) {
@@ -499,11 +595,13 @@
     execBtn.className = 'exec-btn ' + (type === 'BUY' ? 'buy' : 'sell');
     execBtn.textContent = type === 'BUY' ? 'Buy' : 'Sell';
     amountLabel.textContent = type === 'BUY' ? 'Amount (USD)' : 'Amount (Coins)';
+    document.getElementById('split-option').style.display = type === 'SELL' ? '' : 'none';
   };
 
   window.executeTrade = async function() {
     var symbol = document.getElementById('trade-symbol').value.trim().toUpperCase();
     var amount = parseFloat(document.getElementById('trade-amount').value);
+    var split = tradeType === 'SELL' && document.getElementById('trade-split').checked;
     var $result = document.getElementById('trade-result');
     var $btn = document.getElementById('exec-trade-btn');
 
@@ -511,11 +609,24 @@
     if (!amount || amount <= 0) { showTradeResult('error', 'Enter a valid amount'); return; }
 
     $btn.disabled = true;
+    $btn.textContent = 'Checking...';
+
+    var check = await api('/api/trade/check', {
+      method: 'POST',
+      body: JSON.stringify({ symbol: symbol, tradeType: tradeType, amount: amount }),
+    });
+    if (check && check.allowed === false) {
+      $btn.disabled = false;
+      $btn.textContent = tradeType === 'BUY' ? 'Buy' : 'Sell';
+      showTradeResult('error', check.violations.map(function(v) { return v.message; }).join('; '));
+      return;
+    }
+
     $btn.textContent = 'Executing...';
 
     var data = await api('/api/trade', {
       method: 'POST',
-      body: JSON.stringify({ symbol: symbol, tradeType: tradeType, amount: amount }),
+      body: JSON.stringify({ symbol: symbol, tradeType: tradeType, amount: amount, split: split }),
     });
 
     $btn.disabled = false;
@@ -529,7 +640,11 @@
       showTradeResult('error', data.error);
     } else if (data.success) {
       var resp = data.response || {};
-      showTradeResult('success', tradeType + ' executed — new price: ' + formatUSD(resp.newPrice));
+      if (data.unsoldAmount > 0) {
+        showTradeResult('error', 'Split sell stopped with ' + data.unsoldAmount + ' ' + symbol + ' unsold: ' + data.unsoldReason);
+      } else {
+        showTradeResult('success', tradeType + ' executed — new price: ' + (resp.newPrice == null ? 'not reported' : formatUSD(resp.newPrice)));
+      }
       document.getElementById('trade-amount').value = '';
       loadPortfolio();
     } else {
@@ -600,7 +715,7 @@
         var res = await fetch('/api/auth/check?session=' + encodeURIComponent(saved), { credentials: 'include' });
         var data = await res.json();
         if (data.valid) {
-          applyRole(data.role || 'viewer');
+          applyRole(data.role || 'viewer', data.permissions);
           showApp();
           return;
         }
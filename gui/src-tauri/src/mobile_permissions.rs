@@ -0,0 +1,134 @@
+//! Mobile Access Permission Matrix
+//!
+//! Maps each `SessionRole` to a set of capabilities instead of fixed route
+//! tiers. The matrix is editable from the desktop and persisted in the
+//! settings table; the mobile server checks it on every gated request.
+
+use crate::mobile_server::SessionRole;
+use serde::{Deserialize, Serialize};
+
+/// A remotely-exercisable capability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Capability {
+    /// Portfolio, summary, and the live trade feed
+    ViewPortfolio,
+    /// Sentinels, module configs, and the activity log
+    ViewActivity,
+    /// Enable/disable automation modules; no mobile route needs it yet
+    ToggleModules,
+    /// Submit buy/sell trades
+    ExecuteTrades,
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Capability::ViewPortfolio => write!(f, "View Portfolio"),
+            Capability::ViewActivity => write!(f, "View Activity"),
+            Capability::ToggleModules => write!(f, "Toggle Modules"),
+            Capability::ExecuteTrades => write!(f, "Execute Trades"),
+        }
+    }
+}
+
+/// Capabilities granted to a single role
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RolePermissions {
+    pub view_portfolio: bool,
+    pub view_activity: bool,
+    pub toggle_modules: bool,
+    pub execute_trades: bool,
+}
+
+impl RolePermissions {
+    pub fn allows(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::ViewPortfolio => self.view_portfolio,
+            Capability::ViewActivity => self.view_activity,
+            Capability::ToggleModules => self.toggle_modules,
+            Capability::ExecuteTrades => self.execute_trades,
+        }
+    }
+}
+
+/// Per-role permission matrix (settings key `mobile_permissions`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionMatrix {
+    pub viewer: RolePermissions,
+    pub trusted: RolePermissions,
+    pub admin: RolePermissions,
+}
+
+impl Default for PermissionMatrix {
+    /// Mirrors the original fixed tiers: Viewer reads the portfolio,
+    /// Trusted also sees activity, Admin can do everything.
+    fn default() -> Self {
+        Self {
+            viewer: RolePermissions {
+                view_portfolio: true,
+                view_activity: false,
+                toggle_modules: false,
+                execute_trades: false,
+            },
+            trusted: RolePermissions {
+                view_portfolio: true,
+                view_activity: true,
+                toggle_modules: false,
+                execute_trades: false,
+            },
+            admin: RolePermissions {
+                view_portfolio: true,
+                view_activity: true,
+                toggle_modules: true,
+                execute_trades: true,
+            },
+        }
+    }
+}
+
+impl PermissionMatrix {
+    pub fn for_role(&self, role: SessionRole) -> &RolePermissions {
+        match role {
+            SessionRole::Viewer => &self.viewer,
+            SessionRole::Trusted => &self.trusted,
+            SessionRole::Admin => &self.admin,
+        }
+    }
+
+    pub fn allows(&self, role: SessionRole, capability: Capability) -> bool {
+        self.for_role(role).allows(capability)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_matrix_matches_fixed_tiers() {
+        let m = PermissionMatrix::default();
+        assert!(m.allows(SessionRole::Viewer, Capability::ViewPortfolio));
+        assert!(!m.allows(SessionRole::Viewer, Capability::ViewActivity));
+        assert!(m.allows(SessionRole::Trusted, Capability::ViewActivity));
+        assert!(!m.allows(SessionRole::Trusted, Capability::ExecuteTrades));
+        assert!(m.allows(SessionRole::Admin, Capability::ExecuteTrades));
+    }
+
+    #[test]
+    fn test_custom_matrix_gates_capabilities() {
+        let mut m = PermissionMatrix::default();
+        // Household setup: trusted members may toggle modules but not see activity,
+        // and even admins can't trade remotely.
+        m.trusted.view_activity = false;
+        m.trusted.toggle_modules = true;
+        m.admin.execute_trades = false;
+
+        assert!(!m.allows(SessionRole::Trusted, Capability::ViewActivity));
+        assert!(m.allows(SessionRole::Trusted, Capability::ToggleModules));
+        assert!(!m.allows(SessionRole::Admin, Capability::ExecuteTrades));
+        assert!(!m.allows(SessionRole::Viewer, Capability::ToggleModules));
+    }
+}
@@ -8,23 +8,28 @@
 //!   from anywhere, HTTPS, no account required, no firewall config needed
 //! - **Local WiFi**: Binds to LAN IP — accessible only from same WiFi network
 
+use crate::app_event::{emit_event, AppEvent};
+use crate::mobile_permissions::{Capability, PermissionMatrix, RolePermissions};
+use crate::order_split::SplitFill;
+use crate::trade_amount::{AmountUnit, TradeAmount};
+use crate::trade_reason::TradeReason;
 use crate::AppState;
 use axum::{
-    extract::{Json, Query, State as AxumState},
+    extract::{Json, Path, Query, State as AxumState},
     http::{header, HeaderMap, StatusCode},
     middleware::{self, Next},
     response::{Html, IntoResponse, Response},
     routing::{get, post},
     Router,
 };
-use rugplay_core::{PortfolioResponse, PortfolioSummary, RecentTrade, TradeType};
+use rugplay_core::{PortfolioResponse, PortfolioSummary, RecentTrade, TradeType, truncate_to_8_decimals};
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tauri::{Emitter, Manager};
+use tauri::Manager;
 use tokio::io::AsyncBufReadExt;
 use tokio::sync::{watch, RwLock};
 use tracing::{error, info, warn};
@@ -92,6 +97,8 @@ pub struct MobileServerState {
     pub failed_attempts: Arc<RwLock<HashMap<String, (u32, chrono::DateTime<chrono::Utc>)>>>,
     /// Default role assigned to new sessions
     pub default_role: Arc<RwLock<SessionRole>>,
+    /// Per-role capability matrix
+    pub permissions: Arc<RwLock<PermissionMatrix>>,
     /// Tauri app handle for accessing managed state
     pub app_handle: Option<tauri::AppHandle>,
 }
@@ -184,6 +191,7 @@ impl MobileServerHandle {
 
         // Generate a new 6-digit PIN
         let pin = generate_pin();
+        let permissions = load_permission_matrix(&app_state).await;
 
         // Create server state
         let server_state = MobileServerState {
@@ -192,6 +200,7 @@ impl MobileServerHandle {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             failed_attempts: Arc::new(RwLock::new(HashMap::new())),
             default_role: Arc::new(RwLock::new(SessionRole::Viewer)),
+            permissions: Arc::new(RwLock::new(permissions)),
             app_handle: Some(app_handle),
         };
 
@@ -446,6 +455,14 @@ impl MobileServerHandle {
         }
     }
 
+    /// Replace the permission matrix on the running server (no-op if stopped)
+    pub async fn set_permissions(&self, matrix: PermissionMatrix) {
+        if let Some(state) = self.server_state.read().await.as_ref() {
+            *state.permissions.write().await = matrix;
+            info!("Mobile permission matrix updated");
+        }
+    }
+
     /// Kick a session by its token prefix
     pub async fn kick_session(&self, token_prefix: &str) -> Result<(), String> {
         let ss = self.server_state.read().await;
@@ -461,13 +478,13 @@ impl MobileServerHandle {
 
                 // Emit event to desktop
                 if let Some(app_handle) = &state.app_handle {
-                    let _ = app_handle.emit("mobile-connection", MobileConnectionEvent {
+                    let _ = emit_event(app_handle, AppEvent::MobileConnection(MobileConnectionEvent {
                         event_type: "kicked".into(),
                         token_prefix: token_prefix.to_string(),
                         role: SessionRole::Viewer,
                         label: String::new(),
                         total_sessions: sessions.len(),
-                    });
+                    }));
                 }
                 Ok(())
             } else {
@@ -661,43 +678,55 @@ fn build_router(state: MobileServerState) -> Router {
         .route("/app.js", get(serve_mobile_js))
         .route("/favicon.ico", get(serve_favicon));
 
-    // Routes available to all authenticated users (viewer+)
-    let viewer_routes = Router::new()
+    // Routes available to all authenticated sessions
+    let session_routes = Router::new()
         .route("/api/status", get(handle_status))
-        .route("/api/portfolio", get(handle_portfolio))
-        .route("/api/portfolio/summary", get(handle_portfolio_summary))
         .route("/api/dashboard", get(handle_dashboard))
-        .route("/api/trades/recent", get(handle_recent_trades))
         .route("/api/session/role", get(handle_session_role))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             auth_middleware,
         ));
 
-    // Routes requiring Trusted+ role
-    let trusted_routes = Router::new()
+    // Routes gated by the permission matrix
+    let portfolio_routes = Router::new()
+        .route("/api/portfolio", get(handle_portfolio))
+        .route("/api/portfolio/summary", get(handle_portfolio_summary))
+        .route("/api/trades/recent", get(handle_recent_trades))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            view_portfolio_middleware,
+        ));
+
+    let activity_routes = Router::new()
         .route("/api/sentinels", get(handle_sentinels))
         .route("/api/sniper", get(handle_sniper_status))
         .route("/api/dipbuyer", get(handle_dipbuyer_status))
         .route("/api/activity", get(handle_activity_log))
+        .route("/api/trades/pending", get(handle_pending_trades))
+        .route("/api/trades/approvals", get(handle_pending_approvals))
         .layer(middleware::from_fn_with_state(
             state.clone(),
-            trusted_middleware,
+            view_activity_middleware,
         ));
 
-    // Routes requiring Admin role
-    let admin_routes = Router::new()
+    let trade_routes = Router::new()
         .route("/api/trade", post(handle_trade))
+        .route("/api/trade/check", post(handle_trade_check))
+        .route("/api/trades/pending/{id}/cancel", post(handle_cancel_pending_trade))
+        .route("/api/trades/approvals/{id}/approve", post(handle_approve_pending))
+        .route("/api/trades/approvals/{id}/reject", post(handle_reject_pending))
         .layer(middleware::from_fn_with_state(
             state.clone(),
-            admin_middleware,
+            execute_trades_middleware,
         ));
 
     Router::new()
         .merge(public_routes)
-        .merge(viewer_routes)
-        .merge(trusted_routes)
-        .merge(admin_routes)
+        .merge(session_routes)
+        .merge(portfolio_routes)
+        .merge(activity_routes)
+        .merge(trade_routes)
         .with_state(state)
 }
 
@@ -749,11 +778,13 @@ async fn auth_middleware(
     Ok((StatusCode::UNAUTHORIZED, "Unauthorized").into_response())
 }
 
-/// Middleware requiring Trusted or Admin role
-async fn trusted_middleware(
-    AxumState(state): AxumState<MobileServerState>,
+/// Shared check for capability-gated routes: the session must exist and
+/// its role must be granted `capability` in the permission matrix.
+async fn require_capability(
+    state: &MobileServerState,
     req: axum::extract::Request,
     next: Next,
+    capability: Capability,
 ) -> Result<Response, StatusCode> {
     let query = req.uri().query().unwrap_or("");
     let token = extract_session_token(req.headers(), query);
@@ -761,38 +792,43 @@ async fn trusted_middleware(
     if let Some(token) = token {
         let mut sessions = state.sessions.write().await;
         if let Some(data) = sessions.get_mut(&token) {
-            if matches!(data.role, SessionRole::Trusted | SessionRole::Admin) {
+            if state.permissions.read().await.allows(data.role, capability) {
                 data.last_activity = chrono::Utc::now();
+                drop(sessions);
                 return Ok(next.run(req).await);
             }
-            return Ok((StatusCode::FORBIDDEN, "Insufficient permissions — Trusted role required").into_response());
+            return Ok((
+                StatusCode::FORBIDDEN,
+                format!("Insufficient permissions — {} not allowed for {}", capability, data.role),
+            ).into_response());
         }
     }
 
     Ok((StatusCode::UNAUTHORIZED, "Unauthorized").into_response())
 }
 
-/// Middleware requiring Admin role
-async fn admin_middleware(
+async fn view_portfolio_middleware(
     AxumState(state): AxumState<MobileServerState>,
     req: axum::extract::Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    let query = req.uri().query().unwrap_or("");
-    let token = extract_session_token(req.headers(), query);
+    require_capability(&state, req, next, Capability::ViewPortfolio).await
+}
 
-    if let Some(token) = token {
-        let mut sessions = state.sessions.write().await;
-        if let Some(data) = sessions.get_mut(&token) {
-            if matches!(data.role, SessionRole::Admin) {
-                data.last_activity = chrono::Utc::now();
-                return Ok(next.run(req).await);
-            }
-            return Ok((StatusCode::FORBIDDEN, "Insufficient permissions — Admin role required").into_response());
-        }
-    }
+async fn view_activity_middleware(
+    AxumState(state): AxumState<MobileServerState>,
+    req: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    require_capability(&state, req, next, Capability::ViewActivity).await
+}
 
-    Ok((StatusCode::UNAUTHORIZED, "Unauthorized").into_response())
+async fn execute_trades_middleware(
+    AxumState(state): AxumState<MobileServerState>,
+    req: axum::extract::Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    require_capability(&state, req, next, Capability::ExecuteTrades).await
 }
 
 // ─── Route Handlers ────────────────────────────────────────────────
@@ -808,6 +844,7 @@ struct AuthResponse {
     success: bool,
     session_token: Option<String>,
     role: Option<SessionRole>,
+    permissions: Option<RolePermissions>,
     message: String,
 }
 
@@ -831,6 +868,7 @@ async fn handle_pin_auth(
         }
 
         let default_role = *state.default_role.read().await;
+        let permissions = *state.permissions.read().await.for_role(default_role);
         let token = uuid::Uuid::new_v4().to_string();
         let session_num = sessions.len() + 1;
         let label = format!("Device {}", session_num);
@@ -849,13 +887,13 @@ async fn handle_pin_auth(
 
         // Emit connection event to desktop
         if let Some(app_handle) = &state.app_handle {
-            let _ = app_handle.emit("mobile-connection", MobileConnectionEvent {
+            let _ = emit_event(app_handle, AppEvent::MobileConnection(MobileConnectionEvent {
                 event_type: "connected".into(),
                 token_prefix: token_prefix.clone(),
                 role: default_role,
                 label: label.clone(),
                 total_sessions: total,
-            });
+            }));
 
             // Also send a native notification
             if let Some(notif) = app_handle.try_state::<crate::NotificationHandle>() {
@@ -880,6 +918,7 @@ async fn handle_pin_auth(
                 success: true,
                 session_token: Some(token),
                 role: Some(default_role),
+                permissions: Some(permissions),
                 message: "Authenticated successfully".into(),
             }),
         )
@@ -891,6 +930,7 @@ async fn handle_pin_auth(
                 success: false,
                 session_token: None,
                 role: None,
+                permissions: None,
                 message: "Invalid PIN".into(),
             }),
         )
@@ -913,10 +953,12 @@ async fn handle_auth_check(
     if let Some(token) = token {
         let sessions = state.sessions.read().await;
         if let Some(data) = sessions.get(&token) {
+            let permissions = *state.permissions.read().await.for_role(data.role);
             return (StatusCode::OK, Json(serde_json::json!({
                 "valid": true,
                 "role": data.role,
                 "label": data.label,
+                "permissions": permissions,
             }))).into_response();
         }
     }
@@ -954,7 +996,15 @@ async fn handle_portfolio_summary(
         error!("Portfolio summary fetch failed: {}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
-    let summary = PortfolioSummary::from(&portfolio);
+    let frozen_reserve = match state
+        .app_handle
+        .as_ref()
+        .and_then(|h| h.try_state::<crate::TradeExecutorHandle>())
+    {
+        Some(executor) => executor.get_risk_limits().await.frozen_reserve_usd,
+        None => 0.0,
+    };
+    let summary = PortfolioSummary::from(&portfolio).with_frozen_reserve(frozen_reserve);
     Ok(Json(summary))
 }
 
@@ -963,54 +1013,37 @@ async fn handle_dashboard(
     AxumState(state): AxumState<MobileServerState>,
 ) -> impl IntoResponse {
     let mut modules = serde_json::Map::new();
+    let mut acquisition_paused = false;
 
     if let Some(app_handle) = &state.app_handle {
-        // Sentinel status
-        if let Some(handle) = app_handle.try_state::<crate::SentinelMonitorHandle>() {
-            let handle: &crate::SentinelMonitorHandle = &handle;
-            let status = handle.status().await;
-            let is_paused = handle.is_paused().await;
-            modules.insert("sentinel".into(), serde_json::json!({
-                "status": format!("{:?}", status),
-                "isPaused": is_paused,
-            }));
-        }
-
-        // Harvester — always enabled
-        modules.insert("harvester".into(), serde_json::json!({
-            "enabled": true,
-        }));
-
-        // Sniper status
-        if let Some(handle) = app_handle.try_state::<crate::SniperHandle>() {
-            let handle: &crate::SniperHandle = &handle;
-            let enabled = handle.is_enabled();
-            modules.insert("sniper".into(), serde_json::json!({
-                "enabled": enabled,
-            }));
-        }
-
-        // Mirror status
-        if let Some(handle) = app_handle.try_state::<crate::MirrorHandle>() {
-            let handle: &crate::MirrorHandle = &handle;
-            let enabled = handle.is_enabled();
-            modules.insert("mirror".into(), serde_json::json!({
-                "enabled": enabled,
-            }));
-        }
-
-        // Dip Buyer status
-        if let Some(handle) = app_handle.try_state::<crate::DipBuyerHandle>() {
-            let handle: &crate::DipBuyerHandle = &handle;
-            let enabled = handle.is_enabled();
-            modules.insert("dipbuyer".into(), serde_json::json!({
-                "enabled": enabled,
-            }));
+        let status = crate::commands::collect_bot_status(app_handle, false).await;
+        acquisition_paused = status.acquisition_paused;
+
+        for module in &status.modules {
+            let entry = if module.name == "sentinel" {
+                let status = match (module.enabled, module.paused) {
+                    (false, _) => "Stopped",
+                    (true, true) => "Paused",
+                    (true, false) => "Running",
+                };
+                serde_json::json!({
+                    "status": status,
+                    "isPaused": module.paused,
+                    "lastTickAt": module.last_tick_at,
+                })
+            } else {
+                serde_json::json!({
+                    "enabled": module.enabled,
+                    "lastTickAt": module.last_tick_at,
+                })
+            };
+            modules.insert(module.name.clone(), entry);
         }
     }
 
     Json(serde_json::json!({
         "modules": modules,
+        "acquisitionPaused": acquisition_paused,
         "timestamp": chrono::Utc::now().to_rfc3339(),
     }))
 }
@@ -1062,9 +1095,11 @@ async fn handle_session_role(
     if let Some(token) = token {
         let sessions = state.sessions.read().await;
         if let Some(data) = sessions.get(&token) {
+            let permissions = *state.permissions.read().await.for_role(data.role);
             return (StatusCode::OK, Json(serde_json::json!({
                 "role": data.role,
                 "label": data.label,
+                "permissions": permissions,
             }))).into_response();
         }
     }
@@ -1177,9 +1212,19 @@ struct TradePayload {
     symbol: String,
     trade_type: String,
     amount: f64,
+    /// Unit of `amount`: "usd" or "coins". Defaults to USD for buys and
+    /// coins for sells; a sell in USD is sized at execution time.
+    #[serde(default)]
+    amount_unit: Option<AmountUnit>,
+    /// Spend into the frozen reserve for this trade only
+    #[serde(default)]
+    override_reserve: bool,
+    /// Sell in tranches using the split sell config
+    #[serde(default)]
+    split: bool,
 }
 
-/// POST /api/trade — execute a buy/sell trade (Admin only)
+/// POST /api/trade — execute a buy/sell trade (ExecuteTrades)
 async fn handle_trade(
     AxumState(state): AxumState<MobileServerState>,
     Json(body): Json<TradePayload>,
@@ -1195,28 +1240,48 @@ async fn handle_trade(
     if body.amount <= 0.0 {
         return Ok((StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Amount must be positive"}))).into_response());
     }
+    let amount = TradeAmount::new(body.amount, body.amount_unit.unwrap_or(AmountUnit::native(trade_type)));
+    if body.split && amount.needs_price(trade_type) {
+        return Ok((StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Split sells take a coin amount"}))).into_response());
+    }
 
     let executor = app_handle
         .try_state::<crate::TradeExecutorHandle>()
         .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    let result = executor
-        .submit_trade(
-            body.symbol.clone(),
-            trade_type,
-            body.amount,
-            crate::trade_executor::TradePriority::Normal,
-            "Mobile trade".to_string(),
-        )
-        .await;
+    let result = if body.split && matches!(trade_type, TradeType::Sell) {
+        executor
+            .submit_split_sell(
+                body.symbol.clone(),
+                truncate_to_8_decimals(body.amount),
+                crate::trade_executor::TradePriority::Normal,
+                TradeReason::Manual { side: TradeType::Sell, split: true, mobile: true, note: None },
+                "mobile",
+            )
+            .await
+    } else {
+        executor
+            .submit_manual_trade(
+                body.symbol.clone(),
+                trade_type,
+                amount,
+                TradeReason::Manual { side: trade_type, split: false, mobile: true, note: None },
+                "mobile",
+                body.override_reserve,
+            )
+            .await
+            .map(|response| SplitFill::complete(response, body.amount))
+    };
 
     match result {
-        Ok(response) => Ok(Json(serde_json::json!({
+        Ok(fill) => Ok(Json(serde_json::json!({
             "success": true,
             "response": {
-                "newPrice": response.new_price,
-                "priceImpact": response.price_impact,
-            }
+                "newPrice": fill.response.fill_price(),
+                "priceImpact": fill.response.price_impact,
+            },
+            "unsoldAmount": fill.unsold,
+            "unsoldReason": fill.error,
         })).into_response()),
         Err(e) => {
             warn!("Mobile trade failed: {}", e);
@@ -1225,6 +1290,103 @@ async fn handle_trade(
     }
 }
 
+/// POST /api/trade/check — dry-run the risk checks before confirming (ExecuteTrades)
+async fn handle_trade_check(
+    AxumState(state): AxumState<MobileServerState>,
+    Json(body): Json<TradePayload>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let app_handle = state.app_handle.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let trade_type = match body.trade_type.to_uppercase().as_str() {
+        "BUY" => TradeType::Buy,
+        "SELL" => TradeType::Sell,
+        _ => return Ok((StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Invalid trade type"}))).into_response()),
+    };
+
+    if !body.amount.is_finite() || body.amount <= 0.0 {
+        return Ok((StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Amount must be positive"}))).into_response());
+    }
+
+    let executor = app_handle
+        .try_state::<crate::TradeExecutorHandle>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let check = executor
+        .check_trade_allowed(app_handle, &body.symbol, "mobile", trade_type, body.amount, body.override_reserve)
+        .await;
+    Ok(Json(check).into_response())
+}
+
+/// GET /api/trades/pending — orders queued in the executor (ViewActivity)
+async fn handle_pending_trades(
+    AxumState(state): AxumState<MobileServerState>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let app_handle = state.app_handle.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let executor = app_handle
+        .try_state::<crate::TradeExecutorHandle>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(executor.list_pending_trades().await))
+}
+
+/// POST /api/trades/pending/{id}/cancel — drop a queued order (ExecuteTrades)
+async fn handle_cancel_pending_trade(
+    AxumState(state): AxumState<MobileServerState>,
+    Path(id): Path<u64>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let app_handle = state.app_handle.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let executor = app_handle
+        .try_state::<crate::TradeExecutorHandle>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match executor.cancel_pending_trade(id).await {
+        Ok(cancelled) => Ok(Json(serde_json::json!({ "success": true, "trade": cancelled })).into_response()),
+        Err(e) => Ok((StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": e }))).into_response()),
+    }
+}
+
+/// GET /api/trades/approvals — trades semi-auto mode is holding (ViewActivity)
+async fn handle_pending_approvals(
+    AxumState(state): AxumState<MobileServerState>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let app_handle = state.app_handle.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let executor = app_handle
+        .try_state::<crate::TradeExecutorHandle>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(executor.list_pending_approvals().await))
+}
+
+/// POST /api/trades/approvals/{id}/approve — let a held trade go ahead (ExecuteTrades)
+async fn handle_approve_pending(
+    AxumState(state): AxumState<MobileServerState>,
+    Path(id): Path<u64>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let app_handle = state.app_handle.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let executor = app_handle
+        .try_state::<crate::TradeExecutorHandle>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match executor.approve_pending(id).await {
+        Ok(approval) => Ok(Json(serde_json::json!({ "success": true, "approval": approval })).into_response()),
+        Err(e) => Ok((StatusCode::CONFLICT, Json(serde_json::json!({ "error": e }))).into_response()),
+    }
+}
+
+/// POST /api/trades/approvals/{id}/reject — drop a held trade (ExecuteTrades)
+async fn handle_reject_pending(
+    AxumState(state): AxumState<MobileServerState>,
+    Path(id): Path<u64>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let app_handle = state.app_handle.as_ref().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+    let executor = app_handle
+        .try_state::<crate::TradeExecutorHandle>()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    match executor.reject_pending(id).await {
+        Ok(approval) => Ok(Json(serde_json::json!({ "success": true, "approval": approval })).into_response()),
+        Err(e) => Ok((StatusCode::NOT_FOUND, Json(serde_json::json!({ "error": e }))).into_response()),
+    }
+}
+
 // ─── Helper Functions ──────────────────────────────────────────────
 
 /// Build a RugplayClient from the active profile's token
@@ -1260,6 +1422,42 @@ async fn fetch_portfolio(state: &MobileServerState) -> Result<PortfolioResponse,
         .map_err(|e| format!("Portfolio fetch failed: {}", e))
 }
 
+/// Load the permission matrix from the settings table (defaults if unset)
+pub async fn load_permission_matrix(app_state: &AppState) -> PermissionMatrix {
+    let db_guard = app_state.db.read().await;
+    let Some(db) = db_guard.as_ref() else {
+        return PermissionMatrix::default();
+    };
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'mobile_permissions'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|j| serde_json::from_str(&j).ok())
+    .unwrap_or_default()
+}
+
+/// Save the permission matrix to the settings table
+pub async fn save_permission_matrix(app_state: &AppState, matrix: &PermissionMatrix) -> Result<(), String> {
+    let db_guard = app_state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let json = serde_json::to_string(matrix).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('mobile_permissions', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
 /// Generate a random 6-digit PIN
 fn generate_pin() -> String {
     use rand::Rng;
@@ -1306,3 +1504,70 @@ async fn serve_mobile_js() -> impl IntoResponse {
 async fn serve_favicon() -> impl IntoResponse {
     StatusCode::NO_CONTENT
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{Method, Request};
+    use tower::ServiceExt;
+
+    fn server_state(permissions: PermissionMatrix) -> MobileServerState {
+        let app_state = AppState::new(std::env::temp_dir(), &[7u8; 32]).unwrap();
+        let now = chrono::Utc::now();
+        let sessions = [("viewer-token", SessionRole::Viewer), ("trusted-token", SessionRole::Trusted)]
+            .into_iter()
+            .map(|(token, role)| {
+                let data = SessionData { role, label: token.to_string(), connected_at: now, last_activity: now };
+                (token.to_string(), data)
+            })
+            .collect();
+        MobileServerState {
+            app_state,
+            pin: Arc::new(RwLock::new("123456".to_string())),
+            sessions: Arc::new(RwLock::new(sessions)),
+            failed_attempts: Arc::new(RwLock::new(HashMap::new())),
+            default_role: Arc::new(RwLock::new(SessionRole::Viewer)),
+            permissions: Arc::new(RwLock::new(permissions)),
+            app_handle: None,
+        }
+    }
+
+    async fn status(router: &Router, method: Method, uri: &str, token: &str) -> StatusCode {
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::COOKIE, format!("session={}", token))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        router.clone().oneshot(req).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_each_permission_layer_forbids_roles_without_its_capability() {
+        // Viewers may see nothing gated; trusted members may trade but not see activity
+        let mut matrix = PermissionMatrix::default();
+        matrix.viewer.view_portfolio = false;
+        matrix.trusted.view_activity = false;
+        matrix.trusted.execute_trades = true;
+        let router = build_router(server_state(matrix));
+
+        let gated = [
+            (Method::GET, "/api/portfolio"),
+            (Method::GET, "/api/activity"),
+            (Method::POST, "/api/trade"),
+        ];
+        for (method, uri) in gated.clone() {
+            assert_eq!(status(&router, method, uri, "viewer-token").await, StatusCode::FORBIDDEN, "{}", uri);
+        }
+        assert_eq!(status(&router, Method::GET, "/api/activity", "trusted-token").await, StatusCode::FORBIDDEN);
+        assert_ne!(status(&router, Method::GET, "/api/portfolio", "trusted-token").await, StatusCode::FORBIDDEN);
+        assert_ne!(status(&router, Method::POST, "/api/trade", "trusted-token").await, StatusCode::FORBIDDEN);
+
+        // No session at all is unauthorized rather than forbidden
+        for (method, uri) in gated {
+            assert_eq!(status(&router, method, uri, "unknown").await, StatusCode::UNAUTHORIZED, "{}", uri);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Notification Outbox — queued delivery with a flush on shutdown
+//!
+//! Notifications are pushed onto an outbox and delivered by a background
+//! worker, so a slow toast never holds up the loop that raised it. When the
+//! app exits, whatever is still queued (e.g. the stop-loss that fired while
+//! the automation loops were stopping) is delivered inline before the
+//! process goes away, bounded by a deadline so a stuck sender can't hang
+//! the exit.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+/// How long the exit handler spends delivering queued notifications
+pub const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of a shutdown flush
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FlushReport {
+    pub delivered: usize,
+    /// Handed to the deliverer, which couldn't show them
+    pub failed: usize,
+    /// Left undelivered because the deadline passed
+    pub dropped: usize,
+}
+
+/// FIFO of outbound notifications shared by senders, the delivery worker
+/// and the shutdown flush
+#[derive(Debug)]
+pub struct Outbox<T> {
+    queue: Mutex<VecDeque<T>>,
+    ready: Notify,
+    closed: AtomicBool,
+}
+
+impl<T> Default for Outbox<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Outbox<T> {
+    pub fn new() -> Self {
+        Self { queue: Mutex::new(VecDeque::new()), ready: Notify::new(), closed: AtomicBool::new(false) }
+    }
+
+    /// Queue an item for delivery. Returns false once the outbox has been
+    /// closed by a flush.
+    pub fn push(&self, item: T) -> bool {
+        if self.closed.load(Ordering::Acquire) {
+            return false;
+        }
+        self.lock().push_back(item);
+        self.ready.notify_one();
+        true
+    }
+
+    /// Items waiting to be delivered
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Next item for the delivery worker; `None` once the outbox is closed
+    pub async fn next(&self) -> Option<T> {
+        loop {
+            // Register for wakeups before checking, so a flush that lands
+            // between the check and the await isn't missed
+            let mut notified = std::pin::pin!(self.ready.notified());
+            notified.as_mut().enable();
+
+            if let Some(item) = self.lock().pop_front() {
+                return Some(item);
+            }
+            if self.closed.load(Ordering::Acquire) {
+                return None;
+            }
+            notified.await;
+        }
+    }
+
+    /// Close the outbox and deliver everything still queued, in order,
+    /// until `timeout` runs out. `deliver` says whether an item got through;
+    /// a failed one doesn't stop the rest. The worker stops picking up items.
+    pub fn flush(&self, timeout: Duration, mut deliver: impl FnMut(T) -> bool) -> FlushReport {
+        self.closed.store(true, Ordering::Release);
+        self.ready.notify_waiters();
+
+        let deadline = Instant::now() + timeout;
+        let mut report = FlushReport::default();
+        loop {
+            let Some(item) = self.lock().pop_front() else { break };
+            if Instant::now() >= deadline {
+                report.dropped += 1 + self.lock().drain(..).count();
+                break;
+            }
+            if deliver(item) {
+                report.delivered += 1;
+            } else {
+                report.failed += 1;
+            }
+        }
+        report
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<T>> {
+        // A panicking deliverer can't leave the queue itself inconsistent
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pending_notifications_are_flushed_on_shutdown() {
+        let outbox = Outbox::new();
+        assert!(outbox.push("stop loss AAA"));
+        assert!(outbox.push("risk limit BBB"));
+
+        let mut sent = Vec::new();
+        let report = outbox.flush(SHUTDOWN_FLUSH_TIMEOUT, |n| {
+            sent.push(n);
+            true
+        });
+        assert_eq!(sent, vec!["stop loss AAA", "risk limit BBB"]);
+        assert_eq!(report, FlushReport { delivered: 2, failed: 0, dropped: 0 });
+        assert!(outbox.is_empty());
+
+        // Nothing can be queued after shutdown
+        assert!(!outbox.push("late"));
+        assert!(outbox.is_empty());
+    }
+
+    #[test]
+    fn test_flush_stops_at_deadline() {
+        let outbox = Outbox::new();
+        for i in 0..3 {
+            outbox.push(i);
+        }
+        let mut sent = Vec::new();
+        let report = outbox.flush(Duration::ZERO, |n| {
+            sent.push(n);
+            true
+        });
+        assert!(sent.is_empty());
+        assert_eq!(report, FlushReport { delivered: 0, failed: 0, dropped: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_worker_delivers_in_order_and_stops_on_flush() {
+        let outbox = Arc::new(Outbox::new());
+        let worker = {
+            let outbox = outbox.clone();
+            tokio::spawn(async move {
+                let mut delivered = Vec::new();
+                while let Some(n) = outbox.next().await {
+                    delivered.push(n);
+                }
+                delivered
+            })
+        };
+
+        outbox.push(1);
+        outbox.push(2);
+        while !outbox.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let report = outbox.flush(SHUTDOWN_FLUSH_TIMEOUT, |_| true);
+        assert_eq!(report, FlushReport::default());
+        assert_eq!(worker.await.unwrap(), vec![1, 2]);
+    }
+}
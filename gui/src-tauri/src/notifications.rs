@@ -2,17 +2,57 @@
 //!
 //! Provides native Windows toast notifications for all automated events
 //! (sentinel triggers, sniper buys, harvester claims, risk alerts).
-//! Uses tauri-plugin-notification under the hood.
+//! Uses tauri-plugin-notification under the hood. Notifications go through
+//! an outbox drained by a worker and flushed when the app exits.
+//!
+//! A notification identical (same title and body) to one shown within its
+//! category's suppression window is dropped, so a module retrying the same
+//! failure every tick raises one toast instead of a stack of them.
 
+use crate::notification_outbox::{FlushReport, Outbox};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
 use tauri::AppHandle;
 use tauri_plugin_notification::NotificationExt;
 use tokio::sync::RwLock;
-use tracing::{debug, warn};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
 
 // ─── Config ──────────────────────────────────────────────────────────
 
+fn default_dedup_window_secs() -> u64 { 60 }
+
+/// Trades are each worth a toast, even when two look alike
+fn default_dedup_overrides() -> BTreeMap<NotificationCategory, u64> {
+    BTreeMap::from([(NotificationCategory::Trade, 0)])
+}
+
+/// Saved overrides on top of the defaults, so a config that tunes one
+/// category doesn't start suppressing trades
+fn merge_dedup_overrides<'de, D>(deserializer: D) -> Result<BTreeMap<NotificationCategory, u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let saved = BTreeMap::<NotificationCategory, u64>::deserialize(deserializer)?;
+    let mut overrides = default_dedup_overrides();
+    overrides.extend(saved);
+    Ok(overrides)
+}
+
+/// What a notification is about, for choosing its suppression window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NotificationCategory {
+    /// A buy or sell went through
+    Trade,
+    /// Something failed or was refused and needs attention
+    Failure,
+    /// Price, watchlist and equity alerts
+    Alert,
+    General,
+}
+
 /// Per-category notification toggles
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -31,6 +71,21 @@ pub struct NotificationConfig {
     pub session_alerts: bool,
     /// Trade execution confirmations (manual)
     pub trade_confirmations: bool,
+    /// Repeats of a notification within this many seconds are dropped; 0 shows every one
+    #[serde(default = "default_dedup_window_secs")]
+    pub dedup_window_secs: u64,
+    /// Per-category windows used instead of `dedup_window_secs`, merged
+    /// over the defaults
+    #[serde(default = "default_dedup_overrides", deserialize_with = "merge_dedup_overrides")]
+    pub dedup_overrides: BTreeMap<NotificationCategory, u64>,
+}
+
+impl NotificationConfig {
+    /// How long repeats in `category` are suppressed
+    pub fn dedup_window(&self, category: NotificationCategory) -> Duration {
+        let secs = self.dedup_overrides.get(&category).copied().unwrap_or(self.dedup_window_secs);
+        Duration::from_secs(secs)
+    }
 }
 
 impl Default for NotificationConfig {
@@ -43,26 +98,100 @@ impl Default for NotificationConfig {
             risk_alerts: true,
             session_alerts: true,
             trade_confirmations: false, // off by default — too noisy
+            dedup_window_secs: default_dedup_window_secs(),
+            dedup_overrides: default_dedup_overrides(),
+        }
+    }
+}
+
+// ─── De-duplication ──────────────────────────────────────────────────
+
+/// Notifications shown recently, keyed on (title, body)
+#[derive(Debug, Default)]
+struct RecentNotifications {
+    /// When each one's suppression window ends
+    suppressed_until: HashMap<(String, String), Instant>,
+}
+
+impl RecentNotifications {
+    /// Whether to show this notification; if so, repeats are suppressed for `window`
+    fn admit(&mut self, title: &str, body: &str, window: Duration, now: Instant) -> bool {
+        self.suppressed_until.retain(|_, until| *until > now);
+        if window.is_zero() {
+            return true;
+        }
+        let key = (title.to_string(), body.to_string());
+        if self.suppressed_until.contains_key(&key) {
+            return false;
         }
+        self.suppressed_until.insert(key, now + window);
+        true
     }
 }
 
 // ─── Handle ──────────────────────────────────────────────────────────
 
+/// Where notifications are shown
+pub trait NotificationSink: Send + Sync {
+    fn show(&self, title: &str, body: &str) -> Result<(), String>;
+}
+
+/// Native toasts through tauri-plugin-notification
+impl NotificationSink for AppHandle {
+    fn show(&self, title: &str, body: &str) -> Result<(), String> {
+        self.notification().builder().title(title).body(body).show().map_err(|e| e.to_string())
+    }
+}
+
 /// Shared handle for sending notifications from anywhere in the app
 #[derive(Clone)]
 pub struct NotificationHandle {
-    app: AppHandle,
+    sink: Arc<dyn NotificationSink>,
     config: Arc<RwLock<NotificationConfig>>,
+    /// (title, body) pairs waiting to be shown
+    outbox: Arc<Outbox<(String, String)>>,
+    recent: Arc<Mutex<RecentNotifications>>,
 }
 
 impl NotificationHandle {
-    /// Create a new notification handle
+    /// Create a new notification handle and start its delivery worker
     pub fn new(app: AppHandle) -> Self {
+        let handle = Self::with_sink(Arc::new(app));
+        handle.spawn_worker();
+        handle
+    }
+
+    /// A handle showing notifications on `sink`, without a delivery worker
+    fn with_sink(sink: Arc<dyn NotificationSink>) -> Self {
         Self {
-            app,
+            sink,
             config: Arc::new(RwLock::new(NotificationConfig::default())),
+            outbox: Arc::new(Outbox::new()),
+            recent: Arc::new(Mutex::new(RecentNotifications::default())),
+        }
+    }
+
+    fn spawn_worker(&self) {
+        let worker = self.clone();
+        tauri::async_runtime::spawn(async move {
+            while let Some((title, body)) = worker.outbox.next().await {
+                let _ = worker.show(&title, &body);
+            }
+            debug!("Notification worker stopped");
+        });
+    }
+
+    /// Deliver every queued notification before the app exits, giving up
+    /// after `timeout`. Later notifications are shown immediately.
+    pub fn flush(&self, timeout: Duration) -> FlushReport {
+        let report = self.outbox.flush(timeout, |(title, body)| self.show(&title, &body));
+        if report.delivered > 0 || report.failed > 0 || report.dropped > 0 {
+            info!(
+                "Flushed {} queued notifications ({} failed, {} dropped)",
+                report.delivered, report.failed, report.dropped
+            );
         }
+        report
     }
 
     /// Update the notification configuration
@@ -85,7 +214,8 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Trade,
             "🛑 Stop Loss Triggered",
             &format!(
                 "${} sold at {:.1}% loss (price: ${:.8})",
@@ -93,7 +223,8 @@ impl NotificationHandle {
                 loss_pct.abs(),
                 price
             ),
-        );
+        )
+        .await;
     }
 
     /// Notify when a take-profit triggers
@@ -104,13 +235,15 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Trade,
             "🎯 Take Profit Triggered",
             &format!(
                 "${} sold at +{:.1}% profit (price: ${:.8})",
                 symbol, gain_pct, price
             ),
-        );
+        )
+        .await;
     }
 
     /// Notify when a trailing stop triggers
@@ -121,13 +254,34 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Trade,
             "📉 Trailing Stop Triggered",
             &format!(
                 "${} sold after {:.1}% drop from peak (price: ${:.8})",
                 symbol, drop_pct, price
             ),
-        );
+        )
+        .await;
+    }
+
+    /// Notify when a held coin is sold because its pool drained
+    pub async fn notify_liquidity_drain(&self, symbol: &str, liquidity: f64, price: f64) {
+        let cfg = self.config.read().await;
+        if !cfg.enabled || !cfg.sentinel_triggers {
+            return;
+        }
+        drop(cfg);
+
+        self.notify_dedup(
+            NotificationCategory::Trade,
+            "🚰 Liquidity Drain Exit",
+            &format!(
+                "${} sold as pool liquidity fell to ${:.2} (price: ${:.8})",
+                symbol, liquidity, price
+            ),
+        )
+        .await;
     }
 
     // ─── Sniper Notifications ────────────────────────────────────
@@ -140,13 +294,15 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Trade,
             "🎯 Sniper Buy",
             &format!(
                 "Bought ${} for ${:.2} (price: ${:.8})",
                 symbol, amount_usd, price
             ),
-        );
+        )
+        .await;
     }
 
     // ─── Harvester Notifications ─────────────────────────────────
@@ -159,13 +315,15 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::General,
             "🌾 Reward Claimed",
             &format!(
                 "{}: ${:.2} claimed (streak: {} days)",
                 username, reward_amount, streak
             ),
-        );
+        )
+        .await;
     }
 
     // ─── Risk Notifications ──────────────────────────────────────
@@ -178,10 +336,12 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Failure,
             "⚠️ Risk Limit Hit",
             &format!("${} trade rejected: {}", symbol, reason),
-        );
+        )
+        .await;
     }
 
     // ─── Session Notifications ───────────────────────────────────
@@ -194,10 +354,28 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Failure,
             "🔑 Session Expired",
             "Your token has expired — please re-authenticate",
-        );
+        )
+        .await;
+    }
+
+    /// Notify when automation switched to a backup profile
+    pub async fn notify_profile_failover(&self, from: &str, to: &str, reason: &str) {
+        let cfg = self.config.read().await;
+        if !cfg.enabled || !cfg.session_alerts {
+            return;
+        }
+        drop(cfg);
+
+        self.notify_dedup(
+            NotificationCategory::General,
+            "🔁 Profile Switched",
+            &format!("@{} {} — automation continues as @{}", from, reason, to),
+        )
+        .await;
     }
 
     // ─── Trade Confirmations ─────────────────────────────────────
@@ -210,38 +388,67 @@ impl NotificationHandle {
         }
         drop(cfg);
 
-        self.send(
+        self.notify_dedup(
+            NotificationCategory::Trade,
             &format!("💰 {} Executed", trade_type),
             &format!("${} — {} ${:.2}", symbol, trade_type, amount),
-        );
+        )
+        .await;
+    }
+
+    /// Notify when semi-auto mode holds a trade for the user's approval
+    pub async fn notify_approval_needed(&self, symbol: &str, reason: &str) {
+        self.notify_dedup(NotificationCategory::Trade, &format!("✋ Approve ${}?", symbol), reason).await;
     }
 
     // ─── Internal ────────────────────────────────────────────────
 
-    /// Send a native notification (internal use)
+    /// Queue a native notification (internal use)
     fn send(&self, title: &str, body: &str) {
         debug!("Notification: {} — {}", title, body);
 
-        if let Err(e) = self
-            .app
-            .notification()
-            .builder()
-            .title(title)
-            .body(body)
-            .show()
-        {
-            warn!("Failed to send notification: {}", e);
+        // Once the outbox is flushed on exit, show directly
+        if !self.outbox.push((title.to_string(), body.to_string())) {
+            let _ = self.show(title, body);
+        }
+    }
+
+    /// Show a notification now; false if the sink failed
+    fn show(&self, title: &str, body: &str) -> bool {
+        match self.sink.show(title, body) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to send notification: {}", e);
+                false
+            }
         }
     }
 
     /// Send a raw notification — used by modules that manage their own checks
     pub async fn send_raw(&self, title: &str, body: &str) {
+        self.notify_dedup(NotificationCategory::General, title, body).await;
+    }
+
+    /// Queue a notification unless an identical one was shown within the
+    /// category's suppression window
+    pub async fn notify_dedup(&self, category: NotificationCategory, title: &str, body: &str) {
         let cfg = self.config.read().await;
         if !cfg.enabled {
             return;
         }
+        let window = cfg.dedup_window(category);
         drop(cfg);
-        self.send(title, body);
+
+        let admitted = self
+            .recent
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .admit(title, body, window, Instant::now());
+        if admitted {
+            self.send(title, body);
+        } else {
+            debug!("Suppressed repeat notification: {} — {}", title, body);
+        }
     }
 }
 
@@ -292,3 +499,113 @@ pub async fn save_notification_config(app_handle: &AppHandle, config: &Notificat
     .execute(db.pool())
     .await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification_outbox::SHUTDOWN_FLUSH_TIMEOUT;
+
+    #[test]
+    fn test_repeats_within_the_window_are_dropped() {
+        let mut recent = RecentNotifications::default();
+        let start = Instant::now();
+        let window = Duration::from_secs(60);
+
+        assert!(recent.admit("Sentinel Failed: MOON", "Sell failed", window, start));
+        assert!(!recent.admit("Sentinel Failed: MOON", "Sell failed", window, start + Duration::from_secs(30)));
+        // A different body or title is a different notification
+        assert!(recent.admit("Sentinel Failed: MOON", "Sell failed twice", window, start));
+        assert!(recent.admit("Sentinel Failed: SUN", "Sell failed", window, start));
+        // Once the window has passed it shows again, and starts a new window
+        assert!(recent.admit("Sentinel Failed: MOON", "Sell failed", window, start + Duration::from_secs(61)));
+        assert!(!recent.admit("Sentinel Failed: MOON", "Sell failed", window, start + Duration::from_secs(90)));
+
+        // A zero window never suppresses
+        assert!(recent.admit("Buy", "MOON", Duration::ZERO, start));
+        assert!(recent.admit("Buy", "MOON", Duration::ZERO, start));
+    }
+
+    #[test]
+    fn test_category_overrides_replace_the_default_window() {
+        let config = NotificationConfig::default();
+        assert_eq!(config.dedup_window(NotificationCategory::Failure), Duration::from_secs(60));
+        assert_eq!(config.dedup_window(NotificationCategory::Trade), Duration::ZERO);
+
+        // Configs saved before de-duplication get the defaults
+        let old: NotificationConfig = serde_json::from_str(
+            r#"{"enabled":true,"sentinelTriggers":true,"sniperBuys":true,"harvesterClaims":true,"riskAlerts":true,"sessionAlerts":true,"tradeConfirmations":false}"#,
+        )
+        .unwrap();
+        assert_eq!(old.dedup_window_secs, 60);
+        assert_eq!(old.dedup_window(NotificationCategory::Trade), Duration::ZERO);
+
+        let tuned: NotificationConfig = serde_json::from_value(serde_json::json!({
+            "enabled": true, "sentinelTriggers": true, "sniperBuys": true, "harvesterClaims": true,
+            "riskAlerts": true, "sessionAlerts": true, "tradeConfirmations": false,
+            "dedupWindowSecs": 10, "dedupOverrides": { "failure": 300 }
+        }))
+        .unwrap();
+        assert_eq!(tuned.dedup_window(NotificationCategory::Failure), Duration::from_secs(300));
+        // Tuning one category keeps trades unsuppressed
+        assert_eq!(tuned.dedup_window(NotificationCategory::Trade), Duration::ZERO);
+        assert_eq!(tuned.dedup_window(NotificationCategory::Alert), Duration::from_secs(10));
+    }
+
+    /// Records every notification it is handed and fails the ones titled "Broken"
+    #[derive(Default)]
+    struct FlakySink {
+        shown: Mutex<Vec<String>>,
+    }
+
+    impl NotificationSink for FlakySink {
+        fn show(&self, title: &str, _body: &str) -> Result<(), String> {
+            self.shown.lock().unwrap().push(title.to_string());
+            if title.starts_with("Broken") {
+                return Err("notification service unavailable".to_string());
+            }
+            Ok(())
+        }
+    }
+
+    impl FlakySink {
+        fn shown(&self) -> Vec<String> {
+            self.shown.lock().unwrap().clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_a_failing_sink_does_not_hold_up_the_flush() {
+        let sink = Arc::new(FlakySink::default());
+        let handle = NotificationHandle::with_sink(sink.clone());
+        for title in ["Stop loss AAA", "Broken toast", "Risk limit BBB"] {
+            handle.send_raw(title, "body").await;
+        }
+        assert!(sink.shown().is_empty());
+
+        let report = handle.flush(SHUTDOWN_FLUSH_TIMEOUT);
+        assert_eq!(report, FlushReport { delivered: 2, failed: 1, dropped: 0 });
+        assert_eq!(sink.shown(), ["Stop loss AAA", "Broken toast", "Risk limit BBB"]);
+
+        // After the flush, notifications skip the outbox
+        handle.send_raw("Broken late", "body").await;
+        handle.send_raw("Late", "body").await;
+        assert_eq!(sink.shown().len(), 5);
+        assert_eq!(handle.flush(SHUTDOWN_FLUSH_TIMEOUT), FlushReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_the_worker_keeps_delivering_after_a_failure() {
+        let sink = Arc::new(FlakySink::default());
+        let handle = NotificationHandle::with_sink(sink.clone());
+        handle.spawn_worker();
+        handle.send_raw("Broken toast", "body").await;
+        handle.send_raw("Stop loss AAA", "body").await;
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while sink.shown().len() < 2 && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(sink.shown(), ["Broken toast", "Stop loss AAA"]);
+        assert_eq!(handle.flush(SHUTDOWN_FLUSH_TIMEOUT), FlushReport::default());
+    }
+}
@@ -0,0 +1,213 @@
+//! Smart Order Split — break large sells into tranches
+//!
+//! Selling a big position in one order eats the whole slippage curve at once.
+//! Splitting it into a few smaller sells spaced a couple of seconds apart lets
+//! the pool recover between fills. The total window is capped so a crash never
+//! leaves a position half-sold for long.
+
+use rugplay_core::{truncate_to_8_decimals, TradeResponse};
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on tranches per split sell
+pub const MAX_TRANCHES: u32 = 10;
+
+/// Split sell settings (settings key `split_sell_config`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitSellConfig {
+    /// Split sentinel-triggered sells automatically
+    pub sentinel_enabled: bool,
+    /// Only split sentinel sells worth at least this much USD
+    pub min_value_usd: f64,
+    /// Number of tranches (1 = no split)
+    pub tranches: u32,
+    /// Delay between tranches in milliseconds
+    pub spacing_ms: u64,
+    /// Hard cap on the whole split in seconds; spacing shrinks to fit
+    pub max_total_secs: u64,
+}
+
+impl Default for SplitSellConfig {
+    fn default() -> Self {
+        Self {
+            sentinel_enabled: false,
+            min_value_usd: 1000.0,
+            tranches: 3,
+            spacing_ms: 3000,
+            max_total_secs: 15,
+        }
+    }
+}
+
+/// How a sell will be carried out
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranchePlan {
+    /// Coin amount per tranche, in submission order (8-decimal safe)
+    pub amounts: Vec<f64>,
+    /// Delay before each tranche after the first
+    pub spacing_ms: u64,
+}
+
+impl SplitSellConfig {
+    /// Whether a sentinel sell of this USD value should be split
+    pub fn applies_to_sentinel(&self, value_usd: f64) -> bool {
+        self.sentinel_enabled && self.tranches > 1 && value_usd >= self.min_value_usd
+    }
+
+    /// Plan the tranches for selling `total_qty` coins.
+    ///
+    /// Tranches are equal except the last, which takes the rounding remainder
+    /// so the amounts always sum to the total. Falls back to a single order
+    /// when the position is too small to split.
+    pub fn plan(&self, total_qty: f64) -> TranchePlan {
+        let n = self.tranches.clamp(1, MAX_TRANCHES);
+        let per = truncate_to_8_decimals(total_qty / n as f64);
+
+        if n == 1 || per <= 0.0 {
+            return TranchePlan { amounts: vec![total_qty], spacing_ms: 0 };
+        }
+
+        let mut amounts = vec![per; n as usize - 1];
+        amounts.push(truncate_to_8_decimals(total_qty - per * (n - 1) as f64));
+
+        let max_spacing = self.max_total_secs * 1000 / (n as u64 - 1);
+        TranchePlan {
+            amounts,
+            spacing_ms: self.spacing_ms.min(max_spacing),
+        }
+    }
+}
+
+/// What a split sell got done. It stops at the first failed tranche, so it
+/// can fill only part of the quantity.
+#[derive(Debug, Clone)]
+pub struct SplitFill {
+    /// Combined response of the tranches that filled
+    pub response: TradeResponse,
+    /// Coins sold by the filled tranches
+    pub sold: f64,
+    /// Coins of the tranches that never filled
+    pub unsold: f64,
+    /// Why the split stopped early
+    pub error: Option<String>,
+}
+
+impl SplitFill {
+    /// A sell of `quantity` that went out in full
+    pub fn complete(response: TradeResponse, quantity: f64) -> Self {
+        Self { response, sold: quantity, unsold: 0.0, error: None }
+    }
+
+    /// Whether some of the quantity was left unsold
+    pub fn is_partial(&self) -> bool {
+        self.unsold > 0.0
+    }
+}
+
+/// Fold a tranche fill into the running total for the whole split sell.
+/// Amounts and price impact accumulate; price and balance come from the latest fill.
+pub fn merge_fill(total: Option<TradeResponse>, fill: TradeResponse) -> TradeResponse {
+    let Some(mut total) = total else {
+        return fill;
+    };
+    let add = |a: Option<f64>, b: Option<f64>| match (a, b) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+    };
+    total.coins_sold = add(total.coins_sold, fill.coins_sold);
+    total.total_received = add(total.total_received, fill.total_received);
+    total.price_impact += fill.price_impact;
+    total.new_price = fill.new_price.or(total.new_price);
+    total.new_balance = fill.new_balance;
+    total.success = total.success && fill.success;
+    total.paper = total.paper || fill.paper;
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(tranches: u32, spacing_ms: u64, max_total_secs: u64) -> SplitSellConfig {
+        SplitSellConfig {
+            sentinel_enabled: true,
+            tranches,
+            spacing_ms,
+            max_total_secs,
+            ..SplitSellConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_tranche_sizing_sums_to_total() {
+        let plan = config(3, 2000, 30).plan(100.0);
+        assert_eq!(plan.amounts.len(), 3);
+        assert_eq!(plan.amounts[0], 33.33333333);
+        assert_eq!(plan.amounts[1], 33.33333333);
+        let sum: f64 = plan.amounts.iter().sum();
+        assert!((sum - 100.0).abs() < 1e-8);
+        assert!(plan.amounts[2] >= plan.amounts[0]);
+        assert_eq!(plan.spacing_ms, 2000);
+
+        // Dust that can't be split is sold in one go
+        let dust = config(5, 2000, 30).plan(0.00000003);
+        assert_eq!(dust.amounts, vec![0.00000003]);
+        assert_eq!(dust.spacing_ms, 0);
+    }
+
+    #[test]
+    fn test_spacing_is_capped_by_total_time() {
+        // 6 tranches * 5s would take 25s; the 10s cap squeezes spacing to 2s
+        let plan = config(6, 5000, 10).plan(60.0);
+        assert_eq!(plan.amounts.len(), 6);
+        assert_eq!(plan.spacing_ms, 2000);
+        assert!(plan.spacing_ms * (plan.amounts.len() as u64 - 1) <= 10_000);
+
+        // Tranche count is bounded
+        assert_eq!(config(50, 100, 60).plan(1000.0).amounts.len(), MAX_TRANCHES as usize);
+    }
+
+    #[test]
+    fn test_sentinel_threshold() {
+        let cfg = config(3, 1000, 10);
+        assert!(cfg.applies_to_sentinel(5000.0));
+        assert!(!cfg.applies_to_sentinel(10.0));
+        assert!(!SplitSellConfig { sentinel_enabled: false, ..cfg }.applies_to_sentinel(5000.0));
+    }
+
+    fn fill(sold: f64, received: f64, price: f64, balance: f64) -> TradeResponse {
+        TradeResponse {
+            success: true,
+            trade_type: "SELL".to_string(),
+            coins_bought: None,
+            coins_sold: Some(sold),
+            total_cost: None,
+            total_received: Some(received),
+            new_price: Some(price),
+            price_impact: -1.0,
+            new_balance: balance,
+            paper: false,
+        }
+    }
+
+    #[test]
+    fn test_fills_merge_in_sequence() {
+        let plan = config(3, 1000, 10).plan(30.0);
+        let prices = [0.9, 0.8, 0.7];
+
+        let mut total = None;
+        let mut balance = 0.0;
+        for (amount, price) in plan.amounts.iter().zip(prices) {
+            balance += amount * price;
+            total = Some(merge_fill(total, fill(*amount, amount * price, price, balance)));
+        }
+
+        let total = total.unwrap();
+        assert!((total.coins_sold.unwrap() - 30.0).abs() < 1e-8);
+        assert!((total.total_received.unwrap() - 24.0).abs() < 1e-8);
+        // Later tranches overwrite the price/balance snapshot
+        assert_eq!(total.new_price, Some(0.7));
+        assert_eq!(total.new_balance, balance);
+        assert_eq!(total.price_impact, -3.0);
+    }
+}
@@ -0,0 +1,82 @@
+//! Pagination — one page of a list command's results
+//!
+//! List commands return a [`Page`] instead of a bare vector, so the UI
+//! knows the total and whether another page follows without guessing from
+//! the page length.
+
+use serde::Serialize;
+
+/// Page size when a list command is called without a limit
+pub const DEFAULT_PAGE_LIMIT: u32 = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Matching items across all pages
+    pub total: u64,
+    /// Index of the first item of this page
+    pub offset: u64,
+    /// Requested page size
+    pub limit: u64,
+    pub has_more: bool,
+}
+
+impl<T> Page<T> {
+    /// A page of `items` starting at `offset`, out of `total`
+    pub fn new(items: Vec<T>, total: u64, offset: u64, limit: u64) -> Self {
+        let has_more = offset + (items.len() as u64) < total;
+        Self { items, total, offset, limit, has_more }
+    }
+
+    /// Cut a page out of a list that was loaded whole
+    pub fn slice(all: Vec<T>, offset: u64, limit: u64) -> Self {
+        let total = all.len() as u64;
+        let items = all.into_iter().skip(offset as usize).take(limit as usize).collect();
+        Self::new(items, total, offset, limit)
+    }
+}
+
+/// Offset and limit from optional command arguments. The limit defaults to
+/// `default_limit` and is capped at `max_limit`.
+pub fn page_bounds(offset: Option<u32>, limit: Option<u32>, default_limit: u32, max_limit: u32) -> (u64, u64) {
+    let limit = limit.unwrap_or(default_limit).clamp(1, max_limit.max(1));
+    (offset.unwrap_or(0) as u64, limit as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_metadata_for_partial_and_full_pages() {
+        let all: Vec<u32> = (0..25).collect();
+
+        let first = Page::slice(all.clone(), 0, 10);
+        assert_eq!(first.items, (0..10).collect::<Vec<_>>());
+        assert_eq!((first.total, first.offset, first.limit, first.has_more), (25, 0, 10, true));
+
+        // The last page is partial and says so
+        let last = Page::slice(all.clone(), 20, 10);
+        assert_eq!(last.items, vec![20, 21, 22, 23, 24]);
+        assert!(!last.has_more);
+
+        // A full page that ends exactly at the total has nothing after it
+        let exact = Page::slice((0..20).collect::<Vec<u32>>(), 10, 10);
+        assert_eq!(exact.items.len(), 10);
+        assert!(!exact.has_more);
+
+        // Past the end: empty, still reporting the total
+        let beyond = Page::slice(all, 40, 10);
+        assert!(beyond.items.is_empty());
+        assert_eq!((beyond.total, beyond.has_more), (25, false));
+
+        // Pages fetched from a store that counted separately
+        assert!(Page::new(vec![1, 2], 5, 2, 2).has_more);
+        assert!(!Page::new(vec![5], 5, 4, 2).has_more);
+
+        assert_eq!(page_bounds(None, None, 50, 100), (0, 50));
+        assert_eq!(page_bounds(Some(30), Some(500), 50, 100), (30, 100));
+        assert_eq!(page_bounds(None, Some(0), 50, 100), (0, 1));
+    }
+}
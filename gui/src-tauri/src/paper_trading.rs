@@ -0,0 +1,106 @@
+//! Paper Trading — dry runs of the automation modules
+//!
+//! With paper trading on, automated orders still go through the executor's
+//! queue, risk limits and daily tracker, so those can be validated too, but
+//! the trade is never sent: the order is answered with a fill estimated on
+//! the coin's constant-product pool at its current reserves. Trades the user
+//! places by hand are always real, and so are sentinel sells, which only
+//! ever guard real holdings. Paper fills (`TradeResponse::paper`) aren't
+//! journaled, get no protective sentinel and stay out of the modules' stored
+//! stats, and the automation log marks them with `"paper": true`.
+
+use crate::round_trip::PoolReserves;
+use rugplay_core::{TradeResponse, TradeType};
+
+/// The fill `amount` (USD for buys, coins for sells) would get from `pool`.
+/// `balance` is the cash balance before the trade.
+pub fn simulate_fill(pool: PoolReserves, trade_type: TradeType, amount: f64, balance: f64) -> Result<TradeResponse, String> {
+    if !(pool.coins.is_finite() && pool.usd.is_finite()) || pool.coins <= 0.0 || pool.usd <= 0.0 {
+        return Err("Paper trade: coin has no pool to price against".to_string());
+    }
+    let k = pool.coins * pool.usd;
+    let spot = pool.usd / pool.coins;
+
+    let (usd_after, coins_after) = match trade_type {
+        TradeType::Buy => (pool.usd + amount, k / (pool.usd + amount)),
+        TradeType::Sell => (k / (pool.coins + amount), pool.coins + amount),
+    };
+    let new_price = usd_after / coins_after;
+
+    let mut response = TradeResponse {
+        success: true,
+        trade_type: String::new(),
+        coins_bought: None,
+        coins_sold: None,
+        total_cost: None,
+        total_received: None,
+        new_price: Some(new_price),
+        price_impact: (new_price - spot) / spot,
+        new_balance: balance,
+        paper: true,
+    };
+    match trade_type {
+        TradeType::Buy => {
+            response.trade_type = "BUY".to_string();
+            response.coins_bought = Some(pool.coins - coins_after);
+            response.total_cost = Some(amount);
+            response.new_balance = balance - amount;
+        }
+        TradeType::Sell => {
+            let received = pool.usd - usd_after;
+            response.trade_type = "SELL".to_string();
+            response.coins_sold = Some(amount);
+            response.total_received = Some(received);
+            response.new_balance = balance + received;
+        }
+    }
+    Ok(response)
+}
+
+/// Automation log `details` (a JSON object) marked as a paper trade
+pub fn tag_details(details: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(details) {
+        Ok(serde_json::Value::Object(mut fields)) => {
+            fields.insert("paper".to_string(), serde_json::Value::Bool(true));
+            serde_json::Value::Object(fields).to_string()
+        }
+        _ => serde_json::json!({ "paper": true, "details": details }).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_fills_follow_the_constant_product_pool() {
+        // 1M coins against $100k: spot $0.10
+        let pool = PoolReserves { coins: 1_000_000.0, usd: 100_000.0 };
+
+        let buy = simulate_fill(pool, TradeType::Buy, 10_000.0, 50_000.0).unwrap();
+        let bought = buy.coins_bought.unwrap();
+        assert!(buy.paper);
+        assert!((bought - 90_909.09).abs() < 1e-2);
+        assert!((buy.new_price.unwrap() - 0.121).abs() < 1e-9);
+        assert!((buy.price_impact - 0.21).abs() < 1e-9);
+        assert_eq!(buy.new_balance, 40_000.0);
+
+        // Selling the coins back returns the pool to where it started
+        let after = PoolReserves { coins: pool.coins - bought, usd: 110_000.0 };
+        let sell = simulate_fill(after, TradeType::Sell, bought, buy.new_balance).unwrap();
+        assert!((sell.total_received.unwrap() - 10_000.0).abs() < 1e-6);
+        assert!((sell.new_price.unwrap() - 0.1).abs() < 1e-9);
+        assert!(sell.price_impact < 0.0);
+
+        assert!(simulate_fill(PoolReserves { coins: 0.0, usd: 0.0 }, TradeType::Buy, 10.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_automation_log_details_are_tagged() {
+        let tagged: serde_json::Value = serde_json::from_str(&tag_details(r#"{"price":0.5}"#)).unwrap();
+        assert_eq!(tagged, serde_json::json!({ "price": 0.5, "paper": true }));
+
+        let wrapped: serde_json::Value = serde_json::from_str(&tag_details("not json")).unwrap();
+        assert_eq!(wrapped["paper"], true);
+    }
+}
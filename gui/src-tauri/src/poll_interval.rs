@@ -0,0 +1,216 @@
+//! Adaptive Poll Interval — how often the automation loops hit the API
+//!
+//! Each polling loop starts at its configured interval and retunes it after
+//! every poll: busier feeds are polled faster, quiet ones slower, and any
+//! rate limit backs off every loop at once through the shared
+//! [`ApiPressure`]. The interval always stays inside the user's bounds
+//! (settings key `adaptive_poll`).
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Shrink factor after a poll that found new activity
+const ACTIVE_FACTOR: f64 = 0.8;
+/// Growth factor after a poll that found nothing new
+const QUIET_FACTOR: f64 = 1.25;
+/// Growth factor after a failed poll (network, 5xx)
+const FAILED_FACTOR: f64 = 1.5;
+/// Growth factor after a rate-limited poll
+const RATE_LIMITED_FACTOR: f64 = 2.0;
+/// How long after a 429 every loop holds off speeding up
+pub const RATE_LIMIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Bounds for the adaptive interval. Off by default: loops poll at their
+/// configured interval until the user turns it on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdaptivePollPolicy {
+    pub enabled: bool,
+    pub min_secs: u64,
+    pub max_secs: u64,
+}
+
+impl Default for AdaptivePollPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_secs: 2,
+            max_secs: 30,
+        }
+    }
+}
+
+impl AdaptivePollPolicy {
+    fn bounds(&self) -> (Duration, Duration) {
+        let min = Duration::from_secs(self.min_secs.max(1));
+        let max = Duration::from_secs(self.max_secs).max(min);
+        (min, max)
+    }
+}
+
+/// What a single poll of the API found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollOutcome {
+    /// Succeeded and returned something new
+    Active,
+    /// Succeeded with nothing new
+    Quiet,
+    /// Rejected with HTTP 429
+    RateLimited,
+    /// Rejected because the profile's token has expired
+    TokenExpired,
+    /// Failed for any other reason
+    Failed,
+}
+
+impl PollOutcome {
+    /// Classify a fetch result by its error, if any
+    pub fn from_error(error: &str) -> Self {
+        if is_rate_limited(error) {
+            Self::RateLimited
+        } else if error.contains("Session token expired") {
+            Self::TokenExpired
+        } else {
+            Self::Failed
+        }
+    }
+}
+
+/// Whether an API error is a rate limit rejection
+pub fn is_rate_limited(error: &str) -> bool {
+    error.contains("429") || error.contains("Rate limit")
+}
+
+/// Rate limit state shared by every loop on the same account
+#[derive(Debug, Default)]
+pub struct ApiPressure {
+    last_rate_limit: Mutex<Option<Instant>>,
+}
+
+impl ApiPressure {
+    pub fn record_rate_limit(&self) {
+        self.record_rate_limit_at(Instant::now());
+    }
+
+    pub fn record_rate_limit_at(&self, at: Instant) {
+        *self.last_rate_limit.lock().unwrap_or_else(|e| e.into_inner()) = Some(at);
+    }
+
+    /// Whether any loop was rate limited within the cooldown before `now`
+    pub fn is_throttled(&self, now: Instant) -> bool {
+        self.last_rate_limit
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .is_some_and(|at| now.saturating_duration_since(at) < RATE_LIMIT_COOLDOWN)
+    }
+}
+
+/// One loop's current poll interval
+#[derive(Debug, Clone)]
+pub struct AdaptiveInterval {
+    configured: Duration,
+    current: Duration,
+}
+
+impl AdaptiveInterval {
+    pub fn new(configured: Duration) -> Self {
+        Self { configured, current: configured }
+    }
+
+    pub fn current(&self) -> Duration {
+        self.current
+    }
+
+    /// Follow a change of the loop's configured interval; returns whether
+    /// it changed (and the interval was reset to it)
+    pub fn set_configured(&mut self, configured: Duration) -> bool {
+        if configured == self.configured {
+            return false;
+        }
+        self.configured = configured;
+        self.current = configured;
+        true
+    }
+
+    /// Retune after a poll and return the interval to wait next.
+    ///
+    /// While `throttled` (another loop was recently rate limited) the
+    /// interval may still grow but never shrinks.
+    pub fn record(&mut self, outcome: PollOutcome, throttled: bool, policy: &AdaptivePollPolicy) -> Duration {
+        if !policy.enabled {
+            self.current = self.configured;
+            return self.current;
+        }
+
+        let factor = match outcome {
+            PollOutcome::Active if throttled => 1.0,
+            PollOutcome::Active => ACTIVE_FACTOR,
+            PollOutcome::Quiet => QUIET_FACTOR,
+            PollOutcome::Failed | PollOutcome::TokenExpired => FAILED_FACTOR,
+            PollOutcome::RateLimited => RATE_LIMITED_FACTOR,
+        };
+        let (min, max) = policy.bounds();
+        self.current = self.current.mul_f64(factor).clamp(min, max);
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> AdaptivePollPolicy {
+        AdaptivePollPolicy { enabled: true, min_secs: 2, max_secs: 60 }
+    }
+
+    #[test]
+    fn test_repeated_rate_limits_back_off_to_max() {
+        let mut interval = AdaptiveInterval::new(Duration::from_secs(5));
+        let mut previous = interval.current();
+        for _ in 0..3 {
+            let next = interval.record(PollOutcome::RateLimited, true, &policy());
+            assert!(next > previous, "{:?} should exceed {:?}", next, previous);
+            previous = next;
+        }
+        assert_eq!(previous, Duration::from_secs(40));
+
+        for _ in 0..5 {
+            interval.record(PollOutcome::RateLimited, true, &policy());
+        }
+        assert_eq!(interval.current(), Duration::from_secs(60));
+
+        // Activity can't speed the loop up while the API is throttled
+        assert_eq!(interval.record(PollOutcome::Active, true, &policy()), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_sustained_success_converges_to_min() {
+        let mut interval = AdaptiveInterval::new(Duration::from_secs(15));
+        let mut previous = interval.current();
+        for _ in 0..20 {
+            let next = interval.record(PollOutcome::Active, false, &policy());
+            assert!(next <= previous);
+            previous = next;
+        }
+        assert_eq!(previous, Duration::from_secs(2));
+
+        // Quiet polls drift back up; disabling restores the configured interval
+        assert!(interval.record(PollOutcome::Quiet, false, &policy()) > Duration::from_secs(2));
+        let disabled = AdaptivePollPolicy { enabled: false, ..policy() };
+        assert_eq!(interval.record(PollOutcome::Active, false, &disabled), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn test_shared_pressure_expires_after_cooldown() {
+        let pressure = ApiPressure::default();
+        let now = Instant::now();
+        assert!(!pressure.is_throttled(now));
+        pressure.record_rate_limit_at(now);
+        assert!(pressure.is_throttled(now + Duration::from_secs(30)));
+        assert!(!pressure.is_throttled(now + RATE_LIMIT_COOLDOWN));
+        assert_eq!(PollOutcome::from_error("HTTP status client error (429 Too Many Requests)"), PollOutcome::RateLimited);
+        assert_eq!(PollOutcome::from_error("Network error: connection reset"), PollOutcome::Failed);
+        assert_eq!(PollOutcome::from_error("Session token expired"), PollOutcome::TokenExpired);
+    }
+}
@@ -0,0 +1,134 @@
+//! Price Alert Loop — periodic check of the active profile's price alerts
+//!
+//! Every tick the distinct alert symbols are priced through the coin cache,
+//! move-alert history is updated, and each alert is evaluated with
+//! `price_alerts::evaluate`. Firing disarms the alert in the database before
+//! notifying, so a failed write can't turn into repeated notifications.
+
+use crate::app_event::{emit_event, AppEvent};
+use crate::notifications::{NotificationCategory, NotificationHandle};
+use crate::price_alerts::{evaluate, AlertCondition, AlertStep, PriceHistory};
+use crate::AppState;
+use rugplay_persistence::sqlite;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// How often alerts are checked
+const TICK_SECS: u64 = 30;
+
+/// Emitted to the frontend when an alert fires
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceAlertFiredEvent {
+    pub id: i64,
+    pub symbol: String,
+    pub condition: AlertCondition,
+    pub price: f64,
+    pub move_pct: Option<f64>,
+}
+
+/// One pass over the active profile's alerts
+async fn check_alerts(app_handle: &tauri::AppHandle, history: &mut PriceHistory) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+
+    let (pool, alerts, token) = {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let Some(profile) = sqlite::get_active_profile(db.pool()).await.map_err(|e| e.to_string())? else {
+            return Ok(());
+        };
+        let alerts = sqlite::get_price_alerts(db.pool(), profile.id).await.map_err(|e| e.to_string())?;
+        if alerts.is_empty() {
+            return Ok(());
+        }
+        let encrypted = sqlite::get_profile_token(db.pool(), profile.id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Profile token not found")?;
+        let token = state.encryptor.decrypt(&encrypted).map_err(|e| e.to_string())?;
+        (db.pool().clone(), alerts, token)
+    };
+
+    let client = state.api_client(&token);
+    let now = chrono::Utc::now().timestamp();
+    let max_age = Duration::from_secs(TICK_SECS / 2);
+
+    let symbols: HashSet<String> = alerts.iter().map(|a| a.symbol.clone()).collect();
+    let mut prices = HashMap::new();
+    for symbol in &symbols {
+        match client.get_coin_within(symbol, max_age).await {
+            Ok(coin) if coin.has_valid_price() => {
+                history.record(symbol, now, coin.current_price);
+                prices.insert(symbol.clone(), coin.current_price);
+            }
+            Ok(_) => debug!("Price alerts: no valid price for {}", symbol),
+            Err(e) => debug!("Price alerts: failed to price {}: {}", symbol, e),
+        }
+    }
+
+    let max_window = alerts
+        .iter()
+        .filter_map(AlertCondition::from_row)
+        .map(|c| match c {
+            AlertCondition::Move { window_secs, .. } => window_secs,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0);
+    history.prune(now, max_window, &symbols);
+
+    for alert in &alerts {
+        let Some(condition) = AlertCondition::from_row(alert) else { continue };
+        let Some(&price) = prices.get(&alert.symbol) else { continue };
+        let reference = match condition {
+            AlertCondition::Move { window_secs, .. } => history.reference(&alert.symbol, now, window_secs),
+            _ => None,
+        };
+
+        match evaluate(&condition, alert.armed, price, reference) {
+            AlertStep::Fire { price, move_pct } => {
+                info!("Price alert #{} fired: {} at ${:.8}", alert.id, condition.describe(&alert.symbol), price);
+                if let Err(e) = sqlite::mark_price_alert_fired(&pool, alert.id).await {
+                    warn!("Price alerts: failed to disarm #{}: {}", alert.id, e);
+                    continue;
+                }
+                if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+                    let body = match move_pct {
+                        Some(moved) => format!("${} moved {:+.1}% to ${:.8}", alert.symbol, moved, price),
+                        None => format!("${} is at ${:.8}", alert.symbol, price),
+                    };
+                    notif.notify_dedup(NotificationCategory::Alert, &format!("Price Alert: {}", condition.describe(&alert.symbol)), &body).await;
+                }
+                let _ = emit_event(
+                    app_handle,
+                    AppEvent::PriceAlertFired(PriceAlertFiredEvent { id: alert.id, symbol: alert.symbol.clone(), condition, price, move_pct }),
+                );
+            }
+            AlertStep::Rearm => {
+                debug!("Price alert #{} re-armed", alert.id);
+                if let Err(e) = sqlite::rearm_price_alert(&pool, alert.id).await {
+                    warn!("Price alerts: failed to re-arm #{}: {}", alert.id, e);
+                }
+            }
+            AlertStep::Hold => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Spawn the background alert checker
+pub fn spawn_price_alert_task(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        let mut history = PriceHistory::default();
+        loop {
+            if let Err(e) = check_alerts(&app_handle, &mut history).await {
+                debug!("Price alerts: check skipped: {}", e);
+            }
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
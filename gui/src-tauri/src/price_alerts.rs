@@ -0,0 +1,233 @@
+//! Price Alerts — crossing and move triggers for any coin
+//!
+//! Alerts are checked by a lightweight background task (`price_alert_loop`),
+//! independent of holdings and sentinels. Each alert is a condition ("above $X", "below
+//! $X", "moves ±Y% within Z minutes") plus an armed flag:
+//!
+//! - armed and the condition holds → fire once and disarm
+//! - disarmed and the condition has clearly cleared → re-arm
+//!
+//! "Clearly" adds a little hysteresis so a price wobbling around a level
+//! doesn't re-arm and re-fire on every check. Move alerts compare against
+//! the oldest price seen within their window, kept in memory by the task.
+
+use rugplay_persistence::sqlite::PriceAlertRow;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Crossing alerts re-arm once the price is this far back across the level
+const CROSS_REARM_MARGIN_PCT: f64 = 1.0;
+
+/// Move alerts re-arm once the move shrinks below this fraction of the target
+const MOVE_REARM_FRACTION: f64 = 0.5;
+
+/// What an alert watches for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum AlertCondition {
+    /// Price at or above `price`
+    Above { price: f64 },
+    /// Price at or below `price`
+    Below { price: f64 },
+    /// Price moved at least `pct` percent (either way) within `window_secs`
+    Move { pct: f64, window_secs: u64 },
+}
+
+impl AlertCondition {
+    /// Rebuild from the stored kind/target/window columns
+    pub fn from_row(row: &PriceAlertRow) -> Option<Self> {
+        match row.kind.as_str() {
+            "above" => Some(Self::Above { price: row.target }),
+            "below" => Some(Self::Below { price: row.target }),
+            "move" => Some(Self::Move { pct: row.target, window_secs: row.window_secs.unwrap_or(0).max(0) as u64 }),
+            _ => None,
+        }
+    }
+
+    /// Stored (kind, target, window_secs) columns
+    pub fn columns(&self) -> (&'static str, f64, Option<i64>) {
+        match *self {
+            Self::Above { price } => ("above", price, None),
+            Self::Below { price } => ("below", price, None),
+            Self::Move { pct, window_secs } => ("move", pct, Some(window_secs as i64)),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Self::Above { price } | Self::Below { price } if !(price.is_finite() && price > 0.0) => {
+                Err("Alert price must be greater than 0".to_string())
+            }
+            Self::Move { pct, .. } if !(pct.is_finite() && pct > 0.0) => {
+                Err("Move percentage must be greater than 0".to_string())
+            }
+            Self::Move { window_secs: 0, .. } => Err("Move window must be at least 1 second".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn describe(&self, symbol: &str) -> String {
+        match *self {
+            Self::Above { price } => format!("${} above ${:.8}", symbol, price),
+            Self::Below { price } => format!("${} below ${:.8}", symbol, price),
+            Self::Move { pct, window_secs } => {
+                format!("${} moves ±{}% within {} min", symbol, pct, window_secs.div_ceil(60))
+            }
+        }
+    }
+}
+
+/// Recent prices per symbol, for move alerts
+#[derive(Debug, Default)]
+pub struct PriceHistory {
+    prices: HashMap<String, VecDeque<(i64, f64)>>,
+}
+
+impl PriceHistory {
+    pub fn record(&mut self, symbol: &str, at: i64, price: f64) {
+        self.prices.entry(symbol.to_string()).or_default().push_back((at, price));
+    }
+
+    /// Drop samples older than `max_window_secs`, and symbols no longer watched
+    pub fn prune(&mut self, now: i64, max_window_secs: u64, watched: &HashSet<String>) {
+        self.prices.retain(|symbol, samples| {
+            while samples.front().is_some_and(|(at, _)| now - at > max_window_secs as i64) {
+                samples.pop_front();
+            }
+            watched.contains(symbol) && !samples.is_empty()
+        });
+    }
+
+    /// Oldest price seen for `symbol` within the window ending at `now`
+    pub fn reference(&self, symbol: &str, now: i64, window_secs: u64) -> Option<f64> {
+        self.prices
+            .get(symbol)?
+            .iter()
+            .find(|(at, _)| now - at <= window_secs as i64)
+            .map(|(_, price)| *price)
+    }
+}
+
+/// What to do with an alert after a price check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AlertStep {
+    /// Condition started to hold while armed: notify and disarm
+    Fire { price: f64, move_pct: Option<f64> },
+    /// Condition cleared while disarmed: arm again
+    Rearm,
+    /// Nothing changes
+    Hold,
+}
+
+/// Whether the condition holds now (`None` if it can't be judged yet)
+/// and whether it has cleared enough to re-arm
+fn condition_state(condition: &AlertCondition, price: f64, reference: Option<f64>) -> Option<(bool, bool, Option<f64>)> {
+    let margin = CROSS_REARM_MARGIN_PCT / 100.0;
+    match *condition {
+        AlertCondition::Above { price: level } => Some((price >= level, price < level * (1.0 - margin), None)),
+        AlertCondition::Below { price: level } => Some((price <= level, price > level * (1.0 + margin), None)),
+        AlertCondition::Move { pct, .. } => {
+            let reference = reference.filter(|r| *r > 0.0)?;
+            let moved = (price - reference) / reference * 100.0;
+            Some((moved.abs() >= pct, moved.abs() < pct * MOVE_REARM_FRACTION, Some(moved)))
+        }
+    }
+}
+
+/// Decide what an alert does at `price`. `reference` is the window-start
+/// price for move alerts and ignored otherwise.
+pub fn evaluate(condition: &AlertCondition, armed: bool, price: f64, reference: Option<f64>) -> AlertStep {
+    let Some((holds, cleared, move_pct)) = condition_state(condition, price, reference) else {
+        return AlertStep::Hold;
+    };
+    if armed && holds {
+        AlertStep::Fire { price, move_pct }
+    } else if !armed && cleared {
+        AlertStep::Rearm
+    } else {
+        AlertStep::Hold
+    }
+}
+
+/// Whether a new alert should start armed: a condition that already holds
+/// when the alert is created waits for the next crossing.
+pub fn starts_armed(condition: &AlertCondition, price: Option<f64>) -> bool {
+    match (condition, price) {
+        (AlertCondition::Move { .. }, _) | (_, None) => true,
+        (_, Some(price)) => !condition_state(condition, price, None).is_some_and(|(holds, _, _)| holds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crossing_fires_once_and_rearms_after_clearing() {
+        let above = AlertCondition::Above { price: 1.0 };
+        let mut armed = true;
+        let mut fired = 0;
+        // Rises through the level, hovers around it, dips just under, then
+        // falls clearly below and crosses again
+        for price in [0.9, 1.05, 1.2, 0.995, 1.01, 0.95, 1.1] {
+            match evaluate(&above, armed, price, None) {
+                AlertStep::Fire { .. } => {
+                    fired += 1;
+                    armed = false;
+                }
+                AlertStep::Rearm => armed = true,
+                AlertStep::Hold => {}
+            }
+        }
+        assert_eq!(fired, 2);
+
+        let below = AlertCondition::Below { price: 0.5 };
+        assert_eq!(evaluate(&below, true, 0.5, None), AlertStep::Fire { price: 0.5, move_pct: None });
+        assert_eq!(evaluate(&below, false, 0.502, None), AlertStep::Hold);
+        assert_eq!(evaluate(&below, false, 0.6, None), AlertStep::Rearm);
+    }
+
+    #[test]
+    fn test_move_uses_window_reference() {
+        let mut history = PriceHistory::default();
+        history.record("AAA", 0, 1.0);
+        history.record("AAA", 300, 1.05);
+        history.record("AAA", 600, 1.12);
+
+        let cond = AlertCondition::Move { pct: 10.0, window_secs: 600 };
+        // Window covers the 1.0 sample: +12%
+        let step = evaluate(&cond, true, 1.12, history.reference("AAA", 600, 600));
+        assert!(matches!(step, AlertStep::Fire { move_pct: Some(m), .. } if (m - 12.0).abs() < 1e-9));
+
+        // A shorter window starts at 1.05: under 10%
+        let short = AlertCondition::Move { pct: 10.0, window_secs: 300 };
+        assert_eq!(evaluate(&short, true, 1.12, history.reference("AAA", 600, 300)), AlertStep::Hold);
+
+        // Without any reference the alert can't be judged
+        assert_eq!(evaluate(&cond, true, 2.0, None), AlertStep::Hold);
+
+        // Re-arms only once the move is back under half the target
+        assert_eq!(evaluate(&cond, false, 1.06, Some(1.0)), AlertStep::Hold);
+        assert_eq!(evaluate(&cond, false, 1.04, Some(1.0)), AlertStep::Rearm);
+
+        let watched: HashSet<String> = ["AAA".to_string()].into();
+        history.prune(900, 600, &watched);
+        assert_eq!(history.reference("AAA", 900, 600), Some(1.05));
+        history.prune(900, 600, &HashSet::new());
+        assert_eq!(history.reference("AAA", 900, 600), None);
+    }
+
+    #[test]
+    fn test_new_alerts_wait_for_the_next_crossing() {
+        let above = AlertCondition::Above { price: 1.0 };
+        assert!(!starts_armed(&above, Some(1.5)));
+        assert!(starts_armed(&above, Some(0.5)));
+        assert!(starts_armed(&above, None));
+        assert!(starts_armed(&AlertCondition::Move { pct: 5.0, window_secs: 60 }, Some(1.0)));
+
+        let (kind, target, window) = AlertCondition::Move { pct: 5.0, window_secs: 900 }.columns();
+        assert_eq!((kind, target, window), ("move", 5.0, Some(900)));
+        assert!(AlertCondition::Above { price: 0.0 }.validate().is_err());
+        assert!(AlertCondition::Move { pct: 5.0, window_secs: 0 }.validate().is_err());
+    }
+}
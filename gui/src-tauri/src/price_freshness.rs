@@ -0,0 +1,66 @@
+//! Price Freshness — how old a quote each decision may act on
+//!
+//! Coin data can come from the shared cache (30s TTL) or a slow fetch. Each
+//! decision that reads a coin price names itself here and gets a maximum
+//! age; anything older is re-fetched via `RugplayClient::get_coin_within`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Decisions that read a coin quote
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceDecision {
+    /// Quote shown in the trade dialog before a manual trade
+    TradePreview,
+    /// Dip buyer deciding whether and how much to buy
+    AutomatedBuy,
+}
+
+/// Maximum quote age per decision (settings key `price_freshness`).
+/// 0 means always fetch a fresh quote.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceFreshnessPolicy {
+    pub trade_preview_max_age_secs: u64,
+    pub automated_buy_max_age_secs: u64,
+}
+
+impl Default for PriceFreshnessPolicy {
+    fn default() -> Self {
+        Self {
+            trade_preview_max_age_secs: 10,
+            automated_buy_max_age_secs: 5,
+        }
+    }
+}
+
+impl PriceFreshnessPolicy {
+    pub fn max_age(&self, decision: PriceDecision) -> Duration {
+        Duration::from_secs(match decision {
+            PriceDecision::TradePreview => self.trade_preview_max_age_secs,
+            PriceDecision::AutomatedBuy => self.automated_buy_max_age_secs,
+        })
+    }
+}
+
+/// A coin price with how old it is, for trade previews
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoinQuote {
+    pub symbol: String,
+    pub price: f64,
+    /// When the quote was fetched from the API (RFC 3339)
+    pub fetched_at: Option<String>,
+    pub age_ms: u64,
+}
+
+impl CoinQuote {
+    pub fn from_coin(coin: &rugplay_core::CoinDetails, now: chrono::DateTime<chrono::Utc>) -> Self {
+        Self {
+            symbol: coin.symbol.clone(),
+            price: coin.current_price,
+            fetched_at: coin.fetched_at.map(|at| at.to_rfc3339()),
+            age_ms: coin.age(now).map(|age| age.as_millis() as u64).unwrap_or(0),
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Profile Duplicates — the same Rugplay session saved as two profiles
+//!
+//! Two profiles holding the same token are one account: automation on
+//! either trades the same balance, and failing over from one to the other
+//! changes nothing. Tokens are encrypted with a fresh IV each time, so
+//! profiles are compared by their decrypted tokens. What happens when a
+//! token being saved already belongs to another profile is configurable
+//! (settings key `duplicate_token_policy`).
+
+use rugplay_core::{Profile, Result};
+use rugplay_persistence::{sqlite, TokenEncryptor};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// What to do when a token being saved already belongs to another profile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateTokenPolicy {
+    /// Save it anyway and log a warning
+    #[default]
+    Warn,
+    /// Reject the save
+    Refuse,
+    /// Adding returns the existing profile instead of creating a second one
+    Merge,
+}
+
+/// The outcome of checking a token against the saved profiles
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenCollision {
+    /// No other profile has this token
+    Unique,
+    /// Save it, warning which profile it duplicates
+    Warn(String),
+    Refuse(String),
+    /// Use this profile instead of adding a new one
+    Merge(i64),
+}
+
+impl DuplicateTokenPolicy {
+    /// Decide what to do with a token that `existing` already holds.
+    /// `adding` is false when replacing a profile's token, where merging
+    /// isn't possible and refusing takes its place.
+    pub fn resolve(self, existing: Option<&Profile>, adding: bool) -> TokenCollision {
+        let Some(existing) = existing else {
+            return TokenCollision::Unique;
+        };
+        let message = format!(
+            "This token is already saved as profile @{} (#{}); both would trade the same account",
+            existing.username, existing.id
+        );
+        match self {
+            Self::Warn => TokenCollision::Warn(message),
+            Self::Merge if adding => TokenCollision::Merge(existing.id),
+            Self::Refuse | Self::Merge => TokenCollision::Refuse(message),
+        }
+    }
+}
+
+/// The profile, other than `exclude`, whose saved token is `token`.
+/// Profiles whose token can't be decrypted are skipped.
+pub async fn find_token_owner(
+    pool: &SqlitePool,
+    encryptor: &TokenEncryptor,
+    token: &str,
+    exclude: Option<i64>,
+) -> Result<Option<Profile>> {
+    for profile in sqlite::list_profiles(pool).await? {
+        if Some(profile.id) == exclude {
+            continue;
+        }
+        let Some(encrypted) = sqlite::get_profile_token(pool, profile.id).await? else {
+            continue;
+        };
+        if encryptor.decrypt(&encrypted).is_ok_and(|saved| saved == token) {
+            return Ok(Some(profile));
+        }
+    }
+    Ok(None)
+}
+
+/// Groups of profile ids sharing a token, lowest id first. Profiles with a
+/// token of their own aren't listed.
+pub async fn duplicate_groups(pool: &SqlitePool, encryptor: &TokenEncryptor) -> Result<Vec<Vec<i64>>> {
+    let mut profiles = sqlite::list_profiles(pool).await?;
+    profiles.sort_by_key(|p| p.id);
+
+    let mut groups: Vec<(String, Vec<i64>)> = Vec::new();
+    for profile in profiles {
+        let Some(encrypted) = sqlite::get_profile_token(pool, profile.id).await? else {
+            continue;
+        };
+        let Ok(token) = encryptor.decrypt(&encrypted) else { continue };
+        match groups.iter_mut().find(|(t, _)| *t == token) {
+            Some((_, ids)) => ids.push(profile.id),
+            None => groups.push((token, vec![profile.id])),
+        }
+    }
+    Ok(groups.into_iter().map(|(_, ids)| ids).filter(|ids| ids.len() > 1).collect())
+}
+
+pub async fn load_duplicate_token_policy(pool: &SqlitePool) -> DuplicateTokenPolicy {
+    sqlx::query_scalar::<_, String>("SELECT value FROM settings WHERE key = 'duplicate_token_policy'")
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
+pub async fn save_duplicate_token_policy(pool: &SqlitePool, policy: DuplicateTokenPolicy) -> Result<()> {
+    let json = serde_json::to_string(&policy).map_err(|e| rugplay_core::Error::DatabaseError(e.to_string()))?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('duplicate_token_policy', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(json)
+    .execute(pool)
+    .await
+    .map_err(|e| rugplay_core::Error::DatabaseError(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_persistence::Database;
+
+    #[tokio::test]
+    async fn test_adding_an_already_saved_token_is_flagged() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        let encryptor = TokenEncryptor::new(&[7u8; 32]).unwrap();
+        let main = sqlite::create_profile(pool, "trader", None, &encryptor.encrypt("session_a").unwrap()).await.unwrap();
+        sqlite::create_profile(pool, "alt", None, &encryptor.encrypt("session_b").unwrap()).await.unwrap();
+
+        // Same token, different name (e.g. saved unverified) and a fresh IV
+        let owner = find_token_owner(pool, &encryptor, "session_a", None).await.unwrap().unwrap();
+        assert_eq!(owner.id, main);
+        assert!(find_token_owner(pool, &encryptor, "session_c", None).await.unwrap().is_none());
+        // A profile re-saving its own token isn't a collision
+        assert!(find_token_owner(pool, &encryptor, "session_a", Some(main)).await.unwrap().is_none());
+
+        assert_eq!(load_duplicate_token_policy(pool).await, DuplicateTokenPolicy::Warn);
+        assert!(matches!(DuplicateTokenPolicy::Warn.resolve(Some(&owner), true), TokenCollision::Warn(m) if m.contains("@trader")));
+        assert!(matches!(DuplicateTokenPolicy::Refuse.resolve(Some(&owner), true), TokenCollision::Refuse(_)));
+        assert_eq!(DuplicateTokenPolicy::Merge.resolve(Some(&owner), true), TokenCollision::Merge(main));
+        assert!(matches!(DuplicateTokenPolicy::Merge.resolve(Some(&owner), false), TokenCollision::Refuse(_)));
+        assert_eq!(DuplicateTokenPolicy::Refuse.resolve(None, true), TokenCollision::Unique);
+
+        save_duplicate_token_policy(pool, DuplicateTokenPolicy::Merge).await.unwrap();
+        assert_eq!(load_duplicate_token_policy(pool).await, DuplicateTokenPolicy::Merge);
+
+        // Profiles that already collide are reported together
+        let dup = sqlite::create_profile(pool, "trader2", None, &encryptor.encrypt("session_a").unwrap()).await.unwrap();
+        assert_eq!(duplicate_groups(pool, &encryptor).await.unwrap(), vec![vec![main, dup]]);
+    }
+}
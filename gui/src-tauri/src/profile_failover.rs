@@ -0,0 +1,188 @@
+//! Profile Failover — keep automation running on a backup account
+//!
+//! Users with several profiles can list backups in order. When the active
+//! profile's token expires, the automation loops report it here and the app
+//! switches to the next listed profile whose token still works (settings key
+//! `profile_failover`). Rate limits only count when the user opts in with a
+//! window: they usually pass on their own, and switching accounts over them
+//! just moves the load.
+
+use crate::poll_interval::PollOutcome;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Failover settings
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileFailoverConfig {
+    pub enabled: bool,
+    /// Profile ids in failover order
+    pub profile_ids: Vec<i64>,
+    /// Also fail over once 429s persist this long with no successful poll
+    /// (seconds). 0 leaves rate limits alone.
+    #[serde(default)]
+    pub rate_limit_failover_secs: u64,
+}
+
+impl ProfileFailoverConfig {
+    /// How long sustained rate limiting has to last to trigger, if it does
+    pub fn rate_limit_window(&self) -> Option<Duration> {
+        (self.rate_limit_failover_secs > 0).then(|| Duration::from_secs(self.rate_limit_failover_secs))
+    }
+}
+
+/// Emitted after switching to a backup profile
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileFailoverEvent {
+    pub from_profile_id: Option<i64>,
+    pub to_profile_id: i64,
+    /// Username of the profile switched to
+    pub username: String,
+    pub reason: String,
+}
+
+/// Why a failover was triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverTrigger {
+    TokenExpired,
+    /// Rate limited without a success for this long
+    RateLimited(Duration),
+}
+
+impl FailoverTrigger {
+    pub fn reason(&self) -> String {
+        match self {
+            Self::TokenExpired => "token expired".to_string(),
+            Self::RateLimited(d) => format!("rate limited for {}s", d.as_secs()),
+        }
+    }
+}
+
+/// Watches poll outcomes of the active profile, shared by every loop
+#[derive(Debug, Default)]
+pub struct FailoverMonitor {
+    /// A failover is running; further triggers wait for it
+    switching: AtomicBool,
+    /// Start of the current run of 429s with no successful poll in between
+    rate_limited_since: Mutex<Option<Instant>>,
+}
+
+impl FailoverMonitor {
+    /// Record a poll outcome at `now`; returns a trigger when the active
+    /// profile should be switched. Sustained rate limiting only triggers
+    /// with a `rate_limit_window`. Only one trigger is handed out until
+    /// [`FailoverMonitor::finish`] is called.
+    pub fn record_at(&self, outcome: PollOutcome, now: Instant, rate_limit_window: Option<Duration>) -> Option<FailoverTrigger> {
+        let trigger = match outcome {
+            PollOutcome::TokenExpired => FailoverTrigger::TokenExpired,
+            PollOutcome::RateLimited => {
+                let window = rate_limit_window?;
+                let mut since = self.rate_limited_since.lock().unwrap_or_else(|e| e.into_inner());
+                let limited_for = now.saturating_duration_since(*since.get_or_insert(now));
+                if limited_for < window {
+                    return None;
+                }
+                FailoverTrigger::RateLimited(limited_for)
+            }
+            PollOutcome::Active | PollOutcome::Quiet => {
+                *self.rate_limited_since.lock().unwrap_or_else(|e| e.into_inner()) = None;
+                return None;
+            }
+            PollOutcome::Failed => return None,
+        };
+        self.switching
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+            .then_some(trigger)
+    }
+
+    /// A failover attempt is over; start watching the (possibly new)
+    /// active profile from scratch
+    pub fn finish(&self) {
+        *self.rate_limited_since.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        self.switching.store(false, Ordering::Release);
+    }
+}
+
+/// Profiles to try after `active`, in order: those listed after it, then
+/// wrapping round to the start. The active profile itself is never tried.
+pub fn failover_candidates(order: &[i64], active: Option<i64>) -> Vec<i64> {
+    let start = active
+        .and_then(|id| order.iter().position(|p| *p == id))
+        .map_or(0, |i| i + 1);
+    order[start..]
+        .iter()
+        .chain(&order[..start])
+        .copied()
+        .filter(|id| Some(*id) != active)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_active_profile_fails_over_to_the_next_one() {
+        let monitor = FailoverMonitor::default();
+        let now = Instant::now();
+
+        assert_eq!(monitor.record_at(PollOutcome::Quiet, now, None), None);
+        assert_eq!(monitor.record_at(PollOutcome::TokenExpired, now, None), Some(FailoverTrigger::TokenExpired));
+        // Other loops hitting the same expiry don't start a second switch
+        assert_eq!(monitor.record_at(PollOutcome::TokenExpired, now, None), None);
+        monitor.finish();
+        assert_eq!(monitor.record_at(PollOutcome::TokenExpired, now, None), Some(FailoverTrigger::TokenExpired));
+
+        // Profile 2 is active: 3 is next, then wrap round to 1
+        assert_eq!(failover_candidates(&[1, 2, 3], Some(2)), vec![3, 1]);
+        assert_eq!(failover_candidates(&[1, 2, 3], Some(3)), vec![1, 2]);
+        // An active profile outside the list tries the whole list
+        assert_eq!(failover_candidates(&[1, 2, 3], Some(9)), vec![1, 2, 3]);
+        assert!(failover_candidates(&[2], Some(2)).is_empty());
+    }
+
+    #[test]
+    fn test_rate_limits_never_trigger_by_default() {
+        let monitor = FailoverMonitor::default();
+        let now = Instant::now();
+        let config = ProfileFailoverConfig::default();
+        for outcome in [PollOutcome::RateLimited, PollOutcome::Failed, PollOutcome::Active] {
+            assert_eq!(monitor.record_at(outcome, now, config.rate_limit_window()), None);
+        }
+        let later = now + Duration::from_secs(3600);
+        assert_eq!(monitor.record_at(PollOutcome::RateLimited, later, config.rate_limit_window()), None);
+
+        // Settings saved with the old rate-limit window load with it off
+        let old: ProfileFailoverConfig =
+            serde_json::from_str(r#"{"enabled":true,"profileIds":[2,1],"sustainedRateLimitSecs":300}"#).unwrap();
+        assert_eq!(old.profile_ids, vec![2, 1]);
+        assert_eq!(old.rate_limit_window(), None);
+    }
+
+    #[test]
+    fn test_opted_in_sustained_rate_limits_trigger() {
+        let monitor = FailoverMonitor::default();
+        let now = Instant::now();
+        let config = ProfileFailoverConfig { rate_limit_failover_secs: 300, ..Default::default() };
+        let window = config.rate_limit_window();
+
+        assert_eq!(monitor.record_at(PollOutcome::RateLimited, now, window), None);
+        // A success in between resets the streak
+        assert_eq!(monitor.record_at(PollOutcome::Quiet, now + Duration::from_secs(200), window), None);
+        assert_eq!(monitor.record_at(PollOutcome::RateLimited, now + Duration::from_secs(250), window), None);
+        assert_eq!(monitor.record_at(PollOutcome::Failed, now + Duration::from_secs(400), window), None);
+        assert_eq!(
+            monitor.record_at(PollOutcome::RateLimited, now + Duration::from_secs(550), window),
+            Some(FailoverTrigger::RateLimited(Duration::from_secs(300)))
+        );
+        // Already switching
+        assert_eq!(monitor.record_at(PollOutcome::TokenExpired, now + Duration::from_secs(551), window), None);
+
+        monitor.finish();
+        assert_eq!(monitor.record_at(PollOutcome::RateLimited, now + Duration::from_secs(560), window), None);
+    }
+}
@@ -0,0 +1,164 @@
+//! Profile Refresh — keep stored usernames and avatars in sync
+//!
+//! Profiles remember the username captured when they were added. If the user
+//! renames themselves on Rugplay the picker goes stale, so this task re-reads
+//! `get_session` per profile and stores whatever the server reports. The
+//! active profile is refreshed often, recently used ones daily, and profiles
+//! nobody has touched in a while are left alone unless refreshed on demand.
+
+use crate::AppState;
+use chrono::{DateTime, Utc};
+use rugplay_core::{Error, Profile};
+use rugplay_networking::RugplayClient;
+use rugplay_persistence::sqlite;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// How often the refresh task wakes up
+const TICK_SECS: u64 = 5 * 60;
+
+/// Delay before the first pass so startup isn't slowed down
+const STARTUP_DELAY_SECS: u64 = 30;
+
+/// Active profile refresh interval (30 minutes)
+const ACTIVE_REFRESH_SECS: u64 = 30 * 60;
+
+/// Recently used profile refresh interval (24 hours)
+const RECENT_REFRESH_SECS: u64 = 24 * 3600;
+
+/// Profiles selected within this many days count as recently used
+const RECENT_WINDOW_DAYS: i64 = 7;
+
+/// Pause between session requests for different profiles
+const REQUEST_SPACING_MS: u64 = 1500;
+
+/// Outcome of refreshing one profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RefreshOutcome {
+    /// Stored identity was updated
+    Updated,
+    /// Server identity matches what is stored
+    Unchanged,
+    /// Token no longer works; left for the user to re-enter
+    Expired,
+}
+
+/// How long to wait between refreshes of this profile, or `None` to skip it
+pub fn refresh_interval(profile: &Profile, now: DateTime<Utc>) -> Option<Duration> {
+    if profile.is_active {
+        return Some(Duration::from_secs(ACTIVE_REFRESH_SECS));
+    }
+    let recent = profile
+        .last_used_at
+        .is_some_and(|used| now - used < chrono::Duration::days(RECENT_WINDOW_DAYS));
+    recent.then(|| Duration::from_secs(RECENT_REFRESH_SECS))
+}
+
+/// Fetch the session for one profile and store the server's username/avatar
+pub async fn refresh_profile(
+    app_handle: &tauri::AppHandle,
+    profile_id: i64,
+) -> Result<RefreshOutcome, String> {
+    let state = app_handle.state::<AppState>();
+
+    let token = {
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        let encrypted = sqlite::get_profile_token(db.pool(), profile_id)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or("Profile not found")?;
+        state.encryptor.decrypt(&encrypted).map_err(|e| e.to_string())?
+    };
+
+    let user = match RugplayClient::new(&token).get_session().await {
+        Ok(user) => user,
+        Err(Error::TokenExpired) => return Ok(RefreshOutcome::Expired),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+    let changed = sqlite::update_profile_identity(
+        db.pool(),
+        profile_id,
+        &user.username,
+        &user.id,
+        user.image.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if changed {
+        info!("Profile {} identity refreshed: @{}", profile_id, user.username);
+        Ok(RefreshOutcome::Updated)
+    } else {
+        Ok(RefreshOutcome::Unchanged)
+    }
+}
+
+/// Refresh several profiles in turn, spacing requests to stay under rate limits
+pub async fn refresh_profiles(
+    app_handle: &tauri::AppHandle,
+    profile_ids: &[i64],
+) -> Vec<(i64, Result<RefreshOutcome, String>)> {
+    let mut results = Vec::with_capacity(profile_ids.len());
+    for (i, &id) in profile_ids.iter().enumerate() {
+        if i > 0 {
+            tokio::time::sleep(Duration::from_millis(REQUEST_SPACING_MS)).await;
+        }
+        results.push((id, refresh_profile(app_handle, id).await));
+    }
+    results
+}
+
+/// Spawn the background refresh task
+pub fn spawn_profile_refresh_task(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(STARTUP_DELAY_SECS)).await;
+
+        let mut last_refreshed: HashMap<i64, Instant> = HashMap::new();
+        loop {
+            let profiles = {
+                let state = app_handle.state::<AppState>();
+                let db_guard = state.db.read().await;
+                match db_guard.as_ref() {
+                    Some(db) => sqlite::list_profiles(db.pool()).await.unwrap_or_default(),
+                    None => Vec::new(),
+                }
+            };
+
+            let now = Utc::now();
+            let due: Vec<i64> = profiles
+                .iter()
+                .filter(|p| match refresh_interval(p, now) {
+                    Some(interval) => last_refreshed
+                        .get(&p.id)
+                        .is_none_or(|at| at.elapsed() >= interval),
+                    None => false,
+                })
+                .map(|p| p.id)
+                .collect();
+
+            if !due.is_empty() {
+                debug!("Profile refresh: {} profile(s) due", due.len());
+            }
+            for (id, result) in refresh_profiles(&app_handle, &due).await {
+                match result {
+                    Ok(outcome) => {
+                        if outcome == RefreshOutcome::Expired {
+                            debug!("Profile refresh: token for profile {} has expired", id);
+                        }
+                        last_refreshed.insert(id, Instant::now());
+                    }
+                    // Retried on the next tick
+                    Err(e) => warn!("Profile refresh: profile {} failed: {}", id, e),
+                }
+            }
+
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
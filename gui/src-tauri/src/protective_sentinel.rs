@@ -0,0 +1,258 @@
+//! Protective Sentinel — the stop-loss/take-profit every automated buy gets
+//!
+//! Sniper, mirror and dip buyer all cover a fresh buy with a sentinel. They
+//! go through this one helper so the entry price is sourced the same way
+//! (per the user's [`EntryPriceSource`]), the same instant-trigger guard
+//! applies, and a coin that is bought again updates its open sentinel
+//! instead of getting a second one.
+
+use rugplay_core::{CoinHolding, Error, Result};
+use rugplay_persistence::sqlite;
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+
+/// Sell percentage used when the app settings don't set one
+const FALLBACK_SELL_PERCENTAGE: f64 = 100.0;
+
+/// Where an auto-created sentinel takes its entry price from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EntryPriceSource {
+    /// The price this buy filled at. Precise to this buy, but a coin bought
+    /// several times gets its SL/TP measured from the latest buy only.
+    FillPrice,
+    /// The holding's weighted average purchase price. Protects DCA: the
+    /// SL/TP track the whole position, so averaging down doesn't move the
+    /// take profit below what the older buys cost.
+    #[default]
+    PortfolioAverage,
+    /// The market price once the buy has landed. Includes our own price
+    /// impact, so on thin coins the stop loss sits closer to the top.
+    CurrentMarket,
+}
+
+impl EntryPriceSource {
+    /// Whether the holding has to be fetched to price the entry
+    pub fn needs_holding(self) -> bool {
+        self != Self::FillPrice
+    }
+}
+
+/// The parts of the app's sentinel defaults that apply to auto-created sentinels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StoredSentinelDefaults {
+    pub sell_percentage: f64,
+    pub entry_price_source: EntryPriceSource,
+}
+
+impl Default for StoredSentinelDefaults {
+    fn default() -> Self {
+        Self {
+            sell_percentage: FALLBACK_SELL_PERCENTAGE,
+            entry_price_source: EntryPriceSource::default(),
+        }
+    }
+}
+
+impl StoredSentinelDefaults {
+    /// Read the sentinel defaults from the app settings
+    pub async fn load(pool: &SqlitePool) -> Self {
+        let settings_json: Option<String> = sqlx::query_scalar(
+            "SELECT value FROM settings WHERE key = 'app_settings'",
+        )
+        .fetch_optional(pool)
+        .await
+        .ok()
+        .flatten();
+
+        let Some(settings) = settings_json.and_then(|j| serde_json::from_str::<serde_json::Value>(&j).ok()) else {
+            return Self::default();
+        };
+        let defaults = &settings["sentinelDefaults"];
+        Self {
+            sell_percentage: defaults["sellPercentage"].as_f64().unwrap_or(FALLBACK_SELL_PERCENTAGE),
+            entry_price_source: serde_json::from_value(defaults["entryPriceSource"].clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Sentinel settings a module applies to its buys
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProtectiveSentinelConfig {
+    pub stop_loss_pct: f64,
+    pub take_profit_pct: f64,
+    pub trailing_stop_pct: Option<f64>,
+    /// `None` uses the sell percentage from the sentinel defaults
+    pub sell_percentage: Option<f64>,
+}
+
+/// Entry price that won't trip the stop loss the moment it is saved.
+///
+/// When an old, pricier position drags the weighted average far above the
+/// market, a sentinel at that average would sell straight away. In that case
+/// the current price is used instead.
+pub fn guarded_entry_price(avg_entry: f64, current_price: f64, stop_loss_pct: Option<f64>) -> f64 {
+    match stop_loss_pct {
+        Some(sl) if sl < 0.0 => {
+            let sl_floor = avg_entry * (1.0 + sl / 100.0);
+            if current_price > 0.0 && current_price <= sl_floor {
+                current_price
+            } else {
+                avg_entry
+            }
+        }
+        _ => avg_entry,
+    }
+}
+
+/// Entry price for a buy filled at `fill_price`, given the holding after it.
+/// Falls back to the fill price when the holding (or the price the source
+/// wants from it) is missing; `None` when there is no price to go on.
+pub fn protective_entry_price(
+    source: EntryPriceSource,
+    holding: Option<&CoinHolding>,
+    fill_price: Option<f64>,
+    stop_loss_pct: f64,
+) -> Option<f64> {
+    let current_price = holding
+        .map(|h| h.current_price)
+        .filter(|&p| p > 0.0)
+        .or(fill_price);
+    let entry = match source {
+        EntryPriceSource::FillPrice => fill_price,
+        EntryPriceSource::PortfolioAverage => holding
+            .map(|h| h.avg_purchase_price)
+            .filter(|&p| p > 0.0)
+            .or(fill_price),
+        EntryPriceSource::CurrentMarket => current_price,
+    }?;
+    Some(guarded_entry_price(entry, current_price.unwrap_or(0.0), Some(stop_loss_pct)))
+}
+
+/// Create or update the open sentinel for `symbol`; returns its id. Refuses
+/// when no entry price can be found, rather than arming one at 0.
+pub async fn upsert_protective_sentinel(
+    pool: &SqlitePool,
+    profile_id: i64,
+    symbol: &str,
+    holding: Option<&CoinHolding>,
+    fill_price: Option<f64>,
+    config: &ProtectiveSentinelConfig,
+    defaults: &StoredSentinelDefaults,
+) -> Result<i64> {
+    let entry_price = protective_entry_price(defaults.entry_price_source, holding, fill_price, config.stop_loss_pct)
+        .ok_or_else(|| Error::InvalidData(format!("No entry price for {}", symbol)))?;
+    let sell_percentage = config.sell_percentage.unwrap_or(defaults.sell_percentage);
+
+    sqlite::upsert_sentinel(
+        pool,
+        profile_id,
+        symbol,
+        Some(config.stop_loss_pct),
+        Some(config.take_profit_pct),
+        config.trailing_stop_pct,
+        sell_percentage,
+        entry_price,
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_persistence::test_support::db_with_profile;
+
+    fn holding(symbol: &str, avg_purchase_price: f64, current_price: f64) -> CoinHolding {
+        serde_json::from_value(serde_json::json!({
+            "symbol": symbol,
+            "quantity": 100.0,
+            "currentPrice": current_price,
+            "value": 100.0 * current_price,
+            "avgPurchasePrice": avg_purchase_price,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_guard_avoids_instant_stop_loss() {
+        // Market is well above the SL floor: keep the weighted average
+        assert_eq!(guarded_entry_price(1.0, 0.95, Some(-10.0)), 1.0);
+        // Old expensive buys put the average above the floor: use the market
+        assert_eq!(guarded_entry_price(1.0, 0.5, Some(-10.0)), 0.5);
+        // No stop loss (or a nonsensical positive one) never swaps the price
+        assert_eq!(guarded_entry_price(1.0, 0.5, None), 1.0);
+        assert_eq!(guarded_entry_price(1.0, 0.5, Some(5.0)), 1.0);
+        // Missing market price can't be trusted as an entry
+        assert_eq!(guarded_entry_price(1.0, 0.0, Some(-10.0)), 1.0);
+    }
+
+    #[test]
+    fn test_each_entry_price_source() {
+        use EntryPriceSource::*;
+
+        // DCA position: bought higher before, this buy filled at 2.2 and
+        // pushed the market to 2.3
+        let h = holding("COIN", 2.0, 2.3);
+        assert_eq!(protective_entry_price(FillPrice, Some(&h), Some(2.2), -10.0), Some(2.2));
+        assert_eq!(protective_entry_price(PortfolioAverage, Some(&h), Some(2.2), -10.0), Some(2.0));
+        assert_eq!(protective_entry_price(CurrentMarket, Some(&h), Some(2.2), -10.0), Some(2.3));
+
+        // Every source falls back to the fill when the holding is unknown,
+        // and with neither there is no entry to arm a sentinel at
+        for source in [FillPrice, PortfolioAverage, CurrentMarket] {
+            assert_eq!(protective_entry_price(source, None, Some(2.2), -10.0), Some(2.2));
+            assert_eq!(protective_entry_price(source, None, None, -10.0), None);
+        }
+        assert_eq!(protective_entry_price(PortfolioAverage, Some(&h), None, -10.0), Some(2.0));
+
+        // The guard applies whatever the source
+        let dragged = holding("COIN", 4.0, 2.1);
+        assert_eq!(protective_entry_price(PortfolioAverage, Some(&dragged), Some(2.2), -10.0), Some(2.1));
+        assert_eq!(protective_entry_price(FillPrice, Some(&holding("COIN", 2.2, 1.5)), Some(2.2), -10.0), Some(1.5));
+
+        assert!(!FillPrice.needs_holding());
+        assert!(PortfolioAverage.needs_holding() && CurrentMarket.needs_holding());
+    }
+
+    #[tokio::test]
+    async fn test_repeat_buys_update_one_sentinel() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+        assert_eq!(StoredSentinelDefaults::load(pool).await, StoredSentinelDefaults::default());
+        sqlx::query("INSERT INTO settings (key, value) VALUES ('app_settings', ?)")
+            .bind(r#"{"sentinelDefaults":{"sellPercentage":60,"entryPriceSource":"currentMarket"}}"#)
+            .execute(pool)
+            .await
+            .unwrap();
+        let defaults = StoredSentinelDefaults::load(pool).await;
+        assert_eq!(defaults.entry_price_source, EntryPriceSource::CurrentMarket);
+        let defaults = StoredSentinelDefaults { entry_price_source: EntryPriceSource::PortfolioAverage, ..defaults };
+
+        let config = ProtectiveSentinelConfig {
+            stop_loss_pct: -20.0,
+            take_profit_pct: 50.0,
+            trailing_stop_pct: None,
+            sell_percentage: None,
+        };
+        let first = upsert_protective_sentinel(pool, 1, "COIN", None, Some(1.0), &config, &defaults).await.unwrap();
+
+        // Second buy lower down: same sentinel, re-anchored to the new average
+        let after = holding("COIN", 0.9, 0.8);
+        let config = ProtectiveSentinelConfig { sell_percentage: Some(75.0), ..config };
+        let second = upsert_protective_sentinel(pool, 1, "COIN", Some(&after), Some(0.8), &config, &defaults).await.unwrap();
+        assert_eq!(first, second);
+
+        let sentinels = sqlite::get_sentinels(pool, 1).await.unwrap();
+        assert_eq!(sentinels.len(), 1);
+        assert_eq!(sentinels[0].entry_price, 0.9);
+        assert_eq!(sentinels[0].sell_percentage, 75.0);
+
+        // Settings default applies when the module doesn't set a sell %
+        let config = ProtectiveSentinelConfig { sell_percentage: None, ..config };
+        upsert_protective_sentinel(pool, 1, "OTHER", None, Some(3.0), &config, &defaults).await.unwrap();
+        assert!(upsert_protective_sentinel(pool, 1, "BLIND", None, None, &config, &defaults).await.is_err());
+        let other = sqlite::get_sentinels(pool, 1).await.unwrap().into_iter().find(|s| s.symbol == "OTHER").unwrap();
+        assert_eq!(other.sell_percentage, 60.0);
+        assert_eq!(other.entry_price, 3.0);
+    }
+}
@@ -0,0 +1,307 @@
+//! Portfolio Rebalance — orders that move holdings toward target allocations
+//!
+//! Targets are percentages of total portfolio value (cash included) per
+//! coin. Coins without a target are left alone and whatever the targets
+//! don't allocate stays in cash, so a target of 0 is how a coin is exited.
+//! Sells are planned before buys so their proceeds can fund the top-ups.
+
+use rugplay_core::{truncate_to_8_decimals, TradeType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Limits a rebalance runs under
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceOptions {
+    /// Positions closer than this (USD) to their target are left as is
+    #[serde(default = "default_min_trade_usd")]
+    pub min_trade_usd: f64,
+    /// Largest price impact one order may have; bigger orders are shrunk
+    /// to fit (0 = no cap)
+    #[serde(default = "default_max_slippage_pct")]
+    pub max_slippage_pct: f64,
+}
+
+fn default_min_trade_usd() -> f64 { 1.0 }
+fn default_max_slippage_pct() -> f64 { 5.0 }
+
+impl Default for RebalanceOptions {
+    fn default() -> Self {
+        Self {
+            min_trade_usd: default_min_trade_usd(),
+            max_slippage_pct: default_max_slippage_pct(),
+        }
+    }
+}
+
+/// A coin the portfolio holds or a target names, priced from its pool
+#[derive(Debug, Clone)]
+pub struct RebalanceCoin {
+    pub symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    /// USD side of the pool (0 when unknown)
+    pub pool_base: f64,
+}
+
+/// One trade of the rebalance
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceOrder {
+    pub symbol: String,
+    pub trade_type: TradeType,
+    /// Coins for a sell, USD for a buy
+    pub amount: f64,
+    pub value_usd: f64,
+    pub current_pct: f64,
+    pub target_pct: f64,
+    /// Estimated price impact of the order
+    pub slippage_pct: f64,
+    /// The order was shrunk to stay under the slippage cap or the cash on hand
+    pub capped: bool,
+}
+
+/// A target the rebalance won't act on, and why
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceSkip {
+    pub symbol: String,
+    pub reason: String,
+}
+
+/// Orders that take the portfolio toward its targets, sells first
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalancePlan {
+    pub total_value: f64,
+    pub orders: Vec<RebalanceOrder>,
+    pub skipped: Vec<RebalanceSkip>,
+}
+
+/// Outcome of one submitted rebalance order
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceFill {
+    pub symbol: String,
+    pub trade_type: TradeType,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// What `rebalance_to_targets` planned and, unless previewing, submitted
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RebalanceResult {
+    pub plan: RebalancePlan,
+    pub dry_run: bool,
+    pub fills: Vec<RebalanceFill>,
+}
+
+/// Reject targets that aren't percentages or allocate more than everything
+pub fn validate_targets(targets: &HashMap<String, f64>) -> Result<(), String> {
+    if targets.is_empty() {
+        return Err("No target allocations given".to_string());
+    }
+    if let Some((symbol, pct)) = targets.iter().find(|(_, p)| !p.is_finite() || **p < 0.0 || **p > 100.0) {
+        return Err(format!("Target for {} must be between 0 and 100%, got {}", symbol, pct));
+    }
+    let total: f64 = targets.values().sum();
+    if total > 100.0 + 1e-9 {
+        return Err(format!("Targets add up to {:.2}%, more than 100%", total));
+    }
+    Ok(())
+}
+
+/// Largest order value that keeps price impact under the cap
+fn slippage_cap_usd(pool_base: f64, max_slippage_pct: f64) -> f64 {
+    if max_slippage_pct > 0.0 {
+        pool_base * max_slippage_pct / 100.0
+    } else {
+        f64::INFINITY
+    }
+}
+
+fn slippage_pct(value_usd: f64, pool_base: f64) -> f64 {
+    if pool_base > 0.0 { value_usd / pool_base * 100.0 } else { 0.0 }
+}
+
+/// Work out the trades that move each targeted coin to its target share of
+/// the portfolio. Buys are funded from `cash` plus the planned sells, the
+/// biggest shortfall first.
+pub fn plan_rebalance(
+    cash: f64,
+    coins: &[RebalanceCoin],
+    targets: &HashMap<String, f64>,
+    options: &RebalanceOptions,
+) -> RebalancePlan {
+    let holdings_value: f64 = coins
+        .iter()
+        .filter(|c| c.price.is_finite() && c.price > 0.0)
+        .map(|c| c.quantity * c.price)
+        .sum();
+    let total_value = cash.max(0.0) + holdings_value;
+    let mut plan = RebalancePlan { total_value, ..Default::default() };
+    if total_value <= 0.0 {
+        return plan;
+    }
+
+    let mut symbols: Vec<&String> = targets.keys().collect();
+    symbols.sort();
+
+    let mut buys = Vec::new();
+    for symbol in symbols {
+        let target_pct = targets[symbol];
+        let skip = |reason: String| RebalanceSkip { symbol: symbol.clone(), reason };
+
+        let Some(coin) = coins.iter().find(|c| &c.symbol == symbol) else {
+            plan.skipped.push(skip("No price data".to_string()));
+            continue;
+        };
+        if !coin.price.is_finite() || coin.price <= 0.0 {
+            plan.skipped.push(skip("No valid price".to_string()));
+            continue;
+        }
+
+        let current_value = coin.quantity * coin.price;
+        let current_pct = current_value / total_value * 100.0;
+        let diff = total_value * target_pct / 100.0 - current_value;
+        // An exit sells the whole position, however small
+        let exiting = target_pct == 0.0 && coin.quantity > 0.0;
+        if diff.abs() < options.min_trade_usd.max(0.0) && !exiting {
+            plan.skipped.push(skip(format!("Within ${:.2} of target", options.min_trade_usd)));
+            continue;
+        }
+
+        let cap = slippage_cap_usd(coin.pool_base, options.max_slippage_pct);
+        if cap <= 0.0 {
+            plan.skipped.push(skip("Pool data unavailable for the slippage cap".to_string()));
+            continue;
+        }
+
+        let order = RebalanceOrder {
+            symbol: symbol.clone(),
+            trade_type: TradeType::Buy,
+            amount: 0.0,
+            value_usd: 0.0,
+            current_pct,
+            target_pct,
+            slippage_pct: 0.0,
+            capped: false,
+        };
+        if diff < 0.0 {
+            let capped = -diff > cap;
+            let value = (-diff).min(cap);
+            let amount = if exiting && !capped {
+                coin.quantity
+            } else {
+                truncate_to_8_decimals((value / coin.price).min(coin.quantity))
+            };
+            plan.orders.push(RebalanceOrder {
+                trade_type: TradeType::Sell,
+                amount,
+                value_usd: value,
+                slippage_pct: slippage_pct(value, coin.pool_base),
+                capped,
+                ..order
+            });
+        } else {
+            buys.push((diff, cap, coin.pool_base, order));
+        }
+    }
+
+    let mut cash_available = cash.max(0.0) + plan.orders.iter().map(|o| o.value_usd).sum::<f64>();
+    buys.sort_by(|a, b| b.0.total_cmp(&a.0));
+    for (diff, cap, pool_base, order) in buys {
+        let value = diff.min(cap).min(cash_available);
+        if value <= 0.0 || value < options.min_trade_usd {
+            plan.skipped.push(RebalanceSkip { symbol: order.symbol, reason: "Not enough cash to top up".to_string() });
+            continue;
+        }
+        cash_available -= value;
+        plan.orders.push(RebalanceOrder {
+            amount: value,
+            value_usd: value,
+            slippage_pct: slippage_pct(value, pool_base),
+            capped: value < diff,
+            ..order
+        });
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coin(symbol: &str, quantity: f64, price: f64, pool_base: f64) -> RebalanceCoin {
+        RebalanceCoin { symbol: symbol.to_string(), quantity, price, pool_base }
+    }
+
+    fn targets(pairs: &[(&str, f64)]) -> HashMap<String, f64> {
+        pairs.iter().map(|(s, p)| (s.to_string(), *p)).collect()
+    }
+
+    #[test]
+    fn test_diff_to_orders() {
+        // $1000 total: AAA $500 (50%), BBB $100 (10%), OLD $100, $300 cash
+        let coins = [
+            coin("AAA", 100.0, 5.0, 100_000.0),
+            coin("BBB", 10.0, 10.0, 100_000.0),
+            coin("OLD", 50.0, 2.0, 100_000.0),
+            coin("NEW", 0.0, 1.0, 100_000.0),
+            coin("HELD", 20.0, 5.0, 100_000.0),
+        ];
+        let goal = targets(&[("AAA", 30.0), ("BBB", 30.0), ("OLD", 0.0), ("NEW", 15.0), ("HELD", 10.0)]);
+        let options = RebalanceOptions { min_trade_usd: 5.0, max_slippage_pct: 0.0 };
+        let plan = plan_rebalance(200.0, &coins, &goal, &options);
+        assert_eq!(plan.total_value, 1000.0);
+
+        let summary: Vec<(&str, TradeType, f64)> =
+            plan.orders.iter().map(|o| (o.symbol.as_str(), o.trade_type, o.amount)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("AAA", TradeType::Sell, 40.0),
+                ("OLD", TradeType::Sell, 50.0),
+                ("BBB", TradeType::Buy, 200.0),
+                ("NEW", TradeType::Buy, 150.0),
+            ]
+        );
+        // HELD is already at 10%
+        assert_eq!(plan.skipped, [RebalanceSkip { symbol: "HELD".into(), reason: "Within $5.00 of target".into() }]);
+        assert_eq!(plan.orders[0].current_pct, 50.0);
+
+        // Only the cash on hand plus sell proceeds fund buys; the biggest
+        // shortfall is topped up first
+        let coins = [coin("AAA", 100.0, 1.0, 1_000.0), coin("BIG", 0.0, 1.0, 1e6), coin("SMALL", 0.0, 1.0, 1e6)];
+        let goal = targets(&[("AAA", 0.0), ("BIG", 70.0), ("SMALL", 30.0)]);
+        let plan = plan_rebalance(0.0, &coins, &goal, &RebalanceOptions::default());
+        let summary: Vec<(&str, f64, bool)> = plan.orders.iter().map(|o| (o.symbol.as_str(), o.amount, o.capped)).collect();
+        // The AAA exit is capped at $50 of slippage, so BIG gets only that
+        assert_eq!(summary, [("AAA", 50.0, true), ("BIG", 50.0, true)]);
+        assert_eq!(plan.skipped, [RebalanceSkip { symbol: "SMALL".into(), reason: "Not enough cash to top up".into() }]);
+    }
+
+    #[test]
+    fn test_slippage_cap_shrinks_orders_and_validates_targets() {
+        // A $2000 pool allows $100 per order at a 5% cap
+        let coins = [coin("THIN", 0.0, 1.0, 2_000.0), coin("DEEP", 1_000.0, 1.0, 0.0)];
+        let plan = plan_rebalance(1_000.0, &coins, &targets(&[("THIN", 50.0)]), &RebalanceOptions::default());
+        assert_eq!(plan.orders.len(), 1);
+        assert_eq!(plan.orders[0].amount, 100.0);
+        assert_eq!(plan.orders[0].slippage_pct, 5.0);
+        assert!(plan.orders[0].capped);
+
+        // Without pool data the cap can't be honored
+        let plan = plan_rebalance(1_000.0, &coins, &targets(&[("DEEP", 0.0)]), &RebalanceOptions::default());
+        assert!(plan.orders.is_empty());
+        assert_eq!(plan.skipped[0].symbol, "DEEP");
+
+        assert!(validate_targets(&targets(&[("A", 60.0), ("B", 40.0)])).is_ok());
+        assert!(validate_targets(&targets(&[("A", 60.0), ("B", 41.0)])).is_err());
+        assert!(validate_targets(&targets(&[("A", -1.0)])).is_err());
+        assert!(validate_targets(&targets(&[("A", f64::NAN)])).is_err());
+        assert!(validate_targets(&HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+//! History Retention — periodic pruning of old log rows
+//!
+//! Runs `prune_history` from the persistence layer on a fixed interval using
+//! the configured retention window. Automation log totals are rolled up
+//! before rows are deleted, so lifetime module stats are preserved.
+
+use crate::AppState;
+use rugplay_persistence::sqlite::{self, PruneReport};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tracing::{debug, info, warn};
+
+/// How often the pruning task runs (6 hours)
+const PRUNE_INTERVAL_SECS: u64 = 6 * 3600;
+
+/// Delay before the first pass so startup isn't slowed down
+const STARTUP_DELAY_SECS: u64 = 120;
+
+/// Shortest retention window the user can configure
+pub const MIN_RETENTION_DAYS: u32 = 1;
+
+/// Retention policy (settings key `retention_config`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionConfig {
+    /// Prune automatically in the background; off until the user opts in,
+    /// since pruning deletes history for good
+    pub enabled: bool,
+    /// Rows older than this many days are pruned
+    pub retention_days: u32,
+    /// When the last prune pass ran (RFC 3339)
+    #[serde(default)]
+    pub last_pruned_at: Option<String>,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            retention_days: 90,
+            last_pruned_at: None,
+        }
+    }
+}
+
+/// Load the retention policy from the settings table
+pub async fn load_retention_config(app_handle: &tauri::AppHandle) -> RetentionConfig {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else {
+        return RetentionConfig::default();
+    };
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'retention_config'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|j| serde_json::from_str(&j).ok())
+    .unwrap_or_default()
+}
+
+/// Persist the retention policy
+pub async fn save_retention_config(
+    app_handle: &tauri::AppHandle,
+    config: &RetentionConfig,
+) -> Result<(), String> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+
+    let json = serde_json::to_string(config).map_err(|e| e.to_string())?;
+    sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('retention_config', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(&json)
+    .execute(db.pool())
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Prune history older than `older_than_days` and record when it ran
+pub async fn run_prune(
+    app_handle: &tauri::AppHandle,
+    older_than_days: u32,
+) -> Result<PruneReport, String> {
+    let report = {
+        let state = app_handle.state::<AppState>();
+        let db_guard = state.db.read().await;
+        let db = db_guard.as_ref().ok_or("Database not initialized")?;
+        sqlite::prune_history(db.pool(), older_than_days)
+            .await
+            .map_err(|e| e.to_string())?
+    };
+
+    let mut config = load_retention_config(app_handle).await;
+    config.last_pruned_at = Some(chrono::Utc::now().to_rfc3339());
+    save_retention_config(app_handle, &config).await?;
+
+    if report.total() > 0 {
+        info!(
+            "Retention: pruned {} rows older than {} days ({:?})",
+            report.total(),
+            older_than_days,
+            report
+        );
+    } else {
+        debug!("Retention: nothing older than {} days", older_than_days);
+    }
+    Ok(report)
+}
+
+/// Spawn the background pruning task
+pub fn spawn_retention_task(app_handle: tauri::AppHandle) {
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(STARTUP_DELAY_SECS)).await;
+
+        loop {
+            let config = load_retention_config(&app_handle).await;
+            if config.enabled {
+                if let Err(e) = run_prune(&app_handle, config.retention_days.max(MIN_RETENTION_DAYS)).await {
+                    warn!("Retention: prune failed: {}", e);
+                }
+            }
+
+            tokio::time::sleep(std::time::Duration::from_secs(PRUNE_INTERVAL_SECS)).await;
+        }
+    });
+}
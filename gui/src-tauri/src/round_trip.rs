@@ -0,0 +1,93 @@
+//! Round Trip — can a position be exited as cheaply as it was entered
+//!
+//! A buy into a deep USD pool looks cheap, but the exit is what matters: if
+//! we already hold a big share of the coin's reserve, selling everything
+//! later moves the price far more than the buy did. Before an automated buy
+//! the executor estimates both legs on the constant-product pool and skips
+//! the buy when a full exit of the resulting position would cost more than
+//! the user's cap.
+
+/// A coin's AMM pool
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolReserves {
+    pub coins: f64,
+    pub usd: f64,
+}
+
+/// Both legs of a buy followed by a full exit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RoundTrip {
+    /// Execution price over spot for the buy, in percent
+    pub buy_impact_pct: f64,
+    pub coins_bought: f64,
+    /// Value lost against the post-buy spot price selling the whole
+    /// position (held coins plus this buy), in percent
+    pub exit_impact_pct: f64,
+    /// USD the full exit would return
+    pub exit_value_usd: f64,
+}
+
+/// Estimate a buy of `buy_usd` and a full exit of `held_coins` plus what it
+/// buys. `None` when the pool has no reserves to price against.
+pub fn evaluate_round_trip(pool: PoolReserves, buy_usd: f64, held_coins: f64) -> Option<RoundTrip> {
+    if !(pool.coins.is_finite() && pool.usd.is_finite()) || pool.coins <= 0.0 || pool.usd <= 0.0 {
+        return None;
+    }
+    let buy_usd = buy_usd.max(0.0);
+    let k = pool.coins * pool.usd;
+
+    let usd_after = pool.usd + buy_usd;
+    let coins_after = k / usd_after;
+    let coins_bought = pool.coins - coins_after;
+
+    let position = held_coins.max(0.0) + coins_bought;
+    let exit_value_usd = usd_after * position / (coins_after + position);
+    let exit_impact_pct = position / (coins_after + position) * 100.0;
+
+    Some(RoundTrip {
+        buy_impact_pct: buy_usd / pool.usd * 100.0,
+        coins_bought,
+        exit_impact_pct,
+        exit_value_usd,
+    })
+}
+
+/// Reject a buy whose full exit would cost more than `max_exit_impact_pct`
+pub fn check_round_trip(symbol: &str, round_trip: &RoundTrip, max_exit_impact_pct: f64) -> Result<(), String> {
+    if max_exit_impact_pct > 0.0 && round_trip.exit_impact_pct > max_exit_impact_pct {
+        return Err(format!(
+            "Exit check: {} buy costs {:.1}% impact but a full exit would cost {:.1}% (max {:.1}%)",
+            symbol, round_trip.buy_impact_pct, round_trip.exit_impact_pct, max_exit_impact_pct
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_way_liquid_coin_is_rejected() {
+        // Deep $100k pool, 1M coins at $0.10: a $100 buy barely moves it
+        let pool = PoolReserves { coins: 1_000_000.0, usd: 100_000.0 };
+        let fresh = evaluate_round_trip(pool, 100.0, 0.0).unwrap();
+        assert!((fresh.buy_impact_pct - 0.1).abs() < 1e-9);
+        // A fresh position exits about as cheaply as it came in
+        assert!(fresh.exit_impact_pct < 0.11);
+        assert!((fresh.exit_value_usd - 100.0).abs() < 0.01);
+        assert!(check_round_trip("DEEP", &fresh, 5.0).is_ok());
+
+        // Holding 40% of the coin reserve already: the buy is just as cheap,
+        // the exit is not
+        let trapped = evaluate_round_trip(pool, 100.0, 400_000.0).unwrap();
+        assert_eq!(trapped.buy_impact_pct, fresh.buy_impact_pct);
+        assert!(trapped.exit_impact_pct > 25.0);
+        let err = check_round_trip("TRAP", &trapped, 5.0).unwrap_err();
+        assert!(err.starts_with("Exit check: TRAP buy costs 0.1% impact but a full exit would cost 28."), "{}", err);
+
+        // Disabled cap and unpriced pools
+        assert!(check_round_trip("TRAP", &trapped, 0.0).is_ok());
+        assert_eq!(evaluate_round_trip(PoolReserves { coins: 0.0, usd: 1.0 }, 10.0, 0.0), None);
+    }
+}
@@ -0,0 +1,106 @@
+//! Seen Trades — bounded dedup of recently processed trade keys
+//!
+//! The mirror and dip buyer poll the recent trades feed and must never act on
+//! the same trade twice. Each key remembers when it was last seen. Once the
+//! set grows past its capacity, keys older than the window are dropped, then
+//! the oldest remaining ones, so recent keys always survive eviction.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SeenTrades {
+    /// trade key -> epoch last seen
+    seen: HashMap<String, i64>,
+}
+
+impl SeenTrades {
+    pub fn contains(&self, key: &str) -> bool {
+        self.seen.contains_key(key)
+    }
+
+    /// Remember a key as seen at `now`
+    pub fn insert(&mut self, key: String, now: i64) {
+        self.seen.insert(key, now);
+    }
+
+    pub fn len(&self) -> usize {
+        self.seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seen.is_empty()
+    }
+
+    /// Trim the set once it holds more than `capacity` keys: drop keys older
+    /// than `window_secs`, then the oldest until `capacity` remain.
+    /// Returns how many keys were evicted.
+    pub fn evict(&mut self, now: i64, window_secs: i64, capacity: usize) -> usize {
+        let before = self.seen.len();
+        if before <= capacity {
+            return 0;
+        }
+
+        self.seen.retain(|_, seen_at| now - *seen_at < window_secs);
+
+        let excess = self.seen.len().saturating_sub(capacity);
+        if excess > 0 {
+            let mut by_age: Vec<(i64, String)> = self
+                .seen
+                .iter()
+                .map(|(key, seen_at)| (*seen_at, key.clone()))
+                .collect();
+            by_age.sort();
+            for (_, key) in by_age.into_iter().take(excess) {
+                self.seen.remove(&key);
+            }
+        }
+
+        before - self.seen.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_keys_evict_while_recent_survive_past_bound() {
+        let now = 1_700_000_000;
+        let mut seen = SeenTrades::default();
+        // 10 stale keys from two hours ago, 10 recent ones a second apart
+        for i in 0..10 {
+            seen.insert(format!("old{}", i), now - 7200);
+            seen.insert(format!("new{}", i), now - 10 + i);
+        }
+
+        // Under the bound nothing is touched, however old
+        assert_eq!(seen.evict(now, 3600, 20), 0);
+        assert!(seen.contains("old0"));
+
+        // Over the bound the stale keys go first
+        assert_eq!(seen.evict(now, 3600, 15), 10);
+        assert!((0..10).all(|i| seen.contains(&format!("new{}", i))));
+        assert!(!seen.contains("old0"));
+
+        // All recent: only the oldest beyond capacity are dropped
+        assert_eq!(seen.evict(now, 3600, 6), 4);
+        assert_eq!(seen.len(), 6);
+        assert!(!seen.contains("new3"));
+        assert!((4..10).all(|i| seen.contains(&format!("new{}", i))));
+    }
+
+    #[test]
+    fn test_reinserting_a_key_refreshes_it() {
+        let now = 1_700_000_000;
+        let mut seen = SeenTrades::default();
+        seen.insert("a".into(), now - 100);
+        seen.insert("b".into(), now - 50);
+        seen.insert("a".into(), now);
+
+        assert_eq!(seen.evict(now, 3600, 1), 1);
+        assert!(seen.contains("a"));
+        assert!(!seen.contains("b"));
+    }
+}
@@ -1,5 +1,7 @@
-use rugplay_persistence::sqlite::SentinelRow;
-use serde::Serialize;
+use rugplay_core::is_valid_price;
+use rugplay_persistence::sqlite::{SentinelRow, TpRung};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -7,6 +9,7 @@ pub enum TriggerType {
     StopLoss,
     TakeProfit,
     TrailingStop,
+    LiquidityDrain,
 }
 
 impl TriggerType {
@@ -15,6 +18,7 @@ impl TriggerType {
             TriggerType::StopLoss => "stop_loss",
             TriggerType::TakeProfit => "take_profit",
             TriggerType::TrailingStop => "trailing_stop",
+            TriggerType::LiquidityDrain => "liquidity_drain",
         }
     }
 }
@@ -25,11 +29,116 @@ impl std::fmt::Display for TriggerType {
     }
 }
 
+/// Percentage of holdings to sell for `trigger_type`.
+///
+/// Stop-loss, take-profit and trailing stop can each override the
+/// sentinel's `sell_percentage`; a liquidity drain always uses it.
+pub fn sell_percentage_for(sentinel: &SentinelRow, trigger_type: &TriggerType) -> f64 {
+    let specific = match trigger_type {
+        TriggerType::StopLoss => sentinel.sl_sell_pct,
+        TriggerType::TakeProfit => sentinel.tp_sell_pct,
+        TriggerType::TrailingStop => sentinel.ts_sell_pct,
+        TriggerType::LiquidityDrain => None,
+    };
+    specific
+        .filter(|pct| pct.is_finite() && *pct > 0.0)
+        .unwrap_or(sentinel.sell_percentage)
+}
+
+/// The sentinel's take-profit ladder. A sentinel without one is a
+/// one-rung ladder: `take_profit_pct`, selling its take-profit percentage.
+pub fn take_profit_ladder(sentinel: &SentinelRow) -> Vec<TpRung> {
+    sentinel.tp_rungs().unwrap_or_else(|| {
+        sentinel
+            .take_profit_pct
+            .map(|gain_pct| TpRung { gain_pct, sell_pct: sell_percentage_for(sentinel, &TriggerType::TakeProfit) })
+            .into_iter()
+            .collect()
+    })
+}
+
+/// Check a take-profit ladder: gains rising and above zero, each rung
+/// selling a share of the position, together no more than all of it.
+pub fn validate_tp_ladder(ladder: &[TpRung]) -> Result<(), String> {
+    if ladder.is_empty() {
+        return Err("A take-profit ladder needs at least one rung".to_string());
+    }
+    let mut previous_gain = 0.0;
+    for (i, rung) in ladder.iter().enumerate() {
+        if !rung.gain_pct.is_finite() || rung.gain_pct <= previous_gain {
+            return Err(format!("Rung {} gain must be above {}%, got {}", i + 1, previous_gain, rung.gain_pct));
+        }
+        if !rung.sell_pct.is_finite() || rung.sell_pct <= 0.0 || rung.sell_pct > 100.0 {
+            return Err(format!("Rung {} sell must be between 0 and 100%, got {}", i + 1, rung.sell_pct));
+        }
+        previous_gain = rung.gain_pct;
+    }
+    let total: f64 = ladder.iter().map(|r| r.sell_pct).sum();
+    if total > 100.0 + 1e-9 {
+        return Err(format!("Ladder rungs sell {}% in total, more than the whole position", total));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct TriggerResult {
     pub trigger_type: TriggerType,
     pub reason: String,
     pub trigger_price: f64,
+    /// Index of the take-profit rung that fired
+    pub tp_rung: Option<usize>,
+}
+
+/// Percentage of the current holding a trigger sells. Rungs of a stored
+/// ladder are shares of the position the ladder started with, so each one
+/// sells that share of what the earlier rungs left; a ladder totalling
+/// under 100% leaves the rest held. Everything else goes by
+/// [`sell_percentage_for`].
+pub fn trigger_sell_percentage(sentinel: &SentinelRow, trigger: &TriggerResult) -> f64 {
+    let (Some(rung), Some(ladder)) = (trigger.tp_rung, sentinel.tp_rungs()) else {
+        return sell_percentage_for(sentinel, &trigger.trigger_type);
+    };
+    let left: f64 = 100.0 - ladder[..rung].iter().map(|r| r.sell_pct).sum::<f64>();
+    if left <= 0.0 {
+        return 100.0;
+    }
+    (ladder[rung].sell_pct / left * 100.0).min(100.0)
+}
+
+/// Coins a trigger sells out of `holding_qty`. A trigger whose last sell
+/// only partly went out sells what it still owes, not its share of a
+/// holding that has since shrunk.
+pub fn trigger_sell_quantity(sentinel: &SentinelRow, trigger: &TriggerResult, sell_pct: f64, holding_qty: f64) -> f64 {
+    match sentinel.unsold_for(trigger.trigger_type.as_str()) {
+        Some(owed) => owed.min(holding_qty),
+        None => holding_qty * (sell_pct / 100.0),
+    }
+}
+
+/// What a confirmed, uncapped sell leaves the sentinel as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AfterSell {
+    /// Nothing left to guard
+    Triggered,
+    /// A ladder rung sold; stay armed at the same entry for the next rung
+    /// (or, after the last, for the stop-loss and trailing stop)
+    NextRung,
+    /// A partial sell; re-arm at the current price for the remainder
+    Rearm,
+}
+
+pub fn after_sell(sentinel: &SentinelRow, trigger: &TriggerResult, sell_pct: f64) -> AfterSell {
+    if let (Some(rung), Some(ladder)) = (trigger.tp_rung, sentinel.tp_rungs()) {
+        // The last rung of a ladder that sells less than everything leaves
+        // the rest under the stop-loss and trailing stop
+        let sells_all = ladder.iter().map(|r| r.sell_pct).sum::<f64>() >= 100.0 - 1e-9;
+        return if rung + 1 >= ladder.len() && sells_all { AfterSell::Triggered } else { AfterSell::NextRung };
+    }
+    if sell_pct >= 100.0 {
+        AfterSell::Triggered
+    } else {
+        AfterSell::Rearm
+    }
 }
 
 /// Evaluate whether a sentinel should trigger based on the current price.
@@ -44,6 +153,12 @@ pub struct TriggerResult {
 pub fn evaluate_sentinel(sentinel: &SentinelRow, current_price: f64) -> Option<TriggerResult> {
     let entry_price = sentinel.entry_price;
 
+    // NaN compares false and a zero entry collapses every threshold to 0,
+    // so bad prices are never evaluated
+    if !is_valid_price(current_price) || !is_valid_price(entry_price) {
+        return None;
+    }
+
     // Stop loss
     if let Some(sl_pct) = sentinel.stop_loss_pct {
         if sl_pct < 0.0 {
@@ -58,6 +173,7 @@ pub fn evaluate_sentinel(sentinel: &SentinelRow, current_price: f64) -> Option<T
                         current_price, sl_pct, sl_price
                     ),
                     trigger_price: sl_price,
+                    tp_rung: None,
                 });
             }
         } else if sl_pct > 0.0 {
@@ -74,23 +190,36 @@ pub fn evaluate_sentinel(sentinel: &SentinelRow, current_price: f64) -> Option<T
                         current_price, sl_pct, sl_price, highest
                     ),
                     trigger_price: sl_price,
+                    tp_rung: None,
                 });
             }
         }
         // sl_pct == 0.0 means disabled, skip
     }
 
-    // Take profit
-    if let Some(tp_pct) = sentinel.take_profit_pct {
+    // Take profit: the next unfilled rung of the ladder
+    let ladder = take_profit_ladder(sentinel);
+    let filled = usize::try_from(sentinel.tp_rungs_filled).unwrap_or(0);
+    if let Some(rung) = ladder.get(filled) {
+        let tp_pct = rung.gain_pct;
         let tp_price = entry_price * (1.0 + tp_pct / 100.0);
         if current_price >= tp_price {
-            return Some(TriggerResult {
-                trigger_type: TriggerType::TakeProfit,
-                reason: format!(
+            let reason = if ladder.len() > 1 {
+                format!(
+                    "Take profit rung {}/{} triggered at {} (TP={:.1}%, target={})",
+                    filled + 1, ladder.len(), current_price, tp_pct, tp_price
+                )
+            } else {
+                format!(
                     "Take profit triggered at {} (TP={:.1}%, target={})",
                     current_price, tp_pct, tp_price
-                ),
+                )
+            };
+            return Some(TriggerResult {
+                trigger_type: TriggerType::TakeProfit,
+                reason,
                 trigger_price: tp_price,
+                tp_rung: Some(filled),
             });
         }
     }
@@ -108,6 +237,7 @@ pub fn evaluate_sentinel(sentinel: &SentinelRow, current_price: f64) -> Option<T
                         current_price, ts_pct, highest, ts_price
                     ),
                     trigger_price: ts_price,
+                    tp_rung: None,
                 });
             }
         }
@@ -115,3 +245,798 @@ pub fn evaluate_sentinel(sentinel: &SentinelRow, current_price: f64) -> Option<T
 
     None
 }
+
+/// Which price sentinel thresholds are compared against
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceSource {
+    /// `current_price` from the portfolio (last trade)
+    #[default]
+    LastTrade,
+    /// AMM spot price from pool reserves, closer to what a sell would fetch
+    Mark,
+}
+
+impl PriceSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceSource::LastTrade => "last_trade",
+            PriceSource::Mark => "mark",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "last_trade" => Some(PriceSource::LastTrade),
+            "mark" => Some(PriceSource::Mark),
+            _ => None,
+        }
+    }
+
+    /// The sentinel's own source if it has one, otherwise the global default
+    pub fn for_sentinel(sentinel: &SentinelRow, global: PriceSource) -> Self {
+        sentinel
+            .price_source
+            .as_deref()
+            .and_then(Self::parse)
+            .unwrap_or(global)
+    }
+}
+
+/// Spot price implied by constant-product pool reserves (base / coins)
+pub fn amm_spot_price(pool_coin_amount: f64, pool_base_currency_amount: f64) -> Option<f64> {
+    let price = pool_base_currency_amount / pool_coin_amount;
+    (is_valid_price(price) && pool_coin_amount > 0.0).then_some(price)
+}
+
+/// Exit when a held coin's pool has drained below `min_liquidity_usd` of
+/// base currency. Once the pool is that thin a stop-loss can't get a fair
+/// fill anyway, so the position is sold while there is still something to
+/// sell into. A threshold of 0 disables the rule, and an unknown pool size
+/// never triggers.
+pub fn evaluate_liquidity(
+    pool_liquidity_usd: Option<f64>,
+    min_liquidity_usd: f64,
+    current_price: f64,
+) -> Option<TriggerResult> {
+    let liquidity = pool_liquidity_usd?;
+    if !min_liquidity_usd.is_finite()
+        || min_liquidity_usd <= 0.0
+        || !liquidity.is_finite()
+        || !is_valid_price(current_price)
+    {
+        return None;
+    }
+    (liquidity < min_liquidity_usd).then(|| TriggerResult {
+        trigger_type: TriggerType::LiquidityDrain,
+        reason: format!(
+            "Liquidity drain triggered at {} (pool=${:.2}, min=${:.2})",
+            current_price, liquidity, min_liquidity_usd
+        ),
+        trigger_price: current_price,
+        tp_rung: None,
+    })
+}
+
+/// Prices available to one sentinel tick
+#[derive(Debug, Clone, Default)]
+pub struct SentinelPrices {
+    /// Global default source; sentinels may override it
+    pub source: PriceSource,
+    /// Mark price per symbol, for symbols with a sentinel on `Mark`
+    pub marks: HashMap<String, f64>,
+    /// Minimum pool liquidity to keep holding (0 = rule off)
+    pub min_liquidity_usd: f64,
+    /// Pool base-currency reserves per symbol, from this tick's coin fetches
+    pub liquidity: HashMap<String, f64>,
+    /// How take-profits react when spot has dumped below the target
+    pub tp_gap: TpGapPolicy,
+}
+
+impl SentinelPrices {
+    /// Price to evaluate this sentinel against. Falls back to the last trade
+    /// when no mark price could be fetched, so protection never goes blind.
+    pub fn price_for(&self, sentinel: &SentinelRow, last_trade: f64) -> f64 {
+        match PriceSource::for_sentinel(sentinel, self.source) {
+            PriceSource::LastTrade => last_trade,
+            PriceSource::Mark => self.marks.get(&sentinel.symbol).copied().unwrap_or(last_trade),
+        }
+    }
+
+    /// Threshold triggers first, then the liquidity exit
+    pub fn evaluate(&self, sentinel: &SentinelRow, current_price: f64) -> Option<TriggerResult> {
+        evaluate_sentinel(sentinel, current_price).or_else(|| {
+            evaluate_liquidity(
+                self.liquidity.get(&sentinel.symbol).copied(),
+                self.min_liquidity_usd,
+                current_price,
+            )
+        })
+    }
+
+    /// Take-profit gap check for a sentinel's trigger, from this tick's pool data
+    pub fn tp_gap(&self, sentinel: &SentinelRow, trigger: &TriggerResult) -> TpGapDecision {
+        let spot = self.marks.get(&sentinel.symbol).copied();
+        let pool_coins = spot.zip(self.liquidity.get(&sentinel.symbol).copied()).map(|(spot, base)| base / spot);
+        tp_gap_decision(&self.tp_gap, trigger, spot, pool_coins)
+    }
+}
+
+/// What to do when a take-profit fires but the pool's spot price has already
+/// dumped well below the target, so selling would realise far less profit
+/// than the trigger promised.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TpGapAction {
+    /// Sell as usual, whatever the fill
+    #[default]
+    Proceed,
+    /// Sell only as much as keeps the average fill within the slippage cap
+    CappedMarket,
+    /// Skip the sell and keep the sentinel armed
+    AbortRearm,
+}
+
+/// Take-profit gap handling (settings key `sentinel_tp_gap`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TpGapPolicy {
+    pub action: TpGapAction,
+    /// How far (%) spot must sit below the take-profit target to count as a gap
+    pub gap_pct: f64,
+    /// Largest average-fill discount to spot (%) a capped sell accepts
+    pub max_slippage_pct: f64,
+}
+
+impl Default for TpGapPolicy {
+    fn default() -> Self {
+        Self { action: TpGapAction::Proceed, gap_pct: 5.0, max_slippage_pct: 3.0 }
+    }
+}
+
+impl TpGapPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.gap_pct.is_finite() || self.gap_pct <= 0.0 || self.gap_pct >= 100.0 {
+            return Err("Take-profit gap must be between 0 and 100%".to_string());
+        }
+        if !self.max_slippage_pct.is_finite() || self.max_slippage_pct <= 0.0 || self.max_slippage_pct >= 100.0 {
+            return Err("Max slippage must be between 0 and 100%".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether the policy needs spot prices for take-profit sentinels
+    pub fn needs_spot(&self) -> bool {
+        self.action != TpGapAction::Proceed
+    }
+}
+
+/// Outcome of the take-profit gap check for one trigger
+#[derive(Debug, Clone, PartialEq)]
+pub enum TpGapDecision {
+    /// No gap (or the policy proceeds): sell as planned
+    Sell,
+    /// Sell at most `max_quantity` coins so the fill stays within the cap
+    Capped { max_quantity: f64, spot: f64 },
+    /// Don't sell this tick; the sentinel stays armed
+    Abort { spot: f64 },
+}
+
+/// Check a trigger against the take-profit gap policy. Only take-profits are
+/// affected, and an unknown spot or pool never holds a sell back.
+///
+/// Selling `q` coins into a constant-product pool holding `x` coins fills at
+/// an average of `spot * x / (x + q)`, so keeping the discount within `c`
+/// allows at most `x * c / (1 - c)` coins.
+pub fn tp_gap_decision(
+    policy: &TpGapPolicy,
+    trigger: &TriggerResult,
+    spot: Option<f64>,
+    pool_coins: Option<f64>,
+) -> TpGapDecision {
+    if !policy.needs_spot() || !matches!(trigger.trigger_type, TriggerType::TakeProfit) {
+        return TpGapDecision::Sell;
+    }
+    let Some(spot) = spot.filter(|p| is_valid_price(*p)) else {
+        return TpGapDecision::Sell;
+    };
+    if spot >= trigger.trigger_price * (1.0 - policy.gap_pct / 100.0) {
+        return TpGapDecision::Sell;
+    }
+
+    match policy.action {
+        TpGapAction::Proceed => TpGapDecision::Sell,
+        TpGapAction::AbortRearm => TpGapDecision::Abort { spot },
+        TpGapAction::CappedMarket => {
+            let Some(pool_coins) = pool_coins.filter(|x| x.is_finite() && *x > 0.0) else {
+                return TpGapDecision::Sell;
+            };
+            let cap = policy.max_slippage_pct / 100.0;
+            let max_quantity = pool_coins * cap / (1.0 - cap);
+            if max_quantity > 0.0 {
+                TpGapDecision::Capped { max_quantity, spot }
+            } else {
+                TpGapDecision::Abort { spot }
+            }
+        }
+    }
+}
+
+/// Burst shaping for protective sells.
+///
+/// During a broad crash many sentinels trigger in the same tick. The limiter
+/// admits at most `cap` sells per tick (0 = unlimited) and remembers the rest,
+/// so on the next tick the deferred sentinels are checked first and exits
+/// drain in trigger order instead of all at once.
+#[derive(Debug, Default)]
+pub struct SellBurstLimiter {
+    cap: usize,
+    admitted: usize,
+    /// Sentinels deferred on the previous tick, in trigger order
+    carried: Vec<i64>,
+    /// Sentinels deferred on the current tick
+    deferred: Vec<i64>,
+}
+
+impl SellBurstLimiter {
+    pub fn new(cap: usize) -> Self {
+        Self { cap, ..Self::default() }
+    }
+
+    pub fn set_cap(&mut self, cap: usize) {
+        self.cap = cap;
+    }
+
+    /// Start a new tick: reset the admitted count and carry over deferrals
+    pub fn begin_tick(&mut self) {
+        self.admitted = 0;
+        self.carried = std::mem::take(&mut self.deferred);
+    }
+
+    /// Sort candidates so sentinels deferred last tick come first (stable)
+    pub fn prioritize<T>(&self, items: &mut [T], id_of: impl Fn(&T) -> i64) {
+        if self.carried.is_empty() {
+            return;
+        }
+        items.sort_by_key(|item| {
+            let id = id_of(item);
+            self.carried.iter().position(|&c| c == id).unwrap_or(usize::MAX)
+        });
+    }
+
+    /// Try to claim a sell slot for this tick; defers the sentinel if full
+    pub fn try_admit(&mut self, sentinel_id: i64) -> bool {
+        if self.cap == 0 || self.admitted < self.cap {
+            self.admitted += 1;
+            true
+        } else {
+            if !self.deferred.contains(&sentinel_id) {
+                self.deferred.push(sentinel_id);
+            }
+            false
+        }
+    }
+
+    /// Number of sells waiting for a later tick
+    pub fn deferred_count(&self) -> usize {
+        self.deferred.len()
+    }
+}
+
+/// Shortest first retry delay. Retries run on the monitor's tick (10s by
+/// default), so anything shorter would only wait for the next tick anyway.
+pub const MIN_RETRY_DELAY_SECS: i64 = 10;
+
+/// Backoff schedule for retrying a sentinel's failed sell.
+///
+/// The first retry waits `base_secs`, each further one twice as long up to
+/// `max_secs`. A retry runs on the first monitor tick after its delay is up,
+/// so with a longer monitor interval it waits for that tick. After
+/// `max_failures` consecutive failures the sentinel is deactivated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SellRetryPolicy {
+    pub base_secs: i64,
+    pub max_secs: i64,
+    pub max_failures: u32,
+}
+
+impl Default for SellRetryPolicy {
+    fn default() -> Self {
+        Self { base_secs: MIN_RETRY_DELAY_SECS, max_secs: 60, max_failures: 3 }
+    }
+}
+
+impl SellRetryPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.base_secs < MIN_RETRY_DELAY_SECS {
+            return Err(format!("Retry delay must be at least {} seconds", MIN_RETRY_DELAY_SECS));
+        }
+        if self.max_secs < self.base_secs || self.max_secs > 3600 {
+            return Err("Max retry delay must be between the base delay and 3600 seconds".to_string());
+        }
+        if !(1..=10).contains(&self.max_failures) {
+            return Err("Max sell failures must be between 1 and 10".to_string());
+        }
+        Ok(())
+    }
+
+    /// Delay before retrying after the `failures`-th consecutive failure
+    pub fn delay_secs(&self, failures: u32) -> i64 {
+        let doublings = failures.saturating_sub(1).min(32);
+        self.base_secs.saturating_mul(1_i64 << doublings).min(self.max_secs)
+    }
+}
+
+/// Consecutive sell failures and pending retries, per sentinel
+#[derive(Debug, Default)]
+pub struct SellRetries {
+    pub policy: SellRetryPolicy,
+    /// sentinel id -> consecutive sell failures
+    failures: HashMap<i64, u32>,
+    /// sentinel id -> epoch before which the sell isn't retried
+    retry_at: HashMap<i64, i64>,
+}
+
+impl SellRetries {
+    pub fn restore(failures: HashMap<i64, u32>, retry_at: HashMap<i64, i64>) -> Self {
+        Self { failures, retry_at, ..Self::default() }
+    }
+
+    /// The persisted parts: (failures, retry_at)
+    pub fn into_parts(self) -> (HashMap<i64, u32>, HashMap<i64, i64>) {
+        (self.failures, self.retry_at)
+    }
+
+    pub fn failures(&self, sentinel_id: i64) -> u32 {
+        self.failures.get(&sentinel_id).copied().unwrap_or(0)
+    }
+
+    /// Whether the sentinel has used up its retries
+    pub fn is_exhausted(&self, sentinel_id: i64) -> bool {
+        self.failures(sentinel_id) >= self.policy.max_failures
+    }
+
+    /// Seconds left before the sentinel may retry, if it is backing off
+    pub fn backoff_remaining(&self, sentinel_id: i64, now: i64) -> Option<i64> {
+        self.retry_at.get(&sentinel_id).map(|&at| at - now).filter(|&left| left > 0)
+    }
+
+    pub fn record_success(&mut self, sentinel_id: i64) {
+        self.failures.remove(&sentinel_id);
+        self.retry_at.remove(&sentinel_id);
+    }
+
+    /// Count a failed sell and schedule its retry. Returns the failure count.
+    pub fn record_failure(&mut self, sentinel_id: i64, now: i64) -> u32 {
+        let count = self.failures.entry(sentinel_id).or_insert(0);
+        *count += 1;
+        let count = *count;
+        self.retry_at.insert(sentinel_id, now + self.policy.delay_secs(count));
+        count
+    }
+}
+
+/// One trigger fired while replaying a price path
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedTrigger {
+    /// Index into the path
+    pub step: usize,
+    pub price: f64,
+    pub trigger_type: TriggerType,
+    pub trigger_price: f64,
+    pub reason: String,
+    /// Entry price the trigger was measured from
+    pub entry_price: f64,
+    pub sell_pct: f64,
+    /// Share of the original holding left after this sell, in percent
+    pub remaining_pct: f64,
+}
+
+/// Replay `path` through a copy of `sentinel` and list the triggers it
+/// would fire, in order.
+///
+/// Each step tracks the highest price seen and evaluates like the monitor
+/// does. A partial sell re-arms the sentinel at that price and a ladder
+/// rung moves on to the next rung, as the monitor does after a confirmed
+/// sell; a full sell, or the last rung of a ladder selling everything,
+/// ends the run. Cooldowns, the creation grace period and the take-profit
+/// gap check are left out, so every step counts as a separate tick. Invalid prices are skipped.
+pub fn simulate_path(sentinel: &SentinelRow, path: &[f64]) -> Vec<SimulatedTrigger> {
+    let mut sim = sentinel.clone();
+    let mut remaining_pct = 100.0;
+    let mut triggers = Vec::new();
+
+    for (step, &price) in path.iter().enumerate() {
+        if !is_valid_price(price) {
+            continue;
+        }
+        let Some(trigger) = evaluate_sentinel(&sim, price) else {
+            sim.highest_price_seen = sim.highest_price_seen.max(price);
+            continue;
+        };
+
+        let sell_pct = trigger_sell_percentage(&sim, &trigger).min(100.0);
+        let next = after_sell(&sim, &trigger, sell_pct);
+        remaining_pct *= 1.0 - sell_pct / 100.0;
+        triggers.push(SimulatedTrigger {
+            step,
+            price,
+            trigger_type: trigger.trigger_type,
+            trigger_price: trigger.trigger_price,
+            reason: trigger.reason,
+            entry_price: sim.entry_price,
+            sell_pct,
+            remaining_pct,
+        });
+
+        match next {
+            AfterSell::Triggered => break,
+            AfterSell::NextRung => {
+                sim.tp_rungs_filled += 1;
+                sim.highest_price_seen = sim.highest_price_seen.max(price);
+            }
+            AfterSell::Rearm => {
+                sim.entry_price = price;
+                sim.highest_price_seen = price;
+            }
+        }
+    }
+    triggers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simultaneous_triggers_serialize_to_cap() {
+        let mut limiter = SellBurstLimiter::new(2);
+        let mut pending: Vec<i64> = vec![1, 2, 3, 4, 5];
+        let mut ticks: Vec<Vec<i64>> = Vec::new();
+
+        while !pending.is_empty() {
+            limiter.begin_tick();
+            limiter.prioritize(&mut pending, |&id| id);
+            let sold: Vec<i64> = pending.iter().copied().filter(|&id| limiter.try_admit(id)).collect();
+            assert!(sold.len() <= 2);
+            pending.retain(|id| !sold.contains(id));
+            assert_eq!(limiter.deferred_count(), pending.len());
+            ticks.push(sold);
+        }
+
+        assert_eq!(ticks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn test_deferred_sentinels_go_first_and_zero_cap_is_unlimited() {
+        let mut limiter = SellBurstLimiter::new(1);
+        limiter.begin_tick();
+        assert!(limiter.try_admit(10));
+        assert!(!limiter.try_admit(20));
+
+        // Next tick: a new trigger (5) sorts behind the deferred sentinel (20)
+        limiter.begin_tick();
+        let mut candidates = vec![5, 20];
+        limiter.prioritize(&mut candidates, |&id| id);
+        assert_eq!(candidates, vec![20, 5]);
+
+        let mut unlimited = SellBurstLimiter::new(0);
+        unlimited.begin_tick();
+        assert!((0..50).all(|id| unlimited.try_admit(id)));
+        assert_eq!(unlimited.deferred_count(), 0);
+    }
+
+    #[test]
+    fn test_sell_retry_backoff_schedule_and_failure_cap() {
+        let policy = SellRetryPolicy { base_secs: 5, max_secs: 60, max_failures: 4 };
+        let delays: Vec<i64> = (1..=6).map(|n| policy.delay_secs(n)).collect();
+        assert_eq!(delays, vec![5, 10, 20, 40, 60, 60]);
+        assert_eq!(policy.delay_secs(u32::MAX), 60);
+
+        let mut retries = SellRetries { policy, ..SellRetries::default() };
+        assert_eq!(retries.record_failure(1, 1_000), 1);
+        assert_eq!(retries.backoff_remaining(1, 1_002), Some(3));
+        assert_eq!(retries.backoff_remaining(1, 1_005), None);
+        // Another sentinel isn't held back by the failing one
+        assert_eq!(retries.backoff_remaining(2, 1_002), None);
+
+        assert_eq!(retries.record_failure(1, 1_005), 2);
+        assert_eq!(retries.backoff_remaining(1, 1_005), Some(10));
+        retries.record_failure(1, 1_015);
+        assert!(!retries.is_exhausted(1));
+        retries.record_failure(1, 1_035);
+        assert!(retries.is_exhausted(1));
+
+        retries.record_success(1);
+        assert_eq!((retries.failures(1), retries.backoff_remaining(1, 1_035)), (0, None));
+        assert!(SellRetryPolicy { base_secs: 30, max_secs: 10, max_failures: 3 }.validate().is_err());
+        assert!(SellRetryPolicy::default().validate().is_ok());
+        // Shorter than a monitor tick: the retry couldn't run on time
+        assert!(SellRetryPolicy { base_secs: 5, ..SellRetryPolicy::default() }.validate().is_err());
+    }
+
+    fn sentinel(entry_price: f64, highest: f64) -> SentinelRow {
+        SentinelRow {
+            id: 1,
+            profile_id: 1,
+            symbol: "TEST".into(),
+            stop_loss_pct: Some(-20.0),
+            take_profit_pct: Some(100.0),
+            trailing_stop_pct: Some(10.0),
+            sell_percentage: 100.0,
+            entry_price,
+            highest_price_seen: highest,
+            is_active: true,
+            created_at: None,
+            triggered_at: None,
+            price_source: None,
+            sl_sell_pct: None,
+            tp_sell_pct: None,
+            ts_sell_pct: None,
+            tp_ladder: None,
+            tp_rungs_filled: 0,
+            unsold_trigger: None,
+            unsold_qty: None,
+        }
+    }
+
+    #[test]
+    fn test_each_trigger_type_sells_its_configured_fraction() {
+        let split = SentinelRow {
+            sell_percentage: 80.0,
+            sl_sell_pct: Some(100.0),
+            tp_sell_pct: Some(50.0),
+            ts_sell_pct: Some(25.0),
+            ..sentinel(1.0, 1.0)
+        };
+
+        let stop = evaluate_sentinel(&split, 0.5).unwrap();
+        assert!(matches!(stop.trigger_type, TriggerType::StopLoss));
+        assert_eq!(sell_percentage_for(&split, &stop.trigger_type), 100.0);
+
+        let take = evaluate_sentinel(&split, 2.5).unwrap();
+        assert!(matches!(take.trigger_type, TriggerType::TakeProfit));
+        assert_eq!(sell_percentage_for(&split, &take.trigger_type), 50.0);
+
+        let trailing = SentinelRow { highest_price_seen: 1.5, ..split.clone() };
+        let trail = evaluate_sentinel(&trailing, 1.3).unwrap();
+        assert!(matches!(trail.trigger_type, TriggerType::TrailingStop));
+        assert_eq!(sell_percentage_for(&trailing, &trail.trigger_type), 25.0);
+
+        assert_eq!(sell_percentage_for(&split, &TriggerType::LiquidityDrain), 80.0);
+
+        // Unset (or nonsensical) overrides fall back to the single value
+        let single = SentinelRow { sell_percentage: 80.0, tp_sell_pct: Some(0.0), ..sentinel(1.0, 1.0) };
+        for trigger_type in [TriggerType::StopLoss, TriggerType::TakeProfit, TriggerType::TrailingStop] {
+            assert_eq!(sell_percentage_for(&single, &trigger_type), 80.0);
+        }
+    }
+
+    #[test]
+    fn test_a_partly_sold_trigger_sells_only_what_it_owes() {
+        let s = SentinelRow { sl_sell_pct: Some(50.0), ..sentinel(1.0, 1.0) };
+        let stop = evaluate_sentinel(&s, 0.5).unwrap();
+        assert_eq!(trigger_sell_quantity(&s, &stop, 50.0, 100.0), 50.0);
+
+        // Half of the 50 went out: the rest is 25 coins, not half of the 75 left
+        let owed = SentinelRow { unsold_trigger: Some("stop_loss".into()), unsold_qty: Some(25.0), ..s.clone() };
+        assert_eq!(trigger_sell_quantity(&owed, &stop, 50.0, 75.0), 25.0);
+        assert_eq!(trigger_sell_quantity(&owed, &stop, 50.0, 10.0), 10.0);
+
+        // What one trigger owes doesn't size another
+        let take = evaluate_sentinel(&s, 2.5).unwrap();
+        assert_eq!(trigger_sell_quantity(&owed, &take, 100.0, 75.0), 75.0);
+    }
+
+    #[test]
+    fn test_nan_and_zero_prices_never_trigger() {
+        let s = sentinel(1.0, 1.0);
+        assert!(evaluate_sentinel(&s, 0.5).is_some());
+        assert!(evaluate_sentinel(&s, f64::NAN).is_none());
+        assert!(evaluate_sentinel(&s, f64::INFINITY).is_none());
+        assert!(evaluate_sentinel(&s, 0.0).is_none());
+
+        // Zero entry would make take-profit fire at any price
+        assert!(evaluate_sentinel(&sentinel(0.0, 0.0), 1.0).is_none());
+        assert!(evaluate_sentinel(&sentinel(f64::NAN, 1.0), 1.0).is_none());
+
+        // A corrupted peak doesn't fire the trailing stop on its own
+        assert!(evaluate_sentinel(&sentinel(1.0, f64::NAN), 1.0).is_none());
+    }
+
+    #[test]
+    fn test_trigger_decisions_under_both_price_sources() {
+        // Thin pool: the last trade printed at 1.10 but the reserves only
+        // support 0.75, below the -20% stop (0.80)
+        let s = sentinel(1.0, 1.10);
+        let mark = amm_spot_price(1_000.0, 750.0).unwrap();
+        assert_eq!(mark, 0.75);
+
+        let mut prices = SentinelPrices { source: PriceSource::LastTrade, ..Default::default() };
+        prices.marks.insert("TEST".into(), mark);
+        assert!(evaluate_sentinel(&s, prices.price_for(&s, 1.10)).is_none());
+
+        prices.source = PriceSource::Mark;
+        let trigger = evaluate_sentinel(&s, prices.price_for(&s, 1.10)).unwrap();
+        assert!(matches!(trigger.trigger_type, TriggerType::StopLoss));
+
+        // Per-sentinel override beats the global default
+        let pinned = SentinelRow { price_source: Some("last_trade".into()), ..sentinel(1.0, 1.10) };
+        assert!(evaluate_sentinel(&pinned, prices.price_for(&pinned, 1.10)).is_none());
+
+        // The reverse: last trade dumped to 1.00 after a 1.20 peak while the
+        // pool still marks 1.15, so only last-trade fires the trailing stop
+        let s = sentinel(1.0, 1.20);
+        prices.marks.insert("TEST".into(), 1.15);
+        assert!(evaluate_sentinel(&s, prices.price_for(&s, 1.00)).is_none());
+        prices.source = PriceSource::LastTrade;
+        let trigger = evaluate_sentinel(&s, prices.price_for(&s, 1.00)).unwrap();
+        assert!(matches!(trigger.trigger_type, TriggerType::TrailingStop));
+    }
+
+    #[test]
+    fn test_mark_falls_back_to_last_trade_without_reserves() {
+        assert_eq!(amm_spot_price(0.0, 100.0), None);
+        assert_eq!(amm_spot_price(100.0, 0.0), None);
+        assert_eq!(amm_spot_price(f64::NAN, 1.0), None);
+
+        let s = SentinelRow { price_source: Some("mark".into()), ..sentinel(1.0, 1.0) };
+        let prices = SentinelPrices::default();
+        assert_eq!(prices.price_for(&s, 0.9), 0.9);
+
+        // Unknown stored values are ignored in favour of the global source
+        let s = SentinelRow { price_source: Some("bogus".into()), ..sentinel(1.0, 1.0) };
+        assert_eq!(PriceSource::for_sentinel(&s, PriceSource::Mark), PriceSource::Mark);
+    }
+
+    #[test]
+    fn test_take_profit_behaviors_when_spot_is_below_target() {
+        // TP=+100% from 1.0: target 2.0. The last trade printed 2.1 but the
+        // pool (10_000 coins, 15_000 base) only marks 1.5, a 25% gap
+        let s = sentinel(1.0, 2.1);
+        let take = evaluate_sentinel(&s, 2.1).unwrap();
+        assert_eq!(take.trigger_price, 2.0);
+        let mut prices = SentinelPrices::default();
+        prices.marks.insert("TEST".into(), 1.5);
+        prices.liquidity.insert("TEST".into(), 15_000.0);
+
+        assert_eq!(prices.tp_gap(&s, &take), TpGapDecision::Sell);
+
+        prices.tp_gap = TpGapPolicy { action: TpGapAction::AbortRearm, ..TpGapPolicy::default() };
+        assert_eq!(prices.tp_gap(&s, &take), TpGapDecision::Abort { spot: 1.5 });
+
+        prices.tp_gap = TpGapPolicy { action: TpGapAction::CappedMarket, max_slippage_pct: 20.0, ..TpGapPolicy::default() };
+        let TpGapDecision::Capped { max_quantity, spot } = prices.tp_gap(&s, &take) else { panic!("expected a capped sell") };
+        assert_eq!(spot, 1.5);
+        assert!((max_quantity - 2_500.0).abs() < 1e-9, "{}", max_quantity);
+        // Selling exactly the cap averages 20% under spot
+        let avg_fill = 15_000.0 / (10_000.0 + max_quantity);
+        assert!((avg_fill / spot - 0.8).abs() < 1e-9);
+
+        // A gap inside the threshold, other triggers and unknown pools all sell
+        prices.marks.insert("TEST".into(), 1.95);
+        assert_eq!(prices.tp_gap(&s, &take), TpGapDecision::Sell);
+        let stop = evaluate_sentinel(&s, 0.5).unwrap();
+        prices.marks.insert("TEST".into(), 0.1);
+        assert_eq!(prices.tp_gap(&s, &stop), TpGapDecision::Sell);
+        prices.marks.clear();
+        assert_eq!(prices.tp_gap(&s, &take), TpGapDecision::Sell);
+
+        assert!(TpGapPolicy { gap_pct: 0.0, ..TpGapPolicy::default() }.validate().is_err());
+        assert!(TpGapPolicy { max_slippage_pct: 100.0, ..TpGapPolicy::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_liquidity_drain_triggers_exit() {
+        // Price sits inside every threshold, so only the pool size matters
+        let s = sentinel(1.0, 1.0);
+        let mut prices = SentinelPrices { min_liquidity_usd: 500.0, ..Default::default() };
+        prices.liquidity.insert("TEST".into(), 2_000.0);
+        assert!(prices.evaluate(&s, 1.0).is_none());
+
+        prices.liquidity.insert("TEST".into(), 499.0);
+        let trigger = prices.evaluate(&s, 1.0).unwrap();
+        assert!(matches!(trigger.trigger_type, TriggerType::LiquidityDrain));
+        assert_eq!(trigger.trigger_price, 1.0);
+
+        // Price thresholds still take priority over the drain
+        let trigger = prices.evaluate(&s, 0.5).unwrap();
+        assert!(matches!(trigger.trigger_type, TriggerType::StopLoss));
+
+        // Disabled, unknown pool, or a bad reading never fire
+        assert!(evaluate_liquidity(Some(10.0), 0.0, 1.0).is_none());
+        assert!(evaluate_liquidity(None, 500.0, 1.0).is_none());
+        assert!(evaluate_liquidity(Some(f64::NAN), 500.0, 1.0).is_none());
+        assert!(evaluate_liquidity(Some(10.0), f64::NAN, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_path_hits_trailing_stop_then_take_profit() {
+        let s = SentinelRow { ts_sell_pct: Some(50.0), ..sentinel(1.0, 1.0) };
+        let path = [1.0, 1.2, 1.5, f64::NAN, 1.3, 1.6, 2.0, 2.7, 3.0, 0.1];
+        let triggers = simulate_path(&s, &path);
+
+        assert_eq!(triggers.len(), 2, "{:?}", triggers);
+        // 10% off the 1.5 peak fires the trailing stop, selling half
+        let trailing = &triggers[0];
+        assert_eq!((trailing.step, trailing.trigger_type.as_str()), (4, "trailing_stop"));
+        assert!((trailing.trigger_price - 1.35).abs() < 1e-9);
+        assert_eq!((trailing.sell_pct, trailing.remaining_pct), (50.0, 50.0));
+
+        // Re-armed at 1.3, so +100% is 2.6 and the rest goes there
+        let tp = &triggers[1];
+        assert_eq!((tp.step, tp.trigger_type.as_str()), (7, "take_profit"));
+        assert_eq!(tp.entry_price, 1.3);
+        assert_eq!(tp.remaining_pct, 0.0);
+
+        // The live sentinel isn't touched
+        assert_eq!((s.entry_price, s.highest_price_seen), (1.0, 1.0));
+        assert!(simulate_path(&s, &[1.0, 1.05, 1.02]).is_empty());
+    }
+
+    #[test]
+    fn test_tp_ladder_scales_out_rung_by_rung() {
+        let ladder = [
+            TpRung { gain_pct: 100.0, sell_pct: 25.0 },
+            TpRung { gain_pct: 300.0, sell_pct: 25.0 },
+            TpRung { gain_pct: 1000.0, sell_pct: 50.0 },
+        ];
+        assert!(validate_tp_ladder(&ladder).is_ok());
+        let s = SentinelRow {
+            stop_loss_pct: None,
+            trailing_stop_pct: None,
+            tp_ladder: Some(serde_json::to_string(&ladder).unwrap()),
+            ..sentinel(1.0, 1.0)
+        };
+
+        // Gains stay measured from the original entry as the rungs fill
+        let triggers = simulate_path(&s, &[1.5, 2.0, 3.0, 4.2, 8.0, 11.0, 20.0]);
+        let steps: Vec<(usize, f64, f64)> =
+            triggers.iter().map(|t| (t.step, t.entry_price, t.remaining_pct.round())).collect();
+        assert_eq!(steps, vec![(1, 1.0, 75.0), (3, 1.0, 50.0), (5, 1.0, 0.0)]);
+        // 25% of the position is a third of the 75% left after the first rung
+        assert!((triggers[1].sell_pct - 100.0 / 3.0).abs() < 1e-9);
+        assert!(triggers[2].reason.contains("rung 3/3"));
+
+        // The monitor's view of the same rungs
+        let first = evaluate_sentinel(&s, 2.0).unwrap();
+        assert_eq!((first.tp_rung, after_sell(&s, &first, 25.0)), (Some(0), AfterSell::NextRung));
+        // A capped sell of the rung leaves the rest owed, and the rung waits for it
+        let owed = SentinelRow { unsold_trigger: Some("take_profit".into()), unsold_qty: Some(10.0), ..s.clone() };
+        let again = evaluate_sentinel(&owed, 2.0).unwrap();
+        assert_eq!(again.tp_rung, Some(0));
+        assert_eq!(trigger_sell_quantity(&owed, &again, trigger_sell_percentage(&owed, &again), 90.0), 10.0);
+        let on_last = SentinelRow { tp_rungs_filled: 2, ..s.clone() };
+        assert!(evaluate_sentinel(&on_last, 4.0).is_none());
+        let last = evaluate_sentinel(&on_last, 11.0).unwrap();
+        assert_eq!(trigger_sell_percentage(&on_last, &last), 100.0);
+        assert_eq!(after_sell(&on_last, &last, 100.0), AfterSell::Triggered);
+
+        // A plain take-profit is a one-rung ladder that re-arms after a partial sell
+        let single = SentinelRow { tp_sell_pct: Some(50.0), ..sentinel(1.0, 1.0) };
+        assert_eq!(take_profit_ladder(&single), vec![TpRung { gain_pct: 100.0, sell_pct: 50.0 }]);
+        let tp = evaluate_sentinel(&single, 2.0).unwrap();
+        assert_eq!(trigger_sell_percentage(&single, &tp), 50.0);
+        assert_eq!(after_sell(&single, &tp, 50.0), AfterSell::Rearm);
+
+        // A ladder selling 60% in total sells 60% and keeps guarding the rest
+        let partial = [ladder[0], TpRung { gain_pct: 300.0, sell_pct: 35.0 }];
+        assert!(validate_tp_ladder(&partial).is_ok());
+        let s = SentinelRow { tp_ladder: Some(serde_json::to_string(&partial).unwrap()), ..s };
+        let triggers = simulate_path(&s, &[2.0, 4.0, 0.7]);
+        let steps: Vec<(usize, f64)> = triggers.iter().map(|t| (t.step, t.remaining_pct.round())).collect();
+        assert_eq!(steps, vec![(0, 75.0), (1, 40.0)]);
+        let on_last = SentinelRow { tp_rungs_filled: 1, ..s.clone() };
+        let last = evaluate_sentinel(&on_last, 4.0).unwrap();
+        assert_eq!(after_sell(&on_last, &last, trigger_sell_percentage(&on_last, &last)), AfterSell::NextRung);
+        let done = SentinelRow { tp_rungs_filled: 2, stop_loss_pct: Some(-20.0), ..s };
+        assert!(evaluate_sentinel(&done, 40.0).is_none());
+        assert!(matches!(evaluate_sentinel(&done, 0.7).unwrap().trigger_type, TriggerType::StopLoss));
+
+        assert!(validate_tp_ladder(&[]).is_err());
+        assert!(validate_tp_ladder(&[ladder[1], ladder[0]]).is_err());
+        assert!(validate_tp_ladder(&[ladder[2], TpRung { gain_pct: 2000.0, sell_pct: 60.0 }]).is_err());
+    }
+}
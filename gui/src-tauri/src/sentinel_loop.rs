@@ -4,18 +4,26 @@
 //! (SL/TP/trailing stops) by polling portfolio prices on a configurable interval.
 //! Submits triggered sells through the TradeExecutor queue.
 
-use crate::notifications::NotificationHandle;
-use crate::sentinel_eval::evaluate_sentinel;
+use crate::app_event::{emit_event, AppEvent};
+use crate::decision_log::{self, DecisionAction, SentinelDecision, SentinelDecisionConfig, SentinelDecisionInput};
+use crate::loop_snapshot::{LoopTask, SentinelSnapshot, SHUTDOWN_TIMEOUT};
+use crate::notifications::{NotificationCategory, NotificationHandle};
+use crate::order_split::SplitFill;
+use crate::poll_interval::is_rate_limited;
+use crate::protective_sentinel::guarded_entry_price;
+use crate::sentinel_eval::{after_sell, amm_spot_price, trigger_sell_percentage, trigger_sell_quantity, AfterSell, PriceSource, SellBurstLimiter, SellRetries, SellRetryPolicy, SentinelPrices, TpGapDecision, TpGapPolicy};
+use crate::sentinel_reconcile::SentinelSyncDefaults;
 use crate::trade_executor::{TradeExecutorHandle, TradePriority};
+use crate::trade_reason::TradeReason;
 use crate::AppState;
-use crate::save_automation_log;
-use rugplay_core::{TradeType, truncate_to_8_decimals};
+use crate::{restore_loop_snapshot, save_automation_log, save_loop_snapshot};
+use rugplay_core::{parse_db_timestamp, TradeType, truncate_to_8_decimals};
 use rugplay_networking::RugplayClient;
 use rugplay_persistence::sqlite;
 use serde::Serialize;
 use std::sync::Arc;
 use std::time::Duration;
-use tauri::{Emitter, Manager};
+use tauri::Manager;
 use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
@@ -35,9 +43,6 @@ const SYNC_EVERY_N_TICKS: u32 = 6;
 /// How often (in ticks) to run a stale sentinel cleanup
 const CLEANUP_EVERY_N_TICKS: u32 = 12;
 
-/// Max consecutive sell failures before deactivating a sentinel to prevent spam
-const MAX_SELL_FAILURES: u32 = 3;
-
 /// Maximum fraction of pool tokens the server allows selling (99.5%)
 const MAX_POOL_SELL_FRACTION: f64 = 0.99;
 
@@ -61,7 +66,7 @@ pub struct SentinelTriggeredEvent {
     pub sentinel_id: i64,
     pub symbol: String,
     pub reason: String,
-    pub trigger_type: String, // "stop_loss", "take_profit", "trailing_stop"
+    pub trigger_type: String, // "stop_loss", "take_profit", "trailing_stop", "liquidity_drain"
     pub current_price: f64,
     pub entry_price: f64,
     pub sell_amount: f64,
@@ -84,7 +89,38 @@ pub struct SentinelMonitorHandle {
     pause_tx: watch::Sender<bool>,
     cancel_token: CancellationToken,
     status: Arc<tokio::sync::RwLock<MonitorStatus>>,
+    settings: MonitorSettings,
+    task: LoopTask,
+}
+
+/// Settings the monitor reads every tick, shared with its handle
+#[derive(Clone)]
+struct MonitorSettings {
     interval_secs: Arc<tokio::sync::RwLock<u64>>,
+    max_sells_per_tick: Arc<tokio::sync::RwLock<u32>>,
+    sell_retry: Arc<tokio::sync::RwLock<SellRetryPolicy>>,
+    price_source: Arc<tokio::sync::RwLock<PriceSource>>,
+    min_liquidity_usd: Arc<tokio::sync::RwLock<f64>>,
+    tp_gap: Arc<tokio::sync::RwLock<TpGapPolicy>>,
+}
+
+/// What the monitor carries from one tick to the next
+#[derive(Default)]
+struct SentinelLoopState {
+    /// Cooldown per symbol: symbol -> epoch when cooldown expires
+    trigger_cooldowns: std::collections::HashMap<String, i64>,
+    tick_counter: u32,
+    /// Consecutive sell failures per sentinel and when each may retry
+    sell_retries: SellRetries,
+    sell_limiter: SellBurstLimiter,
+}
+
+/// The portfolio and prices one tick checks sentinels against
+struct TickMarket<'a> {
+    portfolio: &'a rugplay_core::PortfolioResponse,
+    held_symbols: &'a std::collections::HashSet<String>,
+    blacklist: &'a std::collections::HashSet<String>,
+    prices: &'a SentinelPrices,
 }
 
 impl SentinelMonitorHandle {
@@ -109,6 +145,14 @@ impl SentinelMonitorHandle {
         info!("Sentinel monitor stopped");
     }
 
+    /// Stop the monitor and wait for it to save its in-memory state
+    pub async fn shutdown(&self) {
+        self.stop().await;
+        if !self.task.join(SHUTDOWN_TIMEOUT).await {
+            warn!("Sentinel monitor did not stop in time, state snapshot skipped");
+        }
+    }
+
     /// Get current status
     pub async fn status(&self) -> MonitorStatus {
         *self.status.read().await
@@ -121,13 +165,74 @@ impl SentinelMonitorHandle {
 
     /// Update polling interval
     pub async fn set_interval(&self, secs: u64) {
-        *self.interval_secs.write().await = secs;
+        *self.settings.interval_secs.write().await = secs;
         info!("Sentinel monitor interval set to {}s", secs);
     }
 
     /// Get current polling interval
     pub async fn get_interval(&self) -> u64 {
-        *self.interval_secs.read().await
+        *self.settings.interval_secs.read().await
+    }
+
+    /// Cap sentinel sells dispatched per tick (0 = unlimited)
+    pub async fn set_max_sells_per_tick(&self, max: u32) {
+        *self.settings.max_sells_per_tick.write().await = max;
+        info!("Sentinel sell burst cap set to {} per tick", max);
+    }
+
+    /// Get the per-tick sentinel sell cap
+    pub async fn get_max_sells_per_tick(&self) -> u32 {
+        *self.settings.max_sells_per_tick.read().await
+    }
+
+    /// Set the backoff schedule and failure cap for failed sentinel sells
+    pub async fn set_sell_retry_policy(&self, policy: SellRetryPolicy) {
+        *self.settings.sell_retry.write().await = policy;
+        info!(
+            "Sentinel sell retry set to {}s doubling up to {}s, {} attempts",
+            policy.base_secs, policy.max_secs, policy.max_failures
+        );
+    }
+
+    /// Get the sentinel sell retry policy
+    pub async fn get_sell_retry_policy(&self) -> SellRetryPolicy {
+        *self.settings.sell_retry.read().await
+    }
+
+    /// Set the default price source for sentinels without their own
+    pub async fn set_price_source(&self, source: PriceSource) {
+        *self.settings.price_source.write().await = source;
+        info!("Sentinel price source set to {}", source.as_str());
+    }
+
+    /// Get the default sentinel price source
+    pub async fn get_price_source(&self) -> PriceSource {
+        *self.settings.price_source.read().await
+    }
+
+    /// Sell held coins whose pool drops below this much base currency (0 = off)
+    pub async fn set_min_liquidity_usd(&self, min: f64) {
+        *self.settings.min_liquidity_usd.write().await = min;
+        info!("Sentinel minimum hold liquidity set to ${:.2}", min);
+    }
+
+    /// Get the minimum pool liquidity to keep holding
+    pub async fn get_min_liquidity_usd(&self) -> f64 {
+        *self.settings.min_liquidity_usd.read().await
+    }
+
+    /// Set how take-profits react when spot has dumped below the target
+    pub async fn set_tp_gap_policy(&self, policy: TpGapPolicy) {
+        *self.settings.tp_gap.write().await = policy;
+        info!(
+            "Sentinel take-profit gap set to {:?} beyond {:.1}% (max slippage {:.1}%)",
+            policy.action, policy.gap_pct, policy.max_slippage_pct
+        );
+    }
+
+    /// Get the take-profit gap policy
+    pub async fn get_tp_gap_policy(&self) -> TpGapPolicy {
+        *self.settings.tp_gap.read().await
     }
 }
 
@@ -141,25 +246,25 @@ pub fn spawn_sentinel_monitor(
     let (pause_tx, pause_rx) = watch::channel(false); // starts unpaused
     let cancel_token = CancellationToken::new();
     let status = Arc::new(tokio::sync::RwLock::new(MonitorStatus::Running));
-    let interval_secs = Arc::new(tokio::sync::RwLock::new(DEFAULT_INTERVAL_SECS));
-
-    let handle = SentinelMonitorHandle {
-        pause_tx,
-        cancel_token: cancel_token.clone(),
-        status: status.clone(),
-        interval_secs: interval_secs.clone(),
+    let settings = MonitorSettings {
+        interval_secs: Arc::new(tokio::sync::RwLock::new(DEFAULT_INTERVAL_SECS)),
+        max_sells_per_tick: Arc::new(tokio::sync::RwLock::new(0)),
+        sell_retry: Arc::new(tokio::sync::RwLock::new(SellRetryPolicy::default())),
+        price_source: Arc::new(tokio::sync::RwLock::new(PriceSource::default())),
+        min_liquidity_usd: Arc::new(tokio::sync::RwLock::new(0.0)),
+        tp_gap: Arc::new(tokio::sync::RwLock::new(TpGapPolicy::default())),
     };
 
-    tokio::spawn(sentinel_monitor_loop(
+    let task = LoopTask::spawn(sentinel_monitor_loop(
         app_handle,
         executor_handle,
         pause_rx,
-        cancel_token,
-        status,
-        interval_secs,
+        cancel_token.clone(),
+        status.clone(),
+        settings.clone(),
     ));
 
-    handle
+    SentinelMonitorHandle { pause_tx, cancel_token, status, settings, task }
 }
 
 /// The main sentinel monitor loop
@@ -169,18 +274,36 @@ async fn sentinel_monitor_loop(
     mut pause_rx: watch::Receiver<bool>,
     cancel_token: CancellationToken,
     status: Arc<tokio::sync::RwLock<MonitorStatus>>,
-    interval_secs: Arc<tokio::sync::RwLock<u64>>,
+    settings: MonitorSettings,
 ) {
     info!("Sentinel monitor started (interval: {}s)", DEFAULT_INTERVAL_SECS);
+    let MonitorSettings { interval_secs, max_sells_per_tick, sell_retry, price_source, min_liquidity_usd, tp_gap } =
+        settings;
 
     // Give the app a moment to initialize DB and login
     tokio::time::sleep(Duration::from_secs(3)).await;
 
-    // Track cooldown per symbol: symbol -> epoch when cooldown expires
-    let mut trigger_cooldowns: std::collections::HashMap<String, i64> = std::collections::HashMap::new();
-    let mut tick_counter: u32 = 0;
-    // Track consecutive sell failures per sentinel to prevent infinite retry spam
-    let mut sell_failures: std::collections::HashMap<i64, u32> = std::collections::HashMap::new();
+    // Restore the persisted sell burst cap
+    if let Some(max) = load_max_sells_per_tick(&app_handle).await {
+        *max_sells_per_tick.write().await = max;
+    }
+    if let Some(policy) = load_sell_retry_policy(&app_handle).await {
+        *sell_retry.write().await = policy;
+    }
+    if let Some(source) = load_price_source(&app_handle).await {
+        *price_source.write().await = source;
+    }
+    if let Some(min) = load_min_liquidity_usd(&app_handle).await {
+        *min_liquidity_usd.write().await = min;
+    }
+    if let Some(policy) = load_tp_gap_policy(&app_handle).await {
+        *tp_gap.write().await = policy;
+    }
+    let mut loop_state = SentinelLoopState::default();
+    if let Some(snapshot) = restore_loop_snapshot::<SentinelSnapshot>(&app_handle).await {
+        loop_state.trigger_cooldowns = snapshot.trigger_cooldowns;
+        loop_state.sell_retries = SellRetries::restore(snapshot.sell_failures, snapshot.sell_retry_at);
+    }
 
     loop {
         let current_interval = *interval_secs.read().await;
@@ -191,25 +314,45 @@ async fn sentinel_monitor_loop(
                 break;
             }
             _ = tokio::time::sleep(Duration::from_secs(current_interval)) => {
+                app_handle.state::<AppState>().heartbeats.beat("sentinel");
+
+                // Stops only wait for the warm-up to load settings and the profile
+                if !app_handle.state::<AppState>().warmup.allows_protective() {
+                    debug!("Sentinel: warming up, skipping tick");
+                    continue;
+                }
+
                 // Check if paused
                 if *pause_rx.borrow() {
                     debug!("Sentinel monitor is paused, skipping tick");
                     continue;
                 }
 
+                loop_state.sell_limiter.set_cap(*max_sells_per_tick.read().await as usize);
+                loop_state.sell_limiter.begin_tick();
+                loop_state.sell_retries.policy = *sell_retry.read().await;
+                let source = *price_source.read().await;
+                let min_liquidity = *min_liquidity_usd.read().await;
+                let gap_policy = *tp_gap.read().await;
+
                 // Run a sentinel check
-                match run_sentinel_tick(&app_handle, &executor_handle, &mut trigger_cooldowns, &mut tick_counter, &mut sell_failures).await {
+                match run_sentinel_tick(&app_handle, &executor_handle, &mut loop_state, source, min_liquidity, gap_policy).await {
                     Ok(tick) => {
                         debug!(
                             "Sentinel tick: checked={}, active={}",
                             tick.checked, tick.active_count
                         );
                         // Emit tick event to frontend
-                        if let Err(e) = app_handle.emit("sentinel-tick", &tick) {
+                        if let Err(e) = emit_event(&app_handle, AppEvent::SentinelTick(tick)) {
                             warn!("Failed to emit sentinel-tick event: {}", e);
                         }
                     }
                     Err(e) => {
+                        // Stop-losses keep their fixed interval, but let the
+                        // polling loops know the API is pushing back
+                        if is_rate_limited(&e) {
+                            app_handle.state::<AppState>().api_pressure.record_rate_limit();
+                        }
                         // Don't spam errors if user just hasn't logged in yet
                         if !e.contains("No active profile") && !e.contains("Database not initialized") {
                             error!("Sentinel tick error: {}", e);
@@ -232,6 +375,9 @@ async fn sentinel_monitor_loop(
         }
     }
 
+    let SentinelLoopState { trigger_cooldowns, sell_retries, .. } = loop_state;
+    let (sell_failures, sell_retry_at) = sell_retries.into_parts();
+    save_loop_snapshot(&app_handle, &SentinelSnapshot { trigger_cooldowns, sell_failures, sell_retry_at }).await;
     *status.write().await = MonitorStatus::Stopped;
     info!("Sentinel monitor loop exited");
 }
@@ -241,10 +387,12 @@ async fn sentinel_monitor_loop(
 async fn run_sentinel_tick(
     app_handle: &tauri::AppHandle,
     executor_handle: &TradeExecutorHandle,
-    trigger_cooldowns: &mut std::collections::HashMap<String, i64>,
-    tick_counter: &mut u32,
-    sell_failures: &mut std::collections::HashMap<i64, u32>,
+    loop_state: &mut SentinelLoopState,
+    price_source: PriceSource,
+    min_liquidity_usd: f64,
+    tp_gap: TpGapPolicy,
 ) -> Result<SentinelTickEvent, String> {
+    let SentinelLoopState { trigger_cooldowns, tick_counter, sell_retries, sell_limiter } = loop_state;
     let state = app_handle.state::<AppState>();
 
     let db_guard = state.db.read().await;
@@ -270,10 +418,11 @@ async fn run_sentinel_tick(
         .await
         .map_err(|e| e.to_string())?;
 
-    let active_sentinels: Vec<_> = sentinels
+    let mut active_sentinels: Vec<_> = sentinels
         .iter()
         .filter(|s| s.is_active && s.triggered_at.is_none())
         .collect();
+    sell_limiter.prioritize(&mut active_sentinels, |s| s.id);
 
     let active_count = active_sentinels.len() as u32;
 
@@ -290,7 +439,7 @@ async fn run_sentinel_tick(
     drop(db_guard);
 
     // Fetch portfolio for current prices (using cached client)
-    let client = RugplayClient::new_with_cache(&token, state.coin_cache.clone());
+    let client = state.api_client(&token);
     let portfolio = client.get_portfolio().await.map_err(|e| {
         format!("Failed to fetch portfolio: {}", e)
     })?;
@@ -301,6 +450,8 @@ async fn run_sentinel_tick(
         .map(|h| h.symbol.clone())
         .collect();
 
+    let prices = fetch_sentinel_prices(&client, &active_sentinels, price_source, min_liquidity_usd, tp_gap).await;
+
     // Load blacklist from settings
     let blacklist_set: std::collections::HashSet<String> = {
         let db_guard = state.db.read().await;
@@ -385,18 +536,24 @@ async fn run_sentinel_tick(
         let db_guard2 = state.db.read().await;
         if let Some(db2) = db_guard2.as_ref() {
             if let Ok(refreshed) = sqlite::get_sentinels(db2.pool(), active_profile.id).await {
-                let refreshed_active: Vec<_> = refreshed
+                let mut refreshed_active: Vec<_> = refreshed
                     .iter()
                     .filter(|s| s.is_active && s.triggered_at.is_none())
                     .cloned()
                     .collect();
+                sell_limiter.prioritize(&mut refreshed_active, |s| s.id);
                 let refreshed_count = refreshed_active.len() as u32;
                 drop(db_guard2);
 
                 // Run the check with refreshed sentinels
+                let market = TickMarket {
+                    portfolio: &portfolio,
+                    held_symbols: &held_symbols,
+                    blacklist: &blacklist_set,
+                    prices: &prices,
+                };
                 return run_sentinel_checks(
-                    app_handle, executor_handle, trigger_cooldowns, sell_failures,
-                    &refreshed_active, refreshed_count, &portfolio, &held_symbols, &blacklist_set, &state,
+                    app_handle, executor_handle, loop_state, &refreshed_active, refreshed_count, market, &state,
                 ).await;
             }
         }
@@ -417,15 +574,19 @@ async fn run_sentinel_tick(
         }
 
         // Skip if sentinel has too many consecutive sell failures
-        if let Some(&failures) = sell_failures.get(&sentinel.id) {
-            if failures >= MAX_SELL_FAILURES {
-                debug!("Sentinel #{}: skipping, {} consecutive sell failures (deactivated)", sentinel.id, failures);
-                continue;
-            }
+        if sell_retries.is_exhausted(sentinel.id) {
+            debug!("Sentinel #{}: skipping, {} consecutive sell failures (deactivated)", sentinel.id, sell_retries.failures(sentinel.id));
+            continue;
         }
 
-        // Skip if in cooldown after a recent trigger
+        // Skip while backing off after a failed sell
         let now_epoch = chrono::Utc::now().timestamp();
+        if let Some(wait) = sell_retries.backoff_remaining(sentinel.id, now_epoch) {
+            debug!("Sentinel #{}: retrying {} sell in {}s", sentinel.id, sentinel.symbol, wait);
+            continue;
+        }
+
+        // Skip if in cooldown after a recent trigger
         if let Some(&cooldown_until) = trigger_cooldowns.get(&sentinel.symbol) {
             if now_epoch < cooldown_until {
                 debug!("Sentinel: skipping {} (cooldown {}s remaining)", sentinel.symbol, cooldown_until - now_epoch);
@@ -435,8 +596,8 @@ async fn run_sentinel_tick(
 
         // Grace period: skip newly created sentinels to prevent instant triggers
         if let Some(ref created_str) = sentinel.created_at {
-            if let Ok(created) = chrono::NaiveDateTime::parse_from_str(created_str, "%Y-%m-%d %H:%M:%S") {
-                let created_ts = created.and_utc().timestamp();
+            if let Some(created) = parse_db_timestamp(created_str) {
+                let created_ts = created.timestamp();
                 let age = now_epoch - created_ts;
                 if age < CREATION_GRACE_SECS {
                     debug!("Sentinel #{}: skipping {} (grace period, {}s old)", sentinel.id, sentinel.symbol, age);
@@ -451,7 +612,7 @@ async fn run_sentinel_tick(
         };
 
         checked += 1;
-        let current_price = holding.current_price;
+        let current_price = prices.price_for(sentinel, holding.current_price);
         let entry_price = sentinel.entry_price;
 
         // Re-acquire DB lock for price updates
@@ -466,12 +627,33 @@ async fn run_sentinel_tick(
             let _ = sqlite::update_highest_price(db.pool(), sentinel.id, current_price).await;
         }
 
-        let trigger = evaluate_sentinel(sentinel, current_price);
+        let trigger = prices.evaluate(sentinel, current_price);
 
         if let Some(trigger) = trigger {
+            // Take-profit into a dump: either stay armed or cap the sell
+            let gap = prices.tp_gap(sentinel, &trigger);
+            if let TpGapDecision::Abort { spot } = gap {
+                info!(
+                    "Sentinel #{}: {} take-profit hit but spot {} is below the target {}, staying armed",
+                    sentinel.id, sentinel.symbol, spot, trigger.trigger_price
+                );
+                continue;
+            }
+
+            // Burst cap: leave the rest of a crash wave for the next tick
+            if !sell_limiter.try_admit(sentinel.id) {
+                debug!(
+                    "Sentinel #{}: {} triggered but sell cap reached, deferring to next tick",
+                    sentinel.id, sentinel.symbol
+                );
+                continue;
+            }
+
             let reason = trigger.reason.clone();
             let trigger_type = trigger.trigger_type.as_str().to_string();
             info!("Sentinel #{} triggered for {}: {}", sentinel.id, sentinel.symbol, reason);
+            let sell_pct = trigger_sell_percentage(sentinel, &trigger);
+            record_trigger(db, sentinel, current_price, &prices, &trigger_type).await;
 
             // Send native notification
             if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
@@ -488,18 +670,34 @@ async fn run_sentinel_tick(
                         let drop_pct = sentinel.trailing_stop_pct.unwrap_or(0.0);
                         notif.notify_trailing_stop(&sentinel.symbol, drop_pct, current_price).await;
                     }
+                    "liquidity_drain" => {
+                        let liquidity = prices.liquidity.get(&sentinel.symbol).copied().unwrap_or(0.0);
+                        notif.notify_liquidity_drain(&sentinel.symbol, liquidity, current_price).await;
+                    }
                     _ => {}
                 }
             }
 
-            let sell_qty = holding.quantity * (sentinel.sell_percentage / 100.0);
+            let sell_qty = trigger_sell_quantity(sentinel, &trigger, sell_pct, holding.quantity);
             // Cap to 99% of holdings to avoid "Cannot sell more than 99.5% of pool" errors
-            let sell_qty = if sentinel.sell_percentage >= 100.0 {
+            let sell_qty = if sell_pct >= 100.0 {
                 f64::min(sell_qty, holding.quantity * MAX_POOL_SELL_FRACTION)
             } else {
                 sell_qty
             };
             let sell_qty = truncate_to_8_decimals(sell_qty);
+            // A capped sell leaves the rest owed by the trigger for later ticks
+            let target_qty = sell_qty;
+            let sell_qty = match gap {
+                TpGapDecision::Capped { max_quantity, spot } if max_quantity < target_qty => {
+                    info!(
+                        "Sentinel #{}: {} spot {} is below the take-profit target, selling {} of {} within the slippage cap",
+                        sentinel.id, sentinel.symbol, spot, truncate_to_8_decimals(max_quantity), target_qty
+                    );
+                    truncate_to_8_decimals(max_quantity)
+                }
+                _ => target_qty,
+            };
 
             // Skip if holding balance is effectively zero
             if sell_qty <= 0.0 || holding.quantity <= 0.0 {
@@ -518,29 +716,50 @@ async fn run_sentinel_tick(
                     current_price,
                     entry_price,
                     sell_amount: sell_qty,
-                    sell_percentage: sentinel.sell_percentage,
+                    sell_percentage: sell_pct,
                 };
-                if let Err(e) = app_handle.emit("sentinel-triggered", &triggered_event) {
+                if let Err(e) = emit_event(app_handle, AppEvent::SentinelTriggered(triggered_event)) {
                     warn!("Failed to emit sentinel-triggered event: {}", e);
                 }
 
                 // Submit sell through the trade executor and WAIT for the result
-                let sell_result = executor_handle
-                    .submit_trade(
-                        sentinel.symbol.clone(),
-                        TradeType::Sell,
-                        sell_qty,
-                        TradePriority::High,
-                        format!("Sentinel #{}: {}", sentinel.id, reason),
-                    )
-                    .await;
+                let sell_reason = TradeReason::Sentinel {
+                    sentinel_id: sentinel.id,
+                    trigger_type: trigger_type.clone(),
+                    detail: reason.clone(),
+                };
+                let split = executor_handle.get_split_config().await;
+                let sell_result = if split.applies_to_sentinel(sell_qty * current_price) {
+                    executor_handle
+                        .submit_split_sell(
+                            sentinel.symbol.clone(),
+                            sell_qty,
+                            TradePriority::High,
+                            sell_reason,
+                            "sentinel",
+                        )
+                        .await
+                } else {
+                    executor_handle
+                        .submit_trade(
+                            sentinel.symbol.clone(),
+                            TradeType::Sell,
+                            sell_qty,
+                            TradePriority::High,
+                            sell_reason,
+                            "sentinel",
+                        )
+                        .await
+                        .map(|response| SplitFill::complete(response, sell_qty))
+                };
 
+                let mut backing_off = false;
                 match sell_result {
-                    Ok(_response) => {
+                    Ok(fill) => {
                         info!("Sentinel #{} sell CONFIRMED for {} — {}", sentinel.id, sentinel.symbol, reason);
 
                         // Clear failure counter on success
-                        sell_failures.remove(&sentinel.id);
+                        sell_retries.record_success(sentinel.id);
 
                         let pnl_pct = if entry_price > 0.0 { ((current_price - entry_price) / entry_price) * 100.0 } else { 0.0 };
 
@@ -550,7 +769,7 @@ async fn run_sentinel_tick(
                             &sentinel.symbol,
                             &sentinel.symbol,
                             "SELL",
-                            sell_qty,
+                            fill.sold,
                             &serde_json::json!({
                                 "sentinelId": sentinel.id,
                                 "triggerType": trigger_type,
@@ -559,26 +778,49 @@ async fn run_sentinel_tick(
                                 "triggerPrice": trigger.trigger_price,
                                 "currentPrice": current_price,
                                 "pnlPct": (pnl_pct * 100.0).round() / 100.0,
-                                "sellPercentage": sentinel.sell_percentage,
-                                "status": "confirmed",
+                                "sellPercentage": sell_pct,
+                                "status": if fill.is_partial() { "partial" } else { "confirmed" },
                             }).to_string(),
                         ).await;
 
-                        if sentinel.sell_percentage >= 100.0 {
-                            let _ = sqlite::mark_sentinel_triggered(db.pool(), sentinel.id).await;
+                        // Whatever the cap held back or a split left unsold is still owed
+                        let unsold = truncate_to_8_decimals(target_qty - fill.sold);
+                        if unsold > 0.0 {
+                            if let Some(e) = &fill.error {
+                                warn!(
+                                    "Sentinel #{}: split sell of {} stopped with {} unsold ({}), staying armed for the rest",
+                                    sentinel.id, sentinel.symbol, fill.unsold, e
+                                );
+                                trigger_cooldowns.insert(sentinel.symbol.clone(), chrono::Utc::now().timestamp() + FAILED_COOLDOWN_SECS);
+                            } else {
+                                info!("Sentinel #{} stays armed for the {} {} left after a capped sell", sentinel.id, unsold, sentinel.symbol);
+                            }
+                            let _ = sqlite::set_sentinel_unsold(db.pool(), sentinel.id, &trigger_type, unsold).await;
                         } else {
-                            let _ = sqlite::rearm_sentinel(db.pool(), sentinel.id, current_price).await;
-                            info!("Sentinel #{} re-armed after partial sell ({:.0}%) — new entry price: {}", sentinel.id, sentinel.sell_percentage, current_price);
+                            match after_sell(sentinel, &trigger, sell_pct) {
+                                AfterSell::Triggered => {
+                                    let _ = sqlite::mark_sentinel_triggered(db.pool(), sentinel.id).await;
+                                }
+                                AfterSell::NextRung => {
+                                    let _ = sqlite::fill_tp_rung(db.pool(), sentinel.id).await;
+                                    info!("Sentinel #{} take-profit rung {} filled, armed for the next", sentinel.id, trigger.tp_rung.map_or(0, |r| r + 1));
+                                }
+                                AfterSell::Rearm => {
+                                    let _ = sqlite::rearm_sentinel(db.pool(), sentinel.id, current_price).await;
+                                    info!("Sentinel #{} re-armed after partial sell ({:.0}%) — new entry price: {}", sentinel.id, sell_pct, current_price);
+                                }
+                            }
                         }
                     }
                     Err(e) => {
                         let error_str = e.to_string();
-                        let is_rate_limited = error_str.contains("429") || error_str.contains("Rate limit");
+                        let rate_limited = is_rate_limited(&error_str);
                         let is_pool_limit = error_str.contains("99.5%") || error_str.contains("pool tokens");
                         let is_zero_balance = error_str.contains("Insufficient coins") || error_str.contains("have 0");
 
                         // Don't count rate limits or pool limits as "real" failures
-                        if is_rate_limited {
+                        if rate_limited {
+                            app_handle.state::<AppState>().api_pressure.record_rate_limit();
                             warn!("Sentinel #{}: rate-limited for {}, will retry next tick", sentinel.id, sentinel.symbol);
                             // Use short cooldown for rate limits
                             trigger_cooldowns.insert(sentinel.symbol.clone(), chrono::Utc::now().timestamp() + FAILED_COOLDOWN_SECS);
@@ -589,24 +831,27 @@ async fn run_sentinel_tick(
                             warn!("Sentinel #{}: pool limit hit for {}, will retry with smaller amount", sentinel.id, sentinel.symbol);
                             // Don't increment failure counter, the next tick will use the capped amount
                         } else {
-                            let fail_count = sell_failures.entry(sentinel.id).or_insert(0);
-                            *fail_count += 1;
+                            // Retried on its own backoff, not the symbol cooldown
+                            backing_off = true;
+                            let max_failures = sell_retries.policy.max_failures;
+                            let fail_count = sell_retries.record_failure(sentinel.id, chrono::Utc::now().timestamp());
                             error!(
                                 "Sentinel #{} sell FAILED for {} (attempt {}/{}): {}",
-                                sentinel.id, sentinel.symbol, fail_count, MAX_SELL_FAILURES, e
+                                sentinel.id, sentinel.symbol, fail_count, max_failures, e
                             );
 
-                            if *fail_count >= MAX_SELL_FAILURES {
+                            if fail_count >= max_failures {
                                 warn!(
                                     "Sentinel #{} for {} deactivated after {} consecutive sell failures",
-                                    sentinel.id, sentinel.symbol, MAX_SELL_FAILURES
+                                    sentinel.id, sentinel.symbol, max_failures
                                 );
                                 let _ = sqlite::set_sentinel_active(db.pool(), sentinel.id, false).await;
 
                                 if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
-                                    notif.send_raw(
+                                    notif.notify_dedup(
+                                        NotificationCategory::Failure,
                                         &format!("Sentinel Failed: {}", sentinel.symbol),
-                                        &format!("Sell failed {} times, sentinel deactivated. Check your holdings.", MAX_SELL_FAILURES),
+                                        &format!("Sell failed {} times, sentinel deactivated. Check your holdings.", max_failures),
                                     ).await;
                                 }
                             }
@@ -627,15 +872,15 @@ async fn run_sentinel_tick(
                                 "triggerPrice": trigger.trigger_price,
                                 "currentPrice": current_price,
                                 "error": e,
-                                "failureCount": sell_failures.get(&sentinel.id).copied().unwrap_or(0),
-                                "isRateLimited": is_rate_limited,
+                                "failureCount": sell_retries.failures(sentinel.id),
+                                "isRateLimited": rate_limited,
                             }).to_string(),
                         ).await;
                     }
                 }
 
                 // Set appropriate cooldown: shorter for failures, longer for successful sells
-                if !trigger_cooldowns.contains_key(&sentinel.symbol) {
+                if !backing_off && !trigger_cooldowns.contains_key(&sentinel.symbol) {
                     trigger_cooldowns.insert(sentinel.symbol.clone(), chrono::Utc::now().timestamp() + TRIGGER_COOLDOWN_SECS);
                 }
             }
@@ -656,15 +901,14 @@ async fn run_sentinel_tick(
 async fn run_sentinel_checks(
     app_handle: &tauri::AppHandle,
     executor_handle: &TradeExecutorHandle,
-    trigger_cooldowns: &mut std::collections::HashMap<String, i64>,
-    sell_failures: &mut std::collections::HashMap<i64, u32>,
+    loop_state: &mut SentinelLoopState,
     active_sentinels: &[sqlite::SentinelRow],
     active_count: u32,
-    portfolio: &rugplay_core::PortfolioResponse,
-    held_symbols: &std::collections::HashSet<String>,
-    blacklist: &std::collections::HashSet<String>,
+    market: TickMarket<'_>,
     state: &AppState,
 ) -> Result<SentinelTickEvent, String> {
+    let SentinelLoopState { trigger_cooldowns, sell_retries, sell_limiter, .. } = loop_state;
+    let TickMarket { portfolio, held_symbols, blacklist, prices } = market;
     let mut checked: u32 = 0;
 
     for sentinel in active_sentinels {
@@ -677,13 +921,14 @@ async fn run_sentinel_checks(
         }
 
         // Skip if sentinel has too many consecutive sell failures
-        if let Some(&failures) = sell_failures.get(&sentinel.id) {
-            if failures >= MAX_SELL_FAILURES {
-                continue;
-            }
+        if sell_retries.is_exhausted(sentinel.id) {
+            continue;
         }
 
         let now_epoch = chrono::Utc::now().timestamp();
+        if sell_retries.backoff_remaining(sentinel.id, now_epoch).is_some() {
+            continue;
+        }
         if let Some(&cooldown_until) = trigger_cooldowns.get(&sentinel.symbol) {
             if now_epoch < cooldown_until {
                 continue;
@@ -692,8 +937,8 @@ async fn run_sentinel_checks(
 
         // Grace period: skip newly created sentinels to prevent instant triggers
         if let Some(ref created_str) = sentinel.created_at {
-            if let Ok(created) = chrono::NaiveDateTime::parse_from_str(created_str, "%Y-%m-%d %H:%M:%S") {
-                let created_ts = created.and_utc().timestamp();
+            if let Some(created) = parse_db_timestamp(created_str) {
+                let created_ts = created.timestamp();
                 let age = now_epoch - created_ts;
                 if age < CREATION_GRACE_SECS {
                     debug!("Sentinel #{}: skipping {} (grace period, {}s old)", sentinel.id, sentinel.symbol, age);
@@ -708,7 +953,7 @@ async fn run_sentinel_checks(
         };
 
         checked += 1;
-        let current_price = holding.current_price;
+        let current_price = prices.price_for(sentinel, holding.current_price);
         let entry_price = sentinel.entry_price;
 
         let db_guard = state.db.read().await;
@@ -721,12 +966,33 @@ async fn run_sentinel_checks(
             let _ = sqlite::update_highest_price(db.pool(), sentinel.id, current_price).await;
         }
 
-        let trigger = evaluate_sentinel(sentinel, current_price);
+        let trigger = prices.evaluate(sentinel, current_price);
 
         if let Some(trigger) = trigger {
+            // Take-profit into a dump: either stay armed or cap the sell
+            let gap = prices.tp_gap(sentinel, &trigger);
+            if let TpGapDecision::Abort { spot } = gap {
+                info!(
+                    "Sentinel #{}: {} take-profit hit but spot {} is below the target {}, staying armed",
+                    sentinel.id, sentinel.symbol, spot, trigger.trigger_price
+                );
+                continue;
+            }
+
+            // Burst cap: leave the rest of a crash wave for the next tick
+            if !sell_limiter.try_admit(sentinel.id) {
+                debug!(
+                    "Sentinel #{}: {} triggered but sell cap reached, deferring to next tick",
+                    sentinel.id, sentinel.symbol
+                );
+                continue;
+            }
+
             let reason = trigger.reason.clone();
             let trigger_type = trigger.trigger_type.as_str().to_string();
             info!("Sentinel #{} triggered for {}: {}", sentinel.id, sentinel.symbol, reason);
+            let sell_pct = trigger_sell_percentage(sentinel, &trigger);
+            record_trigger(db, sentinel, current_price, prices, &trigger_type).await;
 
             if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
                 match trigger_type.as_str() {
@@ -742,17 +1008,33 @@ async fn run_sentinel_checks(
                         let drop_pct = sentinel.trailing_stop_pct.unwrap_or(0.0);
                         notif.notify_trailing_stop(&sentinel.symbol, drop_pct, current_price).await;
                     }
+                    "liquidity_drain" => {
+                        let liquidity = prices.liquidity.get(&sentinel.symbol).copied().unwrap_or(0.0);
+                        notif.notify_liquidity_drain(&sentinel.symbol, liquidity, current_price).await;
+                    }
                     _ => {}
                 }
             }
 
-            let sell_qty = holding.quantity * (sentinel.sell_percentage / 100.0);
-            let sell_qty = if sentinel.sell_percentage >= 100.0 {
+            let sell_qty = trigger_sell_quantity(sentinel, &trigger, sell_pct, holding.quantity);
+            let sell_qty = if sell_pct >= 100.0 {
                 f64::min(sell_qty, holding.quantity * MAX_POOL_SELL_FRACTION)
             } else {
                 sell_qty
             };
             let sell_qty = truncate_to_8_decimals(sell_qty);
+            // A capped sell leaves the rest owed by the trigger for later ticks
+            let target_qty = sell_qty;
+            let sell_qty = match gap {
+                TpGapDecision::Capped { max_quantity, spot } if max_quantity < target_qty => {
+                    info!(
+                        "Sentinel #{}: {} spot {} is below the take-profit target, selling {} of {} within the slippage cap",
+                        sentinel.id, sentinel.symbol, spot, truncate_to_8_decimals(max_quantity), target_qty
+                    );
+                    truncate_to_8_decimals(max_quantity)
+                }
+                _ => target_qty,
+            };
 
             if sell_qty <= 0.0 || holding.quantity <= 0.0 {
                 warn!("Sentinel #{}: skipping {} — zero balance", sentinel.id, sentinel.symbol);
@@ -769,24 +1051,45 @@ async fn run_sentinel_checks(
                     current_price,
                     entry_price,
                     sell_amount: sell_qty,
-                    sell_percentage: sentinel.sell_percentage,
+                    sell_percentage: sell_pct,
+                };
+                let _ = emit_event(app_handle, AppEvent::SentinelTriggered(triggered_event));
+
+                let sell_reason = TradeReason::Sentinel {
+                    sentinel_id: sentinel.id,
+                    trigger_type: trigger_type.clone(),
+                    detail: reason.clone(),
+                };
+                let split = executor_handle.get_split_config().await;
+                let sell_result = if split.applies_to_sentinel(sell_qty * current_price) {
+                    executor_handle
+                        .submit_split_sell(
+                            sentinel.symbol.clone(),
+                            sell_qty,
+                            TradePriority::High,
+                            sell_reason,
+                            "sentinel",
+                        )
+                        .await
+                } else {
+                    executor_handle
+                        .submit_trade(
+                            sentinel.symbol.clone(),
+                            TradeType::Sell,
+                            sell_qty,
+                            TradePriority::High,
+                            sell_reason,
+                            "sentinel",
+                        )
+                        .await
+                        .map(|response| SplitFill::complete(response, sell_qty))
                 };
-                let _ = app_handle.emit("sentinel-triggered", &triggered_event);
-
-                let sell_result = executor_handle
-                    .submit_trade(
-                        sentinel.symbol.clone(),
-                        TradeType::Sell,
-                        sell_qty,
-                        TradePriority::High,
-                        format!("Sentinel #{}: {}", sentinel.id, reason),
-                    )
-                    .await;
 
+                let mut backing_off = false;
                 match sell_result {
-                    Ok(_response) => {
+                    Ok(fill) => {
                         info!("Sentinel #{} sell CONFIRMED for {} — {}", sentinel.id, sentinel.symbol, reason);
-                        sell_failures.remove(&sentinel.id);
+                        sell_retries.record_success(sentinel.id);
 
                         let pnl_pct = if entry_price > 0.0 { ((current_price - entry_price) / entry_price) * 100.0 } else { 0.0 };
 
@@ -796,7 +1099,7 @@ async fn run_sentinel_checks(
                             &sentinel.symbol,
                             &sentinel.symbol,
                             "SELL",
-                            sell_qty,
+                            fill.sold,
                             &serde_json::json!({
                                 "sentinelId": sentinel.id,
                                 "triggerType": trigger_type,
@@ -805,25 +1108,48 @@ async fn run_sentinel_checks(
                                 "triggerPrice": trigger.trigger_price,
                                 "currentPrice": current_price,
                                 "pnlPct": (pnl_pct * 100.0).round() / 100.0,
-                                "sellPercentage": sentinel.sell_percentage,
-                                "status": "confirmed",
+                                "sellPercentage": sell_pct,
+                                "status": if fill.is_partial() { "partial" } else { "confirmed" },
                             }).to_string(),
                         ).await;
 
-                        if sentinel.sell_percentage >= 100.0 {
-                            let _ = sqlite::mark_sentinel_triggered(db.pool(), sentinel.id).await;
+                        // Whatever the cap held back or a split left unsold is still owed
+                        let unsold = truncate_to_8_decimals(target_qty - fill.sold);
+                        if unsold > 0.0 {
+                            if let Some(e) = &fill.error {
+                                warn!(
+                                    "Sentinel #{}: split sell of {} stopped with {} unsold ({}), staying armed for the rest",
+                                    sentinel.id, sentinel.symbol, fill.unsold, e
+                                );
+                                trigger_cooldowns.insert(sentinel.symbol.clone(), chrono::Utc::now().timestamp() + FAILED_COOLDOWN_SECS);
+                            } else {
+                                info!("Sentinel #{} stays armed for the {} {} left after a capped sell", sentinel.id, unsold, sentinel.symbol);
+                            }
+                            let _ = sqlite::set_sentinel_unsold(db.pool(), sentinel.id, &trigger_type, unsold).await;
                         } else {
-                            let _ = sqlite::rearm_sentinel(db.pool(), sentinel.id, current_price).await;
-                            info!("Sentinel #{} re-armed after partial sell ({:.0}%) — new entry price: {}", sentinel.id, sentinel.sell_percentage, current_price);
+                            match after_sell(sentinel, &trigger, sell_pct) {
+                                AfterSell::Triggered => {
+                                    let _ = sqlite::mark_sentinel_triggered(db.pool(), sentinel.id).await;
+                                }
+                                AfterSell::NextRung => {
+                                    let _ = sqlite::fill_tp_rung(db.pool(), sentinel.id).await;
+                                    info!("Sentinel #{} take-profit rung {} filled, armed for the next", sentinel.id, trigger.tp_rung.map_or(0, |r| r + 1));
+                                }
+                                AfterSell::Rearm => {
+                                    let _ = sqlite::rearm_sentinel(db.pool(), sentinel.id, current_price).await;
+                                    info!("Sentinel #{} re-armed after partial sell ({:.0}%) — new entry price: {}", sentinel.id, sell_pct, current_price);
+                                }
+                            }
                         }
                     }
                     Err(e) => {
                         let error_str = e.to_string();
-                        let is_rate_limited = error_str.contains("429") || error_str.contains("Rate limit");
+                        let rate_limited = is_rate_limited(&error_str);
                         let is_pool_limit = error_str.contains("99.5%") || error_str.contains("pool tokens");
                         let is_zero_balance = error_str.contains("Insufficient coins") || error_str.contains("have 0");
 
-                        if is_rate_limited {
+                        if rate_limited {
+                            app_handle.state::<AppState>().api_pressure.record_rate_limit();
                             warn!("Sentinel #{}: rate-limited for {}, will retry next tick", sentinel.id, sentinel.symbol);
                             trigger_cooldowns.insert(sentinel.symbol.clone(), chrono::Utc::now().timestamp() + FAILED_COOLDOWN_SECS);
                         } else if is_zero_balance {
@@ -832,15 +1158,16 @@ async fn run_sentinel_checks(
                         } else if is_pool_limit {
                             warn!("Sentinel #{}: pool limit hit for {}, will retry with smaller amount", sentinel.id, sentinel.symbol);
                         } else {
-                            let fail_count = sell_failures.entry(sentinel.id).or_insert(0);
-                            *fail_count += 1;
+                            backing_off = true;
+                            let max_failures = sell_retries.policy.max_failures;
+                            let fail_count = sell_retries.record_failure(sentinel.id, chrono::Utc::now().timestamp());
                             error!(
                                 "Sentinel #{} sell FAILED for {} (attempt {}/{}): {}",
-                                sentinel.id, sentinel.symbol, fail_count, MAX_SELL_FAILURES, e
+                                sentinel.id, sentinel.symbol, fail_count, max_failures, e
                             );
 
-                            if *fail_count >= MAX_SELL_FAILURES {
-                                warn!("Sentinel #{} for {} deactivated after {} consecutive failures", sentinel.id, sentinel.symbol, MAX_SELL_FAILURES);
+                            if fail_count >= max_failures {
+                                warn!("Sentinel #{} for {} deactivated after {} consecutive failures", sentinel.id, sentinel.symbol, max_failures);
                                 let _ = sqlite::set_sentinel_active(db.pool(), sentinel.id, false).await;
                             }
                         }
@@ -860,14 +1187,14 @@ async fn run_sentinel_checks(
                                 "triggerPrice": trigger.trigger_price,
                                 "currentPrice": current_price,
                                 "error": e,
-                                "failureCount": sell_failures.get(&sentinel.id).copied().unwrap_or(0),
-                                "isRateLimited": is_rate_limited,
+                                "failureCount": sell_retries.failures(sentinel.id),
+                                "isRateLimited": rate_limited,
                             }).to_string(),
                         ).await;
                     }
                 }
 
-                if !trigger_cooldowns.contains_key(&sentinel.symbol) {
+                if !backing_off && !trigger_cooldowns.contains_key(&sentinel.symbol) {
                     trigger_cooldowns.insert(sentinel.symbol.clone(), chrono::Utc::now().timestamp() + TRIGGER_COOLDOWN_SECS);
                 }
             }
@@ -884,6 +1211,221 @@ async fn run_sentinel_checks(
     })
 }
 
+/// Log a trigger the monitor is about to act on in the replayable decision log
+async fn record_trigger(
+    db: &rugplay_persistence::Database,
+    sentinel: &sqlite::SentinelRow,
+    price: f64,
+    prices: &SentinelPrices,
+    trigger_type: &str,
+) {
+    let config = SentinelDecisionConfig {
+        min_liquidity_usd: prices.min_liquidity_usd,
+        ..SentinelDecisionConfig::from_row(sentinel)
+    };
+    let input = SentinelDecisionInput {
+        sentinel: sentinel.clone(),
+        price,
+        liquidity_usd: prices.liquidity.get(&sentinel.symbol).copied(),
+    };
+    let action = DecisionAction::Sell { symbol: sentinel.symbol.clone(), trigger: trigger_type.to_string() };
+    decision_log::record_decision::<SentinelDecision>(db, &config, &input, &action).await;
+}
+
+/// Load the persisted per-tick sentinel sell cap
+async fn load_max_sells_per_tick(app_handle: &tauri::AppHandle) -> Option<u32> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'sentinel_max_sells_per_tick'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|v| v.parse().ok())
+}
+
+/// Load the persisted sentinel sell retry policy
+async fn load_sell_retry_policy(app_handle: &tauri::AppHandle) -> Option<SellRetryPolicy> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'sentinel_sell_retry'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|v| serde_json::from_str::<SellRetryPolicy>(&v).ok())
+    .filter(|policy| policy.validate().is_ok())
+}
+
+/// Persist the sentinel sell retry policy
+pub async fn save_sell_retry_policy(app_handle: &tauri::AppHandle, policy: &SellRetryPolicy) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let Ok(json) = serde_json::to_string(policy) else { return };
+
+    let _ = sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('sentinel_sell_retry', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(json)
+    .execute(db.pool())
+    .await;
+}
+
+/// Load the persisted default sentinel price source
+async fn load_price_source(app_handle: &tauri::AppHandle) -> Option<PriceSource> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'sentinel_price_source'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|v| PriceSource::parse(&v))
+}
+
+/// Load the persisted minimum hold liquidity
+async fn load_min_liquidity_usd(app_handle: &tauri::AppHandle) -> Option<f64> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'sentinel_min_liquidity_usd'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|v| v.parse().ok())
+    .filter(|v: &f64| v.is_finite() && *v >= 0.0)
+}
+
+/// Persist the minimum hold liquidity
+pub async fn save_min_liquidity_usd(app_handle: &tauri::AppHandle, min: f64) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+
+    let _ = sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('sentinel_min_liquidity_usd', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(min.to_string())
+    .execute(db.pool())
+    .await;
+}
+
+/// Load the persisted take-profit gap policy
+async fn load_tp_gap_policy(app_handle: &tauri::AppHandle) -> Option<TpGapPolicy> {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let db = db_guard.as_ref()?;
+
+    sqlx::query_scalar::<sqlx::Sqlite, String>(
+        "SELECT value FROM settings WHERE key = 'sentinel_tp_gap'",
+    )
+    .fetch_optional(db.pool())
+    .await
+    .ok()
+    .flatten()
+    .and_then(|v| serde_json::from_str::<TpGapPolicy>(&v).ok())
+    .filter(|policy| policy.validate().is_ok())
+}
+
+/// Persist the take-profit gap policy
+pub async fn save_tp_gap_policy(app_handle: &tauri::AppHandle, policy: &TpGapPolicy) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let Ok(json) = serde_json::to_string(policy) else { return };
+
+    let _ = sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('sentinel_tp_gap', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(json)
+    .execute(db.pool())
+    .await;
+}
+
+/// Persist the default sentinel price source
+pub async fn save_price_source(app_handle: &tauri::AppHandle, source: PriceSource) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+
+    let _ = sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('sentinel_price_source', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(source.as_str())
+    .execute(db.pool())
+    .await;
+}
+
+/// Fetch AMM mark prices for every symbol whose sentinel evaluates against
+/// the mark (or has a take-profit the gap policy watches), and pool
+/// liquidity for every symbol when the liquidity exit is on. Both come from
+/// the same coin fetch, which skips the cache: a stop must not fire, or
+/// miss, on a quote up to a cache lifetime old. Coins that fail to load
+/// fall back to the last-trade price and skip the liquidity check.
+pub async fn fetch_sentinel_prices(
+    client: &RugplayClient,
+    sentinels: &[&sqlite::SentinelRow],
+    source: PriceSource,
+    min_liquidity_usd: f64,
+    tp_gap: TpGapPolicy,
+) -> SentinelPrices {
+    let mut prices = SentinelPrices { source, min_liquidity_usd, tp_gap, ..Default::default() };
+    let mut fetched = std::collections::HashSet::new();
+    for sentinel in sentinels {
+        let wants_mark = PriceSource::for_sentinel(sentinel, source) == PriceSource::Mark
+            || (tp_gap.needs_spot() && sentinel.take_profit_pct.is_some());
+        if !(wants_mark || min_liquidity_usd > 0.0) || !fetched.insert(sentinel.symbol.clone()) {
+            continue;
+        }
+        match client.get_coin_within(&sentinel.symbol, Duration::ZERO).await {
+            Ok(coin) => {
+                if let Some(mark) = amm_spot_price(coin.pool_coin_amount, coin.pool_base_currency_amount) {
+                    prices.marks.insert(sentinel.symbol.clone(), mark);
+                }
+                prices.liquidity.insert(sentinel.symbol.clone(), coin.pool_base_currency_amount);
+            }
+            Err(e) => debug!("Sentinel: could not load {} ({}), using last trade", sentinel.symbol, e),
+        }
+    }
+    prices
+}
+
+/// Persist the per-tick sentinel sell cap
+pub async fn save_max_sells_per_tick(app_handle: &tauri::AppHandle, max: u32) {
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+
+    let _ = sqlx::query(
+        "INSERT INTO settings (key, value) VALUES ('sentinel_max_sells_per_tick', ?1)
+         ON CONFLICT(key) DO UPDATE SET value = ?1",
+    )
+    .bind(max.to_string())
+    .execute(db.pool())
+    .await;
+}
+
 /// Automatically sync sentinels with the current portfolio.
 /// Removes sentinels for coins no longer held, adds default sentinels for new holdings.
 async fn auto_sync_sentinels(
@@ -898,32 +1440,13 @@ async fn auto_sync_sentinels(
     let db_guard = state.db.read().await;
     let db = db_guard.as_ref().ok_or("Database not initialized")?;
 
-    let settings_json: Option<String> = sqlx::query_scalar(
-        "SELECT value FROM settings WHERE key = 'app_settings'"
-    )
-    .fetch_optional(db.pool())
-    .await
-    .map_err(|e| e.to_string())?;
-
-    let (default_sl, default_tp, default_ts, default_sell, blacklist) = match settings_json {
-        Some(ref j) => {
-            if let Ok(settings) = serde_json::from_str::<serde_json::Value>(j) {
-                let sd = &settings["sentinelDefaults"];
-                let sl = sd["stopLossPct"].as_f64();
-                let tp = sd["takeProfitPct"].as_f64();
-                let ts = sd["trailingStopPct"].as_f64();
-                let sell = sd["sellPercentage"].as_f64().unwrap_or(100.0);
-                let bl: Vec<String> = settings["blacklistedCoins"]
-                    .as_array()
-                    .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
-                    .unwrap_or_default();
-                (sl, tp, ts, sell, bl)
-            } else {
-                (Some(-10.0), Some(50.0), None, 100.0, Vec::new())
-            }
-        }
-        None => (Some(-10.0), Some(50.0), None, 100.0, Vec::new()),
-    };
+    let SentinelSyncDefaults {
+        stop_loss_pct: default_sl,
+        take_profit_pct: default_tp,
+        trailing_stop_pct: default_ts,
+        sell_percentage: default_sell,
+        blacklist,
+    } = SentinelSyncDefaults::load(db.pool()).await.map_err(|e| e.to_string())?;
 
     let blacklist_set: std::collections::HashSet<&str> = blacklist.iter().map(|s| s.as_str()).collect();
 
@@ -990,24 +1513,13 @@ async fn auto_sync_sentinels(
         // This prevents the scenario where a user re-buys a coin at a low price
         // but the old expensive position drags the avg entry way above current,
         // causing an instant sell.
-        let entry_price = if let Some(sl) = default_sl {
-            if sl < 0.0 {
-                let sl_floor = avg_entry * (1.0 + sl / 100.0);
-                if holding.current_price <= sl_floor && holding.current_price > 0.0 {
-                    info!(
-                        "Auto-sync: using current price {:.8} instead of avg {:.8} for {} (would instantly trigger SL={:.0}%)",
-                        holding.current_price, avg_entry, holding.symbol, sl
-                    );
-                    holding.current_price
-                } else {
-                    avg_entry
-                }
-            } else {
-                avg_entry
-            }
-        } else {
-            avg_entry
-        };
+        let entry_price = guarded_entry_price(avg_entry, holding.current_price, default_sl);
+        if entry_price != avg_entry {
+            info!(
+                "Auto-sync: using current price {:.8} instead of avg {:.8} for {} (would instantly trigger SL={:.0}%)",
+                holding.current_price, avg_entry, holding.symbol, default_sl.unwrap_or_default()
+            );
+        }
 
         if sentinel_symbols.contains(&holding.symbol) {
             // Existing sentinel: sync entry price with portfolio avg if it drifted
@@ -0,0 +1,239 @@
+//! Sentinel Reconcile — one-shot repair of sentinels against the portfolio
+//!
+//! The monitor's auto-sync keeps sentinels and holdings in step on every
+//! tick, but it can be switched off or miss a change. Reconciling is the
+//! manual, thorough pass: every sentinel for a coin that isn't held (open
+//! or triggered), for a blacklisted coin, or duplicating another open one is
+//! removed, and every held coin without an open sentinel gets one with the app's
+//! sentinel defaults, priced with the same instant-trigger guard.
+
+use crate::protective_sentinel::guarded_entry_price;
+use rugplay_core::{CoinHolding, Result};
+use rugplay_persistence::sqlite;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+
+/// Sentinel defaults and blacklist from the app settings, as auto-sync uses them
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentinelSyncDefaults {
+    pub stop_loss_pct: Option<f64>,
+    pub take_profit_pct: Option<f64>,
+    pub trailing_stop_pct: Option<f64>,
+    pub sell_percentage: f64,
+    pub blacklist: Vec<String>,
+}
+
+impl Default for SentinelSyncDefaults {
+    fn default() -> Self {
+        Self {
+            stop_loss_pct: Some(-10.0),
+            take_profit_pct: Some(50.0),
+            trailing_stop_pct: None,
+            sell_percentage: 100.0,
+            blacklist: Vec::new(),
+        }
+    }
+}
+
+impl SentinelSyncDefaults {
+    /// Read the defaults from the app settings; unreadable settings give [`Self::default`]
+    pub async fn load(pool: &SqlitePool) -> Result<Self> {
+        let settings_json: Option<String> = sqlx::query_scalar(
+            "SELECT value FROM settings WHERE key = 'app_settings'",
+        )
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| rugplay_core::Error::DatabaseError(e.to_string()))?;
+
+        let Some(settings) = settings_json.and_then(|j| serde_json::from_str::<serde_json::Value>(&j).ok()) else {
+            return Ok(Self::default());
+        };
+        let sd = &settings["sentinelDefaults"];
+        Ok(Self {
+            stop_loss_pct: sd["stopLossPct"].as_f64(),
+            take_profit_pct: sd["takeProfitPct"].as_f64(),
+            trailing_stop_pct: sd["trailingStopPct"].as_f64(),
+            sell_percentage: sd["sellPercentage"].as_f64().unwrap_or(100.0),
+            blacklist: settings["blacklistedCoins"]
+                .as_array()
+                .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// What a reconcile changed
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileSummary {
+    /// Coins in the portfolio
+    pub held: u32,
+    /// Open sentinels for coins no longer held
+    pub orphans_removed: u64,
+    /// Triggered sentinels for coins no longer held
+    pub triggered_purged: u64,
+    pub blacklisted_removed: u64,
+    /// Extra open sentinels for the same coin
+    pub duplicates_removed: u64,
+    /// Coins that were held without a sentinel and now have one
+    pub created: Vec<String>,
+    /// Of `created`, coins given the market price as entry so the stop loss
+    /// wouldn't fire straight away
+    pub guarded: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Bring the profile's sentinels in line with `holdings`
+pub async fn reconcile(
+    pool: &SqlitePool,
+    profile_id: i64,
+    holdings: &[CoinHolding],
+    defaults: &SentinelSyncDefaults,
+) -> Result<ReconcileSummary> {
+    let mut summary = ReconcileSummary { held: holdings.len() as u32, ..Default::default() };
+    let held: Vec<String> = holdings.iter().map(|h| h.symbol.clone()).collect();
+
+    summary.orphans_removed = sqlite::cleanup_stale_sentinels(pool, profile_id, &held).await?;
+    summary.triggered_purged = sqlite::cleanup_triggered_sentinels(pool, profile_id, &held).await?;
+    summary.blacklisted_removed = sqlite::remove_blacklisted_sentinels(pool, profile_id, &defaults.blacklist).await?;
+    summary.duplicates_removed = sqlite::deduplicate_sentinels(pool).await?;
+
+    // A triggered sentinel no longer protects anything: a coin held again needs a new one
+    let covered: HashSet<String> = sqlite::get_sentinels(pool, profile_id)
+        .await?
+        .into_iter()
+        .filter(|s| s.triggered_at.is_none())
+        .map(|s| s.symbol)
+        .collect();
+    let blacklist: HashSet<&str> = defaults.blacklist.iter().map(String::as_str).collect();
+
+    for holding in holdings {
+        if covered.contains(&holding.symbol) || blacklist.contains(holding.symbol.as_str()) {
+            continue;
+        }
+        let avg_entry = if holding.avg_purchase_price > 0.0 {
+            holding.avg_purchase_price
+        } else {
+            holding.current_price
+        };
+        let entry_price = guarded_entry_price(avg_entry, holding.current_price, defaults.stop_loss_pct);
+
+        match sqlite::upsert_sentinel(
+            pool,
+            profile_id,
+            &holding.symbol,
+            defaults.stop_loss_pct,
+            defaults.take_profit_pct,
+            defaults.trailing_stop_pct,
+            defaults.sell_percentage,
+            entry_price,
+        )
+        .await
+        {
+            Ok(_) => {
+                if entry_price != avg_entry {
+                    summary.guarded.push(holding.symbol.clone());
+                }
+                summary.created.push(holding.symbol.clone());
+            }
+            Err(e) => summary.errors.push(format!("{}: {}", holding.symbol, e)),
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_persistence::test_support::db_with_profile;
+
+    fn holding(symbol: &str, avg_purchase_price: f64, current_price: f64) -> CoinHolding {
+        serde_json::from_value(serde_json::json!({
+            "symbol": symbol,
+            "quantity": 100.0,
+            "currentPrice": current_price,
+            "value": 100.0 * current_price,
+            "avgPurchasePrice": avg_purchase_price,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_orphaned_sentinels_are_removed() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+        let held_id = sqlite::create_sentinel(pool, 1, "KEEP", Some(-10.0), None, None, 100.0, 1.0).await.unwrap();
+        sqlite::create_sentinel(pool, 1, "GONE", Some(-10.0), None, None, 100.0, 1.0).await.unwrap();
+        let fired = sqlite::create_sentinel(pool, 1, "SOLD", Some(-10.0), None, None, 100.0, 1.0).await.unwrap();
+        sqlx::query("UPDATE sentinels SET triggered_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(fired)
+            .execute(pool)
+            .await
+            .unwrap();
+        sqlite::create_sentinel(pool, 1, "BANNED", Some(-10.0), None, None, 100.0, 1.0).await.unwrap();
+
+        let defaults = SentinelSyncDefaults { blacklist: vec!["BANNED".to_string()], ..Default::default() };
+        let holdings = [holding("KEEP", 1.0, 1.0), holding("BANNED", 1.0, 1.0)];
+        let summary = reconcile(pool, 1, &holdings, &defaults).await.unwrap();
+
+        assert_eq!(summary.orphans_removed, 1);
+        assert_eq!(summary.triggered_purged, 1);
+        assert_eq!(summary.blacklisted_removed, 1);
+        assert!(summary.created.is_empty(), "{:?}", summary.created);
+        let left = sqlite::get_sentinels(pool, 1).await.unwrap();
+        assert_eq!(left.len(), 1);
+        assert_eq!(left[0].id, held_id);
+    }
+
+    #[tokio::test]
+    async fn test_missing_sentinels_are_created_with_guarded_defaults() {
+        let db = db_with_profile().await;
+        let pool = db.pool();
+        assert_eq!(SentinelSyncDefaults::load(pool).await.unwrap(), SentinelSyncDefaults::default());
+        sqlx::query("INSERT INTO settings (key, value) VALUES ('app_settings', ?)")
+            .bind(r#"{"sentinelDefaults":{"stopLossPct":-20,"takeProfitPct":80,"sellPercentage":50}}"#)
+            .execute(pool)
+            .await
+            .unwrap();
+        let defaults = SentinelSyncDefaults::load(pool).await.unwrap();
+        sqlite::create_sentinel(pool, 1, "HAVE", Some(-5.0), None, None, 100.0, 2.0).await.unwrap();
+        // Fired earlier and bought back since: the old sentinel doesn't cover it
+        let fired = sqlite::create_sentinel(pool, 1, "BACK", Some(-5.0), None, None, 100.0, 3.0).await.unwrap();
+        sqlx::query("UPDATE sentinels SET triggered_at = CURRENT_TIMESTAMP WHERE id = ?")
+            .bind(fired)
+            .execute(pool)
+            .await
+            .unwrap();
+
+        // DRAG's average sits above its stop-loss floor: entry at the market instead
+        let holdings = [
+            holding("HAVE", 2.0, 2.0),
+            holding("NEW", 1.0, 1.1),
+            holding("DRAG", 4.0, 2.0),
+            holding("BACK", 1.5, 1.5),
+        ];
+        let summary = reconcile(pool, 1, &holdings, &defaults).await.unwrap();
+        assert_eq!(summary.created, vec!["NEW".to_string(), "DRAG".to_string(), "BACK".to_string()]);
+        assert_eq!(summary.guarded, vec!["DRAG".to_string()]);
+
+        let sentinels = sqlite::get_sentinels(pool, 1).await.unwrap();
+        let by_symbol = |symbol: &str| sentinels.iter().find(|s| s.symbol == symbol).unwrap();
+        assert_eq!(by_symbol("NEW").entry_price, 1.0);
+        assert_eq!(by_symbol("NEW").stop_loss_pct, Some(-20.0));
+        assert_eq!(by_symbol("NEW").take_profit_pct, Some(80.0));
+        assert_eq!(by_symbol("NEW").sell_percentage, 50.0);
+        assert_eq!(by_symbol("DRAG").entry_price, 2.0);
+        let back: Vec<_> = sentinels.iter().filter(|s| s.symbol == "BACK" && s.triggered_at.is_none()).collect();
+        assert_eq!(back.len(), 1);
+        assert_eq!(back[0].entry_price, 1.5);
+        // An existing sentinel keeps its own settings
+        assert_eq!(by_symbol("HAVE").stop_loss_pct, Some(-5.0));
+
+        // A second pass has nothing left to do
+        let again = reconcile(pool, 1, &holdings, &defaults).await.unwrap();
+        assert!(again.created.is_empty());
+        assert_eq!(sqlite::get_sentinels(pool, 1).await.unwrap().len(), 5);
+    }
+}
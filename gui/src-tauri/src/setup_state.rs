@@ -0,0 +1,171 @@
+//! First-Run Setup State
+//!
+//! Inspects the backend (database, profiles, token encryption, saved
+//! configs) and reports which onboarding steps are still open, so the UI can
+//! drive setup without guessing from individual commands.
+
+use serde::{Deserialize, Serialize};
+
+/// One onboarding step, in the order the UI should walk them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SetupStep {
+    /// The SQLite database has not finished opening
+    InitDatabase,
+    /// No profile has been added yet
+    AddProfile,
+    /// Profiles exist but none is selected
+    SelectProfile,
+    /// The active profile's token can't be decrypted on this machine
+    UpdateToken,
+    /// Sentinel defaults and blacklist were never saved
+    ConfigureSettings,
+    /// Risk limits were never saved (executor runs on built-in defaults)
+    ReviewRiskLimits,
+}
+
+impl SetupStep {
+    /// Required steps block the dashboard; the rest are recommendations
+    pub fn is_required(self) -> bool {
+        matches!(
+            self,
+            SetupStep::InitDatabase | SetupStep::AddProfile | SetupStep::SelectProfile | SetupStep::UpdateToken
+        )
+    }
+}
+
+/// Raw observations gathered from the backend
+#[derive(Debug, Clone, Default)]
+pub struct SetupFacts {
+    pub database_ready: bool,
+    pub profile_count: usize,
+    pub active_profile_id: Option<i64>,
+    /// `Some(false)` when the active token exists but fails to decrypt
+    pub active_token_readable: Option<bool>,
+    pub has_app_settings: bool,
+    pub has_risk_limits: bool,
+}
+
+/// Status of a single step
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupStepStatus {
+    pub step: SetupStep,
+    pub required: bool,
+    pub done: bool,
+}
+
+/// What the onboarding UI needs to know
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetupState {
+    pub database_ready: bool,
+    pub profile_count: usize,
+    pub active_profile_id: Option<i64>,
+    /// All required steps are done
+    pub ready: bool,
+    /// Required and recommended steps are all done
+    pub complete: bool,
+    /// First open step, if any
+    pub next_step: Option<SetupStep>,
+    pub steps: Vec<SetupStepStatus>,
+}
+
+impl SetupState {
+    pub fn from_facts(facts: &SetupFacts) -> Self {
+        let db = facts.database_ready;
+        let has_profile = db && facts.profile_count > 0;
+        let has_active = has_profile && facts.active_profile_id.is_some();
+        let token_ok = has_active && facts.active_token_readable.unwrap_or(false);
+
+        let steps: Vec<SetupStepStatus> = [
+            (SetupStep::InitDatabase, db),
+            (SetupStep::AddProfile, has_profile),
+            (SetupStep::SelectProfile, has_active),
+            (SetupStep::UpdateToken, token_ok),
+            (SetupStep::ConfigureSettings, db && facts.has_app_settings),
+            (SetupStep::ReviewRiskLimits, db && facts.has_risk_limits),
+        ]
+        .into_iter()
+        .map(|(step, done)| SetupStepStatus { step, required: step.is_required(), done })
+        .collect();
+
+        let ready = steps.iter().filter(|s| s.required).all(|s| s.done);
+        let complete = steps.iter().all(|s| s.done);
+        let next_step = steps.iter().find(|s| !s.done).map(|s| s.step);
+
+        Self {
+            database_ready: db,
+            profile_count: facts.profile_count,
+            active_profile_id: facts.active_profile_id,
+            ready,
+            complete,
+            next_step,
+            steps,
+        }
+    }
+
+    pub fn is_done(&self, step: SetupStep) -> bool {
+        self.steps.iter().any(|s| s.step == step && s.done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configured() -> SetupFacts {
+        SetupFacts {
+            database_ready: true,
+            profile_count: 1,
+            active_profile_id: Some(1),
+            active_token_readable: Some(true),
+            has_app_settings: true,
+            has_risk_limits: true,
+        }
+    }
+
+    #[test]
+    fn test_cold_start_reports_everything_missing() {
+        let state = SetupState::from_facts(&SetupFacts::default());
+        assert!(!state.ready);
+        assert!(!state.complete);
+        assert_eq!(state.next_step, Some(SetupStep::InitDatabase));
+        assert!(state.steps.iter().all(|s| !s.done));
+
+        // DB open but nothing saved yet
+        let state = SetupState::from_facts(&SetupFacts { database_ready: true, ..Default::default() });
+        assert_eq!(state.next_step, Some(SetupStep::AddProfile));
+        assert!(!state.is_done(SetupStep::ConfigureSettings));
+    }
+
+    #[test]
+    fn test_partial_setups() {
+        // Profiles saved but none active (e.g. after logout)
+        let state = SetupState::from_facts(&SetupFacts { active_profile_id: None, active_token_readable: None, ..configured() });
+        assert_eq!(state.next_step, Some(SetupStep::SelectProfile));
+        assert!(!state.ready);
+
+        // Token encrypted on another machine
+        let state = SetupState::from_facts(&SetupFacts { active_token_readable: Some(false), ..configured() });
+        assert_eq!(state.next_step, Some(SetupStep::UpdateToken));
+        assert!(!state.ready);
+
+        // Logged in, configs still default: usable but not complete
+        let state = SetupState::from_facts(&SetupFacts { has_app_settings: false, has_risk_limits: false, ..configured() });
+        assert!(state.ready);
+        assert!(!state.complete);
+        assert_eq!(state.next_step, Some(SetupStep::ConfigureSettings));
+
+        let state = SetupState::from_facts(&SetupFacts { has_risk_limits: false, ..configured() });
+        assert_eq!(state.next_step, Some(SetupStep::ReviewRiskLimits));
+    }
+
+    #[test]
+    fn test_fully_configured() {
+        let state = SetupState::from_facts(&configured());
+        assert!(state.ready);
+        assert!(state.complete);
+        assert_eq!(state.next_step, None);
+    }
+}
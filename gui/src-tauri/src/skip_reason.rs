@@ -0,0 +1,420 @@
+//! Skip Reasons — why the dip buyer or sniper passed on a coin
+//!
+//! Skips used to be free-form strings, which the UI could show but not
+//! group or count. Each skip is now a `SkipReason` carrying the value that
+//! failed and the threshold it was held to; the human-readable message is
+//! derived from it, so the two can't drift apart.
+
+use crate::dipbuyer_signals::DipAnalysis;
+use rugplay_core::{checked_ratio, CoinDetails, MarketCoin};
+use serde::{Deserialize, Serialize};
+
+/// A structured skip reason. `actual` is the value that failed the check,
+/// `threshold` the configured limit it was compared against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SkipReason {
+    /// Sell value (USD) under the tier's minimum
+    SellBelowTierMin { actual: f64, threshold: f64 },
+    /// 24h volume (USD) under the minimum
+    LowVolume { actual: f64, threshold: f64 },
+    /// Market cap (USD) under the minimum
+    LowMarketCap { actual: f64, threshold: f64 },
+    /// Market cap (USD) over the maximum
+    HighMarketCap { actual: f64, threshold: f64 },
+    /// The API didn't report a market cap to check against
+    MarketCapUnknown,
+    /// 24h change (%) already below the allowed drop
+    DroppedTooMuch { actual: f64, threshold: f64 },
+    /// Position share of the portfolio (%) after the buy over the maximum
+    PositionTooLarge { actual: f64, threshold: f64 },
+    /// Holdings couldn't be fetched, so the position size is unverified
+    PortfolioUnavailable { error: String },
+    /// A scoring signal rejected the coin outright
+    SignalReject { detail: String },
+    /// Confidence score under the minimum; `signals` summarizes the scores
+    LowConfidence { actual: f64, threshold: f64, signals: String },
+    /// Coin age (seconds) over the maximum
+    CoinTooOld { actual: f64, threshold: f64 },
+    /// Coin age (seconds) still inside the creator cooldown
+    CreatorCooldown { actual: f64, threshold: f64 },
+    /// Coin created by a blacklisted user
+    BlacklistedCreator { creator: String },
+    /// Spend (USD) including this buy over the daily cap
+    DailySpendLimit { actual: f64, threshold: f64 },
+    /// Coin risk score (0–100) over the module's limit
+    HighRisk { actual: f64, threshold: f64 },
+    /// The user's entry script vetoed the buy, failed or timed out
+    ScriptReject { detail: String },
+}
+
+impl SkipReason {
+    /// Name of the check that failed, as in the serialized `kind` tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::SellBelowTierMin { .. } => "sellBelowTierMin",
+            Self::LowVolume { .. } => "lowVolume",
+            Self::LowMarketCap { .. } => "lowMarketCap",
+            Self::HighMarketCap { .. } => "highMarketCap",
+            Self::MarketCapUnknown => "marketCapUnknown",
+            Self::DroppedTooMuch { .. } => "droppedTooMuch",
+            Self::PositionTooLarge { .. } => "positionTooLarge",
+            Self::PortfolioUnavailable { .. } => "portfolioUnavailable",
+            Self::SignalReject { .. } => "signalReject",
+            Self::LowConfidence { .. } => "lowConfidence",
+            Self::CoinTooOld { .. } => "coinTooOld",
+            Self::CreatorCooldown { .. } => "creatorCooldown",
+            Self::BlacklistedCreator { .. } => "blacklistedCreator",
+            Self::DailySpendLimit { .. } => "dailySpendLimit",
+            Self::HighRisk { .. } => "highRisk",
+            Self::ScriptReject { .. } => "scriptReject",
+        }
+    }
+
+    /// Human-readable message for logs and the UI
+    pub fn message(&self) -> String {
+        match self {
+            Self::SellBelowTierMin { actual, threshold } => {
+                format!("Sell ${:.0} below tier min ${:.0}", actual, threshold)
+            }
+            Self::LowVolume { actual, threshold } => format!("Low volume (${:.0} < ${:.0})", actual, threshold),
+            Self::LowMarketCap { actual, threshold } => {
+                format!("Low market cap (${:.0} < ${:.0})", actual, threshold)
+            }
+            Self::HighMarketCap { actual, threshold } => {
+                format!("High market cap (${:.0} > ${:.0})", actual, threshold)
+            }
+            Self::MarketCapUnknown => "Market cap unknown".to_string(),
+            Self::DroppedTooMuch { actual, threshold } => {
+                format!("Already dropped too much ({:.1}% < {:.1}%)", actual, threshold)
+            }
+            Self::PositionTooLarge { actual, threshold } => {
+                format!("Position {:.1}% would exceed max {:.1}%", actual, threshold)
+            }
+            Self::PortfolioUnavailable { error } => format!("Portfolio unavailable, position unverified ({})", error),
+            Self::SignalReject { detail } => detail.clone(),
+            Self::LowConfidence { actual, threshold, signals } => {
+                format!("Low confidence {:.2} < {:.2} ({})", actual, threshold, signals)
+            }
+            Self::CoinTooOld { actual, threshold } => format!("Coin too old ({:.0}s > {:.0}s)", actual, threshold),
+            Self::CreatorCooldown { actual, threshold } => {
+                format!("In creator cooldown (age {:.0}s < {:.0}s)", actual, threshold)
+            }
+            Self::BlacklistedCreator { creator } => format!("Blacklisted creator {}", creator),
+            Self::DailySpendLimit { actual, threshold } => {
+                format!("Would exceed daily spend (${:.2} > ${:.2})", actual, threshold)
+            }
+            Self::HighRisk { actual, threshold } => format!("Risk score {:.0} > {:.0}", actual, threshold),
+            Self::ScriptReject { detail } => format!("Entry script: {}", detail),
+        }
+    }
+}
+
+/// Hard gates the dip buyer applies before scoring (tier already resolved)
+#[derive(Debug, Clone, Copy)]
+pub struct DipGates {
+    pub min_sell_value_usd: f64,
+    pub min_volume_24h: f64,
+    pub min_market_cap: f64,
+    /// 0 = no maximum
+    pub max_market_cap: f64,
+    /// Negative to enable (e.g. -50 skips coins already down more than 50%)
+    pub max_price_drop_pct: f64,
+}
+
+/// First hard gate a dip candidate fails, if any
+pub fn dip_gate_skip(gates: &DipGates, sell_value: f64, coin: &CoinDetails) -> Option<SkipReason> {
+    if sell_value < gates.min_sell_value_usd {
+        return Some(SkipReason::SellBelowTierMin { actual: sell_value, threshold: gates.min_sell_value_usd });
+    }
+    dip_market_skips(gates, coin.market_cap, coin.volume_24h, coin.change_24h).into_iter().next()
+}
+
+/// Every dip gate a coin's market numbers fail, in the order the dip buyer
+/// checks them. The sell value gate depends on the trade, not the coin, and
+/// isn't part of this. A coin without a market cap always fails: the tiers
+/// and the volume signal are measured against it.
+pub fn dip_market_skips(gates: &DipGates, market_cap: Option<f64>, volume_24h: f64, change_24h: f64) -> Vec<SkipReason> {
+    let mut skips = Vec::new();
+    if volume_24h < gates.min_volume_24h {
+        skips.push(SkipReason::LowVolume { actual: volume_24h, threshold: gates.min_volume_24h });
+    }
+    match market_cap {
+        None => skips.push(SkipReason::MarketCapUnknown),
+        Some(market_cap) if market_cap < gates.min_market_cap => {
+            skips.push(SkipReason::LowMarketCap { actual: market_cap, threshold: gates.min_market_cap });
+        }
+        Some(market_cap) if gates.max_market_cap > 0.0 && market_cap > gates.max_market_cap => {
+            skips.push(SkipReason::HighMarketCap { actual: market_cap, threshold: gates.max_market_cap });
+        }
+        Some(_) => {}
+    }
+    if gates.max_price_drop_pct < 0.0 && change_24h < gates.max_price_drop_pct {
+        skips.push(SkipReason::DroppedTooMuch { actual: change_24h, threshold: gates.max_price_drop_pct });
+    }
+    skips
+}
+
+/// Position-size gate. `portfolio` is the total portfolio value and the
+/// value already held in the coin, or the error fetching them. When the
+/// fetch fails the buy is skipped unless `proceed_without_portfolio` is set;
+/// an empty portfolio has nothing to measure against and passes.
+pub fn dip_position_skip(
+    portfolio: Result<(f64, f64), String>,
+    buy_amount: f64,
+    max_position_pct: f64,
+    proceed_without_portfolio: bool,
+) -> Option<SkipReason> {
+    let (total_value, existing_value) = match portfolio {
+        Ok(values) => values,
+        Err(_) if proceed_without_portfolio => return None,
+        Err(error) => return Some(SkipReason::PortfolioUnavailable { error }),
+    };
+    if total_value <= 0.0 {
+        return None;
+    }
+    // Unusable ratio counts as over the limit
+    let position_pct = checked_ratio(existing_value + buy_amount, total_value).map_or(f64::INFINITY, |r| r * 100.0);
+    (position_pct > max_position_pct)
+        .then_some(SkipReason::PositionTooLarge { actual: position_pct, threshold: max_position_pct })
+}
+
+/// Why a scored dip is not bought: a hard reject or too little confidence
+pub fn dip_decision_skip(analysis: &DipAnalysis, min_confidence_score: f64) -> Option<SkipReason> {
+    if analysis.hard_reject {
+        let detail = analysis.reject_reason.as_deref().unwrap_or("Signal hard reject");
+        return Some(SkipReason::SignalReject { detail: detail.to_string() });
+    }
+    if analysis.confidence_score < min_confidence_score {
+        let signals = analysis
+            .signals
+            .iter()
+            .map(|s| format!("{}:{:.2}", s.name, s.score))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Some(SkipReason::LowConfidence {
+            actual: analysis.confidence_score,
+            threshold: min_confidence_score,
+            signals,
+        });
+    }
+    None
+}
+
+/// Filters the sniper applies to each new coin
+#[derive(Debug, Clone, Copy)]
+pub struct SniperGates<'a> {
+    /// 0 = no limit
+    pub max_market_cap_usd: f64,
+    /// 0 = no limit
+    pub max_coin_age_secs: u64,
+    /// 0 = no creator cooldown
+    pub min_coin_age_secs: u64,
+    pub blacklisted_creators: &'a [String],
+    /// 0 = unlimited
+    pub max_daily_spend_usd: f64,
+    pub buy_amount_usd: f64,
+}
+
+/// First sniper filter a new coin fails, if any. `age_secs` is `None`
+/// when the coin's creation time is unknown, which skips the age checks.
+pub fn sniper_skip(gates: &SniperGates, coin: &MarketCoin, age_secs: Option<i64>, spent_today: f64) -> Option<SkipReason> {
+    sniper_skips(gates, coin, age_secs, spent_today).into_iter().next()
+}
+
+/// Every sniper filter a new coin fails, in the order the sniper checks them.
+/// A coin without a market cap is never sniped, since a buy is logged and
+/// reasoned about by it.
+pub fn sniper_skips(gates: &SniperGates, coin: &MarketCoin, age_secs: Option<i64>, spent_today: f64) -> Vec<SkipReason> {
+    let mut skips = Vec::new();
+    match coin.market_cap {
+        None => skips.push(SkipReason::MarketCapUnknown),
+        Some(market_cap) if gates.max_market_cap_usd > 0.0 && market_cap > gates.max_market_cap_usd => {
+            skips.push(SkipReason::HighMarketCap { actual: market_cap, threshold: gates.max_market_cap_usd });
+        }
+        Some(_) => {}
+    }
+    if let Some(age) = age_secs {
+        if gates.max_coin_age_secs > 0 && age > gates.max_coin_age_secs as i64 {
+            skips.push(SkipReason::CoinTooOld { actual: age as f64, threshold: gates.max_coin_age_secs as f64 });
+        }
+        if gates.min_coin_age_secs > 0 && age < gates.min_coin_age_secs as i64 {
+            skips.push(SkipReason::CreatorCooldown {
+                actual: age as f64,
+                threshold: gates.min_coin_age_secs as f64,
+            });
+        }
+    }
+    if let Some(creator) = coin.creator_name.as_deref() {
+        if gates.blacklisted_creators.iter().any(|b| b.eq_ignore_ascii_case(creator)) {
+            skips.push(SkipReason::BlacklistedCreator { creator: creator.to_string() });
+        }
+    }
+    if gates.max_daily_spend_usd > 0.0 && spent_today + gates.buy_amount_usd > gates.max_daily_spend_usd {
+        skips.push(SkipReason::DailySpendLimit {
+            actual: spent_today + gates.buy_amount_usd,
+            threshold: gates.max_daily_spend_usd,
+        });
+    }
+    skips
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dipbuyer_signals::SignalResult;
+
+    fn coin(market_cap: f64, volume_24h: f64, change_24h: f64) -> CoinDetails {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "symbol": "AAA", "name": "AAA Coin", "currentPrice": 1.0,
+            "marketCap": market_cap, "volume24h": volume_24h, "change24h": change_24h,
+            "poolCoinAmount": 10.0, "poolBaseCurrencyAmount": 10.0
+        }))
+        .unwrap()
+    }
+
+    fn market_coin(market_cap: f64, creator: Option<&str>) -> MarketCoin {
+        MarketCoin {
+            symbol: "NEW".to_string(),
+            name: "New Coin".to_string(),
+            icon: None,
+            current_price: 0.01,
+            market_cap: Some(market_cap),
+            volume_24h: 0.0,
+            change_24h: 0.0,
+            created_at: None,
+            creator_name: creator.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_each_dip_gate_reports_its_values() {
+        let gates = DipGates {
+            min_sell_value_usd: 500.0,
+            min_volume_24h: 1_000.0,
+            min_market_cap: 5_000.0,
+            max_market_cap: 100_000.0,
+            max_price_drop_pct: -50.0,
+        };
+        let ok = coin(10_000.0, 2_000.0, -10.0);
+
+        assert_eq!(
+            dip_gate_skip(&gates, 400.0, &ok),
+            Some(SkipReason::SellBelowTierMin { actual: 400.0, threshold: 500.0 })
+        );
+        assert_eq!(
+            dip_gate_skip(&gates, 600.0, &coin(10_000.0, 900.0, -10.0)),
+            Some(SkipReason::LowVolume { actual: 900.0, threshold: 1_000.0 })
+        );
+        assert_eq!(
+            dip_gate_skip(&gates, 600.0, &coin(4_000.0, 2_000.0, -10.0)),
+            Some(SkipReason::LowMarketCap { actual: 4_000.0, threshold: 5_000.0 })
+        );
+        assert_eq!(
+            dip_gate_skip(&gates, 600.0, &coin(200_000.0, 2_000.0, -10.0)),
+            Some(SkipReason::HighMarketCap { actual: 200_000.0, threshold: 100_000.0 })
+        );
+        let dropped = dip_gate_skip(&gates, 600.0, &coin(10_000.0, 2_000.0, -60.0)).unwrap();
+        assert_eq!(dropped, SkipReason::DroppedTooMuch { actual: -60.0, threshold: -50.0 });
+        assert_eq!(dropped.message(), "Already dropped too much (-60.0% < -50.0%)");
+        assert_eq!(dip_gate_skip(&gates, 600.0, &ok), None);
+
+        // A missing market cap is not a market cap of 0, but it isn't a pass either
+        let unknown = CoinDetails { market_cap: None, ..ok };
+        assert_eq!(dip_gate_skip(&gates, 600.0, &unknown), Some(SkipReason::MarketCapUnknown));
+        let no_caps = DipGates { min_market_cap: 0.0, max_market_cap: 0.0, ..gates };
+        assert_eq!(dip_market_skips(&no_caps, None, 2_000.0, -10.0), vec![SkipReason::MarketCapUnknown]);
+    }
+
+    #[test]
+    fn test_position_gate_fails_closed_unless_told_otherwise() {
+        let fetch_error = || Err("HTTP 503".to_string());
+
+        // Fail-closed (default): no holdings, no buy
+        let skip = dip_position_skip(fetch_error(), 100.0, 5.0, false).unwrap();
+        assert_eq!(skip, SkipReason::PortfolioUnavailable { error: "HTTP 503".to_string() });
+        assert_eq!(skip.message(), "Portfolio unavailable, position unverified (HTTP 503)");
+
+        // Fail-open: buy without the position check
+        assert_eq!(dip_position_skip(fetch_error(), 100.0, 5.0, true), None);
+
+        // With holdings the limit applies either way: $400 + $100 of $5000 is 10%
+        let over = dip_position_skip(Ok((5_000.0, 400.0)), 100.0, 5.0, true).unwrap();
+        assert!(matches!(over, SkipReason::PositionTooLarge { actual, threshold } if actual == 10.0 && threshold == 5.0));
+        assert_eq!(dip_position_skip(Ok((5_000.0, 100.0)), 100.0, 5.0, false), None);
+        assert_eq!(dip_position_skip(Ok((0.0, 0.0)), 100.0, 5.0, false), None);
+    }
+
+    #[test]
+    fn test_dip_decision_skips_keep_their_messages() {
+        let mut analysis = DipAnalysis {
+            symbol: "AAA".to_string(),
+            confidence_score: 0.4,
+            signals: vec![SignalResult {
+                name: "momentum".to_string(),
+                raw_value: 0.0,
+                score: 0.25,
+                weight: 1.0,
+                weighted: 0.25,
+                reason: String::new(),
+            }],
+            hard_reject: false,
+            reject_reason: None,
+            recommended_buy_pct: 0.0,
+            slippage_pct: 0.0,
+            sell_impact_pct: 0.0,
+        };
+        let low = dip_decision_skip(&analysis, 0.6).unwrap();
+        assert!(matches!(low, SkipReason::LowConfidence { actual, threshold, .. } if actual == 0.4 && threshold == 0.6));
+        assert_eq!(low.message(), "Low confidence 0.40 < 0.60 (momentum:0.25)");
+        assert_eq!(dip_decision_skip(&analysis, 0.3), None);
+
+        analysis.hard_reject = true;
+        analysis.reject_reason = Some("Seller is a top holder (whale dump)".to_string());
+        let reject = dip_decision_skip(&analysis, 0.3).unwrap();
+        assert_eq!(reject.message(), "Seller is a top holder (whale dump)");
+    }
+
+    #[test]
+    fn test_each_sniper_filter_reports_its_values() {
+        let blacklist = vec!["Rugger".to_string()];
+        let gates = SniperGates {
+            max_market_cap_usd: 50_000.0,
+            max_coin_age_secs: 300,
+            min_coin_age_secs: 65,
+            blacklisted_creators: &blacklist,
+            max_daily_spend_usd: 3_000.0,
+            buy_amount_usd: 1_000.0,
+        };
+        let fresh = market_coin(10_000.0, Some("alice"));
+
+        assert_eq!(
+            sniper_skip(&gates, &market_coin(60_000.0, None), Some(100), 0.0),
+            Some(SkipReason::HighMarketCap { actual: 60_000.0, threshold: 50_000.0 })
+        );
+        assert_eq!(
+            sniper_skip(&gates, &fresh, Some(301), 0.0),
+            Some(SkipReason::CoinTooOld { actual: 301.0, threshold: 300.0 })
+        );
+        assert_eq!(
+            sniper_skip(&gates, &fresh, Some(30), 0.0),
+            Some(SkipReason::CreatorCooldown { actual: 30.0, threshold: 65.0 })
+        );
+        assert_eq!(
+            sniper_skip(&gates, &market_coin(10_000.0, Some("rugger")), Some(100), 0.0),
+            Some(SkipReason::BlacklistedCreator { creator: "rugger".to_string() })
+        );
+        assert_eq!(
+            sniper_skip(&gates, &fresh, Some(100), 2_500.0),
+            Some(SkipReason::DailySpendLimit { actual: 3_500.0, threshold: 3_000.0 })
+        );
+        // Unknown age skips the age checks only
+        assert_eq!(sniper_skip(&gates, &fresh, None, 0.0), None);
+        assert_eq!(sniper_skip(&gates, &fresh, Some(100), 2_000.0), None);
+        let no_cap = MarketCoin { market_cap: None, ..fresh };
+        assert_eq!(sniper_skip(&gates, &no_cap, Some(100), 0.0), Some(SkipReason::MarketCapUnknown));
+
+        let json = serde_json::to_value(SkipReason::CoinTooOld { actual: 301.0, threshold: 300.0 }).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "coinTooOld", "actual": 301.0, "threshold": 300.0 }));
+    }
+}
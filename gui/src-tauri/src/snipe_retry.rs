@@ -0,0 +1,93 @@
+//! Snipe Retry — coins the sniper will try again shortly
+//!
+//! A new coin can reject trades for a while after launch (the creator-only
+//! window), even past `min_coin_age_secs`. Such a buy isn't a permanent
+//! failure: the coin stays out of the sniped set and is retried after a
+//! short backoff that doubles with each rejection, instead of every tick.
+
+use std::collections::HashMap;
+
+/// Wait before the first retry of a coin that wasn't tradable yet
+pub const RETRY_BASE_SECS: i64 = 10;
+/// Longest wait between retries
+pub const RETRY_MAX_SECS: i64 = 60;
+/// Forget a coin this long after its last scheduled retry
+const FORGET_AFTER_SECS: i64 = 3600;
+
+/// Whether a trade error means the coin isn't open for trading yet
+pub fn is_not_yet_tradable(error: &str) -> bool {
+    error.to_lowercase().contains("not yet tradable")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pending {
+    retry_at: i64,
+    attempts: u32,
+}
+
+/// Coins waiting to be retried, by symbol
+#[derive(Debug, Default)]
+pub struct SnipeRetries {
+    pending: HashMap<String, Pending>,
+}
+
+impl SnipeRetries {
+    /// Schedule another attempt at `symbol`; returns when it becomes due
+    pub fn defer(&mut self, symbol: &str, now: i64) -> i64 {
+        let attempts = self.pending.get(symbol).map_or(0, |p| p.attempts) + 1;
+        let backoff = (RETRY_BASE_SECS << (attempts - 1).min(8)).min(RETRY_MAX_SECS);
+        let retry_at = now + backoff;
+        self.pending.insert(symbol.to_string(), Pending { retry_at, attempts });
+        retry_at
+    }
+
+    /// Whether `symbol` is still backing off at `now`
+    pub fn is_waiting(&self, symbol: &str, now: i64) -> bool {
+        self.pending.get(symbol).is_some_and(|p| now < p.retry_at)
+    }
+
+    /// Drop `symbol` once it was bought (or failed for another reason)
+    pub fn clear(&mut self, symbol: &str) {
+        self.pending.remove(symbol);
+    }
+
+    /// Forget coins that haven't been retried in a long time
+    pub fn prune(&mut self, now: i64) {
+        self.pending.retain(|_, p| now - p.retry_at < FORGET_AFTER_SECS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn test_not_tradable_error_schedules_backoff_retry() {
+        let error = "Trade failed: HTTP 400 Bad Request: Coin is not yet tradable. Only the creator can trade during the first minute.";
+        assert!(is_not_yet_tradable(error));
+        assert!(!is_not_yet_tradable("Trade API error: Trade failed: HTTP 400 Bad Request: Insufficient balance"));
+
+        let mut retries = SnipeRetries::default();
+        assert!(!retries.is_waiting("NEW", NOW));
+
+        assert_eq!(retries.defer("NEW", NOW), NOW + 10);
+        assert!(retries.is_waiting("NEW", NOW + 9));
+        assert!(!retries.is_waiting("NEW", NOW + 10));
+        assert!(!retries.is_waiting("OTHER", NOW));
+
+        // Each further rejection waits longer, up to the cap
+        assert_eq!(retries.defer("NEW", NOW + 10), NOW + 30);
+        assert_eq!(retries.defer("NEW", NOW + 30), NOW + 70);
+        assert_eq!(retries.defer("NEW", NOW + 70), NOW + 130);
+        assert_eq!(retries.defer("NEW", NOW + 130), NOW + 190);
+
+        retries.clear("NEW");
+        assert!(!retries.is_waiting("NEW", NOW + 131));
+        assert_eq!(retries.defer("NEW", NOW), NOW + 10);
+
+        retries.prune(NOW + 10 + FORGET_AFTER_SECS);
+        assert!(retries.pending.is_empty());
+    }
+}
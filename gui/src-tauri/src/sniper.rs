@@ -4,20 +4,31 @@
 //! automatically buys coins matching the user's criteria. Optionally
 //! creates a sentinel for auto-protection.
 
+use crate::acquisition::BuySideModule;
+use crate::app_event::{emit_event, AppEvent};
+use crate::budget_window::BudgetResetMode;
+use crate::coin_risk::{fetch_coin_risk, RISK_HOLDER_LIMIT};
+use crate::loop_snapshot::{LoopTask, SniperSnapshot, SHUTDOWN_TIMEOUT};
 use crate::notifications::NotificationHandle;
-use crate::trade_executor::{TradeExecutorHandle, TradePriority};
+use crate::skip_reason::{sniper_skip, SkipReason, SniperGates};
+use crate::snipe_retry::{is_not_yet_tradable, SnipeRetries};
+use crate::trade_amount::TradeAmount;
+use crate::trade_approval::HeldOrders;
+use crate::trade_executor::{AutomatedTrade, Submission, TradeExecutorHandle, TradePriority};
+use crate::trade_reason::TradeReason;
 use crate::AppState;
-use crate::save_automation_log;
-use rugplay_core::TradeType;
-use rugplay_networking::RugplayClient;
+use crate::poll_interval::{AdaptiveInterval, PollOutcome};
+use crate::protective_sentinel::ProtectiveSentinelConfig;
+use crate::{ensure_protective_sentinel, restore_loop_snapshot, retune_poll_interval, save_automation_log, save_loop_snapshot};
+use rugplay_core::{parse_api_timestamp, MarketCoin, TradeResponse, TradeType};
 use rugplay_persistence::sqlite;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::sync::Arc;
-use tauri::{Emitter, Manager};
+use tauri::Manager;
 use tokio::sync::{watch, RwLock};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 /// Default polling interval for sniper (seconds)
 const DEFAULT_POLL_INTERVAL_SECS: u64 = 15;
@@ -48,7 +59,7 @@ pub struct SniperConfig {
     /// Minimum pool liquidity in USD to buy (0 = no limit)
     #[serde(default)]
     pub min_liquidity_usd: f64,
-    /// Maximum USD to spend via sniper per 24h rolling window (0 = unlimited)
+    /// Maximum USD to spend via sniper per budget window (0 = unlimited)
     #[serde(default)]
     pub max_daily_spend_usd: f64,
     /// Polling interval in seconds (0 = use default 15s)
@@ -57,6 +68,36 @@ pub struct SniperConfig {
     /// Minimum coin age in seconds before buying (creator cooldown buffer, default 65s)
     #[serde(default = "default_min_coin_age_secs")]
     pub min_coin_age_secs: u64,
+    /// When the daily spend cap resets (rolling 24h or a fixed UTC hour)
+    #[serde(default)]
+    pub budget_reset_mode: BudgetResetMode,
+    /// Skip coins whose risk score (0–100) is above this (0 = no limit)
+    #[serde(default)]
+    pub max_risk_score: f64,
+}
+
+impl SniperConfig {
+    /// Filters a new coin has to pass before it is bought
+    pub fn gates(&self) -> SniperGates<'_> {
+        SniperGates {
+            max_market_cap_usd: self.max_market_cap_usd,
+            max_coin_age_secs: self.max_coin_age_secs,
+            min_coin_age_secs: self.min_coin_age_secs,
+            blacklisted_creators: &self.blacklisted_creators,
+            max_daily_spend_usd: self.max_daily_spend_usd,
+            buy_amount_usd: self.buy_amount_usd,
+        }
+    }
+
+    /// Sentinel put on every snipe
+    pub fn protective_sentinel(&self) -> ProtectiveSentinelConfig {
+        ProtectiveSentinelConfig {
+            stop_loss_pct: self.stop_loss_pct,
+            take_profit_pct: self.take_profit_pct,
+            trailing_stop_pct: self.trailing_stop_pct,
+            sell_percentage: Some(self.sell_percentage),
+        }
+    }
 }
 
 fn default_min_coin_age_secs() -> u64 { 65 }
@@ -78,6 +119,8 @@ impl Default for SniperConfig {
             max_daily_spend_usd: 0.0,  // unlimited by default
             poll_interval_secs: 0,     // use default 15s
             min_coin_age_secs: 65,     // 60s creator period + 5s buffer
+            budget_reset_mode: BudgetResetMode::Rolling24h,
+            max_risk_score: 0.0,       // disabled; new coins all score high on age
         }
     }
 }
@@ -104,6 +147,18 @@ pub struct SniperTickEvent {
     pub total_sniped: u32,
     pub last_sniped_at: Option<String>,
     pub coins_checked: u32,
+    /// Coins passed over this tick and why
+    pub skipped: Vec<SniperSkippedCoin>,
+}
+
+/// A new coin the sniper passed on
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SniperSkippedCoin {
+    pub symbol: String,
+    /// Human-readable form of `skip`
+    pub reason: String,
+    pub skip: SkipReason,
 }
 
 // ─── Handle ──────────────────────────────────────────────────────────
@@ -114,6 +169,7 @@ pub struct SniperHandle {
     enabled_tx: Arc<watch::Sender<bool>>,
     config: Arc<RwLock<SniperConfig>>,
     cancel: CancellationToken,
+    task: LoopTask,
 }
 
 impl SniperHandle {
@@ -143,6 +199,21 @@ impl SniperHandle {
     pub fn stop(&self) {
         self.cancel.cancel();
     }
+
+    /// Stop the loop and wait for it to save its in-memory state
+    pub async fn shutdown(&self) {
+        self.stop();
+        if !self.task.join(SHUTDOWN_TIMEOUT).await {
+            warn!("Sniper: loop did not stop in time, state snapshot skipped");
+        }
+    }
+}
+
+impl BuySideModule for SniperHandle {
+    fn module_name(&self) -> &'static str { "sniper" }
+    fn is_enabled(&self) -> bool { SniperHandle::is_enabled(self) }
+    fn enable(&self) { SniperHandle::enable(self) }
+    fn disable(&self) { SniperHandle::disable(self) }
 }
 
 // ─── Spawn ───────────────────────────────────────────────────────────
@@ -156,10 +227,19 @@ pub fn spawn_sniper(
     let config = Arc::new(RwLock::new(SniperConfig::default()));
     let cancel = CancellationToken::new();
 
+    let task = LoopTask::spawn(sniper_loop(
+        app_handle.clone(),
+        enabled_rx,
+        config.clone(),
+        executor,
+        cancel.clone(),
+    ));
+
     let handle = SniperHandle {
         enabled_tx: Arc::new(enabled_tx),
-        config: config.clone(),
-        cancel: cancel.clone(),
+        config,
+        cancel,
+        task,
     };
 
     // Restore enabled state from DB after a short delay
@@ -175,8 +255,6 @@ pub fn spawn_sniper(
         }
     });
 
-    tokio::spawn(sniper_loop(app_handle, enabled_rx, config, executor, cancel));
-
     handle
 }
 
@@ -191,14 +269,18 @@ async fn sniper_loop(
 ) {
     info!("Sniper loop started");
 
-    // Track coins we've already sniped to avoid double-buying
-    // Now stores (symbol, timestamp) for pruning old entries
-    let mut sniped_symbols: HashSet<String> = load_sniped_symbols(&app_handle).await;
-    let mut total_sniped: u32 = load_sniper_total(&app_handle).await;
-    let mut last_sniped_at: Option<String> = load_sniper_last_at(&app_handle).await;
-
-    // Daily spend tracking for the sniper: (timestamp, usd_amount)
-    let mut daily_spend: Vec<(i64, f64)> = Vec::new();
+    // Daily spend is restored from the snapshot saved at the last shutdown
+    let mut progress = SniperProgress {
+        sniped_symbols: load_sniped_symbols(&app_handle).await,
+        total_sniped: load_sniper_total(&app_handle).await,
+        last_sniped_at: load_sniper_last_at(&app_handle).await,
+        daily_spend: restore_loop_snapshot::<SniperSnapshot>(&app_handle)
+            .await
+            .map(|snapshot| snapshot.daily_spend)
+            .unwrap_or_default(),
+    };
+    // Snipes waiting for the user's approval (semi-auto mode)
+    let mut held: HeldOrders<Snipe> = HeldOrders::default();
 
     // Load config from DB
     if let Some(saved_config) = load_sniper_config(&app_handle).await {
@@ -206,30 +288,55 @@ async fn sniper_loop(
     }
 
     // Prune sniped symbols older than 7 days on startup
-    prune_old_sniped_symbols(&app_handle, &mut sniped_symbols).await;
+    prune_old_sniped_symbols(&app_handle, &mut progress.sniped_symbols).await;
+    let mut retries = SnipeRetries::default();
 
     let mut interval = tokio::time::interval(
         std::time::Duration::from_secs(DEFAULT_POLL_INTERVAL_SECS)
     );
+    let mut adaptive = AdaptiveInterval::new(interval.period());
+    let mut newest_seen: Option<String> = None;
 
     loop {
         tokio::select! {
             _ = cancel.cancelled() => {
+                let snapshot = SniperSnapshot { daily_spend: std::mem::take(&mut progress.daily_spend) };
+                save_loop_snapshot(&app_handle, &snapshot).await;
                 info!("Sniper cancelled, exiting");
                 return;
             }
             _ = interval.tick() => {
+                app_handle.state::<AppState>().heartbeats.beat("sniper");
+
+                // Nothing acts until the startup warm-up has loaded its state
+                if !app_handle.state::<AppState>().warmup.is_ready() {
+                    debug!("Sniper: warming up, skipping tick");
+                    continue;
+                }
+
+                // Approved snipes that filled since the last tick
+                if !held.is_empty() {
+                    let cfg = config.read().await.clone();
+                    for (snipe, result) in held.take_settled() {
+                        match result {
+                            Ok(response) => record_snipe(&app_handle, &cfg, &snipe, &response, &mut progress).await,
+                            Err(e) => info!("Sniper: proposal for {} not filled: {}", snipe.symbol, e),
+                        }
+                    }
+                }
+
                 let enabled = *enabled_rx.borrow_and_update();
 
                 if !enabled {
                     // Emit idle tick
                     let tick = SniperTickEvent {
                         enabled: false,
-                        total_sniped,
-                        last_sniped_at: last_sniped_at.clone(),
+                        total_sniped: progress.total_sniped,
+                        last_sniped_at: progress.last_sniped_at.clone(),
                         coins_checked: 0,
+                        skipped: Vec::new(),
                     };
-                    let _ = app_handle.emit("sniper-tick", &tick);
+                    let _ = emit_event(&app_handle, AppEvent::SniperTick(tick));
                     continue;
                 }
 
@@ -242,10 +349,7 @@ async fn sniper_loop(
                     }
                 };
 
-                let client = RugplayClient::new_with_cache(&token, {
-                    let state = app_handle.state::<AppState>();
-                    state.coin_cache.clone()
-                });
+                let client = app_handle.state::<AppState>().api_client(&token);
                 let cfg = config.read().await.clone();
 
                 // Update interval if config changed
@@ -254,181 +358,136 @@ async fn sniper_loop(
                 } else {
                     DEFAULT_POLL_INTERVAL_SECS
                 };
-                let current_period = interval.period();
-                if current_period != std::time::Duration::from_secs(desired_interval) {
+                if adaptive.set_configured(std::time::Duration::from_secs(desired_interval)) {
                     interval = tokio::time::interval(std::time::Duration::from_secs(desired_interval));
                     info!("Sniper: poll interval updated to {}s", desired_interval);
                 }
 
-                // Daily spend check: prune entries > 24h
+                // Daily spend check: prune entries outside the budget window
                 let now_epoch = chrono::Utc::now().timestamp();
-                daily_spend.retain(|(ts, _)| now_epoch - *ts < 86400);
-                let spent_today: f64 = daily_spend.iter().map(|(_, a)| a).sum();
+                cfg.budget_reset_mode.prune(&mut progress.daily_spend, now_epoch);
+                // Proposals still waiting count against the budget as if approved
+                let spent_today: f64 = progress.daily_spend.iter().map(|(_, a)| a).sum::<f64>()
+                    + held.contexts().map(|snipe| snipe.buy_amount_usd).sum::<f64>();
 
                 if cfg.max_daily_spend_usd > 0.0 && spent_today >= cfg.max_daily_spend_usd {
                     debug!("Sniper: daily spend limit reached (${:.2} / ${:.2})", spent_today, cfg.max_daily_spend_usd);
                     let tick = SniperTickEvent {
                         enabled: true,
-                        total_sniped,
-                        last_sniped_at: last_sniped_at.clone(),
+                        total_sniped: progress.total_sniped,
+                        last_sniped_at: progress.last_sniped_at.clone(),
                         coins_checked: 0,
+                        skipped: Vec::new(),
                     };
-                    let _ = app_handle.emit("sniper-tick", &tick);
+                    let _ = emit_event(&app_handle, AppEvent::SniperTick(tick));
                     continue;
                 }
 
                 // Poll newest coins
-                match client.get_market(1, 20, "createdAt", "desc", None).await {
+                let market = client.get_market(1, 20, "createdAt", "desc", None).await;
+                let outcome = match &market {
                     Ok(market) => {
-                        let now = chrono::Utc::now();
-                        let mut checked = 0u32;
-
-                        for coin in &market.coins {
-                            checked += 1;
-
-                            // Skip if already sniped
-                            if sniped_symbols.contains(&coin.symbol) {
-                                continue;
-                            }
-
-                            // Check market cap filter
-                            if cfg.max_market_cap_usd > 0.0 && coin.market_cap > cfg.max_market_cap_usd {
-                                continue;
-                            }
+                        let newest = market.coins.first().map(|c| c.symbol.clone());
+                        let listed = newest.is_some() && newest != newest_seen;
+                        newest_seen = newest.or(newest_seen.take());
+                        if listed { PollOutcome::Active } else { PollOutcome::Quiet }
+                    }
+                    Err(e) => PollOutcome::from_error(&e.to_string()),
+                };
+                retune_poll_interval(&app_handle, &mut adaptive, &mut interval, outcome, "Sniper").await;
 
-                            // Check coin age filter (too old)
-                            if cfg.max_coin_age_secs > 0 {
-                                if let Some(ref created_str) = coin.created_at {
-                                    if let Ok(created) = chrono::DateTime::parse_from_rfc3339(created_str) {
-                                        let age_secs = (now - created.with_timezone(&chrono::Utc)).num_seconds();
-                                        if age_secs > cfg.max_coin_age_secs as i64 {
-                                            continue;
-                                        }
+                match market {
+                    Ok(market) => {
+                        let now = chrono::Utc::now();
+                        retries.prune(now.timestamp());
+                        let checked = market.coins.len() as u32;
+                        let (targets, mut skipped) =
+                            select_snipes(&cfg, &market.coins, &progress.sniped_symbols, &retries, now, spent_today);
+
+                        for (coin, coin_age) in targets {
+                            // select_snipes skips coins without a market cap
+                            let Some(market_cap) = coin.market_cap else { continue };
+
+                            // Shared coin risk score, when the sniper has a limit set.
+                            // A coin that can't be scored waits for the next tick.
+                            let mut risk_holders = None;
+                            if cfg.max_risk_score > 0.0 {
+                                match fetch_coin_risk(&client, &coin.symbol).await {
+                                    Ok((risk, _)) if risk.exceeds(cfg.max_risk_score) => {
+                                        debug!("Sniper: {} risk {:.0} ({})", coin.symbol, risk.score, risk.breakdown());
+                                        let skip = SkipReason::HighRisk { actual: risk.score, threshold: cfg.max_risk_score };
+                                        skipped.push(SniperSkippedCoin { symbol: coin.symbol.clone(), reason: skip.message(), skip });
+                                        continue;
                                     }
-                                }
-                            }
-
-                            // Check creator cooldown (too young — within creator-only period)
-                            if cfg.min_coin_age_secs > 0 {
-                                if let Some(ref created_str) = coin.created_at {
-                                    if let Ok(created) = chrono::DateTime::parse_from_rfc3339(created_str) {
-                                        let age_secs = (now - created.with_timezone(&chrono::Utc)).num_seconds();
-                                        if age_secs < cfg.min_coin_age_secs as i64 {
-                                            debug!("Sniper: skipping {} (age {}s < {}s creator cooldown)", 
-                                                   coin.symbol, age_secs, cfg.min_coin_age_secs);
-                                            continue;
-                                        }
+                                    Ok((_, holders)) => risk_holders = Some(holders),
+                                    Err(e) => {
+                                        debug!("Sniper: risk check failed for {}: {}", coin.symbol, e);
+                                        continue;
                                     }
                                 }
                             }
 
-                            // Check blacklisted creators
-                            if let Some(ref creator) = coin.creator_name {
-                                if cfg.blacklisted_creators.iter().any(|b| b.eq_ignore_ascii_case(creator)) {
-                                    debug!("Sniper: skipping {} (blacklisted creator: {})", coin.symbol, creator);
+                            // The user's entry script, if any, has the last word
+                            let entry_script = app_handle.state::<AppState>().entry_script.read().await.clone();
+                            if let Some(script) = entry_script {
+                                let holders = match risk_holders {
+                                    Some(holders) => holders,
+                                    None => client.get_coin_holders(&coin.symbol, RISK_HOLDER_LIMIT).await.ok(),
+                                };
+                                if let Err(detail) = script.check_blocking("sniper", coin, holders.as_ref(), &[]).await {
+                                    debug!("Sniper: {} vetoed: {}", coin.symbol, detail);
+                                    let skip = SkipReason::ScriptReject { detail };
+                                    skipped.push(SniperSkippedCoin { symbol: coin.symbol.clone(), reason: skip.message(), skip });
                                     continue;
                                 }
                             }
 
-                            // Check remaining daily spend budget
-                            if cfg.max_daily_spend_usd > 0.0 && spent_today + cfg.buy_amount_usd > cfg.max_daily_spend_usd {
-                                debug!("Sniper: skipping {} (would exceed daily spend limit)", coin.symbol);
-                                continue;
-                            }
-
                             // This coin qualifies — SNIPE IT
                             info!("Sniper: targeting {} (mcap: ${:.2}, price: ${:.8})", 
-                                coin.symbol, coin.market_cap, coin.current_price);
-
-                            let coin_age = coin.created_at.as_ref()
-                                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
-                                .map(|dt| (now - dt.with_timezone(&chrono::Utc)).num_seconds())
-                                .unwrap_or(0);
+                                coin.symbol, market_cap, coin.current_price);
 
                             // Emit sniper triggered event
                             let event = SniperTriggeredEvent {
                                 symbol: coin.symbol.clone(),
                                 coin_name: coin.name.clone(),
                                 buy_amount_usd: cfg.buy_amount_usd,
-                                market_cap: coin.market_cap,
+                                market_cap,
                                 price: coin.current_price,
                                 coin_age_secs: coin_age,
                             };
-                            let _ = app_handle.emit("sniper-triggered", &event);
+                            let _ = emit_event(&app_handle, AppEvent::SniperTriggered(event));
 
                             // Submit buy through trade executor
-                            let reason = format!(
-                                "Sniper: new coin {} (age: {}s, mcap: ${:.0})",
-                                coin.symbol, coin_age, coin.market_cap
-                            );
-
-                            match executor.submit_trade(
-                                coin.symbol.clone(),
-                                TradeType::Buy,
-                                cfg.buy_amount_usd,
-                                TradePriority::High,
-                                reason,
-                            ).await {
-                                Ok(response) => {
-                                    info!("Sniper: bought {} @ ${:.8}", coin.symbol, response.new_price);
-                                    sniped_symbols.insert(coin.symbol.clone());
-                                    total_sniped += 1;
-                                    last_sniped_at = Some(chrono::Utc::now().to_rfc3339());
-
-                                    // Track daily spend
-                                    daily_spend.push((chrono::Utc::now().timestamp(), cfg.buy_amount_usd));
-
-                                    // Send native notification
-                                    if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
-                                        notif.notify_sniper_buy(&coin.symbol, cfg.buy_amount_usd, response.new_price).await;
-                                    }
-
-                                    // Save state (including sniped symbol for restart persistence)
-                                    save_sniper_state(&app_handle, total_sniped, last_sniped_at.as_deref()).await;
-                                    save_sniped_symbol(&app_handle, &coin.symbol).await;
-                                    save_sniped_symbol_timestamp(&app_handle, &coin.symbol).await;
-
-                                    // Persist to snipe_log table
-                                    save_snipe_log_entry(
-                                        &app_handle,
-                                        &coin.symbol,
-                                        &coin.name,
-                                        cfg.buy_amount_usd,
-                                        coin.market_cap,
-                                        response.new_price,
-                                        coin_age,
-                                    ).await;
-
-                                    save_automation_log(
-                                        &app_handle,
-                                        "sniper",
-                                        &coin.symbol,
-                                        &coin.name,
-                                        "BUY",
-                                        cfg.buy_amount_usd,
-                                        &serde_json::json!({
-                                            "marketCap": coin.market_cap,
-                                            "price": response.new_price,
-                                            "coinAgeSecs": coin_age,
-                                        }).to_string(),
-                                    ).await;
-
-                                    // Auto-create sentinel if configured
-                                    if cfg.auto_create_sentinel {
-                                        if let Some(coins_bought) = response.coins_bought {
-                                            create_sentinel_for_snipe(
-                                                &app_handle,
-                                                &coin.symbol,
-                                                response.new_price,
-                                                &cfg,
-                                            ).await;
-                                            debug!("Sniper: sentinel created for {} ({} coins)", coin.symbol, coins_bought);
-                                        }
-                                    }
+                            let snipe = Snipe {
+                                symbol: coin.symbol.clone(),
+                                name: coin.name.clone(),
+                                market_cap,
+                                price: coin.current_price,
+                                coin_age,
+                                buy_amount_usd: cfg.buy_amount_usd,
+                            };
+                            match submit_snipe(&executor, &cfg, coin, market_cap, coin_age).await {
+                                Ok(Submission::Filled(response)) => {
+                                    retries.clear(&coin.symbol);
+                                    record_snipe(&app_handle, &cfg, &snipe, &response, &mut progress).await;
+                                }
+                                Ok(Submission::Held(proposal)) => {
+                                    // Not proposed again while it waits, or this session if declined
+                                    info!("Sniper: {} proposed for approval (#{})", coin.symbol, proposal.approval.id);
+                                    progress.sniped_symbols.insert(coin.symbol.clone());
+                                    retries.clear(&coin.symbol);
+                                    held.hold(proposal, snipe);
+                                }
+                                Err(e) if is_not_yet_tradable(&e) => {
+                                    // Creator-only window: not sniped, retry after a short backoff
+                                    let failed_at = chrono::Utc::now().timestamp();
+                                    let retry_at = retries.defer(&coin.symbol, failed_at);
+                                    info!("Sniper: {} not tradable yet, retrying in {}s", coin.symbol, retry_at - failed_at);
                                 }
                                 Err(e) => {
                                     error!("Sniper: failed to buy {}: {}", coin.symbol, e);
                                     // Don't add to sniped set — allow retry
+                                    retries.clear(&coin.symbol);
                                 }
                             }
                         }
@@ -436,11 +495,12 @@ async fn sniper_loop(
                         // Emit status tick
                         let tick = SniperTickEvent {
                             enabled: true,
-                            total_sniped,
-                            last_sniped_at: last_sniped_at.clone(),
+                            total_sniped: progress.total_sniped,
+                            last_sniped_at: progress.last_sniped_at.clone(),
                             coins_checked: checked,
+                            skipped,
                         };
-                        let _ = app_handle.emit("sniper-tick", &tick);
+                        let _ = emit_event(&app_handle, AppEvent::SniperTick(tick));
                     }
                     Err(e) => {
                         error!("Sniper: failed to fetch market: {}", e);
@@ -453,6 +513,157 @@ async fn sniper_loop(
 
 // ─── Helpers ─────────────────────────────────────────────────────────
 
+/// What the sniper has bought so far
+struct SniperProgress {
+    /// Coins already sniped (or proposed), never bought twice
+    sniped_symbols: HashSet<String>,
+    total_sniped: u32,
+    last_sniped_at: Option<String>,
+    /// (timestamp, usd_amount) of each snipe, for the daily spend limit
+    daily_spend: Vec<(i64, f64)>,
+}
+
+/// A coin the sniper decided to buy, as it looked at the time
+#[derive(Debug, Clone)]
+struct Snipe {
+    symbol: String,
+    name: String,
+    market_cap: f64,
+    /// Listed price, standing in when the fill reports none
+    price: f64,
+    coin_age: i64,
+    buy_amount_usd: f64,
+}
+
+/// Book a filled snipe: stats, notification, logs and the protective sentinel
+async fn record_snipe(
+    app_handle: &tauri::AppHandle,
+    cfg: &SniperConfig,
+    snipe: &Snipe,
+    response: &TradeResponse,
+    progress: &mut SniperProgress,
+) {
+    let price = response.fill_price().unwrap_or(snipe.price);
+    info!("Sniper: bought {} @ ${:.8}", snipe.symbol, price);
+    progress.sniped_symbols.insert(snipe.symbol.clone());
+    progress.total_sniped += 1;
+    progress.last_sniped_at = Some(chrono::Utc::now().to_rfc3339());
+
+    // Track daily spend
+    progress.daily_spend.push((chrono::Utc::now().timestamp(), snipe.buy_amount_usd));
+
+    // Send native notification
+    if let Some(notif) = app_handle.try_state::<NotificationHandle>() {
+        notif.notify_sniper_buy(&snipe.symbol, snipe.buy_amount_usd, price).await;
+    }
+
+    // Save state (including sniped symbol for restart persistence).
+    // A paper snipe isn't kept, so the coin can still be sniped for real.
+    if !response.paper {
+        save_sniper_state(app_handle, progress.total_sniped, progress.last_sniped_at.as_deref()).await;
+        save_sniped_symbol(app_handle, &snipe.symbol).await;
+        save_sniped_symbol_timestamp(app_handle, &snipe.symbol).await;
+
+        // Persist to snipe_log table
+        save_snipe_log_entry(
+            app_handle,
+            &snipe.symbol,
+            &snipe.name,
+            snipe.buy_amount_usd,
+            snipe.market_cap,
+            price,
+            snipe.coin_age,
+        ).await;
+    }
+
+    save_automation_log(
+        app_handle,
+        "sniper",
+        &snipe.symbol,
+        &snipe.name,
+        "BUY",
+        snipe.buy_amount_usd,
+        &serde_json::json!({
+            "marketCap": snipe.market_cap,
+            "price": price,
+            "coinAgeSecs": snipe.coin_age,
+        }).to_string(),
+    ).await;
+
+    // Auto-create sentinel if configured
+    if cfg.auto_create_sentinel {
+        if let Some(coins_bought) = response.coins_bought {
+            ensure_protective_sentinel(app_handle, "Sniper", &snipe.symbol, response, &cfg.protective_sentinel()).await;
+            debug!("Sniper: sentinel created for {} ({} coins)", snipe.symbol, coins_bought);
+        }
+    }
+}
+
+/// Split the newest listings into the coins to snipe (with their age in
+/// seconds) and the ones passed on. Already sniped coins, and coins
+/// waiting to be retried, are neither.
+fn select_snipes<'a>(
+    cfg: &SniperConfig,
+    coins: &'a [MarketCoin],
+    sniped_symbols: &HashSet<String>,
+    retries: &SnipeRetries,
+    now: chrono::DateTime<chrono::Utc>,
+    spent_today: f64,
+) -> (Vec<(&'a MarketCoin, i64)>, Vec<SniperSkippedCoin>) {
+    let mut targets = Vec::new();
+    let mut skipped = Vec::new();
+
+    for coin in coins {
+        if sniped_symbols.contains(&coin.symbol) || retries.is_waiting(&coin.symbol, now.timestamp()) {
+            continue;
+        }
+
+        let age_secs = coin.created_at.as_deref()
+            .and_then(parse_api_timestamp)
+            .map(|dt| (now - dt).num_seconds());
+
+        // Market cap, age (too old / creator cooldown),
+        // blacklisted creators and remaining daily budget
+        if let Some(skip) = sniper_skip(&cfg.gates(), coin, age_secs, spent_today) {
+            let reason = skip.message();
+            debug!("Sniper: skipping {} ({})", coin.symbol, reason);
+            skipped.push(SniperSkippedCoin { symbol: coin.symbol.clone(), reason, skip });
+            continue;
+        }
+
+        targets.push((coin, age_secs.unwrap_or(0)));
+    }
+
+    (targets, skipped)
+}
+
+/// Buy a sniped coin through the executor. In semi-auto mode it comes back
+/// held for approval at once.
+async fn submit_snipe(
+    executor: &TradeExecutorHandle,
+    cfg: &SniperConfig,
+    coin: &MarketCoin,
+    market_cap: f64,
+    coin_age: i64,
+) -> Result<Submission, String> {
+    let reason = TradeReason::Sniper {
+        symbol: coin.symbol.clone(),
+        age_secs: coin_age,
+        market_cap,
+    };
+    executor
+        .submit_automated(AutomatedTrade {
+            symbol: coin.symbol.clone(),
+            trade_type: TradeType::Buy,
+            amount: TradeAmount::Usd(cfg.buy_amount_usd),
+            priority: TradePriority::High,
+            reason,
+            source: "sniper".to_string(),
+            quoted_price: Some(coin.current_price),
+        })
+        .await
+}
+
 async fn get_active_token(app_handle: &tauri::AppHandle) -> Result<String, String> {
     let state = app_handle.state::<AppState>();
     let db_guard = state.db.read().await;
@@ -476,36 +687,6 @@ async fn get_active_token(app_handle: &tauri::AppHandle) -> Result<String, Strin
     Ok(token)
 }
 
-async fn create_sentinel_for_snipe(
-    app_handle: &tauri::AppHandle,
-    symbol: &str,
-    entry_price: f64,
-    config: &SniperConfig,
-) {
-    let state = app_handle.state::<AppState>();
-    let db_guard = state.db.read().await;
-
-    let Some(db) = db_guard.as_ref() else { return };
-
-    let profile = match sqlite::get_active_profile(db.pool()).await {
-        Ok(Some(p)) => p,
-        _ => return,
-    };
-
-    if let Err(e) = sqlite::upsert_sentinel(
-        db.pool(),
-        profile.id,
-        symbol,
-        Some(config.stop_loss_pct),
-        Some(config.take_profit_pct),
-        config.trailing_stop_pct,
-        config.sell_percentage,
-        entry_price,
-    ).await {
-        error!("Sniper: failed to create sentinel for {}: {}", symbol, e);
-    }
-}
-
 // ─── DB Persistence ──────────────────────────────────────────────────
 
 async fn load_sniper_config(app_handle: &tauri::AppHandle) -> Option<SniperConfig> {
@@ -655,18 +836,13 @@ async fn save_sniped_symbol(app_handle: &tauri::AppHandle, symbol: &str) {
     let db_guard = state.db.read().await;
     let Some(db) = db_guard.as_ref() else { return };
 
-    // Load existing, add new, save back
-    let mut symbols = load_sniped_symbols_from_pool(db.pool()).await;
-    symbols.insert(symbol.to_string());
-
-    let json = serde_json::to_string(&symbols.into_iter().collect::<Vec<_>>()).unwrap_or_default();
-    let _ = sqlx::query(
-        "INSERT INTO settings (key, value) VALUES ('sniper_sniped_symbols', ?1)
-         ON CONFLICT(key) DO UPDATE SET value = ?1"
-    )
-    .bind(&json)
-    .execute(db.pool())
+    let added = sqlite::update_setting_json(db.pool(), "sniper_sniped_symbols", |symbols: &mut HashSet<String>| {
+        symbols.insert(symbol.to_string());
+    })
     .await;
+    if let Err(e) = added {
+        warn!("Sniper: failed to save sniped symbol {}: {}", symbol, e);
+    }
 }
 
 /// Internal helper to load from pool directly (avoids re-locking)
@@ -691,48 +867,41 @@ async fn prune_old_sniped_symbols(app_handle: &tauri::AppHandle, sniped: &mut Ha
     let db_guard = state.db.read().await;
     let Some(db) = db_guard.as_ref() else { return };
 
-    // Load symbol timestamps: { symbol: epoch_secs }
-    let ts_json: Option<String> = sqlx::query_scalar(
-        "SELECT value FROM settings WHERE key = 'sniper_sniped_timestamps'"
-    )
-    .fetch_optional(db.pool())
-    .await
-    .ok()
-    .flatten();
-
-    let mut timestamps: std::collections::HashMap<String, i64> = ts_json
-        .and_then(|j| serde_json::from_str(&j).ok())
-        .unwrap_or_default();
-
     let now = chrono::Utc::now().timestamp();
     let seven_days = 7 * 24 * 3600;
 
+    // Drop expired timestamps: { symbol: epoch_secs }
+    let recent = sqlite::update_setting_json(
+        db.pool(),
+        "sniper_sniped_timestamps",
+        |timestamps: &mut std::collections::HashMap<String, i64>| {
+            timestamps.retain(|_sym, ts| now - *ts < seven_days);
+            timestamps.keys().cloned().collect::<HashSet<String>>()
+        },
+    )
+    .await;
+    let recent = match recent {
+        Ok(recent) => recent,
+        Err(e) => {
+            warn!("Sniper: failed to prune sniped symbol timestamps: {}", e);
+            return;
+        }
+    };
+
     // Remove symbols older than 7 days
     let before = sniped.len();
-    timestamps.retain(|_sym, ts| now - *ts < seven_days);
-    sniped.retain(|sym| timestamps.contains_key(sym));
+    sniped.retain(|sym| recent.contains(sym));
 
     if sniped.len() < before {
         info!("Sniper: pruned {} old sniped symbols (kept {})", before - sniped.len(), sniped.len());
 
-        // Save updated sets
-        let symbols_json = serde_json::to_string(&sniped.iter().collect::<Vec<_>>()).unwrap_or_default();
-        let _ = sqlx::query(
-            "INSERT INTO settings (key, value) VALUES ('sniper_sniped_symbols', ?1)
-             ON CONFLICT(key) DO UPDATE SET value = ?1"
-        )
-        .bind(&symbols_json)
-        .execute(db.pool())
-        .await;
-
-        let ts_json_out = serde_json::to_string(&timestamps).unwrap_or_default();
-        let _ = sqlx::query(
-            "INSERT INTO settings (key, value) VALUES ('sniper_sniped_timestamps', ?1)
-             ON CONFLICT(key) DO UPDATE SET value = ?1"
-        )
-        .bind(&ts_json_out)
-        .execute(db.pool())
+        let pruned = sqlite::update_setting_json(db.pool(), "sniper_sniped_symbols", |symbols: &mut HashSet<String>| {
+            symbols.retain(|sym| recent.contains(sym));
+        })
         .await;
+        if let Err(e) = pruned {
+            warn!("Sniper: failed to save pruned sniped symbols: {}", e);
+        }
     }
 }
 
@@ -742,29 +911,18 @@ async fn save_sniped_symbol_timestamp(app_handle: &tauri::AppHandle, symbol: &st
     let db_guard = state.db.read().await;
     let Some(db) = db_guard.as_ref() else { return };
 
-    // Load existing timestamps
-    let ts_json: Option<String> = sqlx::query_scalar(
-        "SELECT value FROM settings WHERE key = 'sniper_sniped_timestamps'"
-    )
-    .fetch_optional(db.pool())
-    .await
-    .ok()
-    .flatten();
-
-    let mut timestamps: std::collections::HashMap<String, i64> = ts_json
-        .and_then(|j| serde_json::from_str(&j).ok())
-        .unwrap_or_default();
-
-    timestamps.insert(symbol.to_string(), chrono::Utc::now().timestamp());
-
-    let json = serde_json::to_string(&timestamps).unwrap_or_default();
-    let _ = sqlx::query(
-        "INSERT INTO settings (key, value) VALUES ('sniper_sniped_timestamps', ?1)
-         ON CONFLICT(key) DO UPDATE SET value = ?1"
+    let now = chrono::Utc::now().timestamp();
+    let saved = sqlite::update_setting_json(
+        db.pool(),
+        "sniper_sniped_timestamps",
+        |timestamps: &mut std::collections::HashMap<String, i64>| {
+            timestamps.insert(symbol.to_string(), now);
+        },
     )
-    .bind(&json)
-    .execute(db.pool())
     .await;
+    if let Err(e) = saved {
+        warn!("Sniper: failed to save snipe timestamp for {}: {}", symbol, e);
+    }
 }
 
 /// Clear all sniped symbols (called from commands)
@@ -818,3 +976,126 @@ async fn save_snipe_log_entry(
 
     debug!("Snipe log entry saved for {}", symbol);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_amount::AmountUnit;
+    use crate::trade_approval::SemiAutoConfig;
+    use crate::trade_executor::{spawn_recording_executor, RecordedTrade};
+    use std::time::Duration;
+
+    fn listing(symbol: &str, market_cap: f64, created_at: &str) -> MarketCoin {
+        serde_json::from_value(serde_json::json!({
+            "symbol": symbol, "name": symbol, "currentPrice": 0.01,
+            "marketCap": market_cap, "createdAt": created_at
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sniper_submits_only_qualifying_listings() {
+        let cfg = SniperConfig::default();
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-01T12:10:00Z").unwrap().with_timezone(&chrono::Utc);
+        let feed = vec![
+            listing("FRESH", 10_000.0, "2026-03-01T12:08:00Z"),
+            listing("BIG", 90_000.0, "2026-03-01T12:08:00Z"),
+            // Still inside the creator's own buy window
+            listing("BABY", 10_000.0, "2026-03-01T12:09:30Z"),
+            listing("DONE", 10_000.0, "2026-03-01T12:08:00Z"),
+        ];
+        let sniped: HashSet<String> = ["DONE".to_string()].into();
+
+        let (targets, skipped) = select_snipes(&cfg, &feed, &sniped, &SnipeRetries::default(), now, 0.0);
+        assert_eq!(skipped.iter().map(|s| s.symbol.as_str()).collect::<Vec<_>>(), vec!["BIG", "BABY"]);
+
+        let (executor, recorder) = spawn_recording_executor();
+        for (coin, age) in targets {
+            submit_snipe(&executor, &cfg, coin, coin.market_cap.unwrap(), age).await.unwrap();
+        }
+
+        assert_eq!(
+            recorder.trades(),
+            vec![RecordedTrade {
+                symbol: "FRESH".into(),
+                trade_type: TradeType::Buy,
+                amount: 1000.0,
+                amount_unit: AmountUnit::Usd,
+                priority: TradePriority::High,
+                reason: TradeReason::Sniper { symbol: "FRESH".into(), age_secs: 120, market_cap: 10_000.0 },
+                source: "sniper".into(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sniper_keeps_ticking_while_a_snipe_awaits_approval() {
+        let cfg = SniperConfig::default();
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-01T12:10:00Z").unwrap().with_timezone(&chrono::Utc);
+        let feed = vec![listing("FRESH", 10_000.0, "2026-03-01T12:08:00Z")];
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_semi_auto_config(SemiAutoConfig { enabled: true, ..Default::default() }).await;
+
+        // The proposal comes straight back instead of blocking the tick
+        let mut sniped = HashSet::new();
+        let mut held = HeldOrders::default();
+        let (targets, _) = select_snipes(&cfg, &feed, &sniped, &SnipeRetries::default(), now, 0.0);
+        let (coin, age) = targets[0];
+        let submitted = submit_snipe(&executor, &cfg, coin, 10_000.0, age);
+        let Submission::Held(proposal) = tokio::time::timeout(Duration::from_secs(1), submitted).await.unwrap().unwrap()
+        else {
+            panic!("snipe was not held");
+        };
+        let id = proposal.approval.id;
+        sniped.insert("FRESH".to_string());
+        held.hold(proposal, "FRESH");
+
+        // The next tick neither re-proposes it nor waits on it
+        let later = now + chrono::Duration::seconds(2);
+        let (targets, _) = select_snipes(&cfg, &feed, &sniped, &SnipeRetries::default(), later, 0.0);
+        assert!(targets.is_empty());
+        assert!(held.take_settled().is_empty());
+        assert!(recorder.trades().is_empty());
+
+        // Once approved, the fill settles for the bookkeeping
+        executor.approve_pending(id).await.unwrap();
+        let settled = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let settled = held.take_settled();
+                if !settled.is_empty() {
+                    return settled;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(settled[0].0, "FRESH");
+        assert!(settled[0].1.is_ok());
+        assert_eq!(recorder.trades().len(), 1);
+    }
+
+    #[test]
+    fn test_not_tradable_coin_is_retried_later_not_marked_sniped() {
+        let cfg = SniperConfig::default();
+        let now = chrono::DateTime::parse_from_rfc3339("2026-03-01T12:10:00Z").unwrap().with_timezone(&chrono::Utc);
+        let feed = vec![listing("FRESH", 10_000.0, "2026-03-01T12:08:00Z")];
+        let sniped = HashSet::new();
+
+        // The buy was rejected because trading hasn't opened yet
+        let error = "Trade failed: HTTP 400 Bad Request: Coin is not yet tradable. Only the creator can trade right now";
+        assert!(is_not_yet_tradable(error));
+        let mut retries = SnipeRetries::default();
+        retries.defer("FRESH", now.timestamp());
+
+        // Held back during the backoff, without being reported as skipped
+        let (targets, skipped) = select_snipes(&cfg, &feed, &sniped, &retries, now, 0.0);
+        assert!(targets.is_empty() && skipped.is_empty());
+
+        // Still unsniped, so it's picked up again once the backoff ends
+        let later = now + chrono::Duration::seconds(crate::snipe_retry::RETRY_BASE_SECS);
+        let (targets, _) = select_snipes(&cfg, &feed, &sniped, &retries, later, 0.0);
+        assert_eq!(targets.iter().map(|(c, _)| c.symbol.as_str()).collect::<Vec<_>>(), vec!["FRESH"]);
+        assert!(sniped.is_empty());
+    }
+}
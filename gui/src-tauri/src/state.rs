@@ -1,11 +1,24 @@
 //! Application state management
 
+use crate::bot_status::Heartbeats;
+use crate::entry_script::EntryScript;
+use crate::live_feed::LiveTradeFeed;
+use crate::poll_interval::{AdaptiveInterval, AdaptivePollPolicy, ApiPressure, PollOutcome};
+use crate::price_freshness::PriceFreshnessPolicy;
+use crate::profile_failover::{failover_candidates, FailoverMonitor, FailoverTrigger, ProfileFailoverConfig};
+use crate::warmup::WarmupGate;
+use rugplay_core::CoinPrecision;
+use rugplay_networking::{ConnectionHealth, RetryConfig, RugplayClient};
 use rugplay_persistence::cache::CoinCache;
+use rugplay_persistence::sqlite::PoolConfig;
 use rugplay_persistence::{Database, TokenEncryptor};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// How often the pool monitor samples connection usage
+const POOL_MONITOR_INTERVAL_SECS: u64 = 5;
+
 /// Global application state shared across Tauri commands
 #[derive(Clone)]
 pub struct AppState {
@@ -14,6 +27,27 @@ pub struct AppState {
     pub data_dir: PathBuf,
     /// Shared coin cache for reducing API calls across all modules
     pub coin_cache: Arc<CoinCache>,
+    /// Connection failures counted across every client built by [`Self::api_client`]
+    pub connection_health: Arc<ConnectionHealth>,
+    /// Maximum quote age per trade decision
+    pub price_freshness: Arc<RwLock<PriceFreshnessPolicy>>,
+    /// Decimals sell amounts are truncated to, per coin
+    pub coin_precision: Arc<RwLock<CoinPrecision>>,
+    /// Last tick of each automation loop, for the bot status
+    pub heartbeats: Arc<Heartbeats>,
+    /// Bounds for the adaptive poll interval of the automation loops
+    pub poll_policy: Arc<RwLock<AdaptivePollPolicy>>,
+    /// Recent rate limits seen by any loop
+    pub api_pressure: Arc<ApiPressure>,
+    /// Backup profiles to switch to when the active one stops working
+    pub profile_failover: Arc<RwLock<ProfileFailoverConfig>>,
+    pub failover_monitor: Arc<FailoverMonitor>,
+    /// Holds automation back until the initial sync has loaded startup state
+    pub warmup: Arc<WarmupGate>,
+    /// Recent trades pushed over the live socket, when connected
+    pub live_feed: Arc<LiveTradeFeed>,
+    /// The user's compiled entry script, when one is enabled
+    pub entry_script: Arc<RwLock<Option<Arc<EntryScript>>>>,
 }
 
 impl AppState {
@@ -27,21 +61,250 @@ impl AppState {
             encryptor: Arc::new(encryptor),
             data_dir,
             coin_cache: Arc::new(CoinCache::default()),
+            connection_health: Arc::new(ConnectionHealth::default()),
+            price_freshness: Arc::new(RwLock::new(PriceFreshnessPolicy::default())),
+            coin_precision: Arc::new(RwLock::new(CoinPrecision::default())),
+            heartbeats: Arc::new(Heartbeats::default()),
+            poll_policy: Arc::new(RwLock::new(AdaptivePollPolicy::default())),
+            api_pressure: Arc::new(ApiPressure::default()),
+            profile_failover: Arc::new(RwLock::new(ProfileFailoverConfig::default())),
+            failover_monitor: Arc::new(FailoverMonitor::default()),
+            warmup: Arc::new(WarmupGate::default()),
+            live_feed: Arc::new(LiveTradeFeed::default()),
+            entry_script: Arc::new(RwLock::new(None)),
         })
     }
 
+    /// API client on the shared coin cache and connection health, retrying
+    /// transient read failures
+    pub fn api_client(&self, token: &str) -> RugplayClient {
+        RugplayClient::new_with_cache(token, self.coin_cache.clone(), RetryConfig::default())
+            .with_connection_health(self.connection_health.clone())
+    }
+
     /// Initialize the database connection
     pub async fn init_db(&self) -> Result<(), String> {
         let db_path = self.data_dir.join("rugplay.db");
-        let db = Database::connect(&db_path)
+        let pool_config = PoolConfig::from_env();
+        let db = Database::connect_with_config(&db_path, pool_config)
             .await
             .map_err(|e| e.to_string())?;
-        
+        tracing::info!(
+            "DB pool: max {} connections, {}s acquire timeout",
+            pool_config.max_connections,
+            pool_config.acquire_timeout.as_secs()
+        );
+
         let mut db_lock = self.db.write().await;
         *db_lock = Some(db);
         
         Ok(())
     }
+
+    /// Periodically sample the connection pool and warn when it is saturated,
+    /// i.e. every connection is checked out and new queries have to wait.
+    pub fn spawn_pool_monitor(&self) {
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let mut was_saturated = false;
+            loop {
+                tokio::time::sleep(std::time::Duration::from_secs(POOL_MONITOR_INTERVAL_SECS)).await;
+                let status = match db.read().await.as_ref() {
+                    Some(db) => db.pool_status(),
+                    None => continue,
+                };
+                let saturated = status.is_saturated();
+                if saturated && !was_saturated {
+                    tracing::warn!(
+                        "DB pool saturated: all {} connections in use — queries are queueing (raise RUGPLAY_DB_POOL_SIZE if this persists)",
+                        status.max_connections
+                    );
+                } else if !saturated && was_saturated {
+                    tracing::info!("DB pool recovered ({} of {} connections idle)", status.idle, status.size);
+                }
+                was_saturated = saturated;
+            }
+        });
+    }
+}
+
+/// Retune a loop's poll interval after a poll and restart its ticker when
+/// the period changes. Rate limits are shared so every loop backs off.
+pub async fn retune_poll_interval(
+    app_handle: &tauri::AppHandle,
+    adaptive: &mut AdaptiveInterval,
+    interval: &mut tokio::time::Interval,
+    outcome: PollOutcome,
+    module: &str,
+) {
+    use tauri::Manager;
+
+    let state = app_handle.state::<AppState>();
+    if outcome == PollOutcome::RateLimited {
+        state.api_pressure.record_rate_limit();
+    }
+    let throttled = state.api_pressure.is_throttled(std::time::Instant::now());
+    let policy = state.poll_policy.read().await.clone();
+
+    let next = adaptive.record(outcome, throttled, &policy);
+    if interval.period() != next {
+        *interval = tokio::time::interval_at(tokio::time::Instant::now() + next, next);
+        tracing::debug!("{}: poll interval now {:.1}s ({:?})", module, next.as_secs_f64(), outcome);
+    }
+
+    let failover = state.profile_failover.read().await.clone();
+    if !failover.enabled {
+        return;
+    }
+    let now = std::time::Instant::now();
+    if let Some(trigger) = state.failover_monitor.record_at(outcome, now, failover.rate_limit_window()) {
+        tracing::warn!("{}: active profile {}, failing over", module, trigger.reason());
+        let app_handle = app_handle.clone();
+        tokio::spawn(async move {
+            fail_over_profile(&app_handle, &failover.profile_ids, trigger).await;
+        });
+    }
+}
+
+/// Make the next listed profile whose token still works the active one.
+/// Automation picks it up on its next tick since every loop reads the
+/// active profile's token per poll. Tokens are verified without holding
+/// the database lock, so a slow API doesn't stall everything else.
+pub async fn fail_over_profile(app_handle: &tauri::AppHandle, order: &[i64], trigger: FailoverTrigger) {
+    use crate::app_event::{emit_event, AppEvent};
+    use crate::notifications::NotificationHandle;
+    use crate::profile_failover::ProfileFailoverEvent;
+    use rugplay_persistence::sqlite;
+    use tauri::Manager;
+
+    let state = app_handle.state::<AppState>();
+    let switched = async {
+        let (active, candidates) = {
+            let db_guard = state.db.read().await;
+            let db = db_guard.as_ref().ok_or("Database not initialized")?;
+            let active = sqlite::get_active_profile(db.pool()).await.map_err(|e| e.to_string())?;
+            let active_token = match &active {
+                Some(profile) => sqlite::get_profile_token(db.pool(), profile.id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .and_then(|encrypted| state.encryptor.decrypt(&encrypted).ok()),
+                None => None,
+            };
+
+            let mut candidates = Vec::new();
+            for id in failover_candidates(order, active.as_ref().map(|p| p.id)) {
+                let Some(encrypted) = sqlite::get_profile_token(db.pool(), id).await.map_err(|e| e.to_string())? else {
+                    continue;
+                };
+                let Ok(token) = state.encryptor.decrypt(&encrypted) else { continue };
+                // A duplicate of the active profile is the same account
+                if active_token.as_deref() == Some(token.as_str()) {
+                    tracing::warn!("Profile failover: skipping profile {} (same token as the active profile)", id);
+                    continue;
+                }
+                candidates.push((id, token));
+            }
+            (active, candidates)
+        };
+
+        for (id, token) in candidates {
+            match RugplayClient::new(&token).verify_auth().await {
+                Ok(user) => {
+                    let db_guard = state.db.read().await;
+                    let db = db_guard.as_ref().ok_or("Database not initialized")?;
+                    sqlite::set_active_profile(db.pool(), id).await.map_err(|e| e.to_string())?;
+                    let _ = sqlite::update_last_verified(db.pool(), id).await;
+                    return Ok::<_, String>(Some((active, id, user.username)));
+                }
+                Err(e) => tracing::warn!("Profile failover: skipping profile {} ({})", id, e),
+            }
+        }
+        Ok(None)
+    }
+    .await;
+
+    let notif = app_handle.try_state::<NotificationHandle>();
+    match switched {
+        Ok(Some((from, to_id, to_username))) => {
+            crate::commands::watchlist::sync_tradable_universe(app_handle).await;
+            let from_username = from.as_ref().map_or("unknown", |p| p.username.as_str()).to_string();
+            tracing::info!("Profile failover: @{} {}, switched to @{}", from_username, trigger.reason(), to_username);
+            let _ = emit_event(app_handle, AppEvent::ProfileFailover(ProfileFailoverEvent {
+                from_profile_id: from.map(|p| p.id),
+                to_profile_id: to_id,
+                username: to_username.clone(),
+                reason: trigger.reason(),
+            }));
+            if let Some(notif) = notif {
+                notif.notify_profile_failover(&from_username, &to_username, &trigger.reason()).await;
+            }
+        }
+        Ok(None) => {
+            tracing::warn!("Profile failover: no working backup profile ({})", trigger.reason());
+            if trigger == FailoverTrigger::TokenExpired {
+                if let Some(notif) = notif {
+                    notif.notify_session_expired().await;
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Profile failover failed: {}", e),
+    }
+
+    state.failover_monitor.finish();
+}
+
+/// Log, store and emit what a config save changed. Returns the changes
+/// (empty when the save changed nothing, which is not recorded).
+pub async fn record_config_change<T: serde::Serialize>(
+    app_handle: &tauri::AppHandle,
+    module: &str,
+    old: &T,
+    new: &T,
+) -> Vec<crate::config_diff::FieldChange> {
+    use crate::app_event::{emit_event, AppEvent};
+    use crate::config_diff::ConfigChangedEvent;
+    use rugplay_persistence::sqlite;
+    use tauri::Manager;
+
+    let changes = crate::config_diff::diff_configs(old, new);
+    if changes.is_empty() {
+        return changes;
+    }
+    let summary = changes.iter().map(|c| c.summary()).collect::<Vec<_>>().join(", ");
+    tracing::info!("{} config changed: {}", module, summary);
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    if let Some(db) = db_guard.as_ref() {
+        let json = serde_json::to_string(&changes).unwrap_or_default();
+        if let Err(e) = sqlite::append_config_change(db.pool(), module, &json).await {
+            tracing::warn!("Failed to record {} config change: {}", module, e);
+        }
+    }
+
+    let _ = emit_event(app_handle, AppEvent::ConfigChanged(ConfigChangedEvent {
+        module: module.to_string(),
+        changes: changes.clone(),
+    }));
+    changes
+}
+
+/// Automation log `details` for a trade, marked `"paper": true` when an
+/// automation module traded while paper trading is on
+pub fn paper_tagged_details(app_handle: &tauri::AppHandle, module: &str, action: &str, details: &str) -> String {
+    use crate::trade_executor::{is_automated_source, TradeExecutorHandle};
+    use tauri::Manager;
+
+    let paper = matches!(action, "BUY" | "SELL")
+        && is_automated_source(module)
+        && app_handle
+            .try_state::<TradeExecutorHandle>()
+            .is_some_and(|executor| executor.is_paper_trading());
+    if paper {
+        crate::paper_trading::tag_details(details)
+    } else {
+        details.to_string()
+    }
 }
 
 /// Write an entry to the centralized automation_log table.
@@ -77,7 +340,103 @@ pub async fn save_automation_log(
     .bind(coin_name)
     .bind(action)
     .bind(amount_usd)
-    .bind(details)
+    .bind(paper_tagged_details(app_handle, module, action, details))
     .execute(db.pool())
     .await;
 }
+
+/// Save a loop's in-memory state for the active profile on shutdown.
+/// Called from the sniper, mirror, dipbuyer and sentinel loops.
+pub async fn save_loop_snapshot<T: crate::loop_snapshot::LoopSnapshot>(
+    app_handle: &tauri::AppHandle,
+    snapshot: &T,
+) {
+    use tauri::Manager;
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    crate::loop_snapshot::save_snapshot(db, snapshot).await;
+}
+
+/// Take the loop state saved at the last shutdown, if any
+pub async fn restore_loop_snapshot<T: crate::loop_snapshot::LoopSnapshot>(
+    app_handle: &tauri::AppHandle,
+) -> Option<T> {
+    use tauri::Manager;
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    crate::loop_snapshot::restore_snapshot(db_guard.as_ref()?).await
+}
+
+/// Append an automation decision to the replayable decision log
+pub async fn record_decision<M: crate::decision_log::DecisionModel>(
+    app_handle: &tauri::AppHandle,
+    config: &M::Config,
+    input: &M::Input,
+    action: &crate::decision_log::DecisionAction,
+) {
+    use tauri::Manager;
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    crate::decision_log::record_decision::<M>(db, config, input, action).await;
+}
+
+/// Make sure the active profile has a sentinel protecting `symbol` after
+/// `fill`, a buy. Called from the sniper, mirror and dipbuyer. A paper fill
+/// bought nothing, so it gets no sentinel.
+pub async fn ensure_protective_sentinel(
+    app_handle: &tauri::AppHandle,
+    module: &str,
+    symbol: &str,
+    fill: &rugplay_core::TradeResponse,
+    config: &crate::protective_sentinel::ProtectiveSentinelConfig,
+) {
+    use crate::protective_sentinel::{upsert_protective_sentinel, StoredSentinelDefaults};
+    use rugplay_persistence::sqlite;
+    use tauri::Manager;
+
+    if fill.paper {
+        tracing::debug!("{}: paper buy of {}, no sentinel", module, symbol);
+        return;
+    }
+    let fill_price = fill.fill_price();
+
+    let state = app_handle.state::<AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let pool = db.pool();
+
+    let profile = match sqlite::get_active_profile(pool).await {
+        Ok(Some(p)) => p,
+        _ => return,
+    };
+    let defaults = StoredSentinelDefaults::load(pool).await;
+
+    // Only the portfolio knows the holding's weighted average and the market
+    // price after our buy; pricing from the fill skips the request
+    let token = if defaults.entry_price_source.needs_holding() {
+        match sqlite::get_profile_token(pool, profile.id).await {
+            Ok(Some(encrypted)) => state.encryptor.decrypt(&encrypted).ok(),
+            _ => None,
+        }
+    } else {
+        None
+    };
+    let holding = match token {
+        Some(token) => state.api_client(&token)
+            .get_portfolio()
+            .await
+            .ok()
+            .and_then(|p| p.coin_holdings.into_iter().find(|h| h.symbol == symbol)),
+        None => None,
+    };
+
+    match upsert_protective_sentinel(pool, profile.id, symbol, holding.as_ref(), fill_price, config, &defaults).await {
+        Ok(id) => tracing::info!("{}: sentinel #{} protecting {}", module, id, symbol),
+        Err(e) => tracing::error!("{}: failed to create sentinel for {}: {}", module, symbol, e),
+    }
+}
@@ -0,0 +1,59 @@
+//! Tick Budget — bounding the API work one poll can do
+//!
+//! Each dip candidate costs several API calls (coin, holders, portfolio), so
+//! a flooded trade feed could stretch one tick far past the poll interval
+//! and eat into the rate limit. Candidates are ranked most promising first
+//! and only the first `max` that reach the expensive checks are evaluated;
+//! the rest wait for the next tick.
+
+/// Sort `candidates` most promising first: largest `value` first, ties kept
+/// in feed order
+pub fn rank_candidates<T>(candidates: &mut [T], value: impl Fn(&T) -> f64) {
+    candidates.sort_by(|a, b| value(b).total_cmp(&value(a)));
+}
+
+/// Evaluations left in the current tick
+#[derive(Debug, Clone)]
+pub struct TickBudget {
+    max: u32,
+    used: u32,
+}
+
+impl TickBudget {
+    /// `max` of 0 means no limit
+    pub fn new(max: u32) -> Self {
+        Self { max, used: 0 }
+    }
+
+    /// Claim one evaluation; false once the budget is spent
+    pub fn take(&mut self) -> bool {
+        if self.max > 0 && self.used >= self.max {
+            return false;
+        }
+        self.used += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flooded_feed_evaluates_only_the_largest_sells() {
+        // 40 sells in feed order, values deliberately shuffled
+        let mut sells: Vec<(usize, f64)> = (0..40).map(|i| (i, ((i * 17) % 40) as f64 * 25.0)).collect();
+        rank_candidates(&mut sells, |(_, value)| *value);
+
+        let mut budget = TickBudget::new(5);
+        let (evaluated, deferred): (Vec<_>, Vec<_>) = sells.into_iter().partition(|_| budget.take());
+        let values: Vec<f64> = evaluated.iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, vec![975.0, 950.0, 925.0, 900.0, 875.0]);
+        assert_eq!(deferred.len(), 35);
+        assert!(deferred.iter().all(|(_, v)| *v < 875.0));
+
+        // No cap evaluates everything
+        let mut unlimited = TickBudget::new(0);
+        assert!((0..1000).all(|_| unlimited.take()));
+    }
+}
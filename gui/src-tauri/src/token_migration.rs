@@ -0,0 +1,202 @@
+//! Token Key Migration — moving profile tokens off the legacy key
+//!
+//! Tokens saved before machine-bound keys were encrypted with the all-zero
+//! [`LEGACY_KEY`]. Startup migrates them once; the same code backs the
+//! health check and the on-demand migration commands, so a DB imported
+//! later or a migration cut short can still be brought up to date.
+
+use rugplay_core::Result;
+use rugplay_persistence::encryption::EncryptedToken;
+use rugplay_persistence::sqlite;
+use rugplay_persistence::{TokenEncryptor, LEGACY_KEY};
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tracing::{error, info, warn};
+
+/// Which key a stored token decrypts with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenKeyStatus {
+    /// Machine-bound key; nothing to do
+    Current,
+    /// All-zero legacy key; needs migrating
+    Legacy,
+    /// Neither key works (corrupt, or encrypted on another machine)
+    Unreadable,
+}
+
+/// Key status of one saved profile
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileKeyHealth {
+    pub profile_id: i64,
+    pub username: String,
+    pub status: TokenKeyStatus,
+}
+
+/// Counts from one migration run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub checked: u32,
+    pub migrated: u32,
+    pub already_current: u32,
+    pub unreadable: u32,
+    /// Legacy tokens that couldn't be re-encrypted or saved
+    pub failed: u32,
+}
+
+fn legacy_encryptor() -> Result<TokenEncryptor> {
+    TokenEncryptor::new(&LEGACY_KEY)
+}
+
+/// Classify a token, trying the current key first
+pub fn classify_token(
+    current: &TokenEncryptor,
+    legacy: &TokenEncryptor,
+    token: &EncryptedToken,
+) -> TokenKeyStatus {
+    if current.decrypt(token).is_ok() {
+        TokenKeyStatus::Current
+    } else if legacy.decrypt(token).is_ok() {
+        TokenKeyStatus::Legacy
+    } else {
+        TokenKeyStatus::Unreadable
+    }
+}
+
+/// Key status of every saved profile
+pub async fn check_profiles(
+    pool: &SqlitePool,
+    current: &TokenEncryptor,
+) -> Result<Vec<ProfileKeyHealth>> {
+    let legacy = legacy_encryptor()?;
+    let mut health = Vec::new();
+
+    for profile in sqlite::list_profiles(pool).await? {
+        let Some(token) = sqlite::get_profile_token(pool, profile.id).await? else {
+            continue;
+        };
+        health.push(ProfileKeyHealth {
+            profile_id: profile.id,
+            username: profile.username,
+            status: classify_token(current, &legacy, &token),
+        });
+    }
+
+    Ok(health)
+}
+
+/// Re-encrypt legacy tokens with the current key.
+///
+/// Covers every profile, or only `profile_ids` when given. Tokens already
+/// on the current key are left alone, so running it again is harmless.
+pub async fn migrate_profiles(
+    pool: &SqlitePool,
+    current: &TokenEncryptor,
+    profile_ids: Option<&[i64]>,
+) -> Result<MigrationReport> {
+    let legacy = legacy_encryptor()?;
+    let mut report = MigrationReport::default();
+
+    for profile in sqlite::list_profiles(pool).await? {
+        if profile_ids.is_some_and(|ids| !ids.contains(&profile.id)) {
+            continue;
+        }
+
+        let token = match sqlite::get_profile_token(pool, profile.id).await {
+            Ok(Some(token)) => token,
+            Ok(None) => continue,
+            Err(e) => {
+                warn!("Could not read token for profile {}: {}", profile.id, e);
+                report.failed += 1;
+                continue;
+            }
+        };
+        report.checked += 1;
+
+        if current.decrypt(&token).is_ok() {
+            report.already_current += 1;
+            continue;
+        }
+
+        let plaintext = match legacy.decrypt(&token) {
+            Ok(plaintext) => plaintext,
+            Err(_) => {
+                warn!(
+                    "Profile {} token cannot be decrypted with either key — token may be corrupt or from another machine",
+                    profile.id
+                );
+                report.unreadable += 1;
+                continue;
+            }
+        };
+
+        info!(
+            "Profile {} ({}): migrating from legacy key to machine key",
+            profile.id, profile.username
+        );
+        let saved = match current.encrypt(&plaintext) {
+            Ok(encrypted) => sqlite::update_profile_token(pool, profile.id, &encrypted).await,
+            Err(e) => Err(e),
+        };
+        match saved {
+            Ok(()) => report.migrated += 1,
+            Err(e) => {
+                error!("Failed to migrate token for profile {}: {}", profile.id, e);
+                report.failed += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rugplay_persistence::Database;
+
+    #[tokio::test]
+    async fn test_migrates_only_legacy_profiles() {
+        let db = Database::connect_in_memory().await.unwrap();
+        let pool = db.pool();
+        let current = TokenEncryptor::new(&[7u8; 32]).unwrap();
+        let legacy = legacy_encryptor().unwrap();
+        let other_machine = TokenEncryptor::new(&[9u8; 32]).unwrap();
+
+        let old_a = sqlite::create_profile(pool, "old_a", None, &legacy.encrypt("token_a").unwrap()).await.unwrap();
+        let old_b = sqlite::create_profile(pool, "old_b", None, &legacy.encrypt("token_b").unwrap()).await.unwrap();
+        let fresh = sqlite::create_profile(pool, "fresh", None, &current.encrypt("token_c").unwrap()).await.unwrap();
+        let foreign = sqlite::create_profile(pool, "foreign", None, &other_machine.encrypt("token_d").unwrap()).await.unwrap();
+
+        let status = |health: &[ProfileKeyHealth], id: i64| {
+            health.iter().find(|h| h.profile_id == id).unwrap().status
+        };
+        let health = check_profiles(pool, &current).await.unwrap();
+        assert_eq!(status(&health, old_a), TokenKeyStatus::Legacy);
+        assert_eq!(status(&health, fresh), TokenKeyStatus::Current);
+        assert_eq!(status(&health, foreign), TokenKeyStatus::Unreadable);
+
+        // Only the requested profile is touched
+        let report = migrate_profiles(pool, &current, Some(&[old_a])).await.unwrap();
+        assert_eq!(report, MigrationReport { checked: 1, migrated: 1, ..Default::default() });
+        let token = sqlite::get_profile_token(pool, old_a).await.unwrap().unwrap();
+        assert_eq!(current.decrypt(&token).unwrap(), "token_a");
+        let health = check_profiles(pool, &current).await.unwrap();
+        assert_eq!(status(&health, old_b), TokenKeyStatus::Legacy);
+
+        let report = migrate_profiles(pool, &current, None).await.unwrap();
+        assert_eq!(
+            report,
+            MigrationReport { checked: 4, migrated: 1, already_current: 2, unreadable: 1, failed: 0 }
+        );
+        let token = sqlite::get_profile_token(pool, old_b).await.unwrap().unwrap();
+        assert_eq!(current.decrypt(&token).unwrap(), "token_b");
+
+        // A second run finds nothing left to do
+        let report = migrate_profiles(pool, &current, None).await.unwrap();
+        assert_eq!(report.migrated, 0);
+        assert_eq!(report.already_current, 3);
+    }
+}
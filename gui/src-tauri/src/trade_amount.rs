@@ -0,0 +1,110 @@
+//! Trade Amount — order sizes in dollars or in coins
+//!
+//! The API takes buys in USD and sells in coins. A caller that thinks in
+//! the other unit ("sell $200 worth") submits a [`TradeAmount`] instead of
+//! dividing by a price it fetched itself: the executor converts it with a
+//! fresh quote just before the order goes out, so a price that moved while
+//! the order was queued doesn't skew the size.
+
+use rugplay_core::TradeType;
+use serde::{Deserialize, Serialize};
+
+/// The unit an order size is given in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AmountUnit {
+    Usd,
+    Coins,
+}
+
+impl AmountUnit {
+    /// The unit the API takes for `trade_type`: USD for buys, coins for sells
+    pub fn native(trade_type: TradeType) -> Self {
+        match trade_type {
+            TradeType::Buy => Self::Usd,
+            TradeType::Sell => Self::Coins,
+        }
+    }
+}
+
+/// An order size with its unit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradeAmount {
+    Usd(f64),
+    Coins(f64),
+}
+
+impl TradeAmount {
+    pub fn new(value: f64, unit: AmountUnit) -> Self {
+        match unit {
+            AmountUnit::Usd => Self::Usd(value),
+            AmountUnit::Coins => Self::Coins(value),
+        }
+    }
+
+    /// `value` in the unit the API takes for `trade_type`
+    pub fn native(trade_type: TradeType, value: f64) -> Self {
+        Self::new(value, AmountUnit::native(trade_type))
+    }
+
+    pub fn value(&self) -> f64 {
+        match *self {
+            Self::Usd(v) | Self::Coins(v) => v,
+        }
+    }
+
+    pub fn unit(&self) -> AmountUnit {
+        match self {
+            Self::Usd(_) => AmountUnit::Usd,
+            Self::Coins(_) => AmountUnit::Coins,
+        }
+    }
+
+    /// Whether `price` is needed to send this as a `trade_type` order
+    pub fn needs_price(&self, trade_type: TradeType) -> bool {
+        self.unit() != AmountUnit::native(trade_type)
+    }
+
+    /// The amount to send for a `trade_type` order, converting at `price`
+    /// if it is in the other unit. Coin amounts are truncated to the
+    /// server's 8 decimals.
+    pub fn resolve(&self, trade_type: TradeType, price: Option<f64>) -> Result<f64, String> {
+        if !self.needs_price(trade_type) {
+            return Ok(self.value());
+        }
+        let price = price
+            .filter(|p| p.is_finite() && *p > 0.0)
+            .ok_or_else(|| format!("No valid price to convert {:?} to {:?}", self, AmountUnit::native(trade_type)))?;
+        let amount = match self {
+            Self::Usd(usd) => (usd / price * 1e8).floor() / 1e8,
+            Self::Coins(coins) => coins * price,
+        };
+        if amount <= 0.0 {
+            return Err(format!("{:?} is less than the smallest tradable amount at ${}", self, price));
+        }
+        Ok(amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usd_sell_resolves_to_coins_at_the_given_price() {
+        let sell = TradeAmount::Usd(200.0);
+        assert!(sell.needs_price(TradeType::Sell));
+        assert_eq!(sell.resolve(TradeType::Sell, Some(0.5)).unwrap(), 400.0);
+        assert_eq!(sell.resolve(TradeType::Sell, Some(3.0)).unwrap(), 66.66666666);
+        assert!(sell.resolve(TradeType::Sell, None).is_err());
+        assert!(sell.resolve(TradeType::Sell, Some(f64::NAN)).is_err());
+        assert!(TradeAmount::Usd(1e-9).resolve(TradeType::Sell, Some(1.0)).is_err());
+
+        // Native units pass through untouched and need no price
+        let coins = TradeAmount::native(TradeType::Sell, 12.5);
+        assert_eq!(coins, TradeAmount::Coins(12.5));
+        assert_eq!(coins.resolve(TradeType::Sell, None).unwrap(), 12.5);
+        assert_eq!(TradeAmount::Usd(50.0).resolve(TradeType::Buy, None).unwrap(), 50.0);
+        assert_eq!(TradeAmount::Coins(10.0).resolve(TradeType::Buy, Some(2.0)).unwrap(), 20.0);
+    }
+}
@@ -0,0 +1,430 @@
+//! Trade Approval — semi-auto mode, where automation proposes and the user decides
+//!
+//! With semi-auto on, an order a module submits is checked as usual but
+//! then held instead of queued: it shows up as a pending approval (with its
+//! structured reason, so the user sees why the module wants it) until the
+//! user approves or rejects it, or it expires. The submitting module gets a
+//! [`Submission::Held`](crate::trade_executor::Submission) back at once and
+//! keeps ticking; it tracks the proposal in [`HeldOrders`] and finishes its
+//! bookkeeping (stats, logs, a protective sentinel) when the approved order
+//! fills. An approved order is dropped if its price moved too far since the
+//! module quoted it. Protective sentinel sells are never held: a held
+//! stop-loss would leave the position unguarded until it was decided.
+//! Settings key `semi_auto`.
+
+use crate::app_event::{emit_event, AppEvent};
+use crate::trade_amount::AmountUnit;
+use crate::trade_executor::{is_protective_source, HeldTrade, TradeExecutorHandle};
+use crate::trade_reason::TradeReason;
+use rugplay_core::{TradeResponse, TradeType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::Manager;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::oneshot::error::TryRecvError;
+use tracing::warn;
+
+/// Approval events buffered per subscriber
+pub const APPROVAL_CHANNEL_CAPACITY: usize = 64;
+
+/// Semi-auto settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SemiAutoConfig {
+    /// Hold automated orders for approval instead of executing them
+    pub enabled: bool,
+    /// Unanswered proposals are rejected after this long
+    pub expiry_secs: u64,
+    /// An approved order is dropped if the coin's price moved more than this
+    /// (percent) from the module's quote while it waited. 0 disables the check.
+    #[serde(default = "default_max_price_move_pct")]
+    pub max_price_move_pct: f64,
+}
+
+fn default_max_price_move_pct() -> f64 {
+    10.0
+}
+
+impl Default for SemiAutoConfig {
+    fn default() -> Self {
+        Self { enabled: false, expiry_secs: 120, max_price_move_pct: default_max_price_move_pct() }
+    }
+}
+
+impl SemiAutoConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(15..=3600).contains(&self.expiry_secs) {
+            return Err("Approval expiry must be between 15 seconds and 1 hour".to_string());
+        }
+        if !(0.0..=100.0).contains(&self.max_price_move_pct) {
+            return Err("Max price move must be between 0% and 100%".to_string());
+        }
+        Ok(())
+    }
+
+    /// Whether an order from `source` waits for approval. Orders the user
+    /// placed (`automated` false) and protective sells never do.
+    pub fn requires_approval(&self, automated: bool, source: &str) -> bool {
+        self.enabled && automated && !is_protective_source(source)
+    }
+}
+
+/// An automated order waiting for the user's decision
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingApproval {
+    /// Approval id, assigned when the order is held
+    pub id: u64,
+    pub symbol: String,
+    pub trade_type: TradeType,
+    pub amount: f64,
+    pub amount_unit: AmountUnit,
+    pub reason: String,
+    pub reason_detail: TradeReason,
+    pub source: String,
+    pub proposed_at: i64,
+    pub expires_at: i64,
+}
+
+/// Refuse an approved order whose coin moved more than `max_move_pct` from
+/// the price it was proposed at
+pub fn check_price_move(symbol: &str, quoted: f64, current: f64, max_move_pct: f64) -> Result<(), String> {
+    if max_move_pct <= 0.0 || quoted <= 0.0 {
+        return Ok(());
+    }
+    let moved_pct = (current - quoted).abs() / quoted * 100.0;
+    if !moved_pct.is_finite() || moved_pct > max_move_pct {
+        return Err(format!(
+            "{} moved {:.1}% since it was proposed (${:.8} -> ${:.8}), over the {:.1}% allowed",
+            symbol, moved_pct, quoted, current, max_move_pct
+        ));
+    }
+    Ok(())
+}
+
+/// Held orders by approval id. `T` is the order itself, handed back once
+/// it is decided.
+#[derive(Debug)]
+pub struct ApprovalQueue<T> {
+    next_id: u64,
+    items: BTreeMap<u64, (PendingApproval, T)>,
+}
+
+impl<T> Default for ApprovalQueue<T> {
+    fn default() -> Self {
+        Self { next_id: 0, items: BTreeMap::new() }
+    }
+}
+
+impl<T> ApprovalQueue<T> {
+    /// Hold `item` until it is decided; returns the approval with its id
+    pub fn hold(&mut self, mut approval: PendingApproval, item: T) -> PendingApproval {
+        self.next_id += 1;
+        approval.id = self.next_id;
+        self.items.insert(approval.id, (approval.clone(), item));
+        approval
+    }
+
+    /// Claim a held order for a decision. `None` if it was already decided
+    /// or expired.
+    pub fn take(&mut self, id: u64) -> Option<(PendingApproval, T)> {
+        self.items.remove(&id)
+    }
+
+    /// Remove every held order that expired by `now` (unix seconds)
+    pub fn take_expired(&mut self, now: i64) -> Vec<(PendingApproval, T)> {
+        let expired: Vec<u64> = self.items.values().filter(|(a, _)| a.expires_at <= now).map(|(a, _)| a.id).collect();
+        expired.into_iter().filter_map(|id| self.items.remove(&id)).collect()
+    }
+
+    /// Remove every held order at once
+    pub fn drain(&mut self) -> Vec<(PendingApproval, T)> {
+        std::mem::take(&mut self.items).into_values().collect()
+    }
+
+    /// Held orders, oldest first
+    pub fn list(&self) -> Vec<PendingApproval> {
+        self.items.values().map(|(a, _)| a.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// A module's proposals still waiting for a decision, each with the context
+/// `C` the module needs to finish its bookkeeping once the order fills
+#[derive(Debug)]
+pub struct HeldOrders<C> {
+    held: Vec<(C, HeldTrade)>,
+}
+
+impl<C> Default for HeldOrders<C> {
+    fn default() -> Self {
+        Self { held: Vec::new() }
+    }
+}
+
+impl<C> HeldOrders<C> {
+    /// Track a held order until it is decided
+    pub fn hold(&mut self, held: HeldTrade, context: C) {
+        self.held.push((context, held));
+    }
+
+    /// Whether a proposal for `symbol` is still waiting
+    pub fn is_held(&self, symbol: &str) -> bool {
+        self.held.iter().any(|(_, held)| held.approval.symbol == symbol)
+    }
+
+    /// The context of every proposal still waiting
+    pub fn contexts(&self) -> impl Iterator<Item = &C> {
+        self.held.iter().map(|(context, _)| context)
+    }
+
+    /// Proposals whose order has been decided: the fill if it was approved
+    /// and executed, otherwise why not (rejected, expired, failed)
+    pub fn take_settled(&mut self) -> Vec<(C, Result<TradeResponse, String>)> {
+        let mut settled = Vec::new();
+        let mut waiting = Vec::with_capacity(self.held.len());
+        for (context, mut held) in self.held.drain(..) {
+            match held.result.try_recv() {
+                Ok(result) => settled.push((context, result)),
+                Err(TryRecvError::Empty) => waiting.push((context, held)),
+                Err(TryRecvError::Closed) => {
+                    settled.push((context, Err(format!("Approval #{} was dropped", held.approval.id))));
+                }
+            }
+        }
+        self.held = waiting;
+        settled
+    }
+
+    pub fn len(&self) -> usize {
+        self.held.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.held.is_empty()
+    }
+}
+
+/// What happened to a proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ApprovalStatus {
+    Proposed,
+    Approved,
+    Rejected,
+    Expired,
+}
+
+/// Event emitted whenever a proposal is made or decided
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeApprovalEvent {
+    pub status: ApprovalStatus,
+    pub approval: PendingApproval,
+    /// Proposals still waiting after this one
+    pub pending_count: usize,
+}
+
+/// Forward approval events to the frontend and alert on new proposals
+pub fn spawn_approval_listener(app_handle: tauri::AppHandle, executor: &TradeExecutorHandle) {
+    let mut approvals = executor.subscribe_approvals();
+    tokio::spawn(async move {
+        loop {
+            let event = match approvals.recv().await {
+                Ok(event) => event,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("Trade approval listener fell behind, {} events missed", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            if event.status == ApprovalStatus::Proposed {
+                if let Some(notif) = app_handle.try_state::<crate::notifications::NotificationHandle>() {
+                    notif.notify_approval_needed(&event.approval.symbol, &event.approval.reason).await;
+                }
+            }
+            if let Err(e) = emit_event(&app_handle, AppEvent::TradeApproval(event)) {
+                warn!("Failed to emit trade-approval event: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_executor::{spawn_recording_executor, AutomatedTrade, Submission, TradePriority};
+    use crate::trade_amount::TradeAmount;
+
+    fn approval(symbol: &str, proposed_at: i64, expires_at: i64) -> PendingApproval {
+        let reason = TradeReason::Sniper { symbol: symbol.to_string(), age_secs: 60, market_cap: 1_000.0 };
+        PendingApproval {
+            id: 0,
+            symbol: symbol.to_string(),
+            trade_type: TradeType::Buy,
+            amount: 10.0,
+            amount_unit: AmountUnit::Usd,
+            reason: reason.to_string(),
+            reason_detail: reason,
+            source: "sniper".to_string(),
+            proposed_at,
+            expires_at,
+        }
+    }
+
+    #[test]
+    fn test_queue_holds_until_decided_or_expired() {
+        let config = SemiAutoConfig { enabled: true, ..Default::default() };
+        assert!(config.requires_approval(true, "sniper"));
+        assert!(!config.requires_approval(true, "sentinel"));
+        assert!(!config.requires_approval(false, "manual"));
+        assert!(!SemiAutoConfig::default().requires_approval(true, "sniper"));
+        assert!(SemiAutoConfig { expiry_secs: 5, ..config }.validate().is_err());
+        // Settings saved before the protective switch was removed still load
+        let old: SemiAutoConfig =
+            serde_json::from_str(r#"{"enabled":true,"expirySecs":120,"autoProtectiveSells":false}"#).unwrap();
+        assert!(!old.requires_approval(true, "sentinel"));
+
+        let mut queue = ApprovalQueue::default();
+        let a = queue.hold(approval("AAA", 100, 220), "a");
+        let b = queue.hold(approval("BBB", 150, 270), "b");
+        assert_eq!((a.id, b.id), (1, 2));
+        assert_eq!(queue.list().iter().map(|a| a.symbol.as_str()).collect::<Vec<_>>(), vec!["AAA", "BBB"]);
+
+        // Only the first has run out
+        assert!(queue.take_expired(219).is_empty());
+        let expired = queue.take_expired(220);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].1, "a");
+
+        // A decided proposal can't be decided again
+        assert_eq!(queue.take(b.id).map(|(_, item)| item), Some("b"));
+        assert!(queue.take(b.id).is_none());
+        assert!(queue.take(a.id).is_none());
+        assert!(queue.is_empty());
+    }
+
+    fn proposal(symbol: &str, source: &str) -> AutomatedTrade {
+        AutomatedTrade {
+            symbol: symbol.to_string(),
+            trade_type: TradeType::Buy,
+            amount: TradeAmount::Usd(10.0),
+            priority: TradePriority::High,
+            reason: TradeReason::Sniper { symbol: symbol.to_string(), age_secs: 60, market_cap: 1_000.0 },
+            source: source.to_string(),
+            quoted_price: Some(0.01),
+        }
+    }
+
+    async fn submit_held(executor: &TradeExecutorHandle, trade: AutomatedTrade) -> HeldTrade {
+        match executor.submit_automated(trade).await.unwrap() {
+            Submission::Held(held) => held,
+            Submission::Filled(_) => panic!("order was not held"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_semi_auto_executes_nothing_until_approved() {
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_semi_auto_config(SemiAutoConfig { enabled: true, ..Default::default() }).await;
+        let mut events = executor.subscribe_approvals();
+
+        // Both return at once with their proposal instead of waiting on the user
+        let mut held = HeldOrders::default();
+        let approved = submit_held(&executor, proposal("AAA", "sniper")).await;
+        let rejected = submit_held(&executor, proposal("BBB", "dipbuyer")).await;
+        let (approved_id, rejected_id) = (approved.approval.id, rejected.approval.id);
+        held.hold(approved, "AAA");
+        held.hold(rejected, "BBB");
+        for _ in 0..2 {
+            assert_eq!(events.recv().await.unwrap().status, ApprovalStatus::Proposed);
+        }
+        assert!(held.is_held("AAA") && held.is_held("BBB"));
+        assert!(held.take_settled().is_empty());
+        assert_eq!(executor.list_pending_approvals().await.len(), 2);
+        assert!(executor.list_pending_trades().await.is_empty());
+        assert!(recorder.trades().is_empty());
+
+        // Rejected: settles as an error and nothing is sent
+        executor.reject_pending(rejected_id).await.unwrap();
+        let settled = held.take_settled();
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].0, "BBB");
+        assert!(settled[0].1.is_err());
+        assert!(recorder.trades().is_empty());
+
+        // Approved: the order runs and the module picks up the fill
+        executor.approve_pending(approved_id).await.unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let settled = loop {
+            let settled = held.take_settled();
+            if !settled.is_empty() || std::time::Instant::now() > deadline {
+                break settled;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        };
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].0, "AAA");
+        assert!(settled[0].1.is_ok());
+        assert!(held.is_empty());
+        assert_eq!(recorder.trades().iter().map(|t| t.symbol.as_str()).collect::<Vec<_>>(), vec!["AAA"]);
+        assert!(executor.approve_pending(approved_id).await.is_err());
+        assert!(executor.list_pending_approvals().await.is_empty());
+
+        // Protective sells go straight through
+        let sell = AutomatedTrade { trade_type: TradeType::Sell, source: "sentinel".to_string(), ..proposal("CCC", "") };
+        assert!(matches!(executor.submit_automated(sell).await.unwrap(), Submission::Filled(_)));
+        assert_eq!(recorder.trades().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_protective_sells_run_while_a_proposal_waits() {
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_semi_auto_config(SemiAutoConfig { enabled: true, expiry_secs: 3600, ..Default::default() }).await;
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+
+        let mut held = submit_held(&executor, proposal("AAA", "sniper")).await;
+
+        // The sentinel's sells complete while the buy is still undecided
+        for symbol in ["BBB", "CCC"] {
+            let sell = executor.submit_trade(
+                symbol.to_string(),
+                TradeType::Sell,
+                5.0,
+                TradePriority::High,
+                reason.clone(),
+                "sentinel",
+            );
+            let filled = tokio::time::timeout(std::time::Duration::from_secs(5), sell).await;
+            filled.expect("sentinel sell was held").unwrap();
+        }
+        assert_eq!(recorder.trades().iter().map(|t| t.symbol.as_str()).collect::<Vec<_>>(), vec!["BBB", "CCC"]);
+        assert_eq!(executor.list_pending_approvals().await.len(), 1);
+        assert!(held.result.try_recv().is_err());
+
+        executor.reject_all_pending("test").await;
+        assert!(held.result.await.unwrap().is_err());
+    }
+
+    #[test]
+    fn test_approved_order_is_dropped_when_the_price_ran() {
+        assert!(check_price_move("AAA", 1.0, 1.09, 10.0).is_ok());
+        assert!(check_price_move("AAA", 1.0, 0.91, 10.0).is_ok());
+        assert!(check_price_move("AAA", 1.0, 1.2, 10.0).unwrap_err().contains("moved 20.0%"));
+        assert!(check_price_move("AAA", 1.0, 0.5, 10.0).is_err());
+        // 0 turns the check off
+        assert!(check_price_move("AAA", 1.0, 5.0, 0.0).is_ok());
+        // Settings saved before the check existed get the default
+        let old: SemiAutoConfig = serde_json::from_str(r#"{"enabled":true,"expirySecs":120}"#).unwrap();
+        assert_eq!(old.max_price_move_pct, 10.0);
+        assert!(SemiAutoConfig { max_price_move_pct: 150.0, ..old }.validate().is_err());
+    }
+}
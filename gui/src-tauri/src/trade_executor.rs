@@ -3,29 +3,40 @@
 //! All trades flow through this executor to enforce rate limiting,
 //! priority ordering, risk validation, retry logic, and event emission.
 
-use rugplay_core::{TradeRequest, TradeResponse, TradeType, truncate_to_8_decimals};
+use crate::bot_status::ExecutorStatus;
+use crate::min_trade::{self, MinTradeCheck};
+use crate::order_split::{self, SplitFill, SplitSellConfig};
+use crate::paper_trading;
+use crate::poll_interval::is_rate_limited;
+use crate::round_trip::{self, PoolReserves, RoundTrip};
+use crate::snipe_retry::is_not_yet_tradable;
+use crate::trade_approval::{
+    self, ApprovalQueue, ApprovalStatus, PendingApproval, SemiAutoConfig, TradeApprovalEvent, APPROVAL_CHANNEL_CAPACITY,
+};
+use crate::trade_amount::{AmountUnit, TradeAmount};
+use crate::trade_outcome::{TradeOutcome, TradeResolution, OUTCOME_CHANNEL_CAPACITY};
+use crate::trade_pacing::{ExecutorStats, PacingBounds, RequestOutcome, TradePacer};
+use crate::trade_reason::TradeReason;
+use crate::trade_reconcile::{self, Reconciliation};
+use crate::warmup::WarmupGate;
+use crate::watchlist::TradableUniverse;
+use rugplay_core::{parse_api_timestamp, truncate_to_8_decimals, ApiTransaction, TradeRequest, TradeResponse, TradeType};
+use rugplay_engine::risk::DrawdownGuard;
 use rugplay_networking::RugplayClient;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::sync::Arc;
-use tauri::Emitter;
-use tokio::sync::{mpsc, oneshot, RwLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
 use tracing::{debug, error, info, warn};
 
 /// Maximum number of orders that can be queued in the priority heap
 const MAX_QUEUE_DEPTH: usize = 1000;
 
-/// Try to get the NotificationHandle without panicking if not yet registered
-fn try_notify(app_handle: &tauri::AppHandle) -> Option<crate::notifications::NotificationHandle> {
-    use tauri::Manager;
-    app_handle
-        .try_state::<crate::notifications::NotificationHandle>()
-        .map(|s: tauri::State<'_, crate::notifications::NotificationHandle>| s.inner().clone())
-}
-
 /// Priority levels for trade orders
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum TradePriority {
     /// Normal manual trades
     Normal = 0,
@@ -50,15 +61,137 @@ impl Ord for TradePriority {
 /// A trade order submitted to the executor
 #[derive(Debug)]
 pub struct TradeOrder {
+    /// Pending-list id, assigned on submission
+    pub id: u64,
     pub symbol: String,
     pub trade_type: TradeType,
     pub amount: f64,
+    /// Unit of `amount`. One other than the API's for the trade type (a
+    /// sell in USD) is converted at a fresh price just before execution.
+    pub amount_unit: AmountUnit,
     pub priority: TradePriority,
-    pub reason: String,
+    pub reason: TradeReason,
+    /// Module or surface that submitted the order (e.g. "sniper", "mobile")
+    pub source: String,
+    /// Skip the frozen reserve check (manual trades only)
+    pub override_reserve: bool,
+    /// Simulated instead of sent (paper trading), set on submission
+    pub paper: bool,
+    /// Price the submitting module decided at, if it quoted one
+    pub quoted_price: Option<f64>,
+    /// Set when a held order is approved: the most (percent) the price may
+    /// have moved from `quoted_price` while it waited
+    pub max_price_move_pct: Option<f64>,
     /// Channel to send the result back to the caller
     pub result_tx: oneshot::Sender<Result<TradeResponse, String>>,
 }
 
+/// An automation module's order, as [`TradeExecutorHandle::submit_automated`] takes it
+#[derive(Debug, Clone)]
+pub struct AutomatedTrade {
+    pub symbol: String,
+    pub trade_type: TradeType,
+    pub amount: TradeAmount,
+    pub priority: TradePriority,
+    pub reason: TradeReason,
+    pub source: String,
+    /// Price the module decided at; an order held for approval is dropped
+    /// if the price moved too far from it by the time it is approved
+    pub quoted_price: Option<f64>,
+}
+
+/// What became of a submitted automated order
+#[derive(Debug)]
+pub enum Submission {
+    Filled(TradeResponse),
+    /// Held for the user's approval (semi-auto mode)
+    Held(HeldTrade),
+}
+
+/// An order held for approval, with the channel its result arrives on once
+/// it is decided (and, if approved, executed)
+#[derive(Debug)]
+pub struct HeldTrade {
+    pub approval: PendingApproval,
+    pub result: oneshot::Receiver<Result<TradeResponse, String>>,
+}
+
+/// Source tag for trades the user places from the trade dialog
+pub const MANUAL_SOURCE: &str = "manual";
+
+/// Source tag for the orders of a user-started portfolio rebalance
+pub const REBALANCE_SOURCE: &str = "rebalance";
+
+/// Sources the automation throttle never holds back: protective sells and
+/// trades the user places by hand
+const THROTTLE_EXEMPT_SOURCES: &[&str] = &["sentinel", MANUAL_SOURCE, "mobile", REBALANCE_SOURCE];
+
+/// Sources of orders the user placed themselves
+const USER_SOURCES: &[&str] = &[MANUAL_SOURCE, "mobile", REBALANCE_SOURCE];
+
+/// Sources whose orders protect a position rather than open one
+const PROTECTIVE_SOURCES: &[&str] = &["sentinel"];
+
+/// Whether orders from `source` are placed by a module rather than the user
+pub fn is_automated_source(source: &str) -> bool {
+    !USER_SOURCES.contains(&source)
+}
+
+/// Whether orders from `source` protect a position (stop-loss, take-profit)
+pub fn is_protective_source(source: &str) -> bool {
+    PROTECTIVE_SOURCES.contains(&source)
+}
+
+impl TradeOrder {
+    /// A user-initiated order. Manual orders run at Normal priority, so they
+    /// still go through the hard risk limits in the executor loop.
+    pub fn manual(
+        symbol: String,
+        trade_type: TradeType,
+        amount: TradeAmount,
+        reason: TradeReason,
+        source: &str,
+        override_reserve: bool,
+        result_tx: oneshot::Sender<Result<TradeResponse, String>>,
+    ) -> Self {
+        Self {
+            id: 0,
+            symbol,
+            trade_type,
+            amount: amount.value(),
+            amount_unit: amount.unit(),
+            priority: TradePriority::Normal,
+            reason,
+            source: source.to_string(),
+            override_reserve,
+            paper: false,
+            quoted_price: None,
+            max_price_move_pct: None,
+            result_tx,
+        }
+    }
+
+    /// Whether the buy risk limits apply (Critical orders are exits and skip them)
+    pub fn is_risk_checked(&self) -> bool {
+        matches!(self.trade_type, TradeType::Buy) && self.priority != TradePriority::Critical
+    }
+
+    /// Whether a module placed the order rather than the user
+    pub fn is_automated(&self) -> bool {
+        is_automated_source(&self.source)
+    }
+
+    /// Whether the order protects a position rather than opening one
+    pub fn is_protective(&self) -> bool {
+        is_protective_source(&self.source)
+    }
+
+    /// Whether the minimum interval between automated trades applies
+    pub fn is_throttled(&self) -> bool {
+        self.priority != TradePriority::Critical && !THROTTLE_EXEMPT_SOURCES.contains(&self.source.as_str())
+    }
+}
+
 /// Wrapper for BinaryHeap ordering (higher priority first)
 struct PrioritizedOrder {
     order: TradeOrder,
@@ -90,6 +223,65 @@ impl Ord for PrioritizedOrder {
     }
 }
 
+/// Pop the highest priority order allowed to run. While `throttled`,
+/// automated orders are skipped and stay queued so exempt ones can pass.
+fn pop_next(heap: &mut BinaryHeap<PrioritizedOrder>, throttled: bool) -> Option<PrioritizedOrder> {
+    if !throttled {
+        return heap.pop();
+    }
+    let mut held = Vec::new();
+    let mut next = None;
+    while let Some(prioritized) = heap.pop() {
+        if prioritized.order.is_throttled() {
+            held.push(prioritized);
+        } else {
+            next = Some(prioritized);
+            break;
+        }
+    }
+    heap.extend(held);
+    next
+}
+
+/// Push an incoming order onto the heap, rejecting it if the queue is full
+async fn queue_order(
+    heap: &mut BinaryHeap<PrioritizedOrder>,
+    seq: &mut u64,
+    order: TradeOrder,
+    pending: &RwLock<PendingRegistry>,
+) {
+    if heap.len() >= MAX_QUEUE_DEPTH {
+        warn!("Trade executor queue full ({} orders), rejecting order for {}", MAX_QUEUE_DEPTH, order.symbol);
+        pending.write().await.take(order.id);
+        let _ = order.result_tx.send(Err(format!(
+            "Trade queue full ({} orders) — try again later", MAX_QUEUE_DEPTH
+        )));
+        return;
+    }
+    *seq += 1;
+    heap.push(PrioritizedOrder { order, seq: *seq });
+}
+
+/// Spaces automated trades at least `min_automated_interval_secs` apart
+#[derive(Debug, Default)]
+struct AutomationThrottle {
+    last_at: Option<Instant>,
+}
+
+impl AutomationThrottle {
+    /// Time left before the next automated trade may run
+    fn remaining(&self, now: Instant, interval: Duration) -> Duration {
+        match self.last_at {
+            Some(at) => (at + interval).saturating_duration_since(now),
+            None => Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, now: Instant) {
+        self.last_at = Some(now);
+    }
+}
+
 /// Event emitted when a trade is executed
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -97,12 +289,86 @@ pub struct TradeExecutedEvent {
     pub symbol: String,
     pub trade_type: String,
     pub amount: f64,
-    pub new_price: f64,
+    /// `None` for failed trades and fills the API reported no price for
+    pub new_price: Option<f64>,
     pub price_impact: f64,
     pub new_balance: f64,
     pub reason: String,
+    /// Why the order was submitted, in structured form
+    pub reason_detail: TradeReason,
+    /// Module or surface that submitted the order
+    pub source: String,
     pub success: bool,
     pub error: Option<String>,
+    /// Simulated by paper trading, not sent
+    pub paper: bool,
+}
+
+// ─── Pending Trades ──────────────────────────────────────────────────
+
+/// Snapshot of an order that is queued but has not started executing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTrade {
+    pub id: u64,
+    pub symbol: String,
+    pub trade_type: TradeType,
+    pub amount: f64,
+    pub amount_unit: AmountUnit,
+    pub priority: TradePriority,
+    pub reason: String,
+    pub reason_detail: TradeReason,
+    pub source: String,
+    pub queued_at: i64,
+}
+
+/// Orders submitted to the executor that have not been picked up yet.
+/// Cancelling removes the entry; the loop skips any order it can't `take`.
+#[derive(Debug, Default)]
+struct PendingRegistry {
+    next_id: u64,
+    items: std::collections::BTreeMap<u64, PendingTrade>,
+}
+
+impl PendingRegistry {
+    fn register(&mut self, order: &TradeOrder) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.items.insert(id, PendingTrade {
+            id,
+            symbol: order.symbol.clone(),
+            trade_type: order.trade_type,
+            amount: order.amount,
+            amount_unit: order.amount_unit,
+            priority: order.priority,
+            reason: order.reason.to_string(),
+            reason_detail: order.reason.clone(),
+            source: order.source.clone(),
+            queued_at: chrono::Utc::now().timestamp(),
+        });
+        id
+    }
+
+    /// Claim an order for execution. Returns false if it was cancelled.
+    fn take(&mut self, id: u64) -> bool {
+        self.items.remove(&id).is_some()
+    }
+
+    fn cancel(&mut self, id: u64) -> Option<PendingTrade> {
+        self.items.remove(&id)
+    }
+
+    /// Cancel every pending order at once
+    fn clear(&mut self) -> Vec<PendingTrade> {
+        std::mem::take(&mut self.items).into_values().collect()
+    }
+
+    /// Pending orders in the order the executor will run them
+    fn list(&self) -> Vec<PendingTrade> {
+        let mut list: Vec<PendingTrade> = self.items.values().cloned().collect();
+        list.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.id.cmp(&b.id)));
+        list
+    }
 }
 
 // ─── Risk Limits ─────────────────────────────────────────────────────
@@ -125,14 +391,67 @@ pub struct RiskLimits {
     /// Base delay in milliseconds between retries (exponential backoff)
     #[serde(default = "default_retry_delay_ms")]
     pub retry_delay_ms: u64,
-    /// Milliseconds between consecutive trades (rate limiting)
+    /// Milliseconds between consecutive trades (rate limiting); the floor
+    /// when adaptive
     #[serde(default = "default_rate_limit_ms")]
     pub rate_limit_ms: u64,
+    /// Widen the delay between trades after 429s and narrow it back when
+    /// calm, between `rate_limit_ms` and `max_rate_limit_ms`
+    #[serde(default = "default_adaptive_rate_limit")]
+    pub adaptive_rate_limit: bool,
+    /// Ceiling for the adaptive delay between trades
+    #[serde(default = "default_max_rate_limit_ms")]
+    pub max_rate_limit_ms: u64,
+    /// Fixed USD amount of cash no buy may dip into (0 = disabled)
+    #[serde(default)]
+    pub frozen_reserve_usd: f64,
+    /// Minimum seconds between automated trades; faster ones wait in the
+    /// queue. Sentinel, manual and Critical orders are exempt (0 = disabled)
+    #[serde(default)]
+    pub min_automated_interval_secs: u64,
+    /// Absolute USD cap on any single automated buy, whatever module
+    /// submits it. A last-line guard against bugs and bad configs (0 = disabled)
+    #[serde(default)]
+    pub max_trade_usd: f64,
+    /// Per-module single-trade USD caps keyed by order source (e.g.
+    /// "sniper"). Applied on top of `max_trade_usd`; 0 = no module cap
+    #[serde(default)]
+    pub module_max_trade_usd: HashMap<String, f64>,
+    /// Minimum coin age in seconds before any module may buy it (0 = disabled)
+    #[serde(default)]
+    pub min_coin_age_secs: u64,
+    /// Minimum 1-minute candles of price history before any module may buy
+    /// the coin (0 = disabled)
+    #[serde(default)]
+    pub min_candle_count: u32,
+    /// Order sources allowed to buy coins that fail the age/history gate
+    #[serde(default = "default_new_coin_sources")]
+    pub new_coin_allowed_sources: Vec<String>,
+    /// Buys short of the server minimum trade by at most this much USD are
+    /// rounded up to it; larger gaps are skipped (0 = never round up)
+    #[serde(default)]
+    pub min_trade_round_up_usd: f64,
+    /// Seconds after a sentinel stop loss sells a coin during which no
+    /// module may buy that coin again (0 = disabled)
+    #[serde(default)]
+    pub stop_loss_cooldown_secs: u64,
+    /// Largest price impact a full exit of the position may have after an
+    /// automated buy; buys into coins that couldn't be left cleanly are
+    /// skipped (0 = disabled)
+    #[serde(default)]
+    pub max_exit_impact_pct: f64,
+    /// Refuse every buy once the portfolio is this far (%) below today's
+    /// peak value; sells still go through (0 = disabled)
+    #[serde(default)]
+    pub max_drawdown_pct: f64,
 }
 
 fn default_retry_count() -> u32 { 2 }
+fn default_new_coin_sources() -> Vec<String> { vec![MANUAL_SOURCE.to_string()] }
 fn default_retry_delay_ms() -> u64 { 1000 }
 fn default_rate_limit_ms() -> u64 { 500 }
+fn default_adaptive_rate_limit() -> bool { true }
+fn default_max_rate_limit_ms() -> u64 { 10_000 }
 
 impl Default for RiskLimits {
     fn default() -> Self {
@@ -144,18 +463,325 @@ impl Default for RiskLimits {
             retry_count: 2,                // 2 retries by default
             retry_delay_ms: 1000,          // 1s base delay
             rate_limit_ms: 500,            // 500ms between trades
+            adaptive_rate_limit: true,     // back off after 429s
+            max_rate_limit_ms: 10_000,     // up to 10s between trades
+            frozen_reserve_usd: 0.0,       // disabled
+            min_automated_interval_secs: 0, // disabled
+            max_trade_usd: 0.0,            // disabled
+            module_max_trade_usd: HashMap::new(),
+            min_coin_age_secs: 0,          // disabled
+            min_candle_count: 0,           // disabled
+            new_coin_allowed_sources: default_new_coin_sources(),
+            min_trade_round_up_usd: 0.0,   // skip, never round up
+            stop_loss_cooldown_secs: 0,    // disabled
+            max_exit_impact_pct: 0.0,      // disabled
+            max_drawdown_pct: 0.0,         // disabled
+        }
+    }
+}
+
+/// Age and price history of a coin, for the new-coin gate
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoinHistory {
+    /// Seconds since the coin was created, if the API reported it
+    pub age_secs: Option<i64>,
+    /// 1-minute candles of price history
+    pub candles: usize,
+}
+
+impl RiskLimits {
+    /// Bounds for the delay between trades
+    pub fn pacing_bounds(&self) -> PacingBounds {
+        PacingBounds {
+            adaptive: self.adaptive_rate_limit,
+            min_ms: self.rate_limit_ms,
+            max_ms: self.max_rate_limit_ms,
+        }
+    }
+
+    /// Cash balance left for buying once the frozen reserve is set aside
+    pub fn spendable_balance(&self, balance: f64) -> f64 {
+        (balance - self.frozen_reserve_usd.max(0.0)).max(0.0)
+    }
+
+    /// Every buy limit this order would break, with current vs limit values.
+    ///
+    /// Covers max position, daily count and volume, the cooldown after a
+    /// losing trade and, when `balance` is known, the frozen reserve. Empty
+    /// means the buy is allowed.
+    pub fn buy_violations(
+        &self,
+        amount: f64,
+        activity: &RiskActivity,
+        balance: Option<f64>,
+        override_reserve: bool,
+    ) -> Vec<RiskViolation> {
+        let mut violations = Vec::new();
+        if self.max_position_usd > 0.0 && amount > self.max_position_usd {
+            violations.push(RiskViolation {
+                rule: RiskRule::MaxPosition,
+                current: amount,
+                limit: self.max_position_usd,
+                message: format!(
+                    "Risk limit: buy ${:.2} exceeds max position ${:.2}",
+                    amount, self.max_position_usd
+                ),
+            });
+        }
+        if self.max_daily_trades_count > 0 && activity.daily_trades >= self.max_daily_trades_count {
+            violations.push(RiskViolation {
+                rule: RiskRule::DailyTradeCount,
+                current: activity.daily_trades as f64,
+                limit: self.max_daily_trades_count as f64,
+                message: format!(
+                    "Risk limit: {} trades today, max {}",
+                    activity.daily_trades, self.max_daily_trades_count
+                ),
+            });
+        }
+        if self.max_daily_volume_usd > 0.0 && activity.daily_volume_usd + amount > self.max_daily_volume_usd {
+            violations.push(RiskViolation {
+                rule: RiskRule::DailyVolume,
+                current: activity.daily_volume_usd + amount,
+                limit: self.max_daily_volume_usd,
+                message: format!(
+                    "Risk limit: daily volume ${:.2} + ${:.2} exceeds max ${:.2}",
+                    activity.daily_volume_usd, amount, self.max_daily_volume_usd
+                ),
+            });
+        }
+        if activity.cooldown_remaining_secs > 0 {
+            violations.push(RiskViolation {
+                rule: RiskRule::LossCooldown,
+                current: activity.cooldown_remaining_secs as f64,
+                limit: self.cooldown_after_loss_secs as f64,
+                message: format!(
+                    "Risk limit: in {}-second cooldown after losing trade ({}s left)",
+                    self.cooldown_after_loss_secs, activity.cooldown_remaining_secs
+                ),
+            });
+        }
+        if let Some(balance) = balance {
+            if let Err(message) = self.check_frozen_reserve(balance, amount, override_reserve) {
+                violations.push(RiskViolation {
+                    rule: RiskRule::FrozenReserve,
+                    current: amount,
+                    limit: self.spendable_balance(balance),
+                    message,
+                });
+            }
+        }
+        violations
+    }
+
+    /// Check a buy against the hard limits that apply to every non-critical
+    /// order, manual or automated. The frozen reserve is checked separately
+    /// since it needs a live balance.
+    pub fn check_buy(&self, amount: f64, activity: &RiskActivity) -> Result<(), String> {
+        match self.buy_violations(amount, activity, None, true).into_iter().next() {
+            Some(violation) => Err(violation.message),
+            None => Ok(()),
+        }
+    }
+
+    /// The tightest single-trade USD cap for orders from `source`, if any
+    pub fn trade_cap_for(&self, source: &str) -> Option<f64> {
+        let module = self.module_max_trade_usd.get(source).copied().unwrap_or(0.0);
+        [self.max_trade_usd, module]
+            .into_iter()
+            .filter(|cap| *cap > 0.0)
+            .reduce(f64::min)
+    }
+
+    /// The trade size cap an order of `amount` from `source` breaks, if any.
+    ///
+    /// Only automated buys are capped, independent of the submitting
+    /// module's own sizing. Sells always go through: a stop-loss or
+    /// take-profit larger than the cap must still close the position.
+    pub fn trade_size_violation(&self, source: &str, trade_type: TradeType, amount: f64) -> Option<RiskViolation> {
+        if !matches!(trade_type, TradeType::Buy) || !is_automated_source(source) {
+            return None;
+        }
+        let cap = self.trade_cap_for(source)?;
+        (amount > cap).then(|| RiskViolation {
+            rule: RiskRule::TradeSizeCap,
+            current: amount,
+            limit: cap,
+            message: format!("Trade size cap: {} buy worth ${:.2} exceeds max ${:.2}", source, amount, cap),
+        })
+    }
+
+    /// Hard-reject an automated buy worth more than its trade size cap
+    pub fn check_trade_size(&self, order: &TradeOrder) -> Result<(), String> {
+        match self.trade_size_violation(&order.source, order.trade_type, order.amount) {
+            Some(violation) => Err(format!("{} ({})", violation.message, order.symbol)),
+            None => Ok(()),
+        }
+    }
+
+    /// Whether this order has to pass the coin age/history gate: a risk
+    /// checked buy from a source that isn't explicitly allowed
+    pub fn requires_coin_history(&self, order: &TradeOrder) -> bool {
+        (self.min_coin_age_secs > 0 || self.min_candle_count > 0)
+            && order.is_risk_checked()
+            && !self.new_coin_allowed_sources.contains(&order.source)
+    }
+
+    /// Reject a buy into a coin younger, or with less price history, than
+    /// the global minimums. Unknown age or history fails closed.
+    pub fn check_coin_history(&self, order: &TradeOrder, history: Option<&CoinHistory>) -> Result<(), String> {
+        if !self.requires_coin_history(order) {
+            return Ok(());
+        }
+        let Some(history) = history else {
+            return Err(format!("New coin gate: could not load history for {}", order.symbol));
+        };
+        if self.min_coin_age_secs > 0 {
+            match history.age_secs {
+                Some(age) if age >= self.min_coin_age_secs as i64 => {}
+                Some(age) => {
+                    return Err(format!(
+                        "New coin gate: {} is {}s old, {} buys need {}s",
+                        order.symbol, age, order.source, self.min_coin_age_secs
+                    ));
+                }
+                None => {
+                    return Err(format!("New coin gate: creation time of {} is unknown", order.symbol));
+                }
+            }
+        }
+        if history.candles < self.min_candle_count as usize {
+            return Err(format!(
+                "New coin gate: {} has {} candles of history, {} buys need {}",
+                order.symbol, history.candles, order.source, self.min_candle_count
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reject an automated buy of a coin still cooling off after a stop loss.
+    /// `remaining_secs` is what's left of the coin's window. Buys the user
+    /// places are their own call and pass.
+    pub fn check_stop_loss_cooldown(&self, order: &TradeOrder, remaining_secs: u64) -> Result<(), String> {
+        if !matches!(order.trade_type, TradeType::Buy) || !order.is_automated() || remaining_secs == 0 {
+            return Ok(());
+        }
+        Err(format!(
+            "Risk limit: {} hit a stop loss, {} buys blocked for {}s more",
+            order.symbol, order.source, remaining_secs
+        ))
+    }
+
+    /// Whether an order has to pass the round-trip exit check
+    pub fn requires_exit_check(&self, order: &TradeOrder) -> bool {
+        self.max_exit_impact_pct > 0.0 && matches!(order.trade_type, TradeType::Buy) && order.is_automated()
+    }
+
+    /// Reject an automated buy whose position couldn't be exited within the
+    /// exit impact cap. An unknown pool fails closed.
+    pub fn check_exit_liquidity(&self, order: &TradeOrder, round_trip: Option<&RoundTrip>) -> Result<(), String> {
+        if !self.requires_exit_check(order) {
+            return Ok(());
+        }
+        let Some(round_trip) = round_trip else {
+            return Err(format!("Exit check: could not load the pool of {}", order.symbol));
+        };
+        round_trip::check_round_trip(&order.symbol, round_trip, self.max_exit_impact_pct)
+    }
+
+    /// Reject a buy that would dip into the frozen reserve, unless the
+    /// caller explicitly overrides it (manual trades only).
+    pub fn check_frozen_reserve(
+        &self,
+        balance: f64,
+        amount: f64,
+        override_reserve: bool,
+    ) -> Result<(), String> {
+        if override_reserve || self.frozen_reserve_usd <= 0.0 {
+            return Ok(());
+        }
+        let spendable = self.spendable_balance(balance);
+        if amount > spendable {
+            return Err(format!(
+                "Risk limit: buy ${:.2} exceeds spendable ${:.2} (${:.2} frozen)",
+                amount, spendable, self.frozen_reserve_usd
+            ));
         }
+        Ok(())
     }
 }
 
+/// A risk rule that can block a buy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RiskRule {
+    MaxPosition,
+    DailyTradeCount,
+    DailyVolume,
+    LossCooldown,
+    FrozenReserve,
+    TradeSizeCap,
+    /// Refused before queueing: warm-up, emergency stop, watchlist-only
+    /// mode or the drawdown breaker
+    Admission,
+    CoinHistory,
+    ExitLiquidity,
+    StopLossCooldown,
+}
+
+/// One failing rule, with the value that broke it and the configured limit
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskViolation {
+    pub rule: RiskRule,
+    pub current: f64,
+    pub limit: f64,
+    pub message: String,
+}
+
+/// Trailing 24h activity the buy limits are measured against
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskActivity {
+    pub daily_trades: u32,
+    pub daily_volume_usd: f64,
+    /// Seconds left in the post-loss cooldown (0 when not cooling down)
+    pub cooldown_remaining_secs: u64,
+}
+
+/// Dry-run result of the risk checks for a prospective trade
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeCheck {
+    pub allowed: bool,
+    pub violations: Vec<RiskViolation>,
+    pub activity: RiskActivity,
+}
+
+/// Drawdown circuit breaker state for the UI
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawdownStatus {
+    #[serde(flatten)]
+    pub guard: DrawdownGuard,
+    pub drawdown_pct: f64,
+    /// Configured limit (0 = breaker off)
+    pub threshold_pct: f64,
+    /// Buys are refused until the value recovers or a new day starts
+    pub tripped: bool,
+}
+
 /// Tracks daily trading activity for risk enforcement
 /// Persisted to SQLite and restored on startup so counters survive restarts.
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct DailyTracker {
     /// (timestamp, usd_amount) for each trade in the last 24h
     trades: Vec<(i64, f64)>,
     /// Timestamp of the last losing trade (sell at loss)
     last_loss_timestamp: Option<i64>,
+    /// When a sentinel stop loss last sold each coin
+    #[serde(default)]
+    stop_losses: HashMap<String, i64>,
     /// Flag to indicate the tracker has unsaved changes
     #[serde(skip)]
     dirty: bool,
@@ -187,16 +813,45 @@ impl DailyTracker {
         self.dirty = true;
     }
 
-    /// Check if in cooldown
-    fn in_cooldown(&self, cooldown_secs: u64) -> bool {
+    /// Seconds left in the post-loss cooldown
+    fn cooldown_remaining(&self, cooldown_secs: u64) -> u64 {
+        if cooldown_secs == 0 {
+            return 0;
+        }
+        match self.last_loss_timestamp {
+            Some(ts) => {
+                let elapsed = (chrono::Utc::now().timestamp() - ts).max(0) as u64;
+                cooldown_secs.saturating_sub(elapsed)
+            }
+            None => 0,
+        }
+    }
+
+    /// Record a sentinel stop loss on `symbol`
+    fn record_stop_loss(&mut self, symbol: &str, at: i64) {
+        self.stop_losses.insert(symbol.to_string(), at);
+        self.dirty = true;
+    }
+
+    /// Seconds left before modules may buy `symbol` again after its last stop loss
+    fn stop_loss_cooldown_remaining(&self, symbol: &str, cooldown_secs: u64, now: i64) -> u64 {
         if cooldown_secs == 0 {
-            return false;
+            return 0;
         }
-        if let Some(ts) = self.last_loss_timestamp {
-            let elapsed = chrono::Utc::now().timestamp() - ts;
-            return elapsed < cooldown_secs as i64;
+        match self.stop_losses.get(symbol) {
+            Some(ts) => cooldown_secs.saturating_sub((now - ts).max(0) as u64),
+            None => 0,
+        }
+    }
+
+    /// Current activity for risk checks
+    fn activity(&mut self, cooldown_secs: u64) -> RiskActivity {
+        let (daily_trades, daily_volume_usd) = self.stats();
+        RiskActivity {
+            daily_trades,
+            daily_volume_usd,
+            cooldown_remaining_secs: self.cooldown_remaining(cooldown_secs),
         }
-        false
     }
 }
 
@@ -205,37 +860,97 @@ impl DailyTracker {
 pub struct TradeExecutorHandle {
     tx: mpsc::Sender<TradeOrder>,
     risk_limits: Arc<RwLock<RiskLimits>>,
+    pending: Arc<RwLock<PendingRegistry>>,
+    split_config: Arc<RwLock<SplitSellConfig>>,
+    tracker: Arc<RwLock<DailyTracker>>,
+    /// Smallest buy the server accepts, once a rejection has named it
+    server_min_trade: Arc<RwLock<Option<f64>>>,
+    /// Automated orders are refused until startup warm-up finishes
+    warmup: Arc<WarmupGate>,
+    /// Set by the emergency stop: automated orders are refused until released
+    halted: Arc<AtomicBool>,
+    /// Automated orders are simulated instead of sent
+    paper_trading: Arc<AtomicBool>,
+    /// Portfolio value against today's peak, for the drawdown circuit breaker
+    drawdown: Arc<RwLock<DrawdownGuard>>,
+    /// Trade API request counts and the adaptive delay between trades
+    pacer: Arc<std::sync::Mutex<TradePacer>>,
+    /// Every resolved order, for side effects that react to trades
+    outcomes: broadcast::Sender<TradeOutcome>,
+    /// Semi-auto: automated orders are held for the user's approval
+    semi_auto: Arc<RwLock<SemiAutoConfig>>,
+    approvals: Arc<RwLock<ApprovalQueue<TradeOrder>>>,
+    approval_events: broadcast::Sender<TradeApprovalEvent>,
+    /// Watchlist-only mode: automated buys are limited to watched coins
+    universe: Arc<RwLock<TradableUniverse>>,
 }
 
 impl TradeExecutorHandle {
-    /// Submit a trade order and wait for the result
+    /// Submit a trade order and wait for the result. `amount` is in USD
+    /// for buys and coins for sells.
     pub async fn submit_trade(
         &self,
         symbol: String,
         trade_type: TradeType,
         amount: f64,
         priority: TradePriority,
-        reason: String,
+        reason: TradeReason,
+        source: &str,
+    ) -> Result<TradeResponse, String> {
+        self.submit_trade_amount(symbol, trade_type, TradeAmount::native(trade_type, amount), priority, reason, source)
+            .await
+    }
+
+    /// [`Self::submit_trade`] with the amount in either unit, e.g. a sell
+    /// worth a fixed number of dollars. An order held for approval returns
+    /// an error at once; it still runs if the user approves it.
+    pub async fn submit_trade_amount(
+        &self,
+        symbol: String,
+        trade_type: TradeType,
+        amount: TradeAmount,
+        priority: TradePriority,
+        reason: TradeReason,
+        source: &str,
     ) -> Result<TradeResponse, String> {
+        let trade =
+            AutomatedTrade { symbol, trade_type, amount, priority, reason, source: source.to_string(), quoted_price: None };
+        match self.submit_automated(trade).await? {
+            Submission::Filled(response) => Ok(response),
+            Submission::Held(held) => Err(format!("Held for approval (#{})", held.approval.id)),
+        }
+    }
+
+    /// Submit a module's order. It is either executed (and the fill
+    /// returned) or, in semi-auto mode, held for approval and returned at
+    /// once, so the module never waits on the user.
+    pub async fn submit_automated(&self, trade: AutomatedTrade) -> Result<Submission, String> {
         let (result_tx, result_rx) = oneshot::channel();
 
         let order = TradeOrder {
-            symbol,
-            trade_type,
-            amount,
-            priority,
-            reason,
+            id: 0,
+            symbol: trade.symbol,
+            trade_type: trade.trade_type,
+            amount: trade.amount.value(),
+            amount_unit: trade.amount.unit(),
+            priority: trade.priority,
+            reason: trade.reason,
+            source: trade.source,
+            override_reserve: false,
+            paper: false,
+            quoted_price: trade.quoted_price,
+            max_price_move_pct: None,
             result_tx,
         };
 
-        self.tx
-            .send(order)
-            .await
-            .map_err(|_| "Trade executor channel closed".to_string())?;
+        if let Some(approval) = self.enqueue(order).await? {
+            return Ok(Submission::Held(HeldTrade { approval, result: result_rx }));
+        }
 
-        result_rx
+        let response = result_rx
             .await
-            .map_err(|_| "Trade executor dropped result channel".to_string())?
+            .map_err(|_| "Trade executor dropped result channel".to_string())??;
+        Ok(Submission::Filled(response))
     }
 
     /// Submit a trade order without waiting for the result (fire-and-forget)
@@ -245,24 +960,348 @@ impl TradeExecutorHandle {
         trade_type: TradeType,
         amount: f64,
         priority: TradePriority,
-        reason: String,
+        reason: TradeReason,
+        source: &str,
     ) {
         let (result_tx, _result_rx) = oneshot::channel();
 
         let order = TradeOrder {
+            id: 0,
             symbol,
             trade_type,
             amount,
+            amount_unit: AmountUnit::native(trade_type),
             priority,
             reason,
+            source: source.to_string(),
+            override_reserve: false,
+            paper: false,
+            quoted_price: None,
+            max_price_move_pct: None,
             result_tx,
         };
 
-        if let Err(e) = self.tx.send(order).await {
+        if let Err(e) = self.enqueue(order).await {
             error!("Failed to submit trade: {}", e);
         }
     }
 
+    /// Submit a user-initiated trade and wait for the result.
+    /// `override_reserve` lets the user knowingly spend frozen funds.
+    pub async fn submit_manual_trade(
+        &self,
+        symbol: String,
+        trade_type: TradeType,
+        amount: TradeAmount,
+        reason: TradeReason,
+        source: &str,
+        override_reserve: bool,
+    ) -> Result<TradeResponse, String> {
+        let (result_tx, result_rx) = oneshot::channel();
+        let order = TradeOrder::manual(symbol, trade_type, amount, reason, source, override_reserve, result_tx);
+
+        self.enqueue(order).await?;
+
+        result_rx
+            .await
+            .map_err(|_| "Trade executor dropped result channel".to_string())?
+    }
+
+    /// Sell `quantity` in tranches according to the split sell config.
+    ///
+    /// Each tranche goes through the queue like any other order, so the
+    /// executor still serializes them with everything else. Stops at the
+    /// first failed tranche; if some tranches already filled, the result
+    /// says how much was sold and how much was left, with the error that
+    /// stopped it, instead of an error.
+    pub async fn submit_split_sell(
+        &self,
+        symbol: String,
+        quantity: f64,
+        priority: TradePriority,
+        reason: TradeReason,
+        source: &str,
+    ) -> Result<SplitFill, String> {
+        let plan = self.split_config.read().await.plan(quantity);
+        let count = plan.amounts.len();
+        let mut filled: Option<TradeResponse> = None;
+        let mut sold = 0.0;
+
+        for (i, amount) in plan.amounts.into_iter().enumerate() {
+            if i > 0 && plan.spacing_ms > 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(plan.spacing_ms)).await;
+            }
+
+            let tranche_reason = reason.clone().tranche(i + 1, count);
+
+            match self
+                .submit_trade(symbol.clone(), TradeType::Sell, amount, priority, tranche_reason, source)
+                .await
+            {
+                Ok(resp) => {
+                    filled = Some(order_split::merge_fill(filled, resp));
+                    sold += amount;
+                }
+                Err(e) => {
+                    warn!("Split sell of {} stopped at tranche {}/{}: {}", symbol, i + 1, count, e);
+                    let Some(response) = filled else {
+                        return Err(e);
+                    };
+                    let unsold = truncate_to_8_decimals(quantity - sold);
+                    return Ok(SplitFill { response, sold, unsold, error: Some(e) });
+                }
+            }
+        }
+
+        filled
+            .map(|response| SplitFill::complete(response, quantity))
+            .ok_or_else(|| "Split sell produced no tranches".to_string())
+    }
+
+    /// Check an order, then either hold it for approval (returning the
+    /// proposal) or hand it to the executor loop
+    async fn enqueue(&self, order: TradeOrder) -> Result<Option<PendingApproval>, String> {
+        self.admit(&order).await?;
+        let config = self.semi_auto.read().await.clone();
+        if config.requires_approval(order.is_automated(), &order.source) {
+            return Ok(Some(self.hold_for_approval(order, config.expiry_secs).await));
+        }
+        self.dispatch(order).await?;
+        Ok(None)
+    }
+
+    /// The checks an order must pass to be queued (again on approval)
+    async fn admit(&self, order: &TradeOrder) -> Result<(), String> {
+        // A NaN amount would sail past every `>` risk check below
+        if !order.amount.is_finite() || order.amount <= 0.0 {
+            return Err(format!("Invalid trade amount {} for {}", order.amount, order.symbol));
+        }
+        self.warmup.check_order(order.is_automated(), order.is_protective())?;
+        if order.is_automated() && self.is_automation_halted() {
+            return Err("Emergency stop: automated trading is halted".to_string());
+        }
+        self.universe.read().await.check_order(&order.symbol, order.trade_type, order.is_automated())?;
+        if matches!(order.trade_type, TradeType::Buy) {
+            let threshold = self.risk_limits.read().await.max_drawdown_pct;
+            self.drawdown
+                .read()
+                .await
+                .check_trade(order.trade_type, threshold)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Register an order in the pending list and hand it to the executor loop
+    async fn dispatch(&self, mut order: TradeOrder) -> Result<(), String> {
+        // Sentinels guard real holdings (paper buys never get one), so their sells stay real
+        order.paper = order.is_automated() && !order.is_protective() && self.is_paper_trading();
+
+        order.id = self.pending.write().await.register(&order);
+        let id = order.id;
+
+        if self.tx.send(order).await.is_err() {
+            self.pending.write().await.take(id);
+            return Err("Trade executor channel closed".to_string());
+        }
+        Ok(())
+    }
+
+    /// Orders waiting to be executed, in execution order
+    pub async fn list_pending_trades(&self) -> Vec<PendingTrade> {
+        self.pending.read().await.list()
+    }
+
+    // ─── Approvals ───────────────────────────────────────────────────
+
+    /// Hold an automated order until the user decides or it expires
+    async fn hold_for_approval(&self, order: TradeOrder, expiry_secs: u64) -> PendingApproval {
+        let now = chrono::Utc::now().timestamp();
+        let proposal = PendingApproval {
+            id: 0,
+            symbol: order.symbol.clone(),
+            trade_type: order.trade_type,
+            amount: order.amount,
+            amount_unit: order.amount_unit,
+            reason: order.reason.to_string(),
+            reason_detail: order.reason.clone(),
+            source: order.source.clone(),
+            proposed_at: now,
+            expires_at: now + expiry_secs as i64,
+        };
+        let (approval, pending_count) = {
+            let mut approvals = self.approvals.write().await;
+            let approval = approvals.hold(proposal, order);
+            (approval, approvals.len())
+        };
+        info!(
+            "Holding {:?} {} of {} from {} for approval (#{})",
+            approval.trade_type, approval.amount, approval.symbol, approval.source, approval.id
+        );
+        let _ = self.approval_events.send(TradeApprovalEvent {
+            status: ApprovalStatus::Proposed,
+            approval: approval.clone(),
+            pending_count,
+        });
+
+        let handle = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(expiry_secs)).await;
+            handle.expire_approvals().await;
+        });
+        approval
+    }
+
+    /// Reject every proposal past its expiry
+    async fn expire_approvals(&self) {
+        let expired = self.approvals.write().await.take_expired(chrono::Utc::now().timestamp());
+        for (approval, order) in expired {
+            info!("Approval #{} for {} expired", approval.id, approval.symbol);
+            self.resolve_held(approval, order, ApprovalStatus::Expired, "Trade approval expired").await;
+        }
+    }
+
+    /// Answer a held order's submitter with `error` and publish the decision
+    async fn resolve_held(&self, approval: PendingApproval, order: TradeOrder, status: ApprovalStatus, error: &str) {
+        let _ = order.result_tx.send(Err(error.to_string()));
+        self.publish_approval(status, approval).await;
+    }
+
+    async fn publish_approval(&self, status: ApprovalStatus, approval: PendingApproval) {
+        let pending_count = self.approvals.read().await.len();
+        let _ = self.approval_events.send(TradeApprovalEvent { status, approval, pending_count });
+    }
+
+    /// Proposals waiting for a decision, oldest first
+    pub async fn list_pending_approvals(&self) -> Vec<PendingApproval> {
+        self.approvals.read().await.list()
+    }
+
+    /// Receive every proposal and decision from now on
+    pub fn subscribe_approvals(&self) -> broadcast::Receiver<TradeApprovalEvent> {
+        self.approval_events.subscribe()
+    }
+
+    /// Approve a held order: it is checked again (the emergency stop or the
+    /// drawdown breaker may have tripped meanwhile) and queued like any other.
+    /// The executor drops it if its price moved too far from the quote.
+    pub async fn approve_pending(&self, id: u64) -> Result<PendingApproval, String> {
+        let (approval, mut order) = self.take_approval(id).await?;
+        if let Err(e) = self.admit(&order).await {
+            self.resolve_held(approval, order, ApprovalStatus::Rejected, &e).await;
+            return Err(e);
+        }
+        order.max_price_move_pct = Some(self.semi_auto.read().await.max_price_move_pct);
+        info!("Approved #{}: {:?} {} of {}", id, approval.trade_type, approval.amount, approval.symbol);
+        self.dispatch(order).await?;
+        self.publish_approval(ApprovalStatus::Approved, approval.clone()).await;
+        Ok(approval)
+    }
+
+    /// Reject a held order. The submitting module's held result is an error.
+    pub async fn reject_pending(&self, id: u64) -> Result<PendingApproval, String> {
+        let (approval, order) = self.take_approval(id).await?;
+        info!("Rejected #{}: {:?} {} of {}", id, approval.trade_type, approval.amount, approval.symbol);
+        self.resolve_held(approval.clone(), order, ApprovalStatus::Rejected, "Trade rejected by user").await;
+        Ok(approval)
+    }
+
+    /// Reject every held order at once
+    pub async fn reject_all_pending(&self, reason: &str) -> Vec<PendingApproval> {
+        let held = self.approvals.write().await.drain();
+        let mut rejected = Vec::with_capacity(held.len());
+        for (approval, order) in held {
+            rejected.push(approval.clone());
+            self.resolve_held(approval, order, ApprovalStatus::Rejected, reason).await;
+        }
+        rejected
+    }
+
+    async fn take_approval(&self, id: u64) -> Result<(PendingApproval, TradeOrder), String> {
+        self.approvals
+            .write()
+            .await
+            .take(id)
+            .ok_or_else(|| format!("Approval #{} is not pending (already decided or expired)", id))
+    }
+
+    /// Update the semi-auto settings. Orders already held stay held until
+    /// decided or expired.
+    pub async fn set_semi_auto_config(&self, config: SemiAutoConfig) {
+        info!("Semi-auto mode {}", if config.enabled { "enabled" } else { "disabled" });
+        *self.semi_auto.write().await = config;
+    }
+
+    pub async fn get_semi_auto_config(&self) -> SemiAutoConfig {
+        self.semi_auto.read().await.clone()
+    }
+
+    /// Limit (or stop limiting) automated buys to the watchlist. Applies to
+    /// orders submitted from now on.
+    pub async fn set_watchlist_only(&self, enabled: bool) {
+        info!("Watchlist-only mode {}", if enabled { "enabled" } else { "disabled" });
+        self.universe.write().await.enabled = enabled;
+    }
+
+    pub async fn is_watchlist_only(&self) -> bool {
+        self.universe.read().await.enabled
+    }
+
+    /// Replace the coins watchlist-only mode lets automation buy
+    pub async fn set_tradable_symbols(&self, symbols: Vec<String>) {
+        let mut universe = self.universe.write().await;
+        universe.set_symbols(symbols);
+        debug!("Tradable universe: {} watched coin(s)", universe.len());
+    }
+
+    /// Receive the outcome of every order resolved from now on
+    pub fn subscribe_outcomes(&self) -> broadcast::Receiver<TradeOutcome> {
+        self.outcomes.subscribe()
+    }
+
+    /// Cancel a queued order before it runs. The submitter receives an error.
+    pub async fn cancel_pending_trade(&self, id: u64) -> Result<PendingTrade, String> {
+        let cancelled = self
+            .pending
+            .write()
+            .await
+            .cancel(id)
+            .ok_or_else(|| format!("Trade #{} is not pending (already executed or cancelled)", id))?;
+        info!("Cancelled pending trade #{}: {:?} {} of {}", id, cancelled.trade_type, cancelled.amount, cancelled.symbol);
+        Ok(cancelled)
+    }
+
+    /// Cancel every queued order. Each submitter receives an error.
+    pub async fn clear_queue(&self) -> Vec<PendingTrade> {
+        let cancelled = self.pending.write().await.clear();
+        if !cancelled.is_empty() {
+            info!("Cleared {} pending trade(s) from the queue", cancelled.len());
+        }
+        cancelled
+    }
+
+    /// Refuse (or accept again) orders placed by automation modules.
+    /// Manual trades are never halted.
+    pub fn set_automation_halted(&self, halted: bool) {
+        self.halted.store(halted, AtomicOrdering::Release);
+        info!("Automated trading {}", if halted { "halted" } else { "resumed" });
+    }
+
+    pub fn is_automation_halted(&self) -> bool {
+        self.halted.load(AtomicOrdering::Acquire)
+    }
+
+    /// Simulate (or send again) the orders of automation modules. Applies
+    /// to orders submitted from now on; manual trades are always sent.
+    pub fn set_paper_trading(&self, paper: bool) {
+        self.paper_trading.store(paper, AtomicOrdering::Release);
+        info!("Paper trading {}", if paper { "enabled" } else { "disabled" });
+    }
+
+    pub fn is_paper_trading(&self) -> bool {
+        self.paper_trading.load(AtomicOrdering::Acquire)
+    }
+
     /// Update the risk limits configuration
     pub async fn set_risk_limits(&self, limits: RiskLimits) {
         *self.risk_limits.write().await = limits;
@@ -273,6 +1312,127 @@ impl TradeExecutorHandle {
     pub async fn get_risk_limits(&self) -> RiskLimits {
         self.risk_limits.read().await.clone()
     }
+
+    /// Run the checks a trade would face without executing it: the same
+    /// admission as [`Self::submit_trade`] (warm-up, emergency stop,
+    /// watchlist-only, drawdown breaker), the execution gates (size cap, new
+    /// coin gate, exit liquidity, stop-loss cool-off) and, for buys, the buy
+    /// limits with the frozen reserve checked against the live balance.
+    pub async fn check_trade_allowed(
+        &self,
+        app_handle: &tauri::AppHandle,
+        symbol: &str,
+        source: &str,
+        trade_type: TradeType,
+        amount: f64,
+        override_reserve: bool,
+    ) -> TradeCheck {
+        let order = preflight_order(symbol, source, trade_type, amount, override_reserve);
+        let mut violations: Vec<RiskViolation> = self.admission_violation(&order).await.into_iter().collect();
+        let limits = self.risk_limits.read().await.clone();
+        violations.extend(order_gate_violations(app_handle, &limits, &self.tracker, &order).await);
+
+        let activity = self.tracker.write().await.activity(limits.cooldown_after_loss_secs);
+        if order.is_risk_checked() {
+            let mut unverified = None;
+            let balance = if limits.frozen_reserve_usd > 0.0 && !override_reserve {
+                match fetch_cash_balance(app_handle).await {
+                    Ok(cash) => Some(cash),
+                    Err(e) => {
+                        unverified = Some(RiskViolation {
+                            rule: RiskRule::FrozenReserve,
+                            current: amount,
+                            limit: 0.0,
+                            message: format!("Risk limit: could not verify frozen reserve ({})", e),
+                        });
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+            violations.extend(limits.buy_violations(amount, &activity, balance, override_reserve));
+            violations.extend(unverified);
+        }
+
+        TradeCheck { allowed: violations.is_empty(), violations, activity }
+    }
+
+    /// Why [`Self::admit`] would refuse the order, as a violation
+    async fn admission_violation(&self, order: &TradeOrder) -> Option<RiskViolation> {
+        let message = self.admit(order).await.err()?;
+        Some(RiskViolation { rule: RiskRule::Admission, current: order.amount, limit: 0.0, message })
+    }
+
+    /// Queue depth, today's activity and the limits currently refusing
+    /// every buy regardless of size (trade count, volume, cooldown)
+    pub async fn status(&self) -> ExecutorStatus {
+        let limits = self.risk_limits.read().await.clone();
+        let activity = self.tracker.write().await.activity(limits.cooldown_after_loss_secs);
+        let mut buy_blocks: Vec<String> = limits
+            .buy_violations(0.0, &activity, None, true)
+            .into_iter()
+            .map(|v| v.message)
+            .collect();
+        if let Err(e) = self.drawdown.read().await.check_trade(TradeType::Buy, limits.max_drawdown_pct) {
+            buy_blocks.push(e.to_string());
+        }
+
+        ExecutorStatus {
+            queue_depth: self.pending.read().await.items.len(),
+            daily_trades: activity.daily_trades,
+            daily_volume_usd: activity.daily_volume_usd,
+            cooldown_remaining_secs: activity.cooldown_remaining_secs,
+            buy_blocks,
+            server_min_trade_usd: self.server_min_trade_usd().await,
+        }
+    }
+
+    /// Record a sample of the portfolio's total value taken on `day`
+    /// (`YYYY-MM-DD`). Returns whether the session peak or day changed.
+    pub async fn record_portfolio_value(&self, value: f64, day: &str) -> bool {
+        self.drawdown.write().await.record(value, day)
+    }
+
+    /// Replace the drawdown state, e.g. with the one persisted for today
+    pub async fn restore_drawdown(&self, guard: DrawdownGuard) {
+        *self.drawdown.write().await = guard;
+    }
+
+    /// Trade API request counts, rate limit hits and the current delay between trades
+    pub async fn stats(&self) -> ExecutorStats {
+        let bounds = self.risk_limits.read().await.pacing_bounds();
+        self.pacer.lock().unwrap_or_else(|e| e.into_inner()).stats_at(Instant::now(), &bounds)
+    }
+
+    /// Session peak, latest value and whether buys are paused by the drawdown breaker
+    pub async fn drawdown_status(&self) -> DrawdownStatus {
+        let threshold_pct = self.risk_limits.read().await.max_drawdown_pct;
+        let guard = self.drawdown.read().await.clone();
+        DrawdownStatus {
+            drawdown_pct: guard.drawdown_pct(),
+            tripped: guard.is_tripped(threshold_pct),
+            threshold_pct,
+            guard,
+        }
+    }
+
+    /// The server's minimum trade size in USD, learned from the first
+    /// rejection that named it. Buys below it are rounded up or skipped.
+    pub async fn server_min_trade_usd(&self) -> Option<f64> {
+        *self.server_min_trade.read().await
+    }
+
+    /// Update the split sell configuration
+    pub async fn set_split_config(&self, config: SplitSellConfig) {
+        *self.split_config.write().await = config;
+        info!("Split sell config updated: {:?}", self.split_config.read().await);
+    }
+
+    /// Get the current split sell configuration
+    pub async fn get_split_config(&self) -> SplitSellConfig {
+        self.split_config.read().await.clone()
+    }
 }
 
 /// Spawn the trade executor background task.
@@ -284,26 +1444,198 @@ pub fn spawn_trade_executor(
 ) -> TradeExecutorHandle {
     let (tx, rx) = mpsc::channel::<TradeOrder>(256);
     let risk_limits = Arc::new(RwLock::new(RiskLimits::default()));
+    let pending = Arc::new(RwLock::new(PendingRegistry::default()));
+    let tracker = Arc::new(RwLock::new(DailyTracker::default()));
+    let server_min_trade = Arc::new(RwLock::new(None));
+    let pacer = Arc::new(std::sync::Mutex::new(TradePacer::default()));
+    let warmup = {
+        use tauri::Manager;
+        app_handle.state::<crate::AppState>().warmup.clone()
+    };
+
+    let (outcomes, _) = broadcast::channel(OUTCOME_CHANNEL_CAPACITY);
+
+    let shared = ExecutorShared {
+        risk_limits: risk_limits.clone(),
+        pending: pending.clone(),
+        tracker: tracker.clone(),
+        server_min_trade: server_min_trade.clone(),
+        pacer: pacer.clone(),
+        outcomes: outcomes.clone(),
+    };
+    tokio::spawn(trade_executor_loop(rx, app_handle, shared));
+
+    TradeExecutorHandle {
+        tx,
+        risk_limits,
+        pending,
+        split_config: Arc::new(RwLock::new(SplitSellConfig::default())),
+        tracker,
+        server_min_trade,
+        warmup,
+        halted: Arc::new(AtomicBool::new(false)),
+        paper_trading: Arc::new(AtomicBool::new(false)),
+        drawdown: Arc::new(RwLock::new(DrawdownGuard::default())),
+        pacer,
+        outcomes,
+        semi_auto: Arc::new(RwLock::new(SemiAutoConfig::default())),
+        approvals: Arc::new(RwLock::new(ApprovalQueue::default())),
+        approval_events: broadcast::channel(APPROVAL_CHANNEL_CAPACITY).0,
+        universe: Arc::new(RwLock::new(TradableUniverse::default())),
+    }
+}
+
+#[cfg(test)]
+/// An order as the recording executor received it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedTrade {
+    pub symbol: String,
+    pub trade_type: TradeType,
+    pub amount: f64,
+    pub amount_unit: AmountUnit,
+    pub priority: TradePriority,
+    pub reason: TradeReason,
+    pub source: String,
+}
 
-    tokio::spawn(trade_executor_loop(rx, app_handle, risk_limits.clone()));
+#[cfg(test)]
+/// Orders captured by [`spawn_recording_executor`], in arrival order
+#[derive(Clone, Default)]
+pub struct TradeRecorder {
+    trades: Arc<std::sync::Mutex<Vec<RecordedTrade>>>,
+}
+
+#[cfg(test)]
+impl TradeRecorder {
+    /// Everything recorded so far
+    pub fn trades(&self) -> Vec<RecordedTrade> {
+        self.trades.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Everything recorded so far, clearing the list
+    pub fn take(&self) -> Vec<RecordedTrade> {
+        std::mem::take(&mut *self.trades.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn record(&self, order: &TradeOrder) {
+        self.trades.lock().unwrap_or_else(|e| e.into_inner()).push(RecordedTrade {
+            symbol: order.symbol.clone(),
+            trade_type: order.trade_type,
+            amount: order.amount,
+            amount_unit: order.amount_unit,
+            priority: order.priority,
+            reason: order.reason.clone(),
+            source: order.source.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+/// The fill a recorded order is answered with: the whole amount at $1
+fn canned_response(order: &TradeOrder) -> TradeResponse {
+    let (trade_type, coins_bought, coins_sold, total_cost, total_received) = match order.trade_type {
+        TradeType::Buy => ("BUY", Some(order.amount), None, Some(order.amount), None),
+        TradeType::Sell => ("SELL", None, Some(order.amount), None, Some(order.amount)),
+    };
+    TradeResponse {
+        success: true,
+        trade_type: trade_type.to_string(),
+        coins_bought,
+        coins_sold,
+        total_cost,
+        total_received,
+        new_price: Some(1.0),
+        price_impact: 0.0,
+        new_balance: 0.0,
+        paper: order.paper,
+    }
+}
+
+#[cfg(test)]
+/// Test mode: an executor that records orders instead of sending them.
+///
+/// Orders go through the same handle, validation and pending list as with
+/// the real executor, but never reach risk checks, throttling or the API.
+/// Each is recorded and answered with a canned fill, so tests can assert
+/// exactly which trades a module submits for a given feed. Paper orders are
+/// answered the same way but not recorded, as the real executor never sends them.
+pub fn spawn_recording_executor() -> (TradeExecutorHandle, TradeRecorder) {
+    spawn_recording_executor_with(Arc::new(WarmupGate::completed()))
+}
+
+#[cfg(test)]
+/// [`spawn_recording_executor`] sharing `warmup`, to test what is held back
+/// while it warms up
+pub fn spawn_recording_executor_with(warmup: Arc<WarmupGate>) -> (TradeExecutorHandle, TradeRecorder) {
+    let (tx, mut rx) = mpsc::channel::<TradeOrder>(256);
+    let pending = Arc::new(RwLock::new(PendingRegistry::default()));
+    let pacer = Arc::new(std::sync::Mutex::new(TradePacer::default()));
+    let recorder = TradeRecorder::default();
+
+    let (outcomes, _) = broadcast::channel(OUTCOME_CHANNEL_CAPACITY);
+
+    let loop_pending = pending.clone();
+    let loop_recorder = recorder.clone();
+    let loop_outcomes = outcomes.clone();
+    tokio::spawn(async move {
+        while let Some(order) = rx.recv().await {
+            if !loop_pending.write().await.take(order.id) {
+                let _ = loop_outcomes.send(TradeOutcome::new(&order, TradeResolution::Cancelled));
+                let _ = order.result_tx.send(Err("Trade cancelled before execution".to_string()));
+                continue;
+            }
+            if !order.paper {
+                loop_recorder.record(&order);
+            }
+            let response = canned_response(&order);
+            let _ = loop_outcomes.send(TradeOutcome::new(&order, TradeResolution::Filled(response.clone())));
+            let _ = order.result_tx.send(Ok(response));
+        }
+    });
+
+    let handle = TradeExecutorHandle {
+        tx,
+        risk_limits: Arc::new(RwLock::new(RiskLimits::default())),
+        pending,
+        split_config: Arc::new(RwLock::new(SplitSellConfig::default())),
+        tracker: Arc::new(RwLock::new(DailyTracker::default())),
+        server_min_trade: Arc::new(RwLock::new(None)),
+        warmup,
+        halted: Arc::new(AtomicBool::new(false)),
+        paper_trading: Arc::new(AtomicBool::new(false)),
+        drawdown: Arc::new(RwLock::new(DrawdownGuard::default())),
+        pacer,
+        outcomes,
+        semi_auto: Arc::new(RwLock::new(SemiAutoConfig::default())),
+        approvals: Arc::new(RwLock::new(ApprovalQueue::default())),
+        approval_events: broadcast::channel(APPROVAL_CHANNEL_CAPACITY).0,
+        universe: Arc::new(RwLock::new(TradableUniverse::default())),
+    };
+    (handle, recorder)
+}
 
-    TradeExecutorHandle { tx, risk_limits }
+/// The parts of [`TradeExecutorHandle`] the executor loop works with
+struct ExecutorShared {
+    risk_limits: Arc<RwLock<RiskLimits>>,
+    pending: Arc<RwLock<PendingRegistry>>,
+    tracker: Arc<RwLock<DailyTracker>>,
+    server_min_trade: Arc<RwLock<Option<f64>>>,
+    pacer: Arc<std::sync::Mutex<TradePacer>>,
+    outcomes: broadcast::Sender<TradeOutcome>,
 }
 
 /// The main executor loop — drains incoming orders into a priority heap,
 /// processes them one at a time with rate limiting, risk validation, and retry logic.
-async fn trade_executor_loop(
-    mut rx: mpsc::Receiver<TradeOrder>,
-    app_handle: tauri::AppHandle,
-    risk_limits: Arc<RwLock<RiskLimits>>,
-) {
+async fn trade_executor_loop(mut rx: mpsc::Receiver<TradeOrder>, app_handle: tauri::AppHandle, shared: ExecutorShared) {
+    let ExecutorShared { risk_limits, pending, tracker, server_min_trade, pacer, outcomes } = shared;
     info!("Trade executor started");
 
     let mut heap: BinaryHeap<PrioritizedOrder> = BinaryHeap::new();
     let mut seq: u64 = 0;
+    let mut throttle = AutomationThrottle::default();
 
     // Load persisted daily tracker or start fresh
-    let mut tracker = load_daily_tracker(&app_handle).await;
+    *tracker.write().await = load_daily_tracker(&app_handle).await;
     let mut save_counter: u32 = 0; // persist every 5 trades
 
     loop {
@@ -317,99 +1649,159 @@ async fn trade_executor_loop(
                 None => {
                     info!("Trade executor channel closed, shutting down");
                     // Persist tracker on shutdown
-                    save_daily_tracker(&app_handle, &tracker).await;
+                    let snapshot = tracker.read().await.clone();
+                    save_daily_tracker(&app_handle, &snapshot).await;
                     return;
                 }
             }
-        }
+        }
+
+        // Drain any additional pending orders into the heap (non-blocking)
+        while let Ok(order) = rx.try_recv() {
+            queue_order(&mut heap, &mut seq, order, &pending).await;
+        }
+
+        // Hold automated orders back until the minimum interval has passed
+        let interval = Duration::from_secs(risk_limits.read().await.min_automated_interval_secs);
+        let wait = throttle.remaining(Instant::now(), interval);
+        let next = pop_next(&mut heap, !wait.is_zero());
+        if next.is_none() && !heap.is_empty() {
+            // Only throttled orders are queued: wake when the interval ends
+            // or a new (possibly exempt) order arrives
+            tokio::select! {
+                _ = tokio::time::sleep(wait) => {}
+                received = rx.recv() => match received {
+                    Some(order) => queue_order(&mut heap, &mut seq, order, &pending).await,
+                    None => tokio::time::sleep(wait).await,
+                },
+            }
+            continue;
+        }
+
+        // Process the highest priority order
+        if let Some(prioritized) = next {
+            let mut order = prioritized.order;
+
+            // Skip orders the user cancelled while they were queued
+            if !pending.write().await.take(order.id) {
+                info!("Skipping cancelled trade #{} for {}", order.id, order.symbol);
+                let _ = outcomes.send(TradeOutcome::new(&order, TradeResolution::Cancelled));
+                let _ = order.result_tx.send(Err("Trade cancelled before execution".to_string()));
+                continue;
+            }
+
+            // ── Approved proposals: the price may have moved while the user decided ──
+            if let (Some(quoted), Some(max_move)) = (order.quoted_price, order.max_price_move_pct) {
+                let checked = match fetch_fresh_coin_price(&app_handle, &order.symbol).await {
+                    Ok(price) => trade_approval::check_price_move(&order.symbol, quoted, price, max_move),
+                    Err(e) => Err(format!("Could not re-check the price of {}: {}", order.symbol, e)),
+                };
+                if let Err(msg) = checked {
+                    warn!("{}", msg);
+                    publish_rejected(&outcomes, &order, &msg);
+                    let _ = order.result_tx.send(Err(msg));
+                    continue;
+                }
+            }
 
-        // Drain any additional pending orders into the heap (non-blocking)
-        while let Ok(order) = rx.try_recv() {
-            if heap.len() >= MAX_QUEUE_DEPTH {
-                warn!("Trade executor queue full ({} orders), rejecting order for {}", MAX_QUEUE_DEPTH, order.symbol);
-                let _ = order.result_tx.send(Err(format!(
-                    "Trade queue full ({} orders) — try again later", MAX_QUEUE_DEPTH
-                )));
-                continue;
+            // ── Amount in the other unit (e.g. a sell worth $X), at a fresh price ──
+            let requested = TradeAmount::new(order.amount, order.amount_unit);
+            if requested.needs_price(order.trade_type) {
+                let price = fetch_fresh_coin_price(&app_handle, &order.symbol)
+                    .await
+                    .map_err(|e| debug!("No price for {}: {}", order.symbol, e))
+                    .ok();
+                match requested.resolve(order.trade_type, price) {
+                    Ok(amount) => {
+                        info!("Resolved {:?} {:?} of {} to {}", order.trade_type, requested, order.symbol, amount);
+                        order.amount = amount;
+                        order.amount_unit = AmountUnit::native(order.trade_type);
+                    }
+                    Err(msg) => {
+                        let msg = format!("Could not size {} order: {}", order.symbol, msg);
+                        warn!("{}", msg);
+                        publish_rejected(&outcomes, &order, &msg);
+                        let _ = order.result_tx.send(Err(msg));
+                        continue;
+                    }
+                }
             }
-            seq += 1;
-            heap.push(PrioritizedOrder { order, seq });
-        }
 
-        // Process the highest priority order
-        if let Some(prioritized) = heap.pop() {
-            let order = prioritized.order;
+            // ── Server minimum (buys only, once a rejection has named it) ──
+            // Runs first so the size and risk checks see the amount actually sent
+            if matches!(order.trade_type, TradeType::Buy) {
+                let round_up = risk_limits.read().await.min_trade_round_up_usd;
+                match min_trade::check_buy(order.amount, *server_min_trade.read().await, round_up) {
+                    MinTradeCheck::Proceed => {}
+                    MinTradeCheck::RoundUp(amount) => {
+                        info!("Rounding {} buy of ${:.2} up to the server minimum ${:.2}", order.symbol, order.amount, amount);
+                        order.amount = amount;
+                    }
+                    MinTradeCheck::Skip(msg) => {
+                        info!("{} ({})", msg, order.symbol);
+                        publish_rejected(&outcomes, &order, &msg);
+                        let _ = order.result_tx.send(Err(msg));
+                        continue;
+                    }
+                }
+            }
             debug!(
                 "Executing {:?} trade: {:?} {} of {} (reason: {})",
                 order.priority, order.trade_type, order.amount, order.symbol, order.reason
             );
 
-            // ── Risk validation (only for buys, skip for Critical priority) ──
-            if matches!(order.trade_type, TradeType::Buy) && order.priority != TradePriority::Critical {
-                let limits = risk_limits.read().await;
-
-                // Check max position size
-                if limits.max_position_usd > 0.0 && order.amount > limits.max_position_usd {
-                    let msg = format!(
-                        "Risk limit: buy ${:.2} exceeds max position ${:.2}",
-                        order.amount, limits.max_position_usd
-                    );
-                    warn!("{}", msg);
-                    emit_rejected(&app_handle, &order, &msg);
-                    let _ = order.result_tx.send(Err(msg));
-                    continue;
-                }
-
-                // Check daily trade count
-                let (daily_count, daily_volume) = tracker.stats();
-                if limits.max_daily_trades_count > 0 && daily_count >= limits.max_daily_trades_count {
-                    let msg = format!(
-                        "Risk limit: {} trades today, max {}",
-                        daily_count, limits.max_daily_trades_count
-                    );
-                    warn!("{}", msg);
-                    emit_rejected(&app_handle, &order, &msg);
-                    let _ = order.result_tx.send(Err(msg));
-                    continue;
-                }
+            // ── Trade size cap, new coin gate, exit liquidity, stop-loss cool-off ──
+            let limits = risk_limits.read().await.clone();
+            if let Some(violation) = order_gate_violations(&app_handle, &limits, &tracker, &order).await.into_iter().next() {
+                let msg = violation.message;
+                warn!("{}", msg);
+                publish_rejected(&outcomes, &order, &msg);
+                let _ = order.result_tx.send(Err(msg));
+                continue;
+            }
 
-                // Check daily volume
-                if limits.max_daily_volume_usd > 0.0 && daily_volume + order.amount > limits.max_daily_volume_usd {
-                    let msg = format!(
-                        "Risk limit: daily volume ${:.2} + ${:.2} exceeds max ${:.2}",
-                        daily_volume, order.amount, limits.max_daily_volume_usd
-                    );
+            // ── Risk validation (only for buys, skip for Critical priority) ──
+            if order.is_risk_checked() {
+                // Check position size, daily count/volume and loss cooldown
+                let activity = tracker.write().await.activity(limits.cooldown_after_loss_secs);
+                if let Err(msg) = limits.check_buy(order.amount, &activity) {
                     warn!("{}", msg);
-                    emit_rejected(&app_handle, &order, &msg);
+                    publish_rejected(&outcomes, &order, &msg);
                     let _ = order.result_tx.send(Err(msg));
                     continue;
                 }
 
-                // Check loss cooldown
-                if tracker.in_cooldown(limits.cooldown_after_loss_secs) {
-                    let msg = format!(
-                        "Risk limit: in {}-second cooldown after losing trade",
-                        limits.cooldown_after_loss_secs
-                    );
-                    warn!("{}", msg);
-                    emit_rejected(&app_handle, &order, &msg);
-                    let _ = order.result_tx.send(Err(msg));
-                    continue;
+                // Check frozen reserve against the live cash balance
+                if limits.frozen_reserve_usd > 0.0 && !order.override_reserve {
+                    let check = match fetch_cash_balance(&app_handle).await {
+                        Ok(balance) => limits.check_frozen_reserve(balance, order.amount, false),
+                        Err(e) => Err(format!("Risk limit: could not verify frozen reserve ({})", e)),
+                    };
+                    if let Err(msg) = check {
+                        warn!("{}", msg);
+                        publish_rejected(&outcomes, &order, &msg);
+                        let _ = order.result_tx.send(Err(msg));
+                        continue;
+                    }
                 }
+            }
 
-                drop(limits);
+            if order.is_throttled() {
+                throttle.record(Instant::now());
             }
 
             // Read retry config
             let limits = risk_limits.read().await;
             let max_retries = limits.retry_count;
             let retry_base_ms = limits.retry_delay_ms;
-            let rate_limit_ms = limits.rate_limit_ms;
+            let pacing = limits.pacing_bounds();
             drop(limits);
 
             // Execute with retry logic
             let mut last_error = String::new();
             let mut result: Result<TradeResponse, String> = Err("Not attempted".to_string());
+            // Reconciling only takes transactions from after this for the order's own
+            let started_at = chrono::Utc::now();
 
             for attempt in 0..=max_retries {
                 if attempt > 0 {
@@ -419,11 +1811,59 @@ async fn trade_executor_loop(
                     tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
                 }
 
-                result = execute_single_trade(&app_handle, &order).await;
+                result = if order.paper {
+                    simulate_trade(&app_handle, &order).await
+                } else {
+                    let result = execute_single_trade(&app_handle, &order).await;
+                    let outcome = match &result {
+                        Ok(_) => RequestOutcome::Success,
+                        Err(e) if is_rate_limited(e) => RequestOutcome::RateLimited,
+                        Err(_) => RequestOutcome::Failed,
+                    };
+                    pacer.lock().unwrap_or_else(|e| e.into_inner()).record(outcome, &pacing);
+                    result
+                };
                 match &result {
                     Ok(_) => break,
                     Err(e) => {
                         last_error = e.clone();
+
+                        // The trade may have gone through despite the error;
+                        // check before retrying so it isn't placed twice
+                        if !order.paper && trade_reconcile::is_ambiguous(e) {
+                            match reconcile_trade(&app_handle, &order, started_at).await {
+                                Reconciliation::Landed(response) => {
+                                    warn!("Trade for {} failed with '{}' but landed server-side", order.symbol, e);
+                                    result = Ok(response);
+                                    break;
+                                }
+                                Reconciliation::NotLanded => {}
+                                Reconciliation::Unknown(why) => {
+                                    error!("Could not confirm trade outcome for {} ({}), not retrying", order.symbol, why);
+                                    last_error = format!("{} (outcome unconfirmed: {})", e, why);
+                                    result = Err(last_error.clone());
+                                    break;
+                                }
+                            }
+                        }
+
+                        // The coin isn't open for trading yet; retrying within
+                        // seconds won't help, the submitter schedules its own retry
+                        if is_not_yet_tradable(e) {
+                            info!("{} is not tradable yet, not retrying", order.symbol);
+                            break;
+                        }
+
+                        // Too small for the server; the same amount won't pass
+                        // on retry. Remember the minimum to pre-empt later buys
+                        if min_trade::is_below_minimum(e) {
+                            if let Some(minimum) = min_trade::learned_minimum(e) {
+                                info!("Server minimum trade is ${:.2}", minimum);
+                                *server_min_trade.write().await = Some(minimum);
+                            }
+                            break;
+                        }
+
                         if attempt < max_retries {
                             warn!("Trade attempt {}/{} failed for {}: {} — retrying", attempt + 1, max_retries + 1, order.symbol, e);
                         } else {
@@ -437,8 +1877,9 @@ async fn trade_executor_loop(
             if let Ok(ref response) = result {
                 let usd_amount = match order.trade_type {
                     TradeType::Buy => order.amount,
-                    TradeType::Sell => order.amount * response.new_price,
+                    TradeType::Sell => sell_proceeds(order.amount, response),
                 };
+                let mut tracker = tracker.write().await;
                 tracker.record(usd_amount);
 
                 // Improved loss detection for sells:
@@ -448,85 +1889,54 @@ async fn trade_executor_loop(
                 if matches!(order.trade_type, TradeType::Sell) && response.price_impact < 0.0 {
                     tracker.record_loss();
                 }
+                // Take-profit and trailing exits don't count: only a stop
+                // loss means the coin went against us
+                if matches!(order.trade_type, TradeType::Sell) && order.reason.is_stop_loss() {
+                    tracker.record_stop_loss(&order.symbol, chrono::Utc::now().timestamp());
+                }
+                drop(tracker);
             }
 
-            // Emit event to frontend
-            let event = match &result {
-                Ok(response) => TradeExecutedEvent {
-                    symbol: order.symbol.clone(),
-                    trade_type: format!("{:?}", order.trade_type),
-                    amount: order.amount,
-                    new_price: response.new_price,
-                    price_impact: response.price_impact,
-                    new_balance: response.new_balance,
-                    reason: order.reason.clone(),
-                    success: true,
-                    error: None,
-                },
-                Err(_) => TradeExecutedEvent {
-                    symbol: order.symbol.clone(),
-                    trade_type: format!("{:?}", order.trade_type),
-                    amount: order.amount,
-                    new_price: 0.0,
-                    price_impact: 0.0,
-                    new_balance: 0.0,
-                    reason: order.reason.clone(),
-                    success: false,
-                    error: Some(last_error),
-                },
+            // Publish the outcome; the frontend event and any other side
+            // effects are subscribers
+            let resolution = match &result {
+                Ok(response) => TradeResolution::Filled(response.clone()),
+                Err(_) => TradeResolution::Failed(last_error),
             };
-
-            // Emit to frontend via Tauri events
-            if let Err(e) = app_handle.emit("trade-executed", &event) {
-                warn!("Failed to emit trade-executed event: {}", e);
-            }
+            let _ = outcomes.send(TradeOutcome::new(&order, resolution));
 
             // Send result back to caller
             let _ = order.result_tx.send(result);
 
             // Persist daily tracker periodically (every 5 trades)
             save_counter += 1;
-            if tracker.dirty && save_counter % 5 == 0 {
-                save_daily_tracker(&app_handle, &tracker).await;
-                tracker.dirty = false;
+            let snapshot = {
+                let mut tracker = tracker.write().await;
+                let due = tracker.dirty && save_counter % 5 == 0;
+                tracker.dirty &= !due;
+                due.then(|| tracker.clone())
+            };
+            if let Some(snapshot) = snapshot {
+                save_daily_tracker(&app_handle, &snapshot).await;
             }
 
-            // Rate limit: configurable ms between trades
-            tokio::time::sleep(std::time::Duration::from_millis(rate_limit_ms)).await;
+            // Rate limit: the configured delay, widened after 429s when adaptive
+            let delay_ms = pacer.lock().unwrap_or_else(|e| e.into_inner()).delay_ms(&pacing);
+            if delay_ms > pacing.min_ms {
+                debug!("Trade pacing: waiting {}ms after recent rate limits", delay_ms);
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
         }
     }
 }
 
-/// Emit a risk-rejected event to the frontend and send notification
-fn emit_rejected(app_handle: &tauri::AppHandle, order: &TradeOrder, reason: &str) {
-    let event = TradeExecutedEvent {
-        symbol: order.symbol.clone(),
-        trade_type: format!("{:?}", order.trade_type),
-        amount: order.amount,
-        new_price: 0.0,
-        price_impact: 0.0,
-        new_balance: 0.0,
-        reason: format!("REJECTED: {}", reason),
-        success: false,
-        error: Some(reason.to_string()),
-    };
-    let _ = app_handle.emit("trade-executed", &event);
-
-    // Send native notification for risk rejection
-    if let Some(notif) = try_notify(app_handle) {
-        let symbol = order.symbol.clone();
-        let reason_owned = reason.to_string();
-        tokio::spawn(async move {
-            notif.notify_risk_rejected(&symbol, &reason_owned).await;
-        });
-    }
+/// Publish a risk-rejected order; listeners tell the frontend and notify
+fn publish_rejected(outcomes: &broadcast::Sender<TradeOutcome>, order: &TradeOrder, reason: &str) {
+    let _ = outcomes.send(TradeOutcome::new(order, TradeResolution::Rejected(reason.to_string())));
 }
 
-/// Execute a single trade using the active profile's token
-async fn execute_single_trade(
-    app_handle: &tauri::AppHandle,
-    order: &TradeOrder,
-) -> Result<TradeResponse, String> {
+/// Build an API client for the active profile
+pub(crate) async fn active_client(app_handle: &tauri::AppHandle) -> Result<RugplayClient, String> {
     use crate::AppState;
     use rugplay_persistence::sqlite;
     use tauri::Manager;
@@ -554,12 +1964,191 @@ async fn execute_single_trade(
     // Drop the DB lock before making the API call
     drop(db_guard);
 
-    let client = RugplayClient::new_with_cache(&token, state.coin_cache.clone());
+    Ok(state.api_client(&token))
+}
+
+/// Fetch the active profile's current cash balance
+async fn fetch_cash_balance(app_handle: &tauri::AppHandle) -> Result<f64, String> {
+    let client = active_client(app_handle).await?;
+    let portfolio = client.get_portfolio().await.map_err(|e| e.to_string())?;
+    Ok(portfolio.base_currency_balance)
+}
+
+/// The coin's price straight from the API, never from the cache
+async fn fetch_fresh_coin_price(app_handle: &tauri::AppHandle, symbol: &str) -> Result<f64, String> {
+    let client = active_client(app_handle).await?;
+    let coin = client.get_coin_within(symbol, Duration::ZERO).await.map_err(|e| e.to_string())?;
+    Ok(coin.current_price)
+}
+
+/// How old a coin is and how many 1-minute candles it has
+async fn fetch_coin_history(app_handle: &tauri::AppHandle, symbol: &str) -> Result<CoinHistory, String> {
+    let client = active_client(app_handle).await?;
+    let details = client.get_coin_with_chart(symbol, "1m").await.map_err(|e| e.to_string())?;
+    let age_secs = details
+        .coin
+        .created_at
+        .as_deref()
+        .and_then(parse_api_timestamp)
+        .map(|created| (chrono::Utc::now() - created).num_seconds());
+    Ok(CoinHistory { age_secs, candles: details.candlestick_data.len() })
+}
+
+/// Both legs of buying `buy_usd` of a coin and later selling the whole
+/// position, from its pool and what the portfolio already holds
+async fn fetch_round_trip(app_handle: &tauri::AppHandle, symbol: &str, buy_usd: f64) -> Result<Option<RoundTrip>, String> {
+    let client = active_client(app_handle).await?;
+    let coin = client.get_coin(symbol).await.map_err(|e| e.to_string())?;
+    let portfolio = client.get_portfolio().await.map_err(|e| e.to_string())?;
+    let held = portfolio
+        .coin_holdings
+        .iter()
+        .find(|h| h.symbol == symbol)
+        .map_or(0.0, |h| h.quantity);
+    let pool = PoolReserves { coins: coin.pool_coin_amount, usd: coin.pool_base_currency_amount };
+    Ok(round_trip::evaluate_round_trip(pool, buy_usd, held))
+}
+
+/// The order a preflight check stands in for; nothing waits on its result
+fn preflight_order(symbol: &str, source: &str, trade_type: TradeType, amount: f64, override_reserve: bool) -> TradeOrder {
+    let (result_tx, _) = oneshot::channel();
+    let reason = TradeReason::Manual { side: trade_type, split: false, mobile: source == "mobile", note: None };
+    let amount = TradeAmount::new(amount, AmountUnit::native(trade_type));
+    TradeOrder::manual(symbol.to_string(), trade_type, amount, reason, source, override_reserve, result_tx)
+}
+
+/// Every execution-time gate an order breaks: the trade size cap, the new
+/// coin gate, round-trip exit liquidity and the per-coin cool-off after a
+/// stop loss. The executor refuses on the first; the preflight lists them all.
+async fn order_gate_violations(
+    app_handle: &tauri::AppHandle,
+    limits: &RiskLimits,
+    tracker: &RwLock<DailyTracker>,
+    order: &TradeOrder,
+) -> Vec<RiskViolation> {
+    let mut violations = Vec::new();
+    if let Some(violation) = limits.trade_size_violation(&order.source, order.trade_type, order.amount) {
+        let message = format!("{} ({})", violation.message, order.symbol);
+        violations.push(RiskViolation { message, ..violation });
+    }
+
+    let history = if limits.requires_coin_history(order) {
+        fetch_coin_history(app_handle, &order.symbol)
+            .await
+            .map_err(|e| debug!("No history for {}: {}", order.symbol, e))
+            .ok()
+    } else {
+        None
+    };
+    if let Err(message) = limits.check_coin_history(order, history.as_ref()) {
+        violations.push(RiskViolation {
+            rule: RiskRule::CoinHistory,
+            current: history.and_then(|h| h.age_secs).unwrap_or(0) as f64,
+            limit: limits.min_coin_age_secs as f64,
+            message,
+        });
+    }
+
+    let round_trip = if limits.requires_exit_check(order) {
+        fetch_round_trip(app_handle, &order.symbol, order.amount)
+            .await
+            .map_err(|e| debug!("No pool for {}: {}", order.symbol, e))
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+    if let Err(message) = limits.check_exit_liquidity(order, round_trip.as_ref()) {
+        violations.push(RiskViolation {
+            rule: RiskRule::ExitLiquidity,
+            current: order.amount,
+            limit: limits.max_exit_impact_pct,
+            message,
+        });
+    }
+
+    let remaining = tracker.read().await.stop_loss_cooldown_remaining(
+        &order.symbol,
+        limits.stop_loss_cooldown_secs,
+        chrono::Utc::now().timestamp(),
+    );
+    if let Err(message) = limits.check_stop_loss_cooldown(order, remaining) {
+        violations.push(RiskViolation {
+            rule: RiskRule::StopLossCooldown,
+            current: remaining as f64,
+            limit: limits.stop_loss_cooldown_secs as f64,
+            message,
+        });
+    }
+    violations
+}
+
+/// Transaction polls made before an ambiguous trade is treated as not landed
+const RECONCILE_POLLS: u32 = 3;
+/// Gap between transaction polls, giving the server time to record the trade
+const RECONCILE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The account's latest transactions of the order's kind on its coin
+async fn recent_transactions(client: &RugplayClient, order: &TradeOrder) -> Result<Vec<ApiTransaction>, String> {
+    let trade_kind = match order.trade_type {
+        TradeType::Buy => "BUY",
+        TradeType::Sell => "SELL",
+    };
+    client
+        .get_transactions(1, 20, Some(trade_kind), Some(&order.symbol))
+        .await
+        .map(|page| page.transactions)
+        .map_err(|e| e.to_string())
+}
+
+/// Poll the account's recent transactions for a trade that failed ambiguously
+async fn reconcile_trade(
+    app_handle: &tauri::AppHandle,
+    order: &TradeOrder,
+    started_at: chrono::DateTime<chrono::Utc>,
+) -> Reconciliation {
+    let client = match active_client(app_handle).await {
+        Ok(client) => client,
+        Err(e) => return Reconciliation::Unknown(e),
+    };
+
+    for poll in 0..RECONCILE_POLLS {
+        if poll > 0 {
+            tokio::time::sleep(RECONCILE_POLL_INTERVAL).await;
+        }
+        let transactions = match recent_transactions(&client, order).await {
+            Ok(transactions) => transactions,
+            Err(e) => return Reconciliation::Unknown(e),
+        };
+        let landed = trade_reconcile::find_landed(&transactions, &order.symbol, order.trade_type, order.amount, started_at);
+        if let Some(tx) = landed {
+            let balance = match client.get_portfolio().await {
+                Ok(portfolio) => portfolio.base_currency_balance,
+                Err(_) => 0.0,
+            };
+            return Reconciliation::Landed(trade_reconcile::landed_response(tx, order.trade_type, balance));
+        }
+    }
+
+    debug!("No {:?} of {} recorded since {}, safe to retry", order.trade_type, order.symbol, started_at);
+    Reconciliation::NotLanded
+}
+
+/// Execute a single trade using the active profile's token
+async fn execute_single_trade(
+    app_handle: &tauri::AppHandle,
+    order: &TradeOrder,
+) -> Result<TradeResponse, String> {
+    let client = active_client(app_handle).await?;
+    let precision = {
+        use tauri::Manager;
+        app_handle.state::<crate::AppState>().coin_precision.read().await.clone()
+    };
 
-    // For sells, truncate to 8 decimal places
+    // For sells, truncate to the coin's decimal places
     let adjusted_amount = match order.trade_type {
         TradeType::Buy => order.amount,
-        TradeType::Sell => truncate_to_8_decimals(order.amount),
+        TradeType::Sell => precision.truncate(&order.symbol, order.amount),
     };
 
     let request = TradeRequest {
@@ -577,7 +2166,7 @@ async fn execute_single_trade(
         Err(ref e) if matches!(order.trade_type, TradeType::Sell) => {
             let err_str = e.to_string();
             if let Some(capped) = parse_max_sellable(&err_str) {
-                let capped = truncate_to_8_decimals(capped);
+                let capped = precision.truncate(&order.symbol, capped);
                 if capped > 0.0 && capped < adjusted_amount {
                     warn!(
                         "Sell {} of {} exceeds pool cap, retrying with max sellable: {}",
@@ -606,8 +2195,27 @@ async fn execute_single_trade(
     }
 
     info!(
-        "Trade executed: {:?} {} of {} @ ${}, impact {:.4}%",
-        order.trade_type, adjusted_amount, order.symbol, response.new_price, response.price_impact * 100.0
+        "Trade executed: {:?} {} of {} @ {:?}, impact {:.4}%",
+        order.trade_type, adjusted_amount, order.symbol, response.fill_price(), response.price_impact * 100.0
+    );
+
+    Ok(response)
+}
+
+/// Answer a paper order with the fill it would get at the coin's current pool
+async fn simulate_trade(app_handle: &tauri::AppHandle, order: &TradeOrder) -> Result<TradeResponse, String> {
+    let client = active_client(app_handle).await?;
+    let coin = client.get_coin(&order.symbol).await.map_err(|e| e.to_string())?;
+    let balance = client
+        .get_portfolio()
+        .await
+        .map_or(0.0, |portfolio| portfolio.base_currency_balance);
+    let pool = PoolReserves { coins: coin.pool_coin_amount, usd: coin.pool_base_currency_amount };
+    let response = paper_trading::simulate_fill(pool, order.trade_type, order.amount, balance)?;
+
+    info!(
+        "Paper trade ({}): would {:?} {} of {} @ {:?}, impact {:.4}%",
+        order.source, order.trade_type, order.amount, order.symbol, response.fill_price(), response.price_impact * 100.0
     );
 
     Ok(response)
@@ -675,6 +2283,15 @@ async fn save_daily_tracker(app_handle: &tauri::AppHandle, tracker: &DailyTracke
     debug!("Daily tracker persisted ({} trades)", tracker.trades.len());
 }
 
+/// USD a sell of `quantity` coins brought in: the API's total, else priced
+/// at the fill (0 when the response has neither)
+pub(crate) fn sell_proceeds(quantity: f64, response: &TradeResponse) -> f64 {
+    response
+        .total_received
+        .or_else(|| response.fill_price().map(|price| quantity * price))
+        .unwrap_or(0.0)
+}
+
 /// Parse the max sellable token amount from a pool cap error message.
 /// Example: `"Cannot sell more than 99.5% of pool tokens. Max sellable: 146960488 tokens"`
 fn parse_max_sellable(error: &str) -> Option<f64> {
@@ -690,3 +2307,642 @@ fn parse_max_sellable(error: &str) -> Option<f64> {
         .parse::<f64>()
         .ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(symbol: &str, priority: TradePriority) -> TradeOrder {
+        let (result_tx, _result_rx) = oneshot::channel();
+        TradeOrder {
+            id: 0,
+            symbol: symbol.to_string(),
+            trade_type: TradeType::Buy,
+            amount: 100.0,
+            amount_unit: AmountUnit::Usd,
+            priority,
+            reason: TradeReason::Sniper { symbol: symbol.to_string(), age_secs: 60, market_cap: 1_000.0 },
+            source: "sniper".to_string(),
+            override_reserve: false,
+            paper: false,
+            quoted_price: None,
+            max_price_move_pct: None,
+            result_tx,
+        }
+    }
+
+    #[test]
+    fn test_queued_trade_can_be_listed_and_cancelled() {
+        let mut registry = PendingRegistry::default();
+        let normal = registry.register(&order("AAA", TradePriority::Normal));
+        let high = registry.register(&order("BBB", TradePriority::High));
+
+        // Listed in execution order: higher priority first
+        let listed = registry.list();
+        assert_eq!(listed.iter().map(|p| p.id).collect::<Vec<_>>(), vec![high, normal]);
+        assert_eq!(listed[1].symbol, "AAA");
+        assert_eq!(listed[1].source, "sniper");
+
+        // Cancel before the loop picks it up: it disappears and won't run
+        assert_eq!(registry.cancel(normal).map(|p| p.symbol), Some("AAA".to_string()));
+        assert!(!registry.take(normal));
+        assert!(registry.cancel(normal).is_none());
+
+        // The other order still executes normally
+        assert!(registry.take(high));
+        assert!(registry.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_emergency_halt_clears_queue_and_refuses_automation() {
+        let mut registry = PendingRegistry::default();
+        let first = registry.register(&order("AAA", TradePriority::Normal));
+        let second = registry.register(&order("BBB", TradePriority::Critical));
+        assert_eq!(registry.clear().len(), 2);
+        assert!(!registry.take(first) && !registry.take(second));
+
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_automation_halted(true);
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+        for source in ["sniper", "sentinel"] {
+            let err = executor
+                .submit_trade("AAA".to_string(), TradeType::Sell, 10.0, TradePriority::Critical, reason.clone(), source)
+                .await
+                .unwrap_err();
+            assert!(err.contains("Emergency stop"), "{}", err);
+        }
+        executor
+            .submit_manual_trade("AAA".to_string(), TradeType::Sell, TradeAmount::Coins(5.0), reason.clone(), MANUAL_SOURCE, false)
+            .await
+            .unwrap();
+        assert_eq!(recorder.take().len(), 1);
+
+        executor.set_automation_halted(false);
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::High, reason, "sniper")
+            .await
+            .unwrap();
+        assert_eq!(recorder.take().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_paper_trading_marks_only_automated_orders() {
+        let (executor, _recorder) = spawn_recording_executor();
+        let mut outcomes = executor.subscribe_outcomes();
+        executor.set_paper_trading(true);
+
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+        let paper_fill = executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::Normal, reason.clone(), "sniper")
+            .await
+            .unwrap();
+        let real_fill = executor
+            .submit_manual_trade("AAA".to_string(), TradeType::Buy, TradeAmount::Usd(10.0), reason, MANUAL_SOURCE, false)
+            .await
+            .unwrap();
+        // The module sees which fill was simulated, to skip its sentinel and stats
+        assert!(paper_fill.paper && !real_fill.paper);
+
+        let sniper = outcomes.recv().await.unwrap();
+        let manual = outcomes.recv().await.unwrap();
+        assert_eq!((sniper.source.as_str(), sniper.paper), ("sniper", true));
+        assert_eq!((manual.source.as_str(), manual.paper), (MANUAL_SOURCE, false));
+        assert!(sniper.to_event().unwrap().paper);
+    }
+
+    #[tokio::test]
+    async fn test_paper_trading_keeps_sentinel_sells_real() {
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_paper_trading(true);
+
+        let exit = TradeReason::Sentinel { sentinel_id: 1, trigger_type: "stop_loss".to_string(), detail: String::new() };
+        let sell = executor
+            .submit_trade("AAA".to_string(), TradeType::Sell, 5.0, TradePriority::High, exit, "sentinel")
+            .await
+            .unwrap();
+        let entry = TradeReason::Sniper { symbol: "BBB".to_string(), age_secs: 60, market_cap: 1_000.0 };
+        let buy = executor
+            .submit_trade("BBB".to_string(), TradeType::Buy, 10.0, TradePriority::Normal, entry, "sniper")
+            .await
+            .unwrap();
+
+        // The stop-loss is sent for real, the sniper buy only simulated
+        assert!(!sell.paper && buy.paper);
+        let sent = recorder.take();
+        assert_eq!(sent.len(), 1);
+        assert_eq!((sent[0].symbol.as_str(), sent[0].source.as_str()), ("AAA", "sentinel"));
+    }
+
+    #[tokio::test]
+    async fn test_drawdown_breaker_refuses_buys_but_not_sells() {
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_risk_limits(RiskLimits { max_drawdown_pct: 20.0, ..RiskLimits::default() }).await;
+        executor.record_portfolio_value(1_000.0, "2026-10-16").await;
+        executor.record_portfolio_value(750.0, "2026-10-16").await;
+        assert!(executor.drawdown_status().await.tripped);
+
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+        let err = executor
+            .submit_manual_trade("AAA".to_string(), TradeType::Buy, TradeAmount::Usd(10.0), reason.clone(), MANUAL_SOURCE, false)
+            .await
+            .unwrap_err();
+        assert!(err.contains("Drawdown circuit breaker"), "{}", err);
+        assert_eq!(executor.status().await.buy_blocks.len(), 1);
+        // The trade dialog's preflight goes through the same admission
+        let buy = preflight_order("AAA", MANUAL_SOURCE, TradeType::Buy, 10.0, false);
+        assert_eq!(executor.admission_violation(&buy).await.map(|v| v.rule), Some(RiskRule::Admission));
+        let sell = preflight_order("AAA", MANUAL_SOURCE, TradeType::Sell, 5.0, false);
+        assert!(executor.admission_violation(&sell).await.is_none());
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Sell, 5.0, TradePriority::High, reason.clone(), "sentinel")
+            .await
+            .unwrap();
+        assert_eq!(recorder.take().len(), 1);
+
+        // Recovering above the limit lets buys through again
+        executor.record_portfolio_value(900.0, "2026-10-16").await;
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::Normal, reason, "sniper")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watchlist_only_limits_automated_buys_to_watched_coins() {
+        let (executor, recorder) = spawn_recording_executor();
+        executor.set_tradable_symbols(vec!["aaa".to_string()]).await;
+        let reason = TradeReason::Sniper { symbol: "BBB".to_string(), age_secs: 60, market_cap: 1_000.0 };
+        let buy = |symbol: &str, source: &'static str| {
+            executor.submit_trade(symbol.to_string(), TradeType::Buy, 10.0, TradePriority::Normal, reason.clone(), source)
+        };
+
+        // Off: anything goes
+        buy("BBB", "sniper").await.unwrap();
+        assert_eq!(recorder.take().len(), 1);
+
+        executor.set_watchlist_only(true).await;
+        let err = buy("BBB", "sniper").await.unwrap_err();
+        assert!(err.contains("not on the watchlist"), "{}", err);
+        assert!(buy("BBB", "dipbuyer").await.is_err());
+        assert!(recorder.trades().is_empty());
+
+        // Watched coins pass, as do sells and the user's own trades
+        buy("AAA", "mirror").await.unwrap();
+        executor
+            .submit_trade("BBB".to_string(), TradeType::Sell, 5.0, TradePriority::High, reason.clone(), "sentinel")
+            .await
+            .unwrap();
+        executor
+            .submit_manual_trade("BBB".to_string(), TradeType::Buy, TradeAmount::Usd(10.0), reason.clone(), MANUAL_SOURCE, false)
+            .await
+            .unwrap();
+        assert_eq!(recorder.take().iter().map(|t| t.symbol.as_str()).collect::<Vec<_>>(), vec!["AAA", "BBB", "BBB"]);
+
+        // The watchlist changed: BBB is now tradable and AAA is not
+        executor.set_tradable_symbols(vec!["BBB".to_string()]).await;
+        buy("BBB", "sniper").await.unwrap();
+        assert!(buy("AAA", "sniper").await.is_err());
+    }
+
+    fn limits_with_reserve(reserve: f64) -> RiskLimits {
+        RiskLimits { frozen_reserve_usd: reserve, ..RiskLimits::default() }
+    }
+
+    #[test]
+    fn test_automated_buy_respects_frozen_reserve() {
+        let limits = limits_with_reserve(10_000.0);
+        assert_eq!(limits.spendable_balance(12_500.0), 2_500.0);
+        assert_eq!(limits.spendable_balance(8_000.0), 0.0);
+
+        assert!(limits.check_frozen_reserve(12_500.0, 2_000.0, false).is_ok());
+        assert!(limits.check_frozen_reserve(12_500.0, 3_000.0, false).is_err());
+        assert!(limits.check_frozen_reserve(8_000.0, 1.0, false).is_err());
+
+        // Disabled reserve never blocks
+        assert!(limits_with_reserve(0.0).check_frozen_reserve(100.0, 100.0, false).is_ok());
+    }
+
+    #[test]
+    fn test_manual_trade_can_override_frozen_reserve() {
+        let limits = limits_with_reserve(10_000.0);
+        assert!(limits.check_frozen_reserve(12_500.0, 5_000.0, false).is_err());
+        assert!(limits.check_frozen_reserve(12_500.0, 5_000.0, true).is_ok());
+    }
+
+    #[test]
+    fn test_manual_trade_is_tagged_and_respects_hard_limits() {
+        let (result_tx, _result_rx) = oneshot::channel();
+        let manual = TradeOrder::manual(
+            "AAA".to_string(),
+            TradeType::Buy,
+            TradeAmount::Usd(500.0),
+            TradeReason::Manual { side: TradeType::Buy, split: false, mobile: false, note: None },
+            MANUAL_SOURCE,
+            true,
+            result_tx,
+        );
+
+        let mut registry = PendingRegistry::default();
+        registry.register(&manual);
+        assert_eq!(registry.list()[0].source, "manual");
+
+        // Overriding the reserve does not lift the other limits
+        assert!(manual.is_risk_checked());
+        let limits = RiskLimits {
+            max_position_usd: 250.0,
+            max_daily_trades_count: 10,
+            max_daily_volume_usd: 1_000.0,
+            cooldown_after_loss_secs: 60,
+            ..RiskLimits::default()
+        };
+        let idle = RiskActivity::default();
+        assert!(limits.check_buy(manual.amount, &idle).unwrap_err().contains("max position"));
+        let busy = RiskActivity { daily_trades: 9, daily_volume_usd: 800.0, cooldown_remaining_secs: 0 };
+        assert!(limits.check_buy(200.0, &busy).is_ok());
+
+        // Unlimited defaults never block
+        let heavy = RiskActivity { daily_trades: 1_000, daily_volume_usd: 1e9, cooldown_remaining_secs: 0 };
+        assert!(RiskLimits::default().check_buy(1e9, &heavy).is_ok());
+    }
+
+    #[test]
+    fn test_only_non_critical_buys_are_risk_checked() {
+        assert!(order("AAA", TradePriority::Normal).is_risk_checked());
+        assert!(!order("AAA", TradePriority::Critical).is_risk_checked());
+
+        let mut sell = order("AAA", TradePriority::Normal);
+        sell.trade_type = TradeType::Sell;
+        assert!(!sell.is_risk_checked());
+    }
+
+    fn strict_limits() -> RiskLimits {
+        RiskLimits {
+            max_position_usd: 250.0,
+            max_daily_trades_count: 10,
+            max_daily_volume_usd: 1_000.0,
+            cooldown_after_loss_secs: 60,
+            frozen_reserve_usd: 5_000.0,
+            ..RiskLimits::default()
+        }
+    }
+
+    fn rules(violations: &[RiskViolation]) -> Vec<RiskRule> {
+        violations.iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn test_each_rule_can_be_the_blocker() {
+        let limits = strict_limits();
+        let idle = RiskActivity::default();
+        let rich = Some(100_000.0);
+
+        assert!(limits.buy_violations(200.0, &idle, rich, false).is_empty());
+
+        let v = limits.buy_violations(300.0, &idle, rich, false);
+        assert_eq!(rules(&v), vec![RiskRule::MaxPosition]);
+        assert_eq!((v[0].current, v[0].limit), (300.0, 250.0));
+
+        let v = limits.buy_violations(200.0, &RiskActivity { daily_trades: 10, ..idle }, rich, false);
+        assert_eq!(rules(&v), vec![RiskRule::DailyTradeCount]);
+        assert_eq!((v[0].current, v[0].limit), (10.0, 10.0));
+
+        let v = limits.buy_violations(200.0, &RiskActivity { daily_volume_usd: 900.0, ..idle }, rich, false);
+        assert_eq!(rules(&v), vec![RiskRule::DailyVolume]);
+        assert_eq!((v[0].current, v[0].limit), (1_100.0, 1_000.0));
+
+        let v = limits.buy_violations(200.0, &RiskActivity { cooldown_remaining_secs: 45, ..idle }, rich, false);
+        assert_eq!(rules(&v), vec![RiskRule::LossCooldown]);
+        assert_eq!((v[0].current, v[0].limit), (45.0, 60.0));
+
+        let v = limits.buy_violations(200.0, &idle, Some(5_100.0), false);
+        assert_eq!(rules(&v), vec![RiskRule::FrozenReserve]);
+        assert_eq!((v[0].current, v[0].limit), (200.0, 100.0));
+        assert!(limits.buy_violations(200.0, &idle, Some(5_100.0), true).is_empty());
+    }
+
+    #[test]
+    fn test_all_failing_rules_are_reported() {
+        let busy = RiskActivity { daily_trades: 12, daily_volume_usd: 950.0, cooldown_remaining_secs: 30 };
+        let v = strict_limits().buy_violations(400.0, &busy, Some(5_000.0), false);
+        assert_eq!(
+            rules(&v),
+            vec![
+                RiskRule::MaxPosition,
+                RiskRule::DailyTradeCount,
+                RiskRule::DailyVolume,
+                RiskRule::LossCooldown,
+                RiskRule::FrozenReserve,
+            ]
+        );
+        // The executor rejects with the first one
+        assert_eq!(strict_limits().check_buy(400.0, &busy).unwrap_err(), v[0].message);
+    }
+
+    #[test]
+    fn test_cooldown_remaining_counts_down() {
+        let mut tracker = DailyTracker {
+            last_loss_timestamp: Some(chrono::Utc::now().timestamp() - 20),
+            ..DailyTracker::default()
+        };
+        let remaining = tracker.activity(60).cooldown_remaining_secs;
+        assert!((39..=40).contains(&remaining));
+        assert_eq!(tracker.cooldown_remaining(15), 0);
+        assert_eq!(tracker.cooldown_remaining(0), 0);
+    }
+
+    #[test]
+    fn test_stop_loss_blocks_rebuying_the_coin() {
+        let limits = RiskLimits { stop_loss_cooldown_secs: 600, ..RiskLimits::default() };
+        let now = 1_700_000_000;
+        let mut tracker = DailyTracker::default();
+        tracker.record_stop_loss("CHOP", now - 100);
+
+        let buy = |symbol: &str, source: &str| sourced(symbol, TradePriority::Normal, source);
+        let remaining = tracker.stop_loss_cooldown_remaining("CHOP", limits.stop_loss_cooldown_secs, now);
+        assert_eq!(remaining, 500);
+        for source in ["dipbuyer", "sniper", "mirror"] {
+            let err = limits.check_stop_loss_cooldown(&buy("CHOP", source), remaining).unwrap_err();
+            assert_eq!(err, format!("Risk limit: CHOP hit a stop loss, {} buys blocked for 500s more", source));
+        }
+        // Only that coin, only module buys, only inside the window
+        assert_eq!(tracker.stop_loss_cooldown_remaining("OTHER", 600, now), 0);
+        assert!(limits.check_stop_loss_cooldown(&buy("CHOP", MANUAL_SOURCE), remaining).is_ok());
+        assert_eq!(tracker.stop_loss_cooldown_remaining("CHOP", 600, now + 500), 0);
+        assert_eq!(tracker.stop_loss_cooldown_remaining("CHOP", 0, now), 0);
+
+        // A take-profit exit doesn't start a cool-off
+        let exit = |trigger: &str| TradeReason::Sentinel { sentinel_id: 1, trigger_type: trigger.into(), detail: String::new() };
+        assert!(exit("stop_loss").is_stop_loss());
+        assert!(exit("stop_loss").tranche(1, 3).is_stop_loss());
+        assert!(!exit("take_profit").is_stop_loss());
+    }
+
+    #[test]
+    fn test_exit_check_rejects_one_way_liquid_buys() {
+        let limits = RiskLimits { max_exit_impact_pct: 10.0, ..RiskLimits::default() };
+        let pool = PoolReserves { coins: 1_000_000.0, usd: 100_000.0 };
+        let fresh = round_trip::evaluate_round_trip(pool, 100.0, 0.0);
+        let trapped = round_trip::evaluate_round_trip(pool, 100.0, 400_000.0);
+
+        let dip = sourced("COIN", TradePriority::Normal, "dipbuyer");
+        assert!(limits.check_exit_liquidity(&dip, fresh.as_ref()).is_ok());
+        assert!(limits.check_exit_liquidity(&dip, trapped.as_ref()).unwrap_err().starts_with("Exit check: COIN"));
+        assert!(limits.check_exit_liquidity(&dip, None).is_err());
+
+        // Manual buys and a disabled cap skip the check
+        let manual = sourced("COIN", TradePriority::Normal, MANUAL_SOURCE);
+        assert!(!limits.requires_exit_check(&manual));
+        assert!(limits.check_exit_liquidity(&manual, trapped.as_ref()).is_ok());
+        assert!(RiskLimits::default().check_exit_liquidity(&dip, None).is_ok());
+    }
+
+    fn sourced(symbol: &str, priority: TradePriority, source: &str) -> TradeOrder {
+        TradeOrder { source: source.to_string(), ..order(symbol, priority) }
+    }
+
+    fn heap_of(orders: Vec<TradeOrder>) -> BinaryHeap<PrioritizedOrder> {
+        orders
+            .into_iter()
+            .enumerate()
+            .map(|(i, order)| PrioritizedOrder { order, seq: i as u64 })
+            .collect()
+    }
+
+    #[test]
+    fn test_rapid_automated_orders_are_spaced_to_interval() {
+        let interval = Duration::from_secs(10);
+        let mut heap = heap_of(vec![
+            sourced("AAA", TradePriority::Normal, "dipbuyer"),
+            sourced("BBB", TradePriority::Normal, "dipbuyer"),
+            sourced("CCC", TradePriority::High, "sniper"),
+        ]);
+        let mut throttle = AutomationThrottle::default();
+        let start = Instant::now();
+
+        // Replay the executor loop on a simulated clock, one second per tick
+        let mut executed = Vec::new();
+        for tick in 0..=30 {
+            let now = start + Duration::from_secs(tick);
+            let wait = throttle.remaining(now, interval);
+            if let Some(next) = pop_next(&mut heap, !wait.is_zero()) {
+                throttle.record(now);
+                executed.push((tick, next.order.symbol));
+            }
+        }
+
+        assert_eq!(
+            executed,
+            vec![(0, "CCC".to_string()), (10, "AAA".to_string()), (20, "BBB".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_exempt_orders_pass_while_throttled() {
+        assert!(sourced("AAA", TradePriority::Normal, "mirror").is_throttled());
+        assert!(!sourced("AAA", TradePriority::High, "sentinel").is_throttled());
+        assert!(!sourced("AAA", TradePriority::Normal, MANUAL_SOURCE).is_throttled());
+        assert!(!sourced("AAA", TradePriority::Critical, "sniper").is_throttled());
+
+        let mut heap = heap_of(vec![
+            sourced("AUTO", TradePriority::High, "sniper"),
+            sourced("EXIT", TradePriority::High, "sentinel"),
+        ]);
+        let next = pop_next(&mut heap, true).unwrap();
+        assert_eq!(next.order.symbol, "EXIT");
+        // The held automated order is still queued
+        assert_eq!(heap.len(), 1);
+        assert!(pop_next(&mut heap, true).is_none());
+        assert_eq!(pop_next(&mut heap, false).unwrap().order.symbol, "AUTO");
+    }
+
+    #[test]
+    fn test_throttle_disabled_by_default() {
+        let mut throttle = AutomationThrottle::default();
+        let now = Instant::now();
+        throttle.record(now);
+        let interval = Duration::from_secs(RiskLimits::default().min_automated_interval_secs);
+        assert!(throttle.remaining(now, interval).is_zero());
+    }
+
+    #[test]
+    fn test_oversized_orders_from_any_module_are_rejected() {
+        let limits = RiskLimits { max_trade_usd: 500.0, ..RiskLimits::default() };
+        for source in ["sniper", "mirror", "dipbuyer"] {
+            let mut big = sourced("AAA", TradePriority::High, source);
+            big.amount = 500.01;
+            let err = limits.check_trade_size(&big).unwrap_err();
+            assert!(err.contains(source) && err.contains("max $500.00"), "{}", err);
+
+            big.amount = 500.0;
+            assert!(limits.check_trade_size(&big).is_ok());
+        }
+
+        // The user's own trades aren't capped
+        for source in [MANUAL_SOURCE, "mobile"] {
+            let mut big = sourced("AAA", TradePriority::Normal, source);
+            big.amount = 1e6;
+            assert!(limits.check_trade_size(&big).is_ok());
+        }
+
+        // Protective sells of any size close the position
+        let mut sell = sourced("AAA", TradePriority::High, "sentinel");
+        sell.trade_type = TradeType::Sell;
+        sell.amount = 1e9;
+        assert!(limits.check_trade_size(&sell).is_ok());
+
+        // The preview reports the same cap
+        let violation = limits.trade_size_violation("sniper", TradeType::Buy, 600.0).unwrap();
+        assert!(matches!(violation.rule, RiskRule::TradeSizeCap));
+        assert_eq!((violation.current, violation.limit), (600.0, 500.0));
+        assert!(limits.trade_size_violation("sentinel", TradeType::Sell, 600.0).is_none());
+
+        // Disabled by default
+        assert!(RiskLimits::default().check_trade_size(&sourced("AAA", TradePriority::Normal, "sniper")).is_ok());
+    }
+
+    #[test]
+    fn test_module_cap_tightens_but_never_loosens_global_cap() {
+        let limits = RiskLimits {
+            max_trade_usd: 1_000.0,
+            module_max_trade_usd: HashMap::from([
+                ("sniper".to_string(), 50.0),
+                ("mirror".to_string(), 5_000.0),
+                ("dipbuyer".to_string(), 0.0),
+            ]),
+            ..RiskLimits::default()
+        };
+        assert_eq!(limits.trade_cap_for("sniper"), Some(50.0));
+        assert_eq!(limits.trade_cap_for("mirror"), Some(1_000.0));
+        assert_eq!(limits.trade_cap_for("dipbuyer"), Some(1_000.0));
+        assert_eq!(limits.trade_cap_for("manual"), Some(1_000.0));
+
+        // Module caps alone still apply with no global cap
+        let module_only = RiskLimits { max_trade_usd: 0.0, ..limits };
+        assert_eq!(module_only.trade_cap_for("sniper"), Some(50.0));
+        assert_eq!(module_only.trade_cap_for("manual"), None);
+        assert!(module_only.check_trade_size(&sourced("AAA", TradePriority::Normal, "sniper")).is_err());
+        assert!(module_only.check_trade_size(&sourced("AAA", TradePriority::Normal, "mirror")).is_ok());
+    }
+
+    #[test]
+    fn test_too_new_coin_is_blocked_across_modules() {
+        let limits = RiskLimits { min_coin_age_secs: 600, min_candle_count: 10, ..RiskLimits::default() };
+        let fresh = CoinHistory { age_secs: Some(120), candles: 2 };
+        let aged = CoinHistory { age_secs: Some(3_600), candles: 60 };
+
+        for source in ["sniper", "mirror", "dipbuyer", "mobile"] {
+            let buy = sourced("NEW", TradePriority::High, source);
+            let err = limits.check_coin_history(&buy, Some(&fresh)).unwrap_err();
+            assert!(err.contains("120s old") && err.contains(source), "{}", err);
+            assert!(limits.check_coin_history(&buy, Some(&aged)).is_ok());
+
+            // Old enough but barely traded, or unknown history, still blocks
+            let quiet = CoinHistory { age_secs: Some(3_600), candles: 3 };
+            assert!(limits.check_coin_history(&buy, Some(&quiet)).unwrap_err().contains("3 candles"));
+            assert!(limits.check_coin_history(&buy, None).is_err());
+            assert!(limits.check_coin_history(&buy, Some(&CoinHistory { age_secs: None, candles: 60 })).is_err());
+        }
+
+        // Manual buys and explicitly allowed modules go through
+        assert!(!limits.requires_coin_history(&sourced("NEW", TradePriority::High, MANUAL_SOURCE)));
+        let allowed = RiskLimits {
+            new_coin_allowed_sources: vec!["sniper".to_string()],
+            ..limits.clone()
+        };
+        assert!(allowed.check_coin_history(&sourced("NEW", TradePriority::High, "sniper"), Some(&fresh)).is_ok());
+        assert!(allowed.check_coin_history(&sourced("NEW", TradePriority::High, "mirror"), Some(&fresh)).is_err());
+
+        // Sells and critical orders are never gated; disabled by default
+        let mut sell = sourced("NEW", TradePriority::High, "mirror");
+        sell.trade_type = TradeType::Sell;
+        assert!(!limits.requires_coin_history(&sell));
+        assert!(!limits.requires_coin_history(&sourced("NEW", TradePriority::Critical, "mirror")));
+        assert!(!RiskLimits::default().requires_coin_history(&sourced("NEW", TradePriority::High, "mirror")));
+    }
+
+    #[tokio::test]
+    async fn test_no_automated_trades_are_submitted_during_warmup() {
+        let warmup = Arc::new(WarmupGate::default());
+        let (executor, recorder) = spawn_recording_executor_with(warmup.clone());
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+
+        for source in ["sniper", "mirror", "dipbuyer", "sentinel"] {
+            let err = executor
+                .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::High, reason.clone(), source)
+                .await
+                .unwrap_err();
+            assert!(err.contains("Warming up"), "{}", err);
+        }
+        executor
+            .submit_trade_fire_and_forget("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::Normal, reason.clone(), "dipbuyer")
+            .await;
+        assert!(executor.list_pending_trades().await.is_empty());
+
+        // The user can still trade by hand
+        executor
+            .submit_manual_trade("AAA".to_string(), TradeType::Buy, TradeAmount::Usd(5.0), reason.clone(), MANUAL_SOURCE, false)
+            .await
+            .unwrap();
+        assert_eq!(recorder.take().len(), 1);
+
+        // Stops run as soon as the settings and the profile are loaded
+        warmup.complete(crate::warmup::WarmupStep::Settings);
+        warmup.complete(crate::warmup::WarmupStep::Profile);
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Sell, 10.0, TradePriority::Critical, reason.clone(), "sentinel")
+            .await
+            .unwrap();
+        assert!(executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::High, reason.clone(), "sniper")
+            .await
+            .is_err());
+        assert_eq!(recorder.take()[0].source, "sentinel");
+
+        for &step in crate::warmup::WARMUP_STEPS {
+            warmup.complete(step);
+        }
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::High, reason, "sniper")
+            .await
+            .unwrap();
+        let trades = recorder.take();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].source, "sniper");
+    }
+
+    #[tokio::test]
+    async fn test_split_sell_reports_what_a_failed_tranche_left_unsold() {
+        let (executor, recorder) = spawn_recording_executor();
+        let split = SplitSellConfig { tranches: 3, spacing_ms: 200, max_total_secs: 10, ..Default::default() };
+        executor.set_split_config(split).await;
+        let reason = TradeReason::Sentinel { sentinel_id: 1, trigger_type: "stop_loss".to_string(), detail: "test".to_string() };
+
+        let sell = {
+            let (executor, reason) = (executor.clone(), reason.clone());
+            tokio::spawn(async move {
+                executor.submit_split_sell("AAA".to_string(), 30.0, TradePriority::High, reason, "sentinel").await
+            })
+        };
+        // The emergency stop lands between the first and second tranche
+        while recorder.trades().is_empty() {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        executor.set_automation_halted(true);
+
+        let fill = sell.await.unwrap().unwrap();
+        assert!(fill.is_partial());
+        assert_eq!((fill.sold, fill.unsold), (10.0, 20.0));
+        assert!(fill.error.unwrap().contains("Emergency stop"));
+        assert_eq!(fill.response.coins_sold, Some(10.0));
+        assert_eq!(recorder.trades().len(), 1);
+
+        // With no tranche filled it is an error, as before
+        let err = executor.submit_split_sell("AAA".to_string(), 30.0, TradePriority::High, reason.clone(), "sentinel").await;
+        assert!(err.is_err());
+
+        executor.set_automation_halted(false);
+        let fill = executor.submit_split_sell("AAA".to_string(), 30.0, TradePriority::High, reason, "sentinel").await.unwrap();
+        assert!(!fill.is_partial());
+        assert_eq!((fill.sold, fill.unsold, fill.error), (30.0, 0.0, None));
+    }
+}
@@ -0,0 +1,126 @@
+//! Trade Journal — local record of filled trades and why they were made
+//!
+//! Every order the executor fills is written to the `transactions` table
+//! with a note: the user's own text for manual trades, the module's reason
+//! string for automated ones. Trade history itself comes from the Rugplay
+//! API, which knows nothing about notes, so history pages are matched back
+//! to journal rows by symbol, side and time.
+
+use rugplay_core::{parse_api_timestamp, parse_db_timestamp};
+use rugplay_persistence::sqlite::TransactionRow;
+
+/// How many recent journal rows are searched for notes on a history page
+pub const JOURNAL_MATCH_WINDOW: u32 = 500;
+
+/// Max clock difference between a journaled trade and its API record
+const JOURNAL_MATCH_TOLERANCE_SECS: i64 = 120;
+
+/// Notes for API history records given as (symbol, trade type, timestamp).
+/// A record takes the note of the closest journal row with the same symbol
+/// and side within the tolerance; each journal row is used at most once.
+pub fn match_journal_notes(records: &[(&str, &str, &str)], journal: &[TransactionRow]) -> Vec<Option<String>> {
+    let mut used = vec![false; journal.len()];
+    records
+        .iter()
+        .map(|(symbol, trade_type, timestamp)| {
+            let at = parse_api_timestamp(timestamp)?;
+            let (i, _) = journal
+                .iter()
+                .enumerate()
+                .filter(|(i, row)| {
+                    !used[*i]
+                        && row.note.is_some()
+                        && row.symbol == *symbol
+                        && row.trade_type.eq_ignore_ascii_case(trade_type)
+                })
+                .filter_map(|(i, row)| {
+                    let logged = parse_db_timestamp(row.timestamp.as_deref()?)?;
+                    let gap = (logged - at).num_seconds().abs();
+                    (gap <= JOURNAL_MATCH_TOLERANCE_SECS).then_some((i, gap))
+                })
+                .min_by_key(|(_, gap)| *gap)?;
+            used[i] = true;
+            journal[i].note.clone()
+        })
+        .collect()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Journal rows as CSV with a header line
+pub fn journal_csv(rows: &[TransactionRow]) -> String {
+    let mut csv = String::from("timestamp,symbol,type,coin_amount,price,usd_value,note\n");
+    for row in rows {
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_field(row.timestamp.as_deref().unwrap_or("")),
+            csv_field(&row.symbol),
+            csv_field(&row.trade_type),
+            row.coin_amount,
+            row.price,
+            row.usd_value,
+            csv_field(row.note.as_deref().unwrap_or("")),
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journaled(symbol: &str, trade_type: &str, timestamp: &str, note: Option<&str>) -> TransactionRow {
+        TransactionRow {
+            id: 0,
+            profile_id: 1,
+            symbol: symbol.to_string(),
+            trade_type: trade_type.to_string(),
+            coin_amount: 1.0,
+            price: 1.0,
+            usd_value: 1.0,
+            timestamp: Some(timestamp.to_string()),
+            note: note.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_notes_match_closest_journal_row() {
+        let records = [
+            ("AAA", "BUY", "2024-05-01T12:00:05.000Z"),
+            ("AAA", "BUY", "2024-05-01T12:01:00.000Z"),
+            ("AAA", "SELL", "2024-05-01T12:00:05.000Z"),
+            ("BBB", "BUY", "2024-05-01T15:00:00.000Z"),
+        ];
+        let journal = vec![
+            journaled("AAA", "BUY", "2024-05-01 12:01:01", Some("second buy")),
+            journaled("AAA", "BUY", "2024-05-01 12:00:00", Some("testing thesis X")),
+            journaled("AAA", "SELL", "2024-05-01 11:00:00", Some("too old to match")),
+            journaled("BBB", "BUY", "2024-05-01 15:00:30", None),
+        ];
+
+        let notes = match_journal_notes(&records, &journal);
+        assert_eq!(
+            notes,
+            [Some("testing thesis X".to_string()), Some("second buy".to_string()), None, None]
+        );
+    }
+
+    #[test]
+    fn test_journal_csv_quotes_notes() {
+        let rows = vec![
+            journaled("AAA", "BUY", "2024-05-01 12:00:00", Some(r#"dip, "maybe""#)),
+            journaled("AAA", "SELL", "2024-05-01 12:05:00", None),
+        ];
+        let csv = journal_csv(&rows);
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines[0], "timestamp,symbol,type,coin_amount,price,usd_value,note");
+        assert_eq!(lines[1], r#"2024-05-01 12:00:00,AAA,BUY,1,1,1,"dip, ""maybe""""#);
+        assert_eq!(lines[2], "2024-05-01 12:05:00,AAA,SELL,1,1,1,");
+    }
+}
@@ -0,0 +1,272 @@
+//! Trade Outcomes — one broadcast per resolved order
+//!
+//! Every order the executor finishes with (filled, failed, rejected by a
+//! risk check or cancelled while queued) is published as a [`TradeOutcome`]
+//! on the executor's broadcast channel. Side effects that react to trades
+//! subscribe through `TradeExecutorHandle::subscribe_outcomes` instead of
+//! being called from the execution path; [`spawn_outcome_listeners`] runs
+//! the built-in ones (the `trade-executed` event, rejection alerts and the
+//! local trade journal).
+
+use crate::app_event::{emit_event, AppEvent};
+use crate::trade_executor::{sell_proceeds, TradeExecutedEvent, TradeExecutorHandle, TradeOrder, TradePriority};
+use crate::trade_reason::TradeReason;
+use chrono::{DateTime, Utc};
+use rugplay_core::{TradeResponse, TradeType};
+use rugplay_persistence::sqlite;
+use tauri::Manager;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+/// Outcomes buffered per subscriber before the slowest one starts missing them
+pub const OUTCOME_CHANNEL_CAPACITY: usize = 256;
+
+/// How an order ended
+#[derive(Debug, Clone)]
+pub enum TradeResolution {
+    Filled(TradeResponse),
+    /// Sent to the server (possibly retried) without success
+    Failed(String),
+    /// Refused by a risk or size check before reaching the server
+    Rejected(String),
+    /// Cancelled by the user while queued
+    Cancelled,
+}
+
+/// A resolved order with everything known about it
+#[derive(Debug, Clone)]
+pub struct TradeOutcome {
+    pub order_id: u64,
+    pub symbol: String,
+    pub trade_type: TradeType,
+    /// Amount actually sent (after any round-up to the server minimum)
+    pub amount: f64,
+    pub priority: TradePriority,
+    pub reason: TradeReason,
+    pub source: String,
+    /// Simulated by paper trading
+    pub paper: bool,
+    pub resolution: TradeResolution,
+    pub resolved_at: DateTime<Utc>,
+}
+
+impl TradeOutcome {
+    pub fn new(order: &TradeOrder, resolution: TradeResolution) -> Self {
+        Self {
+            order_id: order.id,
+            symbol: order.symbol.clone(),
+            trade_type: order.trade_type,
+            amount: order.amount,
+            priority: order.priority,
+            reason: order.reason.clone(),
+            source: order.source.clone(),
+            paper: order.paper,
+            resolution,
+            resolved_at: Utc::now(),
+        }
+    }
+
+    pub fn is_filled(&self) -> bool {
+        matches!(self.resolution, TradeResolution::Filled(_))
+    }
+
+    /// The frontend event for this outcome; cancelled orders have none
+    pub fn to_event(&self) -> Option<TradeExecutedEvent> {
+        let (response, reason, error) = match &self.resolution {
+            TradeResolution::Filled(response) => (Some(response), self.reason.to_string(), None),
+            TradeResolution::Failed(e) => (None, self.reason.to_string(), Some(e.clone())),
+            TradeResolution::Rejected(why) => (None, format!("REJECTED: {}", why), Some(why.clone())),
+            TradeResolution::Cancelled => return None,
+        };
+        Some(TradeExecutedEvent {
+            symbol: self.symbol.clone(),
+            trade_type: format!("{:?}", self.trade_type),
+            amount: self.amount,
+            new_price: response.and_then(TradeResponse::fill_price),
+            price_impact: response.map_or(0.0, |r| r.price_impact),
+            new_balance: response.map_or(0.0, |r| r.new_balance),
+            reason,
+            reason_detail: self.reason.clone(),
+            source: self.source.clone(),
+            success: response.is_some(),
+            error,
+            paper: self.paper,
+        })
+    }
+}
+
+/// A fill as the local trade journal records it
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    pub trade_type: &'static str,
+    pub coin_amount: f64,
+    pub price: f64,
+    pub usd_value: f64,
+    /// The order's reason (the user's note for manual trades)
+    pub note: String,
+}
+
+impl JournalEntry {
+    /// The journal row for a filled order. Paper fills have none: the
+    /// journal mirrors the account.
+    pub fn for_outcome(outcome: &TradeOutcome) -> Option<Self> {
+        let TradeResolution::Filled(response) = &outcome.resolution else {
+            return None;
+        };
+        if outcome.paper {
+            return None;
+        }
+        let (trade_type, coin_amount, usd_value) = match outcome.trade_type {
+            TradeType::Buy => ("BUY", response.coins_bought.unwrap_or(0.0), response.total_cost.unwrap_or(outcome.amount)),
+            TradeType::Sell => (
+                "SELL",
+                response.coins_sold.unwrap_or(outcome.amount),
+                sell_proceeds(outcome.amount, response),
+            ),
+        };
+        let price = if coin_amount > 0.0 { usd_value / coin_amount } else { response.fill_price().unwrap_or(0.0) };
+        Some(Self { trade_type, coin_amount, price, usd_value, note: outcome.reason.to_string() })
+    }
+}
+
+/// Record a filled order in the active profile's trade journal
+async fn journal_trade(app_handle: &tauri::AppHandle, symbol: &str, entry: &JournalEntry) {
+    let state = app_handle.state::<crate::AppState>();
+    let db_guard = state.db.read().await;
+    let Some(db) = db_guard.as_ref() else { return };
+    let profile_id = match sqlite::get_active_profile(db.pool()).await {
+        Ok(Some(p)) => p.id,
+        _ => return,
+    };
+
+    let tx = sqlite::NewTransaction {
+        symbol,
+        trade_type: entry.trade_type,
+        coin_amount: entry.coin_amount,
+        price: entry.price,
+        usd_value: entry.usd_value,
+        note: Some(&entry.note),
+    };
+    if let Err(e) = sqlite::log_transaction(db.pool(), profile_id, &tx).await {
+        warn!("Failed to journal {} trade for {}: {}", entry.trade_type, symbol, e);
+    }
+}
+
+/// Subscribe the built-in outcome side effects: the `trade-executed` event
+/// for the frontend, a native alert for risk rejections and the journal
+pub fn spawn_outcome_listeners(app_handle: tauri::AppHandle, executor: &TradeExecutorHandle) {
+    spawn_journal_listener(app_handle.clone(), executor);
+
+    let mut outcomes = executor.subscribe_outcomes();
+    tokio::spawn(async move {
+        loop {
+            let outcome = match outcomes.recv().await {
+                Ok(outcome) => outcome,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("Trade outcome listener fell behind, {} outcomes missed", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+
+            if let Some(event) = outcome.to_event() {
+                if let Err(e) = emit_event(&app_handle, AppEvent::TradeExecuted(event)) {
+                    warn!("Failed to emit trade-executed event: {}", e);
+                }
+            }
+            if let TradeResolution::Rejected(why) = &outcome.resolution {
+                if let Some(notif) = app_handle.try_state::<crate::notifications::NotificationHandle>() {
+                    notif.notify_risk_rejected(&outcome.symbol, why).await;
+                }
+            }
+        }
+    });
+}
+
+/// Journal every real fill. Its own subscriber, so a slow database write
+/// never holds up the frontend event.
+fn spawn_journal_listener(app_handle: tauri::AppHandle, executor: &TradeExecutorHandle) {
+    let mut outcomes = executor.subscribe_outcomes();
+    tokio::spawn(async move {
+        loop {
+            let outcome = match outcomes.recv().await {
+                Ok(outcome) => outcome,
+                Err(RecvError::Lagged(missed)) => {
+                    warn!("Trade journal fell behind, {} outcomes missed until the next journal reconcile", missed);
+                    continue;
+                }
+                Err(RecvError::Closed) => return,
+            };
+            if let Some(entry) = JournalEntry::for_outcome(&outcome) {
+                journal_trade(&app_handle, &outcome.symbol, &entry).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_executor::spawn_recording_executor;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_fills_failures_and_cancellations() {
+        let (executor, _recorder) = spawn_recording_executor();
+        let mut first = executor.subscribe_outcomes();
+        let mut second = executor.subscribe_outcomes();
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::High, reason.clone(), "sniper")
+            .await
+            .unwrap();
+        for rx in [&mut first, &mut second] {
+            let outcome = rx.recv().await.unwrap();
+            assert!(outcome.is_filled());
+            assert_eq!((outcome.symbol.as_str(), outcome.source.as_str(), outcome.amount), ("AAA", "sniper", 10.0));
+            assert!(outcome.to_event().unwrap().success);
+        }
+
+        // Cancelled before the executor picked it up
+        executor
+            .submit_trade_fire_and_forget("BBB".to_string(), TradeType::Buy, 5.0, TradePriority::Normal, reason.clone(), "mirror")
+            .await;
+        let queued = executor.list_pending_trades().await;
+        executor.cancel_pending_trade(queued[0].id).await.unwrap();
+        let outcome = first.recv().await.unwrap();
+        assert_eq!(outcome.symbol, "BBB");
+        assert!(matches!(outcome.resolution, TradeResolution::Cancelled));
+        assert!(outcome.to_event().is_none());
+
+        // Failures and rejections reach the frontend as unsuccessful trades
+        let mut failed = outcome.clone();
+        failed.resolution = TradeResolution::Failed("Trade API error: timeout".to_string());
+        let event = failed.to_event().unwrap();
+        assert!(!event.success);
+        assert_eq!(event.error.as_deref(), Some("Trade API error: timeout"));
+        failed.resolution = TradeResolution::Rejected("Daily volume limit".to_string());
+        assert_eq!(failed.to_event().unwrap().reason, "REJECTED: Daily volume limit");
+    }
+
+    #[tokio::test]
+    async fn test_only_real_fills_are_journaled() {
+        let (executor, _recorder) = spawn_recording_executor();
+        let mut outcomes = executor.subscribe_outcomes();
+        let reason = TradeReason::Sniper { symbol: "AAA".to_string(), age_secs: 60, market_cap: 1_000.0 };
+        executor
+            .submit_trade("AAA".to_string(), TradeType::Buy, 10.0, TradePriority::High, reason, "sniper")
+            .await
+            .unwrap();
+        let filled = outcomes.recv().await.unwrap();
+
+        let entry = JournalEntry::for_outcome(&filled).unwrap();
+        assert_eq!(entry.trade_type, "BUY");
+        assert_eq!(entry.note, filled.reason.to_string());
+        assert!(entry.usd_value > 0.0);
+
+        let paper = TradeOutcome { paper: true, ..filled.clone() };
+        assert_eq!(JournalEntry::for_outcome(&paper), None);
+        let failed = TradeOutcome { resolution: TradeResolution::Failed("timeout".to_string()), ..filled };
+        assert_eq!(JournalEntry::for_outcome(&failed), None);
+    }
+}
@@ -0,0 +1,180 @@
+//! Trade Pacing — the executor's adaptive delay between trades
+//!
+//! After each trade the executor waits before starting the next. With
+//! adaptive pacing that wait follows the API, AIMD style: every 429
+//! doubles it, so a burst of sentinel sells backs off before each one is
+//! throttled in turn, and once rate limits are rare over the rolling
+//! window each success takes a fixed step off again. The delay stays
+//! between the `rateLimitMs` floor and the `maxRateLimitMs` ceiling from
+//! the risk limits.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How far back rate limit share and request counts look
+pub const STATS_WINDOW: Duration = Duration::from_secs(300);
+
+/// The delay after the first 429 when the floor is lower
+const MIN_BACKOFF_MS: u64 = 1000;
+/// Taken off the delay per calm success
+const DECREASE_STEP_MS: u64 = 250;
+/// Share of rate-limited requests in the window below which it is calm
+const CALM_RATE: f64 = 0.05;
+
+/// Delay bounds from the risk limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PacingBounds {
+    pub adaptive: bool,
+    pub min_ms: u64,
+    pub max_ms: u64,
+}
+
+impl PacingBounds {
+    fn clamp(&self, delay_ms: u64) -> u64 {
+        delay_ms.clamp(self.min_ms, self.max_ms.max(self.min_ms))
+    }
+}
+
+/// How one request to the trade API ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestOutcome {
+    Success,
+    RateLimited,
+    /// Any other error
+    Failed,
+}
+
+/// Request counts and the delay the executor is currently using
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorStats {
+    /// Since startup
+    pub requests_sent: u64,
+    pub successes: u64,
+    pub rate_limit_hits: u64,
+    pub window_secs: u64,
+    pub window_requests: usize,
+    pub window_rate_limit_hits: usize,
+    pub current_delay_ms: u64,
+    pub min_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub adaptive: bool,
+}
+
+/// AIMD controller for the inter-trade delay
+#[derive(Debug, Default)]
+pub struct TradePacer {
+    window: VecDeque<(Instant, RequestOutcome)>,
+    requests_sent: u64,
+    successes: u64,
+    rate_limit_hits: u64,
+    delay_ms: u64,
+}
+
+impl TradePacer {
+    fn prune(&mut self, now: Instant) {
+        while self.window.front().is_some_and(|(at, _)| now.saturating_duration_since(*at) > STATS_WINDOW) {
+            self.window.pop_front();
+        }
+    }
+
+    fn window_rate_limits(&self) -> usize {
+        self.window.iter().filter(|(_, o)| *o == RequestOutcome::RateLimited).count()
+    }
+
+    pub fn record(&mut self, outcome: RequestOutcome, bounds: &PacingBounds) {
+        self.record_at(outcome, Instant::now(), bounds);
+    }
+
+    /// Count a request and retune the delay
+    pub fn record_at(&mut self, outcome: RequestOutcome, now: Instant, bounds: &PacingBounds) {
+        self.prune(now);
+        self.window.push_back((now, outcome));
+        self.requests_sent += 1;
+        match outcome {
+            RequestOutcome::Success => self.successes += 1,
+            RequestOutcome::RateLimited => self.rate_limit_hits += 1,
+            RequestOutcome::Failed => {}
+        }
+
+        let current = self.delay_ms(bounds);
+        self.delay_ms = match outcome {
+            _ if !bounds.adaptive => bounds.min_ms,
+            RequestOutcome::RateLimited => bounds.clamp(current.saturating_mul(2).max(MIN_BACKOFF_MS)),
+            RequestOutcome::Success
+                if (self.window_rate_limits() as f64) < CALM_RATE * self.window.len() as f64 =>
+            {
+                bounds.clamp(current.saturating_sub(DECREASE_STEP_MS))
+            }
+            _ => current,
+        };
+    }
+
+    /// The delay before the next trade
+    pub fn delay_ms(&self, bounds: &PacingBounds) -> u64 {
+        if bounds.adaptive {
+            bounds.clamp(self.delay_ms)
+        } else {
+            bounds.min_ms
+        }
+    }
+
+    pub fn stats_at(&mut self, now: Instant, bounds: &PacingBounds) -> ExecutorStats {
+        self.prune(now);
+        ExecutorStats {
+            requests_sent: self.requests_sent,
+            successes: self.successes,
+            rate_limit_hits: self.rate_limit_hits,
+            window_secs: STATS_WINDOW.as_secs(),
+            window_requests: self.window.len(),
+            window_rate_limit_hits: self.window_rate_limits(),
+            current_delay_ms: self.delay_ms(bounds),
+            min_delay_ms: bounds.min_ms,
+            max_delay_ms: bounds.max_ms.max(bounds.min_ms),
+            adaptive: bounds.adaptive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limits_widen_the_delay_and_calm_narrows_it() {
+        let bounds = PacingBounds { adaptive: true, min_ms: 500, max_ms: 8000 };
+        let mut pacer = TradePacer::default();
+        let start = Instant::now();
+        assert_eq!(pacer.delay_ms(&bounds), 500);
+
+        let mut delays = Vec::new();
+        for i in 0..5 {
+            pacer.record_at(RequestOutcome::RateLimited, start + Duration::from_secs(i), &bounds);
+            delays.push(pacer.delay_ms(&bounds));
+        }
+        assert_eq!(delays, vec![1000, 2000, 4000, 8000, 8000]);
+
+        // Successes don't narrow while 429s are still a big share of the window
+        pacer.record_at(RequestOutcome::Success, start + Duration::from_secs(10), &bounds);
+        assert_eq!(pacer.delay_ms(&bounds), 8000);
+
+        // Once those 429s age out, each success takes a step off, down to the floor
+        let calm = start + STATS_WINDOW + Duration::from_secs(10);
+        pacer.record_at(RequestOutcome::Success, calm, &bounds);
+        assert_eq!(pacer.delay_ms(&bounds), 7750);
+        for i in 0..40 {
+            pacer.record_at(RequestOutcome::Success, calm + Duration::from_secs(i), &bounds);
+        }
+        assert_eq!(pacer.delay_ms(&bounds), 500);
+
+        let stats = pacer.stats_at(calm + Duration::from_secs(40), &bounds);
+        assert_eq!((stats.requests_sent, stats.successes, stats.rate_limit_hits), (47, 42, 5));
+        assert_eq!((stats.window_requests, stats.window_rate_limit_hits), (41, 0));
+
+        // Fixed pacing ignores the controller
+        let fixed = PacingBounds { adaptive: false, ..bounds };
+        pacer.record_at(RequestOutcome::RateLimited, calm + Duration::from_secs(41), &fixed);
+        assert_eq!(pacer.delay_ms(&fixed), 500);
+    }
+}
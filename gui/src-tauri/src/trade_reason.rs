@@ -0,0 +1,173 @@
+//! Trade Reason — why an order was submitted
+//!
+//! Every order carries a [`TradeReason`] instead of a preformatted string.
+//! `Display` renders the human-readable line shown in the queue, the trade
+//! feed and the journal; serde gives the same reason as tagged JSON for the
+//! automation log, so nothing has to be parsed back out of display text.
+
+use rugplay_core::TradeType;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum TradeReason {
+    /// Sniper buy of a freshly listed coin
+    Sniper { symbol: String, age_secs: i64, market_cap: f64 },
+    /// Copy of a tracked whale's trade
+    Mirror { whale: String, whale_side: String, value_usd: f64, symbol: String },
+    /// Dip buyer buy after a large sell
+    Dip {
+        seller: String,
+        sell_value_usd: f64,
+        symbol: String,
+        confidence: f64,
+        slippage_pct: f64,
+        buy_usd: f64,
+    },
+    /// Sentinel exit; `detail` is the trigger's own description
+    Sentinel { sentinel_id: i64, trigger_type: String, detail: String },
+    /// Placed by the user from the desktop or the mobile app
+    Manual { side: TradeType, split: bool, mobile: bool, note: Option<String> },
+    /// Part of a portfolio rebalance toward a target allocation
+    Rebalance { symbol: String, current_pct: f64, target_pct: f64 },
+    /// One tranche of a split sell
+    Tranche { part: usize, parts: usize, reason: Box<TradeReason> },
+}
+
+impl TradeReason {
+    /// This reason for tranche `part` of `parts` (unchanged for a single tranche)
+    pub fn tranche(self, part: usize, parts: usize) -> Self {
+        if parts > 1 {
+            Self::Tranche { part, parts, reason: Box::new(self) }
+        } else {
+            self
+        }
+    }
+
+    /// Whether this is a sentinel stop-loss exit (or a tranche of one)
+    pub fn is_stop_loss(&self) -> bool {
+        match self {
+            Self::Sentinel { trigger_type, .. } => trigger_type == "stop_loss",
+            Self::Tranche { reason, .. } => reason.is_stop_loss(),
+            _ => false,
+        }
+    }
+
+    /// The reason as a JSON value for log details
+    pub fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// Read a reason back from log details
+    pub fn from_value(value: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+impl fmt::Display for TradeReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sniper { symbol, age_secs, market_cap } => {
+                write!(f, "Sniper: new coin {} (age: {}s, mcap: ${:.0})", symbol, age_secs, market_cap)
+            }
+            Self::Mirror { whale, whale_side, value_usd, symbol } => {
+                write!(f, "Mirror: copying {} {} ${:.2} of {}", whale, whale_side, value_usd, symbol)
+            }
+            Self::Dip { seller, sell_value_usd, symbol, confidence, slippage_pct, buy_usd } => write!(
+                f,
+                "DipBuyer: {} sold ${:.0} of {} (conf={:.2}, slip={:.1}%), buy ${:.0}",
+                seller, sell_value_usd, symbol, confidence, slippage_pct, buy_usd
+            ),
+            Self::Sentinel { sentinel_id, detail, .. } => write!(f, "Sentinel #{}: {}", sentinel_id, detail),
+            Self::Manual { note: Some(note), .. } => f.write_str(note),
+            Self::Manual { side, split, mobile, note: None } => {
+                let surface = if *mobile { "Mobile" } else { "Manual" };
+                let action = match (side, split) {
+                    (TradeType::Buy, _) => "buy",
+                    (TradeType::Sell, true) => "split sell",
+                    (TradeType::Sell, false) => "sell",
+                };
+                write!(f, "{} {}", surface, action)
+            }
+            Self::Rebalance { symbol, current_pct, target_pct } => {
+                write!(f, "Rebalance: {} {:.1}% -> {:.1}%", symbol, current_pct, target_pct)
+            }
+            Self::Tranche { part, parts, reason } => write!(f, "{} (tranche {}/{})", reason, part, parts),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manual(side: TradeType, split: bool, mobile: bool, note: Option<&str>) -> TradeReason {
+        TradeReason::Manual { side, split, mobile, note: note.map(str::to_string) }
+    }
+
+    #[test]
+    fn test_each_variant_renders_consistently() {
+        let cases = [
+            (
+                TradeReason::Sniper { symbol: "NEW".into(), age_secs: 42, market_cap: 1234.5 },
+                "Sniper: new coin NEW (age: 42s, mcap: $1234)",
+            ),
+            (
+                TradeReason::Mirror { whale: "whale".into(), whale_side: "BUY".into(), value_usd: 250.0, symbol: "MOON".into() },
+                "Mirror: copying whale BUY $250.00 of MOON",
+            ),
+            (
+                TradeReason::Dip {
+                    seller: "dumper".into(),
+                    sell_value_usd: 5000.0,
+                    symbol: "DIP".into(),
+                    confidence: 0.8123,
+                    slippage_pct: 1.25,
+                    buy_usd: 300.0,
+                },
+                "DipBuyer: dumper sold $5000 of DIP (conf=0.81, slip=1.2%), buy $300",
+            ),
+            (
+                TradeReason::Sentinel { sentinel_id: 7, trigger_type: "stop_loss".into(), detail: "Stop loss hit at -12.0%".into() },
+                "Sentinel #7: Stop loss hit at -12.0%",
+            ),
+            (manual(TradeType::Buy, false, false, None), "Manual buy"),
+            (manual(TradeType::Sell, true, true, None), "Mobile split sell"),
+            (manual(TradeType::Sell, false, false, Some("taking profit")), "taking profit"),
+            (
+                TradeReason::Rebalance { symbol: "AAA".into(), current_pct: 52.345, target_pct: 30.0 },
+                "Rebalance: AAA 52.3% -> 30.0%",
+            ),
+        ];
+        for (reason, expected) in cases {
+            assert_eq!(reason.to_string(), expected);
+        }
+
+        let tranche = manual(TradeType::Sell, true, false, None).tranche(2, 3);
+        assert_eq!(tranche.to_string(), "Manual split sell (tranche 2/3)");
+        assert_eq!(manual(TradeType::Sell, true, false, None).tranche(1, 1).to_string(), "Manual split sell");
+    }
+
+    #[test]
+    fn test_reason_round_trips_through_log_details() {
+        let reason = TradeReason::Dip {
+            seller: "dumper".into(),
+            sell_value_usd: 5000.0,
+            symbol: "DIP".into(),
+            confidence: 0.8,
+            slippage_pct: 1.5,
+            buy_usd: 300.0,
+        };
+        let details = serde_json::json!({ "reason": reason.to_value() }).to_string();
+        assert!(details.contains(r#""kind":"dip""#), "{}", details);
+        assert!(details.contains(r#""sellValueUsd":5000.0"#), "{}", details);
+
+        let parsed: serde_json::Value = serde_json::from_str(&details).unwrap();
+        assert_eq!(TradeReason::from_value(&parsed["reason"]), Some(reason));
+
+        let split = TradeReason::Sentinel { sentinel_id: 3, trigger_type: "take_profit".into(), detail: "TP".into() }.tranche(1, 2);
+        assert_eq!(TradeReason::from_value(&split.to_value()), Some(split));
+        assert_eq!(TradeReason::from_value(&serde_json::json!({ "kind": "unknown" })), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Trade Reconciliation — did an ambiguous trade land?
+//!
+//! A trade that fails with a network error, a gateway error or an
+//! unreadable response may still have executed server-side. Retrying it
+//! blindly risks a double buy or double sell, so before the executor
+//! retries it looks for the trade in the account's recent transactions and
+//! only retries once it is clear the trade did not happen. Nothing is read
+//! before the order is sent (that would delay every trade, stop-loss sells
+//! included); only transactions recorded after the send, give or take a
+//! few seconds of clock skew, can be taken for it.
+
+use chrono::{DateTime, Utc};
+use rugplay_core::{parse_api_timestamp, ApiTransaction, TradeResponse, TradeType};
+
+/// Allowed difference between our clock and the server's when matching a
+/// transaction to the order that may have created it. Kept short: an
+/// identical trade recorded inside it would be taken for ours.
+pub const CLOCK_SKEW_SECS: i64 = 5;
+/// Relative difference tolerated between the order amount and the recorded one
+const AMOUNT_TOLERANCE: f64 = 0.01;
+
+/// What the reconciliation poll found out about a failed trade
+#[derive(Debug, Clone)]
+pub enum Reconciliation {
+    /// The trade executed; the response is rebuilt from the transaction
+    Landed(TradeResponse),
+    /// The transactions were readable and the trade isn't among them
+    NotLanded,
+    /// The transactions couldn't be checked, so retrying isn't safe
+    Unknown(String),
+}
+
+/// Whether a failed trade may nonetheless have executed.
+///
+/// Rejections the server answered (4xx, auth failures, "not successful")
+/// are definite. Transport failures, 5xx gateway errors and 2xx responses
+/// that couldn't be parsed are not.
+pub fn is_ambiguous(error: &str) -> bool {
+    if error.contains("Network error:") || error.contains("Invalid data:") {
+        return true;
+    }
+    error
+        .split("HTTP ")
+        .skip(1)
+        .any(|rest| rest.starts_with('5'))
+}
+
+/// Find the transaction an order produced, if it landed.
+///
+/// Buys are matched on the USD spent, sells on the coins sold; both must
+/// be for the same coin and recorded no earlier than `since` (the send,
+/// less the allowed clock skew).
+pub fn find_landed<'a>(
+    transactions: &'a [ApiTransaction],
+    symbol: &str,
+    trade_type: TradeType,
+    amount: f64,
+    since: DateTime<Utc>,
+) -> Option<&'a ApiTransaction> {
+    let earliest = since - chrono::Duration::seconds(CLOCK_SKEW_SECS);
+    let expected_type = match trade_type {
+        TradeType::Buy => "BUY",
+        TradeType::Sell => "SELL",
+    };
+
+    transactions.iter().find(|tx| {
+        if tx.is_transfer || !tx.trade_type.eq_ignore_ascii_case(expected_type) {
+            return false;
+        }
+        let same_coin = tx.coin.as_ref().is_some_and(|c| c.symbol.eq_ignore_ascii_case(symbol));
+        let recent = parse_api_timestamp(&tx.timestamp).is_some_and(|at| at >= earliest);
+        let recorded = match trade_type {
+            TradeType::Buy => tx.total_base_currency_amount,
+            TradeType::Sell => tx.quantity,
+        };
+        same_coin && recent && (recorded - amount).abs() <= amount.abs() * AMOUNT_TOLERANCE
+    })
+}
+
+/// Rebuild the trade response from the transaction that proves it landed.
+/// Price impact isn't recorded on transactions and is reported as zero.
+pub fn landed_response(tx: &ApiTransaction, trade_type: TradeType, new_balance: f64) -> TradeResponse {
+    let (coins_bought, coins_sold, total_cost, total_received) = match trade_type {
+        TradeType::Buy => (Some(tx.quantity), None, Some(tx.total_base_currency_amount), None),
+        TradeType::Sell => (None, Some(tx.quantity), None, Some(tx.total_base_currency_amount)),
+    };
+    TradeResponse {
+        success: true,
+        trade_type: tx.trade_type.clone(),
+        coins_bought,
+        coins_sold,
+        total_cost,
+        total_received,
+        new_price: Some(tx.price_per_coin),
+        price_impact: 0.0,
+        new_balance,
+        paper: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(id: i64, kind: &str, symbol: &str, quantity: f64, total: f64, timestamp: &str) -> ApiTransaction {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "type": kind,
+            "quantity": quantity,
+            "pricePerCoin": total / quantity,
+            "totalBaseCurrencyAmount": total,
+            "timestamp": timestamp,
+            "coin": { "id": 1, "name": symbol, "symbol": symbol },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_ambiguous_timeout_but_trade_succeeded() {
+        let timeout = "Trade API error: Network error: error sending request: operation timed out";
+        assert!(is_ambiguous(timeout));
+
+        let since = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        let history = vec![
+            tx(4, "BUY", "OTHER", 10.0, 50.0, "2026-03-01T12:00:02Z"),
+            tx(3, "BUY", "MOON", 100.0, 49.0, "2026-03-01T12:00:02Z"),
+            tx(2, "BUY", "MOON", 200.0, 50.0, "2026-03-01T12:00:01Z"),
+            tx(1, "BUY", "MOON", 100.0, 50.0, "2026-03-01T11:00:00Z"),
+        ];
+
+        let landed = find_landed(&history, "MOON", TradeType::Buy, 50.0, since).unwrap();
+        assert_eq!(landed.id, 2);
+        let response = landed_response(landed, TradeType::Buy, 950.0);
+        assert!(response.success);
+        assert_eq!(response.coins_bought, Some(200.0));
+        assert_eq!(response.new_price, Some(0.25));
+        assert_eq!(response.new_balance, 950.0);
+
+        // An older identical buy or a sell of the same size doesn't count
+        assert!(find_landed(&history[3..], "MOON", TradeType::Buy, 50.0, since).is_none());
+        assert!(find_landed(&history, "MOON", TradeType::Sell, 200.0, since).is_none());
+    }
+
+    #[test]
+    fn test_identical_trade_from_before_the_send_is_not_taken_for_it() {
+        let since = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        // A $50 buy ten seconds before ours
+        let earlier = tx(1, "BUY", "MOON", 200.0, 50.0, "2026-03-01T11:59:50Z");
+        assert!(find_landed(std::slice::from_ref(&earlier), "MOON", TradeType::Buy, 50.0, since).is_none());
+
+        // Ours, stamped by a server clock a couple of seconds behind
+        let ours = tx(2, "BUY", "MOON", 190.0, 50.0, "2026-03-01T11:59:58Z");
+        let after = [ours, earlier];
+        assert_eq!(find_landed(&after, "MOON", TradeType::Buy, 50.0, since).unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_definite_rejections_are_not_ambiguous() {
+        assert!(!is_ambiguous("Trade API error: Trade failed: HTTP 400 Bad Request: Insufficient balance"));
+        assert!(!is_ambiguous("Trade API error: Session token expired"));
+        assert!(!is_ambiguous("Trade was not successful"));
+        assert!(is_ambiguous("Trade API error: Trade failed: HTTP 504 Gateway Timeout"));
+        assert!(is_ambiguous("Trade API error: Invalid data: EOF while parsing a value"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Warm-up — no automated trade until startup state is loaded
+//!
+//! On a fresh start every loop used to act on its very first tick, with an
+//! empty coin cache, default risk limits and dedup/cooldown state rebuilt
+//! from logs. Startup now begins in a warm-up phase: the initial sync
+//! (`initial_sync`) loads persisted settings, the active profile, the
+//! portfolio and the market, and until every [`WarmupStep`] has completed
+//! the loops skip their ticks and the trade executor refuses automated
+//! orders. User-placed trades are not held back, and protective sells only
+//! wait for the settings and the profile: a stop-loss shouldn't sit idle
+//! while the market listing loads.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// What has to be loaded before automation may trade
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WarmupStep {
+    /// Risk limits and policies read from the database
+    Settings,
+    /// An active profile whose token the API accepts
+    Profile,
+    /// Holdings fetched and their coins cached
+    Portfolio,
+    /// Current market listing fetched
+    Market,
+}
+
+/// Every step, in the order the initial sync runs them
+pub const WARMUP_STEPS: &[WarmupStep] = &[WarmupStep::Settings, WarmupStep::Profile, WarmupStep::Portfolio, WarmupStep::Market];
+
+/// Emitted after each initial sync pass and returned by `get_warmup_status`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarmupStatus {
+    pub ready: bool,
+    pub completed: Vec<WarmupStep>,
+    pub pending: Vec<WarmupStep>,
+    /// Initial sync passes run so far
+    pub attempts: u32,
+    /// Why the last pass didn't finish, if it didn't
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct Progress {
+    completed: Vec<WarmupStep>,
+    attempts: u32,
+    last_error: Option<String>,
+}
+
+/// Shared warm-up state. Starts warming up; turns ready once, for good.
+#[derive(Debug, Default)]
+pub struct WarmupGate {
+    ready: AtomicBool,
+    progress: Mutex<Progress>,
+}
+
+impl WarmupGate {
+    /// A gate that has already finished warming up
+    pub fn completed() -> Self {
+        let gate = Self::default();
+        for &step in WARMUP_STEPS {
+            gate.complete(step);
+        }
+        gate
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Whether protective sells may run: they need the sentinel settings and
+    /// a working profile, not the rest of the warm-up
+    pub fn allows_protective(&self) -> bool {
+        let progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        self.is_ready() || [WarmupStep::Settings, WarmupStep::Profile].iter().all(|s| progress.completed.contains(s))
+    }
+
+    /// Start another sync pass, clearing the last pass's error
+    pub fn begin_attempt(&self) {
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        progress.attempts += 1;
+        progress.last_error = None;
+    }
+
+    /// Mark `step` done. Returns true when that finished the warm-up.
+    pub fn complete(&self, step: WarmupStep) -> bool {
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        if !progress.completed.contains(&step) {
+            progress.completed.push(step);
+        }
+        let done = WARMUP_STEPS.iter().all(|s| progress.completed.contains(s));
+        done && !self.ready.swap(true, Ordering::AcqRel)
+    }
+
+    /// Record why `step` couldn't complete this pass
+    pub fn fail(&self, step: WarmupStep, error: &str) {
+        let mut progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        progress.last_error = Some(format!("{:?}: {}", step, error));
+    }
+
+    pub fn status(&self) -> WarmupStatus {
+        let progress = self.progress.lock().unwrap_or_else(|e| e.into_inner());
+        WarmupStatus {
+            ready: self.is_ready(),
+            completed: WARMUP_STEPS.iter().copied().filter(|s| progress.completed.contains(s)).collect(),
+            pending: WARMUP_STEPS.iter().copied().filter(|s| !progress.completed.contains(s)).collect(),
+            attempts: progress.attempts,
+            last_error: progress.last_error.clone(),
+        }
+    }
+
+    /// Refuse automated orders while warming up; protective ones only until
+    /// [`WarmupGate::allows_protective`]
+    pub fn check_order(&self, automated: bool, protective: bool) -> Result<(), String> {
+        let allowed = if protective { self.allows_protective() } else { self.is_ready() };
+        if automated && !allowed {
+            return Err("Warming up: automated trades start once startup state is loaded".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gate_opens_only_after_every_step() {
+        let gate = WarmupGate::default();
+        assert!(gate.check_order(true, false).is_err());
+        assert!(gate.check_order(true, true).is_err());
+        assert!(gate.check_order(false, false).is_ok());
+
+        gate.begin_attempt();
+        assert!(!gate.complete(WarmupStep::Settings));
+        assert!(gate.check_order(true, true).is_err());
+        assert!(!gate.complete(WarmupStep::Profile));
+        // Protective sells only wait for the settings and the profile
+        assert!(gate.allows_protective());
+        assert!(gate.check_order(true, true).is_ok());
+        gate.fail(WarmupStep::Portfolio, "timed out");
+        let status = gate.status();
+        assert!(!status.ready);
+        assert_eq!(status.pending, vec![WarmupStep::Portfolio, WarmupStep::Market]);
+        assert_eq!(status.last_error.as_deref(), Some("Portfolio: timed out"));
+        assert!(gate.check_order(true, false).is_err());
+
+        // Out of order and repeated completions are fine; ready is reported once
+        gate.begin_attempt();
+        assert!(!gate.complete(WarmupStep::Market));
+        assert!(gate.complete(WarmupStep::Portfolio));
+        assert!(!gate.complete(WarmupStep::Portfolio));
+        let status = gate.status();
+        assert!(status.ready && status.pending.is_empty() && status.last_error.is_none());
+        assert_eq!(status.attempts, 2);
+        assert!(gate.check_order(true, false).is_ok());
+        assert!(WarmupGate::completed().is_ready());
+    }
+}
@@ -0,0 +1,144 @@
+//! Wash Trade Filter — feed trades that are likely a user trading with themselves
+//!
+//! Buying and selling the same coin back and forth inflates volume without
+//! anyone else taking the other side. Copying those trades (mirror) or
+//! treating the sell half as a dump (dip buyer) only feeds the fake volume,
+//! so both loops drop flagged trades before acting on the feed.
+
+use rugplay_core::RecentTrade;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+/// How eagerly buy/sell pairs are treated as wash trades
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WashSensitivity {
+    #[default]
+    Off,
+    Low,
+    Medium,
+    High,
+}
+
+/// Thresholds behind a sensitivity level
+#[derive(Debug, Clone, Copy)]
+struct WashThresholds {
+    /// A buy and sell this close together are a wash if their values match
+    round_trip_secs: i64,
+    /// How closely round-trip values must match (fraction of the larger)
+    value_tolerance: f64,
+}
+
+impl WashSensitivity {
+    fn thresholds(self) -> Option<WashThresholds> {
+        match self {
+            Self::Off => None,
+            Self::Low => Some(WashThresholds { round_trip_secs: 60, value_tolerance: 0.02 }),
+            Self::Medium => Some(WashThresholds { round_trip_secs: 300, value_tolerance: 0.05 }),
+            Self::High => Some(WashThresholds { round_trip_secs: 900, value_tolerance: 0.10 }),
+        }
+    }
+}
+
+/// A quick exit at a different size is a trader changing their mind, not a
+/// wash: both the timing and the value have to match.
+fn is_round_trip(buy: &RecentTrade, sell: &RecentTrade, t: &WashThresholds) -> bool {
+    let gap = (buy.timestamp - sell.timestamp).abs();
+    let larger = buy.total_value.max(sell.total_value);
+    gap <= t.round_trip_secs
+        && larger > 0.0
+        && (buy.total_value - sell.total_value).abs() <= larger * t.value_tolerance
+}
+
+/// Indices of `trades` that look like wash trades: a user buying and then
+/// selling a matching amount of the same coin in quick succession. Both
+/// sides of each pair are flagged.
+pub fn wash_trade_indices(trades: &[RecentTrade], sensitivity: WashSensitivity) -> HashSet<usize> {
+    let mut flagged = HashSet::new();
+    let Some(thresholds) = sensitivity.thresholds() else {
+        return flagged;
+    };
+
+    let mut by_user_coin: HashMap<(&str, &str), Vec<usize>> = HashMap::new();
+    for (i, trade) in trades.iter().enumerate() {
+        by_user_coin
+            .entry((trade.user_id.as_str(), trade.coin_symbol.as_str()))
+            .or_default()
+            .push(i);
+    }
+
+    for indices in by_user_coin.values().filter(|v| v.len() > 1) {
+        let (buys, sells): (Vec<usize>, Vec<usize>) = indices.iter().partition(|&&i| trades[i].is_buy());
+        for &b in &buys {
+            for &s in &sells {
+                if is_round_trip(&trades[b], &trades[s], &thresholds) {
+                    flagged.insert(b);
+                    flagged.insert(s);
+                }
+            }
+        }
+    }
+
+    flagged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn trade(user: &str, symbol: &str, side: &str, value: f64, age_secs: i64) -> RecentTrade {
+        RecentTrade {
+            trade_type: side.to_string(),
+            username: user.to_string(),
+            user_image: None,
+            amount: value,
+            coin_symbol: symbol.to_string(),
+            coin_name: symbol.to_string(),
+            coin_icon: None,
+            total_value: value,
+            price: 1.0,
+            timestamp: NOW - age_secs,
+            user_id: format!("id_{}", user),
+        }
+    }
+
+    #[test]
+    fn test_fabricated_wash_pattern_is_filtered() {
+        let feed = vec![
+            // Washer cycles $500 in and out of PUMP every few seconds
+            trade("washer", "PUMP", "BUY", 500.0, 2),
+            trade("washer", "PUMP", "SELL", 499.0, 5),
+            trade("washer", "PUMP", "BUY", 500.0, 8),
+            trade("washer", "PUMP", "SELL", 501.0, 11),
+            // Tiny matching round trip four minutes apart
+            trade("dust", "DUST", "BUY", 1.00, 10),
+            trade("dust", "DUST", "SELL", 0.98, 250),
+            // Genuine activity: a lone dump, a buy-and-hold, and a trader
+            // exiting a different coin
+            trade("whale", "REAL", "SELL", 2_000.0, 3),
+            trade("holder", "PUMP", "BUY", 300.0, 4),
+            trade("trader", "AAA", "BUY", 100.0, 600),
+            trade("trader", "BBB", "SELL", 100.0, 5),
+        ];
+
+        let flagged = wash_trade_indices(&feed, WashSensitivity::Medium);
+        let mut flagged: Vec<usize> = flagged.into_iter().collect();
+        flagged.sort_unstable();
+        assert_eq!(flagged, [0, 1, 2, 3, 4, 5]);
+
+        // Low only catches the fast flips; Off catches nothing
+        let mut low: Vec<usize> = wash_trade_indices(&feed, WashSensitivity::Low).into_iter().collect();
+        low.sort_unstable();
+        assert_eq!(low, [0, 1, 2, 3]);
+        assert!(wash_trade_indices(&feed, WashSensitivity::Off).is_empty());
+
+        // A real exit at a different size isn't a wash, however quick
+        let exit = vec![trade("swing", "REAL", "BUY", 200.0, 200), trade("swing", "REAL", "SELL", 260.0, 5)];
+        assert!(wash_trade_indices(&exit, WashSensitivity::High).is_empty());
+        let quick = vec![trade("fast", "REAL", "BUY", 1_000.0, 8), trade("fast", "REAL", "SELL", 1_400.0, 3)];
+        assert!(wash_trade_indices(&quick, WashSensitivity::High).is_empty());
+        assert_eq!(WashSensitivity::default(), WashSensitivity::Off);
+    }
+}
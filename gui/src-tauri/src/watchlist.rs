@@ -0,0 +1,214 @@
+//! Watchlist — price view and move alerts for coins the user doesn't hold
+//!
+//! Unlike sentinels, watched coins have no position behind them: the list
+//! is only a set of symbols with an optional alert threshold. Prices are
+//! looked up through the shared coin cache when the list is requested, and
+//! a coin whose price moved at least `alert_pct` percent from its reference
+//! price raises an alert and becomes the new reference.
+//!
+//! In watchlist-only mode the list is also the tradable universe: the
+//! executor refuses automated buys of any coin not on it (settings key
+//! `watchlist_only`). Sells still go through, so positions outside the
+//! list can be closed.
+
+use rugplay_core::{CoinDetails, TradeType};
+use rugplay_persistence::sqlite::WatchlistRow;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::future::Future;
+
+/// A watched coin with its current market data
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchedCoin {
+    pub symbol: String,
+    pub added_at: Option<String>,
+    pub alert_pct: Option<f64>,
+    pub reference_price: Option<f64>,
+    pub name: Option<String>,
+    pub icon: Option<String>,
+    pub current_price: Option<f64>,
+    pub change_24h: Option<f64>,
+    pub market_cap: Option<f64>,
+    pub volume_24h: Option<f64>,
+    /// Percent move from the reference price
+    pub move_pct: Option<f64>,
+    /// When the price was fetched (RFC 3339)
+    pub fetched_at: Option<String>,
+    /// Why no price is shown, if the lookup failed
+    pub error: Option<String>,
+}
+
+/// A watched coin that moved past its alert threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchAlert {
+    pub symbol: String,
+    pub move_pct: f64,
+    pub price: f64,
+}
+
+fn move_pct(reference: Option<f64>, price: f64) -> Option<f64> {
+    reference.filter(|r| *r > 0.0).map(|r| (price - r) / r * 100.0)
+}
+
+/// Combine a watchlist row with the result of looking its coin up
+pub fn enrich(row: &WatchlistRow, coin: Result<&CoinDetails, String>) -> WatchedCoin {
+    let mut watched = WatchedCoin {
+        symbol: row.symbol.clone(),
+        added_at: row.added_at.clone(),
+        alert_pct: row.alert_pct,
+        reference_price: row.reference_price,
+        name: None,
+        icon: None,
+        current_price: None,
+        change_24h: None,
+        market_cap: None,
+        volume_24h: None,
+        move_pct: None,
+        fetched_at: None,
+        error: None,
+    };
+
+    match coin {
+        Ok(coin) if coin.has_valid_price() => {
+            watched.name = Some(coin.name.clone());
+            watched.icon = coin.icon.clone();
+            watched.current_price = Some(coin.current_price);
+            watched.change_24h = Some(coin.change_24h);
+            watched.market_cap = coin.market_cap;
+            watched.volume_24h = Some(coin.volume_24h);
+            watched.move_pct = move_pct(row.reference_price, coin.current_price);
+            watched.fetched_at = coin.fetched_at.map(|at| at.to_rfc3339());
+        }
+        Ok(coin) => {
+            watched.name = Some(coin.name.clone());
+            watched.error = Some("No valid price".to_string());
+        }
+        Err(e) => watched.error = Some(e),
+    }
+    watched
+}
+
+/// Whether a priced coin moved far enough from its reference to alert
+pub fn check_alert(watched: &WatchedCoin) -> Option<WatchAlert> {
+    let threshold = watched.alert_pct.filter(|t| *t > 0.0)?;
+    let moved = watched.move_pct?;
+    (moved.abs() >= threshold).then(|| WatchAlert {
+        symbol: watched.symbol.clone(),
+        move_pct: moved,
+        price: watched.current_price.unwrap_or_default(),
+    })
+}
+
+/// Look up every watched coin with `fetch`, in list order. A failed lookup
+/// is reported on that coin and does not stop the rest.
+pub async fn enrich_watchlist<F, Fut>(rows: &[WatchlistRow], fetch: F) -> Vec<WatchedCoin>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<CoinDetails, String>>,
+{
+    let mut watched = Vec::with_capacity(rows.len());
+    for row in rows {
+        let coin = fetch(row.symbol.clone()).await;
+        watched.push(enrich(row, coin.as_ref().map_err(|e| e.clone())));
+    }
+    watched
+}
+
+/// The coins automation may buy in watchlist-only mode
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TradableUniverse {
+    pub enabled: bool,
+    /// The active profile's watched symbols, uppercase
+    symbols: HashSet<String>,
+}
+
+impl TradableUniverse {
+    /// Replace the watched symbols, e.g. after the list or the active profile changed
+    pub fn set_symbols(&mut self, symbols: impl IntoIterator<Item = String>) {
+        self.symbols = symbols.into_iter().map(|s| s.to_uppercase()).collect();
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// Refuse an automated buy of an unwatched coin while the mode is on
+    pub fn check_order(&self, symbol: &str, trade_type: TradeType, automated: bool) -> Result<(), String> {
+        if self.enabled
+            && automated
+            && matches!(trade_type, TradeType::Buy)
+            && !self.symbols.contains(&symbol.to_uppercase())
+        {
+            return Err(format!("Watchlist-only mode: {} is not on the watchlist", symbol));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(symbol: &str, alert_pct: Option<f64>, reference_price: Option<f64>) -> WatchlistRow {
+        WatchlistRow {
+            profile_id: 1,
+            symbol: symbol.to_string(),
+            added_at: Some("2024-05-01 12:00:00".to_string()),
+            alert_pct,
+            reference_price,
+        }
+    }
+
+    fn coin(symbol: &str, price: f64) -> CoinDetails {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "symbol": symbol, "name": format!("{} Coin", symbol), "currentPrice": price,
+            "marketCap": 1000.0, "poolCoinAmount": 10.0, "poolBaseCurrencyAmount": 10.0,
+            "change24h": -4.0
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_enrichment_keeps_order_and_reports_failures() {
+        let rows = vec![row("AAA", None, Some(1.0)), row("GONE", None, None), row("ZERO", None, None)];
+        let watched = enrich_watchlist(&rows, |symbol| async move {
+            match symbol.as_str() {
+                "AAA" => Ok(coin("AAA", 1.25)),
+                "ZERO" => Ok(coin("ZERO", 0.0)),
+                _ => Err("Coin not found".to_string()),
+            }
+        })
+        .await;
+
+        assert_eq!(watched.iter().map(|w| w.symbol.as_str()).collect::<Vec<_>>(), ["AAA", "GONE", "ZERO"]);
+        assert_eq!(watched[0].current_price, Some(1.25));
+        assert_eq!(watched[0].name.as_deref(), Some("AAA Coin"));
+        assert_eq!(watched[0].change_24h, Some(-4.0));
+        assert_eq!(watched[0].move_pct, Some(25.0));
+        assert_eq!(watched[1].current_price, None);
+        assert_eq!(watched[1].error.as_deref(), Some("Coin not found"));
+        assert_eq!(watched[2].current_price, None);
+        assert!(watched[2].error.is_some());
+    }
+
+    #[test]
+    fn test_alert_fires_on_moves_past_threshold_either_way() {
+        let up = enrich(&row("AAA", Some(20.0), Some(1.0)), Ok(&coin("AAA", 1.25)));
+        assert_eq!(check_alert(&up).map(|a| a.move_pct), Some(25.0));
+
+        let down = enrich(&row("AAA", Some(20.0), Some(1.0)), Ok(&coin("AAA", 0.7)));
+        assert_eq!(check_alert(&down).map(|a| a.price), Some(0.7));
+
+        let small = enrich(&row("AAA", Some(20.0), Some(1.0)), Ok(&coin("AAA", 1.1)));
+        assert_eq!(check_alert(&small), None);
+
+        // No threshold, or no reference price yet
+        assert_eq!(check_alert(&enrich(&row("AAA", None, Some(1.0)), Ok(&coin("AAA", 5.0)))), None);
+        assert_eq!(check_alert(&enrich(&row("AAA", Some(20.0), None), Ok(&coin("AAA", 5.0)))), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! Whale Priority — which tracked whales the mirror actually watches
+//!
+//! Every recent trade is checked against the watched set each tick, so a
+//! long whale list on a busy feed gets expensive. With a cap set (there is
+//! none by default) the mirror only watches the best `max_tracked_whales`
+//! by performance score; the rest stay in the database and come back as
+//! soon as they rank high enough again. A whale without a score is not a
+//! zero: unscored whales queue behind the scored ones in the order they
+//! were tracked.
+
+use crate::whale_score::WhaleScore;
+use rugplay_persistence::sqlite::TrackedWhale;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// Default cap on watched whales: none, so every tracked whale is watched
+/// until the user opts into a limit
+pub const DEFAULT_MAX_TRACKED_WHALES: u32 = 0;
+
+/// What the mirror needs to know about a watched whale
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WatchedWhale {
+    /// Copy latency override; `None` uses the global `max_latency_secs`
+    pub max_latency_secs: Option<f64>,
+    /// Performance score; `None` until scored or with too little history
+    pub score: Option<f64>,
+}
+
+impl From<&TrackedWhale> for WatchedWhale {
+    fn from(whale: &TrackedWhale) -> Self {
+        let score = whale
+            .score_detail
+            .as_deref()
+            .and_then(|detail| serde_json::from_str::<WhaleScore>(detail).ok())
+            .and_then(|score| score.score());
+        Self { max_latency_secs: whale.max_latency_secs, score }
+    }
+}
+
+/// Outcome of applying the cap to the tracked whales
+#[derive(Debug, Default)]
+pub struct WhaleSelection {
+    /// User ids the mirror watches
+    pub active: HashSet<String>,
+    /// Whales left out because of the cap, best first
+    pub dropped: Vec<TrackedWhale>,
+}
+
+/// Best performers first, then unscored whales; ties go to the whale tracked the longest
+fn priority(a: &TrackedWhale, b: &TrackedWhale) -> Ordering {
+    let by_score = match (a.performance_score, b.performance_score) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    };
+    by_score
+        .then_with(|| a.tracked_since.cmp(&b.tracked_since))
+        .then_with(|| a.user_id.cmp(&b.user_id))
+}
+
+/// Keep the `cap` highest-priority whales (0 = no cap)
+pub fn prioritize_whales(whales: &[TrackedWhale], cap: u32) -> WhaleSelection {
+    let mut ranked: Vec<&TrackedWhale> = whales.iter().collect();
+    ranked.sort_by(|a, b| priority(a, b));
+
+    let keep = if cap == 0 { ranked.len() } else { (cap as usize).min(ranked.len()) };
+    WhaleSelection {
+        active: ranked[..keep].iter().map(|w| w.user_id.clone()).collect(),
+        dropped: ranked[keep..].iter().map(|w| (*w).clone()).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whale(user_id: &str, score: Option<f64>, tracked_since: &str) -> TrackedWhale {
+        TrackedWhale {
+            user_id: user_id.to_string(),
+            username: format!("user_{}", user_id),
+            performance_score: score,
+            tracked_since: tracked_since.to_string(),
+            max_latency_secs: None,
+            score_detail: None,
+            scored_at: None,
+        }
+    }
+
+    #[test]
+    fn test_cap_drops_lowest_priority_whales() {
+        let whales = vec![
+            whale("low", Some(1.0), "2024-01-01 00:00:00"),
+            whale("high", Some(9.0), "2024-03-01 00:00:00"),
+            whale("mid_new", Some(5.0), "2024-02-02 00:00:00"),
+            whale("mid_old", Some(5.0), "2024-02-01 00:00:00"),
+        ];
+
+        let selection = prioritize_whales(&whales, 2);
+        assert_eq!(selection.active.len(), 2);
+        assert!(selection.active.contains("high"));
+        assert!(selection.active.contains("mid_old"));
+        let dropped: Vec<&str> = selection.dropped.iter().map(|w| w.user_id.as_str()).collect();
+        assert_eq!(dropped, ["mid_new", "low"]);
+
+        // A cap of zero, or one above the list size, keeps everyone
+        for cap in [0, 10] {
+            let selection = prioritize_whales(&whales, cap);
+            assert_eq!(selection.active.len(), 4);
+            assert!(selection.dropped.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_unscored_whales_are_not_ranked_as_zero() {
+        let whales = vec![
+            whale("new", None, "2024-03-01 00:00:00"),
+            whale("zero", Some(0.0), "2024-02-01 00:00:00"),
+            whale("old", None, "2024-01-01 00:00:00"),
+            whale("good", Some(60.0), "2024-04-01 00:00:00"),
+        ];
+
+        // Scored whales keep their places; unscored ones follow, oldest first
+        let selection = prioritize_whales(&whales, 1);
+        assert!(selection.active.contains("good"));
+        let dropped: Vec<&str> = selection.dropped.iter().map(|w| w.user_id.as_str()).collect();
+        assert_eq!(dropped, ["zero", "old", "new"]);
+    }
+}
@@ -0,0 +1,255 @@
+//! Whale Score — is a whale worth copying?
+//!
+//! A whale's recent transactions (from their public profile) are replayed
+//! per coin through a [`CostBasis`]. A round trip runs from a buy into a flat
+//! position until the whale is flat again; its PnL is everything the sells in
+//! between realized, against the cost of the coins they sold. Scaling out in
+//! several sells is one round trip, and a position still open at the end of
+//! the window isn't counted yet. The score (0–100) weighs the
+//! share of winning round trips and their average PnL. With too few round
+//! trips to say anything the result is `insufficientData`, never a score of
+//! zero, and the mirror's `minWhaleScore` gate lets such whales through.
+
+use chrono::{DateTime, Duration, Utc};
+use rugplay_core::{parse_api_timestamp, parse_db_timestamp, CostBasis, TradeType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Round trips needed before a score means anything
+pub const MIN_ROUND_TRIPS: usize = 5;
+
+/// Scores older than this are recomputed
+pub const SCORE_REFRESH_SECS: i64 = 6 * 60 * 60;
+
+/// Share of the score from the win rate; the rest comes from average PnL
+const WIN_RATE_WEIGHT: f64 = 60.0;
+
+/// Average PnL mapped onto the rest of the score, clamped to ±this %
+const PNL_RANGE_PCT: f64 = 50.0;
+
+/// One buy or sell from a whale's transaction history
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhaleTrade {
+    pub side: TradeType,
+    pub symbol: String,
+    pub quantity: f64,
+    pub price: f64,
+    pub at: DateTime<Utc>,
+}
+
+impl WhaleTrade {
+    /// Parse an entry of a public profile's `recentTransactions`. Transfers
+    /// and entries without a usable price or time are skipped.
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        let obj = value.as_object()?;
+        let side = match obj.get("type")?.as_str()?.to_uppercase().as_str() {
+            "BUY" => TradeType::Buy,
+            "SELL" => TradeType::Sell,
+            _ => return None,
+        };
+        let number = |key: &str| match obj.get(key)? {
+            serde_json::Value::Number(n) => n.as_f64(),
+            serde_json::Value::String(s) => s.parse().ok(),
+            _ => None,
+        };
+        let (quantity, price) = (number("quantity")?, number("pricePerCoin")?);
+        if !(quantity > 0.0 && price > 0.0 && quantity.is_finite() && price.is_finite()) {
+            return None;
+        }
+        Some(Self {
+            side,
+            symbol: obj.get("coinSymbol")?.as_str()?.to_string(),
+            quantity,
+            price,
+            at: parse_api_timestamp(obj.get("timestamp")?.as_str()?)?,
+        })
+    }
+}
+
+/// Result of analyzing a whale's history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum WhaleScore {
+    Scored {
+        /// 0–100
+        score: f64,
+        /// Share of round trips closed in profit (0–1)
+        win_rate: f64,
+        /// Mean realized PnL per round trip, in %
+        avg_pnl_pct: f64,
+        round_trips: usize,
+        trades_analyzed: usize,
+    },
+    /// Too few closed round trips to judge
+    InsufficientData { round_trips: usize, trades_analyzed: usize, min_round_trips: usize },
+}
+
+impl WhaleScore {
+    /// The score, if there was enough history for one
+    pub fn score(&self) -> Option<f64> {
+        match self {
+            Self::Scored { score, .. } => Some(*score),
+            Self::InsufficientData { .. } => None,
+        }
+    }
+}
+
+/// A coin's position and the round trip it is in
+#[derive(Default)]
+struct OpenTrip {
+    basis: CostBasis,
+    /// Cost of the coins sold so far in this round trip
+    cost_sold: f64,
+    /// PnL those sells realized
+    realized: f64,
+}
+
+/// Replay `trades` per coin and score the fully closed round trips
+pub fn score_trades(trades: &[WhaleTrade]) -> WhaleScore {
+    let mut ordered: Vec<&WhaleTrade> = trades.iter().collect();
+    ordered.sort_by_key(|t| t.at);
+
+    let mut trips: HashMap<&str, OpenTrip> = HashMap::new();
+    let mut pnls: Vec<f64> = Vec::new();
+    for trade in ordered {
+        let trip = trips.entry(trade.symbol.as_str()).or_default();
+        match trade.side {
+            TradeType::Buy => trip.basis.buy(trade.quantity, trade.quantity * trade.price),
+            // A sell of coins bought before the window says nothing about this whale's entries
+            TradeType::Sell if trip.basis.quantity > 0.0 => {
+                trip.cost_sold += trip.basis.avg_cost() * trade.quantity.min(trip.basis.quantity);
+                trip.realized += trip.basis.sell(trade.quantity, trade.quantity * trade.price);
+                if trip.basis.quantity <= 0.0 {
+                    if trip.cost_sold > 0.0 {
+                        pnls.push(trip.realized / trip.cost_sold * 100.0);
+                    }
+                    trip.cost_sold = 0.0;
+                    trip.realized = 0.0;
+                }
+            }
+            TradeType::Sell => {}
+        }
+    }
+
+    let round_trips = pnls.len();
+    if round_trips < MIN_ROUND_TRIPS {
+        return WhaleScore::InsufficientData {
+            round_trips,
+            trades_analyzed: trades.len(),
+            min_round_trips: MIN_ROUND_TRIPS,
+        };
+    }
+    let win_rate = pnls.iter().filter(|p| **p > 0.0).count() as f64 / round_trips as f64;
+    let avg_pnl_pct = pnls.iter().sum::<f64>() / round_trips as f64;
+    let pnl_share = (avg_pnl_pct.clamp(-PNL_RANGE_PCT, PNL_RANGE_PCT) + PNL_RANGE_PCT) / (2.0 * PNL_RANGE_PCT);
+    let score = win_rate * WIN_RATE_WEIGHT + pnl_share * (100.0 - WIN_RATE_WEIGHT);
+    WhaleScore::Scored {
+        score: (score * 10.0).round() / 10.0,
+        win_rate,
+        avg_pnl_pct,
+        round_trips,
+        trades_analyzed: trades.len(),
+    }
+}
+
+/// Whether a whale's buys are copied under `min_score` (0 = no gate).
+/// Unscored whales and those with too little history are let through.
+pub fn passes_min_score(score: Option<f64>, min_score: f64) -> bool {
+    min_score <= 0.0 || score.is_none_or(|s| s >= min_score)
+}
+
+/// Whether a score stamped `scored_at` (a DB timestamp) is due for a refresh
+pub fn is_stale(scored_at: Option<&str>, now: DateTime<Utc>) -> bool {
+    scored_at
+        .and_then(parse_db_timestamp)
+        .is_none_or(|at| now - at >= Duration::seconds(SCORE_REFRESH_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(side: &str, symbol: &str, price: f64, minute: u32) -> serde_json::Value {
+        serde_json::json!({
+            "type": side, "coinSymbol": symbol, "quantity": "100", "pricePerCoin": price,
+            "timestamp": format!("2025-06-01T12:{:02}:00.000Z", minute),
+        })
+    }
+
+    #[test]
+    fn test_round_trips_are_scored_and_thin_history_is_flagged() {
+        let mut feed = vec![
+            // Sold before buying in the window: not a round trip
+            trade("SELL", "OLD", 2.0, 0),
+            trade("TRANSFER_IN", "AAA", 1.0, 1),
+        ];
+        for (i, (symbol, exit)) in [("A", 1.5), ("B", 1.2), ("C", 2.0), ("D", 0.5), ("E", 1.1)].iter().enumerate() {
+            feed.push(trade("BUY", symbol, 1.0, 2 + i as u32 * 2));
+            feed.push(trade("SELL", symbol, *exit, 3 + i as u32 * 2));
+        }
+        let trades: Vec<WhaleTrade> = feed.iter().filter_map(WhaleTrade::from_json).collect();
+        assert_eq!(trades.len(), 11);
+
+        // Four of five in profit, averaging +26%
+        let score = score_trades(&trades);
+        let WhaleScore::Scored { score: value, win_rate, avg_pnl_pct, round_trips: 5, trades_analyzed: 11 } = score
+        else {
+            panic!("expected a score, got {:?}", score);
+        };
+        assert_eq!(win_rate, 0.8);
+        assert!((avg_pnl_pct - 26.0).abs() < 1e-9);
+        assert_eq!(value, 78.4);
+
+        // A new whale with a couple of trades is flagged, not scored zero
+        let thin = score_trades(&trades[..5]);
+        assert_eq!(thin, WhaleScore::InsufficientData { round_trips: 2, trades_analyzed: 5, min_round_trips: 5 });
+        assert_eq!(serde_json::to_value(&thin).unwrap()["kind"], "insufficientData");
+        assert!(passes_min_score(thin.score(), 50.0));
+        assert!(passes_min_score(Some(78.4), 50.0));
+        assert!(!passes_min_score(Some(30.0), 50.0));
+        assert!(passes_min_score(Some(30.0), 0.0));
+
+        let now = DateTime::parse_from_rfc3339("2025-06-01T18:00:00Z").unwrap().to_utc();
+        assert!(is_stale(None, now));
+        assert!(is_stale(Some("2025-06-01 11:59:00"), now));
+        assert!(!is_stale(Some("2025-06-01 12:30:00"), now));
+    }
+
+    #[test]
+    fn test_scaling_out_is_one_round_trip_counted_on_the_full_close() {
+        let at = |minute: i64| DateTime::from_timestamp(1_750_000_000 + minute * 60, 0).unwrap();
+        let fill = |side, symbol: &str, quantity, price, minute| WhaleTrade {
+            side,
+            symbol: symbol.to_string(),
+            quantity,
+            price,
+            at: at(minute),
+        };
+        let trades = vec![
+            // Closed in two sells: +100% on half, +50% on the rest
+            fill(TradeType::Buy, "A", 100.0, 1.0, 0),
+            fill(TradeType::Sell, "A", 50.0, 2.0, 1),
+            fill(TradeType::Sell, "A", 50.0, 1.5, 2),
+            // Still half open: not a round trip yet
+            fill(TradeType::Buy, "B", 100.0, 1.0, 3),
+            fill(TradeType::Sell, "B", 50.0, 3.0, 4),
+        ];
+
+        assert_eq!(
+            score_trades(&trades),
+            WhaleScore::InsufficientData { round_trips: 1, trades_analyzed: 5, min_round_trips: MIN_ROUND_TRIPS }
+        );
+
+        // Five such trips score on their full-close PnL, weighted by size
+        let repeated: Vec<WhaleTrade> = (0..5)
+            .flat_map(|i| {
+                trades[..3].iter().map(move |t| WhaleTrade { at: t.at + Duration::minutes(10 * i), ..t.clone() })
+            })
+            .collect();
+        let WhaleScore::Scored { win_rate, avg_pnl_pct, round_trips: 5, .. } = score_trades(&repeated) else {
+            panic!("expected a score");
+        };
+        assert_eq!(win_rate, 1.0);
+        assert!((avg_pnl_pct - 75.0).abs() < 1e-9);
+    }
+}
@@ -4,17 +4,21 @@ import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
 import { ProfileSelect } from './components/auth/ProfileSelect'
 import { AddProfile } from './components/auth/AddProfile'
+import type { AddProfileOptions } from './components/auth/AddProfile'
 import { TokenExpired } from './components/auth/TokenExpired'
 import { Dashboard } from './components/layout/Dashboard'
 import { activityStore } from './lib/activityStore'
+import { formatFillPrice } from './lib/utils'
 import type {
   ProfileSummary,
+  SetupState,
   UserProfile,
   LoginResult,
   SniperTriggeredEvent,
   HarvesterClaimedEvent,
   TradeExecutedEvent,
   DipBuyerTriggeredEvent,
+  ProfileFailoverEvent,
 } from './lib/types'
 
 type AppScreen = 
@@ -45,7 +49,7 @@ function App() {
         const p = event.payload
         activityStore.addActivity({
           type: 'sentinel',
-          title: `Sentinel ${p.triggerType === 'stop_loss' ? 'SL' : p.triggerType === 'take_profit' ? 'TP' : 'TS'} — ${p.symbol}`,
+          title: `Sentinel ${p.triggerType === 'stop_loss' ? 'SL' : p.triggerType === 'take_profit' ? 'TP' : p.triggerType === 'liquidity_drain' ? 'LQ' : 'TS'} — ${p.symbol}`,
           description: p.reason,
           timestamp: Date.now(),
         })
@@ -77,8 +81,8 @@ function App() {
       const p = event.payload
       activityStore.addActivity({
         type: 'trade',
-        title: `${p.tradeType} ${p.symbol}`,
-        description: p.success ? `$${p.amount.toFixed(2)} @ $${p.newPrice.toFixed(8)}` : `Failed: ${p.error}`,
+        title: `${p.paper ? 'Paper ' : ''}${p.tradeType} ${p.symbol}`,
+        description: p.success ? `$${p.amount.toFixed(2)} @ ${formatFillPrice(p.newPrice)}` : `Failed: ${p.error}`,
         timestamp: Date.now(),
       })
     }).then((u) => unlisteners.push(u))
@@ -93,6 +97,18 @@ function App() {
       })
     }).then((u) => unlisteners.push(u))
 
+    // Automation switched to a backup profile; follow it in the UI
+    listen<ProfileFailoverEvent>('profile-failover', (event) => {
+      const p = event.payload
+      activityStore.addActivity({
+        type: 'profile',
+        title: `Switched to @${p.username}`,
+        description: `Active profile ${p.reason}`,
+        timestamp: Date.now(),
+      })
+      handleSelectProfile(p.toProfileId)
+    }).then((u) => unlisteners.push(u))
+
     return () => {
       unlisteners.forEach((u) => u())
     }
@@ -105,9 +121,16 @@ function App() {
 
   async function loadProfiles() {
     try {
+      // The database opens asynchronously at startup; wait for it
+      const setup = await invoke<SetupState>('get_setup_state')
+      if (!setup.databaseReady) {
+        setTimeout(loadProfiles, 500)
+        return
+      }
+
       const profiles = await invoke<ProfileSummary[]>('list_profiles')
       
-      if (profiles.length === 0) {
+      if (setup.nextStep === 'addProfile' || profiles.length === 0) {
         setScreen({ type: 'add-profile' })
       } else {
         // Check if there's an active profile
@@ -153,10 +176,14 @@ function App() {
     }
   }
 
-  async function handleAddProfile(token: string) {
+  async function handleAddProfile(token: string, options?: AddProfileOptions) {
     try {
       setError(null)
-      const profile = await invoke<ProfileSummary>('add_profile', { token })
+      const profile = await invoke<ProfileSummary>('add_profile', {
+        token,
+        force: options?.force ?? false,
+        username: options?.username,
+      })
       
       // Auto-login to the new profile
       handleSelectProfile(profile.id)
@@ -198,6 +225,16 @@ function App() {
     }
   }
 
+  async function handleRefreshProfiles() {
+    try {
+      setError(null)
+      const profiles = await invoke<ProfileSummary[]>('refresh_profiles', {})
+      setScreen({ type: 'profile-select', profiles })
+    } catch (e) {
+      setError(`Failed to refresh profiles: ${e}`)
+    }
+  }
+
   // Render current screen
   function renderScreen() {
     console.log('[App] renderScreen called with:', screen.type)
@@ -217,6 +254,7 @@ function App() {
             onSelect={handleSelectProfile}
             onAddNew={() => setScreen({ type: 'add-profile' })}
             onDelete={handleDeleteProfile}
+            onRefresh={handleRefreshProfiles}
             error={error}
           />
         )
@@ -1,22 +1,54 @@
 import { useState } from 'react'
-import { ArrowLeft, Key, Loader2 } from 'lucide-react'
+import { invoke } from '@tauri-apps/api/core'
+import { ArrowLeft, Key, Loader2, CheckCircle, AlertTriangle } from 'lucide-react'
+import type { TokenValidation } from '@/lib/types'
+
+export interface AddProfileOptions {
+  force: boolean
+  username?: string
+}
 
 interface AddProfileProps {
-  onSubmit: (token: string) => void
+  onSubmit: (token: string, options?: AddProfileOptions) => void
   onBack?: () => void
   error: string | null
 }
 
+function describeValidation(v: TokenValidation): string {
+  switch (v.status) {
+    case 'valid': return `Valid — belongs to @${v.username}`
+    case 'expired': return 'This session has expired. Log in again and copy a fresh token.'
+    case 'invalid': return `Rejected by Rugplay: ${v.reason}`
+    case 'unreachable': return `Could not reach Rugplay (${v.reason}). You can still save it unverified.`
+  }
+}
+
 export function AddProfile({ onSubmit, onBack, error }: AddProfileProps) {
   const [token, setToken] = useState('')
   const [isLoading, setIsLoading] = useState(false)
+  const [checking, setChecking] = useState(false)
+  const [validation, setValidation] = useState<TokenValidation | null>(null)
+  const [saveAnyway, setSaveAnyway] = useState(false)
+  const [username, setUsername] = useState('')
+
+  async function handleCheck() {
+    if (!token.trim()) return
+    setChecking(true)
+    try {
+      setValidation(await invoke<TokenValidation>('validate_token', { token: token.trim() }))
+    } catch (e) {
+      setValidation({ status: 'unreachable', reason: String(e) })
+    } finally {
+      setChecking(false)
+    }
+  }
 
   async function handleSubmit(e: React.FormEvent) {
     e.preventDefault()
     if (!token.trim()) return
     
     setIsLoading(true)
-    await onSubmit(token.trim())
+    await onSubmit(token.trim(), saveAnyway ? { force: true, username: username.trim() } : undefined)
     setIsLoading(false)
   }
 
@@ -62,28 +94,72 @@ export function AddProfile({ onSubmit, onBack, error }: AddProfileProps) {
             <textarea
               id="token"
               value={token}
-              onChange={(e) => setToken(e.target.value)}
+              onChange={(e) => { setToken(e.target.value); setValidation(null) }}
               placeholder="Paste your __Secure-better-auth.session_token here..."
               className="input min-h-[100px] resize-none font-mono text-xs"
               disabled={isLoading}
             />
-            <p className="mt-2 text-xs text-foreground-muted">
-              Find this in your browser's cookies at rugplay.com
-            </p>
+            <div className="mt-2 flex items-center justify-between gap-2">
+              <p className="text-xs text-foreground-muted">
+                Find this in your browser's cookies at rugplay.com
+              </p>
+              <button
+                type="button"
+                onClick={handleCheck}
+                disabled={!token.trim() || checking || isLoading}
+                className="text-xs text-blue-400 hover:text-blue-300 disabled:opacity-50 flex-shrink-0"
+              >
+                {checking ? 'Checking...' : 'Check token'}
+              </button>
+            </div>
           </div>
 
+          {validation && (
+            <div className={`flex items-start gap-2 p-3 rounded-lg text-sm ${
+              validation.status === 'valid' ? 'bg-buy/10 text-buy' : 'bg-amber-500/10 text-amber-400'
+            }`}>
+              {validation.status === 'valid'
+                ? <CheckCircle className="w-4 h-4 mt-0.5 flex-shrink-0" />
+                : <AlertTriangle className="w-4 h-4 mt-0.5 flex-shrink-0" />}
+              {describeValidation(validation)}
+            </div>
+          )}
+
+          {validation && validation.status !== 'valid' && (
+            <div className="space-y-2">
+              <label className="flex items-center gap-2 text-xs text-foreground-muted cursor-pointer">
+                <input
+                  type="checkbox"
+                  checked={saveAnyway}
+                  onChange={(e) => setSaveAnyway(e.target.checked)}
+                  disabled={isLoading}
+                />
+                Save anyway without verification
+              </label>
+              {saveAnyway && (
+                <input
+                  value={username}
+                  onChange={(e) => setUsername(e.target.value)}
+                  placeholder="Rugplay username for this token"
+                  className="input text-sm"
+                  disabled={isLoading}
+                />
+              )}
+            </div>
+          )}
+
           <button
             type="submit"
-            disabled={!token.trim() || isLoading}
+            disabled={!token.trim() || isLoading || (saveAnyway && !username.trim())}
             className="btn btn-primary w-full h-12 text-base"
           >
             {isLoading ? (
               <>
                 <Loader2 className="w-5 h-5 mr-2 animate-spin" />
-                Validating...
+                {saveAnyway ? 'Saving...' : 'Validating...'}
               </>
             ) : (
-              'Add Profile'
+              saveAnyway ? 'Save Unverified' : 'Add Profile'
             )}
           </button>
         </form>
@@ -1,12 +1,14 @@
 import { useState } from 'react'
-import { User, Trash2, Plus, ChevronRight } from 'lucide-react'
+import { User, Trash2, Plus, ChevronRight, RefreshCw } from 'lucide-react'
 import type { ProfileSummary } from '@/lib/types'
+import { buildImageUrl } from '@/lib/utils'
 
 interface ProfileSelectProps {
   profiles: ProfileSummary[]
   onSelect: (profileId: number) => void
   onAddNew: () => void
   onDelete: (profileId: number) => void
+  onRefresh: () => Promise<void>
   error: string | null
 }
 
@@ -15,10 +17,18 @@ export function ProfileSelect({
   onSelect, 
   onAddNew, 
   onDelete,
+  onRefresh,
   error 
 }: ProfileSelectProps) {
   const [selectedId, setSelectedId] = useState<number | null>(null)
   const [isLoading, setIsLoading] = useState(false)
+  const [isRefreshing, setIsRefreshing] = useState(false)
+
+  async function handleRefresh() {
+    setIsRefreshing(true)
+    await onRefresh()
+    setIsRefreshing(false)
+  }
 
   async function handleSelect(profileId: number) {
     setSelectedId(profileId)
@@ -67,8 +77,12 @@ export function ProfileSelect({
                 disabled:opacity-50 disabled:cursor-not-allowed
               `}
             >
-              <div className="w-10 h-10 rounded-full bg-background-tertiary flex items-center justify-center">
-                <User className="w-5 h-5 text-foreground-muted" />
+              <div className="w-10 h-10 rounded-full bg-background-tertiary flex items-center justify-center overflow-hidden">
+                {profile.avatar_url ? (
+                  <img src={buildImageUrl(profile.avatar_url) ?? undefined} alt={profile.username} className="w-full h-full object-cover" />
+                ) : (
+                  <User className="w-5 h-5 text-foreground-muted" />
+                )}
               </div>
               
               <div className="flex-1 text-left">
@@ -101,6 +115,17 @@ export function ProfileSelect({
           <Plus className="w-5 h-5" />
           Add New Profile
         </button>
+
+        {profiles.length > 0 && (
+          <button
+            onClick={handleRefresh}
+            disabled={isRefreshing || isLoading}
+            className="mt-2 w-full flex items-center justify-center gap-2 p-2 text-xs text-foreground-muted hover:text-foreground disabled:opacity-50 transition-colors"
+          >
+            <RefreshCw className={`w-3.5 h-3.5 ${isRefreshing ? 'animate-spin' : ''}`} />
+            {isRefreshing ? 'Refreshing usernames...' : 'Refresh usernames'}
+          </button>
+        )}
       </div>
     </div>
   )
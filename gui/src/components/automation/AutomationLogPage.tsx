@@ -10,9 +10,10 @@ import {
   TrendingDown,
   Gift,
   Zap,
+  History,
 } from 'lucide-react'
 import { activityStore } from '@/lib/activityStore'
-import type { AutomationLogEntry } from '@/lib/types'
+import type { AutomationLogEntry, ConfigHistoryEntry, Page } from '@/lib/types'
 
 const MODULE_META: Record<string, { label: string; icon: React.ReactNode; color: string }> = {
   sniper: {
@@ -46,19 +47,26 @@ export function AutomationLogPage() {
   const [dbEntries, setDbEntries] = useState<AutomationLogEntry[]>([])
   const [loading, setLoading] = useState(true)
   const [moduleFilter, setModuleFilter] = useState<string | null>(null)
+  const [configHistory, setConfigHistory] = useState<ConfigHistoryEntry[]>([])
 
   const liveActivities = useSyncExternalStore(
     activityStore.subscribeActivities,
     activityStore.getActivities,
   )
 
+  useEffect(() => {
+    invoke<Page<ConfigHistoryEntry>>('get_config_history', { module: moduleFilter, limit: 50 })
+      .then(page => setConfigHistory(page.items))
+      .catch(e => console.error('Failed to load config history:', e))
+  }, [moduleFilter])
+
   const fetchLog = useCallback(async () => {
     try {
-      const entries = await invoke<AutomationLogEntry[]>('get_automation_log', {
+      const entries = await invoke<Page<AutomationLogEntry>>('get_automation_log', {
         module: moduleFilter,
         limit: 200,
       })
-      setDbEntries(entries)
+      setDbEntries(entries.items)
     } catch (e) {
       console.error('Failed to fetch automation log:', e)
     } finally {
@@ -239,6 +247,11 @@ export function AutomationLogPage() {
                   >
                     {meta.label}
                   </span>
+                  {details.paper === true && (
+                    <span className="text-xs px-1.5 py-0.5 rounded font-medium text-sky-400 bg-sky-500/15">
+                      PAPER
+                    </span>
+                  )}
                   <span className="font-medium text-foreground">
                     {entry.action} ${entry.symbol}
                   </span>
@@ -262,6 +275,37 @@ export function AutomationLogPage() {
           </div>
         )}
       </div>
+
+      {/* Config Changes */}
+      <div className="card">
+        <div className="flex items-center gap-2 mb-4">
+          <History className="w-5 h-5 text-sky-400" />
+          <h2 className="text-lg font-semibold">Config Changes</h2>
+        </div>
+        {configHistory.length === 0 ? (
+          <p className="text-sm text-foreground-muted">No config changes recorded yet.</p>
+        ) : (
+          <div className="space-y-2 max-h-[400px] overflow-y-auto">
+            {configHistory.map(entry => (
+              <div key={entry.id} className="px-3 py-2.5 rounded-lg bg-background">
+                <div className="flex items-center gap-2 mb-1">
+                  <span className={`text-sm font-medium ${MODULE_META[entry.module]?.color ?? 'text-zinc-400'}`}>
+                    {MODULE_META[entry.module]?.label ?? entry.module}
+                  </span>
+                  <span className="ml-auto text-xs text-foreground-muted">
+                    {entry.changedAt ? new Date(entry.changedAt).toLocaleString() : ''}
+                  </span>
+                </div>
+                {entry.changes.map(c => (
+                  <div key={c.field} className="text-xs font-mono text-foreground-muted">
+                    {c.field}: {c.kind === 'added' ? '—' : JSON.stringify(c.old)} → {c.kind === 'removed' ? '—' : JSON.stringify(c.new)}
+                  </div>
+                ))}
+              </div>
+            ))}
+          </div>
+        )}
+      </div>
     </div>
   )
 }
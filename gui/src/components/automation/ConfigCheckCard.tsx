@@ -0,0 +1,88 @@
+import { useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Filter, RefreshCw } from 'lucide-react'
+import type { FilterCheck, SkipReason } from '@/lib/types'
+
+const FILTER_LABELS: Record<SkipReason['kind'], string> = {
+  sellBelowTierMin: 'Min sell value',
+  lowVolume: 'Min 24h volume',
+  lowMarketCap: 'Min market cap',
+  highMarketCap: 'Max market cap',
+  marketCapUnknown: 'Market cap unknown',
+  droppedTooMuch: 'Max price drop',
+  positionTooLarge: 'Max position size',
+  portfolioUnavailable: 'Portfolio unavailable',
+  signalReject: 'Signal reject',
+  lowConfidence: 'Min confidence',
+  coinTooOld: 'Max coin age',
+  creatorCooldown: 'Creator cooldown',
+  blacklistedCreator: 'Blacklisted creators',
+  dailySpendLimit: 'Daily spend cap',
+  highRisk: 'Max risk score',
+  scriptReject: 'Entry script',
+}
+
+interface ConfigCheckCardProps {
+  module: FilterCheck['module']
+}
+
+/** Dry-run the module's saved entry filters over the live market */
+export function ConfigCheckCard({ module }: ConfigCheckCardProps) {
+  const [check, setCheck] = useState<FilterCheck | null>(null)
+  const [running, setRunning] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const run = async () => {
+    setRunning(true)
+    setError(null)
+    try {
+      const checks = await invoke<FilterCheck[]>('validate_config_against_market', {})
+      setCheck(checks.find(c => c.module === module) ?? null)
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setRunning(false)
+    }
+  }
+
+  return (
+    <div className="card">
+      <div className="flex items-center gap-2 mb-2">
+        <Filter className="w-5 h-5 text-sky-400" />
+        <h2 className="text-lg font-semibold">Check Against Market</h2>
+        <button onClick={run} disabled={running} className="btn btn-ghost flex items-center gap-2 ml-auto">
+          <RefreshCw className={`w-4 h-4 ${running ? 'animate-spin' : ''}`} /> Run
+        </button>
+      </div>
+      <p className="text-sm text-foreground-muted mb-3">
+        How many coins on the market right now pass the saved filters. Nothing is bought.
+      </p>
+
+      {error && <p className="text-sm text-rose-400">{error}</p>}
+
+      {check && (
+        <div className="space-y-2 text-sm">
+          <p>
+            <span className={check.qualifying > 0 ? 'text-emerald-400' : 'text-rose-400'}>
+              {check.qualifying} of {check.sampled}
+            </span>{' '}
+            sampled coins qualify
+            {check.qualifyingSymbols.length > 0 && (
+              <span className="text-foreground-muted"> ({check.qualifyingSymbols.join(', ')})</span>
+            )}
+          </p>
+          {check.exclusions.map(e => (
+            <div
+              key={e.filter}
+              className={`flex items-center gap-3 py-1 border-b border-white/[0.04] ${e.filter === check.mostRestrictive ? 'text-amber-400' : ''}`}
+            >
+              <span className="w-40">{FILTER_LABELS[e.filter]}</span>
+              <span className="w-24">excludes {e.excluded}</span>
+              <span className="text-xs text-foreground-muted truncate">{e.example}</span>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  )
+}
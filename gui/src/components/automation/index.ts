@@ -1 +1,2 @@
 export { AutomationLogPage } from './AutomationLogPage'
+export { ConfigCheckCard } from './ConfigCheckCard'
@@ -1,8 +1,8 @@
-import { Activity, Shield, Crosshair, Sprout, ArrowUpDown, TrendingDown } from 'lucide-react'
+import { Activity, Shield, Crosshair, Sprout, ArrowUpDown, TrendingDown, Users } from 'lucide-react'
 
 export interface ActivityItem {
   id: number
-  type: 'sentinel' | 'sniper' | 'harvester' | 'trade' | 'mirror' | 'dipbuyer'
+  type: 'sentinel' | 'sniper' | 'harvester' | 'trade' | 'mirror' | 'dipbuyer' | 'profile'
   title: string
   description: string
   timestamp: number
@@ -15,6 +15,7 @@ const typeIcons: Record<ActivityItem['type'], React.ReactNode> = {
   trade: <ArrowUpDown className="w-4 h-4 text-blue-400" />,
   mirror: <Activity className="w-4 h-4 text-cyan-400" />,
   dipbuyer: <TrendingDown className="w-4 h-4 text-purple-400" />,
+  profile: <Users className="w-4 h-4 text-violet-400" />,
 }
 
 function timeAgo(ts: number): string {
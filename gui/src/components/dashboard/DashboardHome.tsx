@@ -13,8 +13,16 @@ import {
   PieChart,
   Banknote,
   RefreshCw,
+  PauseCircle,
+  PlayCircle,
+  PowerOff,
+  FlaskConical,
+  ShieldCheck,
 } from 'lucide-react'
 import { HarvesterWidget } from './HarvesterWidget'
+import { PendingApprovalsWidget } from './PendingApprovalsWidget'
+import { PendingTradesWidget } from './PendingTradesWidget'
+import { SetupChecklistWidget } from './SetupChecklistWidget'
 import { ModuleStatusCard } from './ModuleStatusCard'
 import { ActivityFeed } from './ActivityFeed'
 import { activityStore } from '@/lib/activityStore'
@@ -26,6 +34,11 @@ import type {
   DipBuyerTickEvent,
   DipBuyerStatusResponse,
   CoinHolding,
+  AcquisitionPauseState,
+  EmergencyStopEvent,
+  Page,
+  SemiAutoConfig,
+  WarmupStatus,
 } from '@/lib/types'
 
 interface DashboardHomeProps {
@@ -67,6 +80,12 @@ export function DashboardHome({
   const [mirrorTotal, setMirrorTotal] = useState(0)
   const [dipbuyerEnabled, setDipbuyerEnabled] = useState(false)
   const [dipbuyerTotal, setDipbuyerTotal] = useState(0)
+  const [acquisitionPaused, setAcquisitionPaused] = useState(false)
+  const [pauseBusy, setPauseBusy] = useState(false)
+  const [emergencyStopped, setEmergencyStopped] = useState(false)
+  const [paperTrading, setPaperTrading] = useState(false)
+  const [semiAuto, setSemiAuto] = useState<SemiAutoConfig | null>(null)
+  const [warmup, setWarmup] = useState<WarmupStatus | null>(null)
 
   // Read persistent activity feed from the store (survives unmount)
   const activities = useSyncExternalStore(
@@ -104,8 +123,8 @@ export function DashboardHome({
       try {
         const sentinelRes = await invoke<{ status: string; intervalSecs: number; isPaused: boolean }>('get_sentinel_monitor_status')
         // Also fetch sentinel count
-        const sentinels = await invoke<Array<{ id: number; isActive: boolean }>>('list_sentinels')
-        const activeCount = sentinels.filter(s => s.isActive).length
+        const sentinels = await invoke<Page<{ id: number; isActive: boolean }>>('list_sentinels')
+        const activeCount = sentinels.items.filter(s => s.isActive).length
         setSentinelStatus({
           status: sentinelRes.status,
           activeCount,
@@ -131,6 +150,18 @@ export function DashboardHome({
         setDipbuyerEnabled(dipbuyerRes.enabled)
         setDipbuyerTotal(dipbuyerRes.totalBought)
       } catch { /* dipbuyer may not be ready */ }
+
+      try {
+        const pauseRes = await invoke<AcquisitionPauseState>('get_acquisition_paused')
+        setAcquisitionPaused(pauseRes.paused)
+        setEmergencyStopped(await invoke<boolean>('get_emergency_stop'))
+        setPaperTrading(await invoke<boolean>('get_paper_trading'))
+        setSemiAuto(await invoke<SemiAutoConfig>('get_semi_auto_config'))
+      } catch { /* DB may not be ready */ }
+
+      try {
+        setWarmup(await invoke<WarmupStatus>('get_warmup_status'))
+      } catch { /* state may not be ready */ }
     }
 
     fetchModuleStatuses()
@@ -167,11 +198,82 @@ export function DashboardHome({
       setDipbuyerTotal(event.payload.totalBought)
     }).then((u) => unlisteners.push(u))
 
+    listen<WarmupStatus>('warmup-status', (event) => {
+      setWarmup(event.payload)
+    }).then((u) => unlisteners.push(u))
+
+    listen<EmergencyStopEvent>('emergency-stop', (event) => {
+      setEmergencyStopped(event.payload.active)
+      if (event.payload.active) {
+        setSniperEnabled(false)
+        setMirrorEnabled(false)
+        setDipbuyerEnabled(false)
+      }
+    }).then((u) => unlisteners.push(u))
+
     return () => {
       unlisteners.forEach((u) => u())
     }
   }, [])
 
+  const toggleAcquisitionPause = async () => {
+    setPauseBusy(true)
+    try {
+      const res = await invoke<AcquisitionPauseState>('set_acquisition_paused', { paused: !acquisitionPaused })
+      setAcquisitionPaused(res.paused)
+      const [sniperRes, mirrorRes, dipbuyerRes] = await Promise.all([
+        invoke<{ enabled: boolean }>('get_sniper_status'),
+        invoke<{ enabled: boolean }>('get_mirror_status'),
+        invoke<DipBuyerStatusResponse>('get_dipbuyer_status'),
+      ])
+      setSniperEnabled(sniperRes.enabled)
+      setMirrorEnabled(mirrorRes.enabled)
+      setDipbuyerEnabled(dipbuyerRes.enabled)
+    } catch (e) {
+      console.error('Failed to toggle acquisition pause:', e)
+    } finally {
+      setPauseBusy(false)
+    }
+  }
+
+  const toggleEmergencyStop = async () => {
+    if (!emergencyStopped && !confirm('Stop all automation and cancel every queued trade?')) return
+    setPauseBusy(true)
+    try {
+      const res = await invoke<EmergencyStopEvent>(emergencyStopped ? 'release_emergency_stop' : 'emergency_stop')
+      setEmergencyStopped(res.active)
+    } catch (e) {
+      console.error('Failed to toggle emergency stop:', e)
+    } finally {
+      setPauseBusy(false)
+    }
+  }
+
+  const togglePaperTrading = async () => {
+    setPauseBusy(true)
+    try {
+      setPaperTrading(await invoke<boolean>('set_paper_trading', { enabled: !paperTrading }))
+    } catch (e) {
+      console.error('Failed to toggle paper trading:', e)
+    } finally {
+      setPauseBusy(false)
+    }
+  }
+
+  const toggleSemiAuto = async () => {
+    if (!semiAuto) return
+    setPauseBusy(true)
+    try {
+      setSemiAuto(await invoke<SemiAutoConfig>('set_semi_auto_config', {
+        config: { ...semiAuto, enabled: !semiAuto.enabled },
+      }))
+    } catch (e) {
+      console.error('Failed to toggle semi-auto mode:', e)
+    } finally {
+      setPauseBusy(false)
+    }
+  }
+
   const pnl = summary?.totalProfitLoss ?? 0
   const pnlPct = summary?.totalProfitLossPct ?? 0
   const pnlPositive = pnl >= 0
@@ -209,6 +311,95 @@ export function DashboardHome({
         />
       </div>
 
+      {/* Startup Warm-up */}
+      {warmup && !warmup.ready && (
+        <div className="flex items-center gap-2 p-3 rounded-lg bg-amber-500/10 border border-amber-500/30 text-sm">
+          <RefreshCw className="w-4 h-4 text-amber-400 animate-spin" />
+          <span className="font-medium">Warming up</span>
+          <span className="text-foreground-muted">
+            Automated trading starts once {warmup.pending.join(', ')} {warmup.pending.length === 1 ? 'is' : 'are'} loaded
+            {warmup.lastError ? ` (${warmup.lastError})` : ''}
+          </span>
+        </div>
+      )}
+
+      {/* Paper Trading */}
+      {paperTrading && (
+        <div className="flex items-center gap-2 p-3 rounded-lg bg-sky-500/10 border border-sky-500/30 text-sm">
+          <FlaskConical className="w-4 h-4 text-sky-400" />
+          <span className="font-medium">Paper trading</span>
+          <span className="text-foreground-muted">
+            Automated trades are simulated at current pool prices — manual trades are still real
+          </span>
+        </div>
+      )}
+
+      {/* Acquisition Pause / Emergency Stop */}
+      <div
+        className={`flex items-center justify-between p-3 rounded-lg border ${
+          emergencyStopped ? 'bg-rose-500/10 border-rose-500/30' : 'bg-background-secondary border-background-tertiary'
+        }`}
+      >
+        <div className="text-sm">
+          {emergencyStopped ? (
+            <>
+              <span className="font-medium text-rose-400">Emergency stop</span>
+              <span className="text-foreground-muted ml-2">
+                All automation is halted, sentinels included — release to resume monitoring
+              </span>
+            </>
+          ) : (
+            <>
+              <span className="font-medium">{acquisitionPaused ? 'Buying paused' : 'Buying active'}</span>
+              <span className="text-foreground-muted ml-2">
+                {acquisitionPaused
+                  ? 'Sniper, Mirror and Dip Buyer are off — sentinels keep protecting your positions'
+                  : 'Pause all buy-side modules while keeping sentinels running'}
+              </span>
+            </>
+          )}
+        </div>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={togglePaperTrading}
+            disabled={pauseBusy}
+            className={`btn ${paperTrading ? 'btn-primary' : 'btn-ghost'} flex items-center gap-2`}
+          >
+            <FlaskConical className="w-4 h-4" />
+            {paperTrading ? 'Trade Live' : 'Paper Trade'}
+          </button>
+          <button
+            onClick={toggleSemiAuto}
+            disabled={pauseBusy || !semiAuto}
+            className={`btn ${semiAuto?.enabled ? 'btn-primary' : 'btn-ghost'} flex items-center gap-2`}
+            title={semiAuto?.enabled
+              ? 'Automated trades wait for your approval'
+              : 'Hold automated trades for your approval instead of executing them'}
+          >
+            <ShieldCheck className="w-4 h-4" />
+            {semiAuto?.enabled ? 'Full Auto' : 'Semi-Auto'}
+          </button>
+          {!emergencyStopped && (
+            <button
+              onClick={toggleAcquisitionPause}
+              disabled={pauseBusy}
+              className={`btn ${acquisitionPaused ? 'btn-primary' : 'btn-ghost'} flex items-center gap-2`}
+            >
+              {acquisitionPaused ? <PlayCircle className="w-4 h-4" /> : <PauseCircle className="w-4 h-4" />}
+              {acquisitionPaused ? 'Resume Buying' : 'Pause Buying'}
+            </button>
+          )}
+          <button
+            onClick={toggleEmergencyStop}
+            disabled={pauseBusy}
+            className={`btn ${emergencyStopped ? 'btn-primary' : 'btn-ghost text-rose-400'} flex items-center gap-2`}
+          >
+            {emergencyStopped ? <PlayCircle className="w-4 h-4" /> : <PowerOff className="w-4 h-4" />}
+            {emergencyStopped ? 'Release Stop' : 'Emergency Stop'}
+          </button>
+        </div>
+      </div>
+
       {/* Module Status Cards */}
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-4 gap-4">
         <ModuleStatusCard
@@ -336,6 +527,15 @@ export function DashboardHome({
         <ActivityFeed activities={activities} />
       </div>
 
+      {/* First-run checklist (hidden once setup is complete) */}
+      <SetupChecklistWidget />
+
+      {/* Trades held by semi-auto mode (hidden when there are none) */}
+      <PendingApprovalsWidget />
+
+      {/* Queued Trades (hidden when the executor queue is empty) */}
+      <PendingTradesWidget />
+
       {/* Harvester Widget */}
       <HarvesterWidget />
 
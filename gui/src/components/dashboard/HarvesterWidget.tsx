@@ -2,7 +2,18 @@ import { useState, useEffect, useCallback } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
 import { Sprout, Clock, Gift, RefreshCw, Zap } from 'lucide-react'
-import type { HarvesterStatusResponse, HarvesterTickEvent, HarvesterClaimedEvent } from '@/lib/types'
+import type { HarvesterStatusResponse, HarvesterTickEvent, HarvesterClaimedEvent, ProfileClaimOutcome } from '@/lib/types'
+
+function describeOutcome(o: ProfileClaimOutcome): string {
+  switch (o.kind) {
+    case 'claimed':
+      return `${o.username}: $${o.amount.toFixed(2)} claimed (streak: ${o.loginStreak})`
+    case 'nothingToClaim':
+      return `${o.username}: nothing to claim until ${new Date(o.nextEligibleAt).toLocaleTimeString()}`
+    case 'failed':
+      return `${o.username}: ${o.rateLimited ? 'rate limited' : 'failed'} — ${o.reason}`
+  }
+}
 
 export function HarvesterWidget() {
   const [status, setStatus] = useState<HarvesterStatusResponse | null>(null)
@@ -34,6 +45,8 @@ export function HarvesterWidget() {
         nextClaimAt: prev?.nextClaimAt ?? null,
         secondsUntilNext,
         totalClaims,
+        lastOutcome: prev?.lastOutcome ?? null,
+        outcomes: prev?.outcomes ?? [],
       }))
     })
 
@@ -89,8 +102,8 @@ export function HarvesterWidget() {
   const forceClaim = async () => {
     setClaiming(true)
     try {
-      const msg = await invoke<string>('force_claim_reward')
-      setLastEvent(msg)
+      const outcomes = await invoke<ProfileClaimOutcome[]>('force_claim_reward')
+      setLastEvent(outcomes.map(describeOutcome).join('\n'))
       fetchStatus()
       setTimeout(() => setLastEvent(null), 5000)
     } catch (e) {
@@ -174,9 +187,16 @@ export function HarvesterWidget() {
         </div>
       </div>
 
+      {/* Last attempt, when it didn't claim */}
+      {status.lastOutcome && status.lastOutcome.kind === 'failed' && (
+        <div className="mt-3 text-xs text-red-400">
+          Last attempt {new Date(status.lastOutcome.at).toLocaleTimeString()}: {describeOutcome(status.lastOutcome)}
+        </div>
+      )}
+
       {/* Event notification */}
       {lastEvent && (
-        <div className="mt-3 p-2 rounded-lg bg-emerald-500/20 text-emerald-400 text-xs text-center">
+        <div className="mt-3 p-2 rounded-lg bg-emerald-500/20 text-emerald-400 text-xs text-center whitespace-pre-line">
           {lastEvent}
         </div>
       )}
@@ -0,0 +1,90 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { ShieldCheck, Check, X } from 'lucide-react'
+import type { PendingApproval } from '@/lib/types'
+
+export function PendingApprovalsWidget() {
+  const [approvals, setApprovals] = useState<PendingApproval[]>([])
+  const [deciding, setDeciding] = useState<number | null>(null)
+  const [now, setNow] = useState(() => Math.floor(Date.now() / 1000))
+
+  const fetchApprovals = useCallback(async () => {
+    try {
+      setApprovals(await invoke<PendingApproval[]>('list_pending_approvals'))
+    } catch {
+      /* executor may not be ready */
+    }
+  }, [])
+
+  useEffect(() => {
+    fetchApprovals()
+    const interval = setInterval(() => setNow(Math.floor(Date.now() / 1000)), 1000)
+    const unlisten = listen('trade-approval', () => fetchApprovals())
+    return () => {
+      clearInterval(interval)
+      unlisten.then(u => u())
+    }
+  }, [fetchApprovals])
+
+  const decide = async (id: number, approve: boolean) => {
+    setDeciding(id)
+    try {
+      await invoke<PendingApproval>(approve ? 'approve_pending' : 'reject_pending', { id })
+    } catch (e) {
+      console.error('Failed to decide on trade:', e)
+    } finally {
+      setDeciding(null)
+      fetchApprovals()
+    }
+  }
+
+  if (approvals.length === 0) return null
+
+  return (
+    <div className="card border border-amber-500/30">
+      <div className="flex items-center gap-2 mb-3">
+        <ShieldCheck className="w-5 h-5 text-amber-400" />
+        <h3 className="font-semibold">Awaiting Approval</h3>
+        <span className="text-xs px-2 py-0.5 rounded-full bg-background-tertiary text-foreground-muted">
+          {approvals.length}
+        </span>
+      </div>
+
+      <div className="space-y-2">
+        {approvals.map(a => (
+          <div key={a.id} className="flex items-center gap-3 p-2.5 rounded-lg bg-background">
+            <span className={`text-xs font-bold ${a.tradeType === 'BUY' ? 'text-buy' : 'text-sell'}`}>
+              {a.tradeType}
+            </span>
+            <div className="flex-1 min-w-0">
+              <p className="text-sm font-medium truncate">
+                {a.symbol} · {a.amountUnit === 'usd' ? `$${a.amount.toFixed(2)}` : `${a.amount.toFixed(8)} coins`}
+              </p>
+              <p className="text-xs text-foreground-muted truncate" title={a.reason}>
+                {a.source} — {a.reason}
+              </p>
+            </div>
+            <span className="text-xs text-foreground-muted">{Math.max(0, a.expiresAt - now)}s</span>
+            <button
+              onClick={() => decide(a.id, true)}
+              disabled={deciding === a.id}
+              className="p-1.5 rounded-lg hover:bg-buy/20 text-foreground-muted hover:text-buy transition-colors disabled:opacity-50"
+              title="Approve and execute"
+            >
+              <Check className="w-4 h-4" />
+            </button>
+            <button
+              onClick={() => decide(a.id, false)}
+              disabled={deciding === a.id}
+              className="p-1.5 rounded-lg hover:bg-sell/20 text-foreground-muted hover:text-sell transition-colors disabled:opacity-50"
+              title="Reject"
+            >
+              <X className="w-4 h-4" />
+            </button>
+          </div>
+        ))}
+      </div>
+    </div>
+  )
+}
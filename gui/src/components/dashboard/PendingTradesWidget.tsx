@@ -0,0 +1,89 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { ListOrdered, X } from 'lucide-react'
+import type { PendingTrade } from '@/lib/types'
+
+const PRIORITY_COLORS: Record<PendingTrade['priority'], string> = {
+  Normal: 'bg-blue-500/20 text-blue-400',
+  High: 'bg-amber-500/20 text-amber-400',
+  Critical: 'bg-rose-500/20 text-rose-400',
+}
+
+export function PendingTradesWidget() {
+  const [pending, setPending] = useState<PendingTrade[]>([])
+  const [cancelling, setCancelling] = useState<number | null>(null)
+
+  const fetchPending = useCallback(async () => {
+    try {
+      setPending(await invoke<PendingTrade[]>('list_pending_trades'))
+    } catch {
+      /* executor may not be ready */
+    }
+  }, [])
+
+  useEffect(() => {
+    fetchPending()
+    const interval = setInterval(fetchPending, 2000)
+    const unlisten = listen('trade-executed', () => fetchPending())
+    return () => {
+      clearInterval(interval)
+      unlisten.then(u => u())
+    }
+  }, [fetchPending])
+
+  const cancel = async (id: number) => {
+    setCancelling(id)
+    try {
+      await invoke<PendingTrade>('cancel_pending_trade', { id })
+    } catch (e) {
+      console.error('Failed to cancel pending trade:', e)
+    } finally {
+      setCancelling(null)
+      fetchPending()
+    }
+  }
+
+  if (pending.length === 0) return null
+
+  return (
+    <div className="card">
+      <div className="flex items-center gap-2 mb-3">
+        <ListOrdered className="w-5 h-5 text-blue-400" />
+        <h3 className="font-semibold">Queued Trades</h3>
+        <span className="text-xs px-2 py-0.5 rounded-full bg-background-tertiary text-foreground-muted">
+          {pending.length}
+        </span>
+      </div>
+
+      <div className="space-y-2">
+        {pending.map(t => (
+          <div key={t.id} className="flex items-center gap-3 p-2.5 rounded-lg bg-background">
+            <span className={`text-xs font-bold ${t.tradeType === 'BUY' ? 'text-buy' : 'text-sell'}`}>
+              {t.tradeType}
+            </span>
+            <div className="flex-1 min-w-0">
+              <p className="text-sm font-medium truncate">
+                {t.symbol} · {t.amountUnit === 'usd' ? `$${t.amount.toFixed(2)}` : `${t.amount.toFixed(8)} coins`}
+              </p>
+              <p className="text-xs text-foreground-muted truncate">
+                {t.source} — {t.reason}
+              </p>
+            </div>
+            <span className={`text-xs px-2 py-0.5 rounded-full ${PRIORITY_COLORS[t.priority]}`}>
+              {t.priority}
+            </span>
+            <button
+              onClick={() => cancel(t.id)}
+              disabled={cancelling === t.id}
+              className="p-1.5 rounded-lg hover:bg-sell/20 text-foreground-muted hover:text-sell transition-colors disabled:opacity-50"
+              title="Cancel before it executes"
+            >
+              <X className="w-4 h-4" />
+            </button>
+          </div>
+        ))}
+      </div>
+    </div>
+  )
+}
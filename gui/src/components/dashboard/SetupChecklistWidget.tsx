@@ -0,0 +1,76 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { ClipboardCheck, CheckCircle, Circle } from 'lucide-react'
+import type { SetupState, SetupStep } from '@/lib/types'
+
+const STEP_LABELS: Record<SetupStep, string> = {
+  initDatabase: 'Open local database',
+  addProfile: 'Add a Rugplay profile',
+  selectProfile: 'Select a profile',
+  updateToken: 'Refresh session token',
+  configureSettings: 'Save sentinel defaults',
+  reviewRiskLimits: 'Save risk limits',
+}
+
+export function SetupChecklistWidget() {
+  const [setup, setSetup] = useState<SetupState | null>(null)
+  const [applying, setApplying] = useState(false)
+
+  const fetchSetup = useCallback(async () => {
+    try {
+      setSetup(await invoke<SetupState>('get_setup_state'))
+    } catch {
+      /* database may not be ready */
+    }
+  }, [])
+
+  useEffect(() => {
+    fetchSetup()
+  }, [fetchSetup])
+
+  const applyDefaults = async () => {
+    setApplying(true)
+    try {
+      setSetup(await invoke<SetupState>('apply_setup_defaults'))
+    } catch (e) {
+      console.error('Failed to apply setup defaults:', e)
+    } finally {
+      setApplying(false)
+    }
+  }
+
+  if (!setup || setup.complete) return null
+
+  return (
+    <div className="card border border-blue-500/30">
+      <div className="flex items-center justify-between mb-3">
+        <div className="flex items-center gap-2">
+          <ClipboardCheck className="w-5 h-5 text-blue-400" />
+          <h3 className="font-semibold">Finish Setup</h3>
+        </div>
+        <button
+          onClick={applyDefaults}
+          disabled={applying}
+          className="px-3 py-1.5 rounded text-sm bg-blue-600 hover:bg-blue-700 text-white transition-colors disabled:opacity-50"
+        >
+          {applying ? 'Applying...' : 'Use Recommended Defaults'}
+        </button>
+      </div>
+      <p className="text-xs text-foreground-muted mb-3">
+        Defaults can be changed any time in Settings.
+      </p>
+
+      <div className="space-y-1.5">
+        {setup.steps.map(s => (
+          <div key={s.step} className="flex items-center gap-2 text-sm">
+            {s.done
+              ? <CheckCircle className="w-4 h-4 text-emerald-400" />
+              : <Circle className="w-4 h-4 text-foreground-muted" />}
+            <span className={s.done ? 'text-foreground-muted line-through' : ''}>{STEP_LABELS[s.step]}</span>
+            {!s.required && !s.done && <span className="text-xs text-foreground-muted">(recommended)</span>}
+          </div>
+        ))}
+      </div>
+    </div>
+  )
+}
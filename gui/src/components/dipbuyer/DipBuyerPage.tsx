@@ -19,11 +19,13 @@ import {
   Activity,
   Settings2,
   History,
+  Layers,
   Filter,
   ChevronDown,
   ChevronRight,
 } from 'lucide-react'
 import { FormattedInput, ToggleSwitch } from '@/components/ui/FormattedInput'
+import { ConfigCheckCard } from '@/components/automation'
 import type {
   DipBuyerStatusResponse,
   DipBuyerConfig,
@@ -31,6 +33,7 @@ import type {
   DipBuyerLogEntry,
   Aggressiveness,
   CoinTier,
+  WashSensitivity,
 } from '@/lib/types'
 
 const PRESET_LABELS: Record<Aggressiveness, string> = {
@@ -73,6 +76,9 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
   const [activeTab, setActiveTab] = useState<TabId>('overview')
   const [history, setHistory] = useState<DipBuyerLogEntry[]>([])
   const [newBlacklistedCoin, setNewBlacklistedCoin] = useState('')
+  const [newExemptCoin, setNewExemptCoin] = useState('')
+  const [newSlippageCoin, setNewSlippageCoin] = useState('')
+  const [newSlippagePct, setNewSlippagePct] = useState(10)
   const [expandedEntry, setExpandedEntry] = useState<number | null>(null)
   const [resetting, setResetting] = useState(false)
   const [resetMessage, setResetMessage] = useState<string | null>(null)
@@ -147,9 +153,13 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
   const applyPreset = async (preset: Aggressiveness) => {
     try {
       const presetConfig = await invoke<DipBuyerConfig>('get_dipbuyer_preset', { preset })
-      // Preserve user's blacklisted coins
+      // Preserve user's per-coin lists and feed depth
       if (config) {
         presetConfig.blacklistedCoins = config.blacklistedCoins
+        presetConfig.cooldownExemptCoins = config.cooldownExemptCoins
+        presetConfig.coinSlippageOverrides = config.coinSlippageOverrides
+        presetConfig.recentTradesDepth = config.recentTradesDepth
+        presetConfig.washTradeSensitivity = config.washTradeSensitivity
       }
       setConfig(presetConfig)
       setHasChanges(true)
@@ -216,6 +226,35 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
     setHasChanges(true)
   }
 
+  const addExemptCoin = () => {
+    const coin = newExemptCoin.trim().toUpperCase()
+    if (coin && config && !config.cooldownExemptCoins.includes(coin)) {
+      updateConfig('cooldownExemptCoins', [...config.cooldownExemptCoins, coin])
+      setNewExemptCoin('')
+    }
+  }
+
+  const removeExemptCoin = (coin: string) => {
+    if (config) {
+      updateConfig('cooldownExemptCoins', config.cooldownExemptCoins.filter((c) => c !== coin))
+    }
+  }
+
+  const addSlippageOverride = () => {
+    const coin = newSlippageCoin.trim().toUpperCase()
+    if (coin && config && newSlippagePct > 0) {
+      updateConfig('coinSlippageOverrides', { ...config.coinSlippageOverrides, [coin]: newSlippagePct })
+      setNewSlippageCoin('')
+    }
+  }
+
+  const removeSlippageOverride = (coin: string) => {
+    if (config) {
+      const { [coin]: _removed, ...rest } = config.coinSlippageOverrides
+      updateConfig('coinSlippageOverrides', rest)
+    }
+  }
+
   if (loading || !config) {
     return (
       <div className="flex items-center justify-center h-64">
@@ -420,6 +459,8 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
               </div>
             </div>
           </div>
+
+          <ConfigCheckCard module="dipbuyer" />
         </div>
       )}
 
@@ -582,6 +623,40 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
                 </div>
               </div>
 
+              {config.scaleByConfidence && (
+                <div className="p-3 rounded-lg bg-background space-y-3">
+                  <div className="flex items-center justify-between">
+                    <div>
+                      <div className="text-sm font-medium">Stricter Sizing Curve</div>
+                      <p className="text-xs text-foreground-muted mt-0.5">Only the strongest dips get the full tier amount</p>
+                    </div>
+                    <ToggleSwitch
+                      enabled={config.confidenceCurve.enabled}
+                      onChange={(v) => updateConfig('confidenceCurve', { ...config.confidenceCurve, enabled: v })}
+                    />
+                  </div>
+                  {config.confidenceCurve.enabled && (
+                    <div className="grid grid-cols-3 gap-3">
+                      <div className="form-field">
+                        <label className="form-label">Size at Min Conf.</label>
+                        <input type="number" min={0} max={1} step={0.05} value={config.confidenceCurve.minSizeScale} onChange={(e) => updateConfig('confidenceCurve', { ...config.confidenceCurve, minSizeScale: parseFloat(e.target.value) || 0 })} className="input" />
+                        <p className="form-hint">Fraction of the tier amount</p>
+                      </div>
+                      <div className="form-field">
+                        <label className="form-label">Full Size At</label>
+                        <input type="number" min={0} max={1} step={0.05} value={config.confidenceCurve.fullSizeConfidence} onChange={(e) => updateConfig('confidenceCurve', { ...config.confidenceCurve, fullSizeConfidence: parseFloat(e.target.value) || 0 })} className="input" />
+                        <p className="form-hint">Confidence for 100%</p>
+                      </div>
+                      <div className="form-field">
+                        <label className="form-label">Curve</label>
+                        <input type="number" min={0.5} max={5} step={0.5} value={config.confidenceCurve.exponent} onChange={(e) => updateConfig('confidenceCurve', { ...config.confidenceCurve, exponent: parseFloat(e.target.value) || 1 })} className="input" />
+                        <p className="form-hint">1 = linear, higher = stricter</p>
+                      </div>
+                    </div>
+                  )}
+                </div>
+              )}
+
               {config.portfolioAware && (
                 <div className="form-field">
                   <label className="form-label">
@@ -593,6 +668,107 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
                 </div>
               )}
 
+              {config.portfolioAware && (
+                <div className="flex items-center justify-between p-3 rounded-lg bg-background">
+                  <div>
+                    <div className="text-sm font-medium">Buy If Portfolio Unavailable</div>
+                    <p className="text-xs text-foreground-muted mt-0.5">When holdings can't be fetched, buy without the position check instead of skipping</p>
+                  </div>
+                  <ToggleSwitch enabled={config.proceedWithoutPortfolio} onChange={(v) => updateConfig('proceedWithoutPortfolio', v)} />
+                </div>
+              )}
+
+              <div className="grid grid-cols-2 gap-4">
+                <div className="form-field">
+                  <label className="form-label">
+                    <TrendingDown className="w-4 h-4 text-rose-400" />
+                    Repeat Dump Decay
+                  </label>
+                  <input type="number" min={0} max={1} step={0.05} value={config.confidenceDecayPerDump} onChange={(e) => updateConfig('confidenceDecayPerDump', parseFloat(e.target.value) || 0)} className="input" />
+                  <p className="form-hint">Confidence penalty per earlier dump of the same coin. 0 = off.</p>
+                </div>
+                <div className="form-field">
+                  <label className="form-label">
+                    <Clock className="w-4 h-4 text-rose-400" />
+                    Decay Window (sec)
+                  </label>
+                  <input type="number" min={0} step={300} value={config.dumpDecayWindowSecs} onChange={(e) => updateConfig('dumpDecayWindowSecs', parseInt(e.target.value) || 0)} className="input" />
+                  <p className="form-hint">How long an earlier dump keeps counting against the coin.</p>
+                </div>
+              </div>
+
+              <div className="flex items-center justify-between p-3 rounded-lg bg-background">
+                <div>
+                  <div className="text-sm font-medium">Buy Without Holder Data</div>
+                  <p className="text-xs text-foreground-muted mt-0.5">If the holders lookup fails, score with a neutral holder signal instead of skipping</p>
+                </div>
+                <ToggleSwitch enabled={config.proceedWithoutHolders} onChange={(v) => updateConfig('proceedWithoutHolders', v)} />
+              </div>
+
+              {config.useMomentumAnalysis && (
+                <div className="form-field">
+                  <label className="form-label">
+                    <Activity className="w-4 h-4 text-amber-400" />
+                    Thin History Penalty
+                  </label>
+                  <input type="number" min={0} max={1} step={0.05} value={config.thinHistoryConfidencePenalty ?? 0} onChange={(e) => updateConfig('thinHistoryConfidencePenalty', parseFloat(e.target.value) || 0)} className="input" />
+                  <p className="form-hint">Coins with fewer than 3 candles get neutral momentum. This takes a fraction off their confidence, e.g. 0.1 = 10% lower. 0 = off.</p>
+                </div>
+              )}
+
+              {config.useMomentumAnalysis && (
+                <div className="flex items-center justify-between p-3 rounded-lg bg-background">
+                  <div>
+                    <div className="text-sm font-medium">Volatility Position Sizing</div>
+                    <p className="text-xs text-foreground-muted mt-0.5">Buy less of choppy coins and more of calm ones, based on their average true range</p>
+                  </div>
+                  <ToggleSwitch enabled={config.atrPositionSizing ?? false} onChange={(v) => updateConfig('atrPositionSizing', v)} />
+                </div>
+              )}
+
+              {config.useMomentumAnalysis && config.atrPositionSizing && config.atrSizing && (
+                <div className="grid grid-cols-3 gap-4">
+                  <div className="form-field">
+                    <label className="form-label">
+                      <Activity className="w-4 h-4 text-amber-400" />
+                      Target ATR %
+                    </label>
+                    <input type="number" min={0.1} step={1} value={config.atrSizing.targetAtrPct} onChange={(e) => updateConfig('atrSizing', { ...config.atrSizing, targetAtrPct: parseFloat(e.target.value) || 0 })} className="input" />
+                    <p className="form-hint">A coin this volatile buys at full size.</p>
+                  </div>
+                  <div className="form-field">
+                    <label className="form-label">Min Scale</label>
+                    <input type="number" min={0} max={1} step={0.05} value={config.atrSizing.minScale} onChange={(e) => updateConfig('atrSizing', { ...config.atrSizing, minScale: parseFloat(e.target.value) || 0 })} className="input" />
+                    <p className="form-hint">Smallest fraction bought on very volatile coins.</p>
+                  </div>
+                  <div className="form-field">
+                    <label className="form-label">Max Scale</label>
+                    <input type="number" min={1} step={0.1} value={config.atrSizing.maxScale} onChange={(e) => updateConfig('atrSizing', { ...config.atrSizing, maxScale: parseFloat(e.target.value) || 0 })} className="input" />
+                    <p className="form-hint">Largest multiple bought on calm coins.</p>
+                  </div>
+                </div>
+              )}
+
+              {config.proceedWithoutHolders && (
+                <div className="form-field">
+                  <label className="form-label">
+                    <Shield className="w-4 h-4 text-blue-400" />
+                    Buy Size Without Holder Data
+                  </label>
+                  <input type="number" min={0} max={1} step={0.05} value={config.withoutHoldersBuyScale} onChange={(e) => updateConfig('withoutHoldersBuyScale', parseFloat(e.target.value) || 0)} className="input" />
+                  <p className="form-hint">Multiplier on the buy amount when holder data is missing. 0.5 = half size.</p>
+                </div>
+              )}
+
+              <div className="form-field">
+                <label className="form-label">
+                  <Shield className="w-4 h-4 text-red-400" />
+                  Max Risk Score
+                </label>
+                <input type="number" min={0} max={100} step={5} value={config.maxRiskScore ?? 0} onChange={(e) => updateConfig('maxRiskScore', parseFloat(e.target.value) || 0)} className="input" />
+                <p className="form-hint">Skip coins whose risk score (holder concentration, liquidity, age, volume, volatility) is above this. 0-100, 0 = off.</p>
+              </div>
+
               <div className="p-4 rounded-lg bg-background">
                 <div className="flex items-center gap-2 mb-3">
                   <SlidersHorizontal className="w-4 h-4 text-violet-400" />
@@ -750,6 +926,24 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
                 <p className="form-hint">0 = unlimited</p>
               </div>
 
+              <div className="form-field">
+                <label className="form-label">
+                  <Clock className="w-4 h-4 text-emerald-400" />
+                  Daily Limits Reset
+                </label>
+                <select
+                  value={config.budgetResetMode.mode === 'dailyAt' ? String(config.budgetResetMode.hour) : 'rolling'}
+                  onChange={(e) => updateConfig('budgetResetMode', e.target.value === 'rolling' ? { mode: 'rolling24h' } : { mode: 'dailyAt', hour: parseInt(e.target.value) })}
+                  className="input"
+                >
+                  <option value="rolling">Rolling 24h</option>
+                  {Array.from({ length: 24 }, (_, h) => (
+                    <option key={h} value={h}>Daily at {String(h).padStart(2, '0')}:00 UTC</option>
+                  ))}
+                </select>
+                <p className="form-hint">Rolling counts each buy for 24h; daily clears the caps at a fixed hour</p>
+              </div>
+
               <div className="form-field">
                 <label className="form-label">
                   <Clock className="w-4 h-4 text-blue-400" />
@@ -767,6 +961,42 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
                 <FormattedInput value={config.pollIntervalSecs} onChange={(v) => updateConfig('pollIntervalSecs', Math.round(v))} suffix="sec" min={2} step={1} />
                 <p className="form-hint">How often to check the live trade feed</p>
               </div>
+
+              <div className="form-field">
+                <label className="form-label">
+                  <Layers className="w-4 h-4 text-blue-400" />
+                  Trade Feed Depth
+                </label>
+                <FormattedInput value={config.recentTradesDepth} onChange={(v) => updateConfig('recentTradesDepth', Math.round(v))} suffix="trades" min={0} max={500} step={10} />
+                <p className="form-hint">Trades fetched per poll (0 = 50). Grows automatically on busy markets.</p>
+              </div>
+
+              <div className="form-field">
+                <label className="form-label">
+                  <Layers className="w-4 h-4 text-blue-400" />
+                  Max Evaluations Per Tick
+                </label>
+                <FormattedInput value={config.maxEvaluationsPerTick} onChange={(v) => updateConfig('maxEvaluationsPerTick', Math.round(v))} suffix="coins" min={0} step={1} />
+                <p className="form-hint">Dips checked in depth per poll, biggest sells first; the rest wait (0 = no limit)</p>
+              </div>
+
+              <div className="form-field">
+                <label className="form-label">
+                  <Shield className="w-4 h-4 text-amber-400" />
+                  Wash Trade Filter
+                </label>
+                <select
+                  value={config.washTradeSensitivity}
+                  onChange={(e) => updateConfig('washTradeSensitivity', e.target.value as WashSensitivity)}
+                  className="input"
+                >
+                  <option value="off">Off</option>
+                  <option value="low">Low</option>
+                  <option value="medium">Medium</option>
+                  <option value="high">High</option>
+                </select>
+                <p className="form-hint">Ignore sells from users buying and selling the same coin back and forth</p>
+              </div>
             </div>
           </div>
 
@@ -806,6 +1036,87 @@ export function DipBuyerPage({ setNavGuard }: DipBuyerPageProps) {
               )}
             </div>
           </div>
+
+          <div className="card">
+            <div className="flex items-center gap-2 mb-4">
+              <Clock className="w-5 h-5 text-amber-400" />
+              <h2 className="text-lg font-semibold">Cooldown-Exempt Coins</h2>
+            </div>
+            <p className="text-sm text-foreground-muted mb-3">
+              Buy every qualifying dip on these coins without waiting out the per-coin cooldown. Daily buy and spend limits still apply.
+            </p>
+
+            <div className="flex gap-2 mb-4">
+              <input
+                type="text"
+                value={newExemptCoin}
+                onChange={(e) => setNewExemptCoin(e.target.value)}
+                placeholder="Enter coin symbol"
+                className="input flex-1"
+                onKeyDown={(e) => { if (e.key === 'Enter') addExemptCoin() }}
+              />
+              <button onClick={addExemptCoin} disabled={!newExemptCoin.trim()} className="px-4 py-2 rounded-lg bg-zinc-700 hover:bg-zinc-600 text-white transition-colors disabled:opacity-50">
+                <Plus className="w-4 h-4" />
+              </button>
+            </div>
+
+            <div className="flex flex-wrap gap-2">
+              {config.cooldownExemptCoins.length === 0 ? (
+                <span className="text-sm text-foreground-muted">Every coin uses the cooldown</span>
+              ) : (
+                config.cooldownExemptCoins.map((coin) => (
+                  <span key={coin} className="inline-flex items-center gap-1 px-3 py-1 rounded-full bg-amber-500/20 text-amber-400 text-sm">
+                    ${coin}
+                    <button onClick={() => removeExemptCoin(coin)} className="p-0.5 rounded hover:bg-amber-500/30 transition-colors">
+                      <X className="w-3 h-3" />
+                    </button>
+                  </span>
+                ))
+              )}
+            </div>
+          </div>
+
+          <div className="card">
+            <div className="flex items-center gap-2 mb-4">
+              <Activity className="w-5 h-5 text-amber-400" />
+              <h2 className="text-lg font-semibold">Per-Coin Slippage</h2>
+            </div>
+            <p className="text-sm text-foreground-muted mb-3">
+              Max buy slippage for specific coins. Takes precedence over the tier and global values.
+            </p>
+
+            <div className="flex gap-2 mb-4">
+              <input
+                type="text"
+                value={newSlippageCoin}
+                onChange={(e) => setNewSlippageCoin(e.target.value)}
+                placeholder="Enter coin symbol"
+                className="input flex-1"
+                onKeyDown={(e) => { if (e.key === 'Enter') addSlippageOverride() }}
+              />
+              <div className="w-28">
+                <FormattedInput value={newSlippagePct} onChange={setNewSlippagePct} suffix="%" min={0.5} step={0.5} />
+              </div>
+              <button onClick={addSlippageOverride} disabled={!newSlippageCoin.trim() || newSlippagePct <= 0} className="px-4 py-2 rounded-lg bg-zinc-700 hover:bg-zinc-600 text-white transition-colors disabled:opacity-50">
+                <Plus className="w-4 h-4" />
+              </button>
+            </div>
+
+            <div className="flex flex-wrap gap-2">
+              {Object.keys(config.coinSlippageOverrides).length === 0 ? (
+                <span className="text-sm text-foreground-muted">Every coin uses its tier or the global cap</span>
+              ) : (
+                Object.entries(config.coinSlippageOverrides).map(([coin, pct]) => (
+                  <span key={coin} className="inline-flex items-center gap-1 px-3 py-1 rounded-full bg-amber-500/20 text-amber-400 text-sm">
+                    ${coin} · {pct}%
+                    <button onClick={() => removeSlippageOverride(coin)} className="p-0.5 rounded hover:bg-amber-500/30 transition-colors">
+                      <X className="w-3 h-3" />
+                    </button>
+                  </span>
+                ))
+              )}
+            </div>
+          </div>
         </div>
       )}
 
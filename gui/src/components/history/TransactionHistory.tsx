@@ -11,8 +11,10 @@ import {
   RefreshCw,
   FileText,
   ArrowLeftRight,
+  Download,
+  ShieldCheck,
 } from 'lucide-react'
-import type { TransactionRecord, TransactionListResponse } from '@/lib/types'
+import type { TransactionRecord, Page, ReconciliationReport } from '@/lib/types'
 
 type TradeTypeFilter = 'all' | 'BUY' | 'SELL'
 
@@ -32,6 +34,10 @@ export function TransactionHistory() {
   const [perPage] = useState(25)
   const [total, setTotal] = useState(0)
 
+  // Journal reconciliation
+  const [reconciliation, setReconciliation] = useState<ReconciliationReport | null>(null)
+  const [reconciling, setReconciling] = useState(false)
+
   const fetchSymbols = useCallback(async () => {
     try {
       const result = await invoke<string[]>('get_traded_symbols')
@@ -66,9 +72,9 @@ export function TransactionHistory() {
       }
 
       console.log('Fetching transactions with params:', params)
-      const result = await invoke<TransactionListResponse>('get_transactions', params)
+      const result = await invoke<Page<TransactionRecord>>('get_transactions', params)
       console.log('Transactions result:', result)
-      setTransactions(result.transactions)
+      setTransactions(result.items)
       setTotal(result.total)
     } catch (err) {
       console.error('Failed to fetch transactions:', err)
@@ -83,6 +89,23 @@ export function TransactionHistory() {
     fetchSymbols()
   }, [fetchSymbols])
 
+  useEffect(() => {
+    invoke<ReconciliationReport | null>('get_journal_reconciliation')
+      .then(setReconciliation)
+      .catch((err) => console.error('Failed to load reconciliation report:', err))
+  }, [])
+
+  const reconcileJournal = async () => {
+    setReconciling(true)
+    try {
+      setReconciliation(await invoke<ReconciliationReport>('reconcile_trade_journal'))
+    } catch (err) {
+      console.error('Failed to reconcile journal:', err)
+    } finally {
+      setReconciling(false)
+    }
+  }
+
   useEffect(() => {
     fetchTransactions()
   }, [fetchTransactions])
@@ -98,10 +121,28 @@ export function TransactionHistory() {
   const filteredTransactions = searchQuery
     ? transactions.filter((tx) =>
         tx.symbol.toLowerCase().includes(searchQuery.toLowerCase()) ||
-        tx.coinName.toLowerCase().includes(searchQuery.toLowerCase())
+        tx.coinName.toLowerCase().includes(searchQuery.toLowerCase()) ||
+        (tx.note?.toLowerCase().includes(searchQuery.toLowerCase()) ?? false)
       )
     : transactions
 
+  const exportJournal = async () => {
+    try {
+      const csv = await invoke<string>('export_trade_journal', {
+        tradeType: tradeTypeFilter !== 'all' ? tradeTypeFilter : null,
+        symbol: symbolFilter !== 'all' ? symbolFilter : null,
+      })
+      const url = URL.createObjectURL(new Blob([csv], { type: 'text/csv' }))
+      const link = document.createElement('a')
+      link.href = url
+      link.download = `trade-journal-${new Date().toISOString().slice(0, 10)}.csv`
+      link.click()
+      URL.revokeObjectURL(url)
+    } catch (e) {
+      console.error('Failed to export trade journal:', e)
+    }
+  }
+
   const formatDate = (timestamp: string) => {
     const date = new Date(timestamp)
     return date.toLocaleDateString('en-US', {
@@ -167,16 +208,63 @@ export function TransactionHistory() {
             {total} total transactions
           </p>
         </div>
-        <button
-          onClick={fetchTransactions}
-          disabled={loading}
-          className="flex items-center gap-2 px-4 py-2 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg transition-colors disabled:opacity-50"
-        >
-          <RefreshCw className={`w-4 h-4 ${loading ? 'animate-spin' : ''}`} />
-          Refresh
-        </button>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={reconcileJournal}
+            disabled={reconciling}
+            className="flex items-center gap-2 px-4 py-2 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg transition-colors disabled:opacity-50"
+            title="Compare the local journal with the server's trade history and add trades it is missing"
+          >
+            <ShieldCheck className={`w-4 h-4 ${reconciling ? 'animate-pulse' : ''}`} />
+            Reconcile
+          </button>
+          <button
+            onClick={exportJournal}
+            className="flex items-center gap-2 px-4 py-2 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg transition-colors"
+            title="Download trades recorded by this app, with notes, as CSV"
+          >
+            <Download className="w-4 h-4" />
+            Export Journal
+          </button>
+          <button
+            onClick={fetchTransactions}
+            disabled={loading}
+            className="flex items-center gap-2 px-4 py-2 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg transition-colors disabled:opacity-50"
+          >
+            <RefreshCw className={`w-4 h-4 ${loading ? 'animate-spin' : ''}`} />
+            Refresh
+          </button>
+        </div>
       </div>
 
+      {/* Journal reconciliation */}
+      {reconciliation && (
+        <div className="bg-zinc-900 border border-zinc-800 rounded-lg p-4 text-sm">
+          <div className="flex items-center justify-between">
+            <div className="flex items-center gap-2 text-zinc-300">
+              <ShieldCheck className={`w-4 h-4 ${reconciliation.missingOnServer.length > 0 ? 'text-amber-400' : 'text-emerald-400'}`} />
+              Journal check: {reconciliation.matched} matched
+              {reconciliation.missingLocally.length > 0 && (
+                <>, {reconciliation.missingLocally.length} missing locally ({reconciliation.repaired} added)</>
+              )}
+              {reconciliation.missingOnServer.length > 0 && (
+                <>, {reconciliation.missingOnServer.length} not on the server</>
+              )}
+            </div>
+            <span className="text-zinc-500 text-xs">{formatDate(reconciliation.checkedAt)}</span>
+          </div>
+          {reconciliation.missingOnServer.length > 0 && (
+            <ul className="mt-2 space-y-1 text-xs text-zinc-400">
+              {reconciliation.missingOnServer.slice(0, 5).map((d) => (
+                <li key={d.localId ?? d.timestamp}>
+                  {d.tradeType} {d.symbol} ${formatNumber(d.usdValue)} at {formatDate(d.timestamp)}
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+      )}
+
       {/* Summary Cards */}
       <div className="grid grid-cols-4 gap-4">
         <div className="bg-zinc-900 border border-zinc-800 rounded-lg p-4">
@@ -352,6 +440,11 @@ export function TransactionHistory() {
                         <div>
                           <span className="font-mono text-white">${tx.symbol}</span>
                           <div className="text-xs text-zinc-500">{tx.coinName}</div>
+                          {tx.note && (
+                            <div className="text-xs text-zinc-400 italic truncate max-w-xs" title={tx.note}>
+                              {tx.note}
+                            </div>
+                          )}
                         </div>
                       </div>
                     </td>
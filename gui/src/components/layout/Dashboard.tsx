@@ -18,6 +18,7 @@ import { MobileAccessPage } from '@/components/mobile/MobileAccessPage'
 import { DashboardHome } from '@/components/dashboard/DashboardHome'
 import { UserProfilePage } from '@/components/user'
 import { LeaderboardPage } from '@/components/leaderboard'
+import { WatchlistPage } from '@/components/watchlist'
 import type { UserProfile, PortfolioResponse } from '@/lib/types'
 
 const AboutPage = lazy(() => import('@/components/about/AboutPage').then(m => ({ default: m.AboutPage })))
@@ -189,6 +190,10 @@ export function Dashboard({ user, onLogout }: DashboardProps) {
               {activeNav === 'history' && (
                 <TransactionHistory />
               )}
+
+              {activeNav === 'watchlist' && (
+                <WatchlistPage onCoinClick={handleCoinClick} />
+              )}
               
               {activeNav === 'settings' && (
                 <SettingsLayout setNavGuard={setNavGuard} />
@@ -1,6 +1,7 @@
 import { useState, useEffect, useRef, useCallback } from 'react'
 import { Bell, X, Shield, Crosshair, Banknote, AlertTriangle, ArrowRightLeft, Trash2 } from 'lucide-react'
 import { listen } from '@tauri-apps/api/event'
+import { formatFillPrice } from '@/lib/utils'
 
 export interface InboxNotification {
   id: number
@@ -51,7 +52,7 @@ export function NotificationInbox() {
       'sentinel-triggered',
       (event) => {
         const p = event.payload
-        const typeLabel = p.triggerType === 'stop_loss' ? 'Stop Loss' : p.triggerType === 'take_profit' ? 'Take Profit' : 'Trailing Stop'
+        const typeLabel = p.triggerType === 'stop_loss' ? 'Stop Loss' : p.triggerType === 'take_profit' ? 'Take Profit' : p.triggerType === 'liquidity_drain' ? 'Liquidity Drain' : 'Trailing Stop'
         addNotification('sentinel', `${typeLabel} — $${p.symbol}`, p.reason)
       }
     ).then(u => unlisteners.push(u))
@@ -72,12 +73,12 @@ export function NotificationInbox() {
       }
     ).then(u => unlisteners.push(u))
 
-    listen<{ tradeType: string; symbol: string; amount: number; newPrice: number; success: boolean; error?: string }>(
+    listen<{ tradeType: string; symbol: string; amount: number; newPrice: number | null; success: boolean; error?: string }>(
       'trade-executed',
       (event) => {
         const p = event.payload
         if (p.success) {
-          addNotification('trade', `${p.tradeType} $${p.symbol}`, `$${p.amount.toFixed(2)} @ $${p.newPrice.toFixed(8)}`)
+          addNotification('trade', `${p.tradeType} $${p.symbol}`, `$${p.amount.toFixed(2)} @ ${formatFillPrice(p.newPrice)}`)
         } else {
           addNotification('trade', `Trade Failed — $${p.symbol}`, p.error || 'Unknown error')
         }
@@ -16,9 +16,10 @@ import {
   ScrollText,
   Trophy,
   Info,
+  Eye,
 } from 'lucide-react'
 
-export type NavItemId = 'dashboard' | 'portfolio' | 'market' | 'feed' | 'history' | 'leaderboard' | 'sentinel' | 'sniper' | 'mirror' | 'dipbuyer' | 'automation' | 'mobile' | 'settings' | 'about'
+export type NavItemId = 'dashboard' | 'portfolio' | 'watchlist' | 'market' | 'feed' | 'history' | 'leaderboard' | 'sentinel' | 'sniper' | 'mirror' | 'dipbuyer' | 'automation' | 'mobile' | 'settings' | 'about'
 
 interface NavItem {
   id: NavItemId
@@ -30,6 +31,7 @@ interface NavItem {
 const navItems: NavItem[] = [
   { id: 'dashboard', label: 'Dashboard', icon: <LayoutDashboard className="w-5 h-5" /> },
   { id: 'portfolio', label: 'Portfolio', icon: <Wallet className="w-5 h-5" /> },
+  { id: 'watchlist', label: 'Watchlist', icon: <Eye className="w-5 h-5" /> },
   { id: 'market', label: 'Market', icon: <Store className="w-5 h-5" /> },
   { id: 'feed', label: 'Live Feed', icon: <Activity className="w-5 h-5" /> },
   { id: 'history', label: 'History', icon: <FileText className="w-5 h-5" /> },
@@ -14,6 +14,7 @@ import {
   Loader2,
 } from 'lucide-react'
 import { FormattedInput, ToggleSwitch } from '@/components/ui/FormattedInput'
+import type { WashSensitivity, Page } from '@/lib/types'
 
 // Backend response types matching Rust structs
 
@@ -27,6 +28,11 @@ interface MirrorConfig {
   trailingStopPct: number | null
   skipIfAlreadyHeld: boolean
   pollIntervalSecs: number
+  recentTradesDepth: number
+  maxTrackedWhales: number
+  washTradeSensitivity: WashSensitivity
+  maxRiskScore: number
+  minWhaleScore: number
 }
 
 interface MirrorStatusResponse {
@@ -40,8 +46,40 @@ interface MirrorStatusResponse {
 interface TrackedWhaleResponse {
   userId: string
   username: string
-  performanceScore: number
+  performanceScore: number | null
   trackedSince: string
+  watched: boolean
+  maxLatencySecs: number | null
+  score: WhaleScore | null
+  scoredAt: string | null
+}
+
+type WhaleScore =
+  | {
+      kind: 'scored'
+      score: number
+      winRate: number
+      avgPnlPct: number
+      roundTrips: number
+      tradesAnalyzed: number
+    }
+  | { kind: 'insufficientData'; roundTrips: number; tradesAnalyzed: number; minRoundTrips: number }
+
+type WhaleAnalysis = WhaleScore & {
+  userId: string
+  username: string
+  portfolioValue: number
+  totalVolume: number
+  tracked: boolean
+  analyzedAt: string
+}
+
+function describeScore(score: WhaleScore | null): string {
+  if (!score) return 'Not scored yet'
+  if (score.kind === 'insufficientData') {
+    return `Insufficient data (${score.roundTrips}/${score.minRoundTrips} round trips)`
+  }
+  return `Score ${score.score.toFixed(0)} · ${(score.winRate * 100).toFixed(0)}% wins · ${score.avgPnlPct >= 0 ? '+' : ''}${score.avgPnlPct.toFixed(1)}% avg over ${score.roundTrips} trips`
 }
 
 interface WhaleProfileResponse {
@@ -91,11 +129,18 @@ export function MirrorPage() {
     trailingStopPct: 15,
     skipIfAlreadyHeld: true,
     pollIntervalSecs: 0,
+    recentTradesDepth: 0,
+    maxTrackedWhales: 0,
+    washTradeSensitivity: 'off',
+    maxRiskScore: 0,
+    minWhaleScore: 0,
   })
   const [searchQuery, setSearchQuery] = useState('')
   const [searchResult, setSearchResult] = useState<WhaleProfileResponse | null>(null)
   const [searching, setSearching] = useState(false)
   const [searchError, setSearchError] = useState<string | null>(null)
+  const [analysis, setAnalysis] = useState<WhaleAnalysis | null>(null)
+  const [analyzing, setAnalyzing] = useState<string | null>(null)
   const [mirrorLog, setMirrorLog] = useState<MirrorTradeRecord[]>([])
   const [totalMirrored, setTotalMirrored] = useState(0)
 
@@ -151,8 +196,8 @@ export function MirrorPage() {
 
   const loadMirrorTrades = async () => {
     try {
-      const trades = await invoke<MirrorTradeRecord[]>('get_mirror_trades')
-      setMirrorLog(trades)
+      const trades = await invoke<Page<MirrorTradeRecord>>('get_mirror_trades', { limit: 50 })
+      setMirrorLog(trades.items)
     } catch (e) {
       console.debug('Failed to load mirror trades:', e)
     }
@@ -175,6 +220,7 @@ export function MirrorPage() {
     setSearching(true)
     setSearchError(null)
     setSearchResult(null)
+    setAnalysis(null)
     try {
       const profile = await invoke<WhaleProfileResponse>('get_whale_profile', {
         userId: searchQuery.trim(),
@@ -208,6 +254,32 @@ export function MirrorPage() {
     }
   }
 
+  const analyzeWhale = async (userId: string) => {
+    setAnalyzing(userId)
+    try {
+      const result = await invoke<WhaleAnalysis>('analyze_whale', { userId })
+      setAnalysis(result)
+      if (result.tracked) await loadTrackedWhales()
+    } catch (e) {
+      console.error('Failed to analyze whale:', e)
+    } finally {
+      setAnalyzing(null)
+    }
+  }
+
+  const setWhaleLatency = async (userId: string, value: string) => {
+    const parsed = parseFloat(value)
+    const maxLatencySecs = value.trim() === '' || !(parsed > 0) ? null : parsed
+    try {
+      await invoke('set_whale_max_latency', { userId, maxLatencySecs })
+      setWhales((prev) =>
+        prev.map((w) => (w.userId === userId ? { ...w, maxLatencySecs } : w))
+      )
+    } catch (e) {
+      console.error('Failed to set whale latency:', e)
+    }
+  }
+
   const updateConfig = async (updates: Partial<MirrorConfig>) => {
     const newConfig = { ...config, ...updates }
     setConfig(newConfig)
@@ -355,16 +427,31 @@ export function MirrorPage() {
                 <div className="text-xs text-foreground-muted">
                   ${searchResult.balance?.toLocaleString()} balance · {searchResult.holdingsCount} holdings · ${searchResult.portfolioValue?.toLocaleString()} portfolio
                 </div>
+                {analysis?.userId === searchResult.userId && (
+                  <div className={`text-xs ${analysis.kind === 'scored' ? 'text-cyan-400' : 'text-amber-400'}`}>
+                    {describeScore(analysis)}
+                  </div>
+                )}
               </div>
             </div>
-            <button
-              onClick={() => addWhale(searchResult.userId, searchResult.username)}
-              disabled={whales.some((w) => w.userId === searchResult.userId)}
-              className="px-3 py-1.5 bg-cyan-600 hover:bg-cyan-500 disabled:opacity-50 rounded-lg text-sm font-medium transition-colors flex items-center gap-1"
-            >
-              <Plus className="w-4 h-4" />
-              {whales.some((w) => w.userId === searchResult.userId) ? 'Tracked' : 'Track'}
-            </button>
+            <div className="flex items-center gap-2">
+              <button
+                onClick={() => analyzeWhale(searchResult.userId)}
+                disabled={analyzing === searchResult.userId}
+                className="px-3 py-1.5 bg-background-tertiary hover:bg-background-secondary disabled:opacity-50 rounded-lg text-sm font-medium transition-colors flex items-center gap-1"
+              >
+                {analyzing === searchResult.userId ? <Loader2 className="w-4 h-4 animate-spin" /> : <Activity className="w-4 h-4" />}
+                Analyze
+              </button>
+              <button
+                onClick={() => addWhale(searchResult.userId, searchResult.username)}
+                disabled={whales.some((w) => w.userId === searchResult.userId)}
+                className="px-3 py-1.5 bg-cyan-600 hover:bg-cyan-500 disabled:opacity-50 rounded-lg text-sm font-medium transition-colors flex items-center gap-1"
+              >
+                <Plus className="w-4 h-4" />
+                {whales.some((w) => w.userId === searchResult.userId) ? 'Tracked' : 'Track'}
+              </button>
+            </div>
           </div>
         )}
       </div>
@@ -396,16 +483,40 @@ export function MirrorPage() {
                     <div className="font-medium text-sm">{whale.username}</div>
                     <div className="text-xs text-foreground-muted">
                       Tracked since {new Date(whale.trackedSince).toLocaleDateString()}
-                      {whale.performanceScore > 0 && ` · Score: ${whale.performanceScore.toFixed(1)}`}
+                      <span className={whale.score?.kind === 'insufficientData' ? 'text-amber-400' : undefined}>
+                        {' · '}{describeScore(whale.score)}
+                      </span>
+                      {!whale.watched && (
+                        <span className="text-amber-400"> · Not watched (over whale limit)</span>
+                      )}
                     </div>
                   </div>
                 </div>
-                <button
-                  onClick={() => removeWhale(whale.userId)}
-                  className="p-1.5 rounded-md text-foreground-muted hover:text-rose-400 hover:bg-rose-500/10 transition-colors"
-                >
-                  <Trash2 className="w-4 h-4" />
-                </button>
+                <div className="flex items-center gap-2">
+                  <input
+                    type="number"
+                    min={1}
+                    defaultValue={whale.maxLatencySecs ?? ''}
+                    onBlur={(e) => setWhaleLatency(whale.userId, e.target.value)}
+                    placeholder={`${config.maxLatencySecs}s`}
+                    title="Max trade age to copy (seconds), blank uses the global setting"
+                    className="input w-20 text-xs"
+                  />
+                  <button
+                    onClick={() => analyzeWhale(whale.userId)}
+                    disabled={analyzing === whale.userId}
+                    title="Rescore from recent trades"
+                    className="p-1.5 rounded-md text-foreground-muted hover:text-cyan-400 hover:bg-cyan-500/10 transition-colors disabled:opacity-50"
+                  >
+                    {analyzing === whale.userId ? <Loader2 className="w-4 h-4 animate-spin" /> : <Activity className="w-4 h-4" />}
+                  </button>
+                  <button
+                    onClick={() => removeWhale(whale.userId)}
+                    className="p-1.5 rounded-md text-foreground-muted hover:text-rose-400 hover:bg-rose-500/10 transition-colors"
+                  >
+                    <Trash2 className="w-4 h-4" />
+                  </button>
+                </div>
               </div>
             ))}
           </div>
@@ -25,6 +25,7 @@ import {
   Unplug,
   X,
   ChevronDown,
+  KeyRound,
 } from 'lucide-react'
 
 type SessionRole = 'viewer' | 'trusted' | 'admin'
@@ -57,6 +58,22 @@ interface MobileConnectionEvent {
   totalSessions: number
 }
 
+interface RolePermissions {
+  viewPortfolio: boolean
+  viewActivity: boolean
+  toggleModules: boolean
+  executeTrades: boolean
+}
+
+type PermissionMatrix = Record<SessionRole, RolePermissions>
+
+const CAPABILITIES: { key: keyof RolePermissions; label: string; desc: string }[] = [
+  { key: 'viewPortfolio', label: 'View Portfolio', desc: 'Holdings, balance, and the live trade feed' },
+  { key: 'viewActivity', label: 'View Activity', desc: 'Sentinels, module settings, and the activity log' },
+  { key: 'toggleModules', label: 'Toggle Modules', desc: 'Turn sentinel, sniper, mirror, and dip buyer on or off' },
+  { key: 'executeTrades', label: 'Execute Trades', desc: 'Submit buy and sell orders' },
+]
+
 const ROLE_LABELS: Record<SessionRole, string> = {
   viewer: 'Viewer',
   trusted: 'Trusted',
@@ -78,6 +95,7 @@ export function MobileAccessPage() {
   const [showPin, setShowPin] = useState(false)
   const [acknowledged, setAcknowledged] = useState(false)
   const [connectionToast, setConnectionToast] = useState<string | null>(null)
+  const [permissions, setPermissions] = useState<PermissionMatrix | null>(null)
 
   const fetchStatus = useCallback(async () => {
     try {
@@ -88,6 +106,12 @@ export function MobileAccessPage() {
     }
   }, [])
 
+  useEffect(() => {
+    invoke<PermissionMatrix>('get_mobile_permissions')
+      .then(setPermissions)
+      .catch((e) => console.error('Failed to load mobile permissions:', e))
+  }, [])
+
   useEffect(() => {
     fetchStatus()
     const interval = setInterval(fetchStatus, 3000)
@@ -179,6 +203,20 @@ export function MobileAccessPage() {
     }
   }
 
+  const handleTogglePermission = async (role: SessionRole, key: keyof RolePermissions) => {
+    if (!permissions) return
+    const next: PermissionMatrix = {
+      ...permissions,
+      [role]: { ...permissions[role], [key]: !permissions[role][key] },
+    }
+    try {
+      const saved = await invoke<PermissionMatrix>('set_mobile_permissions', { permissions: next })
+      setPermissions(saved)
+    } catch (e: any) {
+      setError(e?.toString() || 'Failed to save permissions')
+    }
+  }
+
   const copyUrl = async () => {
     if (!status?.url) return
     await navigator.clipboard.writeText(status.url)
@@ -551,6 +589,56 @@ export function MobileAccessPage() {
         </div>
       )}
 
+      {/* ═══ ROLE PERMISSIONS ═══ */}
+      {permissions && (
+        <div className="card">
+          <div className="flex items-center gap-2 mb-1">
+            <KeyRound className="w-5 h-5 text-purple-400" />
+            <h2 className="text-lg font-semibold">Role Permissions</h2>
+          </div>
+          <p className="text-xs text-foreground-muted mb-4">
+            Choose what each role can do from a connected device. Changes apply to open sessions immediately.
+          </p>
+
+          <div className="overflow-x-auto">
+            <table className="w-full text-sm">
+              <thead>
+                <tr className="text-left text-foreground-muted">
+                  <th className="font-medium pb-2">Capability</th>
+                  {(Object.keys(ROLE_LABELS) as SessionRole[]).map((role) => (
+                    <th key={role} className="font-medium pb-2 text-center">
+                      <span className={`text-xs px-2 py-0.5 rounded-full ${ROLE_COLORS[role]}`}>
+                        {ROLE_LABELS[role]}
+                      </span>
+                    </th>
+                  ))}
+                </tr>
+              </thead>
+              <tbody>
+                {CAPABILITIES.map((cap) => (
+                  <tr key={cap.key} className="border-t border-background-tertiary">
+                    <td className="py-2.5">
+                      <p className="font-medium">{cap.label}</p>
+                      <p className="text-xs text-foreground-muted">{cap.desc}</p>
+                    </td>
+                    {(Object.keys(ROLE_LABELS) as SessionRole[]).map((role) => (
+                      <td key={role} className="py-2.5 text-center">
+                        <input
+                          type="checkbox"
+                          checked={permissions[role][cap.key]}
+                          onChange={() => handleTogglePermission(role, cap.key)}
+                          className="w-4 h-4 accent-emerald-500 cursor-pointer"
+                        />
+                      </td>
+                    ))}
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        </div>
+      )}
+
       {/* ═══ SECURITY INFORMATION ═══ */}
       <div className="card border border-amber-500/15">
         <div className="flex items-center gap-2 mb-5">
@@ -576,7 +664,7 @@ export function MobileAccessPage() {
                 </>,
                 'A unique 6-digit PIN is required — changes every restart',
                 'Server stops automatically when the app closes',
-                'Role-based access: Viewer (read-only), Trusted, Admin',
+                "Role-based access: each role's capabilities are configurable above",
               ].map((item, i) => (
                 <li key={i} className="flex items-start gap-2">
                   <span className="text-blue-400 mt-0.5 shrink-0">•</span>
@@ -3,7 +3,8 @@ import { invoke } from '@tauri-apps/api/core'
 import { RefreshCw, TrendingUp, TrendingDown, Wallet, DollarSign, PieChart } from 'lucide-react'
 import { HoldingsTable } from './HoldingsTable'
 import { CoinDetailsModal } from './CoinDetailsModal'
-import type { PortfolioResponse, PortfolioSummary, CoinHolding, SentinelConfig } from '@/lib/types'
+import { RebalancePanel } from './RebalancePanel'
+import type { PortfolioResponse, PortfolioSummary, CoinHolding, SentinelConfig, Page } from '@/lib/types'
 
 interface PortfolioViewProps {
   onCoinClick?: (symbol: string) => void
@@ -21,8 +22,8 @@ export function PortfolioView({ onCoinClick, onSentinelClick }: PortfolioViewPro
 
   const fetchSentinels = useCallback(async () => {
     try {
-      const data = await invoke<SentinelConfig[]>('list_sentinels')
-      setSentinels(data)
+      const data = await invoke<Page<SentinelConfig>>('list_sentinels')
+      setSentinels(data.items)
     } catch {
       // Sentinels may not be available
     }
@@ -114,6 +115,11 @@ export function PortfolioView({ onCoinClick, onSentinelClick }: PortfolioViewPro
             <div className="min-w-0">
               <p className="text-xs lg:text-sm text-foreground-muted">Cash Balance</p>
               <p className="text-base lg:text-xl font-bold truncate">${formatNumber(summary?.balance ?? 0)}</p>
+              {(summary?.frozenBalance ?? 0) > 0 && (
+                <p className="text-xs text-foreground-muted truncate">
+                  ${formatNumber(summary?.availableBalance ?? 0)} available · ${formatNumber(summary?.frozenBalance ?? 0)} frozen
+                </p>
+              )}
             </div>
           </div>
         </div>
@@ -220,6 +226,15 @@ export function PortfolioView({ onCoinClick, onSentinelClick }: PortfolioViewPro
         )}
       </div>
 
+      {/* Rebalance Section */}
+      {portfolio && portfolio.coinHoldings.length > 0 && (
+        <RebalancePanel
+          holdings={portfolio.coinHoldings}
+          totalValue={portfolio.totalValue}
+          onExecuted={fetchPortfolio}
+        />
+      )}
+
       {/* Coin Details Modal - only shown when no external navigation handler */}
       {!onCoinClick && (
         <CoinDetailsModal
@@ -0,0 +1,184 @@
+import { useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Scale, Play, Eye, Plus, X } from 'lucide-react'
+import type { CoinHolding, RebalanceOptions, RebalanceResult } from '@/lib/types'
+import { formatUsd } from '@/lib/utils'
+
+interface RebalancePanelProps {
+  holdings: CoinHolding[]
+  totalValue: number
+  onExecuted: () => void
+}
+
+export function RebalancePanel({ holdings, totalValue, onExecuted }: RebalancePanelProps) {
+  const [targets, setTargets] = useState<Record<string, number>>({})
+  const [newSymbol, setNewSymbol] = useState('')
+  const [options, setOptions] = useState<RebalanceOptions>({ minTradeUsd: 1, maxSlippagePct: 5 })
+  const [result, setResult] = useState<RebalanceResult | null>(null)
+  const [running, setRunning] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  const currentPct = (symbol: string) => {
+    const holding = holdings.find(h => h.symbol === symbol)
+    return holding && totalValue > 0 ? (holding.value / totalValue) * 100 : 0
+  }
+
+  const setTarget = (symbol: string, pct: number) => {
+    setTargets(prev => ({ ...prev, [symbol]: pct }))
+    setResult(null)
+  }
+
+  const removeTarget = (symbol: string) => {
+    setTargets(prev => {
+      const next = { ...prev }
+      delete next[symbol]
+      return next
+    })
+    setResult(null)
+  }
+
+  const addSymbol = () => {
+    const symbol = newSymbol.trim().toUpperCase()
+    if (symbol && !(symbol in targets)) setTarget(symbol, 0)
+    setNewSymbol('')
+  }
+
+  const run = async (dryRun: boolean) => {
+    setRunning(true)
+    setError(null)
+    try {
+      const res = await invoke<RebalanceResult>('rebalance_to_targets', { targets, dryRun, options })
+      setResult(res)
+      if (!dryRun) onExecuted()
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setRunning(false)
+    }
+  }
+
+  const allocated = Object.values(targets).reduce((sum, p) => sum + p, 0)
+  const symbols = Object.keys(targets).sort()
+
+  return (
+    <div className="card">
+      <div className="flex items-center gap-2 mb-4">
+        <Scale className="w-5 h-5 text-violet-400" />
+        <h2 className="text-xl font-bold">Rebalance</h2>
+        <span className="text-xs text-foreground-muted ml-auto">
+          {allocated.toFixed(1)}% allocated · {(100 - allocated).toFixed(1)}% cash
+        </span>
+      </div>
+      <p className="text-sm text-foreground-muted mb-4">
+        Set a target share of total value per coin. Coins without a target are left alone; 0% sells the whole position.
+      </p>
+
+      <div className="flex flex-wrap gap-2 mb-3">
+        {holdings.filter(h => !(h.symbol in targets)).map(h => (
+          <button
+            key={h.symbol}
+            onClick={() => setTarget(h.symbol, Math.round(currentPct(h.symbol) * 10) / 10)}
+            className="text-xs px-2 py-1 rounded bg-background hover:bg-background-tertiary"
+          >
+            + {h.symbol}
+          </button>
+        ))}
+        <div className="flex items-center gap-1">
+          <input
+            value={newSymbol}
+            onChange={e => setNewSymbol(e.target.value)}
+            onKeyDown={e => e.key === 'Enter' && addSymbol()}
+            placeholder="Other coin"
+            className="input text-xs w-28"
+          />
+          <button onClick={addSymbol} className="p-1 rounded hover:bg-background-tertiary" title="Add coin">
+            <Plus className="w-4 h-4" />
+          </button>
+        </div>
+      </div>
+
+      {symbols.length > 0 && (
+        <div className="space-y-2 mb-4">
+          {symbols.map(symbol => (
+            <div key={symbol} className="flex items-center gap-3 p-2 rounded-lg bg-background">
+              <span className="font-medium w-20">{symbol}</span>
+              <span className="text-xs text-foreground-muted w-24">now {currentPct(symbol).toFixed(1)}%</span>
+              <input
+                type="number"
+                min={0}
+                max={100}
+                step={0.5}
+                value={targets[symbol]}
+                onChange={e => setTarget(symbol, parseFloat(e.target.value) || 0)}
+                className="input w-24"
+              />
+              <span className="text-xs text-foreground-muted">%</span>
+              <button onClick={() => removeTarget(symbol)} className="ml-auto p-1 rounded hover:bg-background-tertiary" title="Remove target">
+                <X className="w-4 h-4" />
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+
+      <div className="grid grid-cols-2 gap-3 mb-4">
+        <div className="form-field">
+          <label className="form-label">Min Trade (USD)</label>
+          <input type="number" min={0} step={1} value={options.minTradeUsd} onChange={e => setOptions(o => ({ ...o, minTradeUsd: parseFloat(e.target.value) || 0 }))} className="input" />
+        </div>
+        <div className="form-field">
+          <label className="form-label">Max Slippage per Order (%)</label>
+          <input type="number" min={0} step={0.5} value={options.maxSlippagePct} onChange={e => setOptions(o => ({ ...o, maxSlippagePct: parseFloat(e.target.value) || 0 }))} className="input" />
+          <p className="form-hint">Larger orders are shrunk to fit. 0 = no cap.</p>
+        </div>
+      </div>
+
+      <div className="flex gap-2">
+        <button onClick={() => run(true)} disabled={running || symbols.length === 0} className="btn btn-ghost flex items-center gap-2">
+          <Eye className="w-4 h-4" /> Preview
+        </button>
+        <button
+          onClick={() => run(false)}
+          disabled={running || !result?.dryRun || result.plan.orders.length === 0}
+          className="btn btn-primary flex items-center gap-2"
+          title="Preview first, then execute the previewed targets"
+        >
+          <Play className="w-4 h-4" /> Execute
+        </button>
+      </div>
+
+      {error && <p className="text-sm text-rose-400 mt-3">{error}</p>}
+
+      {result && (
+        <div className="mt-4 space-y-1 text-sm">
+          {result.plan.orders.length === 0 && <p className="text-foreground-muted">Nothing to trade.</p>}
+          {result.plan.orders.map((o, i) => {
+            const fill = result.fills[i]
+            return (
+              <div key={`${o.symbol}-${o.tradeType}`} className="flex items-center gap-3 py-1 border-b border-white/[0.04]">
+                <span className={o.tradeType === 'BUY' ? 'text-emerald-400 w-10' : 'text-rose-400 w-10'}>{o.tradeType}</span>
+                <span className="font-medium w-20">{o.symbol}</span>
+                <span className="w-24">{formatUsd(o.valueUsd)}</span>
+                <span className="text-foreground-muted">
+                  {o.currentPct.toFixed(1)}% → {o.targetPct.toFixed(1)}% · {o.slippagePct.toFixed(2)}% impact
+                  {o.capped && ' · capped'}
+                </span>
+                {fill && (
+                  <span className={`ml-auto ${fill.success ? 'text-emerald-400' : 'text-rose-400'}`}>
+                    {fill.success ? 'Filled' : fill.error}
+                  </span>
+                )}
+              </div>
+            )
+          })}
+          {result.plan.skipped.map(s => (
+            <div key={s.symbol} className="flex gap-3 text-xs text-foreground-muted">
+              <span className="w-20">{s.symbol}</span>
+              <span>{s.reason}</span>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  )
+}
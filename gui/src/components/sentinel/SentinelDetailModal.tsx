@@ -17,7 +17,7 @@ import {
   ExternalLink,
 } from 'lucide-react'
 import { buildImageUrl } from '@/lib/utils'
-import type { SentinelConfig, CoinHolding, TransactionRecord, TransactionListResponse } from '@/lib/types'
+import type { SentinelConfig, CoinHolding, TransactionRecord, Page, SimulatedTrigger } from '@/lib/types'
 
 interface SentinelDetailModalProps {
   sentinel: SentinelConfig
@@ -33,18 +33,21 @@ export function SentinelDetailModal({ sentinel, holding, onClose, onEdit, onCoin
   const [txError, setTxError] = useState<string | null>(null)
   const [showAllTx, setShowAllTx] = useState(false)
   const [totalTx, setTotalTx] = useState(0)
+  const [pathInput, setPathInput] = useState('0, 20, 50, 35, 60, 120')
+  const [simulation, setSimulation] = useState<SimulatedTrigger[] | null>(null)
+  const [simError, setSimError] = useState<string | null>(null)
 
   const fetchTransactions = useCallback(async () => {
     setLoadingTx(true)
     setTxError(null)
     try {
-      const resp = await invoke<TransactionListResponse>('get_transactions', {
+      const resp = await invoke<Page<TransactionRecord>>('get_transactions', {
         page: 1,
         limit: 100,
         tradeType: null,
         search: sentinel.symbol,
       })
-      const filtered = resp.transactions.filter(
+      const filtered = resp.items.filter(
         tx => tx.symbol === sentinel.symbol
       )
       setTransactions(filtered)
@@ -60,6 +63,22 @@ export function SentinelDetailModal({ sentinel, holding, onClose, onEdit, onCoin
     fetchTransactions()
   }, [fetchTransactions])
 
+  // Path steps are % moves from the entry price, e.g. "-30, 0, 40"
+  const runSimulation = async () => {
+    setSimError(null)
+    const steps = pathInput.split(',').map(v => v.trim()).filter(Boolean).map(Number)
+    if (steps.length === 0 || steps.some(v => !Number.isFinite(v))) {
+      setSimError('Enter comma-separated % changes from entry, e.g. -30, 0, 40')
+      return
+    }
+    try {
+      const pricePath = steps.map(pct => sentinel.entryPrice * (1 + pct / 100))
+      setSimulation(await invoke<SimulatedTrigger[]>('simulate_sentinel_path', { sentinelId: sentinel.id, pricePath }))
+    } catch (e) {
+      setSimError(`Simulation failed: ${e}`)
+    }
+  }
+
   const buyTxs = transactions.filter(tx => tx.tradeType === 'BUY')
   const sellTxs = transactions.filter(tx => tx.tradeType === 'SELL')
 
@@ -231,6 +250,39 @@ export function SentinelDetailModal({ sentinel, holding, onClose, onEdit, onCoin
             </div>
           </div>
 
+          {/* Price Path Simulation */}
+          <div className="p-3 rounded-lg bg-background-tertiary space-y-2">
+            <h3 className="text-xs font-medium text-foreground-muted uppercase tracking-wider">Simulate Price Path</h3>
+            <div className="flex gap-2">
+              <input
+                type="text"
+                value={pathInput}
+                onChange={(e) => setPathInput(e.target.value)}
+                placeholder="-30, 0, 40"
+                className="input flex-1 font-mono text-sm"
+              />
+              <button onClick={runSimulation} className="btn btn-ghost text-sm border border-background-secondary">Run</button>
+            </div>
+            <p className="text-xs text-foreground-muted">% changes from the entry price, one per tick. Partial sells re-arm at the sell price, as the monitor does.</p>
+            {simError && <p className="text-xs text-sell">{simError}</p>}
+            {simulation && (simulation.length === 0 ? (
+              <p className="text-xs text-foreground-muted">No trigger fires on this path.</p>
+            ) : (
+              <div className="space-y-1">
+                {simulation.map((t) => (
+                  <div key={t.step} className="flex items-center justify-between text-xs">
+                    <span>
+                      <span className="text-foreground-muted">Tick {t.step + 1}: </span>
+                      <span className="font-medium">{t.triggerType.replace('_', ' ')}</span>
+                      <span className="text-foreground-muted"> at {formatPrice(t.price)}</span>
+                    </span>
+                    <span className="font-mono">sells {t.sellPct}%, {t.remainingPct.toFixed(1)}% left</span>
+                  </div>
+                ))}
+              </div>
+            ))}
+          </div>
+
           {/* Holding Summary */}
           {holding && (
             <div className="p-3 rounded-lg bg-background-tertiary">
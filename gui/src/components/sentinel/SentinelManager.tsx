@@ -26,11 +26,12 @@ import {
   Search,
   ChevronUp,
   ChevronDown,
+  Wrench,
 } from 'lucide-react'
 import { SentinelForm } from './SentinelForm.tsx'
 import { SentinelDetailModal } from './SentinelDetailModal.tsx'
-import { buildImageUrl } from '@/lib/utils'
-import type { SentinelConfig, CoinHolding, AppSettings, MonitorStatusResponse, SentinelTriggeredEvent, TradeExecutedEvent, TransactionRecord, TransactionListResponse } from '@/lib/types'
+import { buildImageUrl, formatFillPrice } from '@/lib/utils'
+import type { SentinelConfig, SentinelPriceSource, CoinHolding, AppSettings, MonitorStatusResponse, SentinelTriggeredEvent, TradeExecutedEvent, TransactionRecord, Page } from '@/lib/types'
 
 interface SentinelManagerProps {
   holdings?: CoinHolding[]
@@ -47,6 +48,17 @@ interface SentinelCheckResult {
   syncedAdded: number
 }
 
+interface ReconcileSummary {
+  held: number
+  orphansRemoved: number
+  triggeredPurged: number
+  blacklistedRemoved: number
+  duplicatesRemoved: number
+  created: string[]
+  guarded: string[]
+  errors: string[]
+}
+
 const DEFAULT_SENTINEL_SETTINGS = {
   stopLossPct: 10,
   takeProfitPct: 50,
@@ -65,6 +77,7 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
   const [editingSentinel, setEditingSentinel] = useState<SentinelConfig | null>(null)
   const [detailSentinel, setDetailSentinel] = useState<SentinelConfig | null>(null)
   const [checkRunning, setCheckRunning] = useState(false)
+  const [reconciling, setReconciling] = useState(false)
   const [checkResult, setCheckResult] = useState<SentinelCheckResult | null>(null)
   const [syncMessage, setSyncMessage] = useState<string | null>(null)
   const [globalPaused, setGlobalPaused] = useState(false)
@@ -145,8 +158,8 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
   const fetchSentinels = useCallback(async () => {
     try {
       setError(null)
-      const data = await invoke<SentinelConfig[]>('list_sentinels')
-      setSentinels(data)
+      const data = await invoke<Page<SentinelConfig>>('list_sentinels')
+      setSentinels(data.items)
     } catch (e) {
       setError(`Failed to load sentinels: ${e}`)
     } finally {
@@ -288,6 +301,29 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
     }
   }
 
+  const handleReconcile = async () => {
+    setReconciling(true)
+    setError(null)
+    try {
+      const summary = await invoke<ReconcileSummary>('reconcile_sentinels')
+      const removed = summary.orphansRemoved + summary.triggeredPurged + summary.blacklistedRemoved + summary.duplicatesRemoved
+      let message = `Reconciled ${summary.held} holdings: ${summary.created.length} created, ${removed} removed`
+      if (summary.guarded.length > 0) {
+        message += ` (${summary.guarded.join(', ')} entered at market price to avoid an instant stop-loss)`
+      }
+      setSyncMessage(message)
+      setTimeout(() => setSyncMessage(null), 8000)
+      if (summary.errors.length > 0) {
+        setError(`Reconcile errors: ${summary.errors.join('; ')}`)
+      }
+      await refreshAll()
+    } catch (e) {
+      setError(`Sentinel reconcile failed: ${e}`)
+    } finally {
+      setReconciling(false)
+    }
+  }
+
   const handleToggle = async (id: number, isActive: boolean) => {
     try {
       await invoke('toggle_sentinel', { sentinelId: id, isActive: !isActive })
@@ -326,7 +362,27 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
         trailingStopPct: editingSentinel.trailingStopPct,
         sellPercentage: editingSentinel.sellPercentage,
       })
-      setSentinels(prev => prev.map(s => s.id === editingSentinel.id ? editingSentinel : s))
+      await invoke('set_sentinel_price_source', {
+        sentinelId: editingSentinel.id,
+        priceSource: editingSentinel.priceSource,
+      })
+      await invoke('set_sentinel_trigger_sells', {
+        sentinelId: editingSentinel.id,
+        slSellPct: editingSentinel.slSellPct,
+        tpSellPct: editingSentinel.tpSellPct,
+        tsSellPct: editingSentinel.tsSellPct,
+      })
+      // Setting a ladder restarts it from the first rung, so only send changes
+      const saved = sentinels.find(s => s.id === editingSentinel.id)
+      let edited = editingSentinel
+      if (JSON.stringify(saved?.tpLadder ?? null) !== JSON.stringify(editingSentinel.tpLadder)) {
+        await invoke('set_sentinel_tp_ladder', {
+          sentinelId: editingSentinel.id,
+          ladder: editingSentinel.tpLadder,
+        })
+        edited = { ...editingSentinel, tpRungsFilled: 0 }
+      }
+      setSentinels(prev => prev.map(s => s.id === edited.id ? edited : s))
       setEditingSentinel(null)
     } catch (e) {
       setError(`Failed to update sentinel: ${e}`)
@@ -448,10 +504,10 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
       setTooltipLoading(true)
       setTooltipSymbol(symbol)
       try {
-        const resp = await invoke<TransactionListResponse>('get_transactions', {
+        const resp = await invoke<Page<TransactionRecord>>('get_transactions', {
           page: 1, limit: 100, tradeType: null, search: symbol,
         })
-        const filtered = resp.transactions.filter(tx => tx.symbol === symbol)
+        const filtered = resp.items.filter(tx => tx.symbol === symbol)
         txCache.current[symbol] = filtered
         setTooltipTxs(filtered)
       } catch {
@@ -587,6 +643,19 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
             )}
             Check Now
           </button>
+          <button
+            onClick={handleReconcile}
+            disabled={reconciling}
+            className="flex items-center gap-2 px-3 py-2 rounded-lg hover:bg-background-tertiary transition-colors disabled:opacity-50"
+            title="Remove sentinels for coins you no longer hold and add default sentinels to unprotected holdings"
+          >
+            {reconciling ? (
+              <RefreshCw className="w-4 h-4 animate-spin" />
+            ) : (
+              <Wrench className="w-4 h-4" />
+            )}
+            Reconcile
+          </button>
           <button
             onClick={fetchSentinels}
             disabled={loading}
@@ -754,7 +823,7 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
                   n.triggerType === 'take_profit' ? 'bg-buy/20 text-buy' :
                   'bg-amber-500/20 text-amber-400'
                 }`}>
-                  {n.triggerType === 'stop_loss' ? 'SL' : n.triggerType === 'take_profit' ? 'TP' : 'TS'}
+                  {n.triggerType === 'stop_loss' ? 'SL' : n.triggerType === 'take_profit' ? 'TP' : n.triggerType === 'liquidity_drain' ? 'LQ' : 'TS'}
                 </span>
                 <span className="font-medium">${n.symbol}</span>
                 <span className="text-foreground-muted">—</span>
@@ -769,7 +838,7 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
                 <span className="font-medium">${n.symbol}</span>
                 <span className="text-foreground-muted">—</span>
                 <span className="text-foreground-muted truncate">
-                  {n.success ? `${n.tradeType} @ $${n.newPrice.toFixed(6)}` : n.error}
+                  {n.success ? `${n.tradeType} @ ${formatFillPrice(n.newPrice, 6)}` : n.error}
                 </span>
               </div>
             ))}
@@ -1278,6 +1347,116 @@ export function SentinelManager({ holdings: externalHoldings = [], onCoinClick,
                 />
               </div>
 
+              {/* Per-trigger Sell Percentages */}
+              <div>
+                <label className="block text-sm font-medium mb-2">Sell % per Trigger</label>
+                <div className="grid grid-cols-3 gap-2">
+                  {([
+                    ['slSellPct', 'Stop loss'],
+                    ['tpSellPct', 'Take profit'],
+                    ['tsSellPct', 'Trailing'],
+                  ] as const).map(([key, label]) => (
+                    <div key={key}>
+                      <span className="block text-xs text-foreground-muted mb-1">{label}</span>
+                      <input
+                        type="number"
+                        min="1"
+                        max="100"
+                        value={editingSentinel[key] ?? ''}
+                        onChange={(e) => setEditingSentinel({
+                          ...editingSentinel,
+                          [key]: e.target.value === '' ? null : parseFloat(e.target.value),
+                        })}
+                        className="input text-sm"
+                        placeholder={`${editingSentinel.sellPercentage}`}
+                      />
+                    </div>
+                  ))}
+                </div>
+                <p className="text-xs text-foreground-muted mt-1">Leave empty to sell the percentage above</p>
+              </div>
+
+              {/* Take-profit Ladder */}
+              <div>
+                <div className="flex items-center justify-between mb-2">
+                  <label className="block text-sm font-medium">Take-Profit Ladder</label>
+                  <button
+                    type="button"
+                    onClick={() => {
+                      const rungs = editingSentinel.tpLadder ?? []
+                      const lastGain = rungs.length > 0 ? rungs[rungs.length - 1].gainPct : (editingSentinel.takeProfitPct ?? 50)
+                      setEditingSentinel({
+                        ...editingSentinel,
+                        tpLadder: [...rungs, { gainPct: rungs.length > 0 ? lastGain * 2 : lastGain, sellPct: 25 }],
+                      })
+                    }}
+                    className="flex items-center gap-1 text-xs text-foreground-muted hover:text-foreground"
+                  >
+                    <Plus className="w-3 h-3" /> Add rung
+                  </button>
+                </div>
+                {(editingSentinel.tpLadder ?? []).map((rung, i) => (
+                  <div key={i} className="grid grid-cols-[1fr_1fr_auto] gap-2 mb-2 items-center">
+                    <input
+                      type="number"
+                      min="1"
+                      value={rung.gainPct}
+                      onChange={(e) => setEditingSentinel({
+                        ...editingSentinel,
+                        tpLadder: (editingSentinel.tpLadder ?? []).map((r, j) => j === i ? { ...r, gainPct: parseFloat(e.target.value) } : r),
+                      })}
+                      className={`input text-sm ${i < editingSentinel.tpRungsFilled ? 'opacity-50' : ''}`}
+                      placeholder="Gain %"
+                    />
+                    <input
+                      type="number"
+                      min="1"
+                      max="100"
+                      value={rung.sellPct}
+                      onChange={(e) => setEditingSentinel({
+                        ...editingSentinel,
+                        tpLadder: (editingSentinel.tpLadder ?? []).map((r, j) => j === i ? { ...r, sellPct: parseFloat(e.target.value) } : r),
+                      })}
+                      className={`input text-sm ${i < editingSentinel.tpRungsFilled ? 'opacity-50' : ''}`}
+                      placeholder="Sell %"
+                    />
+                    <button
+                      type="button"
+                      onClick={() => {
+                        const rest = (editingSentinel.tpLadder ?? []).filter((_, j) => j !== i)
+                        setEditingSentinel({ ...editingSentinel, tpLadder: rest.length > 0 ? rest : null })
+                      }}
+                      className="p-1 text-foreground-muted hover:text-red-400"
+                      title="Remove rung"
+                    >
+                      <X className="w-4 h-4" />
+                    </button>
+                  </div>
+                ))}
+                <p className="text-xs text-foreground-muted mt-1">
+                  {editingSentinel.tpLadder
+                    ? `Gain % above entry and % of the position to sell at each rung; anything the rungs leave stays under the stop-loss. ${editingSentinel.tpRungsFilled} of ${editingSentinel.tpLadder.length} filled.`
+                    : 'Scale out in steps instead of the single take-profit above'}
+                </p>
+              </div>
+
+              {/* Price Source */}
+              <div>
+                <label className="block text-sm font-medium mb-2">Trigger Price</label>
+                <select
+                  value={editingSentinel.priceSource ?? ''}
+                  onChange={(e) => setEditingSentinel({
+                    ...editingSentinel,
+                    priceSource: (e.target.value || null) as SentinelPriceSource | null,
+                  })}
+                  className="input text-sm w-full"
+                >
+                  <option value="">Monitor default</option>
+                  <option value="last_trade">Last trade</option>
+                  <option value="mark">Pool mark price</option>
+                </select>
+              </div>
+
               <div className="flex flex-col gap-2 pt-2">
                 <div className="flex gap-2">
                   <button
@@ -0,0 +1,102 @@
+import { useState, useEffect } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { FileCode, Save } from 'lucide-react'
+import type { EntryScriptConfig } from '@/lib/types'
+
+const EXAMPLE_SCRIPT = `// Runs after the module's own filters; return false to skip the buy
+if source == "sniper" {
+    coin.volume24h > 1000.0
+} else {
+    signals.momentum >= 0.4
+}`
+
+/** Editor for the user's entry script, saved on its own since it has to compile */
+export function EntryScriptCard() {
+  const [config, setConfig] = useState<EntryScriptConfig | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [saved, setSaved] = useState(false)
+
+  useEffect(() => {
+    invoke<EntryScriptConfig>('get_entry_script')
+      .then(setConfig)
+      .catch((e) => setError(String(e)))
+  }, [])
+
+  if (!config) {
+    return error ? <p className="text-sm text-rose-400">{error}</p> : null
+  }
+
+  const update = (changes: Partial<EntryScriptConfig>) => {
+    setConfig({ ...config, ...changes })
+    setSaved(false)
+  }
+
+  const save = async () => {
+    try {
+      setConfig(await invoke<EntryScriptConfig>('set_entry_script', { config }))
+      setError(null)
+      setSaved(true)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  return (
+    <div className="card">
+      <div className="flex items-center justify-between mb-4">
+        <div className="flex items-center gap-2">
+          <FileCode className="w-5 h-5 text-violet-400" />
+          <h2 className="text-lg font-semibold">Entry Script</h2>
+        </div>
+        <label className="flex items-center gap-2 text-sm text-zinc-300">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            onChange={(e) => update({ enabled: e.target.checked })}
+          />
+          Enabled
+        </label>
+      </div>
+      <p className="text-sm text-foreground-muted mb-4">
+        A Rhai script with the last word on Sniper and Dip Buyer entries. It sees <code>source</code>,{' '}
+        <code>coin</code>, <code>holders</code> and <code>signals</code> and must return true to buy. Errors and
+        timeouts skip the buy.
+      </p>
+
+      <textarea
+        value={config.script}
+        onChange={(e) => update({ script: e.target.value })}
+        placeholder={EXAMPLE_SCRIPT}
+        rows={8}
+        spellCheck={false}
+        className="input w-full font-mono text-xs mb-4"
+      />
+
+      <div className="flex items-end justify-between gap-4">
+        <div className="form-field">
+          <label className="form-label">Timeout</label>
+          <div className="flex items-center gap-2">
+            <input
+              type="number"
+              min="1"
+              max="1000"
+              step="10"
+              value={config.timeoutMs}
+              onChange={(e) => update({ timeoutMs: Math.max(1, parseInt(e.target.value) || 1) })}
+              className="input w-28"
+            />
+            <span className="text-foreground-muted text-sm">ms</span>
+          </div>
+        </div>
+        <div className="flex items-center gap-3">
+          {error && <span className="text-sm text-rose-400">{error}</span>}
+          {saved && !error && <span className="text-sm text-emerald-400">Saved</span>}
+          <button onClick={save} className="btn btn-primary flex items-center gap-2">
+            <Save className="w-4 h-4" />
+            Save Script
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
@@ -13,17 +13,66 @@ import {
   ScrollText,
   Shrink,
   AlertTriangle,
+  Archive,
+  Users,
+  ArrowUp,
+  Copy,
+  Zap,
 } from 'lucide-react'
-import type { AppSettings } from '@/lib/types'
+import type { AppSettings, DuplicateTokenPolicy, ProfileFailoverConfig, ProfileSummary } from '@/lib/types'
 import { ToggleSwitch } from '@/components/ui/FormattedInput'
 
 interface StorageInfo {
   dataDir: string
   dbSizeBytes: number
+  walSizeBytes: number
   profileCount: number
   transactionCount: number
   sentinelCount: number
   automationLogCount: number
+  archivedLogCount: number
+}
+
+interface RetentionConfig {
+  enabled: boolean
+  retentionDays: number
+  lastPrunedAt: string | null
+}
+
+interface CacheWarmConfig {
+  enabled: boolean
+  /** Refetch an entry once it has less than this long left */
+  leadSecs: number
+  maxPerTick: number
+  includeWatchlist: boolean
+}
+
+interface MaintenanceReport {
+  sizeBeforeBytes: number
+  sizeAfterBytes: number
+  checkpointed: boolean
+  durationMs: number
+}
+
+interface PruneReport {
+  automationLogs: number
+  snipeLogs: number
+  triggeredSentinels: number
+  decisionEvents: number
+}
+
+interface ProfileKeyHealth {
+  profileId: number
+  username: string
+  status: 'current' | 'legacy' | 'unreadable'
+}
+
+interface MigrationReport {
+  checked: number
+  migrated: number
+  alreadyCurrent: number
+  unreadable: number
+  failed: number
 }
 
 interface GeneralTabProps {
@@ -45,6 +94,15 @@ export function GeneralTab({ settings, setSettings, onChanged }: GeneralTabProps
   const [actionMsg, setActionMsg] = useState<{ text: string; ok: boolean } | null>(null)
   const [storageInfo, setStorageInfo] = useState<StorageInfo | null>(null)
   const [confirmAction, setConfirmAction] = useState<string | null>(null)
+  const [retention, setRetention] = useState<RetentionConfig | null>(null)
+  const [cacheWarm, setCacheWarm] = useState<CacheWarmConfig | null>(null)
+  const [pruning, setPruning] = useState(false)
+  const [maintaining, setMaintaining] = useState(false)
+  const [migrating, setMigrating] = useState(false)
+  const [failover, setFailover] = useState<ProfileFailoverConfig | null>(null)
+  const [profiles, setProfiles] = useState<ProfileSummary[]>([])
+  const [duplicatePolicy, setDuplicatePolicy] = useState<DuplicateTokenPolicy>('warn')
+  const [duplicateGroups, setDuplicateGroups] = useState<number[][]>([])
 
   const showMessage = (text: string, ok: boolean) => {
     setActionMsg({ text, ok })
@@ -53,8 +111,104 @@ export function GeneralTab({ settings, setSettings, onChanged }: GeneralTabProps
 
   useEffect(() => {
     loadStorageInfo()
+    loadRetention()
+    loadCacheWarm()
+    loadFailover()
   }, [])
 
+  const loadFailover = async () => {
+    try {
+      setFailover(await invoke<ProfileFailoverConfig>('get_profile_failover'))
+      setProfiles(await invoke<ProfileSummary[]>('list_profiles'))
+      setDuplicatePolicy(await invoke<DuplicateTokenPolicy>('get_duplicate_token_policy'))
+      setDuplicateGroups(await invoke<number[][]>('get_duplicate_profiles'))
+    } catch (e) {
+      console.error('Failed to load profile failover:', e)
+    }
+  }
+
+  const saveDuplicatePolicy = async (policy: DuplicateTokenPolicy) => {
+    try {
+      setDuplicatePolicy(await invoke<DuplicateTokenPolicy>('set_duplicate_token_policy', { policy }))
+    } catch (e) {
+      showMessage(`Failed to save duplicate token policy: ${e}`, false)
+    }
+  }
+
+  const saveFailover = async (config: ProfileFailoverConfig) => {
+    try {
+      setFailover(await invoke<ProfileFailoverConfig>('set_profile_failover', { config }))
+    } catch (e) {
+      showMessage(`Failed to save profile failover: ${e}`, false)
+    }
+  }
+
+  const toggleFailoverProfile = (id: number) => {
+    if (!failover) return
+    const profileIds = failover.profileIds.includes(id)
+      ? failover.profileIds.filter(p => p !== id)
+      : [...failover.profileIds, id]
+    saveFailover({ ...failover, profileIds })
+  }
+
+  const moveFailoverProfileUp = (id: number) => {
+    if (!failover) return
+    const i = failover.profileIds.indexOf(id)
+    if (i <= 0) return
+    const profileIds = [...failover.profileIds]
+    ;[profileIds[i - 1], profileIds[i]] = [profileIds[i], profileIds[i - 1]]
+    saveFailover({ ...failover, profileIds })
+  }
+
+  const loadRetention = async () => {
+    try {
+      setRetention(await invoke<RetentionConfig>('get_retention_config'))
+    } catch (e) {
+      console.error('Failed to load retention config:', e)
+    }
+  }
+
+  const saveRetention = async (enabled: boolean, retentionDays: number) => {
+    try {
+      setRetention(await invoke<RetentionConfig>('set_retention_config', { enabled, retentionDays }))
+    } catch (e) {
+      showMessage(`Failed to save retention: ${e}`, false)
+    }
+  }
+
+  const loadCacheWarm = async () => {
+    try {
+      setCacheWarm(await invoke<CacheWarmConfig>('get_cache_warming'))
+    } catch (e) {
+      console.error('Failed to load cache warming config:', e)
+    }
+  }
+
+  const saveCacheWarm = async (config: CacheWarmConfig) => {
+    try {
+      setCacheWarm(await invoke<CacheWarmConfig>('set_cache_warming', { config }))
+    } catch (e) {
+      showMessage(`Failed to save cache warming: ${e}`, false)
+      loadCacheWarm()
+    }
+  }
+
+  const handlePruneNow = async () => {
+    if (!retention) return
+    setPruning(true)
+    try {
+      const r = await invoke<PruneReport>('prune_history', { olderThanDays: retention.retentionDays })
+      const total = r.automationLogs + r.snipeLogs + r.triggeredSentinels + r.decisionEvents
+      showMessage(`Pruned ${total} rows older than ${retention.retentionDays} days`, true)
+      loadStorageInfo()
+      loadRetention()
+    } catch (e) {
+      showMessage(`Failed to prune history: ${e}`, false)
+    } finally {
+      setPruning(false)
+    }
+  }
+
   const loadStorageInfo = async () => {
     try {
       const info = await invoke<StorageInfo>('get_storage_info')
@@ -76,6 +230,16 @@ export function GeneralTab({ settings, setSettings, onChanged }: GeneralTabProps
     }
   }
 
+  const handleCopyDiagnostics = async () => {
+    try {
+      const report = await invoke('generate_diagnostic_report')
+      await navigator.clipboard.writeText(JSON.stringify(report, null, 2))
+      showMessage('Diagnostic report copied (tokens and PINs removed)', true)
+    } catch {
+      showMessage('Failed to generate diagnostic report', false)
+    }
+  }
+
   const handleClearSnipedSymbols = async () => {
     try {
       await invoke('clear_sniped_symbols_cmd')
@@ -118,13 +282,47 @@ export function GeneralTab({ settings, setSettings, onChanged }: GeneralTabProps
     }
   }
 
-  const handleVacuum = async () => {
+  const handleMaintenance = async () => {
+    setMaintaining(true)
     try {
-      await invoke('vacuum_database')
-      showMessage('Database compacted', true)
+      const r = await invoke<MaintenanceReport>('run_db_maintenance', { checkpoint: true })
+      const freed = Math.max(0, r.sizeBeforeBytes - r.sizeAfterBytes)
+      showMessage(
+        `Database optimized: ${formatBytes(r.sizeBeforeBytes)} → ${formatBytes(r.sizeAfterBytes)} (freed ${formatBytes(freed)})`,
+        true,
+      )
       loadStorageInfo()
     } catch {
-      showMessage('Failed to compact database', false)
+      showMessage('Failed to optimize database', false)
+    } finally {
+      setMaintaining(false)
+    }
+  }
+
+  const handleEncryptionMigration = async () => {
+    setMigrating(true)
+    try {
+      const health = await invoke<ProfileKeyHealth[]>('check_encryption_health')
+      const legacy = health.filter(h => h.status === 'legacy').map(h => h.profileId)
+      const unreadable = health.filter(h => h.status === 'unreadable').length
+      if (legacy.length === 0) {
+        showMessage(
+          unreadable > 0
+            ? `No tokens to migrate; ${unreadable} profile(s) need their token re-entered`
+            : 'All profile tokens already use this machine\'s key',
+          unreadable === 0,
+        )
+        return
+      }
+      const r = await invoke<MigrationReport>('run_encryption_migration', { profileIds: legacy })
+      showMessage(
+        `Migrated ${r.migrated} of ${legacy.length} profile token(s)${r.failed > 0 ? `, ${r.failed} failed` : ''}`,
+        r.failed === 0,
+      )
+    } catch (e) {
+      showMessage(`Failed to migrate tokens: ${e}`, false)
+    } finally {
+      setMigrating(false)
     }
   }
 
@@ -177,7 +375,9 @@ export function GeneralTab({ settings, setSettings, onChanged }: GeneralTabProps
             <div className="grid grid-cols-2 sm:grid-cols-4 gap-3">
               <div className="p-3 rounded-lg bg-background text-center">
                 <div className="text-lg font-bold text-foreground">{formatBytes(storageInfo.dbSizeBytes)}</div>
-                <div className="text-xs text-foreground-muted">Database Size</div>
+                <div className="text-xs text-foreground-muted">
+                  Database Size{storageInfo.walSizeBytes > 0 && ` (+${formatBytes(storageInfo.walSizeBytes)} WAL)`}
+                </div>
               </div>
               <div className="p-3 rounded-lg bg-background text-center">
                 <div className="text-lg font-bold text-foreground">{storageInfo.profileCount}</div>
@@ -246,18 +446,269 @@ export function GeneralTab({ settings, setSettings, onChanged }: GeneralTabProps
           </button>
 
           <button
-            onClick={handleVacuum}
+            onClick={handleMaintenance}
+            disabled={maintaining}
             className="flex items-center gap-3 p-4 rounded-lg bg-background hover:bg-zinc-700/50 transition-colors text-left group"
           >
             <div className="p-2 rounded-lg bg-emerald-500/20 group-hover:bg-emerald-500/30 transition-colors">
               <Shrink className="w-4 h-4 text-emerald-400" />
             </div>
             <div>
-              <div className="font-medium text-sm">Compact Database</div>
-              <p className="text-xs text-foreground-muted">Reclaim disk space (VACUUM)</p>
+              <div className="font-medium text-sm">{maintaining ? 'Optimizing...' : 'Optimize Database'}</div>
+              <p className="text-xs text-foreground-muted">Reclaim disk space and refresh stats (VACUUM, ANALYZE)</p>
             </div>
           </button>
+
+          <button
+            onClick={handleEncryptionMigration}
+            disabled={migrating}
+            className="flex items-center gap-3 p-4 rounded-lg bg-background hover:bg-zinc-700/50 transition-colors text-left group"
+          >
+            <div className="p-2 rounded-lg bg-purple-500/20 group-hover:bg-purple-500/30 transition-colors">
+              <Shield className="w-4 h-4 text-purple-400" />
+            </div>
+            <div>
+              <div className="font-medium text-sm">{migrating ? 'Migrating...' : 'Migrate Token Encryption'}</div>
+              <p className="text-xs text-foreground-muted">Re-encrypt profiles saved with the old key (e.g. after importing a DB)</p>
+            </div>
+          </button>
+
+          <button
+            onClick={handleCopyDiagnostics}
+            className="flex items-center gap-3 p-4 rounded-lg bg-background hover:bg-zinc-700/50 transition-colors text-left group"
+          >
+            <div className="p-2 rounded-lg bg-cyan-500/20 group-hover:bg-cyan-500/30 transition-colors">
+              <Copy className="w-4 h-4 text-cyan-400" />
+            </div>
+            <div>
+              <div className="font-medium text-sm">Copy Diagnostic Report</div>
+              <p className="text-xs text-foreground-muted">Settings, module state and recent logs for a bug report</p>
+            </div>
+          </button>
+        </div>
+      </div>
+
+      {/* History Retention */}
+      <div className="card">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Archive className="w-5 h-5 text-cyan-400" />
+            <h2 className="text-lg font-semibold">History Retention</h2>
+          </div>
+          {retention && (
+            <ToggleSwitch
+              enabled={retention.enabled}
+              onChange={() => saveRetention(!retention.enabled, retention.retentionDays)}
+            />
+          )}
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          Periodically delete old automation logs, snipe logs and triggered sentinels.
+          Lifetime totals per module are kept.
+        </p>
+
+        {retention ? (
+          <div className="flex flex-wrap items-end gap-4">
+            <div className="form-field">
+              <label className="form-label">Keep History For</label>
+              <div className="flex items-center gap-2">
+                <input
+                  type="number"
+                  min="1"
+                  step="1"
+                  value={retention.retentionDays}
+                  onChange={e => setRetention({ ...retention, retentionDays: parseInt(e.target.value) || 1 })}
+                  onBlur={() => saveRetention(retention.enabled, Math.max(1, retention.retentionDays))}
+                  className="input w-24"
+                />
+                <span className="text-foreground-muted text-sm">days</span>
+              </div>
+            </div>
+            <button
+              onClick={handlePruneNow}
+              disabled={pruning}
+              className="px-4 py-2 rounded-lg text-sm bg-cyan-600 hover:bg-cyan-700 text-white transition-colors disabled:opacity-50"
+            >
+              {pruning ? 'Pruning...' : 'Prune Now'}
+            </button>
+            <div className="text-xs text-foreground-muted">
+              {retention.lastPrunedAt
+                ? `Last pruned ${new Date(retention.lastPrunedAt).toLocaleString()}`
+                : 'Not pruned yet'}
+              {storageInfo && storageInfo.archivedLogCount > 0 &&
+                ` · ${storageInfo.archivedLogCount.toLocaleString()} log entries archived`}
+            </div>
+          </div>
+        ) : (
+          <div className="text-sm text-foreground-muted">Loading retention policy...</div>
+        )}
+      </div>
+
+      {/* Cache Warming */}
+      <div className="card">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Zap className="w-5 h-5 text-amber-400" />
+            <h2 className="text-lg font-semibold">Cache Warming</h2>
+          </div>
+          {cacheWarm && (
+            <ToggleSwitch
+              enabled={cacheWarm.enabled}
+              onChange={() => saveCacheWarm({ ...cacheWarm, enabled: !cacheWarm.enabled })}
+            />
+          )}
         </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          Refetch held coins shortly before their cached prices expire, so sentinel checks read fresh data.
+          Coins you don't hold are left to expire.
+        </p>
+
+        {cacheWarm ? (
+          <div className="flex flex-wrap items-end gap-4">
+            <div className="form-field">
+              <label className="form-label">Refresh Lead</label>
+              <div className="flex items-center gap-2">
+                <input
+                  type="number"
+                  min="1"
+                  max="25"
+                  step="1"
+                  value={cacheWarm.leadSecs}
+                  onChange={e => setCacheWarm({ ...cacheWarm, leadSecs: parseInt(e.target.value) || 1 })}
+                  onBlur={() => saveCacheWarm(cacheWarm)}
+                  className="input w-24"
+                />
+                <span className="text-foreground-muted text-sm">sec before expiry</span>
+              </div>
+            </div>
+            <div className="form-field">
+              <label className="form-label">Coins Per Tick</label>
+              <input
+                type="number"
+                min="1"
+                max="20"
+                step="1"
+                value={cacheWarm.maxPerTick}
+                onChange={e => setCacheWarm({ ...cacheWarm, maxPerTick: parseInt(e.target.value) || 1 })}
+                onBlur={() => saveCacheWarm(cacheWarm)}
+                className="input w-24"
+              />
+            </div>
+            <label className="flex items-center gap-2 text-sm pb-2">
+              <input
+                type="checkbox"
+                checked={cacheWarm.includeWatchlist}
+                onChange={() => saveCacheWarm({ ...cacheWarm, includeWatchlist: !cacheWarm.includeWatchlist })}
+              />
+              Include watchlist coins
+            </label>
+          </div>
+        ) : (
+          <div className="text-sm text-foreground-muted">Loading cache warming...</div>
+        )}
+      </div>
+
+      {/* Profile Failover */}
+      <div className="card">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Users className="w-5 h-5 text-violet-400" />
+            <h2 className="text-lg font-semibold">Profile Failover</h2>
+          </div>
+          {failover && (
+            <ToggleSwitch
+              enabled={failover.enabled}
+              onChange={() => saveFailover({ ...failover, enabled: !failover.enabled })}
+            />
+          )}
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          When the active profile's token expires (or, if you opt in below, it stays rate limited), switch
+          automation to the next listed profile whose token still works. Profiles are tried in the order shown.
+        </p>
+
+        {failover ? (
+          <div className="space-y-4">
+            <div className="space-y-2">
+              {[
+                ...failover.profileIds
+                  .map(id => profiles.find(p => p.id === id))
+                  .filter((p): p is ProfileSummary => !!p),
+                ...profiles.filter(p => !failover.profileIds.includes(p.id)),
+              ].map(profile => {
+                const position = failover.profileIds.indexOf(profile.id)
+                return (
+                  <div key={profile.id} className="flex items-center gap-3 p-2 rounded-lg bg-background">
+                    <input
+                      type="checkbox"
+                      checked={position >= 0}
+                      onChange={() => toggleFailoverProfile(profile.id)}
+                    />
+                    <span className="w-6 text-xs text-foreground-muted">{position >= 0 ? `#${position + 1}` : ''}</span>
+                    <span className="font-medium text-sm">@{profile.username}</span>
+                    {position > 0 && (
+                      <button
+                        onClick={() => moveFailoverProfileUp(profile.id)}
+                        className="ml-auto p-1 rounded hover:bg-background-tertiary"
+                        title="Try earlier"
+                      >
+                        <ArrowUp className="w-4 h-4" />
+                      </button>
+                    )}
+                  </div>
+                )
+              })}
+            </div>
+            <div className="form-field">
+              <label className="form-label">Switch After Rate Limiting For</label>
+              <div className="flex items-center gap-2">
+                <input
+                  type="number"
+                  min="0"
+                  step="30"
+                  value={failover.rateLimitFailoverSecs}
+                  onChange={e => setFailover({ ...failover, rateLimitFailoverSecs: parseInt(e.target.value) || 0 })}
+                  onBlur={() => saveFailover({ ...failover, rateLimitFailoverSecs: Math.max(0, failover.rateLimitFailoverSecs) })}
+                  className="input w-24"
+                />
+                <span className="text-foreground-muted text-sm">seconds</span>
+              </div>
+              <p className="form-hint">
+                429s with no successful poll in between for this long also trigger a switch. 0 never switches over
+                rate limits, which usually pass on their own.
+              </p>
+            </div>
+            <div className="form-field">
+              <label className="form-label">Profiles Sharing a Token</label>
+              <select
+                value={duplicatePolicy}
+                onChange={e => saveDuplicatePolicy(e.target.value as DuplicateTokenPolicy)}
+                className="input"
+              >
+                <option value="warn">Save anyway and warn</option>
+                <option value="refuse">Refuse to save</option>
+                <option value="merge">Use the existing profile</option>
+              </select>
+              <p className="form-hint">
+                Two profiles with the same token are one account and would double-trade it. Failover never
+                switches to a profile with the active profile's token.
+              </p>
+              {duplicateGroups.length > 0 && (
+                <div className="flex items-center gap-2 p-3 mt-2 rounded-lg text-sm bg-amber-500/10 text-amber-400">
+                  <AlertTriangle className="w-4 h-4 shrink-0" />
+                  <span>
+                    Same token saved more than once:{' '}
+                    {duplicateGroups
+                      .map(ids => ids.map(id => `@${profiles.find(p => p.id === id)?.username ?? id}`).join(' = '))
+                      .join('; ')}
+                  </span>
+                </div>
+              )}
+            </div>
+          </div>
+        ) : (
+          <div className="text-sm text-foreground-muted">Loading profile failover...</div>
+        )}
       </div>
 
       {/* Destructive Actions */}
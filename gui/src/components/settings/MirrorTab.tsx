@@ -7,8 +7,10 @@ import {
   AlertTriangle,
   Percent,
   Ban,
+  Layers,
 } from 'lucide-react'
 import type { MirrorConfigState } from './SettingsLayout'
+import type { WashSensitivity } from '@/lib/types'
 import { ToggleSwitch } from '@/components/ui/FormattedInput'
 
 interface MirrorTabProps {
@@ -149,6 +151,113 @@ export function MirrorTab({ config, setConfig, onChanged }: MirrorTabProps) {
               Skip whale trades older than this
             </p>
           </div>
+
+          {/* Feed Depth */}
+          <div className="form-field">
+            <label className="form-label">
+              <Layers className="w-4 h-4 text-blue-400" />
+              Trade Feed Depth
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="500"
+                step="10"
+                value={config.recentTradesDepth}
+                onChange={e => update('recentTradesDepth', parseInt(e.target.value) || 0)}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">trades</span>
+            </div>
+            <p className="form-hint">
+              Trades fetched per poll (0 = 50). Grows automatically when whales trade faster than the poll interval.
+            </p>
+          </div>
+
+          {/* Whale Limit */}
+          <div className="form-field">
+            <label className="form-label">
+              <Users className="w-4 h-4 text-purple-400" />
+              Max Watched Whales
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="500"
+                step="5"
+                value={config.maxTrackedWhales}
+                onChange={e => update('maxTrackedWhales', parseInt(e.target.value) || 0)}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">whales</span>
+            </div>
+            <p className="form-hint">
+              Only the best-scoring whales are watched when you track more (0 = no limit)
+            </p>
+          </div>
+
+          {/* Max Risk Score */}
+          <div className="form-field">
+            <label className="form-label">
+              <Shield className="w-4 h-4 text-red-400" />
+              Max Risk Score
+            </label>
+            <input
+              type="number"
+              min="0"
+              max="100"
+              step="5"
+              value={config.maxRiskScore ?? 0}
+              onChange={e => update('maxRiskScore', parseFloat(e.target.value) || 0)}
+              className="input"
+            />
+            <p className="form-hint">
+              Skip copying buys of coins whose risk score is above this (0-100, 0 = off)
+            </p>
+          </div>
+
+          {/* Min Whale Score */}
+          <div className="form-field">
+            <label className="form-label">
+              <Users className="w-4 h-4 text-cyan-400" />
+              Min Whale Score
+            </label>
+            <input
+              type="number"
+              min="0"
+              max="100"
+              step="5"
+              value={config.minWhaleScore ?? 0}
+              onChange={e => update('minWhaleScore', parseFloat(e.target.value) || 0)}
+              className="input"
+            />
+            <p className="form-hint">
+              Skip copying buys of whales whose track record scores below this (0-100, 0 = off). Whales with too little history are still copied
+            </p>
+          </div>
+
+          {/* Wash Trade Filter */}
+          <div className="form-field">
+            <label className="form-label">
+              <Shield className="w-4 h-4 text-amber-400" />
+              Wash Trade Filter
+            </label>
+            <select
+              value={config.washTradeSensitivity}
+              onChange={e => update('washTradeSensitivity', e.target.value as WashSensitivity)}
+              className="input"
+            >
+              <option value="off">Off</option>
+              <option value="low">Low</option>
+              <option value="medium">Medium</option>
+              <option value="high">High</option>
+            </select>
+            <p className="form-hint">
+              Don't copy whales buying and selling matching amounts of a coin back and forth to fake volume. Exits from coins you hold are always copied
+            </p>
+          </div>
         </div>
       </div>
 
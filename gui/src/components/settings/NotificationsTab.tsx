@@ -1,5 +1,5 @@
 import { Bell } from 'lucide-react'
-import type { NotificationConfig } from '@/lib/types'
+import type { NotificationCategory, NotificationConfig } from '@/lib/types'
 import { ToggleSwitch } from '@/components/ui/FormattedInput'
 
 interface NotificationsTabProps {
@@ -8,7 +8,9 @@ interface NotificationsTabProps {
   onChanged: () => void
 }
 
-const NOTIFICATION_ITEMS: { key: keyof NotificationConfig; label: string; desc: string }[] = [
+type NotificationToggle = Exclude<keyof NotificationConfig, 'dedupWindowSecs' | 'dedupOverrides'>
+
+const NOTIFICATION_ITEMS: { key: NotificationToggle; label: string; desc: string }[] = [
   { key: 'enabled', label: 'Enable Notifications', desc: 'Master switch for all notifications' },
   { key: 'sentinelTriggers', label: 'Sentinel Triggers', desc: 'SL/TP/trailing stop sell alerts' },
   { key: 'sniperBuys', label: 'Sniper Buys', desc: 'New coin auto-buy alerts' },
@@ -18,12 +20,32 @@ const NOTIFICATION_ITEMS: { key: keyof NotificationConfig; label: string; desc:
   { key: 'tradeConfirmations', label: 'Trade Confirmations', desc: 'Manual trade execution alerts' },
 ]
 
+const DEDUP_CATEGORIES: { category: NotificationCategory; label: string }[] = [
+  { category: 'trade', label: 'Trades' },
+  { category: 'failure', label: 'Failures' },
+  { category: 'alert', label: 'Price & Equity Alerts' },
+  { category: 'general', label: 'Other' },
+]
+
 export function NotificationsTab({ config, setConfig, onChanged }: NotificationsTabProps) {
-  const toggle = (key: keyof NotificationConfig) => {
+  const toggle = (key: NotificationToggle) => {
     setConfig(prev => ({ ...prev, [key]: !prev[key] }))
     onChanged()
   }
 
+  const setOverride = (category: NotificationCategory, value: string) => {
+    setConfig(prev => {
+      const dedupOverrides = { ...prev.dedupOverrides }
+      if (value === '') {
+        delete dedupOverrides[category]
+      } else {
+        dedupOverrides[category] = Math.max(0, parseInt(value) || 0)
+      }
+      return { ...prev, dedupOverrides }
+    })
+    onChanged()
+  }
+
   return (
     <div className="space-y-6">
       <div className="card">
@@ -53,6 +75,54 @@ export function NotificationsTab({ config, setConfig, onChanged }: Notifications
         </div>
       </div>
 
+      <div className={`card ${!config.enabled ? 'opacity-50 pointer-events-none' : ''}`}>
+        <h2 className="text-lg font-semibold mb-1">Repeat Suppression</h2>
+        <p className="text-sm text-foreground-muted mb-4">
+          A notification identical to one shown recently is dropped, so a failure retried every tick raises one toast
+        </p>
+
+        <div className="form-field">
+          <label className="form-label">Suppress Repeats For</label>
+          <div className="flex items-center gap-2">
+            <input
+              type="number"
+              min="0"
+              step="10"
+              value={config.dedupWindowSecs}
+              onChange={e => {
+                setConfig(prev => ({ ...prev, dedupWindowSecs: Math.max(0, parseInt(e.target.value) || 0) }))
+                onChanged()
+              }}
+              className="input w-24"
+            />
+            <span className="text-foreground-muted text-sm">seconds</span>
+          </div>
+          <p className="form-hint">0 shows every notification</p>
+        </div>
+
+        <div className="mt-4 space-y-2">
+          <div className="form-label">Per-Category Overrides</div>
+          {DEDUP_CATEGORIES.map(({ category, label }) => (
+            <div key={category} className="flex items-center justify-between gap-3">
+              <span className="text-sm">{label}</span>
+              <div className="flex items-center gap-2">
+                <input
+                  type="number"
+                  min="0"
+                  step="10"
+                  placeholder={String(config.dedupWindowSecs)}
+                  value={config.dedupOverrides[category] ?? ''}
+                  onChange={e => setOverride(category, e.target.value)}
+                  className="input w-24"
+                />
+                <span className="text-foreground-muted text-sm">s</span>
+              </div>
+            </div>
+          ))}
+          <p className="form-hint">Leave blank to use the window above. Trades default to 0 so every fill is shown.</p>
+        </div>
+      </div>
+
       {/* Info */}
       <div className="card bg-blue-500/10 border-blue-500/30">
         <h3 className="font-semibold text-blue-400 mb-2">About Notifications</h3>
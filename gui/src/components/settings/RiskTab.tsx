@@ -6,21 +6,115 @@ import {
   RotateCcw,
   Clock,
   Layers,
+  Snowflake,
+  Split,
+  Hourglass,
+  Gauge,
+  Ban,
+  Baby,
+  Activity,
+  Binary,
+  Plus,
+  X,
+  TrendingDown,
 } from 'lucide-react'
-import type { RiskLimits } from '@/lib/types'
+import { useEffect, useState } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { EntryScriptCard } from './EntryScriptCard'
+import type { RiskLimits, DrawdownStatus, ExecutorStats, SplitSellConfig, PriceFreshnessPolicy, CoinPrecision, AdaptivePollPolicy } from '@/lib/types'
+
+/** Order sources with their own risk settings */
+const ORDER_SOURCES: { id: string; label: string }[] = [
+  { id: 'sniper', label: 'Sniper' },
+  { id: 'mirror', label: 'Mirror' },
+  { id: 'dipbuyer', label: 'Dip Buyer' },
+  { id: 'sentinel', label: 'Sentinel' },
+  { id: 'manual', label: 'Manual' },
+  { id: 'mobile', label: 'Mobile' },
+]
+
+/** Modules whose buys can carry their own trade size cap */
+const CAPPED_MODULES = ORDER_SOURCES.filter(({ id }) => ['sniper', 'mirror', 'dipbuyer'].includes(id))
 
 interface RiskTabProps {
   limits: RiskLimits
   setLimits: React.Dispatch<React.SetStateAction<RiskLimits>>
+  splitConfig: SplitSellConfig
+  setSplitConfig: React.Dispatch<React.SetStateAction<SplitSellConfig>>
+  priceFreshness: PriceFreshnessPolicy
+  setPriceFreshness: React.Dispatch<React.SetStateAction<PriceFreshnessPolicy>>
+  coinPrecision: CoinPrecision
+  setCoinPrecision: React.Dispatch<React.SetStateAction<CoinPrecision>>
+  adaptivePoll: AdaptivePollPolicy
+  setAdaptivePoll: React.Dispatch<React.SetStateAction<AdaptivePollPolicy>>
   onChanged: () => void
 }
 
-export function RiskTab({ limits, setLimits, onChanged }: RiskTabProps) {
+export function RiskTab({ limits, setLimits, splitConfig, setSplitConfig, priceFreshness, setPriceFreshness, coinPrecision, setCoinPrecision, adaptivePoll, setAdaptivePoll, onChanged }: RiskTabProps) {
+  const [newPrecisionCoin, setNewPrecisionCoin] = useState('')
+  const [newPrecisionDecimals, setNewPrecisionDecimals] = useState(8)
+  const [drawdown, setDrawdown] = useState<DrawdownStatus | null>(null)
+  const [executorStats, setExecutorStats] = useState<ExecutorStats | null>(null)
+
+  useEffect(() => {
+    invoke<DrawdownStatus>('get_drawdown_status').then(setDrawdown).catch(() => setDrawdown(null))
+    invoke<ExecutorStats>('get_executor_stats').then(setExecutorStats).catch(() => setExecutorStats(null))
+  }, [])
+
   const update = <K extends keyof RiskLimits>(key: K, value: RiskLimits[K]) => {
     setLimits(prev => ({ ...prev, [key]: value }))
     onChanged()
   }
 
+  const updateModuleCap = (module: string, cap: number) => {
+    setLimits(prev => ({ ...prev, moduleMaxTradeUsd: { ...(prev.moduleMaxTradeUsd ?? {}), [module]: cap } }))
+    onChanged()
+  }
+
+  const toggleNewCoinSource = (module: string, allowed: boolean) => {
+    setLimits(prev => {
+      const sources = (prev.newCoinAllowedSources ?? []).filter(s => s !== module)
+      return { ...prev, newCoinAllowedSources: allowed ? [...sources, module] : sources }
+    })
+    onChanged()
+  }
+
+  const updateSplit = <K extends keyof SplitSellConfig>(key: K, value: SplitSellConfig[K]) => {
+    setSplitConfig(prev => ({ ...prev, [key]: value }))
+    onChanged()
+  }
+
+  const updateFreshness = <K extends keyof PriceFreshnessPolicy>(key: K, value: PriceFreshnessPolicy[K]) => {
+    setPriceFreshness(prev => ({ ...prev, [key]: value }))
+    onChanged()
+  }
+
+  const setDefaultDecimals = (decimals: number) => {
+    setCoinPrecision(prev => ({ ...prev, defaultDecimals: decimals }))
+    onChanged()
+  }
+
+  const addPrecisionOverride = () => {
+    const coin = newPrecisionCoin.trim().toUpperCase()
+    if (!coin) return
+    setCoinPrecision(prev => ({ ...prev, coinDecimals: { ...prev.coinDecimals, [coin]: newPrecisionDecimals } }))
+    setNewPrecisionCoin('')
+    onChanged()
+  }
+
+  const removePrecisionOverride = (coin: string) => {
+    setCoinPrecision(prev => {
+      const { [coin]: _removed, ...rest } = prev.coinDecimals
+      return { ...prev, coinDecimals: rest }
+    })
+    onChanged()
+  }
+
+  const updatePoll = <K extends keyof AdaptivePollPolicy>(key: K, value: AdaptivePollPolicy[K]) => {
+    setAdaptivePoll(prev => ({ ...prev, [key]: value }))
+    onChanged()
+  }
+
   return (
     <div className="space-y-6">
       {/* Position & Volume Limits */}
@@ -118,6 +212,102 @@ export function RiskTab({ limits, setLimits, onChanged }: RiskTabProps) {
               Pause buys for N seconds after a loss
             </p>
           </div>
+
+          {/* Stop-Loss Cool-off */}
+          <div className="form-field col-span-2">
+            <label className="form-label">
+              <Snowflake className="w-4 h-4 text-rose-400" />
+              Coin Cool-off After Stop Loss
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                step="300"
+                value={limits.stopLossCooldownSecs ?? 0}
+                onChange={e => update('stopLossCooldownSecs', Math.max(0, parseInt(e.target.value) || 0))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+            <p className="form-hint">
+              After a sentinel stop loss sells a coin, no module (sniper, mirror, dip buyer) may buy that coin again for this long. Take-profit exits don't count; manual buys are never blocked. 0 = off
+            </p>
+          </div>
+
+          {/* Round-Trip Exit Impact */}
+          <div className="form-field col-span-2">
+            <label className="form-label">
+              <Activity className="w-4 h-4 text-amber-400" />
+              Max Exit Impact
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="100"
+                step="1"
+                value={limits.maxExitImpactPct ?? 0}
+                onChange={e => update('maxExitImpactPct', Math.max(0, parseFloat(e.target.value) || 0))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">%</span>
+            </div>
+            <p className="form-hint">
+              Before a module buys, estimate selling the whole resulting position (including coins already held). Skip the buy if that exit would move the price more than this. 0 = off
+            </p>
+          </div>
+
+          {/* Drawdown Circuit Breaker */}
+          <div className="form-field col-span-2">
+            <label className="form-label">
+              <TrendingDown className="w-4 h-4 text-rose-400" />
+              Max Portfolio Drawdown
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="99"
+                step="1"
+                value={limits.maxDrawdownPct ?? 0}
+                onChange={e => update('maxDrawdownPct', Math.min(99, Math.max(0, parseFloat(e.target.value) || 0)))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">%</span>
+            </div>
+            <p className="form-hint">
+              Stop every buy (including manual) once the portfolio is this far below today's peak value. Sells keep working, and the peak resets each day. 0 = off
+            </p>
+            {drawdown && drawdown.peakValue > 0 && (
+              <p className={`text-xs mt-1 ${drawdown.tripped ? 'text-rose-400' : 'text-foreground-muted'}`}>
+                Today: ${drawdown.currentValue.toFixed(2)} vs peak ${drawdown.peakValue.toFixed(2)} ({drawdown.drawdownPct.toFixed(1)}% down)
+                {drawdown.tripped && ' — buys paused'}
+              </p>
+            )}
+          </div>
+
+          {/* Frozen Reserve */}
+          <div className="form-field col-span-2">
+            <label className="form-label">
+              <Snowflake className="w-4 h-4 text-cyan-400" />
+              Frozen Reserve
+            </label>
+            <div className="flex items-center gap-2">
+              <span className="text-foreground-muted">$</span>
+              <input
+                type="number"
+                min="0"
+                step="1000"
+                value={limits.frozenReserveUsd}
+                onChange={e => update('frozenReserveUsd', parseFloat(e.target.value) || 0)}
+                className="input flex-1"
+              />
+            </div>
+            <p className="form-hint">
+              Cash no automated buy may touch. Manual trades can override it per trade.
+            </p>
+          </div>
         </div>
       </div>
 
@@ -197,9 +387,496 @@ export function RiskTab({ limits, setLimits, onChanged }: RiskTabProps) {
               Minimum delay between consecutive trades
             </p>
           </div>
+
+          {/* Adaptive Rate Limit */}
+          <div className="form-field col-span-3">
+            <label className="form-label">
+              <Timer className="w-4 h-4 text-purple-400" />
+              Adaptive Rate Limit
+            </label>
+            <div className="flex items-center gap-3">
+              <label className="flex items-center gap-2 text-sm">
+                <input
+                  type="checkbox"
+                  checked={limits.adaptiveRateLimit ?? true}
+                  onChange={e => update('adaptiveRateLimit', e.target.checked)}
+                />
+                Back off after 429s, up to
+              </label>
+              <input
+                type="number"
+                min={limits.rateLimitMs}
+                max="60000"
+                step="500"
+                value={limits.maxRateLimitMs ?? 10000}
+                onChange={e => update('maxRateLimitMs', parseInt(e.target.value) || 10000)}
+                disabled={!(limits.adaptiveRateLimit ?? true)}
+                className="input w-32"
+              />
+              <span className="text-foreground-muted text-sm">ms</span>
+            </div>
+            <p className="form-hint">
+              Each rate-limited trade doubles the delay; it steps back down to the rate limit above once 429s stop.
+              {executorStats && executorStats.requestsSent > 0 &&
+                ` Now ${executorStats.currentDelayMs}ms · ${executorStats.windowRateLimitHits} of ${executorStats.windowRequests} trade requests rate-limited in the last ${Math.round(executorStats.windowSecs / 60)} min.`}
+            </p>
+          </div>
+
+          {/* Automation Throttle */}
+          <div className="form-field col-span-3">
+            <label className="form-label">
+              <Gauge className="w-4 h-4 text-orange-400" />
+              Automation Throttle
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                step="5"
+                value={limits.minAutomatedIntervalSecs}
+                onChange={e => update('minAutomatedIntervalSecs', parseInt(e.target.value) || 0)}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+            <p className="form-hint">
+              Minimum time between automated trades (sniper, dip buyer, mirror). Faster ones wait in the queue; sentinel exits and manual trades are never held. 0 = off
+            </p>
+          </div>
+
+          {/* Server Minimum Round-Up */}
+          <div className="form-field col-span-3">
+            <label className="form-label">
+              <DollarSign className="w-4 h-4 text-emerald-400" />
+              Minimum Trade Round-Up
+            </label>
+            <div className="flex items-center gap-2">
+              <span className="text-foreground-muted">$</span>
+              <input
+                type="number"
+                min="0"
+                step="0.25"
+                value={limits.minTradeRoundUpUsd ?? 0}
+                onChange={e => update('minTradeRoundUpUsd', Math.max(0, parseFloat(e.target.value) || 0))}
+                className="input flex-1"
+              />
+            </div>
+            <p className="form-hint">
+              Once the server rejects a buy as too small, later buys under its minimum are rounded up if they fall short by at most this much, and skipped otherwise. 0 = always skip
+            </p>
+          </div>
+        </div>
+      </div>
+
+      {/* Trade Size Caps */}
+      <div className="card">
+        <div className="flex items-center gap-2 mb-4">
+          <Ban className="w-5 h-5 text-rose-400" />
+          <h2 className="text-lg font-semibold">Trade Size Caps</h2>
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          Last-line safety: any single automated buy worth more than its cap is rejected by the executor, whatever the module's own settings say. Module caps only tighten the global cap. Sells and your own trades are never capped. Set to 0 to disable.
+        </p>
+
+        <div className="grid grid-cols-3 gap-4">
+          <div className="form-field col-span-3">
+            <label className="form-label">
+              <DollarSign className="w-4 h-4 text-rose-400" />
+              Global Max Trade
+            </label>
+            <div className="flex items-center gap-2">
+              <span className="text-foreground-muted">$</span>
+              <input
+                type="number"
+                min="0"
+                step="100"
+                value={limits.maxTradeUsd ?? 0}
+                onChange={e => update('maxTradeUsd', parseFloat(e.target.value) || 0)}
+                className="input flex-1"
+              />
+            </div>
+          </div>
+
+          {CAPPED_MODULES.map(({ id, label }) => (
+            <div key={id} className="form-field">
+              <label className="form-label">{label}</label>
+              <div className="flex items-center gap-2">
+                <span className="text-foreground-muted">$</span>
+                <input
+                  type="number"
+                  min="0"
+                  step="100"
+                  value={limits.moduleMaxTradeUsd?.[id] ?? 0}
+                  onChange={e => updateModuleCap(id, parseFloat(e.target.value) || 0)}
+                  className="input flex-1"
+                />
+              </div>
+            </div>
+          ))}
         </div>
       </div>
 
+      {/* New Coin Gate */}
+      <div className="card">
+        <div className="flex items-center gap-2 mb-4">
+          <Baby className="w-5 h-5 text-amber-400" />
+          <h2 className="text-lg font-semibold">New Coin Gate</h2>
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          The executor rejects buys into coins younger than the minimum age or with fewer 1-minute candles of price history, whichever module sends them. Modules ticked below are allowed in anyway — tick the sniper if it should keep buying fresh launches. Sells are never gated. Set to 0 to disable.
+        </p>
+
+        <div className="grid grid-cols-2 gap-4">
+          <div className="form-field">
+            <label className="form-label">
+              <Hourglass className="w-4 h-4 text-amber-400" />
+              Minimum Coin Age
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                step="60"
+                value={limits.minCoinAgeSecs ?? 0}
+                onChange={e => update('minCoinAgeSecs', Math.max(0, parseInt(e.target.value) || 0))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+          </div>
+          <div className="form-field">
+            <label className="form-label">
+              <Hash className="w-4 h-4 text-amber-400" />
+              Minimum Candles
+            </label>
+            <input
+              type="number"
+              min="0"
+              step="1"
+              value={limits.minCandleCount ?? 0}
+              onChange={e => update('minCandleCount', Math.max(0, parseInt(e.target.value) || 0))}
+              className="input"
+            />
+          </div>
+        </div>
+
+        <div className="flex flex-wrap gap-4 mt-4">
+          {/* Sentinel only sells, so it never meets the gate */}
+          {ORDER_SOURCES.filter(({ id }) => id !== 'sentinel').map(({ id, label }) => (
+            <label key={id} className="flex items-center gap-2 text-sm cursor-pointer">
+              <input
+                type="checkbox"
+                checked={(limits.newCoinAllowedSources ?? []).includes(id)}
+                onChange={e => toggleNewCoinSource(id, e.target.checked)}
+              />
+              Allow {label}
+            </label>
+          ))}
+        </div>
+      </div>
+
+      {/* Split Sells */}
+      <div className="card">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Split className="w-5 h-5 text-emerald-400" />
+            <h2 className="text-lg font-semibold">Split Sells</h2>
+          </div>
+          <label className="flex items-center gap-2 text-sm cursor-pointer">
+            <input
+              type="checkbox"
+              checked={splitConfig.sentinelEnabled}
+              onChange={e => updateSplit('sentinelEnabled', e.target.checked)}
+            />
+            Split sentinel sells
+          </label>
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          Sell large positions in several smaller tranches to reduce slippage. Manual sells can opt in from the trade dialog.
+        </p>
+
+        <div className="grid grid-cols-2 gap-4">
+          {/* Tranches */}
+          <div className="form-field">
+            <label className="form-label">
+              <Hash className="w-4 h-4 text-purple-400" />
+              Tranches
+            </label>
+            <input
+              type="number"
+              min="1"
+              max="10"
+              step="1"
+              value={splitConfig.tranches}
+              onChange={e => updateSplit('tranches', Math.min(10, Math.max(1, parseInt(e.target.value) || 1)))}
+              className="input"
+            />
+            <p className="form-hint">
+              Number of sell orders per split (1–10)
+            </p>
+          </div>
+
+          {/* Min Value */}
+          <div className="form-field">
+            <label className="form-label">
+              <DollarSign className="w-4 h-4 text-emerald-400" />
+              Minimum Sentinel Sell
+            </label>
+            <div className="flex items-center gap-2">
+              <span className="text-foreground-muted">$</span>
+              <input
+                type="number"
+                min="0"
+                step="100"
+                value={splitConfig.minValueUsd}
+                onChange={e => updateSplit('minValueUsd', parseFloat(e.target.value) || 0)}
+                className="input flex-1"
+              />
+            </div>
+            <p className="form-hint">
+              Only split sentinel sells worth at least this much
+            </p>
+          </div>
+
+          {/* Spacing */}
+          <div className="form-field">
+            <label className="form-label">
+              <Clock className="w-4 h-4 text-amber-400" />
+              Tranche Spacing
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="30000"
+                step="500"
+                value={splitConfig.spacingMs}
+                onChange={e => updateSplit('spacingMs', parseInt(e.target.value) || 0)}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">ms</span>
+            </div>
+            <p className="form-hint">
+              Delay between tranches
+            </p>
+          </div>
+
+          {/* Max Total Time */}
+          <div className="form-field">
+            <label className="form-label">
+              <Timer className="w-4 h-4 text-rose-400" />
+              Max Total Time
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="120"
+                step="5"
+                value={splitConfig.maxTotalSecs}
+                onChange={e => updateSplit('maxTotalSecs', parseInt(e.target.value) || 0)}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+            <p className="form-hint">
+              Spacing shrinks so the whole split finishes within this window
+            </p>
+          </div>
+        </div>
+      </div>
+
+      {/* Price Freshness */}
+      <div className="card">
+        <div className="flex items-center gap-2 mb-4">
+          <Hourglass className="w-5 h-5 text-sky-400" />
+          <h2 className="text-lg font-semibold">Price Freshness</h2>
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          How old a cached coin quote may be before it is fetched again. Set to 0 to always fetch a fresh quote.
+        </p>
+
+        <div className="grid grid-cols-2 gap-4">
+          <div className="form-field">
+            <label className="form-label">
+              <Clock className="w-4 h-4 text-sky-400" />
+              Trade Preview
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="300"
+                step="1"
+                value={priceFreshness.tradePreviewMaxAgeSecs}
+                onChange={e => updateFreshness('tradePreviewMaxAgeSecs', Math.max(0, parseInt(e.target.value) || 0))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+            <p className="form-hint">
+              Max quote age shown in the trade dialog
+            </p>
+          </div>
+
+          <div className="form-field">
+            <label className="form-label">
+              <Clock className="w-4 h-4 text-amber-400" />
+              Automated Buys
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="0"
+                max="300"
+                step="1"
+                value={priceFreshness.automatedBuyMaxAgeSecs}
+                onChange={e => updateFreshness('automatedBuyMaxAgeSecs', Math.max(0, parseInt(e.target.value) || 0))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+            <p className="form-hint">
+              Max quote age the dip buyer may act on
+            </p>
+          </div>
+        </div>
+      </div>
+
+      {/* Coin Precision */}
+      <div className="card">
+        <div className="flex items-center gap-2 mb-4">
+          <Binary className="w-5 h-5 text-sky-400" />
+          <h2 className="text-lg font-semibold">Coin Precision</h2>
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          Decimal places sell amounts are truncated to before they are sent. The server accepts 8; only change this if it starts rejecting amounts.
+        </p>
+
+        <div className="form-field mb-4">
+          <label className="form-label">
+            <Hash className="w-4 h-4 text-sky-400" />
+            Default Decimals
+          </label>
+          <input
+            type="number"
+            min="0"
+            max="15"
+            step="1"
+            value={coinPrecision.defaultDecimals}
+            onChange={e => setDefaultDecimals(Math.min(15, Math.max(0, parseInt(e.target.value) || 0)))}
+            className="input w-32"
+          />
+        </div>
+
+        <div className="flex gap-2 mb-4">
+          <input
+            type="text"
+            value={newPrecisionCoin}
+            onChange={e => setNewPrecisionCoin(e.target.value)}
+            placeholder="Coin symbol"
+            className="input flex-1"
+            onKeyDown={e => { if (e.key === 'Enter') addPrecisionOverride() }}
+          />
+          <input
+            type="number"
+            min="0"
+            max="15"
+            step="1"
+            value={newPrecisionDecimals}
+            onChange={e => setNewPrecisionDecimals(Math.min(15, Math.max(0, parseInt(e.target.value) || 0)))}
+            className="input w-24"
+          />
+          <button onClick={addPrecisionOverride} disabled={!newPrecisionCoin.trim()} className="px-4 py-2 rounded-lg bg-zinc-700 hover:bg-zinc-600 text-white transition-colors disabled:opacity-50">
+            <Plus className="w-4 h-4" />
+          </button>
+        </div>
+
+        <div className="flex flex-wrap gap-2">
+          {Object.keys(coinPrecision.coinDecimals).length === 0 ? (
+            <span className="text-sm text-foreground-muted">Every coin uses the default</span>
+          ) : (
+            Object.entries(coinPrecision.coinDecimals).map(([coin, decimals]) => (
+              <span key={coin} className="inline-flex items-center gap-1 px-3 py-1 rounded-full bg-sky-500/20 text-sky-400 text-sm">
+                ${coin} · {decimals} dp
+                <button onClick={() => removePrecisionOverride(coin)} className="p-0.5 rounded hover:bg-sky-500/30 transition-colors">
+                  <X className="w-3 h-3" />
+                </button>
+              </span>
+            ))
+          )}
+        </div>
+      </div>
+
+      {/* Adaptive Polling */}
+      <div className="card">
+        <div className="flex items-center justify-between mb-4">
+          <div className="flex items-center gap-2">
+            <Activity className="w-5 h-5 text-violet-400" />
+            <h2 className="text-lg font-semibold">Adaptive Polling</h2>
+          </div>
+          <label className="flex items-center gap-2 text-sm cursor-pointer">
+            <input
+              type="checkbox"
+              checked={adaptivePoll.enabled}
+              onChange={e => updatePoll('enabled', e.target.checked)}
+            />
+            Enabled
+          </label>
+        </div>
+        <p className="text-sm text-foreground-muted mb-4">
+          Sniper, mirror and dip buyer poll faster while the feed is busy and back off when it is quiet or the API rate limits. Disabled, each module polls at its own interval.
+        </p>
+
+        <div className="grid grid-cols-2 gap-4">
+          <div className="form-field">
+            <label className="form-label">
+              <Timer className="w-4 h-4 text-violet-400" />
+              Fastest Interval
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="1"
+                max="300"
+                step="1"
+                value={adaptivePoll.minSecs}
+                disabled={!adaptivePoll.enabled}
+                onChange={e => updatePoll('minSecs', Math.max(1, parseInt(e.target.value) || 1))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+          </div>
+
+          <div className="form-field">
+            <label className="form-label">
+              <Timer className="w-4 h-4 text-amber-400" />
+              Slowest Interval
+            </label>
+            <div className="flex items-center gap-2">
+              <input
+                type="number"
+                min="1"
+                max="600"
+                step="1"
+                value={adaptivePoll.maxSecs}
+                disabled={!adaptivePoll.enabled}
+                onChange={e => updatePoll('maxSecs', Math.max(1, parseInt(e.target.value) || 1))}
+                className="input flex-1"
+              />
+              <span className="text-foreground-muted text-sm">sec</span>
+            </div>
+            <p className="form-hint">
+              Must be at least the fastest interval
+            </p>
+          </div>
+        </div>
+      </div>
+
+      {/* Entry Script */}
+      <EntryScriptCard />
+
       {/* Info */}
       <div className="card bg-blue-500/10 border-blue-500/30">
         <h3 className="font-semibold text-blue-400 mb-2">About Risk Limits</h3>
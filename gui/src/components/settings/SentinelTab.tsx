@@ -9,8 +9,13 @@ import {
   X,
   Timer,
   Clock,
+  Layers,
+  RotateCcw,
+  Droplets,
+  Scale,
+  Target,
 } from 'lucide-react'
-import type { AppSettings, SentinelDefaults } from '@/lib/types'
+import type { AppSettings, EntryPriceSource, SentinelDefaults } from '@/lib/types'
 import { ToggleSwitch } from '@/components/ui/FormattedInput'
 import type { SentinelMonitorStatus } from './SettingsLayout'
 
@@ -25,7 +30,7 @@ interface SentinelTabProps {
 export function SentinelTab({ settings, setSettings, monitor, setMonitor, onChanged }: SentinelTabProps) {
   const [newBlacklistCoin, setNewBlacklistCoin] = useState('')
 
-  const updateDefault = (key: keyof SentinelDefaults, value: number | boolean | null) => {
+  const updateDefault = (key: keyof SentinelDefaults, value: number | boolean | EntryPriceSource | null) => {
     setSettings(prev => ({
       ...prev,
       sentinelDefaults: { ...prev.sentinelDefaults, [key]: value },
@@ -148,6 +153,27 @@ export function SentinelTab({ settings, setSettings, monitor, setMonitor, onChan
             </div>
             <p className="form-hint">Percentage of holding to sell when triggered</p>
           </div>
+
+          {/* Entry Price Source */}
+          <div className="form-field">
+            <label className="form-label">
+              <Target className="w-4 h-4 text-blue-400" />
+              Auto-Sentinel Entry Price
+            </label>
+            <select
+              value={settings.sentinelDefaults.entryPriceSource ?? 'portfolioAverage'}
+              onChange={e => updateDefault('entryPriceSource', e.target.value as EntryPriceSource)}
+              className="input"
+            >
+              <option value="portfolioAverage">Portfolio average</option>
+              <option value="fillPrice">Fill price</option>
+              <option value="currentMarket">Current market</option>
+            </select>
+            <p className="form-hint">
+              Where sniper, mirror and dip buyer sentinels start from. The average protects repeat buys
+              of a coin; the fill price is exact to the latest buy.
+            </p>
+          </div>
         </div>
       </div>
 
@@ -192,6 +218,182 @@ export function SentinelTab({ settings, setSettings, monitor, setMonitor, onChan
             </p>
           )}
         </div>
+
+        <div className="p-4 rounded-lg bg-background mt-3">
+          <div className="flex items-center justify-between mb-2">
+            <label className="flex items-center gap-2 text-sm text-foreground-muted">
+              <Layers className="w-4 h-4 text-purple-400" />
+              Max Sells per Check
+            </label>
+            <span className="text-sm font-mono text-white">
+              {monitor.maxSellsPerTick === 0 ? 'Unlimited' : monitor.maxSellsPerTick}
+            </span>
+          </div>
+          <input
+            type="range"
+            min="0"
+            max="20"
+            step="1"
+            value={monitor.maxSellsPerTick}
+            onChange={e => {
+              setMonitor(prev => ({ ...prev, maxSellsPerTick: parseInt(e.target.value) }))
+              onChanged()
+            }}
+            className="w-full accent-emerald-500"
+          />
+          <p className="text-xs text-foreground-muted mt-1">
+            When many sentinels trigger at once (e.g. a market crash), only this many sell per check —
+            the rest wait for the next check, in trigger order. 0 sells everything immediately.
+          </p>
+        </div>
+
+        <div className="p-4 rounded-lg bg-background mt-3">
+          <label className="flex items-center gap-2 text-sm text-foreground-muted mb-2">
+            <RotateCcw className="w-4 h-4 text-amber-400" />
+            Failed Sell Retry
+          </label>
+          <div className="grid grid-cols-3 gap-2">
+            {([
+              ['baseSecs', 'First retry (s)', 10],
+              ['maxSecs', 'Max delay (s)', 1],
+              ['maxFailures', 'Attempts', 1],
+            ] as const).map(([key, label, min]) => (
+              <div key={key}>
+                <span className="text-xs text-foreground-muted">{label}</span>
+                <input
+                  type="number"
+                  min={min}
+                  step="1"
+                  value={monitor.sellRetry[key]}
+                  onChange={e => {
+                    const value = Math.max(min, parseInt(e.target.value) || min)
+                    setMonitor(prev => ({ ...prev, sellRetry: { ...prev.sellRetry, [key]: value } }))
+                    onChanged()
+                  }}
+                  className="input w-full"
+                />
+              </div>
+            ))}
+          </div>
+          <p className="text-xs text-foreground-muted mt-1">
+            A failed sell is retried first after the base delay (at least 10s), then twice as long each time up to
+            the max, on the next check once the delay is up. The sentinel is deactivated after this many failures.
+          </p>
+        </div>
+
+        <div className="p-4 rounded-lg bg-background mt-3">
+          <label className="flex items-center gap-2 text-sm text-foreground-muted mb-2">
+            <Scale className="w-4 h-4 text-blue-400" />
+            Trigger Price Source
+          </label>
+          <div className="flex gap-2">
+            {([
+              ['last_trade', 'Last Trade'],
+              ['mark', 'Pool Mark Price'],
+            ] as const).map(([value, label]) => (
+              <button
+                key={value}
+                onClick={() => {
+                  setMonitor(prev => ({ ...prev, priceSource: value }))
+                  onChanged()
+                }}
+                className={`flex-1 py-2 rounded-lg text-sm font-medium transition-colors ${
+                  monitor.priceSource === value
+                    ? 'bg-emerald-600 text-white'
+                    : 'bg-background-tertiary text-foreground-muted hover:text-white'
+                }`}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+          <p className="text-xs text-foreground-muted mt-1">
+            In thin markets the last trade can be far from what a sell would actually fetch. Mark price is
+            computed from the pool reserves. Individual sentinels can override this.
+          </p>
+        </div>
+
+        <div className="p-4 rounded-lg bg-background mt-3">
+          <label className="flex items-center gap-2 text-sm text-foreground-muted mb-2">
+            <Droplets className="w-4 h-4 text-cyan-400" />
+            Minimum Liquidity to Hold ($)
+          </label>
+          <input
+            type="number"
+            min="0"
+            step="any"
+            value={monitor.minLiquidityUsd}
+            onChange={e => {
+              setMonitor(prev => ({ ...prev, minLiquidityUsd: Math.max(0, parseFloat(e.target.value) || 0) }))
+              onChanged()
+            }}
+            className="input w-full"
+          />
+          <p className="text-xs text-foreground-muted mt-1">
+            Sells a held coin when its pool drops below this much base currency, before a drained pool
+            leaves nothing to sell into. 0 disables the rule.
+          </p>
+        </div>
+
+        <div className="p-4 rounded-lg bg-background mt-3">
+          <label className="flex items-center gap-2 text-sm text-foreground-muted mb-2">
+            <TrendingUp className="w-4 h-4 text-emerald-400" />
+            Take-Profit During a Dump
+          </label>
+          <div className="flex gap-2">
+            {([
+              ['proceed', 'Sell Anyway'],
+              ['cappedMarket', 'Capped Sell'],
+              ['abortRearm', 'Stay Armed'],
+            ] as const).map(([value, label]) => (
+              <button
+                key={value}
+                onClick={() => {
+                  setMonitor(prev => ({ ...prev, tpGap: { ...prev.tpGap, action: value } }))
+                  onChanged()
+                }}
+                className={`flex-1 py-2 rounded-lg text-sm font-medium transition-colors ${
+                  monitor.tpGap.action === value
+                    ? 'bg-emerald-600 text-white'
+                    : 'bg-background-tertiary text-foreground-muted hover:text-white'
+                }`}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+          {monitor.tpGap.action !== 'proceed' && (
+            <div className="grid grid-cols-2 gap-2 mt-2">
+              {([
+                ['gapPct', 'Gap below target (%)'],
+                ['maxSlippagePct', 'Max slippage (%)'],
+              ] as const).map(([key, label]) => (
+                <div key={key}>
+                  <span className="text-xs text-foreground-muted">{label}</span>
+                  <input
+                    type="number"
+                    min="0.1"
+                    max="99"
+                    step="0.1"
+                    value={monitor.tpGap[key]}
+                    onChange={e => {
+                      const value = Math.min(99, Math.max(0.1, parseFloat(e.target.value) || 0.1))
+                      setMonitor(prev => ({ ...prev, tpGap: { ...prev.tpGap, [key]: value } }))
+                      onChanged()
+                    }}
+                    className="input w-full"
+                    disabled={key === 'maxSlippagePct' && monitor.tpGap.action !== 'cappedMarket'}
+                  />
+                </div>
+              ))}
+            </div>
+          )}
+          <p className="text-xs text-foreground-muted mt-1">
+            A take-profit can fire on the last trade while the pool has already dumped well below the target.
+            Capped Sell only sells what keeps the fill within the slippage cap and keeps watching the rest;
+            Stay Armed skips the sell until the pool recovers.
+          </p>
+        </div>
       </div>
 
       {/* Auto-Manage */}
@@ -11,7 +11,7 @@ import {
   Save,
   RefreshCw,
 } from 'lucide-react'
-import type { AppSettings, RiskLimits, NotificationConfig, SniperConfig } from '@/lib/types'
+import type { AppSettings, RiskLimits, SplitSellConfig, PriceFreshnessPolicy, CoinPrecision, AdaptivePollPolicy, NotificationConfig, SniperConfig, SentinelPriceSource, SentinelSellRetry, TpGapPolicy, WashSensitivity } from '@/lib/types'
 import { GeneralTab } from './GeneralTab'
 import { SentinelTab } from './SentinelTab'
 import { SniperTab } from './SniperTab'
@@ -39,6 +39,7 @@ const DEFAULT_SETTINGS: AppSettings = {
     takeProfitPct: 100,
     trailingStopPct: 10,
     sellPercentage: 100,
+    entryPriceSource: 'portfolioAverage',
   },
   autoManageSentinels: false,
   blacklistedCoins: [],
@@ -52,6 +53,43 @@ const DEFAULT_RISK_LIMITS: RiskLimits = {
   retryCount: 2,
   retryDelayMs: 1000,
   rateLimitMs: 500,
+  adaptiveRateLimit: true,
+  maxRateLimitMs: 10000,
+  frozenReserveUsd: 0,
+  minAutomatedIntervalSecs: 0,
+  maxTradeUsd: 0,
+  moduleMaxTradeUsd: {},
+  minCoinAgeSecs: 0,
+  minCandleCount: 0,
+  newCoinAllowedSources: ['manual'],
+  minTradeRoundUpUsd: 0,
+  stopLossCooldownSecs: 0,
+  maxExitImpactPct: 0,
+  maxDrawdownPct: 0,
+}
+
+const DEFAULT_SPLIT_SELL_CONFIG: SplitSellConfig = {
+  sentinelEnabled: false,
+  minValueUsd: 1000,
+  tranches: 3,
+  spacingMs: 3000,
+  maxTotalSecs: 15,
+}
+
+const DEFAULT_PRICE_FRESHNESS: PriceFreshnessPolicy = {
+  tradePreviewMaxAgeSecs: 10,
+  automatedBuyMaxAgeSecs: 5,
+}
+
+const DEFAULT_COIN_PRECISION: CoinPrecision = {
+  defaultDecimals: 8,
+  coinDecimals: {},
+}
+
+const DEFAULT_ADAPTIVE_POLL: AdaptivePollPolicy = {
+  enabled: true,
+  minSecs: 2,
+  maxSecs: 30,
 }
 
 const DEFAULT_NOTIFICATION_CONFIG: NotificationConfig = {
@@ -62,6 +100,8 @@ const DEFAULT_NOTIFICATION_CONFIG: NotificationConfig = {
   riskAlerts: true,
   sessionAlerts: true,
   tradeConfirmations: false,
+  dedupWindowSecs: 60,
+  dedupOverrides: { trade: 0 },
 }
 
 export interface MirrorConfigState {
@@ -74,6 +114,11 @@ export interface MirrorConfigState {
   trailingStopPct: number | null
   skipIfAlreadyHeld: boolean
   pollIntervalSecs: number
+  recentTradesDepth: number
+  maxTrackedWhales: number
+  washTradeSensitivity: WashSensitivity
+  maxRiskScore: number
+  minWhaleScore: number
 }
 
 const DEFAULT_MIRROR_CONFIG: MirrorConfigState = {
@@ -86,12 +131,22 @@ const DEFAULT_MIRROR_CONFIG: MirrorConfigState = {
   trailingStopPct: 15,
   skipIfAlreadyHeld: true,
   pollIntervalSecs: 0,
+  recentTradesDepth: 0,
+  maxTrackedWhales: 0,
+  washTradeSensitivity: 'off',
+  maxRiskScore: 0,
+  minWhaleScore: 0,
 }
 
 export interface SentinelMonitorStatus {
   status: string
   intervalSecs: number
   isPaused: boolean
+  maxSellsPerTick: number
+  sellRetry: SentinelSellRetry
+  priceSource: SentinelPriceSource
+  minLiquidityUsd: number
+  tpGap: TpGapPolicy
 }
 
 export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => boolean) | null) => void }) {
@@ -104,6 +159,10 @@ export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => b
   // State for each settings domain
   const [settings, setSettings] = useState<AppSettings>(DEFAULT_SETTINGS)
   const [riskLimits, setRiskLimits] = useState<RiskLimits>(DEFAULT_RISK_LIMITS)
+  const [splitConfig, setSplitConfig] = useState<SplitSellConfig>(DEFAULT_SPLIT_SELL_CONFIG)
+  const [priceFreshness, setPriceFreshness] = useState<PriceFreshnessPolicy>(DEFAULT_PRICE_FRESHNESS)
+  const [coinPrecision, setCoinPrecision] = useState<CoinPrecision>(DEFAULT_COIN_PRECISION)
+  const [adaptivePoll, setAdaptivePoll] = useState<AdaptivePollPolicy>(DEFAULT_ADAPTIVE_POLL)
   const [notifConfig, setNotifConfig] = useState<NotificationConfig>(DEFAULT_NOTIFICATION_CONFIG)
   const [sniperConfig, setSniperConfig] = useState<SniperConfig | null>(null)
   const [mirrorConfig, setMirrorConfig] = useState<MirrorConfigState>(DEFAULT_MIRROR_CONFIG)
@@ -111,6 +170,11 @@ export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => b
     status: 'Running',
     intervalSecs: 10,
     isPaused: false,
+    maxSellsPerTick: 0,
+    sellRetry: { baseSecs: 10, maxSecs: 60, maxFailures: 3 },
+    priceSource: 'last_trade',
+    minLiquidityUsd: 0,
+    tpGap: { action: 'proceed', gapPct: 5, maxSlippagePct: 3 },
   })
 
   const markChanged = useCallback(() => setHasChanges(true), [])
@@ -139,6 +203,27 @@ export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => b
         setRiskLimits(limits)
       } catch { /* use defaults */ }
 
+      // Load split sell config
+      try {
+        const split = await invoke<SplitSellConfig>('get_split_sell_config')
+        setSplitConfig(split)
+      } catch { /* use defaults */ }
+
+      // Load price freshness policy
+      try {
+        setPriceFreshness(await invoke<PriceFreshnessPolicy>('get_price_freshness'))
+      } catch { /* use defaults */ }
+
+      // Load coin amount precision
+      try {
+        setCoinPrecision(await invoke<CoinPrecision>('get_coin_precision'))
+      } catch { /* use defaults */ }
+
+      // Load adaptive poll bounds
+      try {
+        setAdaptivePoll(await invoke<AdaptivePollPolicy>('get_adaptive_poll'))
+      } catch { /* use defaults */ }
+
       // Load notification config
       try {
         const config = await invoke<NotificationConfig>('get_notification_config')
@@ -212,6 +297,10 @@ export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => b
 
       // Save risk limits
       await invoke('set_risk_limits', { limits: riskLimits })
+      await invoke('set_split_sell_config', { config: splitConfig })
+      await invoke('set_price_freshness', { policy: priceFreshness })
+      await invoke('set_coin_precision', { precision: coinPrecision })
+      await invoke('set_adaptive_poll', { policy: adaptivePoll })
 
       // Save notification config
       await invoke('set_notification_config', { config: notifConfig })
@@ -226,6 +315,11 @@ export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => b
 
       // Save sentinel interval
       await invoke('set_sentinel_monitor_interval', { intervalSecs: sentinelMonitor.intervalSecs })
+      await invoke('set_sentinel_max_sells_per_tick', { maxSells: sentinelMonitor.maxSellsPerTick })
+      await invoke('set_sentinel_sell_retry', { policy: sentinelMonitor.sellRetry })
+      await invoke('set_sentinel_monitor_price_source', { priceSource: sentinelMonitor.priceSource })
+      await invoke('set_sentinel_min_liquidity', { minLiquidityUsd: sentinelMonitor.minLiquidityUsd })
+      await invoke('set_sentinel_tp_gap', { policy: sentinelMonitor.tpGap })
 
       // Purge sentinels for any blacklisted coins immediately
       if (settings.blacklistedCoins.length > 0) {
@@ -359,6 +453,14 @@ export function SettingsLayout({ setNavGuard }: { setNavGuard?: (guard: (() => b
           <RiskTab
             limits={riskLimits}
             setLimits={setRiskLimits}
+            splitConfig={splitConfig}
+            setSplitConfig={setSplitConfig}
+            priceFreshness={priceFreshness}
+            setPriceFreshness={setPriceFreshness}
+            coinPrecision={coinPrecision}
+            setCoinPrecision={setCoinPrecision}
+            adaptivePoll={adaptivePoll}
+            setAdaptivePoll={setAdaptivePoll}
             onChanged={markChanged}
           />
         )}
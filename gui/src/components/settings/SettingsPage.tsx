@@ -28,6 +28,7 @@ const DEFAULT_SETTINGS: AppSettings = {
     takeProfitPct: 500,
     trailingStopPct: null,
     sellPercentage: 100,
+    entryPriceSource: 'portfolioAverage',
   },
   autoManageSentinels: true,
   blacklistedCoins: [],
@@ -41,6 +42,19 @@ const DEFAULT_RISK_LIMITS: RiskLimits = {
   retryCount: 2,
   retryDelayMs: 1000,
   rateLimitMs: 500,
+  adaptiveRateLimit: true,
+  maxRateLimitMs: 10000,
+  frozenReserveUsd: 0,
+  minAutomatedIntervalSecs: 0,
+  maxTradeUsd: 0,
+  moduleMaxTradeUsd: {},
+  minCoinAgeSecs: 0,
+  minCandleCount: 0,
+  newCoinAllowedSources: ['manual'],
+  minTradeRoundUpUsd: 0,
+  stopLossCooldownSecs: 0,
+  maxExitImpactPct: 0,
+  maxDrawdownPct: 0,
 }
 
 const DEFAULT_NOTIFICATION_CONFIG: NotificationConfig = {
@@ -51,6 +65,8 @@ const DEFAULT_NOTIFICATION_CONFIG: NotificationConfig = {
   riskAlerts: true,
   sessionAlerts: true,
   tradeConfirmations: false,
+  dedupWindowSecs: 60,
+  dedupOverrides: { trade: 0 },
 }
 
 export function SettingsPage() {
@@ -34,6 +34,8 @@ const DEFAULT_SNIPER_CONFIG: SniperConfig = {
   maxDailySpendUsd: 0,
   pollIntervalSecs: 0,
   minCoinAgeSecs: 0,
+  budgetResetMode: { mode: 'rolling24h' },
+  maxRiskScore: 0,
 }
 
 export function SniperTab({ config, setConfig, onChanged }: SniperTabProps) {
@@ -109,7 +111,26 @@ export function SniperTab({ config, setConfig, onChanged }: SniperTabProps) {
                 className="input flex-1"
               />
             </div>
-            <p className="form-hint">Max USD via sniper per 24h (0 = unlimited)</p>
+            <p className="form-hint">Max USD via sniper per budget window (0 = unlimited)</p>
+          </div>
+
+          {/* Budget Reset */}
+          <div className="form-field">
+            <label className="form-label">
+              <Clock className="w-4 h-4 text-amber-400" />
+              Spend Limit Reset
+            </label>
+            <select
+              value={c.budgetResetMode.mode === 'dailyAt' ? String(c.budgetResetMode.hour) : 'rolling'}
+              onChange={e => update('budgetResetMode', e.target.value === 'rolling' ? { mode: 'rolling24h' } : { mode: 'dailyAt', hour: parseInt(e.target.value) })}
+              className="input"
+            >
+              <option value="rolling">Rolling 24h</option>
+              {Array.from({ length: 24 }, (_, h) => (
+                <option key={h} value={h}>Daily at {String(h).padStart(2, '0')}:00 UTC</option>
+              ))}
+            </select>
+            <p className="form-hint">Rolling counts each snipe for 24h; daily clears the limit at a fixed hour</p>
           </div>
         </div>
       </div>
@@ -14,6 +14,7 @@ import {
   Zap,
 } from 'lucide-react'
 import { activityStore } from '@/lib/activityStore'
+import { ConfigCheckCard } from '@/components/automation'
 import { FormattedInput, ToggleSwitch } from '@/components/ui/FormattedInput'
 import type {
   SniperStatusResponse,
@@ -290,6 +291,21 @@ export function SniperPage({ setNavGuard }: SniperPageProps) {
             <p className="form-hint">Wait this long after creation (60s creator period + buffer)</p>
           </div>
 
+          {/* Max Risk Score */}
+          <div className="form-field">
+            <label className="form-label">
+              <Shield className="w-4 h-4 text-red-400" />
+              Max Risk Score
+            </label>
+            <FormattedInput
+              value={config.maxRiskScore}
+              onChange={(v) => updateConfig('maxRiskScore', Math.min(100, Math.max(0, v)))}
+              min={0}
+              step={5}
+            />
+            <p className="form-hint">Skip coins scoring above this (0-100, 0 = off). Brand-new coins score high on age alone.</p>
+          </div>
+
           {/* Auto-create Sentinel */}
           <div className="form-field">
             <div className="flex items-center justify-between">
@@ -396,6 +412,8 @@ export function SniperPage({ setNavGuard }: SniperPageProps) {
         </div>
       </div>
 
+      <ConfigCheckCard module="sniper" />
+
       {/* Snipe Log */}
       <div className="card">
         <div className="flex items-center gap-2 mb-4">
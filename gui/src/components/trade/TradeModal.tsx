@@ -1,7 +1,8 @@
 import { useState, useEffect } from 'react'
 import { invoke } from '@tauri-apps/api/core'
-import { X, TrendingUp, TrendingDown, Loader2, CheckCircle2, XCircle } from 'lucide-react'
-import type { CoinHolding, TradeResult } from '@/lib/types'
+import { X, TrendingUp, TrendingDown, Loader2, CheckCircle2, XCircle, RefreshCw } from 'lucide-react'
+import type { CoinHolding, CoinQuote, ManualTradeReceipt, RiskViolation, TradeCheck } from '@/lib/types'
+import { formatFillPrice } from '@/lib/utils'
 
 export type TradeDirection = 'BUY' | 'SELL'
 
@@ -25,8 +26,14 @@ export function TradeModal({
   const [amount, setAmount] = useState('')
   const [loading, setLoading] = useState(false)
   const [error, setError] = useState<string | null>(null)
-  const [result, setResult] = useState<TradeResult | null>(null)
+  const [result, setResult] = useState<ManualTradeReceipt | null>(null)
   const [balance, setBalance] = useState<number | null>(null)
+  const [overrideReserve, setOverrideReserve] = useState(false)
+  const [splitSell, setSplitSell] = useState(false)
+  const [note, setNote] = useState('')
+  const [quote, setQuote] = useState<CoinQuote | null>(null)
+  const [quoteLoading, setQuoteLoading] = useState(false)
+  const [violations, setViolations] = useState<RiskViolation[]>([])
 
   // Reset state when modal opens/direction changes
   useEffect(() => {
@@ -34,10 +41,27 @@ export function TradeModal({
       setAmount('')
       setError(null)
       setResult(null)
+      setViolations([])
+      setOverrideReserve(false)
+      setSplitSell(false)
+      setNote('')
       fetchBalance()
+      fetchQuote()
     }
   }, [isOpen, direction])
 
+  async function fetchQuote() {
+    setQuoteLoading(true)
+    try {
+      setQuote(await invoke<CoinQuote>('get_coin_quote', { symbol }))
+    } catch (e) {
+      console.error('Failed to fetch quote:', e)
+      setQuote(null)
+    } finally {
+      setQuoteLoading(false)
+    }
+  }
+
   async function fetchBalance() {
     try {
       const bal = await invoke<number>('get_balance')
@@ -56,12 +80,29 @@ export function TradeModal({
 
     setLoading(true)
     setError(null)
+    setViolations([])
 
     try {
-      const tradeResult = await invoke<TradeResult>('execute_trade', {
+      const check = await invoke<TradeCheck>('check_trade_allowed', {
         symbol,
         direction,
         amount: numAmount,
+        overrideReserve,
+      })
+      if (!check.allowed) {
+        setViolations(check.violations)
+        return
+      }
+
+      const tradeResult = await invoke<ManualTradeReceipt>('manual_trade', {
+        request: {
+          symbol,
+          direction,
+          amount: numAmount,
+          overrideReserve,
+          split: direction === 'SELL' && splitSell,
+          note: note.trim() || null,
+        },
       })
 
       setResult(tradeResult)
@@ -95,6 +136,7 @@ export function TradeModal({
   const isBuy = direction === 'BUY'
   const amountLabel = isBuy ? 'USD' : symbol
   const numAmount = parseFloat(amount) || 0
+  const previewPrice = quote?.price ?? holding?.currentPrice ?? 0
 
   return (
     <div className="fixed inset-0 z-50 flex items-center justify-center">
@@ -145,9 +187,16 @@ export function TradeModal({
                   <p className="text-foreground-muted mb-4">{result.message}</p>
                   
                   <div className="space-y-2 text-sm bg-background p-3 rounded-lg">
+                    <div className="flex justify-between">
+                      <span className="text-foreground-muted">Quoted Price</span>
+                      <span className="font-medium">
+                        ${result.quotedPrice.toFixed(8)}
+                        <span className="text-foreground-muted text-xs ml-1">({(result.quoteAgeMs / 1000).toFixed(1)}s old)</span>
+                      </span>
+                    </div>
                     <div className="flex justify-between">
                       <span className="text-foreground-muted">New Price</span>
-                      <span className="font-medium">${result.newPrice.toFixed(8)}</span>
+                      <span className="font-medium">{formatFillPrice(result.newPrice)}</span>
                     </div>
                     <div className="flex justify-between">
                       <span className="text-foreground-muted">Price Impact</span>
@@ -159,6 +208,12 @@ export function TradeModal({
                       <span className="text-foreground-muted">New Balance</span>
                       <span className="font-medium">${result.newBalance.toFixed(2)}</span>
                     </div>
+                    {result.note && (
+                      <div className="flex justify-between gap-4">
+                        <span className="text-foreground-muted">Note</span>
+                        <span className="font-medium text-right truncate">{result.note}</span>
+                      </div>
+                    )}
                   </div>
                   
                   <button
@@ -220,6 +275,43 @@ export function TradeModal({
                 </div>
               </div>
 
+              {/* Frozen Reserve Override */}
+              {isBuy && (
+                <label className="flex items-center gap-2 text-xs text-foreground-muted mb-4 cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={overrideReserve}
+                    onChange={(e) => setOverrideReserve(e.target.checked)}
+                    disabled={loading}
+                  />
+                  Allow this buy to spend frozen reserve funds
+                </label>
+              )}
+
+              {/* Split Sell */}
+              {!isBuy && (
+                <label className="flex items-center gap-2 text-xs text-foreground-muted mb-4 cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={splitSell}
+                    onChange={(e) => setSplitSell(e.target.checked)}
+                    disabled={loading}
+                  />
+                  Split into tranches to reduce slippage
+                </label>
+              )}
+
+              {/* Trade Note */}
+              <input
+                type="text"
+                value={note}
+                onChange={(e) => setNote(e.target.value)}
+                placeholder="Note (optional) — why are you making this trade?"
+                maxLength={200}
+                className="input w-full text-sm mb-4"
+                disabled={loading}
+              />
+
               {/* Quick Amount Buttons */}
               <div className="grid grid-cols-4 gap-2 mb-4">
                 {[0.25, 0.5, 0.75, 1].map((pct) => (
@@ -235,21 +327,44 @@ export function TradeModal({
               </div>
 
               {/* Preview */}
-              {numAmount > 0 && holding && (
+              {numAmount > 0 && previewPrice > 0 && (
                 <div className="mb-4 p-3 rounded-lg bg-background text-sm">
                   <div className="flex justify-between mb-1">
                     <span className="text-foreground-muted">Current Price</span>
-                    <span>${holding.currentPrice.toFixed(8)}</span>
+                    <span>${previewPrice.toFixed(8)}</span>
                   </div>
-                  <div className="flex justify-between">
+                  <div className="flex justify-between mb-1">
                     <span className="text-foreground-muted">Est. {isBuy ? 'Receive' : 'Receive'}</span>
                     <span className="font-medium">
                       {isBuy 
-                        ? `~${(numAmount / holding.currentPrice).toFixed(4)} ${symbol}`
-                        : `~$${(numAmount * holding.currentPrice).toFixed(2)}`
+                        ? `~${(numAmount / previewPrice).toFixed(4)} ${symbol}`
+                        : `~$${(numAmount * previewPrice).toFixed(2)}`
                       }
                     </span>
                   </div>
+                  <div className="flex justify-between items-center text-xs text-foreground-muted">
+                    <span>
+                      {quote ? `Quote ${(quote.ageMs / 1000).toFixed(1)}s old` : 'Price from portfolio snapshot'}
+                    </span>
+                    <button
+                      onClick={fetchQuote}
+                      disabled={quoteLoading || loading}
+                      className="flex items-center gap-1 hover:text-foreground disabled:opacity-50"
+                    >
+                      <RefreshCw className={`w-3 h-3 ${quoteLoading ? 'animate-spin' : ''}`} />
+                      Refresh
+                    </button>
+                  </div>
+                </div>
+              )}
+
+              {/* Risk rules that would reject this trade */}
+              {violations.length > 0 && (
+                <div className="mb-4 p-3 rounded-lg bg-sell/20 border border-sell/30 text-sell text-sm space-y-1">
+                  <p className="font-medium">Blocked by risk limits:</p>
+                  {violations.map((v) => (
+                    <p key={v.rule}>{v.message}</p>
+                  ))}
                 </div>
               )}
 
@@ -0,0 +1,173 @@
+import { useState, useEffect } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Plus, Save, Trash2, Wallet } from 'lucide-react'
+import type { EquityAlertConfig } from '@/lib/types'
+import { formatUsd } from '@/lib/utils'
+
+export function EquityAlertsPanel() {
+  const [config, setConfig] = useState<EquityAlertConfig | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [saved, setSaved] = useState(false)
+  const [milestone, setMilestone] = useState('')
+  const [movePct, setMovePct] = useState('')
+  const [moveMins, setMoveMins] = useState('60')
+
+  useEffect(() => {
+    invoke<EquityAlertConfig>('get_equity_alerts')
+      .then(setConfig)
+      .catch((e) => setError(String(e)))
+  }, [])
+
+  if (!config) {
+    return error ? <p className="text-sm text-rose-400">{error}</p> : null
+  }
+
+  const update = (changes: Partial<EquityAlertConfig>) => {
+    setConfig({ ...config, ...changes })
+    setSaved(false)
+  }
+
+  const addMilestone = () => {
+    const level = parseFloat(milestone)
+    if (!(level > 0) || config.milestones.includes(level)) return
+    update({ milestones: [...config.milestones, level].sort((a, b) => a - b) })
+    setMilestone('')
+  }
+
+  const addMove = () => {
+    const pct = parseFloat(movePct)
+    if (!(pct > 0)) return
+    const windowSecs = Math.max(1, parseFloat(moveMins) || 0) * 60
+    update({ moves: [...config.moves, { pct, windowSecs }] })
+    setMovePct('')
+  }
+
+  const save = async () => {
+    try {
+      setConfig(await invoke<EquityAlertConfig>('set_equity_alerts', { config }))
+      setError(null)
+      setSaved(true)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  return (
+    <div className="card space-y-4">
+      <div className="flex items-start justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-white flex items-center gap-2">
+            <Wallet className="w-5 h-5 text-emerald-400" />
+            Portfolio Value Alerts
+          </h3>
+          <p className="text-zinc-400 text-sm mt-1">
+            Watches total account value (cash plus coins), sampled every minute while enabled.
+          </p>
+        </div>
+        <label className="flex items-center gap-2 text-sm text-zinc-300">
+          <input
+            type="checkbox"
+            checked={config.enabled}
+            onChange={(e) => update({ enabled: e.target.checked })}
+          />
+          Enabled
+        </label>
+      </div>
+
+      <div className="grid grid-cols-2 gap-6">
+        <div className="space-y-2">
+          <div className="flex items-end gap-2">
+            <div className="form-field flex-1">
+              <label className="form-label">Milestone ($)</label>
+              <input
+                type="number"
+                min={0}
+                step="any"
+                value={milestone}
+                onChange={(e) => setMilestone(e.target.value)}
+                onKeyDown={(e) => e.key === 'Enter' && addMilestone()}
+                className="input"
+              />
+            </div>
+            <button onClick={addMilestone} className="btn btn-ghost h-10" title="Add milestone">
+              <Plus className="w-4 h-4" />
+            </button>
+          </div>
+          {config.milestones.length === 0 ? (
+            <p className="text-sm text-zinc-500">No milestones</p>
+          ) : (
+            config.milestones.map((level) => (
+              <div key={level} className="flex items-center justify-between text-sm">
+                <span className="text-zinc-300">Crosses {formatUsd(level)}</span>
+                <button
+                  onClick={() => update({ milestones: config.milestones.filter((l) => l !== level) })}
+                  className="p-1 text-zinc-400 hover:text-rose-400"
+                  title="Remove milestone"
+                >
+                  <Trash2 className="w-4 h-4" />
+                </button>
+              </div>
+            ))
+          )}
+        </div>
+
+        <div className="space-y-2">
+          <div className="flex items-end gap-2">
+            <div className="form-field flex-1">
+              <label className="form-label">Move (%)</label>
+              <input
+                type="number"
+                min={0}
+                step="any"
+                value={movePct}
+                onChange={(e) => setMovePct(e.target.value)}
+                onKeyDown={(e) => e.key === 'Enter' && addMove()}
+                className="input"
+              />
+            </div>
+            <div className="form-field w-28">
+              <label className="form-label">Within (min)</label>
+              <input
+                type="number"
+                min={1}
+                value={moveMins}
+                onChange={(e) => setMoveMins(e.target.value)}
+                className="input"
+              />
+            </div>
+            <button onClick={addMove} className="btn btn-ghost h-10" title="Add move alert">
+              <Plus className="w-4 h-4" />
+            </button>
+          </div>
+          {config.moves.length === 0 ? (
+            <p className="text-sm text-zinc-500">No move alerts</p>
+          ) : (
+            config.moves.map((move, i) => (
+              <div key={i} className="flex items-center justify-between text-sm">
+                <span className="text-zinc-300">
+                  Moves ±{move.pct}% within {Math.ceil(move.windowSecs / 60)} min
+                </span>
+                <button
+                  onClick={() => update({ moves: config.moves.filter((_, j) => j !== i) })}
+                  className="p-1 text-zinc-400 hover:text-rose-400"
+                  title="Remove move alert"
+                >
+                  <Trash2 className="w-4 h-4" />
+                </button>
+              </div>
+            ))
+          )}
+        </div>
+      </div>
+
+      <div className="flex items-center gap-3">
+        <button onClick={save} className="btn btn-primary flex items-center gap-2">
+          <Save className="w-4 h-4" />
+          Save
+        </button>
+        {saved && <span className="text-sm text-emerald-400">Saved</span>}
+        {error && <span className="text-sm text-rose-400">{error}</span>}
+      </div>
+    </div>
+  )
+}
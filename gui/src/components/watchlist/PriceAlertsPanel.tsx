@@ -0,0 +1,169 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { BellRing, Plus, Trash2 } from 'lucide-react'
+import type { AlertCondition, PriceAlert } from '@/lib/types'
+
+type ConditionKind = AlertCondition['kind']
+
+function describeCondition(condition: AlertCondition): string {
+  switch (condition.kind) {
+    case 'above':
+      return `Crosses above $${condition.price}`
+    case 'below':
+      return `Crosses below $${condition.price}`
+    case 'move':
+      return `Moves ±${condition.pct}% within ${Math.ceil(condition.windowSecs / 60)} min`
+  }
+}
+
+export function PriceAlertsPanel() {
+  const [alerts, setAlerts] = useState<PriceAlert[]>([])
+  const [error, setError] = useState<string | null>(null)
+  const [symbol, setSymbol] = useState('')
+  const [kind, setKind] = useState<ConditionKind>('above')
+  const [value, setValue] = useState('')
+  const [windowMins, setWindowMins] = useState('15')
+
+  const fetchAlerts = useCallback(async () => {
+    try {
+      setAlerts(await invoke<PriceAlert[]>('get_price_alerts'))
+      setError(null)
+    } catch (e) {
+      setError(String(e))
+    }
+  }, [])
+
+  useEffect(() => {
+    fetchAlerts()
+    // Refresh armed state when the background check fires an alert
+    const unlisten = listen('price-alert-fired', () => fetchAlerts())
+    return () => {
+      unlisten.then((fn) => fn())
+    }
+  }, [fetchAlerts])
+
+  const createAlert = async () => {
+    const target = parseFloat(value)
+    if (!symbol.trim() || !(target > 0)) return
+    const condition: AlertCondition =
+      kind === 'move'
+        ? { kind, pct: target, windowSecs: Math.max(1, parseFloat(windowMins) || 0) * 60 }
+        : { kind, price: target }
+    try {
+      const created = await invoke<PriceAlert>('create_price_alert', { symbol, condition })
+      setAlerts((prev) => [...prev, created])
+      setSymbol('')
+      setValue('')
+      setError(null)
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const deleteAlert = async (id: number) => {
+    try {
+      await invoke('delete_price_alert', { id })
+      setAlerts((prev) => prev.filter((a) => a.id !== id))
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  return (
+    <div className="card space-y-4">
+      <div>
+        <h3 className="text-lg font-semibold text-white flex items-center gap-2">
+          <BellRing className="w-5 h-5 text-amber-400" />
+          Price Alerts
+        </h3>
+        <p className="text-zinc-400 text-sm mt-1">
+          Notifies once per crossing for any coin, then re-arms when the price moves back.
+        </p>
+      </div>
+
+      <div className="flex items-end gap-3">
+        <div className="form-field w-32">
+          <label className="form-label">Symbol</label>
+          <input
+            type="text"
+            value={symbol}
+            onChange={(e) => setSymbol(e.target.value.toUpperCase())}
+            placeholder="e.g. BTC"
+            className="input"
+          />
+        </div>
+        <div className="form-field w-40">
+          <label className="form-label">Trigger</label>
+          <select value={kind} onChange={(e) => setKind(e.target.value as ConditionKind)} className="input">
+            <option value="above">Crosses above</option>
+            <option value="below">Crosses below</option>
+            <option value="move">Moves ±%</option>
+          </select>
+        </div>
+        <div className="form-field flex-1">
+          <label className="form-label">{kind === 'move' ? 'Move (%)' : 'Price ($)'}</label>
+          <input
+            type="number"
+            min={0}
+            step="any"
+            value={value}
+            onChange={(e) => setValue(e.target.value)}
+            onKeyDown={(e) => e.key === 'Enter' && createAlert()}
+            className="input"
+          />
+        </div>
+        {kind === 'move' && (
+          <div className="form-field w-32">
+            <label className="form-label">Within (min)</label>
+            <input
+              type="number"
+              min={1}
+              value={windowMins}
+              onChange={(e) => setWindowMins(e.target.value)}
+              className="input"
+            />
+          </div>
+        )}
+        <button onClick={createAlert} className="btn btn-primary flex items-center gap-2 h-10">
+          <Plus className="w-4 h-4" />
+          Add
+        </button>
+      </div>
+      {error && <p className="text-sm text-rose-400">{error}</p>}
+
+      {alerts.length === 0 ? (
+        <p className="text-sm text-zinc-500">No price alerts set</p>
+      ) : (
+        <div className="divide-y divide-zinc-800">
+          {alerts.map((alert) => (
+            <div key={alert.id} className="flex items-center justify-between py-2">
+              <div>
+                <span className="font-mono text-white">${alert.symbol}</span>
+                <span className="text-sm text-zinc-400 ml-3">{describeCondition(alert.condition)}</span>
+                {alert.lastTriggeredAt && (
+                  <div className="text-xs text-zinc-500">Last fired {alert.lastTriggeredAt}</div>
+                )}
+              </div>
+              <div className="flex items-center gap-3">
+                <span
+                  className={`text-xs px-2 py-0.5 rounded ${alert.armed ? 'bg-emerald-500/10 text-emerald-400' : 'bg-zinc-800 text-zinc-400'}`}
+                  title={alert.armed ? 'Will notify on the next crossing' : 'Waiting for the price to move back'}
+                >
+                  {alert.armed ? 'Armed' : 'Waiting'}
+                </span>
+                <button
+                  onClick={() => deleteAlert(alert.id)}
+                  className="p-2 rounded-lg text-zinc-400 hover:text-rose-400 hover:bg-zinc-800 transition-colors"
+                  title="Delete alert"
+                >
+                  <Trash2 className="w-4 h-4" />
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  )
+}
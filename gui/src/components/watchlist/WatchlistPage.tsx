@@ -0,0 +1,221 @@
+import { useState, useEffect, useCallback } from 'react'
+import { invoke } from '@tauri-apps/api/core'
+import { Eye, Plus, RefreshCw, Trash2, Bell, ShieldCheck } from 'lucide-react'
+import type { WatchedCoin } from '@/lib/types'
+import { buildImageUrl, formatPercent } from '@/lib/utils'
+import { PriceAlertsPanel } from './PriceAlertsPanel'
+import { EquityAlertsPanel } from './EquityAlertsPanel'
+
+const REFRESH_INTERVAL_MS = 30_000
+
+interface WatchlistPageProps {
+  onCoinClick?: (symbol: string) => void
+}
+
+export function WatchlistPage({ onCoinClick }: WatchlistPageProps) {
+  const [coins, setCoins] = useState<WatchedCoin[]>([])
+  const [loading, setLoading] = useState(true)
+  const [error, setError] = useState<string | null>(null)
+  const [symbol, setSymbol] = useState('')
+  const [alertPct, setAlertPct] = useState('')
+  const [watchlistOnly, setWatchlistOnly] = useState(false)
+
+  const fetchWatchlist = useCallback(async () => {
+    try {
+      setCoins(await invoke<WatchedCoin[]>('get_watchlist_with_prices'))
+      setError(null)
+    } catch (e) {
+      setError(String(e))
+    } finally {
+      setLoading(false)
+    }
+  }, [])
+
+  useEffect(() => {
+    invoke<boolean>('get_watchlist_only').then(setWatchlistOnly).catch(() => {})
+  }, [])
+
+  useEffect(() => {
+    fetchWatchlist()
+    const id = setInterval(fetchWatchlist, REFRESH_INTERVAL_MS)
+    return () => clearInterval(id)
+  }, [fetchWatchlist])
+
+  const addCoin = async () => {
+    if (!symbol.trim()) return
+    const pct = parseFloat(alertPct)
+    try {
+      await invoke('add_to_watchlist', { symbol, alertPct: pct > 0 ? pct : null })
+      setSymbol('')
+      setAlertPct('')
+      fetchWatchlist()
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const removeCoin = async (target: string) => {
+    try {
+      await invoke('remove_from_watchlist', { symbol: target })
+      setCoins((prev) => prev.filter((c) => c.symbol !== target))
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  const toggleWatchlistOnly = async () => {
+    try {
+      setWatchlistOnly(await invoke<boolean>('set_watchlist_only', { enabled: !watchlistOnly }))
+    } catch (e) {
+      setError(String(e))
+    }
+  }
+
+  return (
+    <div className="space-y-6">
+      <div className="flex items-center justify-between">
+        <div>
+          <h2 className="text-2xl font-bold text-white">Watchlist</h2>
+          <p className="text-zinc-400 text-sm mt-1">
+            Coins you follow without holding them. Prices refresh every 30s.
+          </p>
+          {watchlistOnly && (
+            <p className="text-emerald-400 text-sm mt-1">
+              Watchlist-only: automation only buys coins on this list.
+            </p>
+          )}
+        </div>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={toggleWatchlistOnly}
+            className={`btn ${watchlistOnly ? 'btn-primary' : 'btn-ghost'} flex items-center gap-2`}
+            title={watchlistOnly
+              ? 'Automated buys are limited to watched coins; click to trade any coin again'
+              : 'Limit sniper, dip buyer and mirror buys to the coins on this list'}
+          >
+            <ShieldCheck className="w-4 h-4" />
+            {watchlistOnly ? 'Watchlist Only' : 'Trade Any Coin'}
+          </button>
+          <button
+            onClick={fetchWatchlist}
+            disabled={loading}
+            className="flex items-center gap-2 px-4 py-2 bg-zinc-800 hover:bg-zinc-700 text-white rounded-lg transition-colors disabled:opacity-50"
+          >
+            <RefreshCw className={`w-4 h-4 ${loading ? 'animate-spin' : ''}`} />
+            Refresh
+          </button>
+        </div>
+      </div>
+
+      <div className="card">
+        <div className="flex items-end gap-3">
+          <div className="form-field flex-1">
+            <label className="form-label">
+              <Eye className="w-4 h-4 text-blue-400" />
+              Symbol
+            </label>
+            <input
+              type="text"
+              value={symbol}
+              onChange={(e) => setSymbol(e.target.value.toUpperCase())}
+              onKeyDown={(e) => e.key === 'Enter' && addCoin()}
+              placeholder="e.g. BTC"
+              className="input"
+            />
+          </div>
+          <div className="form-field w-48">
+            <label className="form-label">
+              <Bell className="w-4 h-4 text-amber-400" />
+              Alert on Move (%)
+            </label>
+            <input
+              type="number"
+              min={0}
+              step={5}
+              value={alertPct}
+              onChange={(e) => setAlertPct(e.target.value)}
+              placeholder="Off"
+              className="input"
+            />
+          </div>
+          <button onClick={addCoin} className="btn btn-primary flex items-center gap-2 h-10">
+            <Plus className="w-4 h-4" />
+            Watch
+          </button>
+        </div>
+        {error && <p className="text-sm text-rose-400 mt-3">{error}</p>}
+      </div>
+
+      <div className="bg-zinc-900 border border-zinc-800 rounded-lg overflow-hidden">
+        <table className="w-full">
+          <thead className="bg-zinc-800/50">
+            <tr>
+              <th className="text-left text-xs font-medium text-zinc-400 uppercase px-4 py-3">Coin</th>
+              <th className="text-right text-xs font-medium text-zinc-400 uppercase px-4 py-3">Price</th>
+              <th className="text-right text-xs font-medium text-zinc-400 uppercase px-4 py-3">24h</th>
+              <th className="text-right text-xs font-medium text-zinc-400 uppercase px-4 py-3">Since Reference</th>
+              <th className="text-right text-xs font-medium text-zinc-400 uppercase px-4 py-3">Alert</th>
+              <th className="px-4 py-3" />
+            </tr>
+          </thead>
+          <tbody className="divide-y divide-zinc-800">
+            {coins.length === 0 ? (
+              <tr>
+                <td colSpan={6} className="px-4 py-12 text-center text-zinc-400">
+                  {loading ? 'Loading watchlist...' : 'No coins watched yet'}
+                </td>
+              </tr>
+            ) : (
+              coins.map((coin) => {
+                const iconUrl = buildImageUrl(coin.icon)
+                return (
+                  <tr key={coin.symbol} className="hover:bg-zinc-800/30 transition-colors">
+                    <td className="px-4 py-3">
+                      <button onClick={() => onCoinClick?.(coin.symbol)} className="flex items-center gap-3 text-left">
+                        {iconUrl ? (
+                          <img src={iconUrl} alt={coin.symbol} className="w-8 h-8 rounded-full" />
+                        ) : (
+                          <div className="w-8 h-8 rounded-full bg-zinc-700 flex items-center justify-center text-xs font-bold text-zinc-300">
+                            {coin.symbol.charAt(0)}
+                          </div>
+                        )}
+                        <div>
+                          <span className="font-mono text-white">${coin.symbol}</span>
+                          <div className="text-xs text-zinc-500">{coin.error ?? coin.name}</div>
+                        </div>
+                      </button>
+                    </td>
+                    <td className="px-4 py-3 text-right font-mono text-white">
+                      {coin.currentPrice != null ? `$${coin.currentPrice.toFixed(8)}` : '—'}
+                    </td>
+                    <td className={`px-4 py-3 text-right font-mono ${(coin.change24h ?? 0) >= 0 ? 'text-emerald-400' : 'text-rose-400'}`}>
+                      {coin.change24h != null ? formatPercent(coin.change24h) : '—'}
+                    </td>
+                    <td className={`px-4 py-3 text-right font-mono ${(coin.movePct ?? 0) >= 0 ? 'text-emerald-400' : 'text-rose-400'}`}>
+                      {coin.movePct != null ? formatPercent(coin.movePct) : '—'}
+                    </td>
+                    <td className="px-4 py-3 text-right text-sm text-zinc-400">
+                      {coin.alertPct != null ? `±${coin.alertPct}%` : 'Off'}
+                    </td>
+                    <td className="px-4 py-3 text-right">
+                      <button
+                        onClick={() => removeCoin(coin.symbol)}
+                        className="p-2 rounded-lg text-zinc-400 hover:text-rose-400 hover:bg-zinc-800 transition-colors"
+                        title="Stop watching"
+                      >
+                        <Trash2 className="w-4 h-4" />
+                      </button>
+                    </td>
+                  </tr>
+                )
+              })
+            )}
+          </tbody>
+        </table>
+      </div>
+
+      <PriceAlertsPanel />
+      <EquityAlertsPanel />
+    </div>
+  )
+}
@@ -0,0 +1,3 @@
+export { WatchlistPage } from './WatchlistPage'
+export { PriceAlertsPanel } from './PriceAlertsPanel'
+export { EquityAlertsPanel } from './EquityAlertsPanel'
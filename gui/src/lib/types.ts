@@ -1,11 +1,59 @@
 // Type definitions for RugPlay Manager
 
+export type SetupStep =
+  | 'initDatabase'
+  | 'addProfile'
+  | 'selectProfile'
+  | 'updateToken'
+  | 'configureSettings'
+  | 'reviewRiskLimits'
+
+export interface SetupStepStatus {
+  step: SetupStep
+  required: boolean
+  done: boolean
+}
+
+export interface SetupState {
+  databaseReady: boolean
+  profileCount: number
+  activeProfileId: number | null
+  ready: boolean
+  complete: boolean
+  nextStep: SetupStep | null
+  steps: SetupStepStatus[]
+}
+
 export interface ProfileSummary {
   id: number
   username: string
   last_verified: string | null
+  avatar_url: string | null
+}
+
+export interface ProfileFailoverConfig {
+  enabled: boolean
+  profileIds: number[]
+  /** Also fail over after this long rate limited with no successful poll (seconds); 0 = never */
+  rateLimitFailoverSecs: number
+}
+
+/** What happens when a token being saved already belongs to another profile */
+export type DuplicateTokenPolicy = 'warn' | 'refuse' | 'merge'
+
+export interface ProfileFailoverEvent {
+  fromProfileId: number | null
+  toProfileId: number
+  username: string
+  reason: string
 }
 
+export type TokenValidation =
+  | { status: 'valid'; username: string; userId: string; expiresAt: string }
+  | { status: 'expired' }
+  | { status: 'invalid'; reason: string }
+  | { status: 'unreachable'; reason: string }
+
 export interface UserProfile {
   id: string
   username: string
@@ -51,10 +99,52 @@ export interface TradeResult {
   tradeType: string
   coinsAmount: number
   usdAmount: number
-  newPrice: number
+  /** null when the API reported no price for the fill */
+  newPrice: number | null
   priceImpact: number
   newBalance: number
   message: string
+  /** Coins a split sell left unsold after a tranche failed */
+  unsoldAmount: number
+}
+
+export interface ManualTradeReceipt extends TradeResult {
+  source: string
+  quotedPrice: number
+  quoteFetchedAt: string | null
+  quoteAgeMs: number
+  note: string | null
+}
+
+export type RiskRule =
+  | 'maxPosition'
+  | 'dailyTradeCount'
+  | 'dailyVolume'
+  | 'lossCooldown'
+  | 'frozenReserve'
+  | 'tradeSizeCap'
+  | 'admission'
+  | 'coinHistory'
+  | 'exitLiquidity'
+  | 'stopLossCooldown'
+
+export interface RiskViolation {
+  rule: RiskRule
+  current: number
+  limit: number
+  message: string
+}
+
+export interface RiskActivity {
+  dailyTrades: number
+  dailyVolumeUsd: number
+  cooldownRemainingSecs: number
+}
+
+export interface TradeCheck {
+  allowed: boolean
+  violations: RiskViolation[]
+  activity: RiskActivity
 }
 
 // ============================================================================
@@ -87,6 +177,39 @@ export interface PortfolioSummary {
   totalProfitLoss: number
   totalProfitLossPct: number
   holdingsCount: number
+  frozenBalance: number
+  availableBalance: number
+}
+
+export interface RebalanceOptions {
+  /** Positions closer than this (USD) to their target are left as is */
+  minTradeUsd: number
+  /** Largest price impact per order; bigger orders are shrunk (0 = no cap) */
+  maxSlippagePct: number
+}
+
+export interface RebalanceOrder {
+  symbol: string
+  tradeType: 'BUY' | 'SELL'
+  /** Coins for a sell, USD for a buy */
+  amount: number
+  valueUsd: number
+  currentPct: number
+  targetPct: number
+  slippagePct: number
+  capped: boolean
+}
+
+export interface RebalancePlan {
+  totalValue: number
+  orders: RebalanceOrder[]
+  skipped: { symbol: string; reason: string }[]
+}
+
+export interface RebalanceResult {
+  plan: RebalancePlan
+  dryRun: boolean
+  fills: { symbol: string; tradeType: 'BUY' | 'SELL'; success: boolean; error: string | null }[]
 }
 
 // ============================================================================
@@ -133,6 +256,7 @@ export interface CoinDetails {
   isLocked: boolean
   volume24h: number
   change24h: number
+  createdAt?: string
 }
 
 export interface CandlestickPoint {
@@ -216,8 +340,41 @@ export interface SentinelConfig {
   isActive: boolean
   createdAt: string | null
   triggeredAt: string | null
+  /** Own price source; null follows the monitor default */
+  priceSource: SentinelPriceSource | null
+  /** Per-trigger sell %; null uses sellPercentage */
+  slSellPct: number | null
+  tpSellPct: number | null
+  tsSellPct: number | null
+  /** Take-profit ladder; null uses the single take-profit */
+  tpLadder: TpRung[] | null
+  /** Rungs of the ladder already sold into */
+  tpRungsFilled: number
+}
+
+/** Sell sellPct% of the position once price is gainPct% above entry */
+export interface TpRung {
+  gainPct: number
+  sellPct: number
 }
 
+/** A trigger fired while replaying a hypothetical price path */
+export interface SimulatedTrigger {
+  /** Index into the path */
+  step: number
+  price: number
+  triggerType: 'stop_loss' | 'take_profit' | 'trailing_stop' | 'liquidity_drain'
+  triggerPrice: number
+  reason: string
+  entryPrice: number
+  sellPct: number
+  /** Percent of the original holding left after this sell */
+  remainingPct: number
+}
+
+/** last_trade = portfolio price, mark = AMM spot price from pool reserves */
+export type SentinelPriceSource = 'last_trade' | 'mark'
+
 export interface CreateSentinelRequest {
   symbol: string
   stopLossPct: number | null
@@ -263,24 +420,54 @@ export interface TransactionRecord {
   isIncoming: boolean
   sender?: string
   recipient?: string
+  note?: string | null
 }
 
-export interface TransactionListResponse {
-  transactions: TransactionRecord[]
+/** A trade only the local journal or only the server knows about */
+export interface JournalDiscrepancy {
+  localId: number | null
+  serverId: number | null
+  symbol: string
+  tradeType: string
+  coinAmount: number
+  price: number
+  usdValue: number
+  timestamp: string
+}
+
+/** Result of comparing the local trade journal with the server's history */
+export interface ReconciliationReport {
+  checkedAt: string
+  /** Oldest trade compared; null when the whole server history was read */
+  windowStart: string | null
+  matched: number
+  missingLocally: JournalDiscrepancy[]
+  missingOnServer: JournalDiscrepancy[]
+  /** Server trades added to the journal */
+  repaired: number
+}
+
+/** One page of a list command's results */
+export interface Page<T> {
+  items: T[]
   total: number
-  page: number
+  offset: number
   limit: number
+  hasMore: boolean
 }
 
 // ============================================================================
 // Settings Types
 // ============================================================================
 
+export type EntryPriceSource = 'fillPrice' | 'portfolioAverage' | 'currentMarket'
+
 export interface SentinelDefaults {
   stopLossPct: number
   takeProfitPct: number
   trailingStopPct: number | null
   sellPercentage: number
+  entryPriceSource: EntryPriceSource
 }
 
 export interface AppSettings {
@@ -299,13 +486,91 @@ export interface MonitorStatusResponse {
   status: MonitorStatus
   intervalSecs: number
   isPaused: boolean
+  maxSellsPerTick: number
+  sellRetry: SentinelSellRetry
+  priceSource: SentinelPriceSource
+  tpGap: TpGapPolicy
+}
+
+export interface SentinelSellRetry {
+  baseSecs: number
+  maxSecs: number
+  maxFailures: number
+}
+
+/** What a take-profit does when spot has dumped below its target */
+export type TpGapAction = 'proceed' | 'cappedMarket' | 'abortRearm'
+
+export interface TpGapPolicy {
+  action: TpGapAction
+  /** How far (%) spot must sit below the target to count as a gap */
+  gapPct: number
+  /** Largest average-fill discount to spot (%) a capped sell accepts */
+  maxSlippagePct: number
+}
+
+export interface AcquisitionPauseState {
+  paused: boolean
+  pausedAt: string | null
+  resumeModules: string[]
+}
+
+/** Payload of `emergency-stop`, also returned by the stop/release commands */
+export interface EmergencyStopEvent {
+  active: boolean
+  cancelledTrades: number
+  at: string
+}
+
+export interface ModuleStatus {
+  name: 'sentinel' | 'sniper' | 'mirror' | 'dipbuyer' | 'harvester'
+  enabled: boolean
+  paused: boolean
+  lastTickAt: string | null
+  lastTickAgeSecs: number | null
+}
+
+export interface ExecutorStatus {
+  queueDepth: number
+  dailyTrades: number
+  dailyVolumeUsd: number
+  cooldownRemainingSecs: number
+  buyBlocks: string[]
+  serverMinTradeUsd: number | null
+}
+
+export type Connectivity =
+  | { state: 'online' }
+  | { state: 'offline'; error: string }
+  | { state: 'unknown' }
+
+export type WarmupStep = 'settings' | 'profile' | 'portfolio' | 'market'
+
+/** Startup warm-up; automated trades are held back until `ready` */
+export interface WarmupStatus {
+  ready: boolean
+  completed: WarmupStep[]
+  pending: WarmupStep[]
+  attempts: number
+  lastError: string | null
+}
+
+export interface BotStatus {
+  modules: ModuleStatus[]
+  executor: ExecutorStatus | null
+  acquisitionPaused: boolean
+  activeProfile: { id: number; username: string } | null
+  connectivity: Connectivity
+  portfolio: PortfolioSummary | null
+  warmup: WarmupStatus | null
+  timestamp: string
 }
 
 export interface SentinelTriggeredEvent {
   sentinelId: number
   symbol: string
   reason: string
-  triggerType: 'stop_loss' | 'take_profit' | 'trailing_stop'
+  triggerType: 'stop_loss' | 'take_profit' | 'trailing_stop' | 'liquidity_drain'
   currentPrice: number
   entryPrice: number
   sellAmount: number
@@ -323,24 +588,52 @@ export interface TradeExecutedEvent {
   symbol: string
   tradeType: string
   amount: number
-  newPrice: number
+  /** null for failed trades and fills the API reported no price for */
+  newPrice: number | null
   priceImpact: number
   newBalance: number
   reason: string
+  reasonDetail: TradeReason
+  source: string
   success: boolean
   error?: string
+  /** Simulated by paper trading, not sent */
+  paper: boolean
 }
 
+/** Why an order was submitted; `reason` is its display form */
+export type TradeReason =
+  | { kind: 'sniper'; symbol: string; ageSecs: number; marketCap: number }
+  | { kind: 'mirror'; whale: string; whaleSide: string; valueUsd: number; symbol: string }
+  | { kind: 'dip'; seller: string; sellValueUsd: number; symbol: string; confidence: number; slippagePct: number; buyUsd: number }
+  | { kind: 'sentinel'; sentinelId: number; triggerType: string; detail: string }
+  | { kind: 'manual'; side: 'BUY' | 'SELL'; split: boolean; mobile: boolean; note: string | null }
+  | { kind: 'rebalance'; symbol: string; currentPct: number; targetPct: number }
+  | { kind: 'tranche'; part: number; parts: number; reason: TradeReason }
+
 // ============================================================================
 // Harvester Types (12h auto-claim)
 // ============================================================================
 
+export type ClaimOutcome =
+  | { kind: 'claimed'; amount: number; newBalance: number; loginStreak: number; nextEligibleAt: string }
+  | { kind: 'nothingToClaim'; nextEligibleAt: string }
+  | { kind: 'failed'; reason: string; rateLimited: boolean; retryAt: string }
+
+export type ProfileClaimOutcome = ClaimOutcome & {
+  profileId: number
+  username: string
+  at: string
+}
+
 export interface HarvesterStatusResponse {
   enabled: boolean
   lastClaimAt: string | null
   nextClaimAt: string | null
   secondsUntilNext: number
   totalClaims: number
+  lastOutcome: ProfileClaimOutcome | null
+  outcomes: ProfileClaimOutcome[]
 }
 
 export interface HarvesterTickEvent {
@@ -361,6 +654,81 @@ export interface HarvesterClaimedEvent {
   totalClaims: number
 }
 
+// ============================================================================
+// Watchlist (coins followed without holding them)
+// ============================================================================
+
+export interface WatchedCoin {
+  symbol: string
+  addedAt: string | null
+  alertPct: number | null
+  referencePrice: number | null
+  name: string | null
+  icon: string | null
+  currentPrice: number | null
+  change24h: number | null
+  marketCap: number | null
+  volume24h: number | null
+  movePct: number | null
+  fetchedAt: string | null
+  error: string | null
+}
+
+export type AlertCondition =
+  | { kind: 'above'; price: number }
+  | { kind: 'below'; price: number }
+  | { kind: 'move'; pct: number; windowSecs: number }
+
+export interface PriceAlert {
+  id: number
+  symbol: string
+  condition: AlertCondition
+  armed: boolean
+  lastTriggeredAt: string | null
+  createdAt: string | null
+}
+
+export interface EquityMoveAlert {
+  pct: number
+  windowSecs: number
+}
+
+/** Alerts on total account value (cash plus coins) */
+export interface EquityAlertConfig {
+  enabled: boolean
+  milestones: number[]
+  moves: EquityMoveAlert[]
+}
+
+// ============================================================================
+// Budget Reset (daily buy/spend caps)
+// ============================================================================
+
+export type BudgetResetMode = { mode: 'rolling24h' } | { mode: 'dailyAt'; hour: number }
+
+// ============================================================================
+// Skip Reasons (why the dip buyer or sniper passed on a coin)
+// ============================================================================
+
+/** `actual` is the value that failed, `threshold` the configured limit */
+export type SkipReason =
+  | { kind: 'sellBelowTierMin'; actual: number; threshold: number }
+  | { kind: 'lowVolume'; actual: number; threshold: number }
+  | { kind: 'lowMarketCap'; actual: number; threshold: number }
+  | { kind: 'highMarketCap'; actual: number; threshold: number }
+  | { kind: 'marketCapUnknown' }
+  | { kind: 'droppedTooMuch'; actual: number; threshold: number }
+  | { kind: 'positionTooLarge'; actual: number; threshold: number }
+  | { kind: 'portfolioUnavailable'; error: string }
+  | { kind: 'signalReject'; detail: string }
+  | { kind: 'lowConfidence'; actual: number; threshold: number; signals: string }
+  | { kind: 'coinTooOld'; actual: number; threshold: number }
+  | { kind: 'creatorCooldown'; actual: number; threshold: number }
+  | { kind: 'blacklistedCreator'; creator: string }
+  | { kind: 'dailySpendLimit'; actual: number; threshold: number }
+  | { kind: 'highRisk'; actual: number; threshold: number }
+  | { kind: 'scriptReject'; detail: string }
+
 // ============================================================================
 // Sniper Types (auto-buy new coins)
 // ============================================================================
@@ -379,6 +747,9 @@ export interface SniperConfig {
   maxDailySpendUsd: number
   pollIntervalSecs: number
   minCoinAgeSecs: number
+  budgetResetMode: BudgetResetMode
+  /** Skip coins whose risk score (0-100) is above this, 0 = off */
+  maxRiskScore: number
 }
 
 export interface SniperStatusResponse {
@@ -397,10 +768,111 @@ export interface SniperTriggeredEvent {
   coinAgeSecs: number
 }
 
+export interface SniperSkippedCoin {
+  symbol: string
+  reason: string
+  skip: SkipReason
+}
+
+export interface SniperTickEvent {
+  enabled: boolean
+  totalSniped: number
+  lastSnipedAt: string | null
+  coinsChecked: number
+  skipped: SniperSkippedCoin[]
+}
+
+export interface FieldChange {
+  field: string
+  kind: 'added' | 'removed' | 'changed'
+  old: unknown
+  new: unknown
+}
+
+export interface ConfigHistoryEntry {
+  id: number
+  module: string
+  changes: FieldChange[]
+  changedAt: string | null
+}
+
+export interface FilterExclusion {
+  filter: SkipReason['kind']
+  excluded: number
+  example: string
+}
+
+export interface FilterCheck {
+  module: 'sniper' | 'dipbuyer'
+  sampled: number
+  qualifying: number
+  qualifyingSymbols: string[]
+  exclusions: FilterExclusion[]
+  mostRestrictive: SkipReason['kind'] | null
+}
+
 // ============================================================================
 // Risk Limits Types
 // ============================================================================
 
+export interface PendingTrade {
+  id: number
+  symbol: string
+  tradeType: 'BUY' | 'SELL'
+  amount: number
+  /** USD or coins; a sell in USD is converted at execution */
+  amountUnit: 'usd' | 'coins'
+  priority: 'Normal' | 'High' | 'Critical'
+  reason: string
+  reasonDetail: TradeReason
+  source: string
+  queuedAt: number
+}
+
+/** Semi-auto mode: automated trades wait for the user's approval */
+export interface SemiAutoConfig {
+  enabled: boolean
+  /** Unanswered proposals are rejected after this long */
+  expirySecs: number
+  /** An approved order is dropped if its price moved more than this since it was proposed (0 = no check) */
+  maxPriceMovePct: number
+}
+
+/** An automated trade held for approval in semi-auto mode */
+export interface PendingApproval {
+  id: number
+  symbol: string
+  tradeType: 'BUY' | 'SELL'
+  amount: number
+  amountUnit: 'usd' | 'coins'
+  reason: string
+  reasonDetail: TradeReason
+  source: string
+  proposedAt: number
+  expiresAt: number
+}
+
+/** Payload of the `trade-approval` event */
+export interface TradeApprovalEvent {
+  status: 'proposed' | 'approved' | 'rejected' | 'expired'
+  approval: PendingApproval
+  pendingCount: number
+}
+
+/** Trade API request counts and the executor's current delay between trades */
+export interface ExecutorStats {
+  requestsSent: number
+  successes: number
+  rateLimitHits: number
+  windowSecs: number
+  windowRequests: number
+  windowRateLimitHits: number
+  currentDelayMs: number
+  minDelayMs: number
+  maxDelayMs: number
+  adaptive: boolean
+}
+
 export interface RiskLimits {
   maxPositionUsd: number
   maxDailyTradesCount: number
@@ -409,12 +881,81 @@ export interface RiskLimits {
   retryCount: number
   retryDelayMs: number
   rateLimitMs: number
+  /** Widen the delay between trades after 429s, narrow it when calm */
+  adaptiveRateLimit: boolean
+  maxRateLimitMs: number
+  frozenReserveUsd: number
+  minAutomatedIntervalSecs: number
+  maxTradeUsd: number
+  moduleMaxTradeUsd: Record<string, number>
+  minCoinAgeSecs: number
+  minCandleCount: number
+  newCoinAllowedSources: string[]
+  minTradeRoundUpUsd: number
+  stopLossCooldownSecs: number
+  maxExitImpactPct: number
+  maxDrawdownPct: number
+}
+
+/** Portfolio value against today's peak, from `get_drawdown_status` */
+export interface DrawdownStatus {
+  /** Trading day (YYYY-MM-DD) the peak belongs to, empty before the first sample */
+  day: string
+  peakValue: number
+  currentValue: number
+  drawdownPct: number
+  /** Configured limit (0 = breaker off) */
+  thresholdPct: number
+  /** Buys are refused until the value recovers or a new day starts */
+  tripped: boolean
+}
+
+export interface SplitSellConfig {
+  sentinelEnabled: boolean
+  minValueUsd: number
+  tranches: number
+  spacingMs: number
+  maxTotalSecs: number
+}
+
+/** Maximum quote age per decision (0 = always fetch fresh) */
+export interface PriceFreshnessPolicy {
+  tradePreviewMaxAgeSecs: number
+  automatedBuyMaxAgeSecs: number
+}
+
+/** User-written Rhai entry gate for the sniper and dip buyer */
+export interface EntryScriptConfig {
+  enabled: boolean
+  script: string
+  timeoutMs: number
+}
+
+/** Decimals sell amounts are truncated to (server default 8) */
+export interface CoinPrecision {
+  defaultDecimals: number
+  coinDecimals: Record<string, number>
+}
+
+export interface AdaptivePollPolicy {
+  enabled: boolean
+  minSecs: number
+  maxSecs: number
+}
+
+export interface CoinQuote {
+  symbol: string
+  price: number
+  fetchedAt: string | null
+  ageMs: number
 }
 
 // ============================================================================
 // Notification Types
 // ============================================================================
 
+export type NotificationCategory = 'trade' | 'failure' | 'alert' | 'general'
+
 export interface NotificationConfig {
   enabled: boolean
   sentinelTriggers: boolean
@@ -423,6 +964,10 @@ export interface NotificationConfig {
   riskAlerts: boolean
   sessionAlerts: boolean
   tradeConfirmations: boolean
+  /** Repeats of the same notification within this many seconds are dropped; 0 shows all */
+  dedupWindowSecs: number
+  /** Per-category windows used instead of dedupWindowSecs */
+  dedupOverrides: Partial<Record<NotificationCategory, number>>
 }
 
 // ============================================================================
@@ -448,6 +993,27 @@ export interface SignalWeights {
   volumeQuality: number
 }
 
+/** Sizing where bigger buys need stronger signals (used with scaleByConfidence) */
+export interface ConfidenceCurve {
+  enabled: boolean
+  /** Fraction of the tier amount bought at the minimum confidence */
+  minSizeScale: number
+  /** Confidence needed for the full tier amount */
+  fullSizeConfidence: number
+  /** Above 1 keeps sizes small until confidence is well above the minimum */
+  exponent: number
+}
+
+/** off = no filtering; higher levels treat wider buy/sell gaps as self-trades */
+export type WashSensitivity = 'off' | 'low' | 'medium' | 'high'
+
+/** Volatility position sizing: buy scale = targetAtrPct / coin ATR %, clamped */
+export interface AtrSizing {
+  targetAtrPct: number
+  minScale: number
+  maxScale: number
+}
+
 export interface DipBuyerConfig {
   preset: Aggressiveness
   buyAmountUsd: number
@@ -460,6 +1026,11 @@ export interface DipBuyerConfig {
   skipTopNHolders: number
   maxPriceDropPct: number
   pollIntervalSecs: number
+  recentTradesDepth: number
+  /** How eagerly sells that are half of a self-trade round trip are ignored */
+  washTradeSensitivity: WashSensitivity
+  /** Candidates fully evaluated per tick, biggest sells first (0 = no limit) */
+  maxEvaluationsPerTick: number
   cooldownPerCoinSecs: number
   maxDailyBuys: number
   maxDailySpendUsd: number
@@ -468,13 +1039,31 @@ export interface DipBuyerConfig {
   takeProfitPct: number
   trailingStopPct: number | null
   blacklistedCoins: string[]
+  cooldownExemptCoins: string[]
   minConfidenceScore: number
   maxBuySlippagePct: number
+  /** Per-coin max slippage %, checked before the tier and global values */
+  coinSlippageOverrides: Record<string, number>
   useMomentumAnalysis: boolean
+  /** Fraction of confidence taken off coins with too few candles for momentum (0 = off) */
+  thinHistoryConfidencePenalty: number
+  /** Scale buys by each coin's volatility (needs momentum analysis for candles) */
+  atrPositionSizing: boolean
+  atrSizing: AtrSizing
   signalWeights: SignalWeights
   scaleByConfidence: boolean
+  confidenceCurve: ConfidenceCurve
   maxPositionPct: number
   portfolioAware: boolean
+  /** Buy without the position check when holdings can't be fetched (default: skip) */
+  proceedWithoutPortfolio: boolean
+  confidenceDecayPerDump: number
+  dumpDecayWindowSecs: number
+  proceedWithoutHolders: boolean
+  withoutHoldersBuyScale: number
+  budgetResetMode: BudgetResetMode
+  /** Skip dips on coins whose risk score (0-100) is above this, 0 = off */
+  maxRiskScore: number
 }
 
 export interface DipBuyerStatusResponse {
@@ -507,6 +1096,14 @@ export interface DipBuyerTickEvent {
   dipsDetected: number
 }
 
+export interface DipBuyerSkippedEvent {
+  symbol: string
+  sellerUsername: string
+  sellValueUsd: number
+  reason: string
+  skip: SkipReason
+}
+
 export interface DipBuyerLogEntry {
   id: number
   symbol: string
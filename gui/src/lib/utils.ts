@@ -23,6 +23,11 @@ export function formatNumber(value: number, decimals: number = 8): string {
   return value.toFixed(decimals)
 }
 
+/** A fill price, or a dash when the API didn't report one */
+export function formatFillPrice(price: number | null, decimals: number = 8): string {
+  return price === null ? '—' : `$${price.toFixed(decimals)}`
+}
+
 export function truncateTo8Decimals(value: number): number {
   return Math.floor(value * 1e8) / 1e8
 }